@@ -0,0 +1,38 @@
+//! Embeds the git commit hash so the agent can detect version changes
+//! between restarts (see `agent::canary`), and rebuilds when a migration is
+//! added (they are embedded by `sqlx::migrate!`).
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=GIT_HASH");
+    println!("cargo:rerun-if-changed=migrations");
+
+    // Deploy pipelines without a .git directory can pass the hash in directly.
+    let hash = std::env::var("GIT_HASH").ok().or_else(|| {
+        let output = Command::new("git")
+            .args(["rev-parse", "--short=12", "HEAD"])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    });
+
+    if let Ok(output) = Command::new("git")
+        .args(["rev-parse", "--git-dir"])
+        .output()
+    {
+        if output.status.success() {
+            let git_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            println!("cargo:rerun-if-changed={git_dir}/HEAD");
+            println!("cargo:rerun-if-changed={git_dir}/refs/heads");
+        }
+    }
+
+    println!(
+        "cargo:rustc-env=GIT_HASH={}",
+        hash.unwrap_or_else(|| "unknown".to_string())
+    );
+}
@@ -0,0 +1,2 @@
+ALTER TABLE trades ADD COLUMN bucket TEXT NOT NULL DEFAULT 'directional';
+CREATE INDEX IF NOT EXISTS idx_trades_bucket ON trades(bucket);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS agent_meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TEXT DEFAULT (datetime('now'))
+);
@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS pending_approvals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    cycle INTEGER NOT NULL,
+    market_id TEXT NOT NULL,
+    market_question TEXT NOT NULL,
+    category TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    direction TEXT NOT NULL CHECK (direction IN ('YES', 'NO')),
+    price TEXT NOT NULL,
+    size TEXT NOT NULL,
+    size_usd TEXT NOT NULL,
+    edge TEXT NOT NULL,
+    fair_value TEXT NOT NULL,
+    confidence TEXT NOT NULL,
+    kelly_raw TEXT NOT NULL,
+    kelly_adjusted TEXT NOT NULL,
+    bucket TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'PENDING_APPROVAL' CHECK (status IN ('PENDING_APPROVAL', 'APPROVED', 'REJECTED', 'EXPIRED', 'EXECUTED', 'FAILED')),
+    created_at TEXT DEFAULT (datetime('now')),
+    expires_at TEXT NOT NULL,
+    decided_at TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_pending_approvals_status ON pending_approvals(status);
+CREATE INDEX IF NOT EXISTS idx_pending_approvals_market ON pending_approvals(market_id);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS market_blacklist (
+    market_id TEXT PRIMARY KEY,
+    market_question TEXT,
+    realized_loss TEXT NOT NULL,
+    blacklisted_at TEXT DEFAULT (datetime('now'))
+);
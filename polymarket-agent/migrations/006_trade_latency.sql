@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS trade_latency (
+    trade_id INTEGER PRIMARY KEY,
+    scanned_at TEXT NOT NULL,
+    data_fetched_at TEXT NOT NULL,
+    valued_at TEXT NOT NULL,
+    submitted_at TEXT NOT NULL,
+    filled_at TEXT NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE trades ADD COLUMN fee TEXT NOT NULL DEFAULT '0';
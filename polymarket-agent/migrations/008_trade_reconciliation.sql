@@ -0,0 +1,2 @@
+ALTER TABLE trades ADD COLUMN order_id TEXT;
+ALTER TABLE trades ADD COLUMN reconciled_at TEXT;
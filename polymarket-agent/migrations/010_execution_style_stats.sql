@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS execution_style_stats (
+    style TEXT PRIMARY KEY,
+    orders INTEGER NOT NULL DEFAULT 0,
+    filled_orders INTEGER NOT NULL DEFAULT 0,
+    requested_shares TEXT NOT NULL DEFAULT '0',
+    filled_shares TEXT NOT NULL DEFAULT '0',
+    updated_at TEXT
+);
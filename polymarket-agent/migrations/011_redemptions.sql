@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS redemptions (
+    market_id TEXT PRIMARY KEY,
+    tx_hash TEXT NOT NULL,
+    gas_used INTEGER NOT NULL,
+    gas_cost TEXT NOT NULL,
+    redeemed_at TEXT NOT NULL
+);
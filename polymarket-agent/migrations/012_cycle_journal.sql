@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS cycle_journal (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    cycle INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('VALUATION', 'ORDER')),
+    market_id TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('PENDING', 'SUBMITTED', 'DONE', 'RECOVERED', 'ABANDONED')),
+    order_id TEXT,
+    created_at TEXT DEFAULT (datetime('now')),
+    completed_at TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_cycle_journal_status ON cycle_journal(status);
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS order_book_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    best_bid TEXT NOT NULL,
+    best_ask TEXT NOT NULL,
+    midpoint TEXT NOT NULL,
+    spread TEXT NOT NULL,
+    bids TEXT NOT NULL,
+    asks TEXT NOT NULL,
+    captured_at TEXT DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_order_book_snapshots_token ON order_book_snapshots(token_id, captured_at);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS market_evaluations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    condition_id TEXT NOT NULL,
+    cycle INTEGER NOT NULL,
+    fair_value TEXT NOT NULL,
+    market_price TEXT NOT NULL,
+    net_edge TEXT,
+    edge_found INTEGER NOT NULL,
+    evaluated_at TEXT DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_market_evaluations_condition ON market_evaluations(condition_id, evaluated_at);
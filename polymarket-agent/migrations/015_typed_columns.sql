@@ -0,0 +1,93 @@
+-- Enforce typed values in trades, cycles and api_costs.
+--
+-- Decimals stay TEXT so they round-trip exactly (SQLite's NUMERIC affinity
+-- would turn them into lossy floats), but must now be plain decimal
+-- literals. Timestamps must be canonical UTC 'YYYY-MM-DD HH:MM:SS', the
+-- format datetime('now') produces. Existing rows are normalized on copy:
+-- whitespace is trimmed and RFC 3339 timestamps are converted to UTC. A row
+-- that still fails a check aborts the migration instead of being carried
+-- over corrupt.
+
+CREATE TABLE trades_typed (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    cycle INTEGER NOT NULL,
+    market_id TEXT NOT NULL,
+    market_question TEXT,
+    direction TEXT NOT NULL CHECK (direction IN ('YES', 'NO')),
+    entry_price TEXT NOT NULL CHECK (entry_price NOT GLOB '*[^0-9.-]*' AND entry_price GLOB '*[0-9]*'),
+    size TEXT NOT NULL CHECK (size NOT GLOB '*[^0-9.-]*' AND size GLOB '*[0-9]*'),
+    edge_at_entry TEXT NOT NULL CHECK (edge_at_entry NOT GLOB '*[^0-9.-]*' AND edge_at_entry GLOB '*[0-9]*'),
+    claude_fair_value TEXT NOT NULL CHECK (claude_fair_value NOT GLOB '*[^0-9.-]*' AND claude_fair_value GLOB '*[0-9]*'),
+    confidence TEXT NOT NULL CHECK (confidence NOT GLOB '*[^0-9.-]*' AND confidence GLOB '*[0-9]*'),
+    kelly_raw TEXT NOT NULL CHECK (kelly_raw NOT GLOB '*[^0-9.-]*' AND kelly_raw GLOB '*[0-9]*'),
+    kelly_adjusted TEXT NOT NULL CHECK (kelly_adjusted NOT GLOB '*[^0-9.-]*' AND kelly_adjusted GLOB '*[0-9]*'),
+    status TEXT DEFAULT 'OPEN' CHECK (status IN ('OPEN', 'FILLED', 'RESOLVED_WIN', 'RESOLVED_LOSS', 'CANCELLED')),
+    pnl TEXT CHECK (pnl IS NULL OR (pnl NOT GLOB '*[^0-9.-]*' AND pnl GLOB '*[0-9]*')),
+    created_at TEXT DEFAULT (datetime('now')) CHECK (created_at IS NULL OR created_at = datetime(created_at)),
+    resolved_at TEXT CHECK (resolved_at IS NULL OR resolved_at = datetime(resolved_at)),
+    bucket TEXT NOT NULL DEFAULT 'directional',
+    fee TEXT NOT NULL DEFAULT '0' CHECK (fee NOT GLOB '*[^0-9.-]*' AND fee GLOB '*[0-9]*'),
+    order_id TEXT,
+    reconciled_at TEXT CHECK (reconciled_at IS NULL OR reconciled_at = datetime(reconciled_at))
+);
+
+INSERT INTO trades_typed
+SELECT id, cycle, market_id, market_question, direction,
+       TRIM(entry_price), TRIM(size), TRIM(edge_at_entry), TRIM(claude_fair_value),
+       TRIM(confidence), TRIM(kelly_raw), TRIM(kelly_adjusted), status, TRIM(pnl),
+       COALESCE(datetime(created_at), created_at),
+       COALESCE(datetime(resolved_at), resolved_at),
+       bucket, TRIM(fee), order_id,
+       COALESCE(datetime(reconciled_at), reconciled_at)
+FROM trades;
+
+DROP TABLE trades;
+ALTER TABLE trades_typed RENAME TO trades;
+CREATE INDEX idx_trades_status ON trades(status);
+CREATE INDEX idx_trades_market_id ON trades(market_id);
+CREATE INDEX idx_trades_bucket ON trades(bucket);
+
+CREATE TABLE cycles_typed (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    cycle_number INTEGER NOT NULL UNIQUE,
+    markets_scanned INTEGER,
+    opportunities_found INTEGER,
+    trades_placed INTEGER,
+    api_cost TEXT CHECK (api_cost IS NULL OR (api_cost NOT GLOB '*[^0-9.-]*' AND api_cost GLOB '*[0-9]*')),
+    bankroll TEXT CHECK (bankroll IS NULL OR (bankroll NOT GLOB '*[^0-9.-]*' AND bankroll GLOB '*[0-9]*')),
+    unrealized_pnl TEXT CHECK (unrealized_pnl IS NULL OR (unrealized_pnl NOT GLOB '*[^0-9.-]*' AND unrealized_pnl GLOB '*[0-9]*')),
+    agent_state TEXT NOT NULL,
+    duration_ms INTEGER,
+    created_at TEXT DEFAULT (datetime('now')) CHECK (created_at IS NULL OR created_at = datetime(created_at))
+);
+
+INSERT INTO cycles_typed
+SELECT id, cycle_number, markets_scanned, opportunities_found, trades_placed,
+       TRIM(api_cost), TRIM(bankroll), TRIM(unrealized_pnl), agent_state, duration_ms,
+       COALESCE(datetime(created_at), created_at)
+FROM cycles;
+
+DROP TABLE cycles;
+ALTER TABLE cycles_typed RENAME TO cycles;
+CREATE INDEX idx_cycles_cycle_number ON cycles(cycle_number);
+
+CREATE TABLE api_costs_typed (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    provider TEXT NOT NULL,
+    endpoint TEXT,
+    input_tokens INTEGER,
+    output_tokens INTEGER,
+    cost TEXT NOT NULL CHECK (cost NOT GLOB '*[^0-9.-]*' AND cost GLOB '*[0-9]*'),
+    cycle INTEGER,
+    created_at TEXT DEFAULT (datetime('now')) CHECK (created_at IS NULL OR created_at = datetime(created_at))
+);
+
+INSERT INTO api_costs_typed
+SELECT id, provider, endpoint, input_tokens, output_tokens, TRIM(cost), cycle,
+       COALESCE(datetime(created_at), created_at)
+FROM api_costs;
+
+DROP TABLE api_costs;
+ALTER TABLE api_costs_typed RENAME TO api_costs;
+CREATE INDEX idx_api_costs_provider ON api_costs(provider);
+CREATE INDEX idx_api_costs_cycle ON api_costs(cycle);
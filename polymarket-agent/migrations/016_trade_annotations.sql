@@ -0,0 +1,5 @@
+-- Operator annotations on trades: a free-form note, and tags (a JSON array
+-- of strings such as "manual override") that metrics can be filtered by.
+ALTER TABLE trades ADD COLUMN notes TEXT;
+ALTER TABLE trades ADD COLUMN tags TEXT NOT NULL DEFAULT '[]'
+    CHECK (json_valid(tags) AND json_type(tags) = 'array');
@@ -0,0 +1,3 @@
+-- Cycle phases cut short by the cycle time budget, comma-separated
+-- (e.g. "valuation,execution"); NULL when every phase finished.
+ALTER TABLE cycles ADD COLUMN truncated_phases TEXT;
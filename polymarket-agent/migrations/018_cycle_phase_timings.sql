@@ -0,0 +1,7 @@
+-- Time spent in each cycle phase, in milliseconds; NULL for phases a cycle
+-- didn't run.
+ALTER TABLE cycles ADD COLUMN scan_ms INTEGER;
+ALTER TABLE cycles ADD COLUMN data_ms INTEGER;
+ALTER TABLE cycles ADD COLUMN valuation_ms INTEGER;
+ALTER TABLE cycles ADD COLUMN execution_ms INTEGER;
+ALTER TABLE cycles ADD COLUMN settlement_ms INTEGER;
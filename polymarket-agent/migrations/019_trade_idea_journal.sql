@@ -0,0 +1,29 @@
+-- Every valued opportunity, the decision taken on it and, once its market
+-- resolves, whether that decision was right.
+CREATE TABLE IF NOT EXISTS journal (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    cycle INTEGER NOT NULL,
+    market_id TEXT NOT NULL,
+    market_question TEXT NOT NULL,
+    category TEXT NOT NULL,
+    -- Side the valuation favored, traded or not
+    side TEXT NOT NULL CHECK (side IN ('YES', 'NO')),
+    decision TEXT NOT NULL CHECK (decision IN ('TRADED', 'SKIPPED')),
+    -- Why a skipped idea was skipped
+    reason TEXT,
+    fair_value TEXT NOT NULL,
+    market_price TEXT NOT NULL,
+    net_edge TEXT,
+    confidence TEXT NOT NULL,
+    -- JSON array of the valuation's key factors
+    key_factors TEXT NOT NULL DEFAULT '[]',
+    size_usd TEXT,
+    end_date TEXT NOT NULL,
+    created_at TEXT DEFAULT (datetime('now')),
+    resolved_at TEXT,
+    side_won INTEGER,
+    -- Traded and won, or skipped and lost
+    correct INTEGER
+);
+CREATE INDEX IF NOT EXISTS idx_journal_unresolved ON journal(resolved_at, end_date);
+CREATE INDEX IF NOT EXISTS idx_journal_market ON journal(market_id);
@@ -0,0 +1,9 @@
+-- When each market was first seen by a scan. Markets present in the very
+-- first scan were listed before the agent started watching, so they are
+-- marked `initial_scan` and never count as new listings.
+CREATE TABLE IF NOT EXISTS market_listings (
+    market_id TEXT PRIMARY KEY,
+    first_seen_at TEXT NOT NULL,
+    initial_scan INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_market_listings_first_seen ON market_listings(first_seen_at);
@@ -0,0 +1,3 @@
+-- When a trade was placed on a cached valuation. A consumed valuation still
+-- serves position re-valuation and hedging, but never another entry.
+ALTER TABLE valuation_cache ADD COLUMN consumed_at TEXT;
@@ -0,0 +1,159 @@
+//! Order book history for held markets.
+//!
+//! A background task snapshots the book of every token the agent holds into
+//! `order_book_snapshots`, so slippage, exit timing and backtests can be
+//! studied against the liquidity that was actually there.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::db::batch::BatchWriter;
+use crate::db::store::Store;
+use crate::market::models::TokenInfo;
+use crate::market::polymarket::PolymarketClient;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BookCaptureConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u64,
+    /// Price levels kept per side of each snapshot.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+}
+
+impl Default for BookCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            interval_seconds: default_interval_seconds(),
+            depth: default_depth(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_interval_seconds() -> u64 {
+    300
+}
+
+fn default_depth() -> usize {
+    10
+}
+
+/// The token of `tokens` for the held `direction` ("YES"/"NO"), by outcome
+/// name or, failing that, position (YES first).
+pub fn held_token<'a>(tokens: &'a [TokenInfo], direction: &str) -> Option<&'a TokenInfo> {
+    tokens
+        .iter()
+        .find(|t| t.outcome.eq_ignore_ascii_case(direction))
+        .or_else(|| match direction {
+            "YES" => tokens.first(),
+            "NO" => tokens.get(1),
+            _ => None,
+        })
+}
+
+/// Spawn the task snapshotting held markets' order books.
+pub fn spawn_capture(
+    config: BookCaptureConfig,
+    polymarket: Arc<PolymarketClient>,
+    store: Store,
+    writer: BatchWriter,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        // Token ids never change, so each market is looked up once
+        let mut tokens: HashMap<String, Vec<TokenInfo>> = HashMap::new();
+
+        info!(
+            interval_s = config.interval_seconds,
+            depth = config.depth,
+            "Order book capture started"
+        );
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(config.interval_seconds)).await;
+
+            let held = match store.get_open_trades().await {
+                Ok(trades) => trades,
+                Err(e) => {
+                    warn!(error = %e, "Book capture failed to load open trades");
+                    continue;
+                }
+            };
+            let held: HashSet<(String, String)> = held
+                .into_iter()
+                .map(|t| (t.market_id, t.direction))
+                .collect();
+
+            let mut captured = 0usize;
+            for (market_id, direction) in &held {
+                if !tokens.contains_key(market_id) {
+                    match polymarket.get_market(market_id).await {
+                        Ok(market) => {
+                            tokens.insert(market_id.clone(), market.tokens);
+                        }
+                        Err(e) => {
+                            debug!(market = %market_id, error = %e, "Book capture market lookup failed");
+                            continue;
+                        }
+                    }
+                }
+                let Some(token) = held_token(&tokens[market_id], direction) else {
+                    continue;
+                };
+                // Fresh fetches also refresh the shared book cache
+                let book = match polymarket.fetch_order_book(&token.token_id).await {
+                    Ok(book) => book,
+                    Err(e) => {
+                        debug!(token_id = %token.token_id, error = %e, "Book capture fetch failed");
+                        continue;
+                    }
+                };
+                match writer
+                    .insert_order_book_snapshot(market_id, &book, config.depth)
+                    .await
+                {
+                    Ok(()) => captured += 1,
+                    Err(e) => warn!(error = %e, "Failed to store order book snapshot"),
+                }
+            }
+            if captured > 0 {
+                debug!(books = captured, "Order books captured");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn token(token_id: &str, outcome: &str) -> TokenInfo {
+        TokenInfo {
+            token_id: token_id.to_string(),
+            outcome: outcome.to_string(),
+            price: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_held_token_by_outcome_then_position() {
+        let yes_no = [token("1", "Yes"), token("2", "No")];
+        assert_eq!(held_token(&yes_no, "NO").unwrap().token_id, "2");
+
+        let named = [token("3", "Lakers"), token("4", "Celtics")];
+        assert_eq!(held_token(&named, "YES").unwrap().token_id, "3");
+        assert_eq!(held_token(&named, "NO").unwrap().token_id, "4");
+    }
+}
@@ -0,0 +1,261 @@
+//! Cycle time budget.
+//!
+//! A cycle that runs past `cycle_interval_seconds` delays the next one and
+//! trades on stale books. The budget gives each cycle `budget_pct` of the
+//! interval and splits it into cumulative deadlines for the scan, data
+//! fetch, valuation and execution phases. A phase that reaches its deadline
+//! is cut short — a slow scan or data fetch skips valuation, late
+//! valuations are abandoned, execution stops taking new orders and
+//! market making is skipped — and the cycle record lists the truncated
+//! phases.
+
+use std::fmt;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use tracing::warn;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CycleBudgetConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Share of `cycle_interval_seconds` a cycle may take.
+    #[serde(default = "default_budget_pct")]
+    pub budget_pct: Decimal,
+    /// Shares of the budget for each phase, in order. Each phase's deadline
+    /// is the sum of its share and the earlier ones.
+    #[serde(default = "default_scan_pct")]
+    pub scan_pct: Decimal,
+    #[serde(default = "default_data_pct")]
+    pub data_pct: Decimal,
+    #[serde(default = "default_valuation_pct")]
+    pub valuation_pct: Decimal,
+    #[serde(default = "default_execution_pct")]
+    pub execution_pct: Decimal,
+}
+
+impl Default for CycleBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            budget_pct: default_budget_pct(),
+            scan_pct: default_scan_pct(),
+            data_pct: default_data_pct(),
+            valuation_pct: default_valuation_pct(),
+            execution_pct: default_execution_pct(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_budget_pct() -> Decimal {
+    dec!(0.8)
+}
+
+fn default_scan_pct() -> Decimal {
+    dec!(0.25)
+}
+
+fn default_data_pct() -> Decimal {
+    dec!(0.15)
+}
+
+fn default_valuation_pct() -> Decimal {
+    dec!(0.45)
+}
+
+fn default_execution_pct() -> Decimal {
+    dec!(0.15)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CyclePhase {
+    Scan,
+    DataFetch,
+    Valuation,
+    Execution,
+}
+
+impl CyclePhase {
+    pub const ALL: [CyclePhase; 4] = [
+        CyclePhase::Scan,
+        CyclePhase::DataFetch,
+        CyclePhase::Valuation,
+        CyclePhase::Execution,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CyclePhase::Scan => "scan",
+            CyclePhase::DataFetch => "data_fetch",
+            CyclePhase::Valuation => "valuation",
+            CyclePhase::Execution => "execution",
+        }
+    }
+
+    fn share(&self, config: &CycleBudgetConfig) -> Decimal {
+        match self {
+            CyclePhase::Scan => config.scan_pct,
+            CyclePhase::DataFetch => config.data_pct,
+            CyclePhase::Valuation => config.valuation_pct,
+            CyclePhase::Execution => config.execution_pct,
+        }
+    }
+}
+
+impl fmt::Display for CyclePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One cycle's phase deadlines and the phases cut short so far.
+#[derive(Debug, Clone)]
+pub struct CycleBudget {
+    /// Per phase, in [`CyclePhase::ALL`] order; `None` when unlimited.
+    deadlines: Option<[Instant; 4]>,
+    truncated: Vec<CyclePhase>,
+}
+
+impl CycleBudget {
+    /// Deadlines for a cycle started at `start`. Disabled, or with no
+    /// interval, every phase is unlimited.
+    pub fn new(config: &CycleBudgetConfig, cycle_interval_seconds: u64, start: Instant) -> Self {
+        if !config.enabled || cycle_interval_seconds == 0 {
+            return Self::unlimited();
+        }
+        let budget_ms = Decimal::from(cycle_interval_seconds) * dec!(1000) * config.budget_pct;
+        let mut elapsed = Decimal::ZERO;
+        let deadlines = CyclePhase::ALL.map(|phase| {
+            elapsed += phase.share(config);
+            let ms = (budget_ms * elapsed)
+                .max(Decimal::ZERO)
+                .to_u64()
+                .unwrap_or(0);
+            start + Duration::from_millis(ms)
+        });
+        Self {
+            deadlines: Some(deadlines),
+            truncated: Vec::new(),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self {
+            deadlines: None,
+            truncated: Vec::new(),
+        }
+    }
+
+    pub fn deadline(&self, phase: CyclePhase) -> Option<Instant> {
+        let index = CyclePhase::ALL.iter().position(|p| *p == phase)?;
+        self.deadlines.map(|d| d[index])
+    }
+
+    /// Whether `phase`'s deadline has passed.
+    pub fn is_over(&self, phase: CyclePhase) -> bool {
+        self.deadline(phase)
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Record that `phase` was cut short.
+    pub fn truncate(&mut self, phase: CyclePhase) {
+        if !self.truncated.contains(&phase) {
+            warn!(phase = %phase, "Cycle phase deadline reached — skipping remaining work");
+            self.truncated.push(phase);
+        }
+    }
+
+    /// Run `work` until `phase`'s deadline. `None`, with the phase recorded
+    /// as truncated, if the deadline came first.
+    pub async fn within<F: Future>(&mut self, phase: CyclePhase, work: F) -> Option<F::Output> {
+        let Some(deadline) = self.deadline(phase) else {
+            return Some(work.await);
+        };
+        match tokio::time::timeout_at(deadline.into(), work).await {
+            Ok(output) => Some(output),
+            Err(_) => {
+                self.truncate(phase);
+                None
+            }
+        }
+    }
+
+    pub fn truncated(&self) -> &[CyclePhase] {
+        &self.truncated
+    }
+
+    /// Truncated phases as stored on the cycle record, e.g. `"valuation,execution"`.
+    pub fn truncated_label(&self) -> Option<String> {
+        if self.truncated.is_empty() {
+            return None;
+        }
+        let names: Vec<&str> = self.truncated.iter().map(CyclePhase::as_str).collect();
+        Some(names.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadlines_are_cumulative_shares_of_budget() {
+        let start = Instant::now();
+        // 80% of 100s = 80s: scan 20s, data 32s, valuation 68s, execution 80s
+        let budget = CycleBudget::new(&CycleBudgetConfig::default(), 100, start);
+        let offset = |phase| budget.deadline(phase).unwrap() - start;
+        assert_eq!(offset(CyclePhase::Scan), Duration::from_secs(20));
+        assert_eq!(offset(CyclePhase::DataFetch), Duration::from_secs(32));
+        assert_eq!(offset(CyclePhase::Valuation), Duration::from_secs(68));
+        assert_eq!(offset(CyclePhase::Execution), Duration::from_secs(80));
+    }
+
+    #[test]
+    fn test_disabled_or_no_interval_is_unlimited() {
+        let disabled = CycleBudgetConfig {
+            enabled: false,
+            ..CycleBudgetConfig::default()
+        };
+        let budget = CycleBudget::new(&disabled, 600, Instant::now());
+        assert!(budget.deadline(CyclePhase::Scan).is_none());
+        let budget = CycleBudget::new(&CycleBudgetConfig::default(), 0, Instant::now());
+        assert!(!budget.is_over(CyclePhase::Execution));
+    }
+
+    #[tokio::test]
+    async fn test_overrun_records_truncated_phases() {
+        // A zero budget is past every deadline from the start
+        let config = CycleBudgetConfig {
+            budget_pct: Decimal::ZERO,
+            ..CycleBudgetConfig::default()
+        };
+        let mut budget = CycleBudget::new(&config, 100, Instant::now());
+        assert!(budget.is_over(CyclePhase::Scan));
+
+        let out = budget
+            .within(CyclePhase::Valuation, std::future::pending::<()>())
+            .await;
+        assert!(out.is_none());
+        budget.truncate(CyclePhase::Execution);
+        budget.truncate(CyclePhase::Execution);
+        assert_eq!(
+            budget.truncated_label().as_deref(),
+            Some("valuation,execution")
+        );
+
+        let mut unlimited = CycleBudget::unlimited();
+        assert_eq!(
+            unlimited.within(CyclePhase::Scan, async { 7 }).await,
+            Some(7)
+        );
+        assert!(unlimited.truncated_label().is_none());
+    }
+}
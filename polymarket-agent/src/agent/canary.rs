@@ -0,0 +1,173 @@
+//! Canary cycle after deploys.
+//!
+//! When the running build differs from the one that last ran against this
+//! database, the agent runs a dry-run canary (schema checks, connectivity,
+//! one test valuation) before real trading is enabled.
+
+use anyhow::Result;
+
+use crate::db::store::Store;
+
+/// Version string of this build: crate version plus embedded git hash.
+pub const BUILD_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("GIT_HASH"));
+
+/// `agent_meta` key holding the last version that passed a canary.
+const VERSION_KEY: &str = "last_canary_version";
+
+/// Tables the agent expects after migrations.
+const REQUIRED_TABLES: &[&str] = &[
+    "trades",
+    "cycles",
+    "api_costs",
+    "confidence_calibration",
+    "valuation_cache",
+    "agent_meta",
+    "pending_approvals",
+    "market_blacklist",
+    "trade_latency",
+    "orders",
+    "execution_style_stats",
+    "redemptions",
+    "cycle_journal",
+    "order_book_snapshots",
+];
+
+/// Outcome of one canary check.
+#[derive(Debug, Clone)]
+pub struct CanaryCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Full canary report for a version change.
+#[derive(Debug, Clone)]
+pub struct CanaryReport {
+    pub version: String,
+    pub previous_version: Option<String>,
+    pub checks: Vec<CanaryCheck>,
+}
+
+impl CanaryReport {
+    pub fn new(previous_version: Option<String>) -> Self {
+        Self {
+            version: BUILD_VERSION.to_string(),
+            previous_version,
+            checks: Vec::new(),
+        }
+    }
+
+    pub fn pass(&mut self, name: &str, detail: impl Into<String>) {
+        self.checks.push(CanaryCheck {
+            name: name.to_string(),
+            passed: true,
+            detail: detail.into(),
+        });
+    }
+
+    pub fn fail(&mut self, name: &str, detail: impl Into<String>) {
+        self.checks.push(CanaryCheck {
+            name: name.to_string(),
+            passed: false,
+            detail: detail.into(),
+        });
+    }
+
+    /// Whether every check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+
+    /// One line per check, for logs and alerts.
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "Version {} (previous: {})",
+            self.version,
+            self.previous_version.as_deref().unwrap_or("none")
+        );
+        for check in &self.checks {
+            let mark = if check.passed { "PASS" } else { "FAIL" };
+            out.push_str(&format!("\n[{mark}] {}: {}", check.name, check.detail));
+        }
+        out
+    }
+}
+
+/// Returns the previously recorded version if it differs from this build,
+/// `Ok(None)` if no canary is needed. A fresh database always needs one.
+pub async fn version_change(store: &Store) -> Result<Option<Option<String>>> {
+    let previous = store.get_meta(VERSION_KEY).await?;
+    if previous.as_deref() == Some(BUILD_VERSION) {
+        Ok(None)
+    } else {
+        Ok(Some(previous))
+    }
+}
+
+/// Record this build as having passed its canary.
+pub async fn record_version(store: &Store) -> Result<()> {
+    store.set_meta(VERSION_KEY, BUILD_VERSION).await
+}
+
+/// Verify that all required tables exist.
+pub async fn check_schema(store: &Store, report: &mut CanaryReport) {
+    match store.table_names().await {
+        Ok(tables) => {
+            let missing: Vec<&str> = REQUIRED_TABLES
+                .iter()
+                .filter(|t| !tables.iter().any(|name| name == *t))
+                .copied()
+                .collect();
+            if missing.is_empty() {
+                report.pass(
+                    "schema",
+                    format!("{} tables present", REQUIRED_TABLES.len()),
+                );
+            } else {
+                report.fail("schema", format!("missing tables: {}", missing.join(", ")));
+            }
+        }
+        Err(e) => report.fail("schema", format!("table listing failed: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_version_change_detection() {
+        let store = Store::new(":memory:").await.unwrap();
+
+        // Fresh database: canary required, no previous version
+        assert_eq!(version_change(&store).await.unwrap(), Some(None));
+
+        record_version(&store).await.unwrap();
+        assert_eq!(version_change(&store).await.unwrap(), None);
+
+        store.set_meta(VERSION_KEY, "0.0.1+old").await.unwrap();
+        assert_eq!(
+            version_change(&store).await.unwrap(),
+            Some(Some("0.0.1+old".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schema_check_passes_after_migration() {
+        let store = Store::new(":memory:").await.unwrap();
+        let mut report = CanaryReport::new(None);
+        check_schema(&store, &mut report).await;
+        assert!(report.passed(), "{}", report.summary());
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let mut report = CanaryReport::new(Some("0.1.0+abc".to_string()));
+        report.pass("schema", "ok");
+        report.fail("valuation", "timeout");
+        assert!(!report.passed());
+        let summary = report.summary();
+        assert!(summary.contains("previous: 0.1.0+abc"));
+        assert!(summary.contains("[FAIL] valuation: timeout"));
+    }
+}
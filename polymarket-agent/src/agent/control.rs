@@ -0,0 +1,196 @@
+//! Operator control of a running agent.
+//!
+//! The dashboard flips these flags and the agent checks them at the top of
+//! every cycle. Pausing skips cycles until resumed. The kill switch cancels
+//! every open order and leaves the agent monitoring only: it still settles
+//! and marks positions but places no orders. Flattening also sells every
+//! open position at the best bid.
+//!
+//! The kill switch can also be tripped by creating `agent.kill_switch.file`,
+//! for operators who can reach the host but not the dashboard.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::Deserialize;
+use tokio::sync::Notify;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct KillSwitchConfig {
+    /// Engage the kill switch while this file exists. Checked every cycle.
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Also flatten all positions when the file trips the switch.
+    #[serde(default)]
+    pub flatten: bool,
+}
+
+/// Shared run/pause/kill state, cheap to clone.
+#[derive(Clone, Default)]
+pub struct ControlState {
+    inner: Arc<ControlInner>,
+}
+
+#[derive(Default)]
+struct ControlInner {
+    paused: AtomicBool,
+    killed: AtomicBool,
+    /// Set by the kill switch, cleared once the agent has cancelled orders.
+    cancel_requested: AtomicBool,
+    /// Set by flatten, cleared once the agent has sold its positions.
+    flatten_requested: AtomicBool,
+    changed: Notify,
+}
+
+impl ControlState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pause(&self) {
+        self.inner.paused.store(true, Ordering::SeqCst);
+        self.inner.changed.notify_waiters();
+    }
+
+    /// Clear both pause and kill switch.
+    pub fn resume(&self) {
+        self.inner.paused.store(false, Ordering::SeqCst);
+        self.inner.killed.store(false, Ordering::SeqCst);
+        self.inner.cancel_requested.store(false, Ordering::SeqCst);
+        self.inner.flatten_requested.store(false, Ordering::SeqCst);
+        self.inner.changed.notify_waiters();
+    }
+
+    /// Halt trading and ask the agent to cancel all open orders.
+    pub fn kill(&self) {
+        self.inner.killed.store(true, Ordering::SeqCst);
+        self.inner.cancel_requested.store(true, Ordering::SeqCst);
+        self.inner.changed.notify_waiters();
+    }
+
+    /// Engage the kill switch and ask the agent to sell every position.
+    pub fn flatten(&self) {
+        self.inner.flatten_requested.store(true, Ordering::SeqCst);
+        self.kill();
+    }
+
+    /// Whether trading is halted, by pause or kill switch.
+    pub fn is_halted(&self) -> bool {
+        self.inner.paused.load(Ordering::SeqCst) || self.is_killed()
+    }
+
+    /// Whether the kill switch is engaged.
+    pub fn is_killed(&self) -> bool {
+        self.inner.killed.load(Ordering::SeqCst)
+    }
+
+    /// Consume a pending kill-switch order cancellation.
+    pub fn take_cancel_request(&self) -> bool {
+        self.inner.cancel_requested.swap(false, Ordering::SeqCst)
+    }
+
+    /// Consume a pending request to flatten all positions.
+    pub fn take_flatten_request(&self) -> bool {
+        self.inner.flatten_requested.swap(false, Ordering::SeqCst)
+    }
+
+    /// Engage the kill switch if `config.file` exists and it isn't already.
+    /// Returns whether this call tripped it.
+    pub fn check_kill_file(&self, config: &KillSwitchConfig) -> bool {
+        let Some(file) = config.file.as_deref() else {
+            return false;
+        };
+        if self.is_killed() || !Path::new(file).exists() {
+            return false;
+        }
+        if config.flatten {
+            self.flatten();
+        } else {
+            self.kill();
+        }
+        true
+    }
+
+    /// "running", "paused" or "killed".
+    pub fn status(&self) -> &'static str {
+        if self.inner.killed.load(Ordering::SeqCst) {
+            "killed"
+        } else if self.inner.paused.load(Ordering::SeqCst) {
+            "paused"
+        } else {
+            "running"
+        }
+    }
+
+    /// Resolves on the next pause, resume or kill, so the agent can wake
+    /// from its inter-cycle sleep and act at once.
+    pub async fn changed(&self) {
+        self.inner.changed.notified().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_switch_cancels_once_and_resume_clears() {
+        let control = ControlState::new();
+        assert_eq!(control.status(), "running");
+
+        control.pause();
+        assert!(control.is_halted());
+        assert!(!control.take_cancel_request());
+
+        control.kill();
+        assert_eq!(control.status(), "killed");
+        assert!(control.take_cancel_request());
+        assert!(!control.take_cancel_request());
+
+        control.resume();
+        assert!(!control.is_halted());
+        assert_eq!(control.status(), "running");
+    }
+
+    #[test]
+    fn test_flatten_kills_and_requests_exit_once() {
+        let control = ControlState::new();
+        control.flatten();
+        assert!(control.is_killed());
+        assert!(control.take_cancel_request());
+        assert!(control.take_flatten_request());
+        assert!(!control.take_flatten_request());
+
+        control.flatten();
+        control.resume();
+        assert!(!control.take_flatten_request());
+    }
+
+    #[test]
+    fn test_kill_file_trips_switch_while_present() {
+        let path = std::env::temp_dir().join(format!("kill-switch-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let config = KillSwitchConfig {
+            file: Some(path.to_string_lossy().into_owned()),
+            flatten: true,
+        };
+        let control = ControlState::new();
+        assert!(!control.check_kill_file(&config));
+        assert!(!control.check_kill_file(&KillSwitchConfig::default()));
+
+        std::fs::write(&path, "").unwrap();
+        assert!(control.check_kill_file(&config));
+        assert!(control.take_flatten_request());
+        // Already engaged: not tripped again
+        assert!(!control.check_kill_file(&config));
+
+        // Resuming with the file still present re-engages on the next check
+        control.resume();
+        assert!(control.check_kill_file(&config));
+
+        std::fs::remove_file(&path).unwrap();
+        control.resume();
+        assert!(!control.check_kill_file(&config));
+    }
+}
@@ -0,0 +1,181 @@
+//! Panic handling.
+//!
+//! [`install_panic_hook`] logs every panic with its backtrace and writes a
+//! crash marker file next to the database, before the default hook runs.
+//! The trading loop runs each cycle under [`catch_panic`]; a panicking
+//! cycle sends a critical alert and cancels open orders before the process
+//! exits. Panics in background tasks leave only the marker. Either way the
+//! next startup reports the crash from the marker and removes it.
+
+use std::any::Any;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A panic, as logged and written to the crash marker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub message: String,
+    /// `file:line:column` of the panic, when known.
+    pub location: Option<String>,
+    /// Thread that panicked.
+    pub thread: String,
+    pub backtrace: String,
+    pub at: DateTime<Utc>,
+}
+
+impl std::fmt::Display for CrashReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}'", self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {location}")?;
+        }
+        write!(f, " (thread {}, {})", self.thread, self.at.to_rfc3339())
+    }
+}
+
+/// The most recent panic seen by the hook.
+#[derive(Clone, Default)]
+pub struct LastPanic(Arc<Mutex<Option<CrashReport>>>);
+
+impl LastPanic {
+    pub fn take(&self) -> Option<CrashReport> {
+        self.0.lock().ok()?.take()
+    }
+}
+
+/// Crash marker file for the database at `database_path`.
+pub fn marker_path(database_path: &str) -> PathBuf {
+    PathBuf::from(format!("{database_path}.crash.json"))
+}
+
+/// Log panics with a backtrace and record them in `marker`, then run the
+/// previously installed hook.
+pub fn install_panic_hook(marker: PathBuf) -> LastPanic {
+    let last = LastPanic::default();
+    let slot = last.clone();
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let report = CrashReport {
+            message: payload_message(info.payload()),
+            location: info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("unnamed")
+                .to_string(),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            at: Utc::now(),
+        };
+        tracing::error!(
+            message = %report.message,
+            location = report.location.as_deref().unwrap_or("unknown"),
+            thread = %report.thread,
+            backtrace = %report.backtrace,
+            "Agent panicked"
+        );
+        if let Err(e) = write_marker(&marker, &report) {
+            tracing::error!(error = %e, "Failed to write crash marker");
+        }
+        if let Ok(mut slot) = slot.0.lock() {
+            *slot = Some(report);
+        }
+        previous(info);
+    }));
+    last
+}
+
+/// The crash left by a previous run, if any, removing its marker.
+pub fn take_marker(marker: &Path) -> Result<Option<CrashReport>> {
+    let json = match std::fs::read_to_string(marker) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).context("Failed to read crash marker"),
+    };
+    std::fs::remove_file(marker).context("Failed to remove crash marker")?;
+    let report = serde_json::from_str(&json).context("Corrupt crash marker")?;
+    Ok(Some(report))
+}
+
+fn write_marker(marker: &Path, report: &CrashReport) -> Result<()> {
+    std::fs::write(marker, serde_json::to_string_pretty(report)?)
+        .with_context(|| format!("Failed to write {}", marker.display()))
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+/// Future returned by [`catch_panic`].
+pub struct CatchPanic<F>(Pin<Box<F>>);
+
+/// Resolve to `Err` with the panic payload if `future` panics while polled.
+pub fn catch_panic<F: Future>(future: F) -> CatchPanic<F> {
+    CatchPanic(Box::pin(future))
+}
+
+impl<F: Future> Future for CatchPanic<F> {
+    type Output = std::result::Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        let inner = self.0.as_mut();
+        match panic::catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_catch_panic() {
+        assert_eq!(catch_panic(async { 7 }).await.unwrap(), 7);
+
+        let caught = catch_panic(async {
+            tokio::task::yield_now().await;
+            panic!("cycle blew up");
+        })
+        .await;
+        let payload = caught.unwrap_err();
+        assert_eq!(payload_message(payload.as_ref()), "cycle blew up");
+    }
+
+    #[test]
+    fn test_marker_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("crash-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let marker = marker_path(&dir.join("agent.db").display().to_string());
+        assert!(take_marker(&marker).unwrap().is_none());
+
+        let report = CrashReport {
+            message: "index out of bounds".to_string(),
+            location: Some("src/agent/lifecycle.rs:10:5".to_string()),
+            thread: "main".to_string(),
+            backtrace: String::new(),
+            at: Utc::now(),
+        };
+        write_marker(&marker, &report).unwrap();
+        assert_eq!(take_marker(&marker).unwrap(), Some(report));
+        // Reported once
+        assert!(!marker.exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
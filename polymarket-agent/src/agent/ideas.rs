@@ -0,0 +1,221 @@
+//! Trade idea journal.
+//!
+//! Every valuation becomes an idea in the `journal` table: the side it
+//! favored, the data behind it, and whether it was traded or skipped — with
+//! a [`SkipReason`] code naming the check that stopped it. Once the market
+//! resolves, each idea is graded: a skipped idea whose side won is a
+//! "skipped winner", a traded one whose side lost a "traded loser". Counts
+//! per skip reason show which thresholds cost the most winners.
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+
+use crate::db::store::{sql_timestamp, Store, TradeIdeaRecord};
+use crate::market::models::{MarketCandidate, Side};
+use crate::valuation::edge::EdgeResult;
+use crate::valuation::fair_value::ValuationResult;
+
+pub const TRADED: &str = "TRADED";
+pub const SKIPPED: &str = "SKIPPED";
+
+/// The check that kept a valued opportunity from being traded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Net edge below the threshold, or confidence or data quality too low.
+    NoEdge,
+    ExecutionDeadline,
+    EventWindow,
+    KellyNoTrade,
+    CostNotJustified,
+    PortfolioConstraint,
+    NoCapacity,
+    InsufficientLiquidity,
+    OrderPreparation,
+    MaxTradeLoss,
+    /// Sent for manual approval instead of traded.
+    AwaitingApproval,
+    NoBid,
+    /// Submitted, but nothing filled.
+    NotFilled,
+    /// Valuation contradicts related markets' valuations.
+    Inconsistent,
+}
+
+impl SkipReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::NoEdge => "no_edge",
+            SkipReason::ExecutionDeadline => "execution_deadline",
+            SkipReason::EventWindow => "event_window",
+            SkipReason::KellyNoTrade => "kelly_no_trade",
+            SkipReason::CostNotJustified => "cost_not_justified",
+            SkipReason::PortfolioConstraint => "portfolio_constraint",
+            SkipReason::NoCapacity => "no_capacity",
+            SkipReason::InsufficientLiquidity => "insufficient_liquidity",
+            SkipReason::OrderPreparation => "order_preparation",
+            SkipReason::MaxTradeLoss => "max_trade_loss",
+            SkipReason::AwaitingApproval => "awaiting_approval",
+            SkipReason::NoBid => "no_bid",
+            SkipReason::NotFilled => "not_filled",
+            SkipReason::Inconsistent => "inconsistent",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdeaDecision {
+    Traded { size_usd: Decimal },
+    Skipped(SkipReason),
+}
+
+/// One valued opportunity, before its decision is known.
+#[derive(Debug, Clone)]
+pub struct TradeIdea {
+    record: TradeIdeaRecord,
+}
+
+impl TradeIdea {
+    /// `edge` is `None` when the valuation found none; the idea then
+    /// favors the side its fair value does.
+    pub fn new(
+        cycle: u64,
+        candidate: &MarketCandidate,
+        valuation: &ValuationResult,
+        edge: Option<&EdgeResult>,
+    ) -> Self {
+        let market_price = candidate.order_book.implied_probability;
+        let side = match edge {
+            Some(edge) => edge.side,
+            None if valuation.probability > market_price => Side::Yes,
+            None => Side::No,
+        };
+        let record = TradeIdeaRecord {
+            id: None,
+            cycle: cycle as i64,
+            market_id: candidate.market.condition_id.clone(),
+            market_question: candidate.market.question.clone(),
+            category: candidate.market.category.as_str().to_string(),
+            side: side.to_string(),
+            decision: SKIPPED.to_string(),
+            reason: None,
+            fair_value: valuation.probability.to_string(),
+            market_price: market_price.to_string(),
+            net_edge: edge.map(|e| e.net_edge.to_string()),
+            confidence: valuation.confidence.to_string(),
+            key_factors: serde_json::to_string(&valuation.key_factors)
+                .unwrap_or_else(|_| "[]".to_string()),
+            size_usd: None,
+            end_date: sql_timestamp(candidate.market.end_date),
+            created_at: None,
+            resolved_at: None,
+            side_won: None,
+            correct: None,
+        };
+        Self { record }
+    }
+
+    fn with_decision(&self, decision: IdeaDecision) -> TradeIdeaRecord {
+        let mut record = self.record.clone();
+        match decision {
+            IdeaDecision::Traded { size_usd } => {
+                record.decision = TRADED.to_string();
+                record.size_usd = Some(size_usd.to_string());
+            }
+            IdeaDecision::Skipped(reason) => {
+                record.reason = Some(reason.as_str().to_string());
+            }
+        }
+        record
+    }
+}
+
+/// Journal `idea` with the decision taken on it.
+pub async fn record(store: &Store, idea: &TradeIdea, decision: IdeaDecision) -> Result<i64> {
+    store.insert_trade_idea(&idea.with_decision(decision)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::{BookLiquidity, Market, MarketCategory, OrderBookSnapshot};
+    use crate::valuation::fair_value::{DataQuality, TimeSensitivity};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn candidate() -> MarketCandidate {
+        MarketCandidate {
+            market: Market {
+                condition_id: "0xabc".to_string(),
+                question: "Will it rain in NYC?".to_string(),
+                outcomes: vec!["Yes".to_string(), "No".to_string()],
+                tokens: Vec::new(),
+                end_date: Utc::now(),
+                category: MarketCategory::Weather,
+                volume_24h: dec!(10000),
+                active: true,
+                event_id: None,
+                event_slug: None,
+                neg_risk: false,
+                description: String::new(),
+                resolution_source: None,
+            },
+            order_book: OrderBookSnapshot {
+                token_id: "t1".to_string(),
+                bids: Vec::new(),
+                asks: Vec::new(),
+                spread: dec!(0.02),
+                midpoint: dec!(0.5),
+                implied_probability: dec!(0.5),
+                timestamp: Utc::now(),
+                liquidity: BookLiquidity::Empty,
+            },
+            new_listing: false,
+            liquidity_score: None,
+        }
+    }
+
+    fn valuation(probability: Decimal) -> ValuationResult {
+        ValuationResult {
+            probability,
+            confidence: dec!(0.3),
+            reasoning_summary: String::new(),
+            key_factors: vec!["NWS forecast 70% rain".to_string()],
+            data_quality: DataQuality::Medium,
+            time_sensitivity: TimeSensitivity::Days,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_skipped_and_traded() {
+        let store = Store::new(":memory:").await.unwrap();
+        let candidate = candidate();
+
+        // No edge: the side follows fair value
+        let idea = TradeIdea::new(3, &candidate, &valuation(dec!(0.4)), None);
+        record(&store, &idea, IdeaDecision::Skipped(SkipReason::NoEdge))
+            .await
+            .unwrap();
+        let idea = TradeIdea::new(3, &candidate, &valuation(dec!(0.7)), None);
+        record(
+            &store,
+            &idea,
+            IdeaDecision::Traded {
+                size_usd: dec!(12.5),
+            },
+        )
+        .await
+        .unwrap();
+
+        let ideas = store.get_recent_trade_ideas(None, 10).await.unwrap();
+        let (traded, skipped) = (&ideas[0], &ideas[1]);
+        assert_eq!(skipped.side, "NO");
+        assert_eq!(skipped.decision, SKIPPED);
+        assert_eq!(skipped.reason.as_deref(), Some("no_edge"));
+        assert_eq!(skipped.size_usd, None);
+        assert_eq!(traded.side, "YES");
+        assert_eq!(traded.decision, TRADED);
+        assert_eq!(traded.size_usd.as_deref(), Some("12.5"));
+        assert_eq!(traded.key_factors, r#"["NWS forecast 70% rain"]"#);
+        assert_eq!(traded.category, "weather");
+    }
+}
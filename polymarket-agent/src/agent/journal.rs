@@ -0,0 +1,429 @@
+//! Write-ahead journal of each cycle's valuations and orders.
+//!
+//! Every valuation that finds an edge is journaled as pending and marked
+//! done with the rest of its cycle. At startup [`recover`] looks at the
+//! valuations of a cycle that never finished: one already traded has its
+//! cached valuation consumed so the market is not entered twice on it, and
+//! one not yet traded keeps its cached valuation so the next cycle reuses it
+//! instead of paying to value the market again.
+//!
+//! Every buy placed through the agent's execution path — entries, approved
+//! trades, market making, sniping and hedges — is journaled before
+//! submission, marked submitted with its exchange order id, and done once
+//! its trade is recorded. An order intent still open at startup means the
+//! previous process died mid-order; [`recover`] settles each one against
+//! the trades table and, in live mode, the exchange, so no order is placed
+//! twice or left untracked. Exit sells and YES/NO merges are not journaled:
+//! their trade stays open until they succeed, so an interrupted one is
+//! retried by the next cycle's exit checks.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tracing::{error, info, warn};
+
+use crate::db::export::parse_timestamp;
+use crate::db::store::{JournalEntry, Store};
+use crate::execution::fees::Liquidity;
+use crate::execution::fills;
+use crate::execution::order::{ExecutionResult, OrderStatus, PreparedOrder};
+use crate::market::models::Side;
+use crate::market::polymarket::PolymarketClient;
+
+pub const VALUATION: &str = "VALUATION";
+pub const ORDER: &str = "ORDER";
+
+pub const PENDING: &str = "PENDING";
+pub const SUBMITTED: &str = "SUBMITTED";
+pub const DONE: &str = "DONE";
+/// Completed at startup from the exchange's record of the order.
+pub const RECOVERED: &str = "RECOVERED";
+/// Given up at startup: the order never filled or cannot be traced.
+pub const ABANDONED: &str = "ABANDONED";
+
+/// Journal a valuation that found an edge, pending until its cycle ends.
+pub async fn record_valuation(
+    store: &Store,
+    cycle: u64,
+    market_id: &str,
+    probability: Decimal,
+    confidence: Decimal,
+    side: Side,
+    edge: Decimal,
+) -> Result<()> {
+    let payload = serde_json::json!({
+        "probability": probability,
+        "confidence": confidence,
+        "side": side,
+        "edge": edge,
+    });
+    store
+        .insert_journal_entry(
+            cycle as i64,
+            VALUATION,
+            market_id,
+            &payload.to_string(),
+            PENDING,
+        )
+        .await?;
+    Ok(())
+}
+
+/// Record that `cycle` finished with all its valuations.
+pub async fn valuations_done(store: &Store, cycle: u64) -> Result<()> {
+    store.complete_journal_valuations(cycle as i64).await
+}
+
+/// Journal an order about to be submitted. Returns the entry id.
+pub async fn begin_order(store: &Store, cycle: u64, order: &PreparedOrder) -> Result<i64> {
+    store
+        .insert_journal_entry(
+            cycle as i64,
+            ORDER,
+            &order.market_id,
+            &serde_json::to_string(order)?,
+            PENDING,
+        )
+        .await
+}
+
+/// Record that the exchange acknowledged the order.
+pub async fn order_submitted(store: &Store, id: i64, order_id: &str) -> Result<()> {
+    store
+        .update_journal_entry(id, SUBMITTED, Some(order_id).filter(|o| !o.is_empty()))
+        .await
+}
+
+/// Record that the order's outcome is fully persisted.
+pub async fn order_done(store: &Store, id: i64) -> Result<()> {
+    store.update_journal_entry(id, DONE, None).await
+}
+
+/// What startup recovery found.
+#[derive(Debug, Default)]
+pub struct RecoveryReport {
+    /// Intents whose trade had already been recorded.
+    pub completed: usize,
+    /// Intents whose fill was recovered from the exchange.
+    pub recovered: usize,
+    /// Markets whose intent was abandoned.
+    pub abandoned: Vec<String>,
+    /// Valuations already traded on, whose cached valuation was consumed.
+    pub valuations_consumed: usize,
+    /// Valuations not yet traded on, left cached for the next cycle.
+    pub valuations_reused: usize,
+}
+
+/// Settle order intents and valuations left open by a crash. `live` is the
+/// exchange client in live mode; in paper mode simulated fills did not
+/// survive the crash, so unrecorded intents are simply abandoned.
+pub async fn recover(store: &Store, live: Option<&PolymarketClient>) -> Result<RecoveryReport> {
+    let mut report = RecoveryReport::default();
+    recover_orders(store, live, &mut report).await?;
+    recover_valuations(store, &mut report).await?;
+    Ok(report)
+}
+
+async fn recover_orders(
+    store: &Store,
+    live: Option<&PolymarketClient>,
+    report: &mut RecoveryReport,
+) -> Result<()> {
+    let entries = store.get_incomplete_journal_orders().await?;
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    warn!(
+        intents = entries.len(),
+        "Incomplete orders in the cycle journal — recovering from a crash"
+    );
+    if let Some(client) = live {
+        // Nothing from the crashed cycle may keep working while we reconcile
+        match client.cancel_all_orders().await {
+            Ok(cancelled) => info!(count = cancelled.len(), "Cancelled resting orders"),
+            Err(e) => error!(error = %e, "Failed to cancel resting orders during recovery"),
+        }
+        store.cancel_open_orders().await?;
+    }
+
+    for entry in entries {
+        let order: PreparedOrder = serde_json::from_str(&entry.payload)
+            .with_context(|| format!("Corrupt journal entry {}", entry.id))?;
+        if store
+            .has_trade_since(
+                &order.market_id,
+                &order.side.to_string(),
+                journaled_at(&entry),
+            )
+            .await?
+        {
+            store.update_journal_entry(entry.id, DONE, None).await?;
+            report.completed += 1;
+            continue;
+        }
+
+        let recovered = match (live, entry.order_id.as_deref()) {
+            (Some(client), Some(order_id)) => {
+                recover_fill(store, client, &entry, &order, order_id).await
+            }
+            _ => false,
+        };
+        if recovered {
+            store
+                .update_journal_entry(entry.id, RECOVERED, None)
+                .await?;
+            report.recovered += 1;
+        } else {
+            if live.is_some() && entry.status == PENDING {
+                error!(
+                    market = %order.market_id,
+                    side = %order.side,
+                    size = %order.size,
+                    "Order may have reached the exchange before the crash — verify the position manually"
+                );
+            }
+            store
+                .update_journal_entry(entry.id, ABANDONED, None)
+                .await?;
+            report.abandoned.push(order.market_id);
+        }
+    }
+
+    info!(
+        completed = report.completed,
+        recovered = report.recovered,
+        abandoned = report.abandoned.len(),
+        "Cycle journal recovery complete"
+    );
+    Ok(())
+}
+
+/// Consume the cached valuations a crashed cycle already traded on and keep
+/// the rest for reuse.
+async fn recover_valuations(store: &Store, report: &mut RecoveryReport) -> Result<()> {
+    for entry in store.get_incomplete_journal_valuations().await? {
+        let payload: serde_json::Value = serde_json::from_str(&entry.payload)
+            .with_context(|| format!("Corrupt journal entry {}", entry.id))?;
+        let side: Side = serde_json::from_value(payload["side"].clone())
+            .with_context(|| format!("Corrupt journal entry {}", entry.id))?;
+
+        if store
+            .has_trade_since(&entry.market_id, &side.to_string(), journaled_at(&entry))
+            .await?
+        {
+            store.consume_valuation(&entry.market_id).await?;
+            store.update_journal_entry(entry.id, DONE, None).await?;
+            report.valuations_consumed += 1;
+        } else {
+            store
+                .update_journal_entry(entry.id, RECOVERED, None)
+                .await?;
+            report.valuations_reused += 1;
+        }
+    }
+
+    if report.valuations_consumed + report.valuations_reused > 0 {
+        info!(
+            consumed = report.valuations_consumed,
+            reused = report.valuations_reused,
+            "Recovered valuations of a crashed cycle"
+        );
+    }
+    Ok(())
+}
+
+/// When `entry` was journaled; trades at or after it belong to it.
+fn journaled_at(entry: &JournalEntry) -> DateTime<Utc> {
+    entry
+        .created_at
+        .as_deref()
+        .and_then(parse_timestamp)
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+/// Record the trade for a submitted order from its exchange fill. Returns
+/// whether anything filled.
+async fn recover_fill(
+    store: &Store,
+    client: &PolymarketClient,
+    entry: &JournalEntry,
+    order: &PreparedOrder,
+    order_id: &str,
+) -> bool {
+    let fill = match client.get_order_fill(order_id).await {
+        Ok(fill) => fill,
+        Err(e) => {
+            error!(order_id, error = %e, "Failed to look up journaled order");
+            return false;
+        }
+    };
+    if fill.size_matched <= Decimal::ZERO {
+        return false;
+    }
+
+    let status = if fill.size_matched >= order.size {
+        OrderStatus::Filled
+    } else {
+        OrderStatus::PartiallyFilled {
+            filled_size: fill.size_matched,
+        }
+    };
+    let execution = ExecutionResult {
+        order_id: order_id.to_string(),
+        token_id: order.token_id.clone(),
+        side: order.side,
+        price: fill.avg_price,
+        size: fill.size_matched,
+        fee: client
+            .fees()
+            .fee(Liquidity::Taker, fill.avg_price, fill.size_matched),
+        status,
+    };
+    match fills::record_trade(store, order, &execution, entry.cycle as u64).await {
+        Ok(_) => {
+            info!(order_id, filled = %fill.size_matched, "Recovered fill of journaled order");
+            true
+        }
+        Err(e) => {
+            error!(order_id, error = %e, "Failed to record recovered fill");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::store::TradeRecord;
+    use rust_decimal_macros::dec;
+
+    fn order(market_id: &str) -> PreparedOrder {
+        PreparedOrder {
+            token_id: format!("{market_id}-yes"),
+            side: Side::Yes,
+            price: dec!(0.40),
+            size: dec!(10),
+            market_id: market_id.to_string(),
+            market_question: "Will it happen?".to_string(),
+            edge: dec!(0.1),
+            fair_value: dec!(0.5),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.2),
+            kelly_adjusted: dec!(0.05),
+            bucket: "directional".to_string(),
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recover_settles_incomplete_intents() {
+        let store = Store::new(":memory:").await.unwrap();
+
+        // Crashed after recording the trade
+        begin_order(&store, 1, &order("m1")).await.unwrap();
+        let execution = ExecutionResult {
+            order_id: String::new(),
+            token_id: "m1-yes".to_string(),
+            side: Side::Yes,
+            price: dec!(0.40),
+            size: dec!(10),
+            fee: Decimal::ZERO,
+            status: OrderStatus::Filled,
+        };
+        fills::record_trade(&store, &order("m1"), &execution, 1)
+            .await
+            .unwrap();
+        // Crashed before anything was recorded
+        begin_order(&store, 1, &order("m2")).await.unwrap();
+        // Finished normally
+        let id = begin_order(&store, 1, &order("m3")).await.unwrap();
+        order_done(&store, id).await.unwrap();
+
+        let report = recover(&store, None).await.unwrap();
+        assert_eq!(report.completed, 1);
+        assert_eq!(report.abandoned, vec!["m2".to_string()]);
+        assert!(store
+            .get_incomplete_journal_orders()
+            .await
+            .unwrap()
+            .is_empty());
+        let trades: Vec<TradeRecord> = store.get_all_trades().await.unwrap();
+        assert_eq!(trades.len(), 1);
+    }
+
+    async fn cache_valuation(store: &Store, market_id: &str) {
+        sqlx::query(
+            "INSERT INTO valuation_cache (condition_id, probability, confidence, data_quality, time_sensitivity)
+             VALUES (?, '0.5', '0.8', 'High', 'Days')",
+        )
+        .bind(market_id)
+        .execute(store.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn consumed(store: &Store, market_id: &str) -> bool {
+        let (consumed_at,): (Option<String>,) =
+            sqlx::query_as("SELECT consumed_at FROM valuation_cache WHERE condition_id = ?")
+                .bind(market_id)
+                .fetch_one(store.pool())
+                .await
+                .unwrap();
+        consumed_at.is_some()
+    }
+
+    #[tokio::test]
+    async fn test_recover_consumes_traded_valuations_and_keeps_the_rest() {
+        let store = Store::new(":memory:").await.unwrap();
+        for market_id in ["m1", "m2", "m3"] {
+            cache_valuation(&store, market_id).await;
+        }
+
+        // A finished cycle
+        record_valuation(&store, 1, "m3", dec!(0.5), dec!(0.8), Side::Yes, dec!(0.1))
+            .await
+            .unwrap();
+        valuations_done(&store, 1).await.unwrap();
+        // A crashed cycle that traded m1 but not yet m2
+        for market_id in ["m1", "m2"] {
+            record_valuation(
+                &store,
+                2,
+                market_id,
+                dec!(0.5),
+                dec!(0.8),
+                Side::Yes,
+                dec!(0.1),
+            )
+            .await
+            .unwrap();
+        }
+        let id = begin_order(&store, 2, &order("m1")).await.unwrap();
+        let execution = ExecutionResult {
+            order_id: String::new(),
+            token_id: "m1-yes".to_string(),
+            side: Side::Yes,
+            price: dec!(0.40),
+            size: dec!(10),
+            fee: Decimal::ZERO,
+            status: OrderStatus::Filled,
+        };
+        fills::record_trade(&store, &order("m1"), &execution, 2)
+            .await
+            .unwrap();
+        order_done(&store, id).await.unwrap();
+
+        let report = recover(&store, None).await.unwrap();
+        assert_eq!(report.valuations_consumed, 1);
+        assert_eq!(report.valuations_reused, 1);
+        assert!(consumed(&store, "m1").await);
+        assert!(!consumed(&store, "m2").await);
+        assert!(!consumed(&store, "m3").await);
+        assert!(store
+            .get_incomplete_journal_valuations()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,93 +1,210 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Instant;
 
 use anyhow::Result;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use tracing::{error, info, warn};
-
+use tracing::{debug, error, info, warn, Instrument};
+
+use crate::agent::book_capture;
+use crate::agent::budget::{CycleBudget, CyclePhase};
+use crate::agent::canary::{self, CanaryReport};
+use crate::agent::control::ControlState;
+use crate::agent::ideas::{self, IdeaDecision, SkipReason, TradeIdea};
+use crate::agent::journal;
 use crate::agent::self_funding::{
     self, edge_justifies_cost, enhanced_survival_check, log_cost_breakdown, CycleCosts,
 };
-use crate::config::{AppConfig, Secrets};
+use crate::agent::shutdown;
+use crate::agent::triggers::{self, TriggerHandle};
+use crate::config::{AppConfig, RiskConfig, Secrets};
 use crate::data::crypto::CryptoSource;
 use crate::data::news::NewsSource;
 use crate::data::sports::SportsSource;
 use crate::data::weather::WeatherSource;
 use crate::data::{DataAggregator, DataPoint, MarketQuery};
-use crate::db::store::{CycleRecord, Store};
+use crate::db::batch::BatchWriter;
+use crate::db::store::{
+    add_phase_time, sql_timestamp, CycleRecord, MarketEvaluationRecord, PhaseTimings, Store,
+    TradeRecord,
+};
+use crate::execution::fees::Liquidity;
 use crate::execution::fills;
-use crate::execution::order::{self, OrderStatus};
+use crate::execution::order::{self, OrderStatus, PreparedOrder};
+use crate::execution::redemption;
 use crate::execution::resolution;
-use crate::execution::wallet;
-use crate::market::models::{AgentState, MarketCandidate};
+use crate::execution::routing::{self, ExecutionStyle};
+use crate::execution::twap;
+use crate::execution::wallet::{self, WalletState};
+use crate::market::api::MarketApi;
+use crate::market::models::{AgentState, MarketCandidate, MarketCategory, Opportunity, Side};
 use crate::market::polymarket::PolymarketClient;
 use crate::market::scanner::MarketScanner;
+use crate::market::{liquidity, listings};
 use crate::monitoring::alerts::{check_milestone, AlertClient};
+use crate::monitoring::anomaly::{self, AnomalyKind};
+use crate::monitoring::funding::{self, FundingLevel};
+use crate::monitoring::latency::TradeTimeline;
+use crate::monitoring::logger;
 use crate::monitoring::metrics::{compute_metrics, log_metrics};
+use crate::monitoring::signals::{Signal, SignalApproval, SignalPublisher};
+use crate::monitoring::weekly;
+use crate::risk::approval::{self, ApprovedOrder};
+use crate::risk::blacklist;
+use crate::risk::buckets::{BankrollBuckets, DIRECTIONAL_BUCKET};
+use crate::risk::drawdown::{self, CooldownEvent, CooldownTracker, DrawdownReport};
+use crate::risk::events;
+use crate::risk::hedge::{self, HedgeAction};
 use crate::risk::kelly;
 use crate::risk::limits;
-use crate::risk::portfolio::{PortfolioManager, Position};
+use crate::risk::portfolio::{self, ConstraintCheck, PortfolioManager, Position};
+use crate::risk::rebalance;
+use crate::risk::sizing;
+use crate::risk::strategy::market_making::{self, MARKET_MAKING_BUCKET};
+use crate::risk::strategy::sniping::{self, SNIPING_BUCKET};
+use crate::risk::strategy::Strategies;
 use crate::valuation::calibration;
 use crate::valuation::claude::ClaudeClient;
+use crate::valuation::consistency;
 use crate::valuation::edge::{evaluate_edge, to_opportunity, EdgeResult};
 use crate::valuation::fair_value::{ValuationEngine, ValuationResult};
+use crate::valuation::relevance::RelevanceScorer;
+use crate::valuation::revaluation;
 
 pub struct Agent {
     config: AppConfig,
     store: Store,
+    /// Batches high-frequency inserts (evaluations, book snapshots).
+    writer: BatchWriter,
     state: AgentState,
     cycle_number: u64,
     polymarket: Arc<PolymarketClient>,
+    /// Where markets are scanned and orders worked; `polymarket` unless
+    /// replaced with [`Agent::with_exchange`].
+    exchange: Arc<dyn MarketApi>,
     scanner: MarketScanner,
     data_aggregator: DataAggregator,
     valuation_engine: Option<ValuationEngine>,
+    /// Cheaper engine refreshing held markets' fair value between scans.
+    revaluation_engine: Option<ValuationEngine>,
     portfolio: PortfolioManager,
     alert_client: AlertClient,
+    /// Webhook receiving opportunities and executions, if configured.
+    signals: Option<SignalPublisher>,
     last_balance: Decimal,
+    control: ControlState,
+    strategies: Strategies,
+    wallet: WalletState,
+    /// UTC day the Claude spend-cap alert was last sent.
+    spend_cap_alerted: Option<chrono::NaiveDate>,
+    /// Anomalies flagged last cycle, so persisting ones aren't re-alerted.
+    active_anomalies: HashSet<AnomalyKind>,
+    /// Funding level last cycle; alerts fire when it escalates.
+    funding_level: FundingLevel,
+    /// Drawdown cooldown in force, if any.
+    drawdown_cooldown: CooldownTracker,
+    /// Market-making quotes left resting last cycle, replaced each cycle.
+    quote_order_ids: Vec<String>,
 }
 
 impl Agent {
     pub async fn new(config: AppConfig, secrets: Secrets, store: Store) -> Result<Self> {
         let config_arc = Arc::new(config.clone());
         let polymarket = Arc::new(PolymarketClient::new(config_arc, &secrets).await?);
-        let scanner = MarketScanner::new(polymarket.clone(), config.scanning.clone());
+        let exchange: Arc<dyn MarketApi> = polymarket.clone();
+        let scanner = MarketScanner::new(exchange.clone(), config.scanning.clone())
+            .with_evaluation_history(store.clone_for_parallel());
 
         // Phase 3: Initialize data sources
         let data_sources: Vec<Box<dyn crate::data::DataSource>> = vec![
-            Box::new(WeatherSource::new()),
-            Box::new(SportsSource::new()),
+            Box::new(WeatherSource::new().with_cache(polymarket.http_cache())),
+            Box::new(SportsSource::new().with_cache(polymarket.http_cache())),
             Box::new(CryptoSource::new()),
             Box::new(NewsSource::new()),
         ];
-        let data_aggregator = DataAggregator::new(data_sources);
+        let data_aggregator = DataAggregator::new(data_sources)
+            .with_health(config.source_health.clone())
+            .with_chaos(polymarket.chaos())
+            .with_clock(store.clock().clone());
 
         // Phase 4: Initialize valuation engine (only if API key is available)
         let valuation_engine = if let Some(ref api_key) = secrets.anthropic_api_key {
             let claude_store = Store::new(&config.database.path).await?;
             let valuation_store = Store::new(&config.database.path).await?;
-            let claude_client = Arc::new(ClaudeClient::new(
-                api_key.clone(),
-                config.valuation.claude_model.clone(),
-                claude_store,
-            ));
-            Some(ValuationEngine::new(
-                claude_client,
-                config.valuation.clone(),
-                valuation_store,
-            ))
+            let claude_client = Arc::new(
+                ClaudeClient::new(
+                    api_key.clone(),
+                    config.valuation.claude_model.clone(),
+                    claude_store,
+                )
+                .with_chaos(polymarket.chaos()),
+            );
+            let mut engine =
+                ValuationEngine::new(claude_client, config.valuation.clone(), valuation_store)
+                    .with_daily_budget(config.agent.daily_api_budget);
+            match secrets.voyage_api_key {
+                Some(ref voyage_key) if config.relevance.enabled => {
+                    let relevance_store = Store::new(&config.database.path).await?;
+                    engine = engine.with_relevance(Arc::new(RelevanceScorer::new(
+                        voyage_key.clone(),
+                        config.relevance.clone(),
+                        relevance_store,
+                    )));
+                }
+                _ => info!("Embedding relevance disabled — ranking evidence by keywords"),
+            }
+            Some(engine)
         } else {
             warn!("ANTHROPIC_API_KEY not set — valuation engine disabled");
             None
         };
 
+        let revaluation_engine = match secrets.anthropic_api_key {
+            Some(ref api_key) if config.revaluation.enabled => {
+                let claude_client = Arc::new(
+                    ClaudeClient::new(
+                        api_key.clone(),
+                        config.revaluation.claude_model.clone(),
+                        Store::new(&config.database.path).await?,
+                    )
+                    .with_chaos(polymarket.chaos()),
+                );
+                Some(
+                    ValuationEngine::new(
+                        claude_client,
+                        config.revaluation.valuation_config(&config.valuation),
+                        Store::new(&config.database.path).await?,
+                    )
+                    .with_daily_budget(config.agent.daily_api_budget),
+                )
+            }
+            _ => None,
+        };
+
         // Phase 5: Initialize portfolio manager
+        BankrollBuckets::new(config.risk.buckets.clone()).validate()?;
+        let strategies = Strategies::new(config.strategies.clone());
+        strategies.validate(&config.risk)?;
         let portfolio = PortfolioManager::new(config.risk.clone());
 
         // Phase 8: Initialize alert client
-        let alert_client = AlertClient::new(
-            secrets.discord_webhook_url.clone(),
-            config.monitoring.discord_enabled,
+        let alert_client = AlertClient::from_config(&config.monitoring, &secrets)?;
+        let signals = match secrets.signal_webhook_url.clone() {
+            Some(url) if config.monitoring.signals.enabled => {
+                Some(SignalPublisher::new(url, &config.monitoring.signals)?)
+            }
+            None if config.monitoring.signals.enabled => {
+                warn!("Signal export enabled but SIGNAL_WEBHOOK_URL is not set");
+                None
+            }
+            _ => None,
+        };
+
+        let (writer, _) = BatchWriter::spawn(
+            store.clone_for_parallel(),
+            config.database.write_batch.clone(),
         );
 
         // Resume cycle number from last recorded cycle
@@ -107,24 +224,250 @@ impl Agent {
         Ok(Self {
             config,
             store,
+            writer,
             state: AgentState::Alive,
             cycle_number,
             polymarket,
+            exchange,
             scanner,
             data_aggregator,
             valuation_engine,
+            revaluation_engine,
             portfolio,
             alert_client,
+            signals,
             last_balance: Decimal::ZERO,
+            control: ControlState::new(),
+            strategies,
+            wallet: WalletState::new(),
+            spend_cap_alerted: None,
+            active_anomalies: HashSet::new(),
+            funding_level: FundingLevel::Healthy,
+            drawdown_cooldown: CooldownTracker::default(),
+            quote_order_ids: Vec::new(),
         })
     }
 
+    /// Obey the given pause/kill flags, e.g. ones shared with the dashboard.
+    pub fn with_control(mut self, control: ControlState) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Publish on-chain wallet status to the given state, e.g. the dashboard's.
+    pub fn with_wallet(mut self, wallet: WalletState) -> Self {
+        self.wallet = wallet;
+        self
+    }
+
+    /// Scan markets and work orders on `exchange` instead of Polymarket,
+    /// e.g. a [`FakeExchange`](crate::market::fake::FakeExchange) in tests.
+    pub fn with_exchange(mut self, exchange: Arc<dyn MarketApi>) -> Self {
+        self.scanner = MarketScanner::new(exchange.clone(), self.config.scanning.clone())
+            .with_evaluation_history(self.store.clone_for_parallel());
+        self.exchange = exchange;
+        self
+    }
+
+    /// Fetch external data from `sources` instead of the built-in feeds.
+    pub fn with_data_sources(mut self, sources: Vec<Box<dyn crate::data::DataSource>>) -> Self {
+        self.data_aggregator = DataAggregator::new(sources)
+            .with_health(self.config.source_health.clone())
+            .with_clock(self.store.clock().clone());
+        self
+    }
+
+    /// Value markets with `engine`, e.g. one backed by a stub instead of Claude.
+    pub fn with_valuation_engine(mut self, engine: ValuationEngine) -> Self {
+        self.valuation_engine = Some(engine);
+        self
+    }
+
+    /// The agent's Polymarket client, for sharing with the dashboard.
+    pub fn market_client(&self) -> Arc<PolymarketClient> {
+        self.polymarket.clone()
+    }
+
+    /// Consecutive failed fetches per data source.
+    pub fn source_failures(&self) -> std::collections::BTreeMap<String, u32> {
+        self.data_aggregator.consecutive_failures()
+    }
+
+    /// Consecutive degraded fetches per degraded data source.
+    pub fn degraded_sources(&self) -> std::collections::BTreeMap<String, u32> {
+        self.data_aggregator.degraded_sources()
+    }
+
+    /// Run the post-deploy canary: schema, balance, scan, and one test
+    /// valuation carried through order preparation without executing.
+    pub async fn run_canary(&self, previous_version: Option<String>) -> CanaryReport {
+        let mut report = CanaryReport::new(previous_version);
+        info!(version = %report.version, "Running canary cycle");
+
+        canary::check_schema(&self.store, &mut report).await;
+
+        let balance = match self.exchange.get_balance().await {
+            Ok(b) => {
+                report.pass("balance", format!("${b}"));
+                b
+            }
+            Err(e) => {
+                report.fail("balance", e.to_string());
+                Decimal::ZERO
+            }
+        };
+
+        let candidates = match self.scanner.scan().await {
+            Ok(c) if !c.is_empty() => {
+                report.pass("scan", format!("{} candidates", c.len()));
+                c
+            }
+            Ok(_) => {
+                report.fail("scan", "no candidates returned");
+                Vec::new()
+            }
+            Err(e) => {
+                report.fail("scan", e.to_string());
+                Vec::new()
+            }
+        };
+
+        let Some(engine) = self.valuation_engine.as_ref() else {
+            report.pass("valuation", "skipped — valuation engine disabled");
+            return report;
+        };
+        let Some(candidate) = candidates.first() else {
+            report.fail("valuation", "no candidate to value");
+            return report;
+        };
+
+        let query = MarketQuery {
+            condition_id: candidate.market.condition_id.clone(),
+            question: candidate.market.question.clone(),
+            category: candidate.market.category.clone(),
+        };
+        let data = self.data_aggregator.fetch_all(&[query]).await;
+        let valuation = match engine
+            .evaluate(candidate, &data, balance, self.cycle_number as i64)
+            .await
+        {
+            Ok(Some(v)) => {
+                report.pass(
+                    "valuation",
+                    format!("p={} conf={}", v.probability, v.confidence),
+                );
+                v
+            }
+            Ok(None) => {
+                report.fail("valuation", "valuation skipped (bankroll or market data)");
+                return report;
+            }
+            Err(e) => {
+                report.fail("valuation", e.to_string());
+                return report;
+            }
+        };
+
+        // Dry-run execution: build the order a real cycle would, then stop.
+        match evaluate_edge(
+            candidate,
+            &valuation,
+            &self.config.valuation,
+            &self.exchange.fees(),
+        ) {
+            Some(edge) => {
+                let kelly_result = sizing::position_size(
+                    valuation.probability,
+                    edge.trade_price,
+                    valuation.confidence,
+                    balance,
+                    self.state,
+                    &self.config.risk,
+                );
+                let opportunity =
+                    to_opportunity(candidate, &valuation, &edge, kelly_result.position_usd);
+                if !kelly_result.should_trade() {
+                    report.pass("dry_run_order", "edge found, Kelly size zero");
+                } else {
+                    match order::prepare_order(
+                        &opportunity,
+                        kelly_result.kelly_raw,
+                        kelly_result.kelly_adjusted,
+                        &self.config.execution,
+                    ) {
+                        Ok(p) => report.pass(
+                            "dry_run_order",
+                            format!("{} {} @ {} (not submitted)", p.side, p.size, p.price),
+                        ),
+                        Err(e) => report.fail("dry_run_order", e.to_string()),
+                    }
+                }
+            }
+            None => report.pass("dry_run_order", "no edge — nothing to prepare"),
+        }
+
+        report
+    }
+
+    /// Send the canary report to the alert channel.
+    pub async fn alert_canary(&self, report: &CanaryReport) {
+        if let Err(e) = self.alert_client.canary_result(report).await {
+            warn!(error = %e, "Failed to send canary alert");
+        }
+    }
+
     fn has_valuation_engine(&self) -> bool {
         self.valuation_engine.is_some()
     }
 
+    /// Whether passive paper orders rest and are matched against the book.
+    fn paper_matching(&self) -> bool {
+        self.config.agent.mode == crate::config::AgentMode::Paper
+            && self.config.execution.paper_matching.enabled
+            && self.config.execution.partial_fill == order::PartialFillPolicy::Keep
+    }
+
     pub async fn run_cycle(&mut self) -> Result<()> {
+        let span = logger::cycle_span(self.cycle_number);
+        self.run_cycle_inner().instrument(span).await
+    }
+
+    async fn run_cycle_inner(&mut self) -> Result<()> {
+        let kill_switch = &self.config.agent.kill_switch;
+        if self.control.check_kill_file(kill_switch) {
+            warn!(
+                file = kill_switch.file.as_deref().unwrap_or(""),
+                flatten = kill_switch.flatten,
+                "Kill switch file found — halting trading"
+            );
+        }
+        if self.control.take_cancel_request() {
+            warn!(
+                cycle = self.cycle_number,
+                "Kill switch engaged — cancelling all orders"
+            );
+            self.cancel_all_orders().await;
+        }
+        if self.control.take_flatten_request() {
+            self.flatten_positions().await;
+        }
+        // Killed: keep settling and marking positions, but place no orders
+        let monitoring_only = self.control.is_killed();
+        if self.control.is_halted() && !monitoring_only {
+            info!(
+                cycle = self.cycle_number,
+                control = self.control.status(),
+                "Trading halted — skipping cycle"
+            );
+            return Ok(());
+        }
+
         let start = Instant::now();
+        let mut budget = CycleBudget::new(
+            &self.config.agent.cycle_budget,
+            self.config.agent.cycle_interval_seconds,
+            start,
+        );
         info!(cycle = self.cycle_number, state = %self.state, "Starting cycle");
 
         // 1. Enhanced survival check (Phase 7)
@@ -134,16 +477,45 @@ impl Agent {
             .await
             .unwrap_or(Decimal::ZERO);
         let next_cycle_cost = self_funding::estimate_next_cycle_cost(&self.store, 20).await;
+        // POL held for gas counts toward staying alive, not toward capital
+        let gas_usd = self.wallet.get().await.map_or(Decimal::ZERO, |w| w.pol_usd);
 
         self.state = enhanced_survival_check(
             balance,
-            unrealized,
+            unrealized + gas_usd,
             next_cycle_cost,
             self.config.agent.death_balance_threshold,
             self.config.agent.api_reserve,
             self.config.agent.low_fuel_threshold,
         );
 
+        // Daily API budget spent: the engine stops making new valuations
+        // (cached ones are still used) and the agent drops to reduced
+        // operations until UTC midnight
+        if let Some(engine) = &self.valuation_engine {
+            match engine.daily_spend_exhausted().await {
+                Ok(true) => {
+                    if self.state == AgentState::Alive {
+                        self.state = AgentState::LowFuel;
+                    }
+                    let today = chrono::Utc::now().date_naive();
+                    if self.spend_cap_alerted != Some(today) {
+                        self.spend_cap_alerted = Some(today);
+                        let budget = self.config.agent.daily_api_budget;
+                        if let Err(e) = self.alert_client.api_spend_cap(budget).await {
+                            warn!(error = %e, "Failed to send spend cap alert");
+                        }
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => warn!(error = %e, "Failed to check daily API cost"),
+            }
+        }
+
+        let funding_paused = self.check_funding(balance).await;
+        let drawdown_paused = self.check_drawdown().await;
+        let entries_paused = funding_paused || drawdown_paused;
+
         // Alert on state changes (Phase 8)
         if self.state != old_state {
             if let Err(e) = self
@@ -169,25 +541,65 @@ impl Agent {
         }
         self.last_balance = balance;
 
+        if self.config.agent.mode == crate::config::AgentMode::Paper {
+            match self.polymarket.paper_mark_to_market().await {
+                Ok(v) => info!(
+                    cash = %v.cash,
+                    position_value = %v.position_value,
+                    unrealized_pnl = %v.unrealized_pnl,
+                    realized_pnl = %v.realized_pnl,
+                    equity = %v.equity,
+                    "Paper account marked to market"
+                ),
+                Err(e) => warn!(error = %e, "Failed to mark paper account to market"),
+            }
+        }
+
         let mut markets_scanned: i64 = 0;
         let mut opportunities_found: i64 = 0;
         let mut trades_placed: i64 = 0;
         let mut cycle_api_cost = Decimal::ZERO;
+        let mut timings = PhaseTimings::default();
 
         // Re-evaluate open positions for exit signals (RISK-01).
         // Always run, even in Dead state — positions need cleanup (TRD-06).
-        self.evaluate_open_positions().await;
+        if !monitoring_only {
+            self.revalue_positions().await;
+            self.evaluate_open_positions().await;
+            self.merge_offsetting_positions().await;
+        }
+
+        // Confirm live trades against what the exchange actually filled
+        let settlement_start = Instant::now();
+        if self.config.agent.mode == crate::config::AgentMode::Live {
+            match fills::reconcile_fills(&self.store, &self.polymarket).await {
+                Ok(discrepancies) if !discrepancies.is_empty() => {
+                    warn!(
+                        count = discrepancies.len(),
+                        "Fill reconciliation found execution discrepancies"
+                    );
+                }
+                Err(e) => warn!(error = %e, "Fill reconciliation failed"),
+                _ => {}
+            }
+        }
+
+        // Resting paper orders fill as the real market trades through them
+        if self.paper_matching() {
+            match self.polymarket.match_paper_orders().await {
+                Ok(done) => {
+                    if let Err(e) = fills::reconcile_paper_fills(&self.store, &done).await {
+                        warn!(error = %e, "Paper fill reconciliation failed");
+                    }
+                }
+                Err(e) => warn!(error = %e, "Paper order matching failed"),
+            }
+        }
 
         // Check for resolved markets and settle trades.
         // Always run, even in Dead state — must settle P&L for final accounting (TRD-06).
         {
-            match resolution::check_and_settle(
-                &self.store,
-                self.polymarket.http_client(),
-                self.polymarket.gamma_base_url(),
-            )
-            .await
-            {
+            match resolution::settle_resolved(&self.store, self.exchange.as_ref()).await {
                 Ok(settled) if !settled.is_empty() => {
                     let pnl: Decimal = settled.iter().map(|r| r.pnl).sum();
                     info!(
@@ -195,87 +607,190 @@ impl Agent {
                         pnl = %pnl,
                         "Resolved trades this cycle"
                     );
+                    if self.config.agent.mode == crate::config::AgentMode::Paper {
+                        self.settle_paper_holdings(&settled).await;
+                    }
                 }
                 Err(e) => warn!(error = %e, "Resolution check failed"),
                 _ => {}
             }
         }
+        // Grade journaled trade ideas against the outcomes
+        if let Err(e) = resolution::grade_trade_ideas(&self.store, self.exchange.as_ref()).await {
+            warn!(error = %e, "Trade idea grading failed");
+        }
 
-        // Daily API budget check — skip valuations if we've exceeded the cap
-        let budget_available = match self.store.get_today_api_cost().await {
-            Ok(today_cost) => {
-                if today_cost >= self.config.agent.daily_api_budget {
-                    warn!(
-                        today_cost = %today_cost,
-                        budget = %self.config.agent.daily_api_budget,
-                        "Daily API budget exhausted — skipping valuations this cycle"
-                    );
-                    false
-                } else {
-                    true
-                }
+        // Turn settled live wins into USDC
+        if self.config.agent.mode == crate::config::AgentMode::Live
+            && self.config.redemption.enabled
+        {
+            if let Err(e) =
+                redemption::redeem_winnings(&self.store, &self.polymarket, self.cycle_number).await
+            {
+                warn!(error = %e, "Redemption of winning positions failed");
             }
-            Err(e) => {
-                warn!(error = %e, "Failed to check daily API cost — allowing valuations");
-                true
+        }
+        add_phase_time(&mut timings.settlement_ms, settlement_start.elapsed());
+
+        // Keep the exchange allowed to spend the wallet's USDC
+        if self.config.agent.mode == crate::config::AgentMode::Live {
+            match wallet::refresh(
+                &self.polymarket,
+                &self.config.wallet,
+                &self.store,
+                self.cycle_number,
+            )
+            .await
+            {
+                Ok(status) => self.wallet.set(status).await,
+                Err(e) => warn!(error = %e, "Wallet allowance check failed"),
             }
-        };
+        }
+
+        // Blacklist markets whose lifetime losses now exceed the limit
+        if let Some(max_loss) = self.config.risk.max_market_loss_usd {
+            match blacklist::refresh(&self.store, max_loss).await {
+                Ok(added) => {
+                    for market in added {
+                        warn!(
+                            market_id = %market.market_id,
+                            question = market.market_question.as_deref().unwrap_or(""),
+                            realized_loss = %market.realized_loss,
+                            max_loss = %max_loss,
+                            "Market blacklisted after exceeding lifetime loss limit"
+                        );
+                    }
+                }
+                Err(e) => warn!(error = %e, "Failed to refresh market blacklist"),
+            }
+        }
+
+        // Execute trades a human approved since the last cycle
+        if matches!(self.state, AgentState::Alive | AgentState::LowFuel)
+            && !monitoring_only
+            && !entries_paused
+        {
+            let approvals_start = Instant::now();
+            trades_placed += self.process_approvals().await as i64;
+            add_phase_time(&mut timings.execution_ms, approvals_start.elapsed());
+        }
 
         match self.state {
             AgentState::Dead => {
                 self.shutdown().await?;
                 return Ok(());
             }
+            _ if monitoring_only => {
+                warn!(
+                    cycle = self.cycle_number,
+                    "Kill switch engaged — monitoring only"
+                );
+            }
             AgentState::CriticalSurvival => {
                 warn!(
                     cycle = self.cycle_number,
                     "Critical survival mode — monitoring only"
                 );
             }
+            _ if funding_paused => {
+                warn!(
+                    cycle = self.cycle_number,
+                    "Funding critical — new entries paused"
+                );
+            }
+            _ if drawdown_paused => {
+                warn!(
+                    cycle = self.cycle_number,
+                    "Drawdown cooldown — new entries paused"
+                );
+            }
             AgentState::LowFuel => {
                 warn!(
                     cycle = self.cycle_number,
                     "Low fuel mode — reduced operations"
                 );
-                match self.scanner.scan().await {
-                    Ok(candidates) => {
+                let scan_start = Instant::now();
+                let scanned = budget
+                    .within(CyclePhase::Scan, self.scanner.scan_due())
+                    .await;
+                add_phase_time(&mut timings.scan_ms, scan_start.elapsed());
+                match scanned {
+                    Some(Ok(candidates)) => {
                         markets_scanned = candidates.len() as i64;
-                        if self.has_valuation_engine() && budget_available {
+                        if self.has_valuation_engine() {
                             let bankroll = self.effective_bankroll().await;
-                            let result = self.evaluate_and_trade(&candidates, bankroll, 1).await;
+                            let result = self
+                                .evaluate_and_trade(&candidates, bankroll, 1, &mut budget)
+                                .await;
+                            timings.absorb(result.timings);
                             opportunities_found = result.opportunities as i64;
-                            trades_placed = result.trades as i64;
+                            trades_placed += result.trades as i64;
                             cycle_api_cost = result.api_cost;
                         }
                     }
-                    Err(e) => {
+                    Some(Err(e)) => {
                         warn!(error = %e, "Market scan failed");
                     }
+                    // Scan overran its deadline; nothing left to value
+                    None => {}
                 }
             }
             AgentState::Alive => {
                 info!(cycle = self.cycle_number, "Normal operation");
-                match self.scanner.scan().await {
-                    Ok(candidates) => {
+                let scan_start = Instant::now();
+                let scanned = budget
+                    .within(CyclePhase::Scan, self.scanner.scan_due())
+                    .await;
+                add_phase_time(&mut timings.scan_ms, scan_start.elapsed());
+                match scanned {
+                    Some(Ok(candidates)) => {
                         markets_scanned = candidates.len() as i64;
                         info!(
                             candidates = candidates.len(),
                             "Scan complete — candidates found"
                         );
 
-                        if self.has_valuation_engine() && budget_available {
+                        if self.has_valuation_engine() {
                             let bankroll = self.effective_bankroll().await;
-                            let result = self.evaluate_and_trade(&candidates, bankroll, 10).await;
+                            let result = self
+                                .evaluate_and_trade(&candidates, bankroll, 10, &mut budget)
+                                .await;
+                            timings.absorb(result.timings);
                             opportunities_found = result.opportunities as i64;
-                            trades_placed = result.trades as i64;
+                            trades_placed += result.trades as i64;
                             cycle_api_cost = result.api_cost;
+                            if self.config.sniping.enabled {
+                                if budget.is_over(CyclePhase::Execution) {
+                                    budget.truncate(CyclePhase::Execution);
+                                } else {
+                                    let sniping_start = Instant::now();
+                                    trades_placed +=
+                                        self.snipe_near_resolution(&candidates, &result.data).await
+                                            as i64;
+                                    add_phase_time(
+                                        &mut timings.execution_ms,
+                                        sniping_start.elapsed(),
+                                    );
+                                }
+                            }
                         } else {
                             opportunities_found = candidates.len() as i64;
                         }
                     }
-                    Err(e) => {
+                    Some(Err(e)) => {
                         warn!(error = %e, "Market scan failed");
                     }
+                    None => {}
+                }
+                // Quoting is the first work dropped when execution runs late
+                if self.config.market_making.enabled {
+                    if budget.is_over(CyclePhase::Execution) {
+                        budget.truncate(CyclePhase::Execution);
+                    } else {
+                        let quoting_start = Instant::now();
+                        trades_placed += self.make_markets().await as i64;
+                        add_phase_time(&mut timings.execution_ms, quoting_start.elapsed());
+                    }
                 }
             }
         }
@@ -286,19 +801,43 @@ impl Agent {
             .get_total_api_cost()
             .await
             .unwrap_or(Decimal::ZERO);
-        let costs = CycleCosts::new(cycle_api_cost);
+        let cycle_gas_cost = self
+            .store
+            .get_gas_cost_for_cycle(self.cycle_number as i64)
+            .await
+            .unwrap_or(Decimal::ZERO);
+        let costs = CycleCosts::new(cycle_api_cost, cycle_gas_cost);
         log_cost_breakdown(self.cycle_number, &costs, cumulative_api_cost);
 
         // Log cycle results
         let duration = start.elapsed();
-        self.log_cycle(
+        self.log_cycle(CycleSummary {
             duration,
             markets_scanned,
             opportunities_found,
             trades_placed,
-            cycle_api_cost,
-        )
+            api_cost: cycle_api_cost,
+            truncated_phases: budget.truncated_label(),
+            timings,
+        })
         .await?;
+        if let Err(e) = journal::valuations_done(&self.store, self.cycle_number).await {
+            warn!(error = %e, "Failed to complete journaled valuations");
+        }
+
+        self.check_anomalies().await;
+        self.send_weekly_report_if_due().await;
+
+        for bucket in self.portfolio.bucket_statuses(balance) {
+            info!(
+                bucket = %bucket.name,
+                balance = %bucket.balance,
+                exposure = %bucket.exposure,
+                exposure_cap = %bucket.exposure_cap,
+                available = %bucket.available,
+                "Bucket status"
+            );
+        }
 
         // Phase 8: Periodic metrics summary (every 10 cycles)
         if self.cycle_number > 0 && self.cycle_number % 10 == 0 {
@@ -319,12 +858,14 @@ impl Agent {
     }
 
     /// Full pipeline: evaluate candidates → size with Kelly → check constraints → execute.
-    /// Uses parallel evaluation with JoinSet for higher throughput.
+    /// Uses parallel evaluation with JoinSet for higher throughput. Each
+    /// phase stops at its `budget` deadline.
     async fn evaluate_and_trade(
         &mut self,
         candidates: &[MarketCandidate],
         bankroll: Decimal,
         max_evaluations: usize,
+        budget: &mut CycleBudget,
     ) -> CycleResult {
         let engine = self.valuation_engine.as_ref().unwrap();
         let mut result = CycleResult::default();
@@ -340,8 +881,33 @@ impl Agent {
             .collect();
 
         // Phase 3: Fetch external data for all candidates
-        let all_data = self.data_aggregator.fetch_all(&queries).await;
+        let data_start = Instant::now();
+        let fetched = budget
+            .within(
+                CyclePhase::DataFetch,
+                self.data_aggregator.fetch_all(&queries),
+            )
+            .await;
+        add_phase_time(&mut result.timings.data_ms, data_start.elapsed());
+        let Some(all_data) = fetched else {
+            // Valuing without the evidence would be worse than not valuing
+            budget.truncate(CyclePhase::Valuation);
+            return result;
+        };
+        let data_fetched_at = chrono::Utc::now();
+        let valuation_start = Instant::now();
         info!(data_points = all_data.len(), "External data collected");
+        result.data = all_data.clone();
+
+        // Embed every data point once, up front, so parallel valuations share the cache
+        if let Some(scorer) = engine.relevance_scorer() {
+            if let Err(e) = scorer
+                .prime(&all_data, Some(self.cycle_number as i64))
+                .await
+            {
+                warn!(error = %e, "Failed to embed data points — relevance falls back to keywords");
+            }
+        }
 
         // Phase 4+5+6: Evaluate → Size → Execute
         // Parallel evaluation with JoinSet for higher throughput
@@ -350,7 +916,32 @@ impl Agent {
         let config_valuation = self.config.valuation.clone();
 
         // Spawn parallel valuation tasks
-        for candidate in candidates.iter().take(max_evaluations) {
+        // Blacklisted markets are skipped before spending on a valuation
+        let blacklisted = blacklist::blacklisted_ids(&self.store)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to load market blacklist");
+                Default::default()
+            });
+
+        // With strategies configured, markets no strategy trades aren't worth valuing.
+        // New listings get their own quota on top of max_evaluations.
+        let listing_quota = match self.state {
+            AgentState::Alive => self.config.scanning.new_listings.evaluation_quota,
+            _ => 0,
+        };
+        let selected = listings::select_for_evaluation(
+            candidates
+                .iter()
+                .filter(|c| !blacklisted.contains(&c.market.condition_id))
+                .filter(|c| {
+                    !self.strategies.is_enabled()
+                        || self.strategies.route(&c.market.category).is_some()
+                }),
+            max_evaluations,
+            listing_quota,
+        );
+        for (index, candidate) in selected.into_iter().enumerate() {
             let estimated_cost = engine.estimated_call_cost();
             if estimated_cost > bankroll - result.api_cost {
                 warn!(
@@ -362,55 +953,152 @@ impl Agent {
             }
 
             let candidate = candidate.clone();
-            let relevant_data: Vec<DataPoint> = all_data
-                .iter()
-                .filter(|dp| dp.relevance_to.contains(&candidate.market.condition_id))
-                .cloned()
-                .collect();
+            let relevant_data = relevant_data(engine, &all_data, &candidate);
             let engine = engine_arc.clone();
             let config = config_valuation.clone();
+            let fees = self.exchange.fees();
+            let writer = self.writer.clone();
             let cycle_num = self.cycle_number as i64;
             let remaining_budget = bankroll - result.api_cost;
 
-            join_set.spawn(async move {
-                // Phase 4: Get valuation from Claude
-                let valuation = match engine
-                    .evaluate(&candidate, &relevant_data, remaining_budget, cycle_num)
-                    .await
-                {
-                    Ok(Some(v)) => v,
-                    Ok(None) => return None,
-                    Err(_) => return None,
-                };
+            join_set.spawn(
+                async move {
+                    // Phase 4: Get valuation from Claude
+                    let valuation = match engine
+                        .evaluate(&candidate, &relevant_data, remaining_budget, cycle_num)
+                        .await
+                    {
+                        Ok(Some(v)) => v,
+                        Ok(None) => return None,
+                        Err(_) => return None,
+                    };
 
-                let edge = match evaluate_edge(&candidate, &valuation, &config) {
-                    Some(e) => e,
-                    None => return None,
-                };
+                    let valued_at = chrono::Utc::now();
+
+                    let edge = evaluate_edge(&candidate, &valuation, &config, &fees);
+                    let record = MarketEvaluationRecord {
+                        id: None,
+                        condition_id: candidate.market.condition_id.clone(),
+                        cycle: cycle_num,
+                        fair_value: valuation.probability.to_string(),
+                        market_price: candidate.order_book.midpoint.to_string(),
+                        net_edge: edge.as_ref().map(|e| e.net_edge.to_string()),
+                        edge_found: edge.is_some(),
+                        evaluated_at: None,
+                    };
+                    if let Err(e) = writer.insert_market_evaluation(&record).await {
+                        warn!(error = %e, "Failed to record market evaluation");
+                    }
 
-                Some((candidate, valuation, edge))
-            });
+                    Some((index, candidate, valuation, edge, valued_at))
+                }
+                // Keep the cycle's correlation id on the worker's events
+                .instrument(tracing::Span::current()),
+            );
         }
 
-        // Collect results from parallel tasks
+        // Collect results from parallel tasks until the valuation deadline
         let mut eval_results = Vec::new();
-        while let Some(result_opt) = join_set.join_next().await {
-            if let Ok(Some((candidate, valuation, edge))) = result_opt {
-                eval_results.push((candidate, valuation, edge));
+        loop {
+            match budget
+                .within(CyclePhase::Valuation, join_set.join_next())
+                .await
+            {
+                Some(Some(Ok(Some(evaluated)))) => eval_results.push(evaluated),
+                Some(Some(_)) => {}
+                Some(None) => break,
+                None => {
+                    warn!(
+                        abandoned = join_set.len(),
+                        "Valuation deadline reached — abandoning pending evaluations"
+                    );
+                    join_set.abort_all();
+                    break;
+                }
             }
         }
+        // Trade in scan order, not completion order, so runs are reproducible
+        eval_results.sort_by_key(|(index, ..)| *index);
+        let (eval_results, requery_cost) = self
+            .enforce_consistency(
+                engine,
+                eval_results,
+                &all_data,
+                bankroll - result.api_cost,
+                budget,
+            )
+            .await;
+        result.api_cost += requery_cost;
 
         info!(
             evaluations = eval_results.len(),
             "Parallel evaluations complete"
         );
+        add_phase_time(&mut result.timings.valuation_ms, valuation_start.elapsed());
+        let execution_start = Instant::now();
+
+        // Realized P&L per strategy, for their isolated bankrolls
+        let realized = match self.realized_by_strategy().await {
+            Ok(realized) => realized,
+            Err(e) => {
+                warn!(error = %e, "Failed to load strategy P&L — skipping trades this cycle");
+                return result;
+            }
+        };
 
         // Process results sequentially for trade execution
-        for (candidate, valuation, edge) in eval_results {
-            let estimated_cost = engine.estimated_call_cost();
+        let estimated_cost = engine.estimated_call_cost();
+        let mut rebalances = 0;
+        for (_, candidate, valuation, edge, valued_at) in eval_results {
+            let idea = TradeIdea::new(self.cycle_number, &candidate, &valuation, edge.as_ref());
+            let Some(edge) = edge else {
+                self.journal_skip(&idea, SkipReason::NoEdge).await;
+                continue;
+            };
             result.api_cost += estimated_cost;
             result.opportunities += 1;
             self.log_opportunity(&candidate, &valuation, &edge);
+            if let Err(e) = journal::record_valuation(
+                &self.store,
+                self.cycle_number,
+                &candidate.market.condition_id,
+                valuation.probability,
+                valuation.confidence,
+                edge.side,
+                edge.net_edge,
+            )
+            .await
+            {
+                warn!(error = %e, "Failed to journal valuation");
+            }
+            // Past the execution deadline, finish the accounting but place nothing
+            if budget.is_over(CyclePhase::Execution) {
+                budget.truncate(CyclePhase::Execution);
+                self.journal_skip(&idea, SkipReason::ExecutionDeadline)
+                    .await;
+                continue;
+            }
+
+            // Event risk calendar: demand more edge around scheduled events
+            let event_adjustment = events::adjustment_for(
+                &self.config.risk.event_windows,
+                &candidate.market.category,
+                self.store.clock().now(),
+            );
+            if event_adjustment.is_active() {
+                let event_threshold = edge.threshold * event_adjustment.edge_multiplier;
+                if edge.net_edge < event_threshold {
+                    info!(
+                        market = %candidate.market.question,
+                        events = ?event_adjustment.active_events,
+                        edge = %edge.raw_edge,
+                        threshold = %event_threshold,
+                        "Edge below event-window threshold — skipping"
+                    );
+                    self.journal_skip(&idea, SkipReason::EventWindow).await;
+                    continue;
+                }
+            }
 
             // Apply calibration discount to confidence (HAL-01)
             let calibrated_confidence = match crate::valuation::calibration::compute_discount(
@@ -437,15 +1125,58 @@ impl Agent {
                 }
             };
 
-            // Phase 5: Kelly sizing with calibrated confidence
-            let kelly_result = kelly::kelly_size(
+            // Phase 5: Kelly sizing with calibrated confidence, against the
+            // owning strategy's bankroll and risk settings
+            let strategy = self.strategies.route(&candidate.market.category).cloned();
+            let (mut risk, bucket_bankroll) = match &strategy {
+                Some(s) => (
+                    s.risk_config(&self.config.risk),
+                    self.strategies
+                        .bankroll(s, bankroll - result.api_cost, &realized),
+                ),
+                None => (
+                    self.config.risk.clone(),
+                    self.portfolio
+                        .bucket_balance(DIRECTIONAL_BUCKET, bankroll - result.api_cost),
+                ),
+            };
+            // Event windows lower the position cap; the Kelly bet itself is
+            // only cut when it exceeds the lowered cap
+            if event_adjustment.is_active() {
+                risk.max_position_pct *= event_adjustment.size_multiplier;
+            }
+            let mut kelly_result = sizing::position_size(
                 valuation.probability,
                 edge.trade_price,
                 calibrated_confidence,
-                bankroll - result.api_cost,
+                bucket_bankroll,
                 self.state,
-                &self.config.risk,
+                &risk,
+            );
+            let hours_to_resolution =
+                Decimal::from((candidate.market.end_date - self.store.clock().now()).num_minutes())
+                    / dec!(60);
+            kelly::apply_resolution_decay(
+                &mut kelly_result,
+                hours_to_resolution,
+                bucket_bankroll,
+                &risk,
             );
+            if let Some(score) = candidate.liquidity_score {
+                let factor = liquidity::size_factor(score, &self.config.scanning.liquidity);
+                if factor < Decimal::ONE {
+                    debug!(
+                        market = %candidate.market.condition_id,
+                        liquidity_score = %score,
+                        %factor,
+                        "Position scaled down for liquidity history"
+                    );
+                    kelly_result.position_usd *= factor;
+                    if kelly_result.position_usd < risk.min_position_usd {
+                        kelly_result.position_usd = Decimal::ZERO;
+                    }
+                }
+            }
 
             if !kelly_result.should_trade() {
                 info!(
@@ -453,11 +1184,12 @@ impl Agent {
                     kelly_raw = %kelly_result.kelly_raw,
                     "Kelly says no trade"
                 );
+                self.journal_skip(&idea, SkipReason::KellyNoTrade).await;
                 continue;
             }
 
             // Phase 7: Check if projected profit justifies the API cost
-            if !edge_justifies_cost(kelly_result.position_usd, edge.raw_edge, estimated_cost) {
+            if !edge_justifies_cost(kelly_result.position_usd, edge.net_edge, estimated_cost) {
                 info!(
                     market = %candidate.market.question,
                     position_usd = %kelly_result.position_usd,
@@ -465,6 +1197,7 @@ impl Agent {
                     api_cost = %estimated_cost,
                     "Edge doesn't justify API cost — skipping"
                 );
+                self.journal_skip(&idea, SkipReason::CostNotJustified).await;
                 continue;
             }
 
@@ -472,6 +1205,22 @@ impl Agent {
             let opportunity =
                 to_opportunity(&candidate, &valuation, &edge, kelly_result.position_usd);
 
+            // Exposure maxed out: make room by exiting the weakest position
+            // when this edge is much better
+            if self.config.risk.rebalance.enabled
+                && rebalances < self.config.risk.rebalance.max_per_cycle
+                && self
+                    .rebalance_for(
+                        &candidate,
+                        edge.net_edge,
+                        kelly_result.position_usd,
+                        bankroll,
+                    )
+                    .await
+            {
+                rebalances += 1;
+            }
+
             // Portfolio constraint check
             let constraint_check = self.portfolio.check_constraints(&opportunity, bankroll);
             if !constraint_check.passed() {
@@ -479,14 +1228,31 @@ impl Agent {
                     market = %candidate.market.question,
                     "Portfolio constraint check failed"
                 );
+                self.journal_skip(&idea, SkipReason::PortfolioConstraint)
+                    .await;
                 continue;
             }
 
             // Adjust size for remaining portfolio capacity
-            let adjusted_size = self
-                .portfolio
-                .adjust_size(kelly_result.position_usd, bankroll);
+            let adjusted_size = self.portfolio.adjust_size(
+                kelly_result.position_usd,
+                bankroll,
+                &candidate.market.category,
+            );
+            let adjusted_size = match &strategy {
+                Some(s) => adjusted_size.min(self.strategies.available(
+                    s,
+                    bucket_bankroll,
+                    self.portfolio.bucket_exposure(&s.name),
+                )),
+                None => self.portfolio.adjust_size_for_bucket(
+                    DIRECTIONAL_BUCKET,
+                    adjusted_size,
+                    bankroll,
+                ),
+            };
             if adjusted_size <= Decimal::ZERO {
+                self.journal_skip(&idea, SkipReason::NoCapacity).await;
                 continue;
             }
 
@@ -505,12 +1271,14 @@ impl Agent {
                 depth,
                 self.config.execution.max_slippage_pct,
             );
-            if liquidity_size < self.config.risk.min_position_usd {
+            if liquidity_size < risk.min_position_usd {
                 info!(
                     market = %candidate.market.question,
                     liquidity_size = %liquidity_size,
                     "Insufficient liquidity"
                 );
+                self.journal_skip(&idea, SkipReason::InsufficientLiquidity)
+                    .await;
                 continue;
             }
 
@@ -525,267 +1293,1709 @@ impl Agent {
                 kelly_result.kelly_adjusted,
                 &self.config.execution,
             ) {
-                Ok(p) => p,
+                Ok(mut p) => {
+                    if let Some(s) = &strategy {
+                        p.bucket = s.name.clone();
+                    }
+                    p
+                }
                 Err(e) => {
                     warn!(market = %candidate.market.question, error = %e, "Order preparation failed");
+                    self.journal_skip(&idea, SkipReason::OrderPreparation).await;
                     continue;
                 }
             };
 
-            info!(
-                market = %prepared.market_question,
-                side = %prepared.side,
-                price = %prepared.price,
-                size = %prepared.size,
-                kelly_raw = %kelly_result.kelly_raw,
-                kelly_adjusted = %kelly_result.kelly_adjusted,
-                edge = %edge.raw_edge,
+            // Hard cap on what one trade can lose, independent of Kelly sizing
+            if let Some((worst_case, cap)) = self.trade_loss_over_cap(&prepared, &risk) {
+                warn!(
+                    market = %candidate.market.question,
+                    worst_case = %worst_case,
+                    cap = %cap,
+                    "Worst-case loss exceeds per-trade cap — rejecting"
+                );
+                self.journal_skip(&idea, SkipReason::MaxTradeLoss).await;
+                continue;
+            }
+
+            if self.signal_approval_required()
+                || approval::requires_approval(&self.config.risk.approval, liquidity_size)
+            {
+                self.request_approval(&prepared, &candidate.market.category, liquidity_size)
+                    .await;
+                self.journal_skip(&idea, SkipReason::AwaitingApproval).await;
+                continue;
+            }
+
+            let style = routing::choose_style(
+                &self.config.execution.routing,
+                edge.net_edge,
+                hours_to_resolution,
+            );
+            let mut prepared = prepared;
+            if style == ExecutionStyle::Passive {
+                match routing::join_bid_price(&candidate.order_book, prepared.side) {
+                    Some(bid) => prepared.price = bid.min(prepared.price),
+                    None => {
+                        info!(market = %candidate.market.question, "No bid to join — skipping");
+                        self.journal_skip(&idea, SkipReason::NoBid).await;
+                        continue;
+                    }
+                }
+            }
+
+            info!(
+                market = %prepared.market_question,
+                side = %prepared.side,
+                price = %prepared.price,
+                size = %prepared.size,
+                kelly_raw = %kelly_result.kelly_raw,
+                kelly_adjusted = %kelly_result.kelly_adjusted,
+                edge = %edge.raw_edge,
+                style = style.as_str(),
                 "Executing trade"
             );
 
-            let execution = order::execute_order(&self.polymarket, &prepared).await;
+            self.publish_signal(Signal::opportunity(
+                &prepared,
+                &candidate.market.category,
+                liquidity_size,
+                None,
+            ))
+            .await;
+
+            let timeline =
+                TradeTimeline::new(candidate.order_book.timestamp, data_fetched_at, valued_at);
+            let execution = self
+                .execute_and_track(
+                    &prepared,
+                    candidate.market.category.clone(),
+                    liquidity_size,
+                    style,
+                    Some(depth),
+                    Some(timeline),
+                )
+                .await;
+            let decision = match &execution {
+                Some(execution) if execution.status.has_fill() => IdeaDecision::Traded {
+                    size_usd: liquidity_size,
+                },
+                _ => IdeaDecision::Skipped(SkipReason::NotFilled),
+            };
+            self.journal_idea(&idea, decision).await;
+            if let Some(execution) = execution {
+                if execution.status.has_fill() {
+                    result.trades += 1;
+                }
+                self.publish_signal(Signal::executed(
+                    &prepared,
+                    &candidate.market.category,
+                    liquidity_size,
+                    &execution,
+                ))
+                .await;
+            }
+        }
+        add_phase_time(&mut result.timings.execution_ms, execution_start.elapsed());
 
-            // Record trade in database
-            if let Err(e) =
-                fills::record_trade(&self.store, &prepared, &execution, self.cycle_number).await
+        result
+    }
+
+    async fn journal_idea(&self, idea: &TradeIdea, decision: IdeaDecision) {
+        if let Err(e) = ideas::record(&self.store, idea, decision).await {
+            warn!(error = %e, "Failed to journal trade idea");
+        }
+    }
+
+    async fn journal_skip(&self, idea: &TradeIdea, reason: SkipReason) {
+        self.journal_idea(idea, IdeaDecision::Skipped(reason)).await;
+    }
+
+    /// Check the cycle's valuations against each other (see
+    /// [`consistency`]). Violating markets are re-valued once when
+    /// `requery` is set; those still inconsistent are journaled as skipped,
+    /// dropped from the cache and not traded. Returns the remaining
+    /// valuations and the cost of the re-valuations.
+    async fn enforce_consistency(
+        &self,
+        engine: &ValuationEngine,
+        mut evaluated: Vec<Evaluated>,
+        all_data: &[DataPoint],
+        bankroll: Decimal,
+        budget: &mut CycleBudget,
+    ) -> (Vec<Evaluated>, Decimal) {
+        let config = &self.config.valuation.consistency;
+        let mut cost = Decimal::ZERO;
+        if !config.enabled {
+            return (evaluated, cost);
+        }
+        let mut violating = consistency_violations(&evaluated, config.tolerance);
+        if violating.is_empty() {
+            return (evaluated, cost);
+        }
+
+        if config.requery {
+            let fees = self.exchange.fees();
+            for (_, candidate, valuation, edge, valued_at) in evaluated
+                .iter_mut()
+                .filter(|(_, c, ..)| violating.contains(&c.market.condition_id))
+            {
+                let condition_id = &candidate.market.condition_id;
+                if let Err(e) = engine.invalidate_cached_valuation(condition_id).await {
+                    warn!(error = %e, market = %condition_id, "Failed to drop cached valuation");
+                    continue;
+                }
+                let data = relevant_data(engine, all_data, candidate);
+                let requery =
+                    engine.evaluate(candidate, &data, bankroll - cost, self.cycle_number as i64);
+                match budget.within(CyclePhase::Valuation, requery).await {
+                    Some(Ok(Some(fresh))) => {
+                        cost += engine.estimated_call_cost();
+                        info!(
+                            market = %candidate.market.question,
+                            before = %valuation.probability,
+                            after = %fresh.probability,
+                            "Inconsistent valuation re-queried"
+                        );
+                        *edge = evaluate_edge(candidate, &fresh, &self.config.valuation, &fees);
+                        *valuation = fresh;
+                        *valued_at = chrono::Utc::now();
+                    }
+                    Some(Ok(None)) => {}
+                    Some(Err(e)) => {
+                        warn!(error = %e, market = %condition_id, "Re-query failed");
+                    }
+                    None => break,
+                }
+            }
+            violating = consistency_violations(&evaluated, config.tolerance);
+        }
+
+        let (rejected, kept): (Vec<Evaluated>, Vec<Evaluated>) = evaluated
+            .into_iter()
+            .partition(|(_, c, ..)| violating.contains(&c.market.condition_id));
+        for (_, candidate, valuation, edge, _) in &rejected {
+            warn!(
+                market = %candidate.market.question,
+                probability = %valuation.probability,
+                "Valuation inconsistent with related markets — not trading"
+            );
+            if let Err(e) = engine
+                .invalidate_cached_valuation(&candidate.market.condition_id)
+                .await
             {
-                warn!(error = %e, "Failed to record trade");
+                warn!(error = %e, "Failed to drop cached valuation");
             }
+            let idea = TradeIdea::new(self.cycle_number, candidate, valuation, edge.as_ref());
+            self.journal_skip(&idea, SkipReason::Inconsistent).await;
+        }
+        (kept, cost)
+    }
 
-            if execution.status == OrderStatus::Filled {
-                result.trades += 1;
+    /// Execute an order, record it, and on fill update calibration, the
+    /// portfolio tracker and alerts. Returns the execution, or `None` if the
+    /// order couldn't be journaled and so wasn't submitted.
+    ///
+    /// `style` picks how the order is worked: pegged orders reprice at the
+    /// midpoint, passive orders arrive already priced at the bid, and
+    /// aggressive orders are TWAP-sliced against `top_depth`, the best
+    /// level's size, when given. `timeline` carries pipeline timestamps for
+    /// the latency report; orders executed outside the scan pipeline (e.g.
+    /// after approval) have neither.
+    async fn execute_and_track(
+        &mut self,
+        prepared: &PreparedOrder,
+        category: MarketCategory,
+        size_usd: Decimal,
+        style: ExecutionStyle,
+        top_depth: Option<Decimal>,
+        timeline: Option<TradeTimeline>,
+    ) -> Option<order::ExecutionResult> {
+        let span = logger::trade_span(&prepared.market_id);
+        self.execute_and_track_inner(prepared, category, size_usd, style, top_depth, timeline)
+            .instrument(span)
+            .await
+    }
 
-                // Record prediction for confidence calibration (HAL-01)
-                if let Err(e) = calibration::record_prediction(
-                    self.store.pool(),
-                    &prepared.market_id,
-                    valuation.confidence,
-                    valuation.probability,
-                    prepared.price,
+    async fn execute_and_track_inner(
+        &mut self,
+        prepared: &PreparedOrder,
+        category: MarketCategory,
+        size_usd: Decimal,
+        style: ExecutionStyle,
+        top_depth: Option<Decimal>,
+        mut timeline: Option<TradeTimeline>,
+    ) -> Option<order::ExecutionResult> {
+        // Write-ahead: an order we can't journal is an order we can't recover
+        let journal_id = match journal::begin_order(&self.store, self.cycle_number, prepared).await
+        {
+            Ok(id) => id,
+            Err(e) => {
+                error!(market = %prepared.market_id, error = %e, "Failed to journal order — not submitting");
+                return None;
+            }
+        };
+        if let Some(t) = timeline.as_mut() {
+            t.submitted_at = Some(chrono::Utc::now());
+        }
+        let execution = match (style, top_depth) {
+            (ExecutionStyle::MidpointPeg, _) => {
+                routing::execute_pegged(
+                    self.exchange.as_ref(),
+                    prepared,
+                    &self.config.execution.routing,
                 )
                 .await
-                {
-                    warn!(error = %e, "Failed to record calibration prediction");
-                }
+            }
+            (ExecutionStyle::Aggressive, Some(depth)) => {
+                twap::execute_sliced(
+                    self.exchange.as_ref(),
+                    prepared,
+                    depth,
+                    &self.config.execution.twap,
+                )
+                .await
+                .0
+            }
+            _ => order::execute_order(self.exchange.as_ref(), prepared).await,
+        };
+        if let Some(t) = timeline.as_mut() {
+            t.filled_at = Some(chrono::Utc::now());
+        }
+        if let Err(e) = journal::order_submitted(&self.store, journal_id, &execution.order_id).await
+        {
+            warn!(error = %e, "Failed to journal order submission");
+        }
 
-                // Update portfolio tracker
-                self.portfolio.add_position(Position {
-                    market_id: prepared.market_id.clone(),
-                    token_id: prepared.token_id.clone(),
-                    category: candidate.market.category.clone(),
-                    side: prepared.side,
-                    size_usd: liquidity_size,
-                    entry_price: prepared.price,
-                });
+        let filled = if execution.status.has_fill() {
+            execution.size
+        } else {
+            Decimal::ZERO
+        };
+        if let Err(e) = self
+            .store
+            .record_execution_style(style.as_str(), prepared.size, filled)
+            .await
+        {
+            warn!(error = %e, "Failed to record execution style stats");
+        }
 
-                // Phase 8: Send trade alert
-                if let Err(e) = self
-                    .alert_client
-                    .trade_placed(
-                        &prepared.market_question,
-                        prepared.side,
-                        liquidity_size,
-                        prepared.price,
-                        edge.raw_edge,
-                    )
+        // Live limit orders may keep working; a partial fill's remainder only
+        // does if the policy keeps it. Paper orders only work if they rested.
+        let resting = if self.config.agent.mode == crate::config::AgentMode::Live {
+            execution.status == OrderStatus::Filled
+                || self.config.execution.partial_fill == order::PartialFillPolicy::Keep
+        } else {
+            self.paper_matching()
+                && self
+                    .polymarket
+                    .paper_order_resting(&execution.order_id)
                     .await
-                {
-                    warn!(error = %e, "Failed to send trade alert");
+        };
+        if let Err(e) = fills::record_order(&self.store, prepared, &execution, resting).await {
+            warn!(error = %e, "Failed to record order");
+        }
+
+        // Record trade in database
+        match fills::record_trade(&self.store, prepared, &execution, self.cycle_number).await {
+            Ok(Some(trade_id)) => {
+                logger::record_trade_id(trade_id);
+                // The next entry on this market needs a fresh valuation
+                if let Err(e) = self.store.consume_valuation(&prepared.market_id).await {
+                    warn!(error = %e, "Failed to mark valuation consumed");
+                }
+                if let Some(record) = timeline.and_then(|t| t.to_record(trade_id)) {
+                    if let Err(e) = self.store.insert_trade_latency(&record).await {
+                        warn!(error = %e, "Failed to record trade latency");
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "Failed to record trade"),
+        }
+        if let Err(e) = journal::order_done(&self.store, journal_id).await {
+            warn!(error = %e, "Failed to complete journaled order");
+        }
+
+        if !execution.status.has_fill() {
+            return Some(execution);
+        }
+        // A sliced order may fill only partly
+        let size_usd = size_usd.min(execution.price * execution.size);
+
+        // Record prediction for confidence calibration (HAL-01)
+        if let Err(e) = calibration::record_prediction(
+            self.store.pool(),
+            &prepared.market_id,
+            prepared.confidence,
+            prepared.fair_value,
+            prepared.price,
+        )
+        .await
+        {
+            warn!(error = %e, "Failed to record calibration prediction");
+        }
+
+        // Update portfolio tracker
+        self.portfolio.add_position(Position {
+            market_id: prepared.market_id.clone(),
+            token_id: prepared.token_id.clone(),
+            category,
+            side: prepared.side,
+            size_usd,
+            entry_price: prepared.price,
+            bucket: prepared.bucket.clone(),
+        });
+
+        // Phase 8: Send trade alert
+        if let Err(e) = self
+            .alert_client
+            .trade_placed(
+                &prepared.market_question,
+                prepared.side,
+                size_usd,
+                prepared.price,
+                prepared.edge,
+            )
+            .await
+        {
+            warn!(error = %e, "Failed to send trade alert");
+        }
+
+        info!(
+            market = %prepared.market_question,
+            side = %prepared.side,
+            size_usd = %size_usd,
+            total_exposure = %self.portfolio.total_exposure(),
+            positions = self.portfolio.position_count(),
+            "Position added to portfolio"
+        );
+
+        Some(execution)
+    }
+
+    /// Park a large order for human approval and alert with where to decide it.
+    async fn request_approval(
+        &self,
+        prepared: &PreparedOrder,
+        category: &MarketCategory,
+        size_usd: Decimal,
+    ) {
+        match self.store.has_open_approval(&prepared.market_id).await {
+            Ok(true) => {
+                info!(market = %prepared.market_question, "Trade already awaiting approval");
+                return;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                warn!(error = %e, "Failed to check open approvals — not parking trade");
+                return;
+            }
+        }
+
+        let config = &self.config.risk.approval;
+        let id = match approval::park(
+            &self.store,
+            prepared,
+            category,
+            size_usd,
+            self.cycle_number,
+            config,
+            self.store.clock().now(),
+        )
+        .await
+        {
+            Ok(id) => id,
+            Err(e) => {
+                warn!(error = %e, "Failed to park trade for approval");
+                return;
+            }
+        };
+
+        info!(
+            id,
+            market = %prepared.market_question,
+            size_usd = %size_usd,
+            threshold = %config.threshold_usd,
+            "Trade parked pending approval"
+        );
+
+        let (approve_url, reject_url) =
+            approval::links(&self.config.monitoring.dashboard_url(), id);
+        self.publish_signal(Signal::opportunity(
+            prepared,
+            category,
+            size_usd,
+            Some(SignalApproval {
+                id,
+                approve_url,
+                reject_url,
+                expires_at: self.store.clock().now()
+                    + chrono::Duration::seconds(config.ttl_seconds as i64),
+            }),
+        ))
+        .await;
+        let parked = ApprovedOrder {
+            id,
+            order: prepared.clone(),
+            category: category.clone(),
+            size_usd,
+        };
+        if let Err(e) = self
+            .alert_client
+            .approval_requested(
+                &parked,
+                &self.config.monitoring.dashboard_url(),
+                config.ttl_seconds,
+            )
+            .await
+        {
+            warn!(error = %e, "Failed to send approval alert");
+        }
+    }
+
+    /// Expire stale approvals and execute approved ones. Returns trades filled.
+    async fn process_approvals(&mut self) -> usize {
+        match self.store.expire_approvals(self.store.clock().now()).await {
+            Ok(0) => {}
+            Ok(expired) => info!(expired, "Trade approvals expired"),
+            Err(e) => warn!(error = %e, "Failed to expire approvals"),
+        }
+
+        let approved = match self.store.get_approvals_by_status(approval::APPROVED).await {
+            Ok(a) => a,
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch approved trades");
+                return 0;
+            }
+        };
+
+        let mut filled = 0;
+        for record in approved {
+            let approved = match ApprovedOrder::from_record(&record) {
+                Ok(a) => a,
+                Err(e) => {
+                    warn!(error = %e, "Invalid approval record");
+                    if let Some(id) = record.id {
+                        self.finish_approval(id, approval::FAILED).await;
+                    }
+                    continue;
+                }
+            };
+
+            // The market may have moved or limits filled up since it was parked
+            let bankroll = self.effective_bankroll().await;
+            let (order, size_usd) = match self.recheck_approved(&approved, bankroll).await {
+                Ok(rechecked) => rechecked,
+                Err(reason) => {
+                    warn!(
+                        id = approved.id,
+                        market = %approved.order.market_question,
+                        %reason,
+                        "Approved trade no longer passes pre-trade checks — expiring"
+                    );
+                    self.finish_approval(approved.id, approval::EXPIRED).await;
+                    continue;
                 }
+            };
+
+            info!(
+                id = approved.id,
+                market = %order.market_question,
+                side = %order.side,
+                price = %order.price,
+                size_usd = %size_usd,
+                "Executing approved trade"
+            );
+
+            let execution = self
+                .execute_and_track(
+                    &order,
+                    approved.category.clone(),
+                    size_usd,
+                    ExecutionStyle::Aggressive,
+                    None,
+                    None,
+                )
+                .await;
+            let status = if execution.as_ref().is_some_and(|e| e.status.has_fill()) {
+                filled += 1;
+                approval::EXECUTED
+            } else {
+                approval::FAILED
+            };
+            self.finish_approval(approved.id, status).await;
+            if let Some(execution) = execution {
+                self.publish_signal(Signal::executed(
+                    &order,
+                    &approved.category,
+                    size_usd,
+                    &execution,
+                ))
+                .await;
+            }
+        }
+        filled
+    }
+
+    /// Re-run the pre-trade checks on an approved order against a fresh
+    /// order book: blacklist, portfolio constraints, remaining capacity,
+    /// price movement since approval and the per-trade loss cap. Returns
+    /// the re-priced order and its size, or why it can't be placed.
+    async fn recheck_approved(
+        &self,
+        approved: &ApprovedOrder,
+        bankroll: Decimal,
+    ) -> std::result::Result<(PreparedOrder, Decimal), String> {
+        let parked = &approved.order;
+        let blacklisted = blacklist::blacklisted_ids(&self.store)
+            .await
+            .map_err(|e| format!("failed to load blacklist: {e}"))?;
+        if blacklisted.contains(&parked.market_id) {
+            return Err("market blacklisted".to_string());
+        }
+        let candidate = self
+            .held_candidate(&parked.market_id)
+            .await
+            .map_err(|e| format!("failed to refresh market: {e}"))?;
+        let mut opportunity = Opportunity {
+            market: candidate.market,
+            order_book: candidate.order_book,
+            fair_value: parked.fair_value,
+            confidence: parked.confidence,
+            edge: parked.edge,
+            recommended_side: parked.side,
+            kelly_size: approved.size_usd,
+            new_listing: false,
+        };
+        if let ConstraintCheck::Fail(violations) =
+            self.portfolio.check_constraints(&opportunity, bankroll)
+        {
+            return Err(violations.join("; "));
+        }
+
+        let strategy = self
+            .strategies
+            .route(&approved.category)
+            .filter(|s| s.name == parked.bucket);
+        let risk = match strategy {
+            Some(s) => s.risk_config(&self.config.risk),
+            None => self.config.risk.clone(),
+        };
+        let size_usd = self
+            .portfolio
+            .adjust_size(approved.size_usd, bankroll, &approved.category);
+        let size_usd = match strategy {
+            Some(s) => {
+                let realized = self
+                    .realized_by_strategy()
+                    .await
+                    .map_err(|e| format!("failed to load strategy P&L: {e}"))?;
+                size_usd.min(self.strategies.available(
+                    s,
+                    self.strategies.bankroll(s, bankroll, &realized),
+                    self.portfolio.bucket_exposure(&s.name),
+                ))
+            }
+            None => self
+                .portfolio
+                .adjust_size_for_bucket(&parked.bucket, size_usd, bankroll),
+        };
+        if size_usd < risk.min_position_usd {
+            return Err(format!("only ${size_usd} of capacity left"));
+        }
+
+        opportunity.kelly_size = size_usd;
+        let mut order = order::prepare_order(
+            &opportunity,
+            parked.kelly_raw,
+            parked.kelly_adjusted,
+            &self.config.execution,
+        )
+        .map_err(|e| format!("order preparation failed: {e}"))?;
+        order.bucket = parked.bucket.clone();
+        order.tags = parked.tags.clone();
+        if !approval::still_priced(parked, order.price, self.config.execution.max_slippage_pct) {
+            return Err(format!(
+                "price moved from {} to {} since approval",
+                parked.price, order.price
+            ));
+        }
+        if let Some((worst_case, cap)) = self.trade_loss_over_cap(&order, &risk) {
+            return Err(format!("worst-case loss ${worst_case} exceeds cap ${cap}"));
+        }
+        Ok((order, size_usd))
+    }
+
+    /// Realized P&L per strategy bucket; empty when no strategies are set.
+    async fn realized_by_strategy(&self) -> Result<std::collections::BTreeMap<String, Decimal>> {
+        if !self.strategies.is_enabled() {
+            return Ok(Default::default());
+        }
+        let trades = self.store.get_all_trades().await?;
+        Ok(crate::monitoring::metrics::bucket_breakdown(&trades)
+            .into_iter()
+            .map(|b| (b.bucket, b.realized_pnl))
+            .collect())
+    }
+
+    /// `(worst_case, cap)` when `prepared` could lose more than
+    /// `risk.max_trade_loss_usd`.
+    fn trade_loss_over_cap(
+        &self,
+        prepared: &PreparedOrder,
+        risk: &RiskConfig,
+    ) -> Option<(Decimal, Decimal)> {
+        let cap = risk.max_trade_loss_usd?;
+        let worst_case = limits::worst_case_loss(
+            prepared.size,
+            prepared.price,
+            &self.exchange.fees(),
+            self.config.execution.max_slippage_pct,
+        );
+        (worst_case > cap).then_some((worst_case, cap))
+    }
+
+    /// Whether signal export holds every trade for approval.
+    fn signal_approval_required(&self) -> bool {
+        self.signals.is_some() && self.config.monitoring.signals.require_approval
+    }
+
+    /// Send a signal to the webhook, if one is configured. Delivery failures
+    /// are logged and never block trading.
+    async fn publish_signal(&self, signal: Signal) {
+        let Some(publisher) = &self.signals else {
+            return;
+        };
+        if let Err(e) = publisher.publish(&signal).await {
+            warn!(market_id = %signal.market_id, error = %e, "Failed to publish signal");
+        }
+    }
+
+    async fn finish_approval(&self, id: i64, status: &str) {
+        if let Err(e) = self.store.update_approval_status(id, status).await {
+            warn!(id, error = %e, "Failed to update approval status");
+        }
+    }
+
+    /// Re-evaluate open positions for stop-loss exit signals (RISK-01).
+    /// Fetches current YES price from Gamma and evaluates against max loss threshold.
+    /// Positions whose fresh valuation has turned against them are exited or
+    /// hedged with the opposite token, depending on book liquidity.
+    /// Places orders in live and paper mode; exited positions are marked CANCELLED.
+    async fn evaluate_open_positions(&mut self) {
+        use crate::risk::exit::{evaluate_exit, DEFAULT_MAX_LOSS_PCT};
+
+        let open_trades = match self.store.get_open_trades().await {
+            Ok(t) => t,
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch open trades for exit evaluation");
+                return;
+            }
+        };
+
+        // Markets held on both sides are hedged: the payout is locked in
+        let hedged_markets: HashSet<&str> = open_trades
+            .iter()
+            .filter(|t| t.direction == "YES")
+            .filter(|t| {
+                open_trades
+                    .iter()
+                    .any(|o| o.market_id == t.market_id && o.direction == "NO")
+            })
+            .map(|t| t.market_id.as_str())
+            .collect();
 
+        for trade in &open_trades {
+            let trade_id = match trade.id {
+                Some(id) => id,
+                None => continue,
+            };
+            if hedged_markets.contains(trade.market_id.as_str()) {
                 info!(
-                    market = %prepared.market_question,
-                    side = %prepared.side,
-                    size_usd = %liquidity_size,
-                    total_exposure = %self.portfolio.total_exposure(),
-                    positions = self.portfolio.position_count(),
-                    "Position added to portfolio"
+                    market_id = %trade.market_id,
+                    "Position hedged — holding to resolution"
                 );
+                continue;
             }
-        }
+            let entry_price = trade.entry_price;
+            let side = match trade.direction.as_str() {
+                "YES" => Side::Yes,
+                "NO" => Side::No,
+                _ => continue,
+            };
+            let size = trade.size;
 
-        result
+            // Fetch current YES price (Gamma, unless the exchange is replaced)
+            let current_yes_price =
+                match self.exchange.get_current_yes_price(&trade.market_id).await {
+                    Ok(p) => p,
+                    Err(e) => {
+                        warn!(
+                            market_id = %trade.market_id,
+                            error = %e,
+                            "Failed to fetch current price for exit evaluation"
+                        );
+                        continue;
+                    }
+                };
+
+            let signal = evaluate_exit(
+                &trade.market_id,
+                entry_price,
+                side,
+                current_yes_price,
+                DEFAULT_MAX_LOSS_PCT,
+            );
+
+            if !signal.should_exit {
+                self.flag_evaporated_edge(trade, trade_id, side, current_yes_price)
+                    .await;
+            }
+
+            if signal.should_exit {
+                warn!(
+                    market_id = %trade.market_id,
+                    entry_price = %entry_price,
+                    current_price = %current_yes_price,
+                    pnl_pct = %signal.pnl_pct,
+                    reason = %signal.reason,
+                    "EXIT SIGNAL triggered"
+                );
+
+                // Exit at current market price
+                let exit_price = match side {
+                    Side::Yes => current_yes_price,
+                    Side::No => Decimal::ONE - current_yes_price,
+                };
+                let pnl = signal.pnl_pct * entry_price * size;
+                self.exit_trade(trade, trade_id, side, exit_price, size, pnl)
+                    .await;
+            } else if !self
+                .hedge_on_revaluation(trade, trade_id, side, entry_price, size, current_yes_price)
+                .await
+            {
+                info!(
+                    market_id = %trade.market_id,
+                    pnl_pct = %signal.pnl_pct,
+                    "Position healthy"
+                );
+            }
+        }
+    }
+
+    /// Refresh fair value for held markets not valued within
+    /// `revaluation.interval_seconds`, using the cheaper re-valuation model.
+    /// Results replace the markets' cached valuations, which the hedge and
+    /// exit checks then read.
+    async fn revalue_positions(&self) {
+        let Some(engine) = &self.revaluation_engine else {
+            return;
+        };
+        // Only worth paying for while the agent can still act on the result
+        if !matches!(self.state, AgentState::Alive | AgentState::LowFuel) {
+            return;
+        }
+        match engine.daily_spend_exhausted().await {
+            Ok(true) => {
+                debug!("Daily API budget exhausted — skipping position re-valuation");
+                return;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                warn!(error = %e, "Failed to check daily API cost — skipping position re-valuation");
+                return;
+            }
+        }
+        let open_trades = match self.store.get_open_trades().await {
+            Ok(t) => t,
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch open trades for re-valuation");
+                return;
+            }
+        };
+
+        let config = &self.config.revaluation;
+        let stale_before = sql_timestamp(
+            self.store.clock().now() - chrono::Duration::seconds(config.interval_seconds as i64),
+        );
+        let mut held: Vec<&str> = Vec::new();
+        for trade in &open_trades {
+            if !held.contains(&trade.market_id.as_str()) {
+                held.push(&trade.market_id);
+            }
+        }
+
+        let bankroll = self.current_balance().await;
+        let mut revalued = 0;
+        for market_id in held {
+            if revalued >= config.max_per_cycle {
+                break;
+            }
+            match self
+                .store
+                .get_valuation_since(market_id, &stale_before)
+                .await
+            {
+                Ok(Some(_)) => continue,
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(market_id, error = %e, "Failed to check valuation age");
+                    continue;
+                }
+            }
+            let candidate = match self.held_candidate(market_id).await {
+                Ok(c) => c,
+                Err(e) => {
+                    warn!(market_id, error = %e, "Failed to load held market for re-valuation");
+                    continue;
+                }
+            };
+            let query = MarketQuery {
+                condition_id: candidate.market.condition_id.clone(),
+                question: candidate.market.question.clone(),
+                category: candidate.market.category.clone(),
+            };
+            let data: Vec<DataPoint> = self
+                .data_aggregator
+                .fetch_all(&[query])
+                .await
+                .into_iter()
+                .filter(|dp| dp.relevance_to.contains(&candidate.market.condition_id))
+                .collect();
+
+            revalued += 1;
+            match engine
+                .evaluate(&candidate, &data, bankroll, self.cycle_number as i64)
+                .await
+            {
+                Ok(Some(valuation)) => info!(
+                    market_id,
+                    fair_value = %valuation.probability,
+                    confidence = %valuation.confidence,
+                    market_price = %candidate.order_book.midpoint,
+                    "Position re-valued"
+                ),
+                Ok(None) => {}
+                Err(e) => warn!(market_id, error = %e, "Position re-valuation failed"),
+            }
+        }
+    }
+
+    /// A held market with the order book of its first (YES) token, as the
+    /// scanner would present it.
+    async fn held_candidate(&self, market_id: &str) -> Result<MarketCandidate> {
+        let market = self.exchange.get_market(market_id).await?;
+        let token = market
+            .tokens
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("Market {market_id} has no tokens"))?;
+        let order_book = self.exchange.get_order_book(&token.token_id).await?;
+        Ok(MarketCandidate {
+            market,
+            order_book,
+            new_listing: false,
+            liquidity_score: None,
+        })
+    }
+
+    /// Exit the weakest held position to make room for `candidate` when
+    /// exposure limits block a `size_usd` position and its `net_edge` beats
+    /// that position's by the rebalance margin (see [`rebalance`]). Returns
+    /// whether a position was exited.
+    async fn rebalance_for(
+        &mut self,
+        candidate: &MarketCandidate,
+        net_edge: Decimal,
+        size_usd: Decimal,
+        bankroll: Decimal,
+    ) -> bool {
+        let Some(scope) = rebalance::exit_scope(
+            &self.portfolio,
+            &self.config.risk,
+            &candidate.market.category,
+            size_usd,
+            bankroll,
+        ) else {
+            return false;
+        };
+        let open_trades = match self.store.get_open_trades().await {
+            Ok(trades) => trades,
+            Err(e) => {
+                warn!(error = %e, "Failed to load open trades for rebalancing");
+                return false;
+            }
+        };
+
+        let fees = self.exchange.fees();
+        let positions: Vec<Position> = self
+            .portfolio
+            .positions()
+            .iter()
+            .filter(|p| scope.includes(&p.category))
+            .filter(|p| p.market_id != candidate.market.condition_id)
+            .cloned()
+            .collect();
+        let mut held = Vec::new();
+        let mut exit_prices = std::collections::HashMap::new();
+        for position in positions {
+            if exit_prices.contains_key(&position.market_id) {
+                continue;
+            }
+            let trades: Vec<&TradeRecord> = open_trades
+                .iter()
+                .filter(|t| t.market_id == position.market_id)
+                .collect();
+            // Markets held on both sides are hedged to resolution
+            let side = position.side.to_string();
+            if trades.is_empty() || trades.iter().any(|t| t.direction != side) {
+                continue;
+            }
+            let book = match self.exchange.get_order_book(&position.token_id).await {
+                Ok(book) => book,
+                Err(e) => {
+                    debug!(market_id = %position.market_id, error = %e, "No book for rebalancing");
+                    continue;
+                }
+            };
+            let Some(best_bid) = book.bids.iter().map(|l| l.price).max() else {
+                continue;
+            };
+            let fair_yes = match self
+                .store
+                .get_valuation_since(&position.market_id, "")
+                .await
+            {
+                Ok(Some((fair_yes, _))) => fair_yes,
+                _ => trades[0].claude_fair_value,
+            };
+            let fair = match position.side {
+                Side::Yes => fair_yes,
+                Side::No => Decimal::ONE - fair_yes,
+            };
+            let entered_at = trades
+                .iter()
+                .filter_map(|t| t.created_at)
+                .max()
+                .unwrap_or_else(chrono::Utc::now);
+            held.push(rebalance::HeldPosition {
+                market_id: position.market_id.clone(),
+                side: position.side,
+                remaining_edge: fair - book.midpoint,
+                exit_cost: (book.midpoint - best_bid).max(Decimal::ZERO)
+                    + fees.per_share(Liquidity::Taker, best_bid),
+                entered_at,
+            });
+            exit_prices.insert(position.market_id, best_bid);
+        }
+
+        let Some(weakest) = rebalance::weakest(
+            &held,
+            net_edge,
+            &self.config.risk.rebalance,
+            self.store.clock().now(),
+        ) else {
+            return false;
+        };
+        let exit_price = exit_prices[&weakest.market_id];
+        info!(
+            exited = %weakest.market_id,
+            remaining_edge = %weakest.remaining_edge,
+            exit_cost = %weakest.exit_cost,
+            market = %candidate.market.question,
+            net_edge = %net_edge,
+            "Rebalancing: exiting weakest position for a better opportunity"
+        );
+        for trade in open_trades
+            .iter()
+            .filter(|t| t.market_id == weakest.market_id)
+        {
+            let Some(trade_id) = trade.id else {
+                continue;
+            };
+            let mut tags = trade.tags.clone();
+            tags.push(rebalance::REBALANCED_TAG.to_string());
+            if let Err(e) = self
+                .store
+                .annotate_trade(trade_id, &tags, trade.notes.as_deref())
+                .await
+            {
+                warn!(error = %e, "Failed to tag rebalanced trade");
+            }
+            let fee = fees.fee(Liquidity::Taker, exit_price, trade.size);
+            let pnl = (exit_price - trade.entry_price) * trade.size - fee;
+            self.exit_trade(trade, trade_id, weakest.side, exit_price, trade.size, pnl)
+                .await;
+        }
+        self.portfolio.remove_position(&weakest.market_id);
+        true
+    }
+
+    /// Tag a position whose edge on its latest re-valuation is below
+    /// `revaluation.min_edge`. Tagged once; the tag stays if the edge returns.
+    async fn flag_evaporated_edge(
+        &self,
+        trade: &TradeRecord,
+        trade_id: i64,
+        side: Side,
+        current_yes_price: Decimal,
+    ) {
+        let config = &self.config.revaluation;
+        if !config.enabled || trade.has_tag(revaluation::EVAPORATED_TAG) {
+            return;
+        }
+        let since = trade.created_at.map(sql_timestamp).unwrap_or_default();
+        let fair_yes = match self
+            .store
+            .get_valuation_since(&trade.market_id, &since)
+            .await
+        {
+            Ok(Some((fair_yes, _))) => fair_yes,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(market_id = %trade.market_id, error = %e, "Failed to load re-valuation");
+                return;
+            }
+        };
+        if !revaluation::edge_evaporated(side, fair_yes, current_yes_price, config.min_edge) {
+            return;
+        }
+
+        warn!(
+            market_id = %trade.market_id,
+            fair_value = %fair_yes,
+            current_price = %current_yes_price,
+            edge = %hedge::position_edge(side, fair_yes, current_yes_price),
+            min_edge = %config.min_edge,
+            "Edge evaporated on re-valuation"
+        );
+        let mut tags = trade.tags.clone();
+        tags.push(revaluation::EVAPORATED_TAG.to_string());
+        if let Err(e) = self
+            .store
+            .annotate_trade(trade_id, &tags, trade.notes.as_deref())
+            .await
+        {
+            warn!(error = %e, "Failed to flag position with evaporated edge");
+        }
+    }
+
+    /// Sell a position and mark its trade CANCELLED with the realized `pnl`.
+    async fn exit_trade(
+        &self,
+        trade: &TradeRecord,
+        trade_id: i64,
+        side: Side,
+        exit_price: Decimal,
+        size: Decimal,
+        pnl: Decimal,
+    ) {
+        // Place a sell order to exit. Paper mode sells the simulated
+        // share inventory back, so the exit is reflected in its balance.
+        if matches!(
+            self.config.agent.mode,
+            crate::config::AgentMode::Live
+                | crate::config::AgentMode::Paper
+                | crate::config::AgentMode::Simulation
+        ) {
+            // Find the token_id for this trade
+            let token_id = match self.find_token_id_for_trade(&trade.market_id, side).await {
+                Some(tid) => tid,
+                None => {
+                    warn!(
+                        market_id = %trade.market_id,
+                        "Could not find token_id for exit order"
+                    );
+                    return;
+                }
+            };
+
+            match self
+                .exchange
+                .exit_position(&token_id, side, exit_price, size)
+                .await
+            {
+                Ok(order_id) => {
+                    info!(
+                        order_id = %order_id,
+                        market_id = %trade.market_id,
+                        "Exit order placed"
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        error = %e,
+                        market_id = %trade.market_id,
+                        "Failed to place exit order"
+                    );
+                }
+            }
+        }
+
+        // Mark trade as cancelled/exited in database
+        if let Err(e) = self
+            .store
+            .update_trade_status(
+                trade_id,
+                "CANCELLED",
+                Some(pnl),
+                Some(self.store.clock().now()),
+            )
+            .await
+        {
+            warn!(error = %e, "Failed to update trade status for exit");
+        }
+    }
+
+    /// Replace last cycle's market-making quotes with fresh ones in
+    /// wide-spread markets valued recently enough. Returns how many quotes
+    /// filled on placement.
+    async fn make_markets(&mut self) -> usize {
+        for order_id in std::mem::take(&mut self.quote_order_ids) {
+            match self.exchange.cancel_order(&order_id).await {
+                Ok(()) => {
+                    if let Err(e) = self.store.update_order_status(&order_id, "CANCELLED").await {
+                        warn!(order_id, error = %e, "Failed to mark quote cancelled");
+                    }
+                }
+                // Usually already filled; reconciliation records it
+                Err(e) => debug!(order_id, error = %e, "Failed to cancel quote"),
+            }
+        }
+
+        let config = self.config.market_making.clone();
+        let since = sql_timestamp(
+            self.store.clock().now()
+                - chrono::Duration::seconds(config.max_valuation_age_seconds as i64),
+        );
+        let valued = match self.store.get_valuations_since(&since).await {
+            Ok(v) => v,
+            Err(e) => {
+                warn!(error = %e, "Failed to load valuations for market making");
+                return 0;
+            }
+        };
+        let inventory = match self.store.get_open_trades().await {
+            Ok(trades) => portfolio::net_by_market(&trades),
+            Err(e) => {
+                warn!(error = %e, "Failed to load inventory for market making");
+                return 0;
+            }
+        };
+        let blacklisted = blacklist::blacklisted_ids(&self.store)
+            .await
+            .unwrap_or_default();
+        let bankroll = self.effective_bankroll().await;
+
+        let mut quoted_markets = 0;
+        let mut filled = 0;
+        for (market_id, fair_yes, confidence) in valued {
+            if quoted_markets >= config.max_markets {
+                break;
+            }
+            if confidence < config.min_confidence || blacklisted.contains(&market_id) {
+                continue;
+            }
+            let Ok(market) = self.exchange.get_market(&market_id).await else {
+                continue;
+            };
+            let (Some(yes_token), Some(no_token)) = (
+                self.exchange.find_token_id(&market_id, Side::Yes).await,
+                self.exchange.find_token_id(&market_id, Side::No).await,
+            ) else {
+                continue;
+            };
+            let (yes_book, no_book) = match tokio::try_join!(
+                self.exchange.get_order_book(&yes_token),
+                self.exchange.get_order_book(&no_token),
+            ) {
+                Ok(books) => books,
+                Err(e) => {
+                    debug!(market_id, error = %e, "Failed to fetch books for quoting");
+                    continue;
+                }
+            };
+            let held = inventory.iter().find(|n| n.market_id == market_id);
+            let quotes = market_making::quotes(&config, fair_yes, &yes_book, &no_book, held);
+            if quotes.is_empty() || !market.active {
+                continue;
+            }
+            quoted_markets += 1;
+
+            for quote in quotes {
+                let size_usd = self.portfolio.adjust_size(
+                    quote.price * quote.shares,
+                    bankroll,
+                    &market.category,
+                );
+                let size_usd =
+                    self.portfolio
+                        .adjust_size_for_bucket(MARKET_MAKING_BUCKET, size_usd, bankroll);
+                if size_usd < self.config.risk.min_position_usd {
+                    debug!(market_id, side = %quote.side, "No exposure left for quote");
+                    continue;
+                }
+                let fair = match quote.side {
+                    Side::Yes => fair_yes,
+                    Side::No => Decimal::ONE - fair_yes,
+                };
+                let prepared = PreparedOrder {
+                    token_id: match quote.side {
+                        Side::Yes => yes_token.clone(),
+                        Side::No => no_token.clone(),
+                    },
+                    side: quote.side,
+                    price: quote.price,
+                    size: (size_usd / quote.price).round_dp(2),
+                    market_id: market_id.clone(),
+                    market_question: market.question.clone(),
+                    edge: fair - quote.price,
+                    fair_value: fair_yes,
+                    confidence,
+                    kelly_raw: Decimal::ZERO,
+                    kelly_adjusted: Decimal::ZERO,
+                    bucket: MARKET_MAKING_BUCKET.to_string(),
+                    tags: Vec::new(),
+                };
+                info!(
+                    market_id,
+                    side = %prepared.side,
+                    price = %prepared.price,
+                    shares = %prepared.size,
+                    fair_value = %fair,
+                    "Quoting"
+                );
+                let Some(execution) = self
+                    .execute_and_track(
+                        &prepared,
+                        market.category.clone(),
+                        size_usd,
+                        ExecutionStyle::Passive,
+                        None,
+                        None,
+                    )
+                    .await
+                else {
+                    continue;
+                };
+                if execution.status.has_fill() {
+                    filled += 1;
+                }
+                if execution.status != OrderStatus::Filled && !execution.order_id.is_empty() {
+                    self.quote_order_ids.push(execution.order_id);
+                }
+            }
+        }
+        filled
+    }
+
+    /// Buy the known outcome of candidates about to resolve whose data
+    /// shows them settled, under the `[sniping]` guards instead of Kelly.
+    /// Returns how many snipes filled.
+    async fn snipe_near_resolution(
+        &mut self,
+        candidates: &[MarketCandidate],
+        data: &[DataPoint],
+    ) -> usize {
+        let config = self.config.sniping.clone();
+        let now = self.store.clock().now();
+        let blacklisted = blacklist::blacklisted_ids(&self.store)
+            .await
+            .unwrap_or_default();
+        let held: HashSet<String> = self
+            .portfolio
+            .positions()
+            .iter()
+            .map(|p| p.market_id.clone())
+            .collect();
+        let bankroll = self.effective_bankroll().await;
+
+        let mut sniped = 0;
+        let mut filled = 0;
+        for candidate in candidates {
+            if sniped >= config.max_markets {
+                break;
+            }
+            let market = &candidate.market;
+            if blacklisted.contains(&market.condition_id) || held.contains(&market.condition_id) {
+                continue;
+            }
+            let Some(side) = sniping::settled_side(market, data, config.min_data_confidence) else {
+                continue;
+            };
+            let Some(token_id) = self
+                .exchange
+                .find_token_id(&market.condition_id, side)
+                .await
+            else {
+                continue;
+            };
+            let book = match self.exchange.get_order_book(&token_id).await {
+                Ok(book) => book,
+                Err(e) => {
+                    debug!(market_id = %market.condition_id, error = %e, "Failed to fetch book for sniping");
+                    continue;
+                }
+            };
+            let room = config.max_exposure_usd - self.portfolio.bucket_exposure(SNIPING_BUCKET);
+            let available = self
+                .portfolio
+                .adjust_size_for_bucket(SNIPING_BUCKET, room, bankroll);
+            let available = self
+                .portfolio
+                .adjust_size(available, bankroll, &market.category);
+            let Some(snipe) = sniping::snipe(&config, market, side, &book, available, now) else {
+                continue;
+            };
+            let size_usd = snipe.price * snipe.shares;
+            if size_usd < self.config.risk.min_position_usd {
+                debug!(market_id = %market.condition_id, "No sniping budget left");
+                continue;
+            }
+            sniped += 1;
+
+            let fair_yes = match side {
+                Side::Yes => Decimal::ONE,
+                Side::No => Decimal::ZERO,
+            };
+            let prepared = PreparedOrder {
+                token_id,
+                side,
+                price: snipe.price,
+                size: snipe.shares,
+                market_id: market.condition_id.clone(),
+                market_question: market.question.clone(),
+                edge: Decimal::ONE - snipe.price,
+                fair_value: fair_yes,
+                confidence: config.min_data_confidence,
+                kelly_raw: Decimal::ZERO,
+                kelly_adjusted: Decimal::ZERO,
+                bucket: SNIPING_BUCKET.to_string(),
+                tags: Vec::new(),
+            };
+            info!(
+                market = %market.question,
+                side = %side,
+                price = %snipe.price,
+                shares = %snipe.shares,
+                "Sniping settled market"
+            );
+            let Some(execution) = self
+                .execute_and_track(
+                    &prepared,
+                    market.category.clone(),
+                    size_usd,
+                    ExecutionStyle::Aggressive,
+                    None,
+                    None,
+                )
+                .await
+            else {
+                continue;
+            };
+            if execution.status.has_fill() {
+                filled += 1;
+            }
+        }
+        filled
+    }
+
+    /// Sell every open position at the best bid and close its trade.
+    /// Hedged pairs are sold too, so nothing is left exposed.
+    async fn flatten_positions(&self) {
+        let open_trades = match self.store.get_open_trades().await {
+            Ok(t) => t,
+            Err(e) => {
+                error!(error = %e, "Failed to load open trades — cannot flatten");
+                return;
+            }
+        };
+        warn!(
+            cycle = self.cycle_number,
+            positions = open_trades.len(),
+            "Flattening all positions"
+        );
+
+        for trade in &open_trades {
+            let Some(trade_id) = trade.id else { continue };
+            let side = match trade.direction.as_str() {
+                "YES" => Side::Yes,
+                "NO" => Side::No,
+                _ => continue,
+            };
+            let Some(exit_price) = self.flatten_price(&trade.market_id, side).await else {
+                error!(
+                    market_id = %trade.market_id,
+                    "No price to flatten at — position left open"
+                );
+                continue;
+            };
+            let pnl = (exit_price - trade.entry_price) * trade.size;
+            self.exit_trade(trade, trade_id, side, exit_price, trade.size, pnl)
+                .await;
+        }
+    }
+
+    /// Best bid for the `side` token of `market_id`, falling back to the
+    /// last price when the book has no bids.
+    async fn flatten_price(&self, market_id: &str, side: Side) -> Option<Decimal> {
+        if let Some(token_id) = self.find_token_id_for_trade(market_id, side).await {
+            match self.exchange.get_order_book(&token_id).await {
+                Ok(book) => {
+                    if let Some(best) = book.bids.first() {
+                        return Some(best.price);
+                    }
+                }
+                Err(e) => warn!(market_id, error = %e, "Failed to fetch book to flatten"),
+            }
+        }
+        let yes_price = self.exchange.get_current_yes_price(market_id).await.ok()?;
+        Some(match side {
+            Side::Yes => yes_price,
+            Side::No => Decimal::ONE - yes_price,
+        })
     }
 
-    /// Re-evaluate open positions for stop-loss exit signals (RISK-01).
-    /// Fetches current YES price from Gamma and evaluates against max loss threshold.
-    /// In paper mode, marks positions as CANCELLED. In live mode, places sell orders.
-    async fn evaluate_open_positions(&self) {
-        use crate::risk::exit::{evaluate_exit, DEFAULT_MAX_LOSS_PCT};
-
+    /// Close markets where open YES and NO holdings fully offset (e.g. after
+    /// a hedge) by merging the pairs back into cash. Each leg is closed at
+    /// the exit price from
+    /// [`portfolio::MarketNetting::merge_exit_prices`].
+    async fn merge_offsetting_positions(&mut self) {
         let open_trades = match self.store.get_open_trades().await {
             Ok(t) => t,
             Err(e) => {
-                warn!(error = %e, "Failed to fetch open trades for exit evaluation");
+                warn!(error = %e, "Failed to load open trades for netting");
                 return;
             }
         };
 
-        for trade in &open_trades {
-            let trade_id = match trade.id {
-                Some(id) => id,
-                None => continue,
-            };
-            let entry_price: Decimal = match trade.entry_price.parse() {
-                Ok(p) => p,
-                Err(_) => continue,
-            };
-            let side = match trade.direction.as_str() {
-                "YES" => crate::market::models::Side::Yes,
-                "NO" => crate::market::models::Side::No,
-                _ => continue,
+        for market in portfolio::net_by_market(&open_trades) {
+            if !market.is_flat() {
+                continue;
+            }
+            let Some((yes_exit, no_exit)) = market.merge_exit_prices() else {
+                continue;
             };
-            let size: Decimal = match trade.size.parse() {
-                Ok(s) => s,
-                Err(_) => continue,
+            let (Some(yes_token), Some(no_token)) = (
+                self.find_token_id_for_trade(&market.market_id, Side::Yes)
+                    .await,
+                self.find_token_id_for_trade(&market.market_id, Side::No)
+                    .await,
+            ) else {
+                warn!(market_id = %market.market_id, "Could not find tokens for merge");
+                continue;
             };
-
-            // Fetch current YES price from Gamma API
-            let current_yes_price = match self
+            if let Err(e) = self
                 .polymarket
-                .get_current_yes_price(&trade.market_id)
+                .merge_positions(&yes_token, &no_token, market.matched_shares())
                 .await
             {
-                Ok(p) => p,
-                Err(e) => {
-                    warn!(
-                        market_id = %trade.market_id,
-                        error = %e,
-                        "Failed to fetch current price for exit evaluation"
-                    );
-                    continue;
-                }
-            };
-
-            let signal = evaluate_exit(
-                &trade.market_id,
-                entry_price,
-                side,
-                current_yes_price,
-                DEFAULT_MAX_LOSS_PCT,
-            );
-
-            if signal.should_exit {
-                warn!(
-                    market_id = %trade.market_id,
-                    entry_price = %entry_price,
-                    current_price = %current_yes_price,
-                    pnl_pct = %signal.pnl_pct,
-                    reason = %signal.reason,
-                    "EXIT SIGNAL triggered"
-                );
-
-                // In live mode, place an actual sell order to exit
-                if self.config.agent.mode == crate::config::AgentMode::Live {
-                    // Find the token_id for this trade
-                    let token_id = match self.find_token_id_for_trade(&trade.market_id, side).await
-                    {
-                        Some(tid) => tid,
-                        None => {
-                            warn!(
-                                market_id = %trade.market_id,
-                                "Could not find token_id for exit order"
-                            );
-                            continue;
-                        }
-                    };
-
-                    // Exit at current market price
-                    let exit_price = match side {
-                        crate::market::models::Side::Yes => current_yes_price,
-                        crate::market::models::Side::No => Decimal::ONE - current_yes_price,
-                    };
-
-                    match self
-                        .polymarket
-                        .exit_position(&token_id, side, exit_price, size)
-                        .await
-                    {
-                        Ok(order_id) => {
-                            info!(
-                                order_id = %order_id,
-                                market_id = %trade.market_id,
-                                "Live exit order placed"
-                            );
-                        }
-                        Err(e) => {
-                            warn!(
-                                error = %e,
-                                market_id = %trade.market_id,
-                                "Failed to place live exit order"
-                            );
-                        }
-                    }
-                }
+                debug!(market_id = %market.market_id, error = %e, "Offsetting positions not merged");
+                continue;
+            }
 
-                // Mark trade as cancelled/exited in database
-                let pnl = signal.pnl_pct * entry_price * size;
+            let now = self.store.clock().now();
+            for trade in open_trades
+                .iter()
+                .filter(|t| t.id.is_some_and(|id| market.trade_ids.contains(&id)))
+            {
+                let (entry, shares) = (trade.entry_price, trade.size);
+                let exit = if trade.direction == "NO" {
+                    no_exit
+                } else {
+                    yes_exit
+                };
+                let pnl = (exit - entry) * shares;
                 if let Err(e) = self
                     .store
-                    .update_trade_status(trade_id, "CANCELLED", Some(pnl), Some(chrono::Utc::now()))
+                    .update_trade_status(
+                        trade.id.unwrap_or_default(),
+                        "CANCELLED",
+                        Some(pnl),
+                        Some(now),
+                    )
                     .await
                 {
-                    warn!(error = %e, "Failed to update trade status for exit");
+                    warn!(error = %e, "Failed to close merged trade");
                 }
-            } else {
-                info!(
-                    market_id = %trade.market_id,
-                    pnl_pct = %signal.pnl_pct,
-                    "Position healthy"
-                );
             }
+            self.portfolio.remove_position(&market.market_id);
+            info!(
+                market_id = %market.market_id,
+                pairs = %market.matched_shares(),
+                pnl = %(market.matched_shares() - market.yes_cost - market.no_cost),
+                "Merged fully offsetting YES/NO positions"
+            );
         }
     }
 
-    /// Find the token_id for a given market and side.
-    /// Used for constructing exit orders in live mode.
-    async fn find_token_id_for_trade(
-        &self,
-        market_id: &str,
-        side: crate::market::models::Side,
-    ) -> Option<String> {
-        // Query the Gamma API to get market tokens
-        let url = format!("{}/markets", self.polymarket.gamma_base_url());
-        let markets: Vec<serde_json::Value> = match self
-            .polymarket
-            .http_client()
-            .get(&url)
-            .query(&[("condition_id", market_id)])
-            .send()
+    /// Exit or hedge a position whose valuation since entry has turned
+    /// strongly against it. Returns true if any action was taken.
+    async fn hedge_on_revaluation(
+        &mut self,
+        trade: &TradeRecord,
+        trade_id: i64,
+        side: Side,
+        entry_price: Decimal,
+        size: Decimal,
+        current_yes_price: Decimal,
+    ) -> bool {
+        let config = &self.config.risk.hedge;
+        if !config.enabled {
+            return false;
+        }
+        let since = trade.created_at.map(sql_timestamp).unwrap_or_default();
+        let (fair_yes, confidence) = match self
+            .store
+            .get_valuation_since(&trade.market_id, &since)
             .await
         {
-            Ok(resp) => match resp.json().await {
-                Ok(m) => m,
-                Err(_) => return None,
-            },
-            Err(_) => return None,
+            Ok(Some(v)) => v,
+            Ok(None) => return false,
+            Err(e) => {
+                warn!(market_id = %trade.market_id, error = %e, "Failed to load re-valuation");
+                return false;
+            }
         };
+        if hedge::position_edge(side, fair_yes, current_yes_price) > -config.flip_threshold {
+            return false;
+        }
 
-        let market = markets.first()?;
-        let token_ids = market
-            .get("clobTokenIds")
-            .and_then(|v| v.as_str())
-            .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())?;
+        let (Some(held_token), Some(opposite_token)) = (
+            self.find_token_id_for_trade(&trade.market_id, side).await,
+            self.find_token_id_for_trade(&trade.market_id, side.opposite())
+                .await,
+        ) else {
+            warn!(market_id = %trade.market_id, "Could not find tokens for hedge evaluation");
+            return false;
+        };
+        let (held_book, opposite_book) = match tokio::try_join!(
+            self.exchange.get_order_book(&held_token),
+            self.exchange.get_order_book(&opposite_token),
+        ) {
+            Ok(books) => books,
+            Err(e) => {
+                warn!(market_id = %trade.market_id, error = %e, "Failed to fetch books for hedge");
+                return false;
+            }
+        };
 
-        let outcomes = market
-            .get("outcomes")
-            .and_then(|v| v.as_str())
-            .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())?;
+        let decision = hedge::decide(
+            config,
+            side,
+            size,
+            fair_yes,
+            current_yes_price,
+            &held_book,
+            &opposite_book,
+        );
+        warn!(
+            market_id = %trade.market_id,
+            fair_value = %fair_yes,
+            current_price = %current_yes_price,
+            edge = %decision.edge,
+            action = ?decision.action,
+            reason = %decision.reason,
+            "Edge flipped on re-valuation"
+        );
 
-        // Find the token index matching the side
-        let target_outcome = match side {
-            crate::market::models::Side::Yes => "Yes",
-            crate::market::models::Side::No => "No",
-        };
+        match decision.action {
+            HedgeAction::Hold => false,
+            HedgeAction::Exit { price } => {
+                let pnl = (price - entry_price) * size;
+                self.exit_trade(trade, trade_id, side, price, size, pnl)
+                    .await;
+                true
+            }
+            HedgeAction::Hedge { price, shares } => {
+                if !matches!(
+                    self.config.agent.mode,
+                    crate::config::AgentMode::Live
+                        | crate::config::AgentMode::Paper
+                        | crate::config::AgentMode::Simulation
+                ) {
+                    return true;
+                }
+                // Recorded as a regular trade on the other side, so the pair
+                // settles through normal resolution.
+                let prepared = PreparedOrder {
+                    token_id: opposite_token,
+                    side: side.opposite(),
+                    price,
+                    size: shares,
+                    market_id: trade.market_id.clone(),
+                    market_question: trade.market_question.clone().unwrap_or_default(),
+                    edge: -decision.edge,
+                    fair_value: fair_yes,
+                    confidence,
+                    kelly_raw: Decimal::ZERO,
+                    kelly_adjusted: Decimal::ZERO,
+                    bucket: trade.bucket.clone(),
+                    tags: Vec::new(),
+                };
+                let category = self
+                    .portfolio
+                    .positions()
+                    .iter()
+                    .find(|p| p.market_id == trade.market_id)
+                    .map(|p| p.category.clone())
+                    .unwrap_or_else(|| MarketCategory::Other("unknown".to_string()));
+                // Journaled and tracked like any entry, so a crash can't
+                // place it twice and exposure limits see it
+                let execution = self
+                    .execute_and_track(
+                        &prepared,
+                        category,
+                        price * shares,
+                        ExecutionStyle::Aggressive,
+                        None,
+                        None,
+                    )
+                    .await;
+                if execution.is_some_and(|e| e.status.has_fill()) {
+                    info!(
+                        market_id = %trade.market_id,
+                        side = %prepared.side,
+                        shares = %shares,
+                        price = %price,
+                        "Hedge placed"
+                    );
+                }
+                true
+            }
+        }
+    }
 
-        for (i, outcome) in outcomes.iter().enumerate() {
-            if outcome.eq_ignore_ascii_case(target_outcome) {
-                return token_ids.get(i).cloned();
+    /// Pay out the simulated share inventory of resolved paper trades, so
+    /// the paper balance reflects settlement like a live wallet would.
+    async fn settle_paper_holdings(&self, settled: &[resolution::ResolutionResult]) {
+        let mut seen = HashSet::new();
+        for result in settled {
+            if !seen.insert((result.market_id.as_str(), result.side)) {
+                continue;
+            }
+            let Some(token_id) = self
+                .find_token_id_for_trade(&result.market_id, result.side)
+                .await
+            else {
+                warn!(market_id = %result.market_id, "No token for resolved paper trade");
+                continue;
+            };
+            let payout = if result.won {
+                Decimal::ONE
+            } else {
+                Decimal::ZERO
+            };
+            if let Err(e) = self.polymarket.paper_settle(&token_id, payout).await {
+                warn!(error = %e, market_id = %result.market_id, "Failed to settle paper holding");
             }
         }
+    }
 
-        // Fallback to first token
-        token_ids.first().cloned()
+    /// Find the token_id for a given market and side.
+    /// Used for constructing exit orders in live mode.
+    async fn find_token_id_for_trade(&self, market_id: &str, side: Side) -> Option<String> {
+        self.exchange.find_token_id(market_id, side).await
     }
 
     fn log_opportunity(
@@ -801,12 +3011,13 @@ impl Agent {
             edge = %edge.raw_edge,
             side = %edge.side,
             confidence = %valuation.confidence,
+            new_listing = candidate.new_listing,
             "OPPORTUNITY FOUND"
         );
     }
 
     async fn current_balance(&self) -> Decimal {
-        match self.polymarket.get_balance().await {
+        match self.exchange.get_balance().await {
             Ok(balance) => balance,
             Err(e) => {
                 warn!(error = %e, "Failed to get balance, using zero");
@@ -824,6 +3035,123 @@ impl Agent {
         wallet::effective_bankroll(balance, self.config.agent.api_reserve, unrealized)
     }
 
+    /// Cancel every resting order on the exchange and mark tracked orders
+    /// cancelled, so nothing is left working once the agent stops.
+    pub async fn cancel_all_orders(&self) {
+        match self.exchange.cancel_all_orders().await {
+            Ok(cancelled) => info!(count = cancelled.len(), "Cancelled all open orders"),
+            Err(e) => {
+                error!(error = %e, "Failed to cancel open orders — check the exchange manually");
+                return;
+            }
+        }
+        if let Err(e) = self.store.cancel_open_orders().await {
+            warn!(error = %e, "Failed to mark tracked orders cancelled");
+        }
+    }
+
+    /// Start the watcher that fires `handle` on events affecting held
+    /// markets, unless triggers are disabled.
+    pub fn watch_triggers(&self, handle: TriggerHandle) -> Option<tokio::task::JoinHandle<()>> {
+        let config = self.config.agent.triggers.clone();
+        if !config.enabled {
+            return None;
+        }
+        Some(triggers::spawn_watcher(
+            config,
+            self.polymarket.clone(),
+            self.store.clone_for_parallel(),
+            handle,
+        ))
+    }
+
+    /// Start snapshotting held markets' order books, unless disabled.
+    pub fn capture_order_books(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let config = self.config.agent.book_capture.clone();
+        if !config.enabled {
+            return None;
+        }
+        Some(book_capture::spawn_capture(
+            config,
+            self.polymarket.clone(),
+            self.store.clone_for_parallel(),
+            self.writer.clone(),
+        ))
+    }
+
+    /// Commit every queued batched write, e.g. before shutting down.
+    pub async fn flush_writes(&self) {
+        if let Err(e) = self.writer.flush().await {
+            warn!(error = %e, "Failed to flush batched writes");
+        }
+    }
+
+    /// Mark this run as started and restore positions saved at the last
+    /// clean shutdown whose trades are still open. Reports an unclean stop.
+    pub async fn resume(&mut self) -> Result<()> {
+        let live = (self.config.agent.mode == crate::config::AgentMode::Live)
+            .then_some(self.polymarket.as_ref());
+        let recovery = journal::recover(&self.store, live).await?;
+        if !recovery.abandoned.is_empty() {
+            warn!(
+                markets = ?recovery.abandoned,
+                "Abandoned order intents from a crashed cycle"
+            );
+        }
+
+        match shutdown::mark_running(&self.store, self.cycle_number).await? {
+            Some(marker) if marker.clean => info!(
+                cycle = marker.cycle,
+                reason = %marker.reason,
+                at = %marker.at,
+                "Previous run shut down cleanly"
+            ),
+            Some(marker) => warn!(
+                cycle = marker.cycle,
+                started_at = %marker.at,
+                "Previous run did not shut down cleanly — verify open orders on the exchange"
+            ),
+            None => {}
+        }
+
+        let open: HashSet<String> = self
+            .store
+            .get_open_trades()
+            .await?
+            .into_iter()
+            .map(|t| t.market_id)
+            .collect();
+        let mut restored = 0;
+        for position in shutdown::load_positions(&self.store).await? {
+            if open.contains(&position.market_id) {
+                self.portfolio.add_position(position);
+                restored += 1;
+            }
+        }
+        if restored > 0 {
+            info!(positions = restored, "Restored portfolio from checkpoint");
+        }
+        Ok(())
+    }
+
+    /// Persist portfolio state and a clean-shutdown marker.
+    pub async fn checkpoint(&self, reason: &str) -> Result<()> {
+        shutdown::record_shutdown(
+            &self.store,
+            self.cycle_number,
+            reason,
+            self.portfolio.positions(),
+        )
+        .await?;
+        info!(
+            cycle = self.cycle_number,
+            positions = self.portfolio.position_count(),
+            reason,
+            "Shutdown checkpoint written"
+        );
+        Ok(())
+    }
+
     async fn shutdown(&self) -> Result<()> {
         let balance = self.current_balance().await;
         error!(
@@ -831,6 +3159,7 @@ impl Agent {
             balance = %balance,
             "AGENT DEATH — balance depleted, shutting down"
         );
+        self.cancel_all_orders().await;
 
         // Phase 8: Send death alert
         if let Err(e) = self
@@ -844,14 +3173,245 @@ impl Agent {
         Ok(())
     }
 
-    async fn log_cycle(
-        &self,
-        duration: std::time::Duration,
-        markets_scanned: i64,
-        opportunities_found: i64,
-        trades_placed: i64,
-        api_cost: Decimal,
-    ) -> Result<()> {
+    /// Grade the balance and runway, alerting when the funding level
+    /// escalates or recovers. Returns whether new entries should pause.
+    async fn check_funding(&mut self, balance: Decimal) -> bool {
+        let config = &self.config.monitoring.funding;
+        if !config.enabled {
+            return false;
+        }
+        let runway = funding::runway_days(
+            balance,
+            self.config.agent.death_balance_threshold,
+            self_funding::burn_rate(&self.store).await,
+            self.config.agent.cycle_interval_seconds,
+        );
+        let status = funding::assess(
+            config,
+            balance,
+            self.config.agent.low_fuel_threshold,
+            runway,
+        );
+        let paused = config.pause_entries && status.level == FundingLevel::Critical;
+
+        let escalated = status.level > self.funding_level;
+        let recovered =
+            status.level == FundingLevel::Healthy && self.funding_level != FundingLevel::Healthy;
+        if escalated || recovered {
+            warn!(
+                level = %status.level,
+                balance = %status.balance,
+                runway_days = ?status.runway_days,
+                "Funding level changed"
+            );
+            if let Err(e) = self.alert_client.funding(&status, paused).await {
+                warn!(error = %e, "Failed to send funding alert");
+            }
+        }
+        self.funding_level = status.level;
+        paused
+    }
+
+    /// Pause new entries for a cooldown once the bankroll's drawdown over
+    /// the recent window crosses the threshold, alerting with an analysis
+    /// of the losses, and resume when it ends. Returns whether new entries
+    /// should pause.
+    async fn check_drawdown(&mut self) -> bool {
+        let config = &self.config.risk.drawdown;
+        if !config.enabled {
+            return false;
+        }
+        if let Some(CooldownEvent::Ended(cooldown)) =
+            self.drawdown_cooldown.expire(self.cycle_number)
+        {
+            info!(
+                cycle = self.cycle_number,
+                "Drawdown cooldown over — new entries resumed"
+            );
+            if let Err(e) = self.alert_client.drawdown_resumed(&cooldown).await {
+                warn!(error = %e, "Failed to send drawdown alert");
+            }
+        }
+        if self.drawdown_cooldown.active().is_some() {
+            return true;
+        }
+
+        let mut cycles = match self
+            .store
+            .get_recent_cycles(config.window_cycles as i64)
+            .await
+        {
+            Ok(cycles) => cycles,
+            Err(e) => {
+                warn!(error = %e, "Failed to load cycles for drawdown check");
+                return false;
+            }
+        };
+        // Losses that already caused a cooldown don't count again
+        if let Some(after) = self.drawdown_cooldown.rearmed_after() {
+            cycles.retain(|c| c.cycle_number as u64 > after);
+        }
+        cycles.reverse();
+        let drawdown = drawdown::max_drawdown(&cycles);
+        let Some(CooldownEvent::Started(cooldown)) =
+            self.drawdown_cooldown
+                .observe(self.cycle_number, drawdown, config)
+        else {
+            return false;
+        };
+
+        warn!(
+            drawdown = %drawdown,
+            resume_cycle = cooldown.resume_cycle,
+            "Drawdown limit hit — pausing new entries"
+        );
+        let since = cycles
+            .first()
+            .and_then(|c| c.created_at)
+            .unwrap_or_else(chrono::Utc::now);
+        match tokio::try_join!(
+            self.store.get_all_trades(),
+            self.store.get_market_categories()
+        ) {
+            Ok((trades, categories)) => {
+                let report = DrawdownReport::build(
+                    drawdown,
+                    cycles.len(),
+                    cooldown,
+                    &trades,
+                    &categories,
+                    since,
+                );
+                if let Err(e) = self.alert_client.drawdown_cooldown(&report).await {
+                    warn!(error = %e, "Failed to send drawdown alert");
+                }
+            }
+            Err(e) => warn!(error = %e, "Failed to load trades for drawdown report"),
+        }
+        true
+    }
+
+    /// Warn about behavior that departs from recent history. Each kind of
+    /// anomaly is alerted when it starts, not every cycle it persists.
+    async fn check_anomalies(&mut self) {
+        let config = &self.config.monitoring.anomaly;
+        if !config.enabled {
+            return;
+        }
+        let history = tokio::try_join!(
+            self.store
+                .get_recent_cycles(config.baseline_cycles as i64 + 1),
+            self.store.get_all_trades(),
+            self.store.get_resolved_trades(),
+        );
+        let (cycles, trades, resolved) = match history {
+            Ok(history) => history,
+            Err(e) => {
+                warn!(error = %e, "Failed to load history for anomaly detection");
+                return;
+            }
+        };
+
+        let anomalies = anomaly::detect(&cycles, &trades, &resolved, config);
+        for found in &anomalies {
+            if self.active_anomalies.contains(&found.kind) {
+                continue;
+            }
+            warn!(
+                metric = found.kind.metric(),
+                current = %found.current,
+                baseline = %found.baseline,
+                "Anomaly detected"
+            );
+            if let Err(e) = self.alert_client.anomaly(self.cycle_number, found).await {
+                warn!(error = %e, "Failed to send anomaly alert");
+            }
+        }
+        self.active_anomalies = anomalies.iter().map(|a| a.kind).collect();
+    }
+
+    /// Send the weekly report once its slot has passed. The send time is
+    /// persisted so a restart neither repeats nor skips a week.
+    async fn send_weekly_report_if_due(&self) {
+        let config = &self.config.monitoring.weekly_report;
+        let now = chrono::Utc::now();
+        let last_sent = match self.store.get_meta(weekly::LAST_SENT_KEY).await {
+            Ok(value) => value
+                .as_deref()
+                .and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok())
+                .map(|t| t.with_timezone(&chrono::Utc)),
+            Err(e) => {
+                warn!(error = %e, "Failed to read weekly report marker");
+                return;
+            }
+        };
+        if !weekly::is_due(now, last_sent, config) {
+            return;
+        }
+
+        let history = tokio::try_join!(
+            self.store.get_all_trades(),
+            self.store.get_all_cycles(),
+            self.store.get_all_api_costs(),
+            self.store.get_all_calibration(),
+        );
+        let (trades, cycles, api_costs, calibration) = match history {
+            Ok(history) => history,
+            Err(e) => {
+                warn!(error = %e, "Failed to load history for weekly report");
+                return;
+            }
+        };
+        let report = weekly::build(
+            now,
+            &trades,
+            &cycles,
+            &api_costs,
+            &calibration,
+            self.config.agent.cycle_interval_seconds,
+        );
+
+        let html_path = match weekly::write_html(&report, Path::new(&config.report_dir)) {
+            Ok(path) => Some(path.display().to_string()),
+            Err(e) => {
+                warn!(error = %e, "Failed to write weekly report");
+                None
+            }
+        };
+        info!(
+            pnl = %report.pnl,
+            win_rate = %report.win_rate(),
+            api_cost = %report.api_cost,
+            uptime = %report.uptime,
+            path = html_path.as_deref().unwrap_or(""),
+            "Weekly report"
+        );
+        if let Err(e) = self
+            .alert_client
+            .weekly_report(&report, html_path.as_deref())
+            .await
+        {
+            warn!(error = %e, "Failed to send weekly report");
+        }
+        if let Err(e) = self
+            .store
+            .set_meta(weekly::LAST_SENT_KEY, &now.to_rfc3339())
+            .await
+        {
+            warn!(error = %e, "Failed to record weekly report marker");
+        }
+    }
+
+    async fn log_cycle(&self, summary: CycleSummary) -> Result<()> {
+        let CycleSummary {
+            duration,
+            markets_scanned,
+            opportunities_found,
+            trades_placed,
+            api_cost,
+            truncated_phases,
+            timings,
+        } = summary;
         let balance = self.current_balance().await;
         let unrealized = fills::unrealized_exposure(&self.store)
             .await
@@ -863,11 +3423,13 @@ impl Agent {
             markets_scanned: Some(markets_scanned),
             opportunities_found: Some(opportunities_found),
             trades_placed: Some(trades_placed),
-            api_cost: Some(api_cost.to_string()),
-            bankroll: Some(balance.to_string()),
-            unrealized_pnl: Some(unrealized.to_string()),
+            api_cost: Some(api_cost),
+            bankroll: Some(balance),
+            unrealized_pnl: Some(unrealized),
             agent_state: self.state.to_string(),
             duration_ms: Some(duration.as_millis() as i64),
+            truncated_phases: truncated_phases.clone(),
+            timings,
             created_at: None,
         };
 
@@ -883,8 +3445,36 @@ impl Agent {
             trades_placed,
             api_cost = %api_cost,
             unrealized_exposure = %unrealized,
+            truncated_phases = truncated_phases.as_deref().unwrap_or(""),
+            scan_ms = timings.scan_ms,
+            data_ms = timings.data_ms,
+            valuation_ms = timings.valuation_ms,
+            execution_ms = timings.execution_ms,
+            settlement_ms = timings.settlement_ms,
             "Cycle complete"
         );
+        for stats in self.polymarket.rate_limit_stats() {
+            debug!(
+                family = stats.family,
+                requests = stats.requests,
+                waited_ms = stats.waited_ms,
+                "Rate limiter usage since startup"
+            );
+        }
+        let cache = self.polymarket.http_cache_stats();
+        debug!(
+            requests = cache.requests,
+            not_modified = cache.not_modified,
+            entries = cache.entries,
+            "HTTP cache usage since startup"
+        );
+        let books = self.polymarket.book_cache_stats();
+        debug!(
+            hits = books.hits,
+            misses = books.misses,
+            entries = books.entries,
+            "Order book cache usage since startup"
+        );
 
         Ok(())
     }
@@ -902,10 +3492,66 @@ impl Agent {
     }
 }
 
+/// A valued candidate: scan position, market, valuation, edge (if any) and
+/// when it was valued.
+type Evaluated = (
+    usize,
+    MarketCandidate,
+    ValuationResult,
+    Option<EdgeResult>,
+    chrono::DateTime<chrono::Utc>,
+);
+
+/// The data points to value `candidate` with. With embeddings the engine
+/// picks evidence from every source.
+fn relevant_data(
+    engine: &ValuationEngine,
+    all_data: &[DataPoint],
+    candidate: &MarketCandidate,
+) -> Vec<DataPoint> {
+    if engine.relevance_scorer().is_some() {
+        all_data.to_vec()
+    } else {
+        all_data
+            .iter()
+            .filter(|dp| dp.relevance_to.contains(&candidate.market.condition_id))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Markets in any consistency violation among `evaluated`.
+fn consistency_violations(evaluated: &[Evaluated], tolerance: Decimal) -> BTreeSet<String> {
+    let valued: Vec<_> = evaluated
+        .iter()
+        .map(|(_, candidate, valuation, ..)| (&candidate.market, valuation.probability))
+        .collect();
+    let violations = consistency::check(&valued, tolerance);
+    for violation in &violations {
+        warn!(?violation, "Inconsistent valuations across related markets");
+    }
+    consistency::violating_ids(&violations)
+}
+
+/// What a finished cycle did, as logged and stored in `cycles`.
+struct CycleSummary {
+    duration: std::time::Duration,
+    markets_scanned: i64,
+    opportunities_found: i64,
+    trades_placed: i64,
+    api_cost: Decimal,
+    truncated_phases: Option<String>,
+    timings: PhaseTimings,
+}
+
 /// Aggregated results from a single cycle's evaluate+trade pipeline.
 #[derive(Default)]
 struct CycleResult {
     opportunities: usize,
     trades: usize,
     api_cost: Decimal,
+    timings: PhaseTimings,
+    /// External data fetched for the candidates, for strategies that read
+    /// it directly.
+    data: Vec<DataPoint>,
 }
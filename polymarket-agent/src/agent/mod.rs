@@ -1,2 +1,12 @@
+pub mod book_capture;
+pub mod budget;
+pub mod canary;
+pub mod control;
+pub mod crash;
+pub mod ideas;
+pub mod journal;
 pub mod lifecycle;
 pub mod self_funding;
+pub mod shutdown;
+pub mod simulation;
+pub mod triggers;
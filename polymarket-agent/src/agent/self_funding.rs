@@ -19,17 +19,17 @@ const VPS_COST_PER_CYCLE: &str = "0.001";
 pub struct CycleCosts {
     /// Claude API cost this cycle.
     pub api_cost: Decimal,
-    /// Polygon gas fees this cycle (minimal on Polygon).
+    /// Polygon gas actually paid this cycle (approvals, redemptions).
     pub gas_cost: Decimal,
     /// Amortized VPS cost per cycle.
     pub vps_cost: Decimal,
 }
 
 impl CycleCosts {
-    pub fn new(api_cost: Decimal) -> Self {
+    pub fn new(api_cost: Decimal, gas_cost: Decimal) -> Self {
         Self {
             api_cost,
-            gas_cost: dec!(0.0001), // Polygon gas is negligible
+            gas_cost,
             vps_cost: VPS_COST_PER_CYCLE.parse().unwrap_or(dec!(0.001)),
         }
     }
@@ -62,11 +62,11 @@ pub async fn estimate_next_cycle_cost(store: &Store, lookback_cycles: u64) -> De
         return dec!(0.01);
     }
 
-    // Average API cost per cycle + fixed costs
+    // Average API cost per cycle (gas is booked there too) + fixed costs
     let avg_api = total / Decimal::from(effective_count);
-    let fixed = CycleCosts::new(Decimal::ZERO);
+    let fixed = CycleCosts::new(Decimal::ZERO, Decimal::ZERO);
 
-    avg_api + fixed.gas_cost + fixed.vps_cost
+    avg_api + fixed.vps_cost
 }
 
 /// Enhanced survival check that factors in unrealized PnL and projected costs.
@@ -120,9 +120,9 @@ pub async fn burn_rate(store: &Store) -> Decimal {
         return Decimal::ZERO;
     }
 
-    let fixed_per_cycle = CycleCosts::new(Decimal::ZERO);
+    let fixed_per_cycle = CycleCosts::new(Decimal::ZERO, Decimal::ZERO);
     let avg_api = total / Decimal::from(cycle_count);
-    avg_api + fixed_per_cycle.gas_cost + fixed_per_cycle.vps_cost
+    avg_api + fixed_per_cycle.vps_cost
 }
 
 /// Log a detailed cost breakdown for the current cycle.
@@ -144,9 +144,10 @@ mod tests {
 
     #[test]
     fn test_cycle_costs() {
-        let costs = CycleCosts::new(dec!(0.05));
+        let costs = CycleCosts::new(dec!(0.05), dec!(0.002));
         assert_eq!(costs.api_cost, dec!(0.05));
-        assert!(costs.total() > dec!(0.05)); // Includes gas + VPS
+        assert_eq!(costs.gas_cost, dec!(0.002));
+        assert!(costs.total() > dec!(0.052)); // Includes gas + VPS
         assert!(costs.total() < dec!(0.06)); // But not much more
     }
 
@@ -0,0 +1,110 @@
+//! Shutdown checkpointing.
+//!
+//! A graceful stop saves the portfolio's tracked positions and a shutdown
+//! marker to `agent_meta`. While the agent runs the marker reads unclean, so
+//! a crash or kill leaves it that way and the next startup can say so.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::db::store::Store;
+use crate::risk::portfolio::Position;
+
+/// `agent_meta` key holding the latest [`ShutdownMarker`].
+const MARKER_KEY: &str = "shutdown_marker";
+
+/// `agent_meta` key holding the positions saved at the last clean shutdown.
+const PORTFOLIO_KEY: &str = "portfolio_checkpoint";
+
+/// How the previous run ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShutdownMarker {
+    /// False while running; true once a graceful shutdown completes.
+    pub clean: bool,
+    pub cycle: u64,
+    pub reason: String,
+    pub at: DateTime<Utc>,
+}
+
+/// Mark the agent as running and return the marker the previous run left,
+/// if any.
+pub async fn mark_running(store: &Store, cycle: u64) -> Result<Option<ShutdownMarker>> {
+    let previous = match store.get_meta(MARKER_KEY).await? {
+        Some(json) => Some(serde_json::from_str(&json).context("Corrupt shutdown marker")?),
+        None => None,
+    };
+    write_marker(store, false, cycle, "running").await?;
+    Ok(previous)
+}
+
+/// Save `positions` and mark the shutdown clean.
+pub async fn record_shutdown(
+    store: &Store,
+    cycle: u64,
+    reason: &str,
+    positions: &[Position],
+) -> Result<()> {
+    store
+        .set_meta(PORTFOLIO_KEY, &serde_json::to_string(positions)?)
+        .await?;
+    write_marker(store, true, cycle, reason).await
+}
+
+/// Positions saved at the last clean shutdown.
+pub async fn load_positions(store: &Store) -> Result<Vec<Position>> {
+    match store.get_meta(PORTFOLIO_KEY).await? {
+        Some(json) => serde_json::from_str(&json).context("Corrupt portfolio checkpoint"),
+        None => Ok(Vec::new()),
+    }
+}
+
+async fn write_marker(store: &Store, clean: bool, cycle: u64, reason: &str) -> Result<()> {
+    let marker = ShutdownMarker {
+        clean,
+        cycle,
+        reason: reason.to_string(),
+        at: Utc::now(),
+    };
+    store
+        .set_meta(MARKER_KEY, &serde_json::to_string(&marker)?)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::{MarketCategory, Side};
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_marker_tracks_clean_shutdown() {
+        let store = Store::new(":memory:").await.unwrap();
+        assert_eq!(mark_running(&store, 0).await.unwrap(), None);
+
+        // Restarting without a graceful shutdown looks like a crash
+        let previous = mark_running(&store, 3).await.unwrap().unwrap();
+        assert!(!previous.clean);
+
+        let position = Position {
+            market_id: "m1".to_string(),
+            token_id: "t1".to_string(),
+            category: MarketCategory::Crypto,
+            side: Side::Yes,
+            size_usd: dec!(5),
+            entry_price: dec!(0.40),
+            bucket: "directional".to_string(),
+        };
+        record_shutdown(&store, 7, "signal", &[position])
+            .await
+            .unwrap();
+
+        let previous = mark_running(&store, 8).await.unwrap().unwrap();
+        assert!(previous.clean);
+        assert_eq!(previous.cycle, 7);
+        assert_eq!(previous.reason, "signal");
+        let positions = load_positions(&store).await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].size_usd, dec!(5));
+    }
+}
@@ -0,0 +1,716 @@
+//! Deterministic simulation of the full agent loop.
+//!
+//! `mode = "simulation"` runs the real [`Agent`] — scan, data, valuation,
+//! sizing, execution, exits and settlement — against a [`FakeExchange`]
+//! whose markets are generated from a seed. Each market has a hidden true
+//! probability and a pre-drawn outcome; a simulated data feed reports noisy
+//! readings of the truth, and a valuation stub averages them in place of
+//! Claude. The clock is accelerated: cycles run back to back, each one
+//! advancing simulated time by `hours_per_cycle`, so markets drift toward
+//! their outcome and resolve within a short run. The agent, its store and
+//! the exchange all read the simulated [`Clock`], so nothing they decide
+//! depends on how long a cycle took in real time.
+//!
+//! The same seed and config produce the same trades, which makes a run
+//! usable as a CI check and lets a state-machine bug be replayed cycle by
+//! cycle. Nothing leaves the process: no API keys are used and no alerts
+//! are sent.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::agent::lifecycle::Agent;
+use crate::clock::Clock;
+use crate::config::{AgentMode, AppConfig, ScanningConfig, Secrets};
+use crate::data::{DataPoint, DataSource, MarketQuery};
+use crate::db::store::Store;
+use crate::market::fake::FakeExchange;
+use crate::market::models::{
+    AgentState, Market, MarketCandidate, MarketCategory, PriceLevel, TokenInfo,
+};
+use crate::rng::SplitMix64;
+use crate::valuation::fair_value::{
+    DataQuality, TimeSensitivity, ValuationEngine, ValuationResult, ValuationStub,
+};
+
+/// Categories simulated markets rotate through.
+const CATEGORIES: [MarketCategory; 4] = [
+    MarketCategory::Weather,
+    MarketCategory::Sports,
+    MarketCategory::Crypto,
+    MarketCategory::Politics,
+];
+
+/// Name the simulated feed reports its data points under.
+const FEED_NAME: &str = "simulation";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimulationConfig {
+    /// Seeds market generation, price moves and the data feed.
+    #[serde(default = "default_seed")]
+    pub seed: u64,
+    #[serde(default = "default_cycles")]
+    pub cycles: u64,
+    /// Markets listed at any time; resolved ones are replaced.
+    #[serde(default = "default_markets")]
+    pub markets: usize,
+    /// Simulated time each cycle advances.
+    #[serde(default = "default_hours_per_cycle")]
+    pub hours_per_cycle: u32,
+    /// Standard deviation of the data feed's error on a market's true probability.
+    #[serde(default = "default_signal_noise")]
+    pub signal_noise: f64,
+    /// Standard deviation of a market's opening price around its true probability.
+    #[serde(default = "default_mispricing")]
+    pub mispricing: f64,
+    /// Standard deviation of each cycle's price move.
+    #[serde(default = "default_volatility")]
+    pub volatility: f64,
+    /// Database the run writes to. Recreated at the start of every run.
+    #[serde(default = "default_database_path")]
+    pub database_path: String,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: default_seed(),
+            cycles: default_cycles(),
+            markets: default_markets(),
+            hours_per_cycle: default_hours_per_cycle(),
+            signal_noise: default_signal_noise(),
+            mispricing: default_mispricing(),
+            volatility: default_volatility(),
+            database_path: default_database_path(),
+        }
+    }
+}
+
+fn default_seed() -> u64 {
+    1
+}
+
+fn default_cycles() -> u64 {
+    200
+}
+
+fn default_markets() -> usize {
+    12
+}
+
+fn default_hours_per_cycle() -> u32 {
+    1
+}
+
+fn default_signal_noise() -> f64 {
+    0.05
+}
+
+fn default_mispricing() -> f64 {
+    0.08
+}
+
+fn default_volatility() -> f64 {
+    0.02
+}
+
+fn default_database_path() -> String {
+    "data/simulation.db".to_string()
+}
+
+/// What a simulation run did, for comparing runs and tracing state changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationReport {
+    pub seed: u64,
+    pub cycles: u64,
+    /// `(cycle, from, to)` for every survival state change.
+    pub transitions: Vec<(u64, AgentState, AgentState)>,
+    pub final_state: AgentState,
+    pub final_balance: Decimal,
+    pub trades: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub realized_pnl: Decimal,
+    /// Digest of every trade recorded; equal for reproduced runs.
+    pub fingerprint: u64,
+}
+
+impl std::fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "=== Simulation (seed {}) ===", self.seed)?;
+        writeln!(f, "Cycles run:     {}", self.cycles)?;
+        writeln!(f, "Final state:    {}", self.final_state)?;
+        writeln!(f, "Final balance:  ${}", self.final_balance.round_dp(2))?;
+        writeln!(
+            f,
+            "Trades:         {} ({} won, {} lost)",
+            self.trades, self.wins, self.losses
+        )?;
+        writeln!(f, "Realized P&L:   ${}", self.realized_pnl.round_dp(2))?;
+        writeln!(f, "Fingerprint:    {:016x}", self.fingerprint)?;
+        for (cycle, from, to) in &self.transitions {
+            writeln!(f, "  cycle {cycle}: {from} -> {to}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Run the agent against a seeded simulated market for
+/// `config.simulation.cycles` cycles, or until it dies.
+pub async fn run(mut config: AppConfig) -> Result<SimulationReport> {
+    let sim = config.simulation.clone();
+    config.agent.mode = AgentMode::Simulation;
+    config.database.path = sim.database_path.clone();
+    // Wall-clock schedules mean nothing on an accelerated clock, and a
+    // cached valuation would outlive many simulated hours
+    config.scanning.cadence_seconds.clear();
+    config.risk.event_windows.clear();
+    config.valuation.cache_ttl_seconds = 0;
+    config.monitoring.email.enabled = false;
+
+    recreate_database(&sim.database_path)?;
+    let mut world = SimWorld::new(&sim, &config.scanning);
+    let store = Store::new(&sim.database_path)
+        .await?
+        .with_clock(world.clock.clone());
+    let report_store = Store::from_pool(store.pool().clone());
+
+    let exchange = Arc::new(
+        FakeExchange::new(config.agent.initial_paper_balance)
+            .with_fees(config.execution.fees.clone())
+            .with_partial_fill(config.execution.partial_fill)
+            .with_clock(world.clock.clone()),
+    );
+    let sources: Vec<Box<dyn DataSource>> = CATEGORIES
+        .iter()
+        .map(|category| {
+            Box::new(SimulatedFeed::new(
+                category.clone(),
+                world.feed(),
+                world.clock.clone(),
+            )) as Box<dyn DataSource>
+        })
+        .collect();
+    let engine = ValuationEngine::stubbed(
+        Arc::new(FeedValuer),
+        config.valuation.clone(),
+        store.clone_for_parallel(),
+    );
+    let mut agent = Agent::new(config, Secrets::default(), store)
+        .await?
+        .with_exchange(exchange.clone())
+        .with_data_sources(sources)
+        .with_valuation_engine(engine);
+
+    info!(
+        seed = sim.seed,
+        cycles = sim.cycles,
+        markets = sim.markets,
+        "Starting simulation"
+    );
+    world.publish(&exchange);
+    let mut transitions = Vec::new();
+    let mut state = agent.current_state();
+    let mut cycles = 0;
+    while cycles < sim.cycles {
+        agent.run_cycle().await?;
+        if agent.current_state() != state {
+            transitions.push((cycles, state, agent.current_state()));
+            state = agent.current_state();
+        }
+        cycles += 1;
+        if agent.is_dead() {
+            break;
+        }
+        world.advance(&exchange);
+    }
+    agent.flush_writes().await;
+
+    let trades = report_store.get_all_trades().await?;
+    let settled: Vec<_> = trades.iter().filter_map(|t| t.pnl).collect();
+    Ok(SimulationReport {
+        seed: sim.seed,
+        cycles,
+        transitions,
+        final_state: state,
+        final_balance: exchange.balance(),
+        trades: trades.len(),
+        wins: trades.iter().filter(|t| t.status == "RESOLVED_WIN").count(),
+        losses: trades
+            .iter()
+            .filter(|t| t.status == "RESOLVED_LOSS")
+            .count(),
+        realized_pnl: settled.iter().sum(),
+        fingerprint: fingerprint(trades.iter().map(|t| {
+            format!(
+                "{}|{}|{}|{}|{}|{:?}",
+                t.market_id, t.direction, t.entry_price, t.size, t.status, t.pnl
+            )
+        })),
+    })
+}
+
+/// Start from an empty database so earlier runs can't change this one.
+fn recreate_database(path: &str) -> Result<()> {
+    for file in [
+        path.to_string(),
+        format!("{path}-wal"),
+        format!("{path}-shm"),
+    ] {
+        match std::fs::remove_file(&file) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("Failed to remove {file}")),
+        }
+    }
+    if let Some(dir) = Path::new(path)
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    Ok(())
+}
+
+/// FNV-1a over `lines`, stable across builds and platforms.
+fn fingerprint(lines: impl Iterator<Item = String>) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for line in lines {
+        for byte in line.bytes().chain(std::iter::once(b'\n')) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// A simulated market and its hidden truth.
+#[derive(Debug, Clone)]
+struct SimMarket {
+    id: u64,
+    category: MarketCategory,
+    volume: Decimal,
+    /// Chance the market resolves YES, as the data feed sees it.
+    true_probability: f64,
+    yes_won: bool,
+    /// Current YES midpoint.
+    price: f64,
+    listed_at: DateTime<Utc>,
+    resolves_at: DateTime<Utc>,
+}
+
+impl SimMarket {
+    fn condition_id(&self) -> String {
+        format!("sim-{}", self.id)
+    }
+
+    /// The price the market drifts toward: the true probability at listing,
+    /// the outcome at resolution.
+    fn target(&self, now: DateTime<Utc>) -> f64 {
+        let life = (self.resolves_at - self.listed_at).num_minutes().max(1) as f64;
+        let elapsed = (now - self.listed_at).num_minutes() as f64;
+        let progress = (elapsed / life).clamp(0.0, 1.0);
+        let outcome = if self.yes_won { 1.0 } else { 0.0 };
+        self.true_probability * (1.0 - progress) + outcome * progress
+    }
+}
+
+/// True probabilities shared with the simulated feed, and the feed's noise.
+struct FeedState {
+    truths: HashMap<String, f64>,
+    rng: SplitMix64,
+    noise: f64,
+}
+
+/// The simulated market universe on its own accelerated clock.
+struct SimWorld {
+    rng: SplitMix64,
+    /// Simulated time, shared with the agent, its store and the exchange;
+    /// starts at a fixed instant so runs don't depend on when they were
+    /// started.
+    clock: Clock,
+    step: chrono::Duration,
+    markets: Vec<SimMarket>,
+    next_id: u64,
+    listed: usize,
+    mispricing: f64,
+    volatility: f64,
+    max_resolution_hours: u64,
+    min_volume: Decimal,
+    feed: Arc<Mutex<FeedState>>,
+}
+
+impl SimWorld {
+    fn new(sim: &SimulationConfig, scanning: &ScanningConfig) -> Self {
+        let feed = FeedState {
+            truths: HashMap::new(),
+            // Separate stream, so feed draws don't shift market generation
+            rng: SplitMix64::new(sim.seed ^ 0x5EED_F00D),
+            noise: sim.signal_noise,
+        };
+        let mut world = Self {
+            rng: SplitMix64::new(sim.seed),
+            clock: Clock::manual(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+            step: chrono::Duration::hours(i64::from(sim.hours_per_cycle)),
+            markets: Vec::new(),
+            next_id: 1,
+            listed: sim.markets,
+            mispricing: sim.mispricing,
+            volatility: sim.volatility,
+            max_resolution_hours: u64::from(scanning.max_resolution_days.max(2)) * 24,
+            min_volume: scanning.min_volume_24h.max(dec!(1000)),
+            feed: Arc::new(Mutex::new(feed)),
+        };
+        world.list_markets();
+        world
+    }
+
+    fn feed(&self) -> Arc<Mutex<FeedState>> {
+        self.feed.clone()
+    }
+
+    /// Top the universe back up to `listed` markets.
+    fn list_markets(&mut self) {
+        while self.markets.len() < self.listed {
+            let id = self.next_id;
+            self.next_id += 1;
+            let true_probability = 0.1 + 0.8 * self.rng.next_f64();
+            let yes_won = self.rng.next_f64() < true_probability;
+            let price =
+                (true_probability + self.mispricing * self.rng.next_normal()).clamp(0.03, 0.97);
+            // Resolve between a day out and the scanner's horizon
+            let hours = 24 + self.rng.next_u64() % (self.max_resolution_hours - 24).max(1);
+            let volume = self.min_volume * Decimal::from(2 + self.rng.next_u64() % 8);
+            let now = self.clock.now();
+            let market = SimMarket {
+                id,
+                category: CATEGORIES[(id as usize) % CATEGORIES.len()].clone(),
+                volume,
+                true_probability,
+                yes_won,
+                price,
+                listed_at: now,
+                resolves_at: now + chrono::Duration::hours(hours as i64),
+            };
+            self.feed
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .truths
+                .insert(market.condition_id(), true_probability);
+            self.markets.push(market);
+        }
+    }
+
+    /// Advance the clock one step: prices move, due markets resolve and are
+    /// replaced, and the exchange is updated.
+    fn advance(&mut self, exchange: &FakeExchange) {
+        let now = self.clock.now() + self.step;
+        self.clock.set(now);
+        let mut resolved = Vec::new();
+        for market in &mut self.markets {
+            if now >= market.resolves_at {
+                resolved.push(market.clone());
+                continue;
+            }
+            let pull = 0.2 * (market.target(now) - market.price);
+            market.price =
+                (market.price + pull + self.volatility * self.rng.next_normal()).clamp(0.02, 0.98);
+        }
+        for market in &resolved {
+            let condition_id = market.condition_id();
+            exchange.resolve_market(&condition_id, market.yes_won);
+            self.feed
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .truths
+                .remove(&condition_id);
+            info!(market = %condition_id, yes_won = market.yes_won, "Simulated market resolved");
+        }
+        self.markets.retain(|m| now < m.resolves_at);
+        self.list_markets();
+        self.publish(exchange);
+    }
+
+    /// List every open market on the exchange with fresh books.
+    fn publish(&self, exchange: &FakeExchange) {
+        for market in &self.markets {
+            let condition_id = market.condition_id();
+            let (yes_token, no_token) =
+                (format!("{condition_id}-yes"), format!("{condition_id}-no"));
+            let yes_bid = to_price(market.price - 0.01).clamp(dec!(0.01), dec!(0.97));
+            let yes_ask = yes_bid + dec!(0.02);
+            exchange.add_market(Market {
+                condition_id: condition_id.clone(),
+                question: format!("Will simulated event #{} happen?", market.id),
+                outcomes: vec!["Yes".to_string(), "No".to_string()],
+                tokens: vec![
+                    TokenInfo {
+                        token_id: yes_token.clone(),
+                        outcome: "Yes".to_string(),
+                        price: yes_bid + dec!(0.01),
+                    },
+                    TokenInfo {
+                        token_id: no_token.clone(),
+                        outcome: "No".to_string(),
+                        price: dec!(0.99) - yes_bid,
+                    },
+                ],
+                end_date: market.resolves_at,
+                category: market.category.clone(),
+                volume_24h: market.volume,
+                active: true,
+                event_id: None,
+                event_slug: None,
+                neg_risk: false,
+                description: format!(
+                    "This market resolves to YES if simulated event #{} occurs by the end \
+                     date, according to the simulation's seeded outcome.",
+                    market.id
+                ),
+                resolution_source: Some(FEED_NAME.to_string()),
+            });
+            exchange.set_book(
+                &yes_token,
+                ladder(yes_bid, dec!(-0.01)),
+                ladder(yes_ask, dec!(0.01)),
+            );
+            // A YES bid is a NO offer at the complementary price
+            exchange.set_book(
+                &no_token,
+                ladder(Decimal::ONE - yes_ask, dec!(-0.01)),
+                ladder(Decimal::ONE - yes_bid, dec!(0.01)),
+            );
+        }
+    }
+}
+
+fn to_price(value: f64) -> Decimal {
+    Decimal::try_from(value).unwrap_or(dec!(0.5)).round_dp(2)
+}
+
+/// Three levels from `best`, `tick` apart, deepening away from the touch.
+fn ladder(best: Decimal, tick: Decimal) -> Vec<PriceLevel> {
+    [dec!(100), dec!(250), dec!(500)]
+        .into_iter()
+        .enumerate()
+        .map(|(i, size)| PriceLevel {
+            price: best + tick * Decimal::from(i as u64),
+            size,
+        })
+        .filter(|l| l.price > Decimal::ZERO && l.price < Decimal::ONE)
+        .collect()
+}
+
+/// Noisy readings of each simulated market's true probability.
+struct SimulatedFeed {
+    category: MarketCategory,
+    state: Arc<Mutex<FeedState>>,
+    clock: Clock,
+}
+
+impl SimulatedFeed {
+    fn new(category: MarketCategory, state: Arc<Mutex<FeedState>>, clock: Clock) -> Self {
+        Self {
+            category,
+            state,
+            clock,
+        }
+    }
+}
+
+#[async_trait]
+impl DataSource for SimulatedFeed {
+    async fn fetch(&self, queries: &[MarketQuery]) -> Result<Vec<DataPoint>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut points = Vec::new();
+        for query in queries {
+            let Some(&truth) = state.truths.get(&query.condition_id) else {
+                continue;
+            };
+            let reading = (truth + state.noise * state.rng.next_normal()).clamp(0.01, 0.99);
+            points.push(DataPoint {
+                source: FEED_NAME.to_string(),
+                category: self.category.clone(),
+                timestamp: self.clock.now(),
+                payload: serde_json::json!({ "probability": reading }),
+                confidence: dec!(0.8),
+                relevance_to: vec![query.condition_id.clone()],
+            });
+        }
+        Ok(points)
+    }
+
+    fn category(&self) -> MarketCategory {
+        self.category.clone()
+    }
+
+    fn freshness_window(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    fn name(&self) -> &str {
+        FEED_NAME
+    }
+}
+
+/// Values a market at the mean of the simulated feed's readings for it.
+struct FeedValuer;
+
+impl ValuationStub for FeedValuer {
+    fn value(
+        &self,
+        candidate: &MarketCandidate,
+        evidence: &[&DataPoint],
+    ) -> Option<ValuationResult> {
+        let readings: Vec<f64> = evidence
+            .iter()
+            .filter(|dp| dp.source == FEED_NAME)
+            .filter(|dp| dp.relevance_to.contains(&candidate.market.condition_id))
+            .filter_map(|dp| dp.payload.get("probability")?.as_f64())
+            .collect();
+        if readings.is_empty() {
+            return None;
+        }
+        let mean = readings.iter().sum::<f64>() / readings.len() as f64;
+        Some(ValuationResult {
+            probability: Decimal::try_from(mean).ok()?.round_dp(4),
+            confidence: dec!(0.8),
+            reasoning_summary: format!("Mean of {} simulated readings", readings.len()),
+            key_factors: vec![FEED_NAME.to_string()],
+            data_quality: DataQuality::Medium,
+            time_sensitivity: TimeSensitivity::Days,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::api::MarketApi;
+
+    fn scanning() -> ScanningConfig {
+        let config: AppConfig = toml::from_str(include_str!("../../config/default.toml")).unwrap();
+        config.scanning
+    }
+
+    /// Every listed market's YES midpoint, in listing order.
+    async fn midpoints(world: &SimWorld, exchange: &FakeExchange) -> Vec<Decimal> {
+        let mut mids = Vec::new();
+        for market in &world.markets {
+            let token = format!("{}-yes", market.condition_id());
+            mids.push(exchange.get_midpoint(&token).await.unwrap());
+        }
+        mids
+    }
+
+    #[tokio::test]
+    async fn test_same_seed_same_world() {
+        let sim = SimulationConfig::default();
+        let (mut a, mut b) = (
+            SimWorld::new(&sim, &scanning()),
+            SimWorld::new(&sim, &scanning()),
+        );
+        let (ex_a, ex_b) = (FakeExchange::new(dec!(100)), FakeExchange::new(dec!(100)));
+        for _ in 0..50 {
+            a.advance(&ex_a);
+            b.advance(&ex_b);
+        }
+        assert_eq!(midpoints(&a, &ex_a).await, midpoints(&b, &ex_b).await);
+
+        let other = SimulationConfig {
+            seed: 2,
+            ..SimulationConfig::default()
+        };
+        let mut c = SimWorld::new(&other, &scanning());
+        let ex_c = FakeExchange::new(dec!(100));
+        for _ in 0..50 {
+            c.advance(&ex_c);
+        }
+        assert_ne!(midpoints(&a, &ex_a).await, midpoints(&c, &ex_c).await);
+    }
+
+    #[tokio::test]
+    async fn test_markets_resolve_and_are_replaced() {
+        let sim = SimulationConfig::default();
+        let mut world = SimWorld::new(&sim, &scanning());
+        let exchange = FakeExchange::new(dec!(100));
+        world.publish(&exchange);
+        let first = world.markets[0].clone();
+
+        // Past the scanner's horizon every original market has resolved
+        let steps = world.max_resolution_hours / u64::from(sim.hours_per_cycle) + 1;
+        for _ in 0..steps {
+            world.advance(&exchange);
+        }
+        assert_eq!(world.markets.len(), sim.markets);
+        assert!(world.markets.iter().all(|m| m.id > sim.markets as u64));
+        assert_eq!(
+            exchange
+                .market_resolution(&first.condition_id())
+                .await
+                .unwrap(),
+            Some(first.yes_won)
+        );
+    }
+
+    #[test]
+    fn test_feed_valuer_averages_readings() {
+        let (world, exchange) = (
+            SimWorld::new(&SimulationConfig::default(), &scanning()),
+            FakeExchange::new(dec!(100)),
+        );
+        world.publish(&exchange);
+        let market = &world.markets[0];
+        let candidate = MarketCandidate {
+            market: Market {
+                condition_id: market.condition_id(),
+                question: String::new(),
+                outcomes: Vec::new(),
+                tokens: Vec::new(),
+                end_date: Utc::now(),
+                category: market.category.clone(),
+                volume_24h: market.volume,
+                active: true,
+                event_id: None,
+                event_slug: None,
+                neg_risk: false,
+                description: String::new(),
+                resolution_source: None,
+            },
+            order_book: tokio_test::block_on(
+                exchange.get_order_book(&format!("{}-yes", market.condition_id())),
+            )
+            .unwrap(),
+            new_listing: false,
+            liquidity_score: None,
+        };
+        let point = |reading: f64, market_id: &str| DataPoint {
+            source: FEED_NAME.to_string(),
+            category: market.category.clone(),
+            timestamp: Utc::now(),
+            payload: serde_json::json!({ "probability": reading }),
+            confidence: dec!(0.8),
+            relevance_to: vec![market_id.to_string()],
+        };
+        let (a, b, other) = (
+            point(0.6, &market.condition_id()),
+            point(0.7, &market.condition_id()),
+            point(0.1, "sim-999"),
+        );
+
+        let valuation = FeedValuer.value(&candidate, &[&a, &b, &other]).unwrap();
+        assert_eq!(valuation.probability, dec!(0.65));
+        assert!(FeedValuer.value(&candidate, &[&other]).is_none());
+    }
+}
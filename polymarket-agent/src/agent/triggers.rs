@@ -0,0 +1,381 @@
+//! Event-driven cycle triggers.
+//!
+//! Between cycles a watcher polls the markets the agent holds and wakes it
+//! early when one moves sharply, nears its resolution time, or when a data
+//! source publishes something new about it. The agent still never cycles
+//! more often than `min_cooldown_seconds`.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::data::crypto::CryptoSource;
+use crate::data::news::NewsSource;
+use crate::data::sports::SportsSource;
+use crate::data::weather::WeatherSource;
+use crate::data::{DataAggregator, DataSource, MarketQuery};
+use crate::db::store::Store;
+use crate::market::category::infer_category;
+use crate::market::polymarket::PolymarketClient;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TriggerConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Least time between the starts of two cycles, however many triggers fire.
+    #[serde(default = "default_min_cooldown_seconds")]
+    pub min_cooldown_seconds: u64,
+    /// How often held markets' prices and end dates are polled.
+    #[serde(default = "default_poll_interval_seconds")]
+    pub poll_interval_seconds: u64,
+    /// YES price move since the last cycle, in probability points, that
+    /// triggers a cycle.
+    #[serde(default = "default_price_move_threshold")]
+    pub price_move_threshold: Decimal,
+    /// Trigger once when a held market is this close to its end date.
+    #[serde(default = "default_resolution_window_minutes")]
+    pub resolution_window_minutes: i64,
+    /// Data sources ("news", "sports", "weather", "crypto") whose new data
+    /// points on held markets trigger a cycle.
+    #[serde(default = "default_data_sources")]
+    pub data_sources: Vec<String>,
+    #[serde(default = "default_data_poll_interval_seconds")]
+    pub data_poll_interval_seconds: u64,
+}
+
+impl Default for TriggerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            min_cooldown_seconds: default_min_cooldown_seconds(),
+            poll_interval_seconds: default_poll_interval_seconds(),
+            price_move_threshold: default_price_move_threshold(),
+            resolution_window_minutes: default_resolution_window_minutes(),
+            data_sources: default_data_sources(),
+            data_poll_interval_seconds: default_data_poll_interval_seconds(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_min_cooldown_seconds() -> u64 {
+    120
+}
+
+fn default_poll_interval_seconds() -> u64 {
+    60
+}
+
+fn default_price_move_threshold() -> Decimal {
+    dec!(0.05)
+}
+
+fn default_resolution_window_minutes() -> i64 {
+    30
+}
+
+fn default_data_sources() -> Vec<String> {
+    vec!["news".to_string()]
+}
+
+fn default_data_poll_interval_seconds() -> u64 {
+    300
+}
+
+/// Why a cycle was triggered early.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerReason {
+    PriceMove { market_id: String, delta: Decimal },
+    NewData { source: String },
+    Resolution { market_id: String },
+}
+
+impl fmt::Display for TriggerReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PriceMove { market_id, delta } => write!(f, "price move {delta} on {market_id}"),
+            Self::NewData { source } => write!(f, "new {source} data"),
+            Self::Resolution { market_id } => write!(f, "{market_id} nearing resolution"),
+        }
+    }
+}
+
+/// Shared queue of pending triggers, cheap to clone.
+#[derive(Clone, Default)]
+pub struct TriggerHandle {
+    inner: Arc<TriggerInner>,
+}
+
+#[derive(Default)]
+struct TriggerInner {
+    pending: Mutex<Vec<TriggerReason>>,
+    notify: Notify,
+    /// Bumped at each cycle start so watchers rebase their references.
+    generation: AtomicU64,
+}
+
+impl TriggerHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask for an early cycle.
+    pub fn fire(&self, reason: TriggerReason) {
+        debug!(%reason, "Cycle trigger fired");
+        self.inner.pending.lock().unwrap().push(reason);
+        self.inner.notify.notify_one();
+    }
+
+    /// Wait for at least one trigger and return everything pending.
+    pub async fn wait(&self) -> Vec<TriggerReason> {
+        loop {
+            self.inner.notify.notified().await;
+            let reasons = std::mem::take(&mut *self.inner.pending.lock().unwrap());
+            if !reasons.is_empty() {
+                return reasons;
+            }
+        }
+    }
+
+    /// Drop triggers made stale by a cycle that is starting now.
+    pub fn cycle_started(&self) {
+        self.inner.pending.lock().unwrap().clear();
+        self.inner.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn generation(&self) -> u64 {
+        self.inner.generation.load(Ordering::SeqCst)
+    }
+}
+
+/// Detects YES price moves relative to each market's price at the last
+/// cycle (or the last move reported).
+#[derive(Debug)]
+pub struct MoveDetector {
+    threshold: Decimal,
+    reference: HashMap<String, Decimal>,
+}
+
+impl MoveDetector {
+    pub fn new(threshold: Decimal) -> Self {
+        Self {
+            threshold,
+            reference: HashMap::new(),
+        }
+    }
+
+    /// Record `price` and return the move if it crosses the threshold.
+    pub fn observe(&mut self, market_id: &str, price: Decimal) -> Option<Decimal> {
+        let reference = *self.reference.entry(market_id.to_string()).or_insert(price);
+        let delta = price - reference;
+        if delta.abs() >= self.threshold {
+            self.reference.insert(market_id.to_string(), price);
+            Some(delta)
+        } else {
+            None
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.reference.clear();
+    }
+}
+
+/// Whether a market ending at `end_date` is within `window` of `now`.
+pub fn resolution_due(
+    end_date: DateTime<Utc>,
+    now: DateTime<Utc>,
+    window: chrono::Duration,
+) -> bool {
+    end_date > now && end_date - now <= window
+}
+
+/// Build the data sources named in the trigger config.
+fn trigger_sources(names: &[String]) -> Vec<Box<dyn DataSource>> {
+    names
+        .iter()
+        .filter_map(|name| -> Option<Box<dyn DataSource>> {
+            match name.as_str() {
+                "news" => Some(Box::new(NewsSource::new())),
+                "sports" => Some(Box::new(SportsSource::new())),
+                "weather" => Some(Box::new(WeatherSource::new())),
+                "crypto" => Some(Box::new(CryptoSource::new())),
+                other => {
+                    warn!(source = other, "Unknown trigger data source — ignored");
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Spawn the watcher that fires `triggers` for held markets.
+pub fn spawn_watcher(
+    config: TriggerConfig,
+    polymarket: Arc<PolymarketClient>,
+    store: Store,
+    triggers: TriggerHandle,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut moves = MoveDetector::new(config.price_move_threshold);
+        let mut resolving: HashSet<String> = HashSet::new();
+        let window = chrono::Duration::minutes(config.resolution_window_minutes);
+        let data = DataAggregator::new(trigger_sources(&config.data_sources))
+            .with_chaos(polymarket.chaos());
+        let mut seen_data: HashSet<String> = HashSet::new();
+        let mut data_seeded = false;
+        let mut last_data_poll: Option<tokio::time::Instant> = None;
+        let mut generation = triggers.generation();
+
+        info!(
+            poll_s = config.poll_interval_seconds,
+            price_move = %config.price_move_threshold,
+            data_sources = ?config.data_sources,
+            "Cycle trigger watcher started"
+        );
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(config.poll_interval_seconds)).await;
+
+            if triggers.generation() != generation {
+                generation = triggers.generation();
+                moves.reset();
+            }
+
+            let held = match store.get_open_trades().await {
+                Ok(trades) => trades,
+                Err(e) => {
+                    warn!(error = %e, "Trigger watcher failed to load open trades");
+                    continue;
+                }
+            };
+            let mut markets: HashMap<String, String> = HashMap::new();
+            for trade in held {
+                markets
+                    .entry(trade.market_id)
+                    .or_insert_with(|| trade.market_question.unwrap_or_default());
+            }
+
+            let now = Utc::now();
+            for market_id in markets.keys() {
+                let (price, end_date) = match polymarket.get_price_and_end_date(market_id).await {
+                    Ok(quote) => quote,
+                    Err(e) => {
+                        debug!(market = %market_id, error = %e, "Trigger price poll failed");
+                        continue;
+                    }
+                };
+                if let Some(delta) = moves.observe(market_id, price) {
+                    triggers.fire(TriggerReason::PriceMove {
+                        market_id: market_id.clone(),
+                        delta,
+                    });
+                }
+                if end_date.is_some_and(|end| resolution_due(end, now, window))
+                    && resolving.insert(market_id.clone())
+                {
+                    triggers.fire(TriggerReason::Resolution {
+                        market_id: market_id.clone(),
+                    });
+                }
+            }
+
+            let data_due = last_data_poll.map_or(true, |t| {
+                t.elapsed() >= Duration::from_secs(config.data_poll_interval_seconds)
+            });
+            if data_due && !markets.is_empty() {
+                last_data_poll = Some(tokio::time::Instant::now());
+                let queries: Vec<MarketQuery> = markets
+                    .iter()
+                    .map(|(id, question)| MarketQuery {
+                        condition_id: id.clone(),
+                        question: question.clone(),
+                        category: infer_category(question),
+                    })
+                    .collect();
+                let mut fresh_sources = HashSet::new();
+                for point in data.fetch_all(&queries).await {
+                    let key = format!("{}:{}", point.source, point.payload);
+                    if seen_data.insert(key) && data_seeded {
+                        fresh_sources.insert(point.source);
+                    }
+                }
+                data_seeded = true;
+                for source in fresh_sources {
+                    triggers.fire(TriggerReason::NewData { source });
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_detector_fires_past_threshold_and_rebases() {
+        let mut moves = MoveDetector::new(dec!(0.05));
+        assert_eq!(moves.observe("m1", dec!(0.40)), None);
+        assert_eq!(moves.observe("m1", dec!(0.44)), None);
+        assert_eq!(moves.observe("m1", dec!(0.35)), Some(dec!(-0.05)));
+        // Reported moves become the new reference
+        assert_eq!(moves.observe("m1", dec!(0.38)), None);
+        moves.reset();
+        assert_eq!(moves.observe("m1", dec!(0.50)), None);
+    }
+
+    #[test]
+    fn test_resolution_due() {
+        let now = Utc::now();
+        let window = chrono::Duration::minutes(30);
+        assert!(resolution_due(
+            now + chrono::Duration::minutes(10),
+            now,
+            window
+        ));
+        assert!(!resolution_due(
+            now + chrono::Duration::hours(2),
+            now,
+            window
+        ));
+        assert!(!resolution_due(
+            now - chrono::Duration::minutes(1),
+            now,
+            window
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cycle_start_drops_pending_triggers() {
+        let triggers = TriggerHandle::new();
+        triggers.fire(TriggerReason::NewData {
+            source: "google_news".to_string(),
+        });
+        triggers.cycle_started();
+        triggers.fire(TriggerReason::Resolution {
+            market_id: "m1".to_string(),
+        });
+
+        let reasons = triggers.wait().await;
+        assert_eq!(
+            reasons,
+            vec![TriggerReason::Resolution {
+                market_id: "m1".to_string()
+            }]
+        );
+    }
+}
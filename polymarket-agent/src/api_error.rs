@@ -0,0 +1,225 @@
+//! Typed errors for external HTTP APIs.
+//!
+//! Retry decisions are made from the HTTP status rather than error text:
+//! 408, 429 and 5xx are transient (honoring `Retry-After`), other 4xx fail
+//! fast, and requests that never got a response are retried.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    /// The server answered with a non-success status.
+    #[error("{service} API {status}: {body}")]
+    Status {
+        service: &'static str,
+        status: StatusCode,
+        body: String,
+        retry_after: Option<Duration>,
+    },
+    /// No response arrived: connect failure, timeout or reset.
+    #[error("{service} request failed: {message}")]
+    Transport {
+        service: &'static str,
+        message: String,
+    },
+    /// A response arrived but its body could not be decoded.
+    #[error("{service} response could not be decoded: {message}")]
+    Decode {
+        service: &'static str,
+        message: String,
+    },
+}
+
+/// Whether a failed call is worth repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryClass {
+    /// Try again, after `retry_after` if the server asked for a delay.
+    Transient { retry_after: Option<Duration> },
+    /// Repeating the same request will fail the same way.
+    Permanent,
+}
+
+impl ApiError {
+    pub fn from_status(
+        service: &'static str,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: String,
+    ) -> Self {
+        Self::Status {
+            service,
+            status,
+            body,
+            retry_after: parse_retry_after(headers, Utc::now()),
+        }
+    }
+
+    pub fn from_reqwest(service: &'static str, err: &reqwest::Error) -> Self {
+        match err.status() {
+            Some(status) => Self::Status {
+                service,
+                status,
+                body: err.to_string(),
+                retry_after: None,
+            },
+            None if err.is_decode() => Self::Decode {
+                service,
+                message: err.to_string(),
+            },
+            None => Self::Transport {
+                service,
+                message: err.to_string(),
+            },
+        }
+    }
+
+    /// Wrap an SDK error, classifying it by any `reqwest::Error` in its
+    /// source chain. Errors without one are treated as transport failures.
+    pub fn from_sdk(service: &'static str, err: &(dyn std::error::Error + 'static)) -> Self {
+        let mut source = Some(err);
+        while let Some(e) = source {
+            if let Some(reqwest_err) = e.downcast_ref::<reqwest::Error>() {
+                let mut api_err = Self::from_reqwest(service, reqwest_err);
+                if let Self::Status { body, .. } = &mut api_err {
+                    *body = err.to_string();
+                }
+                return api_err;
+            }
+            source = e.source();
+        }
+        Self::Transport {
+            service,
+            message: err.to_string(),
+        }
+    }
+
+    /// Error for a non-success response, reading its body for the message.
+    pub async fn check(
+        service: &'static str,
+        resp: reqwest::Response,
+    ) -> Result<reqwest::Response, Self> {
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(resp);
+        }
+        let headers = resp.headers().clone();
+        let body = resp.text().await.unwrap_or_default();
+        Err(Self::from_status(service, status, &headers, body))
+    }
+
+    pub fn retry_class(&self) -> RetryClass {
+        match self {
+            Self::Status {
+                status,
+                retry_after,
+                ..
+            } if is_transient_status(*status) => RetryClass::Transient {
+                retry_after: *retry_after,
+            },
+            Self::Status { .. } | Self::Decode { .. } => RetryClass::Permanent,
+            Self::Transport { .. } => RetryClass::Transient { retry_after: None },
+        }
+    }
+}
+
+/// Classify an arbitrary error by the first [`ApiError`] or `reqwest::Error`
+/// in its chain. Anything else (e.g. an injected chaos fault) is transient.
+pub fn classify(err: &anyhow::Error) -> RetryClass {
+    for cause in err.chain() {
+        if let Some(api_err) = cause.downcast_ref::<ApiError>() {
+            return api_err.retry_class();
+        }
+        if let Some(reqwest_err) = cause.downcast_ref::<reqwest::Error>() {
+            return ApiError::from_reqwest("http", reqwest_err).retry_class();
+        }
+    }
+    RetryClass::Transient { retry_after: None }
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// `Retry-After` as either delay-seconds or an HTTP date.
+pub fn parse_retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .with_timezone(&Utc);
+    (at - now).to_std().ok().or(Some(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_error(code: u16) -> ApiError {
+        ApiError::Status {
+            service: "gamma",
+            status: StatusCode::from_u16(code).unwrap(),
+            body: "insufficient balance".to_string(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn test_classification_by_status() {
+        assert_eq!(status_error(400).retry_class(), RetryClass::Permanent);
+        assert_eq!(status_error(401).retry_class(), RetryClass::Permanent);
+        assert_eq!(status_error(404).retry_class(), RetryClass::Permanent);
+        // Body text no longer matters: a 503 mentioning "balance" is retried
+        assert_eq!(
+            status_error(503).retry_class(),
+            RetryClass::Transient { retry_after: None }
+        );
+        assert!(matches!(
+            status_error(429).retry_class(),
+            RetryClass::Transient { .. }
+        ));
+    }
+
+    #[test]
+    fn test_classify_walks_anyhow_chain() {
+        let err = anyhow::Error::new(status_error(403)).context("Failed to fetch markets");
+        assert_eq!(classify(&err), RetryClass::Permanent);
+
+        let chaos = anyhow::anyhow!("Chaos: injected gamma failure");
+        assert_eq!(
+            classify(&chaos),
+            RetryClass::Transient { retry_after: None }
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers, now), None);
+
+        headers.insert(RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(
+            parse_retry_after(&headers, now),
+            Some(Duration::from_secs(7))
+        );
+
+        headers.insert(
+            RETRY_AFTER,
+            "Thu, 01 Jan 2026 00:00:30 GMT".parse().unwrap(),
+        );
+        assert_eq!(
+            parse_retry_after(&headers, now),
+            Some(Duration::from_secs(30))
+        );
+    }
+}
@@ -4,18 +4,29 @@
 //! market scan → valuation → Kelly sizing → simulated execution.
 //! Tracks P&L, drawdown, and other statistics.
 
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use tracing::{info, warn};
 
+use crate::backtesting::fill_model::{FillModel, FillModelConfig};
 use crate::backtesting::historical::{self, HistoricalSnapshot};
 use crate::backtesting::results::{BacktestResults, BacktestTracker, SimulatedTrade};
 use crate::config::{AppConfig, RiskConfig, ValuationConfig};
+use crate::execution::fees::FeeModel;
+use crate::market::listings::{self, NewListingsConfig};
 use crate::market::models::{AgentState, Side};
 use crate::risk::kelly;
 use crate::risk::limits;
 use crate::risk::portfolio::PortfolioManager;
+use crate::risk::sizing;
 use crate::valuation::edge;
+use crate::valuation::fair_value::ValuationResult;
+
+/// Claude valuations of backtest markets, keyed by market id.
+pub type BacktestValuations = HashMap<String, ValuationResult>;
 
 /// Configuration for a backtest run.
 #[derive(Debug, Clone)]
@@ -29,6 +40,15 @@ pub struct BacktestConfig {
     pub max_evaluations_per_cycle: usize,
     /// Whether to skip Claude valuation and use market prices as fair values.
     pub skip_valuation: bool,
+    /// Spread, slippage and partial-fill simulation.
+    pub fill_model: FillModelConfig,
+    /// Which markets count as new listings. A market is first seen at its
+    /// earliest snapshot; those present at the start never count.
+    pub new_listings: NewListingsConfig,
+    /// Fees assumed by the worst-case loss check.
+    pub fees: FeeModel,
+    /// Slippage assumed by the worst-case loss check.
+    pub max_slippage_pct: Decimal,
 }
 
 impl BacktestConfig {
@@ -39,20 +59,65 @@ impl BacktestConfig {
             valuation_config: config.valuation.clone(),
             simulated_api_cost_per_eval: dec!(0.01),
             max_evaluations_per_cycle: 10,
-            skip_valuation: true, // Default: no Claude calls during backtest
+            skip_valuation: !config.backtest.claude.enabled,
+            fill_model: config.backtest.fill_model.clone(),
+            new_listings: config.scanning.new_listings.clone(),
+            fees: FeeModel::new(&config.execution.fees),
+            max_slippage_pct: config.execution.max_slippage_pct,
         }
     }
 }
 
+/// A resolved trade whose payout is credited at its market's end date.
+struct PendingPayout {
+    due: DateTime<Utc>,
+    trade_index: usize,
+    market_id: String,
+    outcome: Decimal,
+}
+
+/// Credit every pending payout due by `now` (all of them if `None`), in due
+/// order.
+fn settle_payouts(
+    pending: &mut Vec<PendingPayout>,
+    now: Option<DateTime<Utc>>,
+    tracker: &mut BacktestTracker,
+    portfolio: &mut PortfolioManager,
+) {
+    pending.sort_by_key(|p| p.due);
+    let due = pending
+        .iter()
+        .take_while(|p| now.map_or(true, |now| p.due <= now))
+        .count();
+    for payout in pending.drain(..due) {
+        tracker.resolve_trade(payout.trade_index, payout.outcome);
+        portfolio.remove_position(&payout.market_id);
+    }
+}
+
 /// Run a backtest over historical snapshots.
 ///
 /// In skip_valuation mode, uses the historical fair value (resolved outcome)
 /// as a proxy for Claude's valuation. This tests the sizing/execution pipeline
 /// without incurring API costs.
 pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -> BacktestResults {
+    run_backtest_with_valuations(snapshots, config, &BacktestValuations::new())
+}
+
+/// Run a backtest, taking fair values from `valuations` unless
+/// `skip_valuation` is set. Markets without a valuation are not evaluated.
+pub fn run_backtest_with_valuations(
+    snapshots: &[HistoricalSnapshot],
+    config: &BacktestConfig,
+    valuations: &BacktestValuations,
+) -> BacktestResults {
     let mut tracker = BacktestTracker::new(config.initial_balance);
     let mut portfolio = PortfolioManager::new(config.risk_config.clone());
     let mut trade_index = 0usize;
+    let mut fills = FillModel::new(&config.fill_model);
+    let mut pending: Vec<PendingPayout> = Vec::new();
+    let start = snapshots.iter().map(|s| s.timestamp).min();
+    let mut first_seen: HashMap<&str, DateTime<Utc>> = HashMap::new();
 
     // Group snapshots into cycles of max_evaluations_per_cycle
     let cycles: Vec<&[HistoricalSnapshot]> =
@@ -81,6 +146,27 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
         };
 
         for snapshot in *cycle_snapshots {
+            settle_payouts(
+                &mut pending,
+                Some(snapshot.timestamp),
+                &mut tracker,
+                &mut portfolio,
+            );
+
+            let seen = *first_seen
+                .entry(snapshot.market_id.as_str())
+                .or_insert(snapshot.timestamp);
+            let new_listing = Some(seen) != start
+                && listings::is_new(
+                    Decimal::from((snapshot.timestamp - seen).num_minutes()) / dec!(60),
+                    &config.new_listings,
+                );
+
+            let valuation = valuations.get(&snapshot.market_id);
+            if !config.skip_valuation && valuation.is_none() {
+                continue;
+            }
+
             // Simulate API cost
             tracker.record_api_cost(config.simulated_api_cost_per_eval);
 
@@ -95,22 +181,23 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
             };
 
             // Convert to candidate
-            let candidate = historical::snapshot_to_candidate(snapshot);
+            let mut candidate = historical::snapshot_to_candidate(snapshot);
+            candidate.new_listing = new_listing;
 
             // Simulate valuation: use a "noisy" version of the true outcome
             // as if Claude had some predictive ability but not perfect
-            let simulated_fair_value = if config.skip_valuation {
-                // Blend market price with outcome to simulate imperfect prediction
-                // 60% weight on true outcome + 40% on market price = decent edge
-                let noise_factor = dec!(0.60);
-                snapshot.yes_price * (Decimal::ONE - noise_factor) + resolved_outcome * noise_factor
-            } else {
-                // Would call Claude here in non-skip mode
-                snapshot.yes_price
+            let (simulated_fair_value, confidence) = match valuation {
+                Some(v) if !config.skip_valuation => (v.probability, v.confidence),
+                _ => {
+                    // Blend market price with outcome to simulate imperfect prediction
+                    // 60% weight on true outcome + 40% on market price = decent edge
+                    let noise_factor = dec!(0.60);
+                    let fair = snapshot.yes_price * (Decimal::ONE - noise_factor)
+                        + resolved_outcome * noise_factor;
+                    (fair, dec!(0.75)) // Simulated confidence
+                }
             };
 
-            let confidence = dec!(0.75); // Simulated confidence
-
             // Determine side and edge
             let market_price = snapshot.yes_price;
             let edge_val = simulated_fair_value - market_price;
@@ -133,7 +220,7 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
                 Decimal::ONE - simulated_fair_value
             };
 
-            let kelly_result = kelly::kelly_size(
+            let mut kelly_result = sizing::position_size(
                 fair_prob,
                 trade_price,
                 confidence,
@@ -141,6 +228,14 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
                 state,
                 &config.risk_config,
             );
+            let hours_to_resolution =
+                Decimal::from((snapshot.end_date - snapshot.timestamp).num_minutes()) / dec!(60);
+            kelly::apply_resolution_decay(
+                &mut kelly_result,
+                hours_to_resolution,
+                tracker.balance(),
+                &config.risk_config,
+            );
 
             if !kelly_result.should_trade() {
                 continue;
@@ -160,6 +255,8 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
                 },
                 &crate::valuation::edge::EdgeResult {
                     raw_edge: edge_result_edge,
+                    fee_per_share: Decimal::ZERO,
+                    net_edge: edge_result_edge,
                     side,
                     trade_price,
                     threshold: min_edge,
@@ -174,7 +271,11 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
                 continue;
             }
 
-            let position_size = portfolio.adjust_size(kelly_result.position_usd, tracker.balance());
+            let position_size = portfolio.adjust_size(
+                kelly_result.position_usd,
+                tracker.balance(),
+                &candidate.market.category,
+            );
             if position_size < config.risk_config.min_position_usd {
                 continue;
             }
@@ -196,37 +297,50 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
             }
 
             // Execute simulated trade
-            let shares = if trade_price > Decimal::ZERO {
-                liquidity_size / trade_price
-            } else {
+            if trade_price <= Decimal::ZERO {
+                continue;
+            }
+            let Some(fill) = fills.fill(snapshot, side, trade_price, liquidity_size / trade_price)
+            else {
                 continue;
             };
+            let shares = fill.shares;
+            let size_usd = shares * fill.price;
+
+            if let Some(cap) = config.risk_config.max_trade_loss_usd {
+                let worst_case = limits::worst_case_loss(
+                    shares,
+                    fill.price,
+                    &config.fees,
+                    config.max_slippage_pct,
+                );
+                if worst_case > cap {
+                    continue;
+                }
+            }
 
             let trade = SimulatedTrade {
                 market_id: snapshot.market_id.clone(),
                 question: snapshot.question.clone(),
+                category: candidate.market.category.as_str().to_string(),
                 side,
-                entry_price: trade_price,
-                size_usd: liquidity_size,
+                entry_price: fill.price,
+                size_usd,
                 shares,
                 fair_value: simulated_fair_value,
                 edge: edge_result_edge,
                 confidence,
                 outcome_price: None,
                 pnl: None,
+                new_listing,
             };
 
             tracker.record_entry(trade);
 
-            // Resolve immediately (backtest has the outcome)
             let outcome_for_side = match side {
                 Side::Yes => resolved_outcome,
                 Side::No => Decimal::ONE - resolved_outcome,
             };
-            tracker.resolve_trade(trade_index, outcome_for_side);
-            trade_index += 1;
-
-            // Add position to portfolio (and immediately remove since resolved)
             portfolio.add_position(crate::risk::portfolio::Position {
                 market_id: snapshot.market_id.clone(),
                 token_id: format!(
@@ -236,13 +350,31 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
                 ),
                 category: candidate.market.category,
                 side,
-                size_usd: liquidity_size,
-                entry_price: trade_price,
+                size_usd,
+                entry_price: fill.price,
+                bucket: crate::risk::buckets::DIRECTIONAL_BUCKET.to_string(),
             });
-            portfolio.remove_position(&snapshot.market_id);
+
+            if fills.delays_payouts() {
+                // Capital stays committed until the market ends
+                pending.push(PendingPayout {
+                    due: snapshot.end_date,
+                    trade_index,
+                    market_id: snapshot.market_id.clone(),
+                    outcome: outcome_for_side,
+                });
+            } else {
+                // Resolve immediately (backtest has the outcome)
+                tracker.resolve_trade(trade_index, outcome_for_side);
+                portfolio.remove_position(&snapshot.market_id);
+            }
+            trade_index += 1;
         }
     }
 
+    // Markets still open when the data ends pay out at their end dates
+    settle_payouts(&mut pending, None, &mut tracker, &mut portfolio);
+
     let results = tracker.finalize();
 
     info!(
@@ -262,6 +394,7 @@ pub fn run_backtest(snapshots: &[HistoricalSnapshot], config: &BacktestConfig) -
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     fn test_config() -> BacktestConfig {
         BacktestConfig {
@@ -272,6 +405,17 @@ mod tests {
                 max_total_exposure_pct: dec!(0.30),
                 max_positions_per_category: 3,
                 min_position_usd: dec!(1),
+                max_market_loss_usd: None,
+                max_trade_loss_usd: None,
+                resolution_decay: Vec::new(),
+                category_exposure_pct: HashMap::new(),
+                event_windows: Vec::new(),
+                buckets: Vec::new(),
+                approval: Default::default(),
+                hedge: Default::default(),
+                sizing: Default::default(),
+                rebalance: Default::default(),
+                drawdown: Default::default(),
             },
             valuation_config: ValuationConfig {
                 claude_model: "claude-sonnet-4-5-20250929".to_string(),
@@ -279,10 +423,17 @@ mod tests {
                 high_confidence_edge: dec!(0.03),
                 low_confidence_edge: dec!(0.08),
                 cache_ttl_seconds: 300,
+                prompt_token_budget: 2000,
+                crypto_pricer: Default::default(),
+                consistency: Default::default(),
             },
             simulated_api_cost_per_eval: dec!(0.01),
             max_evaluations_per_cycle: 10,
             skip_valuation: true,
+            fill_model: Default::default(),
+            new_listings: Default::default(),
+            fees: FeeModel::default(),
+            max_slippage_pct: dec!(0.02),
         }
     }
 
@@ -353,4 +504,23 @@ mod tests {
         // Should stop early due to low balance
         assert!(results.total_trades < 100);
     }
+
+    #[test]
+    fn test_backtest_delayed_payouts() {
+        let snapshots = historical::generate_synthetic(60);
+        let instant = run_backtest(&snapshots, &test_config());
+        let config = BacktestConfig {
+            fill_model: FillModelConfig {
+                delay_payouts: true,
+                ..Default::default()
+            },
+            ..test_config()
+        };
+        let delayed = run_backtest(&snapshots, &config);
+
+        // Every entered trade still resolves by the end of the run
+        assert!(delayed.total_trades > 0);
+        // Capital tied up in open positions leaves fewer trades affordable
+        assert!(delayed.total_trades <= instant.total_trades);
+    }
 }
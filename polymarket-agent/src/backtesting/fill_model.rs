@@ -0,0 +1,219 @@
+//! Backtest execution model.
+//!
+//! Snapshots only carry a midpoint-ish price and a spread, so without a model
+//! every backtest trade fills in full at mid. This model buys at the touch
+//! (half a spread away from mid), adds taker slippage proportional to how
+//! much of the visible depth the order consumes, and fills only what a
+//! randomly placed queue position leaves available. Depth is synthesised
+//! from 24h volume.
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::backtesting::historical::HistoricalSnapshot;
+use crate::market::models::Side;
+use crate::rng::SplitMix64;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FillModelConfig {
+    /// Off: fill in full at the reference price, as the backtester did
+    /// before this model existed.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Price impact, as a fraction of the touch price, of an order taking
+    /// the whole visible depth. Smaller orders pay proportionally less.
+    #[serde(default = "default_taker_slippage_pct")]
+    pub taker_slippage_pct: Decimal,
+    /// Visible depth at the touch, as a share of 24h volume (USD).
+    #[serde(default = "default_depth_volume_share")]
+    pub depth_volume_share: Decimal,
+    /// Least share of visible depth left to us after orders queued ahead.
+    #[serde(default = "default_min_queue_share")]
+    pub min_queue_share: Decimal,
+    /// Credit resolution payouts at the market's end date rather than on
+    /// entry, so capital stays tied up and drawdowns follow real timing.
+    #[serde(default)]
+    pub delay_payouts: bool,
+    #[serde(default = "default_seed")]
+    pub seed: u64,
+}
+
+impl Default for FillModelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            taker_slippage_pct: default_taker_slippage_pct(),
+            depth_volume_share: default_depth_volume_share(),
+            min_queue_share: default_min_queue_share(),
+            delay_payouts: false,
+            seed: default_seed(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_taker_slippage_pct() -> Decimal {
+    dec!(0.02)
+}
+
+fn default_depth_volume_share() -> Decimal {
+    dec!(0.01)
+}
+
+fn default_min_queue_share() -> Decimal {
+    dec!(0.5)
+}
+
+fn default_seed() -> u64 {
+    42
+}
+
+/// A simulated execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedFill {
+    pub price: Decimal,
+    pub shares: Decimal,
+}
+
+pub struct FillModel {
+    config: FillModelConfig,
+    rng: SplitMix64,
+}
+
+impl FillModel {
+    pub fn new(config: &FillModelConfig) -> Self {
+        Self {
+            config: config.clone(),
+            rng: SplitMix64::new(config.seed),
+        }
+    }
+
+    pub fn delays_payouts(&self) -> bool {
+        self.config.delay_payouts
+    }
+
+    /// Fill a buy of `shares` on `side`. `reference_price` is the price the
+    /// strategy saw, used as-is when the model is disabled. Returns `None`
+    /// if nothing fills.
+    pub fn fill(
+        &mut self,
+        snapshot: &HistoricalSnapshot,
+        side: Side,
+        reference_price: Decimal,
+        shares: Decimal,
+    ) -> Option<SimulatedFill> {
+        if !self.config.enabled {
+            return Some(SimulatedFill {
+                price: reference_price,
+                shares,
+            });
+        }
+
+        // Buying YES lifts the ask; buying NO lifts the NO ask, 1 - YES bid
+        let half_spread = snapshot.spread / dec!(2);
+        let touch = match side {
+            Side::Yes => snapshot.yes_price + half_spread,
+            Side::No => snapshot.no_price + half_spread,
+        }
+        .min(dec!(0.99));
+        if touch <= Decimal::ZERO {
+            return None;
+        }
+
+        let depth_shares = snapshot.volume_24h * self.config.depth_volume_share / touch;
+        let queue_share = self.config.min_queue_share
+            + (Decimal::ONE - self.config.min_queue_share)
+                * Decimal::from_f64(self.rng.next_f64()).unwrap_or(Decimal::ONE);
+        let filled = shares.min(depth_shares * queue_share).round_dp(2);
+        if filled <= Decimal::ZERO {
+            return None;
+        }
+
+        let consumed = if depth_shares > Decimal::ZERO {
+            (filled / depth_shares).min(Decimal::ONE)
+        } else {
+            Decimal::ONE
+        };
+        let price = (touch * (Decimal::ONE + self.config.taker_slippage_pct * consumed))
+            .min(dec!(0.99))
+            .round_dp(4);
+
+        Some(SimulatedFill {
+            price,
+            shares: filled,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn snapshot(volume_24h: Decimal) -> HistoricalSnapshot {
+        HistoricalSnapshot {
+            timestamp: Utc::now(),
+            market_id: "m1".to_string(),
+            question: "Will it happen?".to_string(),
+            category: "crypto".to_string(),
+            yes_price: dec!(0.40),
+            no_price: dec!(0.60),
+            volume_24h,
+            spread: dec!(0.04),
+            end_date: Utc::now() + chrono::Duration::days(3),
+            resolved_outcome: Some(Decimal::ONE),
+        }
+    }
+
+    #[test]
+    fn test_disabled_fills_at_reference() {
+        let mut model = FillModel::new(&FillModelConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        let fill = model
+            .fill(&snapshot(dec!(1000)), Side::Yes, dec!(0.40), dec!(50))
+            .unwrap();
+        assert_eq!(
+            fill,
+            SimulatedFill {
+                price: dec!(0.40),
+                shares: dec!(50)
+            }
+        );
+    }
+
+    #[test]
+    fn test_pays_spread_and_slippage() {
+        let mut model = FillModel::new(&FillModelConfig::default());
+        // Deep book: 1% of $1M at 0.42 ≈ 23.8k shares, so 10 fill in full
+        let fill = model
+            .fill(&snapshot(dec!(1000000)), Side::Yes, dec!(0.40), dec!(10))
+            .unwrap();
+        assert_eq!(fill.shares, dec!(10));
+        // Pays half the spread; a sliver of depth adds negligible slippage
+        assert_eq!(fill.price, dec!(0.42));
+
+        let no = model
+            .fill(&snapshot(dec!(1000000)), Side::No, dec!(0.60), dec!(10))
+            .unwrap();
+        assert!(no.price >= dec!(0.62));
+    }
+
+    #[test]
+    fn test_thin_book_partially_fills() {
+        let mut model = FillModel::new(&FillModelConfig::default());
+        // 1% of $1000 at 0.42 ≈ 23.8 shares visible, at least half ours
+        let fill = model
+            .fill(&snapshot(dec!(1000)), Side::Yes, dec!(0.40), dec!(100))
+            .unwrap();
+        assert!(fill.shares < dec!(24) && fill.shares >= dec!(11.9));
+        // Taking most of the level pays most of the slippage
+        assert!(fill.price > dec!(0.424));
+    }
+}
@@ -13,7 +13,8 @@ use std::path::Path;
 use std::str::FromStr;
 
 use crate::market::models::{
-    Market, MarketCandidate, MarketCategory, OrderBookSnapshot, PriceLevel, TokenInfo,
+    BookLiquidity, Market, MarketCandidate, MarketCategory, OrderBookSnapshot, PriceLevel,
+    TokenInfo,
 };
 
 /// A historical market snapshot representing one point in time.
@@ -176,6 +177,11 @@ pub fn snapshot_to_candidate(snapshot: &HistoricalSnapshot) -> MarketCandidate {
         category,
         volume_24h: snapshot.volume_24h,
         active: true,
+        event_id: None,
+        event_slug: None,
+        neg_risk: false,
+        description: String::new(),
+        resolution_source: None,
     };
 
     let midpoint = (snapshot.yes_price + (Decimal::ONE - snapshot.no_price)) / dec!(2);
@@ -196,9 +202,15 @@ pub fn snapshot_to_candidate(snapshot: &HistoricalSnapshot) -> MarketCandidate {
         midpoint,
         implied_probability: snapshot.yes_price,
         timestamp: snapshot.timestamp,
+        liquidity: BookLiquidity::TwoSided,
     };
 
-    MarketCandidate { market, order_book }
+    MarketCandidate {
+        market,
+        order_book,
+        new_listing: false,
+        liquidity_score: None,
+    }
 }
 
 #[cfg(test)]
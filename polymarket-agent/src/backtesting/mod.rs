@@ -1,3 +1,7 @@
 pub mod engine;
+pub mod fill_model;
 pub mod historical;
+pub mod replay;
+pub mod report;
 pub mod results;
+pub mod valuation;
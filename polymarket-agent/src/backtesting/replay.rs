@@ -0,0 +1,335 @@
+//! Replay of the agent's own history.
+//!
+//! Rebuilds past cycles from the `cycles` and `trades` tables and pushes the
+//! stored valuations back through sizing and portfolio constraints under a
+//! different config, answering "what if I had used quarter-Kelly?" without
+//! any new API calls. Only markets the agent actually traded can be replayed:
+//! a config looser than the original cannot surface opportunities that were
+//! filtered out at the time.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tracing::{info, warn};
+
+use crate::backtesting::engine::BacktestConfig;
+use crate::backtesting::results::{BacktestResults, BacktestTracker, SimulatedTrade};
+use crate::db::store::{CycleRecord, Store, TradeRecord};
+use crate::market::category::infer_category;
+use crate::market::listings::NEW_LISTING_TAG;
+use crate::market::models::{AgentState, MarketCategory, Side};
+use crate::risk::limits;
+use crate::risk::portfolio::{PortfolioManager, Position};
+use crate::risk::sizing;
+
+/// A past trading decision, reduced to what re-sizing needs.
+#[derive(Debug, Clone)]
+pub struct ReplayDecision {
+    pub market_id: String,
+    pub question: String,
+    pub category: MarketCategory,
+    pub bucket: String,
+    pub side: Side,
+    pub entry_price: Decimal,
+    /// YES probability from the stored valuation.
+    pub fair_value: Decimal,
+    pub confidence: Decimal,
+    pub edge: Decimal,
+    /// Payout per share for `side` (1 or 0), if the market has resolved.
+    pub outcome: Option<Decimal>,
+    /// The trade was tagged as a new listing.
+    pub new_listing: bool,
+}
+
+impl ReplayDecision {
+    /// Rebuild a decision from a stored trade. Cancelled trades and rows
+    /// with an unknown direction yield `None`.
+    pub fn from_trade(trade: &TradeRecord) -> Option<Self> {
+        let side = match trade.direction.as_str() {
+            "YES" => Side::Yes,
+            "NO" => Side::No,
+            _ => return None,
+        };
+        let outcome = match trade.status.as_str() {
+            "RESOLVED_WIN" => Some(Decimal::ONE),
+            "RESOLVED_LOSS" => Some(Decimal::ZERO),
+            "CANCELLED" => return None,
+            _ => None,
+        };
+        let question = trade.market_question.clone().unwrap_or_default();
+        Some(Self {
+            market_id: trade.market_id.clone(),
+            category: infer_category(&question),
+            question,
+            bucket: trade.bucket.clone(),
+            side,
+            entry_price: trade.entry_price,
+            fair_value: trade.claude_fair_value,
+            confidence: trade.confidence,
+            edge: trade.edge_at_entry,
+            outcome,
+            new_listing: trade.has_tag(NEW_LISTING_TAG),
+        })
+    }
+}
+
+/// One historical cycle: what it cost and what it decided.
+#[derive(Debug, Clone)]
+pub struct ReplayCycle {
+    pub cycle_number: i64,
+    pub api_cost: Decimal,
+    pub decisions: Vec<ReplayDecision>,
+}
+
+/// Group trades under their cycles, in cycle order. Trades from cycles
+/// missing from the `cycles` table get a zero-cost cycle of their own.
+pub fn build_cycles(cycles: &[CycleRecord], trades: &[TradeRecord]) -> Vec<ReplayCycle> {
+    let mut by_number: BTreeMap<i64, ReplayCycle> = cycles
+        .iter()
+        .map(|c| {
+            (
+                c.cycle_number,
+                ReplayCycle {
+                    cycle_number: c.cycle_number,
+                    api_cost: c.api_cost.unwrap_or_default(),
+                    decisions: Vec::new(),
+                },
+            )
+        })
+        .collect();
+
+    for trade in trades {
+        let Some(decision) = ReplayDecision::from_trade(trade) else {
+            continue;
+        };
+        by_number
+            .entry(trade.cycle)
+            .or_insert_with(|| ReplayCycle {
+                cycle_number: trade.cycle,
+                api_cost: Decimal::ZERO,
+                decisions: Vec::new(),
+            })
+            .decisions
+            .push(decision);
+    }
+
+    by_number.into_values().collect()
+}
+
+/// Load the agent's history from its database.
+pub async fn load_cycles(store: &Store) -> Result<Vec<ReplayCycle>> {
+    let cycles = store.get_all_cycles().await?;
+    let trades = store.get_all_trades().await?;
+    Ok(build_cycles(&cycles, &trades))
+}
+
+/// Re-run sizing and constraints over recorded cycles under `config`.
+///
+/// Each decision enters at its recorded price and, if its market resolved,
+/// settles immediately at the recorded outcome. Unresolved decisions keep
+/// their capital committed for the rest of the replay.
+pub fn run_replay(cycles: &[ReplayCycle], config: &BacktestConfig) -> BacktestResults {
+    let mut tracker = BacktestTracker::new(config.initial_balance);
+    let mut portfolio = PortfolioManager::new(config.risk_config.clone());
+    let mut trade_index = 0usize;
+
+    info!(
+        cycles = cycles.len(),
+        decisions = cycles.iter().map(|c| c.decisions.len()).sum::<usize>(),
+        initial_balance = %config.initial_balance,
+        "Starting replay"
+    );
+
+    for cycle in cycles {
+        tracker.record_api_cost(cycle.api_cost);
+
+        let bankroll = tracker.balance();
+        if bankroll <= config.risk_config.min_position_usd {
+            warn!(cycle = cycle.cycle_number, balance = %bankroll, "Insufficient balance — stopping replay");
+            break;
+        }
+        let state = if bankroll < dec!(10) {
+            AgentState::LowFuel
+        } else {
+            AgentState::Alive
+        };
+
+        for decision in &cycle.decisions {
+            if decision.edge < config.valuation_config.min_edge_threshold
+                || decision.entry_price <= Decimal::ZERO
+            {
+                continue;
+            }
+
+            let fair_prob = match decision.side {
+                Side::Yes => decision.fair_value,
+                Side::No => Decimal::ONE - decision.fair_value,
+            };
+            let kelly_result = sizing::position_size(
+                fair_prob,
+                decision.entry_price,
+                decision.confidence,
+                tracker.balance(),
+                state,
+                &config.risk_config,
+            );
+            if !kelly_result.should_trade() {
+                continue;
+            }
+
+            let size_usd = portfolio.adjust_size(
+                kelly_result.position_usd,
+                tracker.balance(),
+                &decision.category,
+            );
+            if size_usd < config.risk_config.min_position_usd {
+                continue;
+            }
+            let shares = size_usd / decision.entry_price;
+
+            if let Some(cap) = config.risk_config.max_trade_loss_usd {
+                let worst_case = limits::worst_case_loss(
+                    shares,
+                    decision.entry_price,
+                    &config.fees,
+                    config.max_slippage_pct,
+                );
+                if worst_case > cap {
+                    continue;
+                }
+            }
+
+            tracker.record_entry(SimulatedTrade {
+                market_id: decision.market_id.clone(),
+                question: decision.question.clone(),
+                category: decision.category.as_str().to_string(),
+                side: decision.side,
+                entry_price: decision.entry_price,
+                size_usd,
+                shares,
+                fair_value: decision.fair_value,
+                edge: decision.edge,
+                confidence: decision.confidence,
+                outcome_price: None,
+                pnl: None,
+                new_listing: decision.new_listing,
+            });
+
+            match decision.outcome {
+                Some(outcome) => tracker.resolve_trade(trade_index, outcome),
+                None => portfolio.add_position(Position {
+                    market_id: decision.market_id.clone(),
+                    token_id: format!("{}_{}", decision.market_id, decision.side),
+                    category: decision.category.clone(),
+                    side: decision.side,
+                    size_usd,
+                    entry_price: decision.entry_price,
+                    bucket: decision.bucket.clone(),
+                }),
+            }
+            trade_index += 1;
+        }
+    }
+
+    let results = tracker.finalize();
+    info!(
+        trades = results.total_trades,
+        pnl = %results.total_pnl,
+        roi = %results.roi_pct,
+        "Replay complete"
+    );
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    fn test_config() -> BacktestConfig {
+        let config: AppConfig = toml::from_str(include_str!("../../config/default.toml")).unwrap();
+        BacktestConfig {
+            // Small enough that max_trade_loss_usd never binds
+            initial_balance: dec!(50),
+            ..BacktestConfig::from_app_config(&config)
+        }
+    }
+
+    fn trade(cycle: i64, market_id: &str, status: &str) -> TradeRecord {
+        TradeRecord {
+            id: None,
+            cycle,
+            market_id: market_id.to_string(),
+            market_question: Some("Will BTC close above $100k?".to_string()),
+            direction: "YES".to_string(),
+            entry_price: dec!(0.40),
+            size: dec!(25),
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.50),
+            confidence: dec!(0.80),
+            kelly_raw: dec!(0.3),
+            kelly_adjusted: dec!(0.06),
+            status: status.to_string(),
+            pnl: None,
+            created_at: None,
+            resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_build_cycles_groups_trades() {
+        let cycles = vec![CycleRecord {
+            id: None,
+            cycle_number: 1,
+            markets_scanned: Some(10),
+            opportunities_found: Some(2),
+            trades_placed: Some(2),
+            api_cost: Some(dec!(0.05)),
+            bankroll: Some(dec!(100)),
+            unrealized_pnl: None,
+            agent_state: "ALIVE".to_string(),
+            duration_ms: None,
+            truncated_phases: None,
+            timings: Default::default(),
+            created_at: None,
+        }];
+        let trades = vec![
+            trade(1, "m1", "RESOLVED_WIN"),
+            trade(1, "m2", "CANCELLED"),
+            trade(3, "m3", "OPEN"),
+        ];
+
+        let replay = build_cycles(&cycles, &trades);
+        assert_eq!(replay.len(), 2);
+        assert_eq!(replay[0].api_cost, dec!(0.05));
+        assert_eq!(replay[0].decisions.len(), 1);
+        assert_eq!(replay[0].decisions[0].outcome, Some(Decimal::ONE));
+        assert_eq!(replay[1].cycle_number, 3);
+        assert_eq!(replay[1].decisions[0].outcome, None);
+    }
+
+    #[test]
+    fn test_smaller_kelly_fraction_sizes_down() {
+        let trades: Vec<_> = (1..=5)
+            .map(|i| trade(i, &format!("m{i}"), "RESOLVED_WIN"))
+            .collect();
+        let cycles = build_cycles(&[], &trades);
+
+        let full = run_replay(&cycles, &test_config());
+        let mut quarter_config = test_config();
+        quarter_config.risk_config.kelly_fraction = dec!(0.25);
+        let quarter = run_replay(&cycles, &quarter_config);
+
+        assert_eq!(full.total_trades, 5);
+        assert_eq!(quarter.total_trades, 5);
+        assert!(quarter.total_pnl < full.total_pnl);
+    }
+}
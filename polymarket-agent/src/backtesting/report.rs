@@ -0,0 +1,303 @@
+//! Backtest report export.
+//!
+//! Writes a run's results as JSON (summary plus every trade), a per-trade
+//! CSV, and a self-contained HTML page with equity-curve and drawdown charts
+//! drawn as inline SVG, so reports open offline and can be archived as-is.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::backtesting::results::BacktestResults;
+use crate::db::export::csv_field;
+
+const CSV_HEADER: &str =
+    "market_id,question,category,side,entry_price,size_usd,shares,fair_value,edge,confidence,outcome_price,pnl";
+
+const CHART_WIDTH: f64 = 800.0;
+const CHART_HEIGHT: f64 = 220.0;
+
+/// Write JSON, CSV and HTML reports for `results` into `dir`, named
+/// `<label>-<UTC timestamp>`. Returns the paths written.
+pub fn write_reports(results: &BacktestResults, dir: &Path, label: &str) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create report directory {}", dir.display()))?;
+    let stem = format!("{label}-{}", Utc::now().format("%Y%m%dT%H%M%SZ"));
+
+    let files = [
+        (format!("{stem}.json"), to_json(results)?),
+        (format!("{stem}-trades.csv"), trades_csv(results)),
+        (format!("{stem}.html"), html_report(results, label)),
+    ];
+    files
+        .into_iter()
+        .map(|(name, contents)| {
+            let path = dir.join(name);
+            fs::write(&path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Summary metrics and every trade as pretty-printed JSON.
+pub fn to_json(results: &BacktestResults) -> Result<String> {
+    serde_json::to_string_pretty(results).context("Failed to serialize backtest results")
+}
+
+/// One row per trade, unresolved trades with empty outcome and P&L.
+pub fn trades_csv(results: &BacktestResults) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for t in &results.trades {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            csv_field(&t.market_id),
+            csv_field(&t.question),
+            csv_field(&t.category),
+            t.side,
+            t.entry_price,
+            t.size_usd.round_dp(4),
+            t.shares.round_dp(4),
+            t.fair_value,
+            t.edge,
+            t.confidence,
+            t.outcome_price.map(|p| p.to_string()).unwrap_or_default(),
+            t.pnl.map(|p| p.round_dp(4).to_string()).unwrap_or_default(),
+        );
+    }
+    out
+}
+
+/// The initial balance followed by the balance after each resolution.
+pub fn equity_curve(results: &BacktestResults) -> Vec<Decimal> {
+    std::iter::once(results.initial_balance)
+        .chain(results.equity_curve.iter().map(|p| p.balance))
+        .collect()
+}
+
+/// Drawdown from the running peak at each point of `curve`, as a fraction.
+pub fn drawdowns(curve: &[Decimal]) -> Vec<Decimal> {
+    let mut peak = Decimal::ZERO;
+    curve
+        .iter()
+        .map(|&v| {
+            peak = peak.max(v);
+            if peak > Decimal::ZERO {
+                (peak - v) / peak
+            } else {
+                Decimal::ZERO
+            }
+        })
+        .collect()
+}
+
+/// A standalone HTML report: summary table, equity and drawdown charts,
+/// and the trade list.
+pub fn html_report(results: &BacktestResults, title: &str) -> String {
+    let curve = equity_curve(results);
+    let dd: Vec<Decimal> = drawdowns(&curve).iter().map(|d| -*d).collect();
+
+    let summary = [
+        ("Trades", results.total_trades.to_string()),
+        ("Win rate", pct(results.win_rate)),
+        ("Total P&L", usd(results.total_pnl)),
+        ("Net profit", usd(results.net_profit)),
+        ("API cost", usd(results.total_api_cost)),
+        ("ROI", pct(results.roi_pct)),
+        (
+            "Sharpe",
+            results
+                .sharpe_ratio
+                .map(|s| format!("{:.2}", s))
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        (
+            "Max drawdown",
+            format!(
+                "{} ({})",
+                usd(results.max_drawdown),
+                pct(results.max_drawdown_pct)
+            ),
+        ),
+        ("Profit factor", format!("{:.2}", results.profit_factor)),
+        ("Final balance", usd(results.final_balance)),
+    ];
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title>\n\
+         <style>body{{font-family:sans-serif;margin:2em;color:#222}}\
+         table{{border-collapse:collapse;margin-bottom:1.5em}}\
+         td,th{{border:1px solid #ccc;padding:4px 8px;text-align:right}}\
+         td:first-child,th:first-child{{text-align:left}}\
+         svg{{border:1px solid #ddd;background:#fafafa}}</style>\n\
+         </head><body>\n<h1>{title}</h1>\n<table>\n",
+        title = escape_html(title)
+    );
+    for (name, value) in &summary {
+        let _ = writeln!(html, "<tr><th>{name}</th><td>{value}</td></tr>");
+    }
+    html.push_str("</table>\n<h2>Equity curve</h2>\n");
+    html.push_str(&svg_line_chart(&curve, "#2b7bb9"));
+    html.push_str("\n<h2>Drawdown</h2>\n");
+    html.push_str(&svg_line_chart(&dd, "#c0392b"));
+    let sections = [
+        ("category", &results.by_category),
+        ("confidence", &results.by_confidence),
+        ("edge size", &results.by_edge),
+        ("listing", &results.by_listing),
+    ];
+    for (name, slices) in sections {
+        let _ = write!(
+            html,
+            "\n<h2>By {name}</h2>\n<table>\n<tr><th>Slice</th><th>Trades</th>\
+             <th>Win rate</th><th>Edge accuracy</th><th>P&amp;L</th></tr>\n"
+        );
+        for s in slices {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&s.label),
+                s.trades,
+                pct(s.win_rate),
+                pct(s.edge_accuracy),
+                usd(s.pnl),
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str(
+        "<h2>Trades</h2>\n<table>\n<tr><th>Market</th><th>Side</th><th>Entry</th>\
+         <th>Size</th><th>Edge</th><th>Outcome</th><th>P&amp;L</th></tr>\n",
+    );
+    for t in &results.trades {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&t.question),
+            t.side,
+            t.entry_price,
+            usd(t.size_usd),
+            pct(t.edge),
+            t.outcome_price.map(|p| p.to_string()).unwrap_or_default(),
+            t.pnl.map(usd).unwrap_or_default(),
+        );
+    }
+    html.push_str("</table>\n</body></html>\n");
+    html
+}
+
+/// Polyline of `values` scaled to the chart box, with the value range
+/// labelled on the left.
+fn svg_line_chart(values: &[Decimal], color: &str) -> String {
+    let points: Vec<f64> = values.iter().filter_map(|v| v.to_f64()).collect();
+    let min = points.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = points.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{CHART_HEIGHT}\">"
+    );
+    if points.len() >= 2 {
+        let range = if max > min { max - min } else { 1.0 };
+        let step = CHART_WIDTH / (points.len() - 1) as f64;
+        let coords: Vec<String> = points
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let y = CHART_HEIGHT - 10.0 - (v - min) / range * (CHART_HEIGHT - 20.0);
+                format!("{:.1},{:.1}", i as f64 * step, y)
+            })
+            .collect();
+        let _ = write!(
+            svg,
+            "<polyline fill=\"none\" stroke=\"{color}\" stroke-width=\"1.5\" points=\"{}\"/>\
+             <text x=\"4\" y=\"14\" font-size=\"11\">{max:.2}</text>\
+             <text x=\"4\" y=\"{}\" font-size=\"11\">{min:.2}</text>",
+            coords.join(" "),
+            CHART_HEIGHT - 4.0
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn usd(v: Decimal) -> String {
+    format!("${}", v.round_dp(2))
+}
+
+fn pct(v: Decimal) -> String {
+    format!("{:.1}%", v * dec!(100))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtesting::results::{BacktestTracker, SimulatedTrade};
+    use crate::market::models::Side;
+
+    fn results() -> BacktestResults {
+        let mut tracker = BacktestTracker::new(dec!(100));
+        for (i, outcome) in [Decimal::ONE, Decimal::ZERO, Decimal::ONE]
+            .into_iter()
+            .enumerate()
+        {
+            tracker.record_entry(SimulatedTrade {
+                market_id: format!("m{i}"),
+                question: "Will \"X\", or Y, happen?".to_string(),
+                category: "politics".to_string(),
+                side: Side::Yes,
+                entry_price: dec!(0.50),
+                size_usd: dec!(10),
+                shares: dec!(20),
+                fair_value: dec!(0.60),
+                edge: dec!(0.10),
+                confidence: dec!(0.8),
+                outcome_price: None,
+                pnl: None,
+                new_listing: false,
+            });
+            tracker.resolve_trade(i, outcome);
+        }
+        tracker.finalize()
+    }
+
+    #[test]
+    fn test_equity_curve_and_drawdown() {
+        let curve = equity_curve(&results());
+        // Win, loss, win of 20 shares at 0.50
+        assert_eq!(curve, vec![dec!(100), dec!(110), dec!(100), dec!(110)]);
+        let dd = drawdowns(&curve);
+        assert_eq!(dd[2], dec!(10) / dec!(110));
+        assert_eq!(dd[3], Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_csv_quotes_and_html_escapes() {
+        let results = results();
+        let csv = trades_csv(&results);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("m0,\"Will \"\"X\"\", or Y, happen?\",politics,YES,0.50,"));
+
+        let html = html_report(&results, "Backtest <synthetic>");
+        assert!(html.contains("Backtest &lt;synthetic&gt;"));
+        assert_eq!(html.matches("<polyline").count(), 2);
+        assert!(to_json(&results).unwrap().contains("\"trades\""));
+    }
+}
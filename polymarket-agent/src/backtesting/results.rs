@@ -5,15 +5,18 @@
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fmt;
 
 use crate::market::models::Side;
 
 /// A single simulated trade in the backtest.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SimulatedTrade {
     pub market_id: String,
     pub question: String,
+    pub category: String,
     pub side: Side,
     pub entry_price: Decimal,
     pub size_usd: Decimal,
@@ -25,6 +28,9 @@ pub struct SimulatedTrade {
     pub outcome_price: Option<Decimal>,
     /// Realized P&L after resolution.
     pub pnl: Option<Decimal>,
+    /// Entered in a market first seen shortly before, per
+    /// `[scanning.new_listings]`.
+    pub new_listing: bool,
 }
 
 impl SimulatedTrade {
@@ -42,10 +48,104 @@ impl SimulatedTrade {
     pub fn is_win(&self) -> bool {
         self.pnl.map(|p| p > Decimal::ZERO).unwrap_or(false)
     }
+
+    /// Whether the sign of the predicted edge matched the realized P&L.
+    pub fn edge_correct(&self) -> bool {
+        match self.pnl {
+            // Edge predicted positive return and trade was profitable
+            Some(pnl) => {
+                (self.edge > Decimal::ZERO && pnl > Decimal::ZERO)
+                    || (self.edge <= Decimal::ZERO && pnl <= Decimal::ZERO)
+            }
+            None => false,
+        }
+    }
+}
+
+/// Number of consecutive resolved trades each rolling Sharpe ratio covers.
+pub const ROLLING_SHARPE_WINDOW: usize = 20;
+
+/// Balance right after a trade resolved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EquityPoint {
+    /// Index into `BacktestResults::trades`.
+    pub trade_index: usize,
+    pub pnl: Decimal,
+    pub balance: Decimal,
+}
+
+/// Upper bounds of the confidence buckets; the last bucket is open-ended.
+const CONFIDENCE_BUCKETS: [Decimal; 4] = [dec!(0.6), dec!(0.7), dec!(0.8), dec!(0.9)];
+
+/// Upper bounds of the edge-size buckets; the last bucket is open-ended.
+const EDGE_BUCKETS: [Decimal; 4] = [dec!(0.05), dec!(0.10), dec!(0.15), dec!(0.20)];
+
+/// Realized results for one slice of trades (a category or bucket).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SliceStats {
+    pub label: String,
+    pub trades: u64,
+    pub wins: u64,
+    pub win_rate: Decimal,
+    pub edge_accuracy: Decimal,
+    pub pnl: Decimal,
+}
+
+/// Group resolved trades by `key`, a sort key plus display label.
+fn slice_stats<K: Ord>(
+    trades: &[&SimulatedTrade],
+    key: impl Fn(&SimulatedTrade) -> (K, String),
+) -> Vec<SliceStats> {
+    let mut slices: BTreeMap<K, SliceStats> = BTreeMap::new();
+    for t in trades {
+        let (order, label) = key(t);
+        let slice = slices.entry(order).or_insert_with(|| SliceStats {
+            label,
+            trades: 0,
+            wins: 0,
+            win_rate: Decimal::ZERO,
+            edge_accuracy: Decimal::ZERO,
+            pnl: Decimal::ZERO,
+        });
+        slice.trades += 1;
+        slice.wins += u64::from(t.is_win());
+        // Running count of correct calls; divided through below
+        slice.edge_accuracy += Decimal::from(u64::from(t.edge_correct()));
+        slice.pnl += t.pnl.unwrap_or_default();
+    }
+    slices
+        .into_values()
+        .map(|mut s| {
+            let n = Decimal::from(s.trades);
+            s.win_rate = Decimal::from(s.wins) / n;
+            s.edge_accuracy /= n;
+            s
+        })
+        .collect()
+}
+
+/// Index and label of the bucket `value` falls in, given ascending upper
+/// bounds; formatted as percentages when `pct` is set.
+fn bucket(value: Decimal, bounds: &[Decimal], pct: bool) -> (usize, String) {
+    let fmt = |d: Decimal| {
+        if pct {
+            format!("{}%", (d * dec!(100)).normalize())
+        } else {
+            d.normalize().to_string()
+        }
+    };
+    let index = bounds.iter().take_while(|b| value >= **b).count();
+    let label = match (index.checked_sub(1).map(|i| bounds[i]), bounds.get(index)) {
+        (None, Some(hi)) => format!("<{}", fmt(*hi)),
+        (Some(lo), Some(hi)) => format!("{}-{}", fmt(lo), fmt(*hi)),
+        (Some(lo), None) => format!(">={}", fmt(lo)),
+        (None, None) => "all".to_string(),
+    };
+    (index, label)
 }
 
 /// Aggregated results from a backtest run.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BacktestResults {
     pub total_trades: u64,
     pub wins: u64,
@@ -65,6 +165,23 @@ pub struct BacktestResults {
     pub edge_accuracy: Decimal,
     pub total_api_cost: Decimal,
     pub net_profit: Decimal,
+    /// Every trade entered, in entry order.
+    pub trades: Vec<SimulatedTrade>,
+    /// Balance after each resolution, in resolution order. Cash basis:
+    /// stakes leave the balance on entry and payouts return on resolution.
+    pub equity_curve: Vec<EquityPoint>,
+    /// Sharpe ratio over each window of `ROLLING_SHARPE_WINDOW` consecutive
+    /// resolutions; entry `i` ends at `equity_curve[i + WINDOW - 1]`.
+    pub rolling_sharpe: Vec<Option<Decimal>>,
+    /// Attribution by market category, sorted by category.
+    pub by_category: Vec<SliceStats>,
+    /// Attribution by valuation confidence bucket, ascending.
+    pub by_confidence: Vec<SliceStats>,
+    /// Attribution by edge-size bucket, ascending.
+    pub by_edge: Vec<SliceStats>,
+    /// New listings against established markets; empty when no trade was
+    /// in a new listing.
+    pub by_listing: Vec<SliceStats>,
 }
 
 impl fmt::Display for BacktestResults {
@@ -98,7 +215,31 @@ impl fmt::Display for BacktestResults {
             self.edge_accuracy * dec!(100),
             self.profit_factor,
             self.avg_pnl_per_trade,
-        )
+        )?;
+        let sections = [
+            ("category", &self.by_category),
+            ("confidence", &self.by_confidence),
+            ("edge", &self.by_edge),
+            ("listing", &self.by_listing),
+        ];
+        for (name, slices) in sections {
+            if slices.is_empty() {
+                continue;
+            }
+            write!(f, "\nBy {name}:")?;
+            for s in slices {
+                write!(
+                    f,
+                    "\n  {}: {} trades, {:.1}% win, {:.1}% edge accuracy, P&L ${}",
+                    s.label,
+                    s.trades,
+                    s.win_rate * dec!(100),
+                    s.edge_accuracy * dec!(100),
+                    s.pnl.round_dp(2)
+                )?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -109,6 +250,7 @@ pub struct BacktestTracker {
     peak_balance: Decimal,
     max_drawdown: Decimal,
     trades: Vec<SimulatedTrade>,
+    equity_curve: Vec<EquityPoint>,
     total_api_cost: Decimal,
 }
 
@@ -120,6 +262,7 @@ impl BacktestTracker {
             peak_balance: initial_balance,
             max_drawdown: Decimal::ZERO,
             trades: Vec::new(),
+            equity_curve: Vec::new(),
             total_api_cost: Decimal::ZERO,
         }
     }
@@ -154,6 +297,12 @@ impl BacktestTracker {
                 if drawdown > self.max_drawdown {
                     self.max_drawdown = drawdown;
                 }
+
+                self.equity_curve.push(EquityPoint {
+                    trade_index: index,
+                    pnl: self.trades[index].pnl.unwrap_or_default(),
+                    balance: self.balance,
+                });
             }
         }
     }
@@ -199,18 +348,7 @@ impl BacktestTracker {
         };
 
         // Edge accuracy: how often the predicted direction was correct
-        let correct_predictions = resolved
-            .iter()
-            .filter(|t| {
-                if let Some(pnl) = t.pnl {
-                    // Edge predicted positive return and trade was profitable
-                    (t.edge > Decimal::ZERO && pnl > Decimal::ZERO)
-                        || (t.edge <= Decimal::ZERO && pnl <= Decimal::ZERO)
-                } else {
-                    false
-                }
-            })
-            .count() as u64;
+        let correct_predictions = resolved.iter().filter(|t| t.edge_correct()).count() as u64;
 
         let edge_accuracy = if total_trades > 0 {
             Decimal::from(correct_predictions) / Decimal::from(total_trades)
@@ -257,6 +395,12 @@ impl BacktestTracker {
         let pnl_values: Vec<Decimal> = resolved.iter().filter_map(|t| t.pnl).collect();
         let sharpe_ratio = compute_sharpe(&pnl_values);
 
+        let resolution_pnl: Vec<Decimal> = self.equity_curve.iter().map(|p| p.pnl).collect();
+        let rolling_sharpe = resolution_pnl
+            .windows(ROLLING_SHARPE_WINDOW)
+            .map(compute_sharpe)
+            .collect();
+
         BacktestResults {
             total_trades,
             wins,
@@ -276,6 +420,26 @@ impl BacktestTracker {
             edge_accuracy,
             total_api_cost: self.total_api_cost,
             net_profit,
+            trades: self.trades.clone(),
+            equity_curve: self.equity_curve.clone(),
+            rolling_sharpe,
+            by_category: slice_stats(&resolved, |t| (t.category.clone(), t.category.clone())),
+            by_confidence: slice_stats(&resolved, |t| {
+                bucket(t.confidence, &CONFIDENCE_BUCKETS, false)
+            }),
+            by_edge: slice_stats(&resolved, |t| bucket(t.edge.abs(), &EDGE_BUCKETS, true)),
+            by_listing: if resolved.iter().any(|t| t.new_listing) {
+                slice_stats(&resolved, |t| {
+                    let label = if t.new_listing {
+                        "new listing"
+                    } else {
+                        "established"
+                    };
+                    (!t.new_listing, label.to_string())
+                })
+            } else {
+                Vec::new()
+            },
         }
     }
 }
@@ -333,6 +497,7 @@ mod tests {
         SimulatedTrade {
             market_id: "m1".to_string(),
             question: "Test?".to_string(),
+            category: "crypto".to_string(),
             side: Side::Yes,
             entry_price,
             size_usd,
@@ -342,6 +507,7 @@ mod tests {
             confidence: dec!(0.8),
             outcome_price: None,
             pnl: None,
+            new_listing: false,
         }
     }
 
@@ -447,4 +613,70 @@ mod tests {
         assert!(results.profit_factor > dec!(0.66));
         assert!(results.profit_factor < dec!(0.67));
     }
+
+    #[test]
+    fn test_equity_curve_and_category_breakdown() {
+        let mut tracker = BacktestTracker::new(dec!(100));
+        for i in 0..ROLLING_SHARPE_WINDOW + 1 {
+            let mut trade = make_trade(dec!(0.10), dec!(0.50), dec!(10));
+            if i % 3 == 0 {
+                trade.category = "sports".to_string();
+            }
+            tracker.record_entry(trade);
+            // Every third trade loses
+            let outcome = if i % 3 == 0 {
+                Decimal::ZERO
+            } else {
+                Decimal::ONE
+            };
+            tracker.resolve_trade(i, outcome);
+        }
+
+        let results = tracker.finalize();
+        assert_eq!(results.equity_curve.len(), ROLLING_SHARPE_WINDOW + 1);
+        // First trade: $10 stake lost
+        assert_eq!(results.equity_curve[0].balance, dec!(90));
+        assert_eq!(
+            results.equity_curve.last().unwrap().balance,
+            results.final_balance
+        );
+        assert_eq!(results.rolling_sharpe.len(), 2);
+        assert!(results.rolling_sharpe[0].is_some());
+
+        assert_eq!(results.by_category.len(), 2);
+        let sports = &results.by_category[1];
+        assert_eq!((sports.label.as_str(), sports.wins), ("sports", 0));
+        assert_eq!(sports.pnl, dec!(-70)); // 7 losses of $10
+    }
+
+    #[test]
+    fn test_confidence_and_edge_buckets() {
+        let mut tracker = BacktestTracker::new(dec!(1000));
+        let slices = [
+            (dec!(0.55), dec!(0.03), Decimal::ZERO),
+            (dec!(0.85), dec!(0.12), Decimal::ONE),
+            (dec!(0.85), dec!(0.25), Decimal::ONE),
+        ];
+        for (i, (confidence, edge, outcome)) in slices.into_iter().enumerate() {
+            let mut trade = make_trade(edge, dec!(0.50), dec!(10));
+            trade.confidence = confidence;
+            trade.new_listing = i == 0;
+            tracker.record_entry(trade);
+            tracker.resolve_trade(i, outcome);
+        }
+
+        let results = tracker.finalize();
+        let labels = |s: &[SliceStats]| s.iter().map(|s| s.label.clone()).collect::<Vec<_>>();
+        assert_eq!(labels(&results.by_confidence), vec!["<0.6", "0.8-0.9"]);
+        assert_eq!(labels(&results.by_edge), vec!["<5%", "10%-15%", ">=20%"]);
+
+        let high = &results.by_confidence[1];
+        assert_eq!((high.trades, high.win_rate), (2, Decimal::ONE));
+        assert_eq!(high.edge_accuracy, Decimal::ONE);
+        assert_eq!(results.by_confidence[0].edge_accuracy, Decimal::ZERO);
+        assert_eq!(
+            labels(&results.by_listing),
+            vec!["new listing", "established"]
+        );
+    }
 }
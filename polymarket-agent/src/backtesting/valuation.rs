@@ -0,0 +1,255 @@
+//! Claude valuations for backtests.
+//!
+//! Values each historical market once through the live valuation engine,
+//! stopping before a call would overrun the budget. Results are cached in a
+//! dedicated SQLite file so repeated runs over the same data cost nothing.
+//! Claude may already know how older markets resolved, so treat accuracy on
+//! data from before its training cutoff as an upper bound.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::backtesting::engine::{self, BacktestConfig, BacktestValuations};
+use crate::backtesting::historical::{self, HistoricalSnapshot};
+use crate::backtesting::results::BacktestResults;
+use crate::db::store::Store;
+use crate::valuation::fair_value::ValuationEngine;
+
+/// Cache TTL for backtest valuations: history doesn't change.
+pub const CACHE_TTL_SECONDS: u64 = u32::MAX as u64;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClaudeBacktestConfig {
+    /// Value snapshots with Claude instead of the noise model.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Hard cap on Claude spend per run, in USD.
+    #[serde(default = "default_budget_usd")]
+    pub budget_usd: Decimal,
+    /// SQLite file caching valuations and their costs across runs.
+    #[serde(default = "default_cache_db")]
+    pub cache_db: String,
+}
+
+impl Default for ClaudeBacktestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            budget_usd: default_budget_usd(),
+            cache_db: default_cache_db(),
+        }
+    }
+}
+
+fn default_budget_usd() -> Decimal {
+    dec!(5.0)
+}
+
+fn default_cache_db() -> String {
+    "data/backtest_valuations.db".to_string()
+}
+
+/// What valuing a set of snapshots produced and cost.
+#[derive(Debug, Default)]
+pub struct ValuationRun {
+    pub valuations: BacktestValuations,
+    pub spent: Decimal,
+    pub calls: usize,
+    pub cached: usize,
+    /// Markets left unvalued because the budget ran out.
+    pub skipped: usize,
+}
+
+/// Value each resolved market in `snapshots` once, from its earliest
+/// snapshot. Cached valuations are free; new calls stop once the next would
+/// exceed `budget`. `store` must be the database backing `engine`.
+pub async fn value_snapshots(
+    engine: &ValuationEngine,
+    store: &Store,
+    snapshots: &[HistoricalSnapshot],
+    budget: Decimal,
+    bankroll: Decimal,
+) -> Result<ValuationRun> {
+    let mut run = ValuationRun::default();
+    let mut seen = HashSet::new();
+    let cost_before = store.get_total_api_cost().await?;
+
+    for snapshot in snapshots {
+        if snapshot.resolved_outcome.is_none() || !seen.insert(snapshot.market_id.as_str()) {
+            continue;
+        }
+
+        if let Some(cached) = engine.get_cached_valuation(&snapshot.market_id).await? {
+            run.valuations.insert(snapshot.market_id.clone(), cached);
+            run.cached += 1;
+            continue;
+        }
+        if run.spent + engine.estimated_call_cost() > budget {
+            run.skipped += 1;
+            continue;
+        }
+
+        let candidate = historical::snapshot_to_candidate(snapshot);
+        match engine.evaluate(&candidate, &[], bankroll, 0).await {
+            Ok(Some(valuation)) => {
+                run.valuations.insert(snapshot.market_id.clone(), valuation);
+            }
+            Ok(None) => {}
+            Err(e) => warn!(market = %snapshot.market_id, error = %e, "Backtest valuation failed"),
+        }
+        run.calls += 1;
+        run.spent = store.get_total_api_cost().await? - cost_before;
+    }
+
+    info!(
+        valued = run.valuations.len(),
+        calls = run.calls,
+        cached = run.cached,
+        skipped = run.skipped,
+        spent = %run.spent,
+        budget = %budget,
+        "Backtest valuations ready"
+    );
+    Ok(run)
+}
+
+/// Claude-valued results beside the noise model over the same markets.
+#[derive(Debug, Clone)]
+pub struct ValuationComparison {
+    pub markets: usize,
+    pub baseline: BacktestResults,
+    pub claude: BacktestResults,
+}
+
+impl fmt::Display for ValuationComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "=== Claude vs Noise Model ({} markets) ===\n\
+             Edge accuracy: {:.1}% Claude vs {:.1}% baseline\n\
+             Trades: {} Claude vs {} baseline\n\
+             P&L: ${} Claude vs ${} baseline",
+            self.markets,
+            self.claude.edge_accuracy * dec!(100),
+            self.baseline.edge_accuracy * dec!(100),
+            self.claude.total_trades,
+            self.baseline.total_trades,
+            self.claude.total_pnl.round_dp(2),
+            self.baseline.total_pnl.round_dp(2),
+        )
+    }
+}
+
+/// Backtest the valued markets with Claude's fair values and with the
+/// noise model, so the two edge accuracies are directly comparable.
+pub fn compare(
+    snapshots: &[HistoricalSnapshot],
+    config: &BacktestConfig,
+    valuations: &BacktestValuations,
+) -> ValuationComparison {
+    let valued: Vec<HistoricalSnapshot> = snapshots
+        .iter()
+        .filter(|s| valuations.contains_key(&s.market_id))
+        .cloned()
+        .collect();
+
+    let baseline = engine::run_backtest(
+        &valued,
+        &BacktestConfig {
+            skip_valuation: true,
+            ..config.clone()
+        },
+    );
+    let claude = engine::run_backtest_with_valuations(
+        &valued,
+        &BacktestConfig {
+            skip_valuation: false,
+            ..config.clone()
+        },
+        valuations,
+    );
+
+    ValuationComparison {
+        markets: valuations.len(),
+        baseline,
+        claude,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::valuation::claude::ClaudeClient;
+    use crate::valuation::fair_value::{DataQuality, TimeSensitivity, ValuationResult};
+    use std::sync::Arc;
+
+    fn test_config() -> BacktestConfig {
+        let config: AppConfig = toml::from_str(include_str!("../../config/default.toml")).unwrap();
+        BacktestConfig {
+            max_evaluations_per_cycle: 5,
+            ..BacktestConfig::from_app_config(&config)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zero_budget_makes_no_calls() {
+        let store = Store::new(":memory:").await.unwrap();
+        let claude = ClaudeClient::new(
+            "test-key".to_string(),
+            "test-model".to_string(),
+            store.clone_for_parallel(),
+        );
+        let engine = ValuationEngine::new(
+            Arc::new(claude),
+            test_config().valuation_config,
+            store.clone_for_parallel(),
+        );
+        let snapshots = historical::generate_synthetic(10);
+
+        let run = value_snapshots(&engine, &store, &snapshots, Decimal::ZERO, dec!(100))
+            .await
+            .unwrap();
+        assert_eq!(run.calls, 0);
+        assert!(run.valuations.is_empty());
+        assert_eq!(
+            run.skipped,
+            snapshots
+                .iter()
+                .filter(|s| s.resolved_outcome.is_some())
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_compare_uses_claude_fair_values() {
+        let snapshots = historical::generate_synthetic(40);
+        // A valuer that always sides with the market shows no edge
+        let valuations: BacktestValuations = snapshots
+            .iter()
+            .map(|s| {
+                (
+                    s.market_id.clone(),
+                    ValuationResult {
+                        probability: s.yes_price,
+                        confidence: dec!(0.8),
+                        reasoning_summary: String::new(),
+                        key_factors: vec![],
+                        data_quality: DataQuality::Medium,
+                        time_sensitivity: TimeSensitivity::Days,
+                    },
+                )
+            })
+            .collect();
+
+        let comparison = compare(&snapshots, &test_config(), &valuations);
+        assert_eq!(comparison.claude.total_trades, 0);
+        assert!(comparison.to_string().contains("Claude vs Noise Model"));
+    }
+}
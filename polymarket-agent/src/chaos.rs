@@ -0,0 +1,286 @@
+//! Fault injection for resilience testing.
+//!
+//! When enabled, calls to Gamma, the CLOB, Claude and the data sources are
+//! randomly delayed or failed before they are sent, and responses that do
+//! arrive are randomly corrupted, according to per-service probabilities.
+//! Failures look like real ones — 5xx, 429 with `Retry-After`, timeouts —
+//! so they take the same retry paths. This exercises retries, backoff and
+//! survival logic in tests and long paper runs. Refused in live mode.
+
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use reqwest::StatusCode;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::api_error::ApiError;
+use crate::config::{AgentMode, ChaosConfig, FaultConfig};
+use crate::rng::SplitMix64;
+
+/// Bodies substituted for a malformed response: a proxy's error page, an
+/// empty body, JSON of the wrong shape. Truncation is the fourth kind.
+const MALFORMED_BODIES: [&str; 3] = [
+    "<html><head><title>502 Bad Gateway</title></head><body>nginx</body></html>",
+    "",
+    "{\"error\": null}",
+];
+
+/// External service a call goes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaosTarget {
+    Gamma,
+    Clob,
+    Claude,
+    /// External data sources (NOAA, ESPN, CoinGecko, news).
+    Data,
+}
+
+impl ChaosTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gamma => "gamma",
+            Self::Clob => "clob",
+            Self::Claude => "claude",
+            Self::Data => "data",
+        }
+    }
+}
+
+impl std::fmt::Display for ChaosTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Randomly delays or fails calls per [`ChaosConfig`]. A disabled injector
+/// is a no-op.
+#[derive(Debug)]
+pub struct ChaosInjector {
+    config: Option<ChaosConfig>,
+    rng: Mutex<SplitMix64>,
+}
+
+impl ChaosInjector {
+    pub fn disabled() -> Self {
+        Self {
+            config: None,
+            rng: Mutex::new(SplitMix64::new(0)),
+        }
+    }
+
+    /// Build from config. Fails if chaos is enabled in live mode.
+    pub fn new(config: &ChaosConfig, mode: AgentMode) -> Result<Self> {
+        if !config.enabled {
+            return Ok(Self::disabled());
+        }
+        if mode == AgentMode::Live {
+            bail!("Chaos injection is only allowed in paper or backtest mode");
+        }
+        warn!(seed = ?config.seed, "Chaos injection enabled — API calls will randomly fail");
+        let rng = match config.seed {
+            Some(seed) => SplitMix64::new(seed),
+            None => SplitMix64::from_time(),
+        };
+        Ok(Self {
+            config: Some(config.clone()),
+            rng: Mutex::new(rng),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    /// Call before an external request. May sleep, and returns an error when
+    /// a failure is injected.
+    pub async fn inject(&self, target: ChaosTarget) -> Result<()> {
+        let Some(config) = &self.config else {
+            return Ok(());
+        };
+        let faults = config.faults(target);
+
+        let (delay_ms, fail) = {
+            let mut rng = self.rng.lock().await;
+            let delay_ms = if rng.next_f64() < faults.delay_rate {
+                (rng.next_f64() * faults.max_delay_ms as f64) as u64
+            } else {
+                0
+            };
+            let fail = (rng.next_f64() < faults.failure_rate)
+                .then(|| injected_failure(target, rng.next_u64()));
+            (delay_ms, fail)
+        };
+
+        if delay_ms > 0 {
+            warn!(%target, delay_ms, "Chaos: delaying call");
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+        }
+        if let Some(error) = fail {
+            warn!(%target, error = %error, "Chaos: failing call");
+            return Err(error.into());
+        }
+        Ok(())
+    }
+
+    /// Pass a response body through, or with `malformed_rate` probability
+    /// return a corrupted one for the caller's parser to choke on.
+    pub async fn malform(&self, target: ChaosTarget, body: String) -> String {
+        let Some(config) = &self.config else {
+            return body;
+        };
+        let mut rng = self.rng.lock().await;
+        if rng.next_f64() >= config.faults(target).malformed_rate {
+            return body;
+        }
+        warn!(%target, "Chaos: malforming response");
+        let kind = (rng.next_u64() % (MALFORMED_BODIES.len() as u64 + 1)) as usize;
+        match MALFORMED_BODIES.get(kind) {
+            Some(replacement) => replacement.to_string(),
+            None => {
+                let mut cut = body.len() / 2;
+                while !body.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                body[..cut].to_string()
+            }
+        }
+    }
+
+    /// For responses decoded where the body can't be reached (inside the
+    /// CLOB SDK): with `malformed_rate` probability, fail as decoding would.
+    pub async fn decode_fault(&self, target: ChaosTarget) -> Result<()> {
+        let Some(config) = &self.config else {
+            return Ok(());
+        };
+        if self.rng.lock().await.next_f64() >= config.faults(target).malformed_rate {
+            return Ok(());
+        }
+        warn!(%target, "Chaos: malforming response");
+        Err(ApiError::Decode {
+            service: target.as_str(),
+            message: "Chaos: injected malformed response".to_string(),
+        }
+        .into())
+    }
+}
+
+/// A failure as the network or server would produce it: mostly 5xx, some
+/// rate limiting with a `Retry-After`, some timeouts.
+fn injected_failure(target: ChaosTarget, roll: u64) -> ApiError {
+    let service = target.as_str();
+    let status = |status: StatusCode, retry_after: Option<Duration>| ApiError::Status {
+        service,
+        status,
+        body: "Chaos: injected failure".to_string(),
+        retry_after,
+    };
+    match roll % 5 {
+        0 => status(StatusCode::INTERNAL_SERVER_ERROR, None),
+        1 => status(StatusCode::BAD_GATEWAY, None),
+        2 => status(StatusCode::SERVICE_UNAVAILABLE, None),
+        3 => status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(1))),
+        _ => ApiError::Transport {
+            service,
+            message: "Chaos: injected timeout".to_string(),
+        },
+    }
+}
+
+impl ChaosConfig {
+    fn faults(&self, target: ChaosTarget) -> &FaultConfig {
+        match target {
+            ChaosTarget::Gamma => &self.gamma,
+            ChaosTarget::Clob => &self.clob,
+            ChaosTarget::Claude => &self.claude,
+            ChaosTarget::Data => &self.data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_error::RetryClass;
+
+    fn config(failure_rate: f64) -> ChaosConfig {
+        ChaosConfig {
+            enabled: true,
+            seed: Some(42),
+            gamma: FaultConfig {
+                failure_rate,
+                ..Default::default()
+            },
+            clob: FaultConfig::default(),
+            claude: FaultConfig {
+                failure_rate: 1.0,
+                ..Default::default()
+            },
+            data: FaultConfig {
+                malformed_rate: 1.0,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_is_noop() {
+        let chaos = ChaosInjector::disabled();
+        assert!(!chaos.is_enabled());
+        assert!(chaos.inject(ChaosTarget::Claude).await.is_ok());
+    }
+
+    #[test]
+    fn test_refused_in_live_mode() {
+        assert!(ChaosInjector::new(&config(0.5), AgentMode::Live).is_err());
+        assert!(ChaosInjector::new(&config(0.5), AgentMode::Paper).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_failure_rates() {
+        let chaos = ChaosInjector::new(&config(0.3), AgentMode::Backtest).unwrap();
+        assert!(chaos.inject(ChaosTarget::Clob).await.is_ok());
+        assert!(chaos.inject(ChaosTarget::Claude).await.is_err());
+
+        let mut failures = 0;
+        for _ in 0..1000 {
+            if chaos.inject(ChaosTarget::Gamma).await.is_err() {
+                failures += 1;
+            }
+        }
+        assert!((200..400).contains(&failures), "failures = {failures}");
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_retryable_api_errors() {
+        let chaos = ChaosInjector::new(&config(0.0), AgentMode::Paper).unwrap();
+        for _ in 0..20 {
+            let err = chaos.inject(ChaosTarget::Claude).await.unwrap_err();
+            assert!(err.downcast_ref::<ApiError>().is_some());
+            assert!(matches!(
+                crate::api_error::classify(&err),
+                RetryClass::Transient { .. }
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_malformed_responses() {
+        let chaos = ChaosInjector::new(&config(0.0), AgentMode::Paper).unwrap();
+        let body = r#"[{"conditionId": "0xabc", "question": "Will it rain?"}]"#;
+
+        // Gamma has no malformed rate; data sources always malform
+        assert_eq!(
+            chaos.malform(ChaosTarget::Gamma, body.to_string()).await,
+            body
+        );
+        for _ in 0..20 {
+            let mangled = chaos.malform(ChaosTarget::Data, body.to_string()).await;
+            assert!(serde_json::from_str::<Vec<serde_json::Value>>(&mangled).is_err());
+        }
+
+        assert!(chaos.decode_fault(ChaosTarget::Gamma).await.is_ok());
+        let err = chaos.decode_fault(ChaosTarget::Data).await.unwrap_err();
+        assert_eq!(crate::api_error::classify(&err), RetryClass::Permanent);
+    }
+}
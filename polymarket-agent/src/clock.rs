@@ -0,0 +1,62 @@
+//! Source of the current time for timestamps the agent compares.
+//!
+//! Runs read the system clock. A simulation drives a manual clock through
+//! its simulated hours instead, so entry times, valuation ages and time to
+//! resolution follow simulated time and a seeded run replays exactly.
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+
+/// The system clock, or a manual one shared by all its clones.
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    manual: Option<Arc<Mutex<DateTime<Utc>>>>,
+}
+
+impl Clock {
+    /// A clock standing at `at` until [`Clock::set`] moves it.
+    pub fn manual(at: DateTime<Utc>) -> Self {
+        Self {
+            manual: Some(Arc::new(Mutex::new(at))),
+        }
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        match &self.manual {
+            Some(at) => *at.lock().unwrap_or_else(|e| e.into_inner()),
+            None => Utc::now(),
+        }
+    }
+
+    /// Move a manual clock to `at`. The system clock ignores this.
+    pub fn set(&self, at: DateTime<Utc>) {
+        if let Some(manual) = &self.manual {
+            *manual.lock().unwrap_or_else(|e| e.into_inner()) = at;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_manual_clock_shared_by_clones() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let clock = Clock::manual(start);
+        let copy = clock.clone();
+        assert_eq!(copy.now(), start);
+
+        clock.set(start + chrono::Duration::hours(3));
+        assert_eq!(copy.now(), start + chrono::Duration::hours(3));
+    }
+
+    #[test]
+    fn test_system_clock_ignores_set() {
+        let clock = Clock::default();
+        clock.set(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap());
+        assert!(clock.now() > Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap());
+    }
+}
@@ -1,20 +1,127 @@
+use std::collections::HashMap;
 use std::path::Path;
 
 use anyhow::{Context, Result};
 use rust_decimal::Decimal;
 use serde::Deserialize;
 
+use crate::agent::book_capture::BookCaptureConfig;
+use crate::agent::budget::CycleBudgetConfig;
+use crate::agent::control::KillSwitchConfig;
+use crate::agent::simulation::SimulationConfig;
+use crate::agent::triggers::TriggerConfig;
+use crate::backtesting::fill_model::FillModelConfig;
+use crate::backtesting::valuation::ClaudeBacktestConfig;
+use crate::data::health::SourceHealthConfig;
+use crate::db::batch::WriteBatchConfig;
+use crate::db::maintenance::MaintenanceConfig;
+use crate::execution::fees::FeeConfig;
+use crate::execution::order::PartialFillPolicy;
+use crate::execution::paper_matching::PaperMatchingConfig;
+use crate::execution::price_feed::PriceFeedConfig;
+use crate::execution::redemption::RedemptionConfig;
+use crate::execution::routing::RoutingConfig;
+use crate::execution::twap::TwapConfig;
+use crate::execution::wallet::WalletConfig;
+use crate::http_cache::HttpCacheConfig;
+use crate::market::book_cache::BookCacheConfig;
+use crate::market::liquidity::LiquidityConfig;
+use crate::market::listings::NewListingsConfig;
+use crate::market::rules::MarketRules;
+use crate::market::watchlist::WatchlistConfig;
+use crate::monitoring::anomaly::AnomalyConfig;
+use crate::monitoring::funding::FundingConfig;
+use crate::monitoring::health::HealthCheckConfig;
+use crate::monitoring::logger::LogFileConfig;
+use crate::monitoring::notify::email::EmailConfig;
+use crate::monitoring::notify::slack::SlackConfig;
+use crate::monitoring::notify::ChannelRouting;
+use crate::monitoring::signals::SignalConfig;
+use crate::monitoring::systemd::SystemdConfig;
+use crate::monitoring::watchdog::WatchdogConfig;
+use crate::monitoring::weekly::WeeklyReportConfig;
+use crate::risk::drawdown::DrawdownConfig;
+use crate::risk::events::EventWindow;
+use crate::risk::hedge::HedgeConfig;
+use crate::risk::rebalance::RebalanceConfig;
+use crate::risk::sizing::SizingConfig;
+use crate::risk::strategy::market_making::MarketMakingConfig;
+use crate::risk::strategy::sniping::SnipingConfig;
+use crate::risk::strategy::StrategyConfig;
+use crate::valuation::consistency::ConsistencyConfig;
+use crate::valuation::crypto_pricer::CryptoPricerConfig;
+use crate::valuation::relevance::RelevanceConfig;
+use crate::valuation::revaluation::RevaluationConfig;
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AppConfig {
     pub agent: AgentConfig,
     pub scanning: ScanningConfig,
     pub valuation: ValuationConfig,
+    #[serde(default)]
+    pub relevance: RelevanceConfig,
+    #[serde(default)]
+    pub revaluation: RevaluationConfig,
     pub risk: RiskConfig,
     pub execution: ExecutionConfig,
     pub monitoring: MonitoringConfig,
     pub polymarket: PolymarketConfig,
     pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub http_cache: HttpCacheConfig,
+    #[serde(default)]
+    pub book_cache: BookCacheConfig,
     pub database: DatabaseConfig,
+    #[serde(default)]
+    pub chaos: ChaosConfig,
+    #[serde(default)]
+    pub redemption: RedemptionConfig,
+    #[serde(default)]
+    pub wallet: WalletConfig,
+    /// USD prices for gas and balances held in POL.
+    #[serde(default)]
+    pub price_feed: PriceFeedConfig,
+    #[serde(default)]
+    pub backtest: BacktestSettings,
+    #[serde(default)]
+    pub simulation: SimulationConfig,
+    /// Concurrent strategies with their own bankrolls. Empty = one strategy.
+    #[serde(default)]
+    pub strategies: Vec<StrategyConfig>,
+    /// Two-sided quoting in wide-spread markets with a recent valuation.
+    #[serde(default)]
+    pub market_making: MarketMakingConfig,
+    /// Buying known outcomes of markets about to resolve.
+    #[serde(default)]
+    pub sniping: SnipingConfig,
+    /// Per-source error, staleness and completeness tracking.
+    #[serde(default)]
+    pub source_health: SourceHealthConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BacktestSettings {
+    #[serde(default)]
+    pub fill_model: FillModelConfig,
+    #[serde(default)]
+    pub claude: ClaudeBacktestConfig,
+    /// Directory backtest reports (JSON, CSV, HTML) are written to.
+    #[serde(default = "default_report_dir")]
+    pub report_dir: String,
+}
+
+impl Default for BacktestSettings {
+    fn default() -> Self {
+        Self {
+            fill_model: FillModelConfig::default(),
+            claude: ClaudeBacktestConfig::default(),
+            report_dir: default_report_dir(),
+        }
+    }
+}
+
+fn default_report_dir() -> String {
+    "data/reports".to_string()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
@@ -23,6 +130,8 @@ pub enum AgentMode {
     Paper,
     Live,
     Backtest,
+    /// Full agent loop against a seeded in-memory exchange.
+    Simulation,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -37,12 +146,31 @@ pub struct AgentConfig {
     /// Default: $5.00 — sufficient for ~550 Claude calls at ~$0.009 each.
     #[serde(default = "default_daily_api_budget")]
     pub daily_api_budget: Decimal,
+    /// How long shutdown waits for an in-flight cycle before abandoning it.
+    #[serde(default = "default_shutdown_timeout_seconds")]
+    pub shutdown_timeout_seconds: u64,
+    /// Events that wake the agent before `cycle_interval_seconds` elapses.
+    #[serde(default)]
+    pub triggers: TriggerConfig,
+    /// Periodic order book snapshots of held markets.
+    #[serde(default)]
+    pub book_capture: BookCaptureConfig,
+    /// File that halts trading while it exists.
+    #[serde(default)]
+    pub kill_switch: KillSwitchConfig,
+    /// Per-phase deadlines within `cycle_interval_seconds`.
+    #[serde(default)]
+    pub cycle_budget: CycleBudgetConfig,
 }
 
 fn default_daily_api_budget() -> Decimal {
     rust_decimal_macros::dec!(5.0)
 }
 
+fn default_shutdown_timeout_seconds() -> u64 {
+    60
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ScanningConfig {
     pub max_markets: usize,
@@ -50,6 +178,63 @@ pub struct ScanningConfig {
     pub max_resolution_days: u32,
     pub max_spread_pct: Decimal,
     pub categories: Vec<String>,
+    /// Minimum seconds between scans of each category. Categories not
+    /// listed are scanned every cycle.
+    #[serde(default)]
+    pub cadence_seconds: HashMap<String, u64>,
+    /// If non-empty, only markets matching these rules are traded.
+    #[serde(default)]
+    pub include: MarketRules,
+    /// Markets matching these rules are never traded.
+    #[serde(default)]
+    pub exclude: MarketRules,
+    /// Markets whose resolution-clarity score (0–1) falls below this are
+    /// skipped before valuation. 0 disables the screen.
+    #[serde(default)]
+    pub min_clarity_score: Decimal,
+    /// Consider markets whose book has orders on only one side. Their price
+    /// comes from the CLOB midpoint or last trade instead of the book.
+    #[serde(default)]
+    pub allow_one_sided_books: bool,
+    /// Markets evaluated this many times with no edge inside
+    /// `no_edge_lookback_hours` are queued behind fresh markets. 0 disables.
+    #[serde(default = "default_no_edge_deprioritize_after")]
+    pub no_edge_deprioritize_after: u32,
+    #[serde(default = "default_no_edge_lookback_hours")]
+    pub no_edge_lookback_hours: u64,
+    /// Order book requests in flight at once while scanning.
+    #[serde(default = "default_book_concurrency")]
+    pub book_concurrency: usize,
+    /// Give up on a single order book after this long; its market is
+    /// skipped for the cycle.
+    #[serde(default = "default_book_timeout_ms")]
+    pub book_timeout_ms: u64,
+    /// Flag markets first seen recently and give them their own
+    /// evaluation quota.
+    #[serde(default)]
+    pub new_listings: NewListingsConfig,
+    /// Topics whose markets are scanned with priority.
+    #[serde(default)]
+    pub watchlist: WatchlistConfig,
+    /// Score markets by their order book history; rank and size by it.
+    #[serde(default)]
+    pub liquidity: LiquidityConfig,
+}
+
+fn default_no_edge_deprioritize_after() -> u32 {
+    2
+}
+
+fn default_no_edge_lookback_hours() -> u64 {
+    24
+}
+
+fn default_book_concurrency() -> usize {
+    8
+}
+
+fn default_book_timeout_ms() -> u64 {
+    10_000
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -59,6 +244,20 @@ pub struct ValuationConfig {
     pub high_confidence_edge: Decimal,
     pub low_confidence_edge: Decimal,
     pub cache_ttl_seconds: u64,
+    /// Input tokens a valuation prompt may use; external data fills
+    /// whatever the market summary leaves.
+    #[serde(default = "default_prompt_token_budget")]
+    pub prompt_token_budget: usize,
+    /// Analytic pricing of crypto price-threshold markets.
+    #[serde(default)]
+    pub crypto_pricer: CryptoPricerConfig,
+    /// Cross-market checks on each cycle's valuations before trading.
+    #[serde(default)]
+    pub consistency: ConsistencyConfig,
+}
+
+fn default_prompt_token_budget() -> usize {
+    2000
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -68,6 +267,97 @@ pub struct RiskConfig {
     pub max_total_exposure_pct: Decimal,
     pub max_positions_per_category: u32,
     pub min_position_usd: Decimal,
+    /// Cumulative realized loss in one market after which it is never traded
+    /// again. Unset disables the blacklist.
+    #[serde(default)]
+    pub max_market_loss_usd: Option<Decimal>,
+    /// Absolute cap on a single trade's worst-case loss (stake plus taker
+    /// fees from `[execution.fees]` and slippage), applied after sizing. Unset disables the guard.
+    #[serde(default)]
+    pub max_trade_loss_usd: Option<Decimal>,
+    /// Curve shrinking the max position size as resolution approaches.
+    /// Empty = no decay.
+    #[serde(default)]
+    pub resolution_decay: Vec<ResolutionDecayPoint>,
+    /// Optional max exposure per category as a fraction of bankroll,
+    /// keyed by lowercase category name (e.g. `crypto = 0.10`).
+    #[serde(default)]
+    pub category_exposure_pct: HashMap<String, Decimal>,
+    /// Scheduled events (FOMC, CPI, elections) that tighten edge and size
+    /// limits for affected categories while their window is open.
+    #[serde(default)]
+    pub event_windows: Vec<EventWindow>,
+    /// Strategy buckets the bankroll is split into. Empty = one shared bankroll.
+    #[serde(default)]
+    pub buckets: Vec<BucketConfig>,
+    /// Human sign-off for large trades.
+    #[serde(default)]
+    pub approval: ApprovalConfig,
+    /// Hedge-or-exit response when re-valuation turns against a position.
+    #[serde(default)]
+    pub hedge: HedgeConfig,
+    /// Position sizing strategy (fractional Kelly by default).
+    #[serde(default)]
+    pub sizing: SizingConfig,
+    /// Exiting the weakest position to make room for a much better one.
+    #[serde(default)]
+    pub rebalance: RebalanceConfig,
+    /// Pausing new entries after a deep drawdown.
+    #[serde(default)]
+    pub drawdown: DrawdownConfig,
+}
+
+/// One point on the time-to-resolution sizing curve: `hours` before
+/// resolution, the max position size is scaled by `multiplier`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResolutionDecayPoint {
+    pub hours: Decimal,
+    pub multiplier: Decimal,
+}
+
+/// A named slice of the bankroll reserved for one strategy.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BucketConfig {
+    pub name: String,
+    /// Fraction of the total bankroll allocated to this bucket.
+    pub allocation_pct: Decimal,
+    /// Maximum open exposure as a fraction of the bucket's balance.
+    #[serde(default = "default_bucket_max_exposure_pct")]
+    pub max_exposure_pct: Decimal,
+}
+
+fn default_bucket_max_exposure_pct() -> Decimal {
+    Decimal::ONE
+}
+
+/// Trades at or above `threshold_usd` are parked until a human approves them
+/// from the dashboard, and expire after `ttl_seconds`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_approval_threshold_usd")]
+    pub threshold_usd: Decimal,
+    #[serde(default = "default_approval_ttl_seconds")]
+    pub ttl_seconds: u64,
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_usd: default_approval_threshold_usd(),
+            ttl_seconds: default_approval_ttl_seconds(),
+        }
+    }
+}
+
+fn default_approval_threshold_usd() -> Decimal {
+    rust_decimal_macros::dec!(25.0)
+}
+
+fn default_approval_ttl_seconds() -> u64 {
+    3600
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -76,17 +366,101 @@ pub struct ExecutionConfig {
     pub order_ttl_seconds: u64,
     pub max_slippage_pct: Decimal,
     pub max_retries: u32,
+    /// When the book can't fill the full size within `max_slippage_pct` of
+    /// the best price, shrink the order to what it can fill instead of
+    /// rejecting it.
+    #[serde(default = "default_reduce_on_impact")]
+    pub reduce_on_impact: bool,
+    /// Cancel or keep working the unfilled part of a partial fill.
+    #[serde(default)]
+    pub partial_fill: PartialFillPolicy,
+    /// Slicing of orders that are large relative to top-of-book depth.
+    #[serde(default)]
+    pub twap: TwapConfig,
+    /// Aggressive / passive / midpoint-peg execution style selection.
+    #[serde(default)]
+    pub routing: RoutingConfig,
+    /// Taker fee / maker rebate schedule.
+    #[serde(default)]
+    pub fees: FeeConfig,
+    /// Resting paper orders matched against the live book.
+    #[serde(default)]
+    pub paper_matching: PaperMatchingConfig,
+}
+
+fn default_reduce_on_impact() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct MonitoringConfig {
     pub log_level: String,
+    /// `json` for log shippers (Loki, Elasticsearch), `text` for a terminal.
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// Rolling log files alongside stdout; off by default.
+    #[serde(default)]
+    pub log_file: LogFileConfig,
     pub discord_enabled: bool,
+    /// Which alerts Discord receives; all by default.
+    #[serde(default)]
+    pub discord: ChannelRouting,
+    #[serde(default)]
+    pub slack: SlackConfig,
+    #[serde(default)]
+    pub email: EmailConfig,
     pub daily_summary_hour: u32,
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
+    /// `sd_notify` readiness, watchdog and shutdown notifications.
+    #[serde(default)]
+    pub systemd: SystemdConfig,
+    /// Readiness probe dependency checks.
+    #[serde(default)]
+    pub health: HealthCheckConfig,
+    #[serde(default)]
+    pub anomaly: AnomalyConfig,
+    #[serde(default)]
+    pub funding: FundingConfig,
+    #[serde(default)]
+    pub weekly_report: WeeklyReportConfig,
+    /// Opportunities exported to an external webhook.
+    #[serde(default)]
+    pub signals: SignalConfig,
     #[serde(default = "default_dashboard_port")]
     pub dashboard_port: u16,
     #[serde(default = "default_dashboard_bind")]
     pub dashboard_bind: String,
+    /// Externally reachable dashboard URL used in alert links. Defaults to
+    /// `http://{dashboard_bind}:{dashboard_port}`, with a wildcard bind
+    /// mapped to loopback.
+    #[serde(default)]
+    pub dashboard_public_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// One JSON object per event, carrying the enclosing spans' fields
+    /// (`cycle_id`, `trade_id`).
+    #[default]
+    Json,
+    Text,
+}
+
+impl MonitoringConfig {
+    pub fn dashboard_url(&self) -> String {
+        // A wildcard bind is reached over loopback from the same host
+        let host = match self.dashboard_bind.as_str() {
+            "0.0.0.0" => "127.0.0.1",
+            "::" | "[::]" => "[::1]",
+            bind => bind,
+        };
+        match &self.dashboard_public_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => format!("http://{host}:{}", self.dashboard_port),
+        }
+    }
 }
 
 fn default_dashboard_port() -> u16 {
@@ -106,15 +480,35 @@ pub struct PolymarketConfig {
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct RateLimitConfig {
+    /// Default quota for every API family without its own below.
     pub requests_per_second: u32,
     pub burst_size: u32,
     pub backoff_base_ms: u64,
     pub backoff_max_ms: u64,
+    /// Gamma market and event listings.
+    #[serde(default)]
+    pub gamma: Option<ApiQuota>,
+    /// CLOB order books, prices and history.
+    #[serde(default)]
+    pub clob_read: Option<ApiQuota>,
+    /// CLOB order placement and cancellation.
+    #[serde(default)]
+    pub clob_order: Option<ApiQuota>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiQuota {
+    pub requests_per_second: u32,
+    pub burst_size: u32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct DatabaseConfig {
     pub path: String,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+    #[serde(default)]
+    pub write_batch: WriteBatchConfig,
 }
 
 impl DatabaseConfig {
@@ -123,14 +517,78 @@ impl DatabaseConfig {
     }
 }
 
+/// Fault injection for paper and backtest runs. See [`crate::chaos`].
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChaosConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Fixed seed for reproducible fault sequences.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub gamma: FaultConfig,
+    #[serde(default)]
+    pub clob: FaultConfig,
+    #[serde(default)]
+    pub claude: FaultConfig,
+    /// External data sources, faulted per fetch.
+    #[serde(default)]
+    pub data: FaultConfig,
+}
+
+/// Fault probabilities for one external service.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FaultConfig {
+    /// Probability a call fails with a 5xx, a 429 or a timeout.
+    #[serde(default)]
+    pub failure_rate: f64,
+    /// Probability a call is delayed, by up to `max_delay_ms`.
+    #[serde(default)]
+    pub delay_rate: f64,
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// Probability a response that did arrive is corrupted: truncated, an
+    /// HTML error page, empty, or JSON of the wrong shape.
+    #[serde(default)]
+    pub malformed_rate: f64,
+}
+
+impl Default for FaultConfig {
+    fn default() -> Self {
+        Self {
+            failure_rate: 0.0,
+            delay_rate: 0.0,
+            max_delay_ms: default_max_delay_ms(),
+            malformed_rate: 0.0,
+        }
+    }
+}
+
+fn default_max_delay_ms() -> u64 {
+    2000
+}
+
 /// Secrets loaded exclusively from environment variables.
 /// Not serializable, not stored in config files.
+#[derive(Default)]
 pub struct Secrets {
     pub polymarket_private_key: Option<String>,
     pub anthropic_api_key: Option<String>,
     pub discord_webhook_url: Option<String>,
+    pub slack_webhook_url: Option<String>,
+    /// Receiver of exported trade signals.
+    pub signal_webhook_url: Option<String>,
+    /// SMTP login for email alerts.
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
     pub noaa_api_token: Option<String>,
     pub espn_api_key: Option<String>,
+    pub voyage_api_key: Option<String>,
+    /// Bearer token accepted by the dashboard.
+    pub dashboard_token: Option<String>,
+    /// Basic-auth credentials accepted by the dashboard.
+    pub dashboard_username: Option<String>,
+    pub dashboard_password: Option<String>,
 }
 
 impl Secrets {
@@ -139,28 +597,146 @@ impl Secrets {
             polymarket_private_key: std::env::var("POLYMARKET_PRIVATE_KEY").ok(),
             anthropic_api_key: std::env::var("ANTHROPIC_API_KEY").ok(),
             discord_webhook_url: std::env::var("DISCORD_WEBHOOK_URL").ok(),
+            slack_webhook_url: std::env::var("SLACK_WEBHOOK_URL").ok(),
+            signal_webhook_url: std::env::var("SIGNAL_WEBHOOK_URL").ok(),
+            smtp_username: std::env::var("SMTP_USERNAME").ok(),
+            smtp_password: std::env::var("SMTP_PASSWORD").ok(),
             noaa_api_token: std::env::var("NOAA_API_TOKEN").ok(),
             espn_api_key: std::env::var("ESPN_API_KEY").ok(),
+            voyage_api_key: std::env::var("VOYAGE_API_KEY").ok(),
+            dashboard_token: std::env::var("DASHBOARD_TOKEN").ok(),
+            dashboard_username: std::env::var("DASHBOARD_USERNAME").ok(),
+            dashboard_password: std::env::var("DASHBOARD_PASSWORD").ok(),
         }
     }
 }
 
+/// Built-in configuration, used when no config file is present.
+const DEFAULT_CONFIG: &str = include_str!("../config/default.toml");
+
+/// Config file path override.
+const CONFIG_PATH_VAR: &str = "POLYMARKET_AGENT_CONFIG";
+
+/// Prefix of per-setting overrides, e.g. `POLYMARKET_AGENT__AGENT__MODE=live`.
+const ENV_PREFIX: &str = "POLYMARKET_AGENT__";
+
 impl AppConfig {
-    /// Load configuration from config/default.toml, overlaying environment variables for secrets.
+    /// Load configuration from `$POLYMARKET_AGENT_CONFIG`, else
+    /// config/default.toml, else the built-in defaults, then apply
+    /// `POLYMARKET_AGENT__*` overrides. Secrets come from the environment.
     pub fn load() -> Result<(Self, Secrets)> {
         dotenvy::dotenv().ok();
 
-        let config_path = Path::new("config/default.toml");
-        let contents = std::fs::read_to_string(config_path)
-            .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+        let explicit = std::env::var(CONFIG_PATH_VAR).ok();
+        let config_path = Path::new(explicit.as_deref().unwrap_or("config/default.toml"));
+        let contents = match std::fs::read_to_string(config_path) {
+            Ok(contents) => contents,
+            // Containers can run on built-in defaults plus environment overrides
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && explicit.is_none() => {
+                DEFAULT_CONFIG.to_string()
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to read config file: {}", config_path.display())
+                })
+            }
+        };
 
-        let config: AppConfig =
-            toml::from_str(&contents).context("Failed to parse config/default.toml")?;
+        let config = Self::from_toml_with_env(&contents, std::env::vars())
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
 
         let secrets = Secrets::from_env();
 
         Ok((config, secrets))
     }
+
+    /// Parse `contents` with `POLYMARKET_AGENT__SECTION__KEY` variables from
+    /// `vars` applied on top. Values are TOML literals (`600`, `true`,
+    /// `["a"]`); anything else, or any value for a setting that is a
+    /// string, is taken as a plain string.
+    pub fn from_toml_with_env(
+        contents: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self> {
+        let mut root: toml::Value = toml::from_str(contents)?;
+        for (name, raw) in vars {
+            let Some(path) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let keys: Vec<String> = path.split("__").map(str::to_lowercase).collect();
+            if keys.iter().any(String::is_empty) {
+                anyhow::bail!("Invalid config override {name}");
+            }
+            set_path(&mut root, &keys, &raw).with_context(|| format!("Invalid {name}"))?;
+        }
+        Ok(root.try_into()?)
+    }
+
+    /// Check settings that parse but can't run together. Returns warnings
+    /// for settings that run but are probably mistakes.
+    pub fn validate(&self, secrets: &Secrets) -> Result<Vec<String>> {
+        let mut warnings = Vec::new();
+        if self.agent.cycle_interval_seconds == 0 {
+            anyhow::bail!("agent.cycle_interval_seconds must be positive");
+        }
+        if self.risk.kelly_fraction <= Decimal::ZERO || self.risk.kelly_fraction > Decimal::ONE {
+            anyhow::bail!(
+                "risk.kelly_fraction must be in (0, 1], got {}",
+                self.risk.kelly_fraction
+            );
+        }
+        if self.agent.death_balance_threshold > self.agent.low_fuel_threshold {
+            anyhow::bail!("agent.death_balance_threshold exceeds agent.low_fuel_threshold");
+        }
+        crate::risk::buckets::BankrollBuckets::new(self.risk.buckets.clone()).validate()?;
+        crate::risk::strategy::Strategies::new(self.strategies.clone()).validate(&self.risk)?;
+        self.sniping.validate()?;
+        self.scanning.liquidity.validate()?;
+        self.valuation.crypto_pricer.validate()?;
+        self.risk.drawdown.validate()?;
+
+        if self.agent.mode == AgentMode::Live && secrets.polymarket_private_key.is_none() {
+            anyhow::bail!("POLYMARKET_PRIVATE_KEY is required in live mode");
+        }
+        if secrets.anthropic_api_key.is_none() {
+            warnings.push("ANTHROPIC_API_KEY not set — no valuations will run".to_string());
+        }
+        let bind = &self.monitoring.dashboard_bind;
+        if !crate::monitoring::auth::is_loopback(bind)
+            && !crate::monitoring::auth::DashboardAuth::from_secrets(secrets).is_enabled()
+        {
+            warnings.push(format!(
+                "Dashboard binds {bind} without authentication — set DASHBOARD_TOKEN or DASHBOARD_USERNAME/DASHBOARD_PASSWORD"
+            ));
+        }
+        Ok(warnings)
+    }
+}
+
+/// Set `keys` in `root` to `raw`, creating tables along the way.
+fn set_path(root: &mut toml::Value, keys: &[String], raw: &str) -> Result<()> {
+    let (last, parents) = keys.split_last().context("empty key")?;
+    let mut table = root.as_table_mut().context("config root is not a table")?;
+    for key in parents {
+        table = table
+            .entry(key.clone())
+            .or_insert_with(|| toml::Value::Table(Default::default()))
+            .as_table_mut()
+            .with_context(|| format!("'{key}' is not a section"))?;
+    }
+    let value = match table.get(last) {
+        Some(toml::Value::String(_)) => toml::Value::String(raw.to_string()),
+        _ => parse_env_value(raw),
+    };
+    table.insert(last.clone(), value);
+    Ok(())
+}
+
+fn parse_env_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut t| t.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
 }
 
 #[cfg(test)]
@@ -178,10 +754,76 @@ mod tests {
         assert_eq!(config.polymarket.chain_id, 137);
     }
 
+    #[test]
+    fn test_parse_event_windows() {
+        let contents = std::fs::read_to_string("config/default.toml")
+            .expect("config/default.toml should exist");
+        let contents = format!(
+            "{contents}\n[[risk.event_windows]]\nname = \"CPI\"\ntime = \"2026-11-12T13:30:00Z\"\ncategories = [\"crypto\"]\n"
+        );
+        let config: AppConfig = toml::from_str(&contents).expect("should parse");
+        let window = &config.risk.event_windows[0];
+        assert_eq!(window.name, "CPI");
+        assert_eq!(window.hours_before, 6);
+        assert_eq!(
+            window.categories,
+            vec![crate::market::models::MarketCategory::Crypto]
+        );
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars = [
+            ("POLYMARKET_AGENT__AGENT__MODE", "live"),
+            ("POLYMARKET_AGENT__AGENT__CYCLE_INTERVAL_SECONDS", "300"),
+            ("POLYMARKET_AGENT__RISK__KELLY_FRACTION", "0.1"),
+            ("POLYMARKET_AGENT__MONITORING__DASHBOARD_BIND", "0.0.0.0"),
+            // A string setting stays a string even when it looks like a number
+            ("POLYMARKET_AGENT__DATABASE__PATH", "2026"),
+            ("POLYMARKET_AGENT__BOOK_CACHE__ENABLED", "false"),
+            ("UNRELATED", "x"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+        let config = AppConfig::from_toml_with_env(DEFAULT_CONFIG, vars).unwrap();
+        assert_eq!(config.agent.mode, AgentMode::Live);
+        assert_eq!(config.agent.cycle_interval_seconds, 300);
+        assert_eq!(config.risk.kelly_fraction, rust_decimal_macros::dec!(0.1));
+        assert_eq!(config.database.path, "2026");
+        assert!(!config.book_cache.enabled);
+        assert_eq!(config.monitoring.dashboard_url(), "http://127.0.0.1:8080");
+
+        let bad = [("POLYMARKET_AGENT__AGENT__MODE", "sideways")]
+            .map(|(k, v)| (k.to_string(), v.to_string()));
+        assert!(AppConfig::from_toml_with_env(DEFAULT_CONFIG, bad).is_err());
+        let empty = [("POLYMARKET_AGENT__AGENT____MODE", "live")]
+            .map(|(k, v)| (k.to_string(), v.to_string()));
+        assert!(AppConfig::from_toml_with_env(DEFAULT_CONFIG, empty).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = AppConfig::from_toml_with_env(DEFAULT_CONFIG, []).unwrap();
+        let secrets = Secrets::default();
+        assert!(config.validate(&secrets).is_ok());
+
+        let mut live = config.clone();
+        live.agent.mode = AgentMode::Live;
+        assert!(live.validate(&secrets).is_err());
+
+        let mut exposed = config;
+        exposed.monitoring.dashboard_bind = "0.0.0.0".to_string();
+        let warnings = exposed.validate(&secrets).unwrap();
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("without authentication")));
+    }
+
     #[test]
     fn test_database_url() {
         let db = DatabaseConfig {
             path: "test.db".to_string(),
+            maintenance: MaintenanceConfig::default(),
+            write_batch: WriteBatchConfig::default(),
         };
         assert_eq!(db.url(), "sqlite:test.db");
     }
@@ -12,6 +12,7 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::Deserialize;
 
+use crate::api_error::ApiError;
 use crate::data::{DataPoint, DataSource, MarketQuery};
 use crate::market::models::MarketCategory;
 
@@ -44,12 +45,14 @@ impl CryptoSource {
             "https://api.coingecko.com/api/v3/coins/markets?vs_currency=usd&ids={ids}&order=market_cap_desc&sparkline=false&price_change_percentage=24h,7d"
         );
 
-        let prices: Vec<CoinGeckoPrice> = self
+        let resp = self
             .client
             .get(&url)
             .send()
             .await
-            .context("CoinGecko request failed")?
+            .context("CoinGecko request failed")?;
+        let prices: Vec<CoinGeckoPrice> = ApiError::check("coingecko", resp)
+            .await?
             .json()
             .await
             .context("Failed to parse CoinGecko response")?;
@@ -0,0 +1,296 @@
+//! Per-source data health.
+//!
+//! A source that errors, returns stale observations or sends back broken
+//! payloads still contributes its self-reported `confidence` to
+//! [`compute_data_quality`](crate::data::quality::compute_data_quality)
+//! unless something notices. The tracker keeps the last `window` fetches of
+//! each source and scores it:
+//!
+//! `(1 − error rate) × (1 − stale fraction) × complete fraction`
+//!
+//! Once a source has at least `min_fetches` on record and scores below
+//! `degraded_below`, its data points' confidence is scaled by its score
+//! (never below `min_confidence_factor`). Consecutive degraded fetches are
+//! counted so the watchdog can alert on a source that stays degraded.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::data::DataPoint;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourceHealthConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Fetches per source kept for scoring.
+    #[serde(default = "default_window")]
+    pub window: usize,
+    /// Fetches needed before a source can be demoted.
+    #[serde(default = "default_min_fetches")]
+    pub min_fetches: usize,
+    /// Health score below which a source counts as degraded.
+    #[serde(default = "default_degraded_below")]
+    pub degraded_below: f64,
+    /// Floor on the confidence multiplier of a degraded source.
+    #[serde(default = "default_min_confidence_factor")]
+    pub min_confidence_factor: f64,
+}
+
+impl Default for SourceHealthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            window: default_window(),
+            min_fetches: default_min_fetches(),
+            degraded_below: default_degraded_below(),
+            min_confidence_factor: default_min_confidence_factor(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_window() -> usize {
+    20
+}
+
+fn default_min_fetches() -> usize {
+    3
+}
+
+fn default_degraded_below() -> f64 {
+    0.6
+}
+
+fn default_min_confidence_factor() -> f64 {
+    0.25
+}
+
+/// One fetch of one source.
+#[derive(Debug, Clone, Copy)]
+struct FetchSample {
+    ok: bool,
+    points: usize,
+    stale: usize,
+    complete: usize,
+}
+
+/// A source's health over its recent fetches.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SourceStatus {
+    pub fetches: usize,
+    pub error_rate: f64,
+    pub stale_fraction: f64,
+    pub complete_fraction: f64,
+    pub score: f64,
+    /// Consecutive fetches the source has been degraded for.
+    pub degraded_cycles: u32,
+}
+
+#[derive(Debug, Default)]
+struct SourceRecord {
+    samples: VecDeque<FetchSample>,
+    degraded_cycles: u32,
+}
+
+/// Rolling health of every source fetched so far.
+#[derive(Debug)]
+pub struct SourceHealthTracker {
+    config: SourceHealthConfig,
+    sources: BTreeMap<String, SourceRecord>,
+}
+
+impl SourceHealthTracker {
+    pub fn new(config: SourceHealthConfig) -> Self {
+        Self {
+            config,
+            sources: BTreeMap::new(),
+        }
+    }
+
+    /// Record a successful fetch of `points`, which go stale after
+    /// `freshness`.
+    pub fn record_success(
+        &mut self,
+        source: &str,
+        points: &[DataPoint],
+        freshness: Duration,
+        now: DateTime<Utc>,
+    ) {
+        let freshness = chrono::Duration::from_std(freshness).ok();
+        let sample = FetchSample {
+            ok: true,
+            points: points.len(),
+            stale: points
+                .iter()
+                .filter(|p| freshness.is_some_and(|f| now - p.timestamp > f))
+                .count(),
+            complete: points.iter().filter(|p| is_complete(&p.payload)).count(),
+        };
+        self.push(source, sample);
+    }
+
+    pub fn record_failure(&mut self, source: &str) {
+        let sample = FetchSample {
+            ok: false,
+            points: 0,
+            stale: 0,
+            complete: 0,
+        };
+        self.push(source, sample);
+    }
+
+    fn push(&mut self, source: &str, sample: FetchSample) {
+        let window = self.config.window.max(1);
+        let record = self.sources.entry(source.to_string()).or_default();
+        record.samples.push_back(sample);
+        while record.samples.len() > window {
+            record.samples.pop_front();
+        }
+        let degraded = self.config.enabled
+            && record.samples.len() >= self.config.min_fetches
+            && score(&record.samples) < self.config.degraded_below;
+        record.degraded_cycles = if degraded {
+            record.degraded_cycles + 1
+        } else {
+            0
+        };
+    }
+
+    /// Multiplier for the confidence of `source`'s data points: 1 unless
+    /// the source is degraded.
+    pub fn confidence_factor(&self, source: &str) -> Decimal {
+        let Some(record) = self.sources.get(source).filter(|r| r.degraded_cycles > 0) else {
+            return Decimal::ONE;
+        };
+        let factor = score(&record.samples).max(self.config.min_confidence_factor);
+        Decimal::from_f64(factor)
+            .map(|f| f.round_dp(2).min(Decimal::ONE))
+            .unwrap_or(Decimal::ONE)
+    }
+
+    pub fn status(&self, source: &str) -> Option<SourceStatus> {
+        let record = self.sources.get(source)?;
+        let (error_rate, stale_fraction, complete_fraction) = rates(&record.samples);
+        Some(SourceStatus {
+            fetches: record.samples.len(),
+            error_rate,
+            stale_fraction,
+            complete_fraction,
+            score: score(&record.samples),
+            degraded_cycles: record.degraded_cycles,
+        })
+    }
+
+    /// Sources currently degraded, with how many fetches in a row.
+    pub fn degraded(&self) -> BTreeMap<String, u32> {
+        self.sources
+            .iter()
+            .filter(|(_, r)| r.degraded_cycles > 0)
+            .map(|(source, r)| (source.clone(), r.degraded_cycles))
+            .collect()
+    }
+}
+
+/// Error rate over every fetch; stale and complete fractions over the
+/// points of successful ones.
+fn rates(samples: &VecDeque<FetchSample>) -> (f64, f64, f64) {
+    if samples.is_empty() {
+        return (0.0, 0.0, 1.0);
+    }
+    let failures = samples.iter().filter(|s| !s.ok).count();
+    let points: usize = samples.iter().map(|s| s.points).sum();
+    let error_rate = failures as f64 / samples.len() as f64;
+    if points == 0 {
+        return (error_rate, 0.0, 1.0);
+    }
+    let stale: usize = samples.iter().map(|s| s.stale).sum();
+    let complete: usize = samples.iter().map(|s| s.complete).sum();
+    (
+        error_rate,
+        stale as f64 / points as f64,
+        complete as f64 / points as f64,
+    )
+}
+
+fn score(samples: &VecDeque<FetchSample>) -> f64 {
+    let (error_rate, stale_fraction, complete_fraction) = rates(samples);
+    (1.0 - error_rate) * (1.0 - stale_fraction) * complete_fraction
+}
+
+/// Whether a payload carries usable fields: a non-empty object or array
+/// with at least one non-null value. Bare strings are what an unparseable
+/// response turns into.
+fn is_complete(payload: &serde_json::Value) -> bool {
+    match payload {
+        serde_json::Value::Object(fields) => fields.values().any(|v| !v.is_null()),
+        serde_json::Value::Array(items) => items.iter().any(|v| !v.is_null()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+    use rust_decimal_macros::dec;
+
+    fn point(payload: serde_json::Value, age_hours: i64) -> DataPoint {
+        DataPoint {
+            source: "noaa".to_string(),
+            category: MarketCategory::Weather,
+            timestamp: Utc::now() - chrono::Duration::hours(age_hours),
+            payload,
+            confidence: dec!(0.8),
+            relevance_to: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_failing_source_is_demoted_then_recovers() {
+        let mut tracker = SourceHealthTracker::new(SourceHealthConfig {
+            window: 4,
+            ..SourceHealthConfig::default()
+        });
+        let fresh = Duration::from_secs(3600);
+        let good = [point(serde_json::json!({ "temp": 70 }), 0)];
+
+        tracker.record_success("noaa", &good, fresh, Utc::now());
+        tracker.record_failure("noaa");
+        assert_eq!(tracker.confidence_factor("noaa"), Decimal::ONE);
+        // Three fetches, two failed: demoted to its score
+        tracker.record_failure("noaa");
+        assert_eq!(
+            tracker.degraded(),
+            BTreeMap::from([("noaa".to_string(), 1)])
+        );
+        assert_eq!(tracker.confidence_factor("noaa"), dec!(0.33));
+
+        // A stale, half-broken fetch keeps it degraded, at the floor
+        let junk = [
+            point(serde_json::json!("<html>502</html>"), 0),
+            point(serde_json::json!({ "temp": 70 }), 5),
+        ];
+        tracker.record_success("noaa", &junk, fresh, Utc::now());
+        let status = tracker.status("noaa").unwrap();
+        assert_eq!(status.degraded_cycles, 2);
+        assert_eq!(status.error_rate, 0.5);
+        assert!((status.stale_fraction - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(tracker.confidence_factor("noaa"), dec!(0.25));
+
+        // Clean fetches push the failures out of the window
+        for _ in 0..4 {
+            tracker.record_success("noaa", &good, fresh, Utc::now());
+        }
+        assert!(tracker.degraded().is_empty());
+        assert_eq!(tracker.confidence_factor("noaa"), Decimal::ONE);
+        assert_eq!(tracker.confidence_factor("espn"), Decimal::ONE);
+    }
+}
@@ -1,9 +1,13 @@
 pub mod crypto;
+pub mod health;
 pub mod news;
 pub mod quality;
 pub mod sports;
 pub mod weather;
+pub mod win_probability;
 
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::Result;
@@ -12,6 +16,9 @@ use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::chaos::{ChaosInjector, ChaosTarget};
+use crate::clock::Clock;
+use crate::data::health::{SourceHealthConfig, SourceHealthTracker};
 use crate::market::models::MarketCategory;
 
 /// Standardized data point output from any data source.
@@ -59,11 +66,92 @@ pub struct MarketQuery {
 /// Aggregates data from multiple sources.
 pub struct DataAggregator {
     sources: Vec<Box<dyn DataSource>>,
+    /// Consecutive failed fetches per source name, reset on success.
+    failures: Mutex<BTreeMap<String, u32>>,
+    /// Rolling error, staleness and completeness per source.
+    health: Mutex<SourceHealthTracker>,
+    chaos: Arc<ChaosInjector>,
+    /// Judges the freshness of fetched points.
+    clock: Clock,
 }
 
 impl DataAggregator {
     pub fn new(sources: Vec<Box<dyn DataSource>>) -> Self {
-        Self { sources }
+        Self {
+            sources,
+            failures: Mutex::new(BTreeMap::new()),
+            health: Mutex::new(SourceHealthTracker::new(SourceHealthConfig::default())),
+            chaos: Arc::new(ChaosInjector::disabled()),
+            clock: Clock::default(),
+        }
+    }
+
+    /// Score sources with `config` and demote degraded ones.
+    pub fn with_health(mut self, config: SourceHealthConfig) -> Self {
+        self.health = Mutex::new(SourceHealthTracker::new(config));
+        self
+    }
+
+    /// Inject faults into every source fetch. Malformed responses arrive
+    /// as data points whose payload is corrupted.
+    pub fn with_chaos(mut self, chaos: Arc<ChaosInjector>) -> Self {
+        self.chaos = chaos;
+        self
+    }
+
+    /// Judge freshness by `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Consecutive failed fetches of every source that has been queried.
+    pub fn consecutive_failures(&self) -> BTreeMap<String, u32> {
+        self.failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Sources currently degraded, with how many fetches in a row.
+    pub fn degraded_sources(&self) -> BTreeMap<String, u32> {
+        self.health
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .degraded()
+    }
+
+    /// Record a fetch of `source` in its health and scale the confidence
+    /// of `points` if that leaves it degraded.
+    fn record_health(&self, source: &dyn DataSource, points: Option<&mut [DataPoint]>) {
+        let mut health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(points) = points else {
+            health.record_failure(source.name());
+            return;
+        };
+        health.record_success(
+            source.name(),
+            points,
+            source.freshness_window(),
+            self.clock.now(),
+        );
+        let factor = health.confidence_factor(source.name());
+        if factor < Decimal::ONE {
+            tracing::warn!(
+                source = source.name(),
+                %factor,
+                "Data source degraded — confidence demoted"
+            );
+            for point in points {
+                point.confidence = (point.confidence * factor).round_dp(4);
+            }
+        }
+    }
+
+    fn record_fetch(&self, source: &str, ok: bool) {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        let count = failures.entry(source.to_string()).or_insert(0);
+        *count = if ok { 0 } else { *count + 1 };
     }
 
     /// Fetch data from all sources relevant to the given markets.
@@ -82,8 +170,19 @@ impl DataAggregator {
                 continue;
             }
 
-            match source.fetch(&relevant).await {
-                Ok(points) => {
+            let result = match self.chaos.inject(ChaosTarget::Data).await {
+                Ok(()) => source.fetch(&relevant).await,
+                Err(e) => Err(e),
+            };
+            self.record_fetch(source.name(), result.is_ok());
+            match result {
+                Ok(mut points) => {
+                    if self.chaos.is_enabled() {
+                        for point in &mut points {
+                            self.malform_payload(point).await;
+                        }
+                    }
+                    self.record_health(source.as_ref(), Some(points.as_mut_slice()));
                     tracing::info!(
                         source = source.name(),
                         points = points.len(),
@@ -92,6 +191,7 @@ impl DataAggregator {
                     all_data.extend(points);
                 }
                 Err(e) => {
+                    self.record_health(source.as_ref(), None);
                     tracing::warn!(
                         source = source.name(),
                         error = %e,
@@ -103,4 +203,13 @@ impl DataAggregator {
 
         all_data
     }
+
+    async fn malform_payload(&self, point: &mut DataPoint) {
+        let body = point.payload.to_string();
+        let mangled = self.chaos.malform(ChaosTarget::Data, body.clone()).await;
+        if mangled != body {
+            point.payload =
+                serde_json::from_str(&mangled).unwrap_or(serde_json::Value::String(mangled));
+        }
+    }
 }
@@ -10,6 +10,7 @@ use async_trait::async_trait;
 use chrono::Utc;
 use rust_decimal_macros::dec;
 
+use crate::api_error::ApiError;
 use crate::data::{DataPoint, DataSource, MarketQuery};
 use crate::market::models::MarketCategory;
 
@@ -43,7 +44,11 @@ impl NewsSource {
             let url =
                 format!("https://news.google.com/rss/search?q={encoded}&hl=en-US&gl=US&ceid=US:en");
 
-            match self.client.get(&url).send().await {
+            let response = match self.client.get(&url).send().await {
+                Ok(response) => ApiError::check("google_news", response).await,
+                Err(e) => Err(ApiError::from_reqwest("google_news", &e)),
+            };
+            match response {
                 Ok(response) => {
                     let body = response.text().await.unwrap_or_default();
                     // Parse RSS XML — extract title and link from <item> elements
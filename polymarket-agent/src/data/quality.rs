@@ -3,13 +3,14 @@
 //! Computes data quality from actual data characteristics (source count,
 //! freshness, confidence) instead of relying on Claude's self-report.
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 
 use crate::data::DataPoint;
 use crate::valuation::fair_value::DataQuality;
 
-/// Compute data quality programmatically from data point characteristics.
+/// Compute data quality programmatically from data point characteristics,
+/// judging freshness as of `now`.
 ///
 /// Scoring factors:
 /// - Coverage: number of distinct sources (capped at 5)
@@ -17,13 +18,11 @@ use crate::valuation::fair_value::DataQuality;
 /// - Confidence: average self-assessed confidence from data sources
 ///
 /// Weights: coverage 40%, freshness 30%, confidence 30%.
-pub fn compute_data_quality(data_points: &[DataPoint]) -> DataQuality {
+pub fn compute_data_quality(data_points: &[DataPoint], now: DateTime<Utc>) -> DataQuality {
     if data_points.is_empty() {
         return DataQuality::Low;
     }
 
-    let now = Utc::now();
-
     // Coverage: how many distinct sources contributed
     let mut sources: Vec<&str> = data_points.iter().map(|dp| dp.source.as_str()).collect();
     sources.sort();
@@ -75,7 +74,7 @@ mod tests {
 
     #[test]
     fn test_empty_data_is_low() {
-        assert_eq!(compute_data_quality(&[]), DataQuality::Low);
+        assert_eq!(compute_data_quality(&[], Utc::now()), DataQuality::Low);
     }
 
     #[test]
@@ -87,13 +86,13 @@ mod tests {
             make_data_point("google_news", 3, dec!(0.5)),
             make_data_point("extra", 1, dec!(0.8)),
         ];
-        assert_eq!(compute_data_quality(&points), DataQuality::High);
+        assert_eq!(compute_data_quality(&points, Utc::now()), DataQuality::High);
     }
 
     #[test]
     fn test_single_stale_source_is_low() {
         let points = vec![make_data_point("noaa", 48, dec!(0.3))];
-        assert_eq!(compute_data_quality(&points), DataQuality::Low);
+        assert_eq!(compute_data_quality(&points, Utc::now()), DataQuality::Low);
     }
 
     #[test]
@@ -102,6 +101,9 @@ mod tests {
             make_data_point("noaa", 6, dec!(0.7)),
             make_data_point("espn", 30, dec!(0.5)),
         ];
-        assert_eq!(compute_data_quality(&points), DataQuality::Medium);
+        assert_eq!(
+            compute_data_quality(&points, Utc::now()),
+            DataQuality::Medium
+        );
     }
 }
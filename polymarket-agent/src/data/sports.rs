@@ -1,8 +1,17 @@
 //! Sports data source.
 //!
 //! Fetches schedules, scores, and injury reports from ESPN's public API
-//! to inform sports-related prediction markets.
+//! to inform sports-related prediction markets. For team sports, each
+//! team in an event payload also carries its injury report and its
+//! standings record, since pre-game markets hinge on who plays. Those
+//! parts are weighted separately in the payload's `confidence_weights`:
+//! injury statuses change up to tip-off, standings are settled fact.
+//! Modeled sports also get a numeric `prior` from
+//! [`crate::data::win_probability`] built from the odds, records, score
+//! and clock.
 
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
@@ -11,21 +20,52 @@ use chrono::Utc;
 use rust_decimal_macros::dec;
 use serde::Deserialize;
 
+use crate::data::win_probability::{self, GameState};
 use crate::data::{DataPoint, DataSource, MarketQuery};
+use crate::http_cache::HttpCache;
 use crate::market::models::MarketCategory;
 
-/// Supported ESPN sport endpoints.
-const SPORT_ENDPOINTS: &[(&str, &str)] = &[
-    ("nfl", "football/nfl"),
-    ("nba", "basketball/nba"),
-    ("mlb", "baseball/mlb"),
-    ("nhl", "hockey/nhl"),
-    ("mma", "mma/ufc"),
-    ("soccer", "soccer/usa.1"),
+/// Supported ESPN sport endpoints, and whether they have team injury
+/// reports and standings.
+const SPORT_ENDPOINTS: &[(&str, &str, bool)] = &[
+    ("nfl", "football/nfl", true),
+    ("nba", "basketball/nba", true),
+    ("mlb", "baseball/mlb", true),
+    ("nhl", "hockey/nhl", true),
+    ("mma", "mma/ufc", false),
+    ("soccer", "soccer/usa.1", false),
 ];
 
+/// Weights of each part of an event payload; the data point itself carries
+/// the scoreboard's.
+const SCOREBOARD_CONFIDENCE: f64 = 0.85;
+const INJURY_CONFIDENCE: f64 = 0.7;
+const STANDINGS_CONFIDENCE: f64 = 0.9;
+
+/// Standings stats kept in a team's record.
+const RECORD_STATS: &[&str] = &[
+    "wins",
+    "losses",
+    "ties",
+    "otLosses",
+    "winPercent",
+    "pointDifferential",
+    "differential",
+    "gamesBehind",
+    "streak",
+];
+
+/// Injury reports and standings records of one sport's teams, keyed by
+/// lowercase display name.
+#[derive(Debug, Default)]
+struct TeamContext {
+    injuries: HashMap<String, Vec<serde_json::Value>>,
+    records: HashMap<String, serde_json::Value>,
+}
+
 pub struct SportsSource {
     client: reqwest::Client,
+    cache: Arc<HttpCache>,
 }
 
 impl Default for SportsSource {
@@ -41,22 +81,187 @@ impl SportsSource {
             .build()
             .expect("Failed to build HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            cache: Arc::new(HttpCache::disabled()),
+        }
+    }
+
+    /// Revalidate requests through a shared HTTP cache.
+    pub fn with_cache(mut self, cache: Arc<HttpCache>) -> Self {
+        self.cache = cache;
+        self
     }
 
     async fn fetch_scoreboard(&self, sport_path: &str) -> Result<EspnScoreboard> {
         let url = format!("https://site.api.espn.com/apis/site/v2/sports/{sport_path}/scoreboard");
         let response: EspnScoreboard = self
-            .client
-            .get(&url)
-            .send()
+            .cache
+            .send(self.client.get(&url))
             .await
             .context("ESPN scoreboard request failed")?
+            .error_for_status("espn")?
             .json()
-            .await
             .context("Failed to parse ESPN scoreboard")?;
         Ok(response)
     }
+
+    async fn fetch_injuries(&self, sport_path: &str) -> Result<EspnInjuries> {
+        let url = format!("https://site.api.espn.com/apis/site/v2/sports/{sport_path}/injuries");
+        self.cache
+            .send(self.client.get(&url))
+            .await
+            .context("ESPN injuries request failed")?
+            .error_for_status("espn")?
+            .json()
+            .context("Failed to parse ESPN injuries")
+    }
+
+    async fn fetch_standings(&self, sport_path: &str) -> Result<EspnStandingsGroup> {
+        let url = format!("https://site.api.espn.com/apis/v2/sports/{sport_path}/standings");
+        self.cache
+            .send(self.client.get(&url))
+            .await
+            .context("ESPN standings request failed")?
+            .error_for_status("espn")?
+            .json()
+            .context("Failed to parse ESPN standings")
+    }
+
+    /// Injuries and standings for a team sport. Either failing leaves its
+    /// part empty rather than dropping the scoreboard.
+    async fn fetch_team_context(&self, sport_name: &str, sport_path: &str) -> TeamContext {
+        let mut context = TeamContext::default();
+        match self.fetch_injuries(sport_path).await {
+            Ok(injuries) => context.injuries = injury_reports(injuries),
+            Err(e) => tracing::warn!(sport = sport_name, error = %e, "Failed to fetch injuries"),
+        }
+        match self.fetch_standings(sport_path).await {
+            Ok(standings) => context.records = standings_records(&standings),
+            Err(e) => tracing::warn!(sport = sport_name, error = %e, "Failed to fetch standings"),
+        }
+        context
+    }
+}
+
+fn injury_reports(injuries: EspnInjuries) -> HashMap<String, Vec<serde_json::Value>> {
+    injuries
+        .injuries
+        .into_iter()
+        .map(|team| {
+            let players = team
+                .injuries
+                .into_iter()
+                .map(|injury| {
+                    serde_json::json!({
+                        "player": injury.athlete.display_name,
+                        "position": injury.athlete.position.map(|p| p.abbreviation),
+                        "status": injury.status,
+                        "detail": injury.short_comment,
+                        "date": injury.date,
+                    })
+                })
+                .collect();
+            (team.display_name.to_lowercase(), players)
+        })
+        .collect()
+}
+
+/// Home win probability of `competition`, for sports with a model.
+fn win_prior(
+    sport: &str,
+    competition: &EspnCompetition,
+    context: &TeamContext,
+) -> Option<serde_json::Value> {
+    let model = win_probability::model(sport)?;
+    let side = |home_away: &str| {
+        competition
+            .competitors
+            .iter()
+            .find(|c| c.home_away == home_away)
+    };
+    let (home, away) = (side("home")?, side("away")?);
+    let score = |c: &EspnCompetitor| {
+        c.score
+            .as_deref()
+            .and_then(|s| s.parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+    let win_pct = |c: &EspnCompetitor| {
+        context
+            .records
+            .get(&c.team.display_name.to_lowercase())
+            .and_then(record_win_pct)
+    };
+    let moneyline = |odds: &Option<EspnTeamOdds>| odds.as_ref()?.money_line;
+    let status = &competition.status;
+    let game = GameState {
+        completed: status.type_detail.completed,
+        in_progress: status.type_detail.state.as_deref() == Some("in"),
+        home_won: home.winner,
+        home_score: score(home),
+        away_score: score(away),
+        period: status.period.unwrap_or(1),
+        clock_seconds: status
+            .display_clock
+            .as_deref()
+            .and_then(win_probability::parse_clock)
+            .unwrap_or(0.0),
+        moneylines: competition
+            .odds
+            .first()
+            .and_then(|o| Some((moneyline(&o.home_team_odds)?, moneyline(&o.away_team_odds)?))),
+        win_pcts: win_pct(home).zip(win_pct(away)),
+    };
+    let estimate = win_probability::estimate(&model, &game);
+    Some(serde_json::json!({
+        "home_team": home.team.display_name,
+        "home_win": estimate.home_win,
+        "away_win": ((1.0 - estimate.home_win) * 1000.0).round() / 1000.0,
+        "pregame_home_win": estimate.pregame_home_win,
+        "method": estimate.method,
+    }))
+}
+
+/// Winning percentage from a standings record: wins over decided games,
+/// else the listed `winPercent`.
+fn record_win_pct(record: &serde_json::Value) -> Option<f64> {
+    let stat = |name: &str| match record.get(name)? {
+        serde_json::Value::String(s) => s.parse::<f64>().ok(),
+        value => value.as_f64(),
+    };
+    match (stat("wins"), stat("losses")) {
+        (Some(wins), Some(losses)) if wins + losses > 0.0 => Some(wins / (wins + losses)),
+        _ => stat("winPercent"),
+    }
+}
+
+/// Records of every team in `group` and its nested groups (conferences,
+/// divisions).
+fn standings_records(group: &EspnStandingsGroup) -> HashMap<String, serde_json::Value> {
+    let mut records = HashMap::new();
+    for entry in group.standings.iter().flat_map(|s| &s.entries) {
+        let record: serde_json::Map<String, serde_json::Value> = entry
+            .stats
+            .iter()
+            .filter(|stat| RECORD_STATS.contains(&stat.name.as_str()))
+            .map(|stat| {
+                let value = match (&stat.display_value, stat.value) {
+                    (Some(display), _) => serde_json::json!(display),
+                    (None, value) => serde_json::json!(value),
+                };
+                (stat.name.clone(), value)
+            })
+            .collect();
+        records.insert(
+            entry.team.display_name.to_lowercase(),
+            serde_json::Value::Object(record),
+        );
+    }
+    for child in &group.children {
+        records.extend(standings_records(child));
+    }
+    records
 }
 
 #[async_trait]
@@ -64,7 +269,7 @@ impl DataSource for SportsSource {
     async fn fetch(&self, queries: &[MarketQuery]) -> Result<Vec<DataPoint>> {
         let mut points = Vec::new();
 
-        for (sport_name, sport_path) in SPORT_ENDPOINTS {
+        for (sport_name, sport_path, team_context) in SPORT_ENDPOINTS {
             // Check if any queries relate to this sport
             let sport_lower = sport_name.to_lowercase();
             let has_relevant = queries.iter().any(|q| {
@@ -85,18 +290,26 @@ impl DataSource for SportsSource {
 
             match self.fetch_scoreboard(sport_path).await {
                 Ok(scoreboard) => {
+                    let context = if *team_context && !scoreboard.events.is_empty() {
+                        self.fetch_team_context(sport_name, sport_path).await
+                    } else {
+                        TeamContext::default()
+                    };
                     for event in &scoreboard.events {
                         let teams: Vec<serde_json::Value> = event
                             .competitions
                             .iter()
                             .flat_map(|c| &c.competitors)
                             .map(|comp| {
+                                let key = comp.team.display_name.to_lowercase();
                                 serde_json::json!({
                                     "name": comp.team.display_name,
                                     "abbreviation": comp.team.abbreviation,
                                     "score": comp.score,
                                     "home_away": comp.home_away,
                                     "winner": comp.winner,
+                                    "injuries": context.injuries.get(&key),
+                                    "record": context.records.get(&key),
                                 })
                             })
                             .collect();
@@ -113,12 +326,25 @@ impl DataSource for SportsSource {
                             })
                             .unwrap_or(serde_json::json!(null));
 
+                        let prior = event
+                            .competitions
+                            .first()
+                            .and_then(|c| win_prior(sport_name, c, &context));
+
+                        // The prior sorts ahead of `teams`, so it survives
+                        // payload truncation in the prompt
                         let payload = serde_json::json!({
                             "sport": sport_name,
+                            "prior": prior,
                             "event_name": event.name,
                             "date": event.date,
                             "teams": teams,
                             "status": status,
+                            "confidence_weights": {
+                                "scoreboard": SCOREBOARD_CONFIDENCE,
+                                "injuries": INJURY_CONFIDENCE,
+                                "standings": STANDINGS_CONFIDENCE,
+                            },
                         });
 
                         // Match to relevant market queries by team names
@@ -186,6 +412,21 @@ struct EspnEvent {
 struct EspnCompetition {
     competitors: Vec<EspnCompetitor>,
     status: EspnStatus,
+    #[serde(default)]
+    odds: Vec<EspnOdds>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnOdds {
+    home_team_odds: Option<EspnTeamOdds>,
+    away_team_odds: Option<EspnTeamOdds>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnTeamOdds {
+    money_line: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -205,13 +446,165 @@ struct EspnTeam {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct EspnStatus {
     #[serde(rename = "type")]
     type_detail: EspnStatusType,
+    period: Option<u32>,
+    display_clock: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct EspnStatusType {
     description: String,
     completed: bool,
+    /// "pre", "in" or "post".
+    state: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnInjuries {
+    #[serde(default)]
+    injuries: Vec<EspnTeamInjuries>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnTeamInjuries {
+    display_name: String,
+    #[serde(default)]
+    injuries: Vec<EspnInjury>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnInjury {
+    athlete: EspnAthlete,
+    status: Option<String>,
+    short_comment: Option<String>,
+    date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnAthlete {
+    display_name: String,
+    position: Option<EspnPosition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnPosition {
+    abbreviation: String,
+}
+
+/// A league, conference or division; any of them may hold standings.
+#[derive(Debug, Deserialize)]
+struct EspnStandingsGroup {
+    #[serde(default)]
+    children: Vec<EspnStandingsGroup>,
+    standings: Option<EspnStandings>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnStandings {
+    #[serde(default)]
+    entries: Vec<EspnStandingsEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnStandingsEntry {
+    team: EspnTeam,
+    #[serde(default)]
+    stats: Vec<EspnStat>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnStat {
+    name: String,
+    value: Option<f64>,
+    display_value: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_injuries_and_nested_standings_by_team() {
+        let injuries: EspnInjuries = serde_json::from_value(serde_json::json!({
+            "injuries": [{
+                "displayName": "Los Angeles Lakers",
+                "injuries": [{
+                    "athlete": { "displayName": "LeBron James", "position": { "abbreviation": "F" } },
+                    "status": "Out",
+                    "shortComment": "Ankle; out Friday",
+                    "date": "2026-01-09T18:00Z"
+                }]
+            }]
+        }))
+        .unwrap();
+        let injuries = injury_reports(injuries);
+        assert_eq!(injuries["los angeles lakers"][0]["player"], "LeBron James");
+        assert_eq!(injuries["los angeles lakers"][0]["status"], "Out");
+
+        let standings: EspnStandingsGroup = serde_json::from_value(serde_json::json!({
+            "children": [{
+                "name": "Western Conference",
+                "standings": { "entries": [{
+                    "team": { "displayName": "Los Angeles Lakers", "abbreviation": "LAL" },
+                    "stats": [
+                        { "name": "wins", "value": 30.0, "displayValue": "30" },
+                        { "name": "losses", "value": 12.0, "displayValue": "12" },
+                        { "name": "avgPointsFor", "value": 115.2 }
+                    ]
+                }] },
+                "children": [{
+                    "standings": { "entries": [{
+                        "team": { "displayName": "Boston Celtics", "abbreviation": "BOS" },
+                        "stats": [{ "name": "winPercent", "value": 0.7 }]
+                    }] }
+                }]
+            }]
+        }))
+        .unwrap();
+        let records = standings_records(&standings);
+        assert_eq!(
+            records["los angeles lakers"],
+            serde_json::json!({ "wins": "30", "losses": "12" })
+        );
+        assert_eq!(records["boston celtics"]["winPercent"], 0.7);
+    }
+
+    #[test]
+    fn test_win_prior_from_odds_and_live_score() {
+        let competition: EspnCompetition = serde_json::from_value(serde_json::json!({
+            "competitors": [
+                { "team": { "displayName": "Boston Celtics", "abbreviation": "BOS" },
+                  "score": "98", "homeAway": "home" },
+                { "team": { "displayName": "Los Angeles Lakers", "abbreviation": "LAL" },
+                  "score": "90", "homeAway": "away" }
+            ],
+            "status": {
+                "type": { "description": "In Progress", "completed": false, "state": "in" },
+                "period": 4,
+                "displayClock": "1:00"
+            },
+            "odds": [{
+                "homeTeamOdds": { "moneyLine": -200 },
+                "awayTeamOdds": { "moneyLine": 170 }
+            }]
+        }))
+        .unwrap();
+
+        let prior = win_prior("nba", &competition, &TeamContext::default()).unwrap();
+        assert_eq!(prior["home_team"], "Boston Celtics");
+        assert_eq!(prior["method"], "in_game");
+        assert_eq!(prior["pregame_home_win"], 0.643);
+        assert!(prior["home_win"].as_f64().unwrap() > 0.99);
+        assert!(win_prior("soccer", &competition, &TeamContext::default()).is_none());
+
+        let record = serde_json::json!({ "wins": "30", "losses": "10" });
+        assert_eq!(record_win_pct(&record), Some(0.75));
+    }
 }
@@ -1,8 +1,14 @@
 //! NOAA weather data source.
 //!
 //! Fetches forecasts from api.weather.gov and detects forecast changes
-//! that could create edge in weather-related prediction markets.
+//! that could create edge in weather-related prediction markets. Each
+//! station's forecast URL is resolved through the `points` endpoint once
+//! and cached. Active alerts (hurricane advisories, severe weather watches
+//! and the like) for each station and for the tropics come in as separate
+//! `noaa_alert` data points, since alerts drive weather-market spikes.
 
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::{Context, Result};
@@ -12,6 +18,7 @@ use rust_decimal_macros::dec;
 use serde::Deserialize;
 
 use crate::data::{DataPoint, DataSource, MarketQuery};
+use crate::http_cache::HttpCache;
 use crate::market::models::MarketCategory;
 
 /// Major US cities for weather market scanning.
@@ -23,8 +30,29 @@ const DEFAULT_STATIONS: &[(&str, f64, f64)] = &[
     ("Houston", 29.7604, -95.3698),
 ];
 
+/// Tropical alerts fetched nationwide: landfall markets rarely name a city.
+const TROPICAL_EVENTS: &str =
+    "Hurricane Watch,Hurricane Warning,Tropical Storm Watch,Tropical Storm Warning,Storm Surge Warning";
+
+/// Words of an alert's event name too generic to match markets on.
+const GENERIC_EVENT_WORDS: &[&str] = &[
+    "warning",
+    "watch",
+    "advisory",
+    "statement",
+    "special",
+    "weather",
+    "severe",
+    "outlook",
+    "local",
+    "area",
+];
+
 pub struct WeatherSource {
     client: reqwest::Client,
+    cache: Arc<HttpCache>,
+    /// Forecast URL per station, from the `points` endpoint.
+    forecast_urls: Mutex<HashMap<String, String>>,
 }
 
 impl Default for WeatherSource {
@@ -41,41 +69,138 @@ impl WeatherSource {
             .build()
             .expect("Failed to build HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            cache: Arc::new(HttpCache::disabled()),
+            forecast_urls: Mutex::new(HashMap::new()),
+        }
     }
 
-    async fn fetch_forecast(&self, lat: f64, lon: f64) -> Result<NoaaForecast> {
-        // Step 1: Get the forecast URL for this point
+    /// Revalidate requests through a shared HTTP cache.
+    pub fn with_cache(mut self, cache: Arc<HttpCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// The forecast URL of `station`, resolved once through `points`.
+    async fn forecast_url(&self, station: &str, lat: f64, lon: f64) -> Result<String> {
+        if let Some(url) = self.cached_forecast_url(station) {
+            return Ok(url);
+        }
         let points_url = format!("https://api.weather.gov/points/{lat:.4},{lon:.4}");
         let points: PointsResponse = self
-            .client
-            .get(&points_url)
-            .send()
+            .cache
+            .send(self.client.get(&points_url))
             .await
             .context("NOAA points request failed")?
+            .error_for_status("noaa")?
             .json()
-            .await
             .context("Failed to parse NOAA points response")?;
+        self.forecast_urls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(station.to_string(), points.properties.forecast.clone());
+        Ok(points.properties.forecast)
+    }
 
-        // Step 2: Fetch the actual forecast
-        let forecast: NoaaForecast = self
-            .client
-            .get(&points.properties.forecast)
-            .send()
-            .await
-            .context("NOAA forecast request failed")?
-            .json()
+    fn cached_forecast_url(&self, station: &str) -> Option<String> {
+        self.forecast_urls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(station)
+            .cloned()
+    }
+
+    async fn fetch_forecast(&self, station: &str, lat: f64, lon: f64) -> Result<NoaaForecast> {
+        let url = self.forecast_url(station, lat, lon).await?;
+        let forecast: Result<NoaaForecast> = self
+            .cache
+            .send(self.client.get(&url))
             .await
-            .context("Failed to parse NOAA forecast")?;
+            .context("NOAA forecast request failed")
+            .and_then(|r| Ok(r.error_for_status("noaa")?))
+            .and_then(|r| r.json().context("Failed to parse NOAA forecast"));
+        if forecast.is_err() {
+            // Gridpoints are occasionally reassigned; resolve again next time
+            self.forecast_urls
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(station);
+        }
+        forecast
+    }
 
-        Ok(forecast)
+    /// Active alerts matching `query`, e.g. `point=40.7128,-74.0060`.
+    async fn fetch_alerts(&self, query: &str) -> Result<Vec<AlertProperties>> {
+        let url = format!("https://api.weather.gov/alerts/active?status=actual&{query}");
+        let alerts: AlertsResponse = self
+            .cache
+            .send(self.client.get(&url))
+            .await
+            .context("NOAA alerts request failed")?
+            .error_for_status("noaa")?
+            .json()
+            .context("Failed to parse NOAA alerts")?;
+        Ok(alerts.features.into_iter().map(|f| f.properties).collect())
     }
 }
 
+/// One data point per alert, relevant to markets naming `station` or the
+/// alert's hazard (e.g. "hurricane" for a Hurricane Warning).
+fn alert_points(
+    alerts: Vec<AlertProperties>,
+    station: Option<&str>,
+    queries: &[MarketQuery],
+    seen: &mut HashSet<String>,
+) -> Vec<DataPoint> {
+    alerts
+        .into_iter()
+        .filter(|alert| seen.insert(alert.id.clone()))
+        .map(|alert| {
+            let hazards: Vec<String> = alert
+                .event
+                .to_lowercase()
+                .split_whitespace()
+                .filter(|w| !GENERIC_EVENT_WORDS.contains(w))
+                .map(str::to_string)
+                .collect();
+            let relevance = queries
+                .iter()
+                .filter(|q| {
+                    let question = q.question.to_lowercase();
+                    station.is_some_and(|s| question.contains(&s.to_lowercase()))
+                        || hazards.iter().any(|h| question.contains(h.as_str()))
+                })
+                .map(|q| q.condition_id.clone())
+                .collect();
+            DataPoint {
+                source: "noaa_alert".to_string(),
+                category: MarketCategory::Weather,
+                timestamp: Utc::now(),
+                payload: serde_json::json!({
+                    "city": station,
+                    "event": alert.event,
+                    "headline": alert.headline,
+                    "severity": alert.severity,
+                    "certainty": alert.certainty,
+                    "urgency": alert.urgency,
+                    "area": alert.area_desc,
+                    "onset": alert.onset,
+                    "expires": alert.expires,
+                    "description": alert.description,
+                }),
+                confidence: dec!(0.95), // Issued by the NWS itself
+                relevance_to: relevance,
+            }
+        })
+        .collect()
+}
+
 #[async_trait]
 impl DataSource for WeatherSource {
     async fn fetch(&self, queries: &[MarketQuery]) -> Result<Vec<DataPoint>> {
         let mut points = Vec::new();
+        let mut seen_alerts = HashSet::new();
 
         for (city, lat, lon) in DEFAULT_STATIONS {
             // Check if any queries mention this city (case-insensitive)
@@ -86,7 +211,7 @@ impl DataSource for WeatherSource {
                 .collect();
 
             // Also fetch for general weather markets even without city match
-            match self.fetch_forecast(*lat, *lon).await {
+            match self.fetch_forecast(city, *lat, *lon).await {
                 Ok(forecast) => {
                     for period in &forecast.properties.periods {
                         let payload = serde_json::json!({
@@ -129,6 +254,21 @@ impl DataSource for WeatherSource {
                     tracing::warn!(city, error = %e, "Failed to fetch weather for city");
                 }
             }
+
+            match self.fetch_alerts(&format!("point={lat:.4},{lon:.4}")).await {
+                Ok(alerts) => {
+                    points.extend(alert_points(alerts, Some(city), queries, &mut seen_alerts))
+                }
+                Err(e) => {
+                    tracing::warn!(city, error = %e, "Failed to fetch weather alerts for city")
+                }
+            }
+        }
+
+        let tropical = format!("event={}", TROPICAL_EVENTS.replace(' ', "%20"));
+        match self.fetch_alerts(&tropical).await {
+            Ok(alerts) => points.extend(alert_points(alerts, None, queries, &mut seen_alerts)),
+            Err(e) => tracing::warn!(error = %e, "Failed to fetch tropical alerts"),
         }
 
         Ok(points)
@@ -186,3 +326,89 @@ struct ForecastPeriod {
 struct PrecipitationProbability {
     value: Option<i32>,
 }
+
+#[derive(Debug, Deserialize)]
+struct AlertsResponse {
+    features: Vec<AlertFeature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AlertFeature {
+    properties: AlertProperties,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlertProperties {
+    id: String,
+    event: String,
+    headline: Option<String>,
+    description: Option<String>,
+    severity: Option<String>,
+    certainty: Option<String>,
+    urgency: Option<String>,
+    area_desc: Option<String>,
+    onset: Option<String>,
+    expires: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(id: &str, question: &str) -> MarketQuery {
+        MarketQuery {
+            condition_id: id.to_string(),
+            question: question.to_string(),
+            category: MarketCategory::Weather,
+        }
+    }
+
+    #[test]
+    fn test_alerts_match_station_and_hazard() {
+        let response: AlertsResponse = serde_json::from_value(serde_json::json!({
+            "features": [
+                { "properties": {
+                    "id": "urn:oid:1", "event": "Hurricane Warning",
+                    "headline": "Hurricane Warning issued for Miami-Dade",
+                    "severity": "Extreme", "areaDesc": "Miami-Dade"
+                } },
+                { "properties": { "id": "urn:oid:2", "event": "Heat Advisory" } }
+            ]
+        }))
+        .unwrap();
+        let alerts: Vec<AlertProperties> = response
+            .features
+            .into_iter()
+            .map(|f| f.properties)
+            .collect();
+        let queries = [
+            query("landfall", "Will a hurricane make landfall in Florida?"),
+            query("miami", "Will Miami hit 95°F this week?"),
+            query("nyc", "Will it snow in New York?"),
+        ];
+
+        let mut seen = HashSet::new();
+        let points = alert_points(alerts, Some("Miami"), &queries, &mut seen);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].source, "noaa_alert");
+        assert_eq!(points[0].relevance_to, ["landfall", "miami"]);
+        assert_eq!(points[1].relevance_to, ["miami"]);
+        assert_eq!(points[0].payload["severity"], "Extreme");
+
+        // The same alert seen again, e.g. nationwide, isn't repeated
+        let again = vec![AlertProperties {
+            id: "urn:oid:1".to_string(),
+            event: "Hurricane Warning".to_string(),
+            headline: None,
+            description: None,
+            severity: None,
+            certainty: None,
+            urgency: None,
+            area_desc: None,
+            onset: None,
+            expires: None,
+        }];
+        assert!(alert_points(again, None, &queries, &mut seen).is_empty());
+    }
+}
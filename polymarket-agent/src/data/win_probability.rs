@@ -0,0 +1,253 @@
+//! Sports win-probability model.
+//!
+//! Gives the valuation prompt a numeric prior instead of raw scores. Before
+//! tip-off the home side's chance comes from bookmaker moneylines (vig
+//! removed) when ESPN has them, else from both teams' winning percentages
+//! (log5 plus home advantage), else from home advantage alone. Once the game
+//! is on, the pre-game prior is turned into an expected margin and combined
+//! with the current score difference and the time remaining:
+//!
+//! `P(home) = Φ((margin + expected × f) / (σ × √f))`
+//!
+//! where `f` is the fraction of regulation left and `σ` the sport's
+//! full-game margin spread, with Φ approximated by a logistic curve.
+
+use serde::Serialize;
+
+/// Scale turning a logistic curve into a close fit of the normal CDF.
+const LOGISTIC_SCALE: f64 = 1.702;
+
+/// Fraction of the game assumed left in overtime or extra innings.
+const OVERTIME_FRACTION: f64 = 0.02;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SportModel {
+    /// Standard deviation of the full-game final margin.
+    pub margin_stdev: f64,
+    pub periods: u32,
+    /// Length of a period; zero for untimed sports (innings).
+    pub period_seconds: f64,
+    /// Home side's win probability between evenly matched teams.
+    pub home_advantage: f64,
+}
+
+/// The model for `sport` (as in the scoreboard's sport name), if any.
+/// Sports with draws or no score margin aren't modeled.
+pub fn model(sport: &str) -> Option<SportModel> {
+    let (margin_stdev, periods, period_seconds, home_advantage) = match sport {
+        "nba" => (12.0, 4, 720.0, 0.58),
+        "nfl" => (13.5, 4, 900.0, 0.56),
+        "nhl" => (2.5, 3, 1200.0, 0.55),
+        "mlb" => (4.0, 9, 0.0, 0.54),
+        _ => return None,
+    };
+    Some(SportModel {
+        margin_stdev,
+        periods,
+        period_seconds,
+        home_advantage,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorMethod {
+    Moneyline,
+    Standings,
+    HomeAdvantage,
+    InGame,
+    Final,
+}
+
+/// Everything known about one game, home side first.
+#[derive(Debug, Clone, Default)]
+pub struct GameState {
+    pub completed: bool,
+    pub in_progress: bool,
+    pub home_won: Option<bool>,
+    pub home_score: f64,
+    pub away_score: f64,
+    /// Current period (quarter, period or inning), from 1.
+    pub period: u32,
+    /// Seconds left in the current period.
+    pub clock_seconds: f64,
+    /// American moneylines, home then away.
+    pub moneylines: Option<(f64, f64)>,
+    /// Winning percentages, home then away.
+    pub win_pcts: Option<(f64, f64)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WinProbability {
+    pub home_win: f64,
+    pub pregame_home_win: f64,
+    pub method: PriorMethod,
+}
+
+/// Home win probability of `game` under `model`.
+pub fn estimate(model: &SportModel, game: &GameState) -> WinProbability {
+    let (pregame, pregame_method) = pregame_prior(model, game);
+    let (home_win, method) = if game.completed {
+        match game.home_won {
+            Some(won) => (if won { 1.0 } else { 0.0 }, PriorMethod::Final),
+            None => (pregame, pregame_method),
+        }
+    } else if game.in_progress {
+        let remaining = fraction_remaining(model, game.period, game.clock_seconds);
+        let margin = game.home_score - game.away_score;
+        (
+            in_game(model, pregame, margin, remaining),
+            PriorMethod::InGame,
+        )
+    } else {
+        (pregame, pregame_method)
+    };
+    WinProbability {
+        home_win: round3(home_win),
+        pregame_home_win: round3(pregame),
+        method,
+    }
+}
+
+fn pregame_prior(model: &SportModel, game: &GameState) -> (f64, PriorMethod) {
+    if let Some((home, away)) = game.moneylines {
+        if let (Some(home), Some(away)) = (implied(home), implied(away)) {
+            return (home / (home + away), PriorMethod::Moneyline);
+        }
+    }
+    if let Some((home, away)) = game.win_pcts {
+        let neutral = log5(home, away);
+        return (
+            sigmoid(logit(neutral) + logit(model.home_advantage)),
+            PriorMethod::Standings,
+        );
+    }
+    (model.home_advantage, PriorMethod::HomeAdvantage)
+}
+
+/// Win probability implied by an American moneyline, vig included.
+fn implied(moneyline: f64) -> Option<f64> {
+    if moneyline <= -100.0 {
+        Some(-moneyline / (-moneyline + 100.0))
+    } else if moneyline >= 100.0 {
+        Some(100.0 / (moneyline + 100.0))
+    } else {
+        None
+    }
+}
+
+/// Chance a team winning `a` of its games beats one winning `b`.
+fn log5(a: f64, b: f64) -> f64 {
+    let (a, b) = (a.clamp(0.01, 0.99), b.clamp(0.01, 0.99));
+    a * (1.0 - b) / (a * (1.0 - b) + b * (1.0 - a))
+}
+
+/// Fraction of regulation still to play.
+fn fraction_remaining(model: &SportModel, period: u32, clock_seconds: f64) -> f64 {
+    let period = period.max(1);
+    if period > model.periods {
+        return OVERTIME_FRACTION;
+    }
+    let remaining = if model.period_seconds > 0.0 {
+        let clock = clock_seconds.clamp(0.0, model.period_seconds);
+        (f64::from(model.periods - period) + clock / model.period_seconds)
+            / f64::from(model.periods)
+    } else {
+        // Untimed: assume the current inning is half played
+        (f64::from(model.periods - period) + 0.5) / f64::from(model.periods)
+    };
+    remaining.max(OVERTIME_FRACTION)
+}
+
+fn in_game(model: &SportModel, pregame: f64, margin: f64, remaining: f64) -> f64 {
+    let expected = model.margin_stdev * logit(pregame) / LOGISTIC_SCALE;
+    let z = (margin + expected * remaining) / (model.margin_stdev * remaining.sqrt());
+    sigmoid(LOGISTIC_SCALE * z)
+}
+
+fn logit(p: f64) -> f64 {
+    let p = p.clamp(0.001, 0.999);
+    (p / (1.0 - p)).ln()
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn round3(p: f64) -> f64 {
+    (p * 1000.0).round() / 1000.0
+}
+
+/// Seconds in an ESPN clock string: `"5:32"`, or `"42.1"` under a minute.
+pub fn parse_clock(clock: &str) -> Option<f64> {
+    match clock.split_once(':') {
+        Some((minutes, seconds)) => {
+            Some(minutes.trim().parse::<f64>().ok()? * 60.0 + seconds.trim().parse::<f64>().ok()?)
+        }
+        None => clock.trim().parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pregame_priors() {
+        let nba = model("nba").unwrap();
+        assert!(model("soccer").is_none());
+
+        // -200 / +170: 66.7% and 37.0% before removing the vig
+        let favored = GameState {
+            moneylines: Some((-200.0, 170.0)),
+            win_pcts: Some((0.3, 0.7)),
+            ..GameState::default()
+        };
+        let prior = estimate(&nba, &favored);
+        assert_eq!(prior.method, PriorMethod::Moneyline);
+        assert_eq!(prior.home_win, 0.643);
+
+        let by_record = GameState {
+            win_pcts: Some((0.5, 0.5)),
+            ..GameState::default()
+        };
+        let prior = estimate(&nba, &by_record);
+        assert_eq!(prior.method, PriorMethod::Standings);
+        assert_eq!(prior.home_win, 0.58);
+
+        let prior = estimate(&nba, &GameState::default());
+        assert_eq!(prior.method, PriorMethod::HomeAdvantage);
+        assert_eq!(prior.home_win, 0.58);
+    }
+
+    #[test]
+    fn test_in_game_leads_matter_more_as_time_runs_out() {
+        let nba = model("nba").unwrap();
+        let game = |period: u32, clock: &str, home: f64, away: f64| GameState {
+            in_progress: true,
+            home_score: home,
+            away_score: away,
+            period,
+            clock_seconds: parse_clock(clock).unwrap(),
+            ..GameState::default()
+        };
+
+        let early = estimate(&nba, &game(1, "10:00", 10.0, 2.0)).home_win;
+        let late = estimate(&nba, &game(4, "1:00", 100.0, 92.0)).home_win;
+        assert!(early > 0.7 && early < 0.9, "{early}");
+        assert!(late > 0.97, "{late}");
+        // Trailing late is close to hopeless
+        assert!(estimate(&nba, &game(4, "0:30", 90.0, 98.0)).home_win < 0.02);
+        // Tied at the start keeps the pre-game prior
+        let tied = estimate(&nba, &game(1, "12:00", 0.0, 0.0));
+        assert!((tied.home_win - tied.pregame_home_win).abs() < 0.01);
+
+        let finished = GameState {
+            completed: true,
+            home_won: Some(false),
+            ..GameState::default()
+        };
+        let result = estimate(&nba, &finished);
+        assert_eq!((result.home_win, result.method), (0.0, PriorMethod::Final));
+    }
+}
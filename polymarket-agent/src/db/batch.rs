@@ -0,0 +1,334 @@
+//! Write-behind batching for high-frequency inserts.
+//!
+//! Order book snapshots and market evaluations arrive in bursts, and one
+//! SQLite transaction per row means one fsync per row. A [`BatchWriter`]
+//! queues them instead and a background task commits the queue in a single
+//! transaction every `flush_interval_ms`, or sooner once `max_batch` rows are
+//! waiting. [`BatchWriter::flush`] waits until everything queued before it
+//! is committed; call it before shutting down.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::db::store::{
+    write_market_evaluation, write_order_book_snapshot, MarketEvaluationRecord, Store,
+};
+use crate::market::models::OrderBookSnapshot;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WriteBatchConfig {
+    /// Queue inserts and commit them in batches. Off writes each row as it
+    /// arrives.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+    /// Rows that trigger an early flush.
+    #[serde(default = "default_max_batch")]
+    pub max_batch: usize,
+}
+
+impl Default for WriteBatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            flush_interval_ms: default_flush_interval_ms(),
+            max_batch: default_max_batch(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_flush_interval_ms() -> u64 {
+    2000
+}
+
+fn default_max_batch() -> usize {
+    500
+}
+
+/// A row waiting to be written.
+#[derive(Debug, Clone)]
+pub enum PendingWrite {
+    OrderBookSnapshot {
+        market_id: String,
+        book: OrderBookSnapshot,
+        depth: usize,
+    },
+    MarketEvaluation(MarketEvaluationRecord),
+}
+
+enum Command {
+    Write(PendingWrite),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Handle for queueing inserts. Cheap to clone; the background task exits,
+/// committing what's left, once every clone is dropped.
+pub struct BatchWriter {
+    store: Store,
+    queue: Option<mpsc::Sender<Command>>,
+}
+
+impl Clone for BatchWriter {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone_for_parallel(),
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl BatchWriter {
+    /// Start the background writer, or write through directly if batching
+    /// is disabled (no task is spawned then).
+    pub fn spawn(store: Store, config: WriteBatchConfig) -> (Self, Option<JoinHandle<()>>) {
+        if !config.enabled {
+            return (Self { store, queue: None }, None);
+        }
+
+        let max_batch = config.max_batch.max(1);
+        // Room for a few batches before producers wait on the writer
+        let (tx, rx) = mpsc::channel(max_batch * 4);
+        let handle = tokio::spawn(run(
+            store.clone_for_parallel(),
+            rx,
+            Duration::from_millis(config.flush_interval_ms.max(1)),
+            max_batch,
+        ));
+        (
+            Self {
+                store,
+                queue: Some(tx),
+            },
+            Some(handle),
+        )
+    }
+
+    /// Queue a row. Only fails when writing through and the insert fails,
+    /// or if the background writer has stopped.
+    pub async fn write(&self, write: PendingWrite) -> Result<()> {
+        match &self.queue {
+            Some(queue) => queue
+                .send(Command::Write(write))
+                .await
+                .ok()
+                .context("Batch writer has stopped"),
+            None => {
+                let mut conn = self.store.pool().acquire().await?;
+                apply(&mut conn, &write).await
+            }
+        }
+    }
+
+    pub async fn insert_order_book_snapshot(
+        &self,
+        market_id: &str,
+        book: &OrderBookSnapshot,
+        depth: usize,
+    ) -> Result<()> {
+        self.write(PendingWrite::OrderBookSnapshot {
+            market_id: market_id.to_string(),
+            book: book.clone(),
+            depth,
+        })
+        .await
+    }
+
+    pub async fn insert_market_evaluation(&self, record: &MarketEvaluationRecord) -> Result<()> {
+        self.write(PendingWrite::MarketEvaluation(record.clone()))
+            .await
+    }
+
+    /// Wait until every row queued so far is committed.
+    pub async fn flush(&self) -> Result<()> {
+        let Some(queue) = &self.queue else {
+            return Ok(());
+        };
+        let (ack, done) = oneshot::channel();
+        queue
+            .send(Command::Flush(ack))
+            .await
+            .ok()
+            .context("Batch writer has stopped")?;
+        done.await.context("Batch writer stopped before flushing")
+    }
+}
+
+async fn run(
+    store: Store,
+    mut queue: mpsc::Receiver<Command>,
+    flush_interval: Duration,
+    max_batch: usize,
+) {
+    let mut pending = Vec::with_capacity(max_batch);
+    let mut ticker =
+        tokio::time::interval_at(tokio::time::Instant::now() + flush_interval, flush_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            command = queue.recv() => match command {
+                Some(Command::Write(write)) => {
+                    pending.push(write);
+                    if pending.len() >= max_batch {
+                        commit(&store, &mut pending).await;
+                    }
+                }
+                Some(Command::Flush(ack)) => {
+                    commit(&store, &mut pending).await;
+                    let _ = ack.send(());
+                }
+                None => {
+                    commit(&store, &mut pending).await;
+                    return;
+                }
+            },
+            _ = ticker.tick() => commit(&store, &mut pending).await,
+        }
+    }
+}
+
+/// Write `pending` in one transaction and clear it. A failed batch is
+/// logged and dropped rather than retried, so a bad row can't wedge the
+/// queue.
+async fn commit(store: &Store, pending: &mut Vec<PendingWrite>) {
+    if pending.is_empty() {
+        return;
+    }
+    match write_all(store, pending).await {
+        Ok(()) => debug!(rows = pending.len(), "Batched writes committed"),
+        Err(e) => warn!(rows = pending.len(), error = %e, "Failed to commit batched writes"),
+    }
+    pending.clear();
+}
+
+async fn write_all(store: &Store, writes: &[PendingWrite]) -> Result<()> {
+    let mut tx = store.pool().begin().await?;
+    for write in writes {
+        apply(&mut tx, write).await?;
+    }
+    tx.commit().await.context("Failed to commit batch")?;
+    Ok(())
+}
+
+async fn apply(conn: &mut sqlx::SqliteConnection, write: &PendingWrite) -> Result<()> {
+    match write {
+        PendingWrite::OrderBookSnapshot {
+            market_id,
+            book,
+            depth,
+        } => write_order_book_snapshot(conn, market_id, book, *depth).await,
+        PendingWrite::MarketEvaluation(record) => write_market_evaluation(conn, record).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluation(condition_id: &str) -> MarketEvaluationRecord {
+        MarketEvaluationRecord {
+            id: None,
+            condition_id: condition_id.to_string(),
+            cycle: 1,
+            fair_value: "0.6".to_string(),
+            market_price: "0.5".to_string(),
+            net_edge: None,
+            edge_found: false,
+            evaluated_at: None,
+        }
+    }
+
+    async fn evaluation_count(store: &Store) -> i64 {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM market_evaluations")
+            .fetch_one(store.pool())
+            .await
+            .unwrap();
+        row.0
+    }
+
+    fn config(max_batch: usize) -> WriteBatchConfig {
+        WriteBatchConfig {
+            enabled: true,
+            // Long enough that only flushes and full batches write
+            flush_interval_ms: 3_600_000,
+            max_batch,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_commits_queued_rows() {
+        let store = Store::new(":memory:").await.unwrap();
+        let (writer, handle) = BatchWriter::spawn(store.clone_for_parallel(), config(100));
+
+        for id in ["a", "b", "c"] {
+            writer
+                .insert_market_evaluation(&evaluation(id))
+                .await
+                .unwrap();
+        }
+        assert_eq!(evaluation_count(&store).await, 0);
+
+        writer.flush().await.unwrap();
+        assert_eq!(evaluation_count(&store).await, 3);
+
+        // Dropping the last handle commits the rest and stops the task
+        writer
+            .insert_market_evaluation(&evaluation("d"))
+            .await
+            .unwrap();
+        drop(writer);
+        handle.unwrap().await.unwrap();
+        assert_eq!(evaluation_count(&store).await, 4);
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_commits_early() {
+        let store = Store::new(":memory:").await.unwrap();
+        let (writer, _handle) = BatchWriter::spawn(store.clone_for_parallel(), config(2));
+
+        for id in ["a", "b", "c"] {
+            writer
+                .insert_market_evaluation(&evaluation(id))
+                .await
+                .unwrap();
+        }
+        for _ in 0..100 {
+            if evaluation_count(&store).await == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        // The first two went out as a full batch; the third waits
+        assert_eq!(evaluation_count(&store).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_writes_through() {
+        let store = Store::new(":memory:").await.unwrap();
+        let (writer, handle) = BatchWriter::spawn(
+            store.clone_for_parallel(),
+            WriteBatchConfig {
+                enabled: false,
+                ..WriteBatchConfig::default()
+            },
+        );
+        assert!(handle.is_none());
+
+        writer
+            .insert_market_evaluation(&evaluation("a"))
+            .await
+            .unwrap();
+        assert_eq!(evaluation_count(&store).await, 1);
+        writer.flush().await.unwrap();
+    }
+}
@@ -0,0 +1,582 @@
+//! Export of recorded history for offline analysis in pandas or Excel.
+//!
+//! Each table is flattened to typed columns, with computed columns appended
+//! (net P&L, return and holding time for trades, bankroll change for cycles,
+//! Brier score for calibration), then rendered as CSV, JSON or — with the
+//! `parquet` feature — Parquet. Decimal columns become floats in Parquet;
+//! the database keeps the exact values.
+
+use std::str::FromStr;
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::db::store::{
+    sql_timestamp, ApiCostRecord, CalibrationRecord, CycleRecord, Store, TradeRecord,
+};
+
+/// A database table that can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTable {
+    Trades,
+    Cycles,
+    ApiCosts,
+    Calibration,
+}
+
+impl ExportTable {
+    pub const ALL: [ExportTable; 4] = [
+        ExportTable::Trades,
+        ExportTable::Cycles,
+        ExportTable::ApiCosts,
+        ExportTable::Calibration,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportTable::Trades => "trades",
+            ExportTable::Cycles => "cycles",
+            ExportTable::ApiCosts => "api_costs",
+            ExportTable::Calibration => "calibration",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            "parquet" => Some(ExportFormat::Parquet),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+/// Column type, used for Parquet and JSON encoding. CSV is untyped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Int,
+    Float,
+    Text,
+}
+
+/// A table flattened for export. Cells are rendered as strings; `None` is
+/// an empty CSV field or a null.
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub columns: &'static [(&'static str, ColumnType)],
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+const TRADE_COLUMNS: &[(&str, ColumnType)] = &[
+    ("id", ColumnType::Int),
+    ("cycle", ColumnType::Int),
+    ("market_id", ColumnType::Text),
+    ("market_question", ColumnType::Text),
+    ("direction", ColumnType::Text),
+    ("entry_price", ColumnType::Float),
+    ("size", ColumnType::Float),
+    ("edge_at_entry", ColumnType::Float),
+    ("claude_fair_value", ColumnType::Float),
+    ("confidence", ColumnType::Float),
+    ("kelly_raw", ColumnType::Float),
+    ("kelly_adjusted", ColumnType::Float),
+    ("status", ColumnType::Text),
+    ("pnl", ColumnType::Float),
+    ("created_at", ColumnType::Text),
+    ("resolved_at", ColumnType::Text),
+    ("bucket", ColumnType::Text),
+    ("fee", ColumnType::Float),
+    ("order_id", ColumnType::Text),
+    ("reconciled_at", ColumnType::Text),
+    ("tags", ColumnType::Text),
+    ("notes", ColumnType::Text),
+    // Computed
+    ("cost_basis", ColumnType::Float),
+    ("gross_pnl", ColumnType::Float),
+    ("net_pnl", ColumnType::Float),
+    ("return_pct", ColumnType::Float),
+    ("holding_hours", ColumnType::Float),
+];
+
+const CYCLE_COLUMNS: &[(&str, ColumnType)] = &[
+    ("id", ColumnType::Int),
+    ("cycle_number", ColumnType::Int),
+    ("markets_scanned", ColumnType::Int),
+    ("opportunities_found", ColumnType::Int),
+    ("trades_placed", ColumnType::Int),
+    ("api_cost", ColumnType::Float),
+    ("bankroll", ColumnType::Float),
+    ("unrealized_pnl", ColumnType::Float),
+    ("agent_state", ColumnType::Text),
+    ("duration_ms", ColumnType::Int),
+    ("created_at", ColumnType::Text),
+    // Computed
+    ("bankroll_change", ColumnType::Float),
+];
+
+const API_COST_COLUMNS: &[(&str, ColumnType)] = &[
+    ("id", ColumnType::Int),
+    ("provider", ColumnType::Text),
+    ("endpoint", ColumnType::Text),
+    ("input_tokens", ColumnType::Int),
+    ("output_tokens", ColumnType::Int),
+    ("cost", ColumnType::Float),
+    ("cycle", ColumnType::Int),
+    ("created_at", ColumnType::Text),
+];
+
+const CALIBRATION_COLUMNS: &[(&str, ColumnType)] = &[
+    ("id", ColumnType::Int),
+    ("market_id", ColumnType::Text),
+    ("claude_confidence", ColumnType::Float),
+    ("fair_value", ColumnType::Float),
+    ("market_price_at_entry", ColumnType::Float),
+    ("actual_outcome", ColumnType::Float),
+    ("forecast_correct", ColumnType::Int),
+    ("resolved", ColumnType::Int),
+    ("created_at", ColumnType::Text),
+    ("resolved_at", ColumnType::Text),
+    // Computed
+    ("brier_score", ColumnType::Float),
+];
+
+/// Load and flatten one table from the store.
+pub async fn load_table(store: &Store, table: ExportTable) -> Result<Table> {
+    Ok(match table {
+        ExportTable::Trades => trades_table(&store.get_all_trades().await?),
+        ExportTable::Cycles => cycles_table(&store.get_all_cycles().await?),
+        ExportTable::ApiCosts => api_costs_table(&store.get_all_api_costs().await?),
+        ExportTable::Calibration => calibration_table(&store.get_all_calibration().await?),
+    })
+}
+
+/// Trades with cost basis, P&L before and after fees, return on cost and
+/// holding time. `pnl` is already net of the entry fee.
+pub fn trades_table(trades: &[TradeRecord]) -> Table {
+    let rows = trades
+        .iter()
+        .map(|t| {
+            let cost_basis = t.entry_price * t.size;
+            let net_pnl = t.pnl;
+            let return_pct = match net_pnl {
+                Some(pnl) if !cost_basis.is_zero() => {
+                    Some((pnl / cost_basis * dec!(100)).round_dp(2))
+                }
+                _ => None,
+            };
+            let holding_hours = match (t.created_at, t.resolved_at) {
+                (Some(opened), Some(closed)) => Some(format!(
+                    "{:.2}",
+                    (closed - opened).num_seconds() as f64 / 3600.0
+                )),
+                _ => None,
+            };
+            vec![
+                t.id.map(|id| id.to_string()),
+                Some(t.cycle.to_string()),
+                Some(t.market_id.clone()),
+                t.market_question.clone(),
+                Some(t.direction.clone()),
+                Some(t.entry_price.to_string()),
+                Some(t.size.to_string()),
+                Some(t.edge_at_entry.to_string()),
+                Some(t.claude_fair_value.to_string()),
+                Some(t.confidence.to_string()),
+                Some(t.kelly_raw.to_string()),
+                Some(t.kelly_adjusted.to_string()),
+                Some(t.status.clone()),
+                t.pnl.map(|d| d.to_string()),
+                t.created_at.map(sql_timestamp),
+                t.resolved_at.map(sql_timestamp),
+                Some(t.bucket.clone()),
+                Some(t.fee.to_string()),
+                t.order_id.clone(),
+                t.reconciled_at.map(sql_timestamp),
+                (!t.tags.is_empty()).then(|| t.tags.join(";")),
+                t.notes.clone(),
+                Some(cost_basis.round_dp(4).to_string()),
+                net_pnl.map(|pnl| (pnl + t.fee).to_string()),
+                net_pnl.map(|pnl| pnl.to_string()),
+                return_pct.map(|d| d.to_string()),
+                holding_hours,
+            ]
+        })
+        .collect();
+    Table {
+        columns: TRADE_COLUMNS,
+        rows,
+    }
+}
+
+/// Cycles with the bankroll change since the previous cycle in `cycles`.
+pub fn cycles_table(cycles: &[CycleRecord]) -> Table {
+    let mut previous: Option<Decimal> = None;
+    let rows = cycles
+        .iter()
+        .map(|c| {
+            let bankroll = c.bankroll;
+            let change = match (previous, bankroll) {
+                (Some(prev), Some(now)) => Some((now - prev).to_string()),
+                _ => None,
+            };
+            if bankroll.is_some() {
+                previous = bankroll;
+            }
+            vec![
+                c.id.map(|id| id.to_string()),
+                Some(c.cycle_number.to_string()),
+                c.markets_scanned.map(|n| n.to_string()),
+                c.opportunities_found.map(|n| n.to_string()),
+                c.trades_placed.map(|n| n.to_string()),
+                c.api_cost.map(|d| d.to_string()),
+                c.bankroll.map(|d| d.to_string()),
+                c.unrealized_pnl.map(|d| d.to_string()),
+                Some(c.agent_state.clone()),
+                c.duration_ms.map(|n| n.to_string()),
+                c.created_at.map(sql_timestamp),
+                change,
+            ]
+        })
+        .collect();
+    Table {
+        columns: CYCLE_COLUMNS,
+        rows,
+    }
+}
+
+pub fn api_costs_table(costs: &[ApiCostRecord]) -> Table {
+    let rows = costs
+        .iter()
+        .map(|c| {
+            vec![
+                c.id.map(|id| id.to_string()),
+                Some(c.provider.clone()),
+                c.endpoint.clone(),
+                c.input_tokens.map(|n| n.to_string()),
+                c.output_tokens.map(|n| n.to_string()),
+                Some(c.cost.to_string()),
+                c.cycle.map(|n| n.to_string()),
+                c.created_at.map(sql_timestamp),
+            ]
+        })
+        .collect();
+    Table {
+        columns: API_COST_COLUMNS,
+        rows,
+    }
+}
+
+/// Calibration predictions with the Brier score of each resolved one.
+pub fn calibration_table(records: &[CalibrationRecord]) -> Table {
+    let rows = records
+        .iter()
+        .map(|r| {
+            let brier = match (
+                decimal(&r.fair_value),
+                r.actual_outcome.as_deref().and_then(decimal),
+            ) {
+                (Some(p), Some(outcome)) => Some(((p - outcome) * (p - outcome)).to_string()),
+                _ => None,
+            };
+            vec![
+                r.id.map(|id| id.to_string()),
+                Some(r.market_id.clone()),
+                Some(r.claude_confidence.clone()),
+                Some(r.fair_value.clone()),
+                Some(r.market_price_at_entry.clone()),
+                r.actual_outcome.clone(),
+                r.forecast_correct.map(|b| (b as i64).to_string()),
+                Some((r.resolved as i64).to_string()),
+                r.created_at.clone(),
+                r.resolved_at.clone(),
+                brier,
+            ]
+        })
+        .collect();
+    Table {
+        columns: CALIBRATION_COLUMNS,
+        rows,
+    }
+}
+
+impl Table {
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn render(&self, format: ExportFormat) -> Result<Vec<u8>> {
+        match format {
+            ExportFormat::Csv => Ok(self.to_csv().into_bytes()),
+            ExportFormat::Json => {
+                Ok((serde_json::to_string_pretty(&self.to_json())? + "\n").into_bytes())
+            }
+            ExportFormat::Parquet => self.to_parquet(),
+        }
+    }
+
+    /// CSV with a header row.
+    pub fn to_csv(&self) -> String {
+        let header: Vec<&str> = self.columns.iter().map(|(name, _)| *name).collect();
+        let mut out = header.join(",");
+        out.push('\n');
+        for row in &self.rows {
+            let fields: Vec<String> = row
+                .iter()
+                .map(|cell| csv_field(cell.as_deref().unwrap_or_default()))
+                .collect();
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// An array of objects. Integers are numbers; decimals stay strings, as
+    /// in the dashboard API, to preserve precision.
+    pub fn to_json(&self) -> serde_json::Value {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let object = self
+                    .columns
+                    .iter()
+                    .zip(row)
+                    .map(|((name, ty), cell)| {
+                        let value = match (ty, cell) {
+                            (_, None) => serde_json::Value::Null,
+                            (ColumnType::Int, Some(s)) => s
+                                .parse::<i64>()
+                                .map(serde_json::Value::from)
+                                .unwrap_or_else(|_| serde_json::Value::from(s.as_str())),
+                            (_, Some(s)) => serde_json::Value::from(s.as_str()),
+                        };
+                        (name.to_string(), value)
+                    })
+                    .collect();
+                serde_json::Value::Object(object)
+            })
+            .collect();
+        serde_json::Value::Array(rows)
+    }
+
+    #[cfg(feature = "parquet")]
+    pub fn to_parquet(&self) -> Result<Vec<u8>> {
+        use std::sync::Arc;
+
+        use arrow_array::{ArrayRef, Float64Array, Int64Array, RecordBatch, StringArray};
+        use arrow_schema::{DataType, Field, Schema};
+        use parquet::arrow::ArrowWriter;
+
+        let fields: Vec<Field> = self
+            .columns
+            .iter()
+            .map(|(name, ty)| {
+                let data_type = match ty {
+                    ColumnType::Int => DataType::Int64,
+                    ColumnType::Float => DataType::Float64,
+                    ColumnType::Text => DataType::Utf8,
+                };
+                Field::new(*name, data_type, true)
+            })
+            .collect();
+        let arrays: Vec<ArrayRef> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, (_, ty))| {
+                let cells = self.rows.iter().map(|row| row[i].as_deref());
+                match ty {
+                    ColumnType::Int => Arc::new(
+                        cells
+                            .map(|c| c.and_then(|s| s.parse::<i64>().ok()))
+                            .collect::<Int64Array>(),
+                    ) as ArrayRef,
+                    ColumnType::Float => Arc::new(
+                        cells
+                            .map(|c| c.and_then(|s| s.parse::<f64>().ok()))
+                            .collect::<Float64Array>(),
+                    ),
+                    ColumnType::Text => Arc::new(cells.collect::<StringArray>()),
+                }
+            })
+            .collect();
+
+        let schema = Arc::new(Schema::new(fields));
+        let batch = RecordBatch::try_new(schema.clone(), arrays)?;
+        let mut out = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut out, schema, None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(out)
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    pub fn to_parquet(&self) -> Result<Vec<u8>> {
+        anyhow::bail!("Parquet export is not compiled in — rebuild with `--features parquet`")
+    }
+}
+
+/// Trades as CSV, one row per trade, with a header row.
+pub fn trades_csv(trades: &[TradeRecord]) -> String {
+    trades_table(trades).to_csv()
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or newline.
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn decimal(s: &str) -> Option<Decimal> {
+    Decimal::from_str(s).ok()
+}
+
+/// Parse SQLite `datetime('now')` output or an RFC 3339 timestamp.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|dt| dt.and_utc())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trades_csv() {
+        let trade = TradeRecord {
+            id: Some(7),
+            cycle: 3,
+            market_id: "0xabc".to_string(),
+            market_question: Some("Will it rain, or \"snow\"?".to_string()),
+            direction: "YES".to_string(),
+            entry_price: dec!(0.45),
+            size: dec!(10),
+            edge_at_entry: dec!(0.12),
+            claude_fair_value: dec!(0.57),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.2),
+            kelly_adjusted: dec!(0.08),
+            status: "RESOLVED_WIN".to_string(),
+            pnl: Some(dec!(5.5)),
+            created_at: parse_timestamp("2026-03-01 12:00:00"),
+            resolved_at: parse_timestamp("2026-03-02 18:30:00"),
+            bucket: "directional".to_string(),
+            fee: dec!(0.1),
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        };
+
+        let csv = trades_csv(&[trade]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].split(',').count(), TRADE_COLUMNS.len());
+        assert!(lines[1].starts_with("7,3,0xabc,\"Will it rain, or \"\"snow\"\"?\",YES,0.45,"));
+        // order id, reconciled at, tags, notes, then cost basis, gross and net
+        // P&L, return on cost, holding hours
+        assert!(lines[1].ends_with(",directional,0.1,,,,,4.50,5.6,5.5,122.22,30.50"));
+    }
+
+    #[test]
+    fn test_cycles_bankroll_change_and_json() {
+        let cycle = |n: i64, bankroll: Decimal| CycleRecord {
+            id: Some(n),
+            cycle_number: n,
+            markets_scanned: Some(10),
+            opportunities_found: None,
+            trades_placed: None,
+            api_cost: None,
+            bankroll: Some(bankroll),
+            unrealized_pnl: None,
+            agent_state: "Alive".to_string(),
+            duration_ms: None,
+            truncated_phases: None,
+            timings: Default::default(),
+            created_at: None,
+        };
+        let table = cycles_table(&[cycle(1, dec!(100)), cycle(2, dec!(97.5))]);
+        assert_eq!(table.rows[0].last().unwrap(), &None);
+        assert_eq!(table.rows[1].last().unwrap().as_deref(), Some("-2.5"));
+
+        let json = table.to_json();
+        assert_eq!(json[1]["cycle_number"], 2);
+        assert_eq!(json[1]["bankroll"], "97.5");
+        assert!(json[1]["trades_placed"].is_null());
+    }
+
+    #[test]
+    fn test_calibration_brier_score() {
+        let record = CalibrationRecord {
+            id: Some(1),
+            market_id: "m1".to_string(),
+            claude_confidence: "0.8".to_string(),
+            fair_value: "0.7".to_string(),
+            market_price_at_entry: "0.5".to_string(),
+            actual_outcome: Some("1".to_string()),
+            forecast_correct: Some(true),
+            resolved: true,
+            created_at: None,
+            resolved_at: None,
+        };
+        let table = calibration_table(&[record]);
+        assert_eq!(table.rows[0][6].as_deref(), Some("1"));
+        assert_eq!(table.rows[0].last().unwrap().as_deref(), Some("0.09"));
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for table in ExportTable::ALL {
+            assert_eq!(ExportTable::from_name(table.name()), Some(table));
+        }
+        assert_eq!(
+            ExportFormat::from_name("Parquet"),
+            Some(ExportFormat::Parquet)
+        );
+        assert_eq!(ExportFormat::from_name("xlsx"), None);
+    }
+}
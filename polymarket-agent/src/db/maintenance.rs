@@ -0,0 +1,246 @@
+//! Periodic database upkeep.
+//!
+//! At one cycle every 10 minutes, `cycles` and `api_costs` gain thousands
+//! of rows a month. A background task moves rows older than the retention
+//! window into monthly archive tables, vacuums and analyzes the database,
+//! and alerts when it grows past a size cap.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::db::export::parse_timestamp;
+use crate::db::store::{sql_timestamp, ArchiveCounts, Store};
+use crate::monitoring::alerts::AlertClient;
+
+/// `agent_meta` key holding when the last pass finished, so restarts don't
+/// reset the schedule.
+const LAST_RUN_KEY: &str = "maintenance_last_run";
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_interval_hours")]
+    pub interval_hours: u64,
+    /// Days of cycles and API costs kept in the live tables; 0 keeps all.
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+    /// Run VACUUM and ANALYZE after archiving.
+    #[serde(default = "default_vacuum")]
+    pub vacuum: bool,
+    /// Alert when the database exceeds this size; 0 disables the check.
+    #[serde(default = "default_max_size_mb")]
+    pub max_size_mb: u64,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            interval_hours: default_interval_hours(),
+            retention_days: default_retention_days(),
+            vacuum: default_vacuum(),
+            max_size_mb: default_max_size_mb(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_interval_hours() -> u64 {
+    24
+}
+
+fn default_retention_days() -> u32 {
+    90
+}
+
+fn default_vacuum() -> bool {
+    true
+}
+
+fn default_max_size_mb() -> u64 {
+    1024
+}
+
+impl MaintenanceConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_hours.max(1) * 3600)
+    }
+
+    pub fn exceeds_size_cap(&self, size_bytes: u64) -> bool {
+        self.max_size_mb > 0 && size_bytes > self.max_size_mb * BYTES_PER_MB
+    }
+}
+
+/// Outcome of one maintenance pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    pub archived: ArchiveCounts,
+    pub size_bytes: u64,
+}
+
+impl MaintenanceReport {
+    pub fn size_mb(&self) -> u64 {
+        self.size_bytes / BYTES_PER_MB
+    }
+}
+
+/// Time until the next pass, given when the last one finished. Due now if
+/// it never ran.
+pub fn next_run_in(
+    last_run: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    interval: Duration,
+) -> Duration {
+    let Some(last_run) = last_run else {
+        return Duration::ZERO;
+    };
+    let elapsed = (now - last_run).to_std().unwrap_or_default();
+    interval.saturating_sub(elapsed)
+}
+
+/// Archive rows past the retention window, vacuum, and measure the result.
+pub async fn run_once(
+    store: &Store,
+    config: &MaintenanceConfig,
+    now: DateTime<Utc>,
+) -> Result<MaintenanceReport> {
+    let archived = if config.retention_days > 0 {
+        let cutoff = now - chrono::Duration::days(config.retention_days.into());
+        store.archive_before(cutoff).await?
+    } else {
+        ArchiveCounts::default()
+    };
+    if config.vacuum {
+        store.vacuum().await?;
+    }
+    store.set_meta(LAST_RUN_KEY, &sql_timestamp(now)).await?;
+
+    Ok(MaintenanceReport {
+        archived,
+        size_bytes: store.database_size_bytes().await?,
+    })
+}
+
+/// Spawn the task running a maintenance pass every `interval_hours`.
+pub fn spawn_maintenance(
+    config: MaintenanceConfig,
+    store: Store,
+    alerts: AlertClient,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        info!(
+            interval_h = config.interval_hours,
+            retention_days = config.retention_days,
+            max_size_mb = config.max_size_mb,
+            "Database maintenance started"
+        );
+
+        loop {
+            let last_run = match store.get_meta(LAST_RUN_KEY).await {
+                Ok(last_run) => last_run.as_deref().and_then(parse_timestamp),
+                Err(e) => {
+                    warn!(error = %e, "Failed to load last maintenance time");
+                    None
+                }
+            };
+            tokio::time::sleep(next_run_in(last_run, Utc::now(), config.interval())).await;
+
+            let report = match run_once(&store, &config, Utc::now()).await {
+                Ok(report) => report,
+                Err(e) => {
+                    warn!(error = %e, "Database maintenance failed");
+                    tokio::time::sleep(config.interval()).await;
+                    continue;
+                }
+            };
+            info!(
+                archived_cycles = report.archived.cycles,
+                archived_api_costs = report.archived.api_costs,
+                size_mb = report.size_mb(),
+                "Database maintenance complete"
+            );
+            if config.exceeds_size_cap(report.size_bytes) {
+                warn!(
+                    size_mb = report.size_mb(),
+                    max_size_mb = config.max_size_mb,
+                    "Database exceeds its size cap"
+                );
+                if let Err(e) = alerts
+                    .database_size(report.size_mb(), config.max_size_mb)
+                    .await
+                {
+                    warn!(error = %e, "Failed to send database size alert");
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_run_in() {
+        let interval = Duration::from_secs(24 * 3600);
+        let now = parse_timestamp("2026-10-17 12:00:00").unwrap();
+
+        assert_eq!(next_run_in(None, now, interval), Duration::ZERO);
+        assert_eq!(
+            next_run_in(parse_timestamp("2026-10-17 06:00:00"), now, interval),
+            Duration::from_secs(18 * 3600)
+        );
+        assert_eq!(
+            next_run_in(parse_timestamp("2026-10-15 12:00:00"), now, interval),
+            Duration::ZERO
+        );
+        // A last run in the future (clock change) waits a full interval
+        assert_eq!(
+            next_run_in(parse_timestamp("2026-10-18 12:00:00"), now, interval),
+            interval
+        );
+    }
+
+    #[test]
+    fn test_size_cap() {
+        let config = MaintenanceConfig {
+            max_size_mb: 100,
+            ..MaintenanceConfig::default()
+        };
+        assert!(!config.exceeds_size_cap(100 * BYTES_PER_MB));
+        assert!(config.exceeds_size_cap(100 * BYTES_PER_MB + 1));
+
+        let unlimited = MaintenanceConfig {
+            max_size_mb: 0,
+            ..MaintenanceConfig::default()
+        };
+        assert!(!unlimited.exceeds_size_cap(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_run_once_records_last_run() {
+        let store = Store::new(":memory:").await.unwrap();
+        let now = parse_timestamp("2026-10-17 12:00:00").unwrap();
+
+        let report = run_once(&store, &MaintenanceConfig::default(), now)
+            .await
+            .unwrap();
+        assert_eq!(report.archived, ArchiveCounts::default());
+        assert!(report.size_bytes > 0);
+        assert_eq!(
+            store.get_meta(LAST_RUN_KEY).await.unwrap().as_deref(),
+            Some("2026-10-17 12:00:00")
+        );
+    }
+}
@@ -1,3 +1,6 @@
+pub mod batch;
+pub mod export;
+pub mod maintenance;
 pub mod store;
 
 pub use store::Store;
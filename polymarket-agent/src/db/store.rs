@@ -1,66 +1,547 @@
-use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
 use rust_decimal::Decimal;
 use serde::Serialize;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
+use sqlx::{FromRow, Row, SqliteConnection, SqlitePool};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
+use crate::clock::Clock;
+use crate::market::models::OrderBookSnapshot;
+
+/// Versioned schema migrations from `migrations/`, embedded at build time.
+/// Each runs exactly once; applied versions are recorded in
+/// `_sqlx_migrations`. Add a schema change as the next numbered file.
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Migrations 001–014, as run on every startup (idempotently) by builds
+/// before versioning. A database created by such a build is brought up to
+/// date with these once, then marked as being at [`LEGACY_SCHEMA_VERSION`].
+const LEGACY_MIGRATIONS: &[&str] = &[
+    include_str!("../../migrations/001_init.sql"),
+    include_str!("../../migrations/002_trade_buckets.sql"),
+    include_str!("../../migrations/003_agent_meta.sql"),
+    include_str!("../../migrations/004_pending_approvals.sql"),
+    include_str!("../../migrations/005_market_blacklist.sql"),
+    include_str!("../../migrations/006_trade_latency.sql"),
+    include_str!("../../migrations/007_trade_fees.sql"),
+    include_str!("../../migrations/008_trade_reconciliation.sql"),
+    include_str!("../../migrations/009_orders.sql"),
+    include_str!("../../migrations/010_execution_style_stats.sql"),
+    include_str!("../../migrations/011_redemptions.sql"),
+    include_str!("../../migrations/012_cycle_journal.sql"),
+    include_str!("../../migrations/013_order_book_snapshots.sql"),
+    include_str!("../../migrations/014_market_evaluations.sql"),
+];
+
+const LEGACY_SCHEMA_VERSION: i64 = 14;
+
+/// Newest schema version this build knows how to migrate to.
+pub fn latest_schema_version() -> i64 {
+    MIGRATOR.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
 pub struct Store {
     pool: SqlitePool,
+    /// Stamps trade entries, valuations and journal entries.
+    clock: Clock,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize)]
+/// Layout of typed timestamp columns: UTC, as produced by SQLite's
+/// `datetime('now')`.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// `at` in the layout of typed timestamp columns, for binding and comparing.
+pub fn sql_timestamp(at: DateTime<Utc>) -> String {
+    at.format(TIMESTAMP_FORMAT).to_string()
+}
+
+fn column_error(column: &str, message: String) -> sqlx::Error {
+    sqlx::Error::ColumnDecode {
+        index: column.to_string(),
+        source: message.into(),
+    }
+}
+
+/// Decode a decimal TEXT column, failing on anything that isn't one.
+fn decimal_column(row: &SqliteRow, column: &str) -> sqlx::Result<Decimal> {
+    let text: String = row.try_get(column)?;
+    Decimal::from_str(&text).map_err(|e| column_error(column, format!("{text:?}: {e}")))
+}
+
+fn optional_decimal_column(row: &SqliteRow, column: &str) -> sqlx::Result<Option<Decimal>> {
+    let text: Option<String> = row.try_get(column)?;
+    text.map(|text| {
+        Decimal::from_str(&text).map_err(|e| column_error(column, format!("{text:?}: {e}")))
+    })
+    .transpose()
+}
+
+/// Decode a timestamp column written as [`sql_timestamp`].
+fn timestamp_column(row: &SqliteRow, column: &str) -> sqlx::Result<Option<DateTime<Utc>>> {
+    let text: Option<String> = row.try_get(column)?;
+    text.map(|text| {
+        NaiveDateTime::parse_from_str(&text, TIMESTAMP_FORMAT)
+            .map(|at| at.and_utc())
+            .map_err(|e| column_error(column, format!("{text:?}: {e}")))
+    })
+    .transpose()
+}
+
+/// `agent_meta` keys carrying totals of rows moved to archive tables, so
+/// lifetime spend and cycle counts survive archiving.
+const ARCHIVED_API_COST_KEY: &str = "archived_api_cost";
+const ARCHIVED_CYCLE_COUNT_KEY: &str = "archived_cycle_count";
+
+/// `agent_meta` key carrying lifetime API spend, archived rows included.
+/// Seeded from the rows on first read, then kept up by `insert_api_cost`.
+const API_COST_TOTAL_KEY: &str = "api_cost_total";
+
+/// Exact total of `api_costs.cost` values.
+fn sum_costs(rows: &[(String,)]) -> Result<Decimal> {
+    rows.iter()
+        .map(|(cost,)| {
+            Decimal::from_str(cost).with_context(|| format!("Corrupt api_costs.cost {cost:?}"))
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct TradeRecord {
     pub id: Option<i64>,
     pub cycle: i64,
     pub market_id: String,
     pub market_question: Option<String>,
     pub direction: String,
-    pub entry_price: String,
-    pub size: String,
-    pub edge_at_entry: String,
-    pub claude_fair_value: String,
-    pub confidence: String,
-    pub kelly_raw: String,
-    pub kelly_adjusted: String,
+    pub entry_price: Decimal,
+    pub size: Decimal,
+    pub edge_at_entry: Decimal,
+    pub claude_fair_value: Decimal,
+    pub confidence: Decimal,
+    pub kelly_raw: Decimal,
+    pub kelly_adjusted: Decimal,
     pub status: String,
-    pub pnl: Option<String>,
-    pub created_at: Option<String>,
-    pub resolved_at: Option<String>,
+    pub pnl: Option<Decimal>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Strategy bucket the trade was sized against.
+    pub bucket: String,
+    /// Entry fee paid in USD (negative for a maker rebate).
+    pub fee: Decimal,
+    /// Exchange order id, for reconciling against actual fills.
+    pub order_id: Option<String>,
+    /// When entry price and size were confirmed against the exchange.
+    pub reconciled_at: Option<DateTime<Utc>>,
+    /// Operator labels, e.g. "manual override"; see [`Store::annotate_trade`].
+    pub tags: Vec<String>,
+    /// Operator note on why the trade was taken or how it went.
+    pub notes: Option<String>,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize)]
+impl TradeRecord {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+}
+
+impl<'r> FromRow<'r, SqliteRow> for TradeRecord {
+    fn from_row(row: &'r SqliteRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            cycle: row.try_get("cycle")?,
+            market_id: row.try_get("market_id")?,
+            market_question: row.try_get("market_question")?,
+            direction: row.try_get("direction")?,
+            entry_price: decimal_column(row, "entry_price")?,
+            size: decimal_column(row, "size")?,
+            edge_at_entry: decimal_column(row, "edge_at_entry")?,
+            claude_fair_value: decimal_column(row, "claude_fair_value")?,
+            confidence: decimal_column(row, "confidence")?,
+            kelly_raw: decimal_column(row, "kelly_raw")?,
+            kelly_adjusted: decimal_column(row, "kelly_adjusted")?,
+            status: row.try_get("status")?,
+            pnl: optional_decimal_column(row, "pnl")?,
+            created_at: timestamp_column(row, "created_at")?,
+            resolved_at: timestamp_column(row, "resolved_at")?,
+            bucket: row.try_get("bucket")?,
+            fee: decimal_column(row, "fee")?,
+            order_id: row.try_get("order_id")?,
+            reconciled_at: timestamp_column(row, "reconciled_at")?,
+            tags: {
+                let text: String = row.try_get("tags")?;
+                serde_json::from_str(&text)
+                    .map_err(|e| column_error("tags", format!("{text:?}: {e}")))?
+            },
+            notes: row.try_get("notes")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct CycleRecord {
     pub id: Option<i64>,
     pub cycle_number: i64,
     pub markets_scanned: Option<i64>,
     pub opportunities_found: Option<i64>,
     pub trades_placed: Option<i64>,
-    pub api_cost: Option<String>,
-    pub bankroll: Option<String>,
-    pub unrealized_pnl: Option<String>,
+    pub api_cost: Option<Decimal>,
+    pub bankroll: Option<Decimal>,
+    pub unrealized_pnl: Option<Decimal>,
     pub agent_state: String,
     pub duration_ms: Option<i64>,
-    pub created_at: Option<String>,
+    /// Phases cut short by the cycle time budget, comma-separated.
+    pub truncated_phases: Option<String>,
+    #[serde(flatten)]
+    pub timings: PhaseTimings,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize)]
+/// Milliseconds a cycle spent in each phase; `None` for phases it didn't
+/// run. Settlement covers fill reconciliation, paper matching, resolution
+/// and redemption.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PhaseTimings {
+    pub scan_ms: Option<i64>,
+    pub data_ms: Option<i64>,
+    pub valuation_ms: Option<i64>,
+    pub execution_ms: Option<i64>,
+    pub settlement_ms: Option<i64>,
+}
+
+impl PhaseTimings {
+    /// Phase names with their timings, in cycle order.
+    pub fn phases(&self) -> [(&'static str, Option<i64>); 5] {
+        [
+            ("scan", self.scan_ms),
+            ("data", self.data_ms),
+            ("valuation", self.valuation_ms),
+            ("execution", self.execution_ms),
+            ("settlement", self.settlement_ms),
+        ]
+    }
+
+    /// Add `other`'s timings to these.
+    pub fn absorb(&mut self, other: PhaseTimings) {
+        for (total, ms) in [
+            (&mut self.scan_ms, other.scan_ms),
+            (&mut self.data_ms, other.data_ms),
+            (&mut self.valuation_ms, other.valuation_ms),
+            (&mut self.execution_ms, other.execution_ms),
+            (&mut self.settlement_ms, other.settlement_ms),
+        ] {
+            if let Some(ms) = ms {
+                *total = Some(total.unwrap_or(0) + ms);
+            }
+        }
+    }
+
+    /// The phase that took longest, if any was timed.
+    pub fn slowest(&self) -> Option<(&'static str, i64)> {
+        self.phases()
+            .into_iter()
+            .filter_map(|(name, ms)| Some((name, ms?)))
+            .max_by_key(|(_, ms)| *ms)
+    }
+}
+
+/// Add `elapsed` to a phase's running total.
+pub fn add_phase_time(total: &mut Option<i64>, elapsed: std::time::Duration) {
+    *total = Some(total.unwrap_or(0) + elapsed.as_millis() as i64);
+}
+
+impl<'r> FromRow<'r, SqliteRow> for CycleRecord {
+    fn from_row(row: &'r SqliteRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            cycle_number: row.try_get("cycle_number")?,
+            markets_scanned: row.try_get("markets_scanned")?,
+            opportunities_found: row.try_get("opportunities_found")?,
+            trades_placed: row.try_get("trades_placed")?,
+            api_cost: optional_decimal_column(row, "api_cost")?,
+            bankroll: optional_decimal_column(row, "bankroll")?,
+            unrealized_pnl: optional_decimal_column(row, "unrealized_pnl")?,
+            agent_state: row.try_get("agent_state")?,
+            duration_ms: row.try_get("duration_ms")?,
+            truncated_phases: row.try_get("truncated_phases")?,
+            timings: PhaseTimings {
+                scan_ms: row.try_get("scan_ms")?,
+                data_ms: row.try_get("data_ms")?,
+                valuation_ms: row.try_get("valuation_ms")?,
+                execution_ms: row.try_get("execution_ms")?,
+                settlement_ms: row.try_get("settlement_ms")?,
+            },
+            created_at: timestamp_column(row, "created_at")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ApiCostRecord {
     pub id: Option<i64>,
     pub provider: String,
     pub endpoint: Option<String>,
     pub input_tokens: Option<i64>,
     pub output_tokens: Option<i64>,
-    pub cost: String,
+    pub cost: Decimal,
     pub cycle: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for ApiCostRecord {
+    fn from_row(row: &'r SqliteRow) -> sqlx::Result<Self> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            provider: row.try_get("provider")?,
+            endpoint: row.try_get("endpoint")?,
+            input_tokens: row.try_get("input_tokens")?,
+            output_tokens: row.try_get("output_tokens")?,
+            cost: decimal_column(row, "cost")?,
+            cycle: row.try_get("cycle")?,
+            created_at: timestamp_column(row, "created_at")?,
+        })
+    }
+}
+
+/// A confidence calibration prediction and, once resolved, its outcome.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct CalibrationRecord {
+    pub id: Option<i64>,
+    pub market_id: String,
+    pub claude_confidence: String,
+    pub fair_value: String,
+    pub market_price_at_entry: String,
+    /// 1 if the market resolved YES, 0 if NO.
+    pub actual_outcome: Option<String>,
+    pub forecast_correct: Option<bool>,
+    pub resolved: bool,
+    pub created_at: Option<String>,
+    pub resolved_at: Option<String>,
+}
+
+/// A trade parked until a human approves it. Mirrors `PreparedOrder`.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct ApprovalRecord {
+    pub id: Option<i64>,
+    pub cycle: i64,
+    pub market_id: String,
+    pub market_question: String,
+    pub category: String,
+    pub token_id: String,
+    pub direction: String,
+    pub price: String,
+    pub size: String,
+    pub size_usd: String,
+    pub edge: String,
+    pub fair_value: String,
+    pub confidence: String,
+    pub kelly_raw: String,
+    pub kelly_adjusted: String,
+    pub bucket: String,
+    pub status: String,
+    pub created_at: Option<String>,
+    pub expires_at: String,
+    pub decided_at: Option<String>,
+}
+
+/// A market that will not be traded again after exceeding its loss limit.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct BlacklistRecord {
+    pub market_id: String,
+    pub market_question: Option<String>,
+    pub realized_loss: String,
+    pub blacklisted_at: Option<String>,
+}
+
+/// One valuation of a market, whether or not it cleared the edge threshold.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct MarketEvaluationRecord {
+    pub id: Option<i64>,
+    pub condition_id: String,
+    pub cycle: i64,
+    pub fair_value: String,
+    pub market_price: String,
+    /// Net edge when one was found.
+    pub net_edge: Option<String>,
+    pub edge_found: bool,
+    pub evaluated_at: Option<String>,
+}
+
+/// A valued opportunity and what the agent did with it; see
+/// [`crate::agent::ideas`].
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct TradeIdeaRecord {
+    pub id: Option<i64>,
+    pub cycle: i64,
+    pub market_id: String,
+    pub market_question: String,
+    pub category: String,
+    pub side: String,
+    /// `TRADED` or `SKIPPED`.
+    pub decision: String,
+    /// Reason code of a skipped idea.
+    pub reason: Option<String>,
+    pub fair_value: String,
+    pub market_price: String,
+    pub net_edge: Option<String>,
+    pub confidence: String,
+    /// JSON array of the valuation's key factors.
+    pub key_factors: String,
+    pub size_usd: Option<String>,
+    pub end_date: String,
+    pub created_at: Option<String>,
+    pub resolved_at: Option<String>,
+    pub side_won: Option<bool>,
+    pub correct: Option<bool>,
+}
+
+/// Ideas with one decision (or skip reason), and how they turned out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IdeaOutcomes {
+    pub ideas: i64,
+    pub resolved: i64,
+    /// Resolved ideas whose favored side won.
+    pub side_won: i64,
+}
+
+/// Post-mortem of the trade idea journal.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IdeaStats {
+    pub traded: IdeaOutcomes,
+    pub skipped: IdeaOutcomes,
+    /// Skipped ideas whose side went on to win.
+    pub skipped_winners: i64,
+    /// Traded ideas whose side lost.
+    pub traded_losers: i64,
+    /// Skipped ideas per reason code.
+    pub by_reason: std::collections::BTreeMap<String, IdeaOutcomes>,
+}
+
+/// Pipeline timestamps for an executed trade (RFC 3339, millisecond precision).
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct LatencyRecord {
+    pub trade_id: i64,
+    pub scanned_at: String,
+    pub data_fetched_at: String,
+    pub valued_at: String,
+    pub submitted_at: String,
+    pub filled_at: String,
+}
+
+/// An order submitted to the exchange. `OPEN` orders may still be resting.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct OrderRecord {
+    pub order_id: String,
+    pub market_id: String,
+    pub token_id: String,
+    pub side: String,
+    pub price: String,
+    pub size: String,
+    pub status: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// An on-chain redemption of a resolved market's winning tokens.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct RedemptionRecord {
+    pub market_id: String,
+    pub tx_hash: String,
+    pub gas_used: i64,
+    /// Gas paid in USD.
+    pub gas_cost: String,
+    pub redeemed_at: String,
+}
+
+/// One journaled action of a cycle, written before it is carried out.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct JournalEntry {
+    pub id: i64,
+    pub cycle: i64,
+    /// `VALUATION` or `ORDER`.
+    pub kind: String,
+    pub market_id: String,
+    /// JSON: the valuation, or the prepared order.
+    pub payload: String,
+    pub status: String,
+    pub order_id: Option<String>,
     pub created_at: Option<String>,
+    pub completed_at: Option<String>,
+}
+
+/// Per-phase averages over `cycles`, before rounding to whole milliseconds.
+#[derive(FromRow)]
+struct AvgPhaseTimings {
+    scan_ms: Option<f64>,
+    data_ms: Option<f64>,
+    valuation_ms: Option<f64>,
+    execution_ms: Option<f64>,
+    settlement_ms: Option<f64>,
+}
+
+/// Rows moved out of the live tables by [`Store::archive_before`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArchiveCounts {
+    pub cycles: u64,
+    pub api_costs: u64,
+}
+
+/// A captured order book. `bids` and `asks` are JSON arrays of price levels.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct OrderBookSnapshotRecord {
+    pub id: i64,
+    pub market_id: String,
+    pub token_id: String,
+    pub best_bid: String,
+    pub best_ask: String,
+    pub midpoint: String,
+    pub spread: String,
+    pub bids: String,
+    pub asks: String,
+    pub captured_at: Option<String>,
+}
+
+/// Running fill statistics for one execution style.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct ExecutionStyleStats {
+    pub style: String,
+    pub orders: i64,
+    pub filled_orders: i64,
+    pub requested_shares: String,
+    pub filled_shares: String,
+    pub updated_at: Option<String>,
+}
+
+impl ExecutionStyleStats {
+    /// Share of orders that filled at all.
+    pub fn fill_rate(&self) -> Decimal {
+        if self.orders == 0 {
+            return Decimal::ZERO;
+        }
+        Decimal::from(self.filled_orders) / Decimal::from(self.orders)
+    }
 }
 
 impl Store {
     /// Create a Store from an existing pool (for sharing between Agent and Dashboard).
     pub fn from_pool(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            clock: Clock::default(),
+        }
+    }
+
+    /// Stamp trades, valuations and journal entries with `clock` instead of
+    /// the system clock, e.g. a simulation's.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The clock this store's trade and valuation timestamps follow.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
     }
 
     /// Get a reference to the underlying connection pool.
@@ -73,6 +554,7 @@ impl Store {
     pub fn clone_for_parallel(&self) -> Self {
         Self {
             pool: self.pool.clone(),
+            clock: self.clock.clone(),
         }
     }
 
@@ -82,52 +564,142 @@ impl Store {
             .create_if_missing(true)
             .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal);
 
+        // Migrate on a connection of its own and open the working pool
+        // afterwards: a connection that prepared `SELECT * FROM trades`
+        // before a migration added columns would keep reading the old shape
+        let migration_pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options.clone())
+            .await
+            .context("Failed to connect to SQLite database")?;
+        Self::from_pool(migration_pool.clone()).migrate().await?;
+
         let pool = SqlitePoolOptions::new()
             .max_connections(5)
             .connect_with(options)
             .await
             .context("Failed to connect to SQLite database")?;
+        // An in-memory database lives only as long as a connection to it,
+        // so the migration pool closes once the working pool holds one
+        migration_pool.close().await;
 
-        let store = Self { pool };
-        store.migrate().await?;
-
-        Ok(store)
+        Ok(Self::from_pool(pool))
     }
 
     async fn migrate(&self) -> Result<()> {
-        let migration_sql = include_str!("../../migrations/001_init.sql");
-        // Execute each statement separately (sqlx doesn't support multiple statements in one call)
-        for statement in migration_sql.split(';') {
-            let trimmed = statement.trim();
-            if !trimmed.is_empty() {
-                sqlx::query(trimmed)
-                    .execute(&self.pool)
-                    .await
-                    .with_context(|| format!("Failed to execute migration: {trimmed}"))?;
+        let tables = self.table_names().await?;
+        if tables.iter().any(|t| t == "trades") && !tables.iter().any(|t| t == "_sqlx_migrations") {
+            self.baseline_legacy_schema().await?;
+        }
+
+        let latest = latest_schema_version();
+        if let Some(current) = self.schema_version().await? {
+            if current > latest {
+                bail!(
+                    "Database schema is at version {current}, newer than this build supports \
+                     ({latest}) — run a newer agent or restore a backup"
+                );
+            }
+        }
+
+        MIGRATOR
+            .run(&self.pool)
+            .await
+            .context("Failed to run database migrations")?;
+        Ok(())
+    }
+
+    /// Bring a database from before versioned migrations up to
+    /// [`LEGACY_SCHEMA_VERSION`] and record those migrations as applied.
+    async fn baseline_legacy_schema(&self) -> Result<()> {
+        for migration_sql in LEGACY_MIGRATIONS {
+            for statement in migration_sql.split(';') {
+                let trimmed = statement.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+                if let Err(e) = sqlx::query(trimmed).execute(&self.pool).await {
+                    // SQLite has no ADD COLUMN IF NOT EXISTS; columns a
+                    // previous build already added hit this
+                    if e.to_string().contains("duplicate column name") {
+                        continue;
+                    }
+                    return Err(e)
+                        .with_context(|| format!("Failed to execute migration: {trimmed}"));
+                }
             }
         }
+
+        let mut conn = self.pool.acquire().await?;
+        conn.ensure_migrations_table()
+            .await
+            .context("Failed to create migrations table")?;
+        for migration in MIGRATOR
+            .iter()
+            .filter(|m| m.version <= LEGACY_SCHEMA_VERSION)
+        {
+            sqlx::query(
+                "INSERT OR IGNORE INTO _sqlx_migrations
+                 (version, description, success, checksum, execution_time)
+                 VALUES (?, ?, TRUE, ?, 0)",
+            )
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(&mut *conn)
+            .await
+            .context("Failed to record legacy migration")?;
+        }
+        tracing::info!(
+            version = LEGACY_SCHEMA_VERSION,
+            "Adopted existing database into versioned migrations"
+        );
         Ok(())
     }
 
+    /// Highest successfully applied migration, or `None` before the first.
+    pub async fn schema_version(&self) -> Result<Option<i64>> {
+        if !self
+            .table_names()
+            .await?
+            .iter()
+            .any(|t| t == "_sqlx_migrations")
+        {
+            return Ok(None);
+        }
+        let row: (Option<i64>,) =
+            sqlx::query_as("SELECT MAX(version) FROM _sqlx_migrations WHERE success = TRUE")
+                .fetch_one(&self.pool)
+                .await
+                .context("Failed to read schema version")?;
+        Ok(row.0)
+    }
+
     // --- Trade operations ---
 
     pub async fn insert_trade(&self, trade: &TradeRecord) -> Result<i64> {
         let result = sqlx::query(
-            "INSERT INTO trades (cycle, market_id, market_question, direction, entry_price, size, edge_at_entry, claude_fair_value, confidence, kelly_raw, kelly_adjusted, status)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO trades (cycle, market_id, market_question, direction, entry_price, size, edge_at_entry, claude_fair_value, confidence, kelly_raw, kelly_adjusted, status, bucket, fee, order_id, tags, notes, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(trade.cycle)
         .bind(&trade.market_id)
         .bind(&trade.market_question)
         .bind(&trade.direction)
-        .bind(&trade.entry_price)
-        .bind(&trade.size)
-        .bind(&trade.edge_at_entry)
-        .bind(&trade.claude_fair_value)
-        .bind(&trade.confidence)
-        .bind(&trade.kelly_raw)
-        .bind(&trade.kelly_adjusted)
+        .bind(trade.entry_price.to_string())
+        .bind(trade.size.to_string())
+        .bind(trade.edge_at_entry.to_string())
+        .bind(trade.claude_fair_value.to_string())
+        .bind(trade.confidence.to_string())
+        .bind(trade.kelly_raw.to_string())
+        .bind(trade.kelly_adjusted.to_string())
         .bind(&trade.status)
+        .bind(&trade.bucket)
+        .bind(trade.fee.to_string())
+        .bind(&trade.order_id)
+        .bind(serde_json::to_string(&trade.tags)?)
+        .bind(&trade.notes)
+        .bind(sql_timestamp(self.clock.now()))
         .execute(&self.pool)
         .await
         .context("Failed to insert trade")?;
@@ -145,7 +717,7 @@ impl Store {
         sqlx::query("UPDATE trades SET status = ?, pnl = ?, resolved_at = ? WHERE id = ?")
             .bind(status)
             .bind(pnl.map(|d| d.to_string()))
-            .bind(resolved_at.map(|dt| dt.to_rfc3339()))
+            .bind(resolved_at.map(sql_timestamp))
             .bind(id)
             .execute(&self.pool)
             .await
@@ -153,6 +725,36 @@ impl Store {
         Ok(())
     }
 
+    /// Open trades with an exchange order id whose fill is not yet confirmed.
+    pub async fn get_unreconciled_trades(&self) -> Result<Vec<TradeRecord>> {
+        let trades = sqlx::query_as::<_, TradeRecord>(
+            "SELECT * FROM trades WHERE status = 'OPEN' AND order_id IS NOT NULL AND reconciled_at IS NULL",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch unreconciled trades")?;
+        Ok(trades)
+    }
+
+    /// Overwrite a trade's entry price and size with the confirmed fill.
+    pub async fn update_trade_fill(
+        &self,
+        id: i64,
+        entry_price: Decimal,
+        size: Decimal,
+        reconciled_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE trades SET entry_price = ?, size = ?, reconciled_at = ? WHERE id = ?")
+            .bind(entry_price.to_string())
+            .bind(size.to_string())
+            .bind(sql_timestamp(reconciled_at))
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to update trade fill")?;
+        Ok(())
+    }
+
     pub async fn get_open_trades(&self) -> Result<Vec<TradeRecord>> {
         let trades = sqlx::query_as::<_, TradeRecord>("SELECT * FROM trades WHERE status = 'OPEN'")
             .fetch_all(&self.pool)
@@ -174,18 +776,24 @@ impl Store {
 
     pub async fn insert_cycle(&self, cycle: &CycleRecord) -> Result<i64> {
         let result = sqlx::query(
-            "INSERT INTO cycles (cycle_number, markets_scanned, opportunities_found, trades_placed, api_cost, bankroll, unrealized_pnl, agent_state, duration_ms)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO cycles (cycle_number, markets_scanned, opportunities_found, trades_placed, api_cost, bankroll, unrealized_pnl, agent_state, duration_ms, truncated_phases, scan_ms, data_ms, valuation_ms, execution_ms, settlement_ms)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(cycle.cycle_number)
         .bind(cycle.markets_scanned)
         .bind(cycle.opportunities_found)
         .bind(cycle.trades_placed)
-        .bind(&cycle.api_cost)
-        .bind(&cycle.bankroll)
-        .bind(&cycle.unrealized_pnl)
+        .bind(cycle.api_cost.map(|d| d.to_string()))
+        .bind(cycle.bankroll.map(|d| d.to_string()))
+        .bind(cycle.unrealized_pnl.map(|d| d.to_string()))
         .bind(&cycle.agent_state)
         .bind(cycle.duration_ms)
+        .bind(&cycle.truncated_phases)
+        .bind(cycle.timings.scan_ms)
+        .bind(cycle.timings.data_ms)
+        .bind(cycle.timings.valuation_ms)
+        .bind(cycle.timings.execution_ms)
+        .bind(cycle.timings.settlement_ms)
         .execute(&self.pool)
         .await
         .context("Failed to insert cycle")?;
@@ -229,7 +837,13 @@ impl Store {
             .fetch_one(&self.pool)
             .await
             .context("Failed to count cycles")?;
-        Ok(row.0)
+        let archived = match self.get_meta(ARCHIVED_CYCLE_COUNT_KEY).await? {
+            Some(count) => count.parse::<i64>().with_context(|| {
+                format!("Corrupt agent_meta.{ARCHIVED_CYCLE_COUNT_KEY} {count:?}")
+            })?,
+            None => 0,
+        };
+        Ok(row.0 + archived)
     }
 
     /// Get average cycle duration in milliseconds.
@@ -242,9 +856,31 @@ impl Store {
         Ok(row.0)
     }
 
+    /// Average milliseconds per phase over the cycles that ran it.
+    pub async fn get_avg_phase_timings(&self) -> Result<PhaseTimings> {
+        let avg: AvgPhaseTimings = sqlx::query_as(
+            "SELECT AVG(scan_ms) AS scan_ms, AVG(data_ms) AS data_ms,
+                    AVG(valuation_ms) AS valuation_ms, AVG(execution_ms) AS execution_ms,
+                    AVG(settlement_ms) AS settlement_ms
+             FROM cycles",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to get average phase timings")?;
+        let ms = |avg: Option<f64>| avg.map(|a| a.round() as i64);
+        Ok(PhaseTimings {
+            scan_ms: ms(avg.scan_ms),
+            data_ms: ms(avg.data_ms),
+            valuation_ms: ms(avg.valuation_ms),
+            execution_ms: ms(avg.execution_ms),
+            settlement_ms: ms(avg.settlement_ms),
+        })
+    }
+
     // --- API cost operations ---
 
     pub async fn insert_api_cost(&self, cost: &ApiCostRecord) -> Result<i64> {
+        let mut tx = self.pool.begin().await?;
         let result = sqlx::query(
             "INSERT INTO api_costs (provider, endpoint, input_tokens, output_tokens, cost, cycle)
              VALUES (?, ?, ?, ?, ?, ?)",
@@ -253,41 +889,51 @@ impl Store {
         .bind(&cost.endpoint)
         .bind(cost.input_tokens)
         .bind(cost.output_tokens)
-        .bind(&cost.cost)
+        .bind(cost.cost.to_string())
         .bind(cost.cycle)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .context("Failed to insert API cost")?;
 
+        // Until the total is first read and seeded, the rows are the total
+        if meta_decimal(&mut tx, API_COST_TOTAL_KEY).await?.is_some() {
+            add_to_meta(&mut tx, API_COST_TOTAL_KEY, cost.cost).await?;
+        }
+        tx.commit().await.context("Failed to commit API cost")?;
+
         Ok(result.last_insert_rowid())
     }
 
+    /// Lifetime API spend, archived rows included. Read from the running
+    /// total in `agent_meta`; the first call sums the rows to seed it.
     pub async fn get_total_api_cost(&self) -> Result<Decimal> {
-        let row: (Option<String>,) =
-            sqlx::query_as("SELECT CAST(SUM(CAST(cost AS REAL)) AS TEXT) FROM api_costs")
-                .fetch_one(&self.pool)
-                .await
-                .context("Failed to get total API cost")?;
-
-        match row.0 {
-            Some(s) => Ok(Decimal::from_str(&s).unwrap_or(Decimal::ZERO)),
-            None => Ok(Decimal::ZERO),
+        let mut tx = self.pool.begin().await?;
+        if let Some(total) = meta_decimal(&mut tx, API_COST_TOTAL_KEY).await? {
+            return Ok(total);
         }
+
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT cost FROM api_costs")
+            .fetch_all(&mut *tx)
+            .await
+            .context("Failed to get total API cost")?;
+        let archived = meta_decimal(&mut tx, ARCHIVED_API_COST_KEY)
+            .await?
+            .unwrap_or(Decimal::ZERO);
+        let total = sum_costs(&rows)? + archived;
+        add_to_meta(&mut tx, API_COST_TOTAL_KEY, total).await?;
+        tx.commit().await.context("Failed to seed total API cost")?;
+        Ok(total)
     }
 
     /// Get total API spend for the current UTC day.
     pub async fn get_today_api_cost(&self) -> Result<Decimal> {
-        let row: (Option<String>,) = sqlx::query_as(
-            "SELECT CAST(SUM(CAST(cost AS REAL)) AS TEXT) FROM api_costs WHERE created_at >= date('now')",
-        )
-        .fetch_one(&self.pool)
-        .await
-        .context("Failed to get today's API cost")?;
+        let rows: Vec<(String,)> =
+            sqlx::query_as("SELECT cost FROM api_costs WHERE created_at >= date('now')")
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to get today's API cost")?;
 
-        match row.0 {
-            Some(s) => Ok(Decimal::from_str(&s).unwrap_or(Decimal::ZERO)),
-            None => Ok(Decimal::ZERO),
-        }
+        sum_costs(&rows)
     }
 
     /// Get all cycles ordered by cycle number.
@@ -308,6 +954,17 @@ impl Store {
         Ok(costs)
     }
 
+    /// Get all calibration predictions, resolved or not.
+    pub async fn get_all_calibration(&self) -> Result<Vec<CalibrationRecord>> {
+        let records = sqlx::query_as::<_, CalibrationRecord>(
+            "SELECT * FROM confidence_calibration ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch calibration records")?;
+        Ok(records)
+    }
+
     /// Get recent trades with a limit.
     pub async fn get_recent_trades(&self, limit: i64) -> Result<Vec<TradeRecord>> {
         let trades =
@@ -319,70 +976,1254 @@ impl Store {
         Ok(trades)
     }
 
-    pub async fn get_api_cost_for_cycle(&self, cycle: i64) -> Result<Decimal> {
-        let row: (Option<String>,) = sqlx::query_as(
-            "SELECT CAST(SUM(CAST(cost AS REAL)) AS TEXT) FROM api_costs WHERE cycle = ?",
+    /// Get the newest trades, optionally only those with `status`.
+    pub async fn get_trades_filtered(
+        &self,
+        status: Option<&str>,
+        tag: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<TradeRecord>> {
+        let trades = sqlx::query_as::<_, TradeRecord>(
+            "SELECT * FROM trades
+             WHERE (? IS NULL OR status = ?)
+               AND (? IS NULL OR EXISTS (SELECT 1 FROM json_each(trades.tags) WHERE value = ?))
+             ORDER BY id DESC LIMIT ?",
         )
-        .bind(cycle)
-        .fetch_one(&self.pool)
+        .bind(status)
+        .bind(status)
+        .bind(tag)
+        .bind(tag)
+        .bind(limit)
+        .fetch_all(&self.pool)
         .await
-        .context("Failed to get API cost for cycle")?;
+        .context("Failed to fetch filtered trades")?;
+        Ok(trades)
+    }
 
-        match row.0 {
-            Some(s) => Ok(Decimal::from_str(&s).unwrap_or(Decimal::ZERO)),
-            None => Ok(Decimal::ZERO),
-        }
+    /// Replace a trade's tags and note. Returns false if there is no such
+    /// trade.
+    pub async fn annotate_trade(
+        &self,
+        id: i64,
+        tags: &[String],
+        notes: Option<&str>,
+    ) -> Result<bool> {
+        let result = sqlx::query("UPDATE trades SET tags = ?, notes = ? WHERE id = ?")
+            .bind(serde_json::to_string(tags)?)
+            .bind(notes)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to annotate trade")?;
+        Ok(result.rows_affected() > 0)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Every tag in use, with how many trades carry it.
+    pub async fn get_trade_tags(&self) -> Result<Vec<(String, i64)>> {
+        let tags = sqlx::query_as(
+            "SELECT tag.value, COUNT(*) FROM trades, json_each(trades.tags) AS tag
+             GROUP BY tag.value ORDER BY tag.value",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list trade tags")?;
+        Ok(tags)
+    }
 
-    #[tokio::test]
-    async fn test_store_create_and_migrate() {
-        let store = Store::new(":memory:").await.expect("should create store");
-        // Verify tables exist by inserting a cycle
-        let cycle = CycleRecord {
-            id: None,
-            cycle_number: 1,
-            markets_scanned: Some(50),
-            opportunities_found: Some(3),
-            trades_placed: Some(1),
-            api_cost: Some("0.05".to_string()),
-            bankroll: Some("100.00".to_string()),
-            unrealized_pnl: Some("0.00".to_string()),
-            agent_state: "ALIVE".to_string(),
-            duration_ms: Some(1500),
-            created_at: None,
-        };
-        let id = store
-            .insert_cycle(&cycle)
-            .await
-            .expect("should insert cycle");
-        assert!(id > 0);
+    /// Get the newest cycles, most recent first.
+    pub async fn get_recent_cycles(&self, limit: i64) -> Result<Vec<CycleRecord>> {
+        let cycles = sqlx::query_as::<_, CycleRecord>(
+            "SELECT * FROM cycles ORDER BY cycle_number DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch recent cycles")?;
+        Ok(cycles)
     }
 
-    #[tokio::test]
-    async fn test_trade_insert_and_query() {
-        let store = Store::new(":memory:").await.expect("should create store");
-        let trade = TradeRecord {
-            id: None,
+    /// Get the newest API cost records, optionally for one provider.
+    pub async fn get_recent_api_costs(
+        &self,
+        provider: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ApiCostRecord>> {
+        let costs = sqlx::query_as::<_, ApiCostRecord>(
+            "SELECT * FROM api_costs WHERE (? IS NULL OR provider = ?) ORDER BY id DESC LIMIT ?",
+        )
+        .bind(provider)
+        .bind(provider)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch recent API costs")?;
+        Ok(costs)
+    }
+
+    pub async fn get_api_cost_for_cycle(&self, cycle: i64) -> Result<Decimal> {
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT cost FROM api_costs WHERE cycle = ?")
+            .bind(cycle)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to get API cost for cycle")?;
+
+        sum_costs(&rows)
+    }
+
+    /// Gas booked to `api_costs` (provider "polygon") during one cycle.
+    pub async fn get_gas_cost_for_cycle(&self, cycle: i64) -> Result<Decimal> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT cost FROM api_costs
+             WHERE cycle = ? AND provider = 'polygon'",
+        )
+        .bind(cycle)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to get gas cost for cycle")?;
+
+        sum_costs(&rows)
+    }
+
+    // --- Maintenance ---
+
+    /// Move `cycles` and `api_costs` rows created before `before` into
+    /// monthly tables such as `cycles_archive_2026_01`. The latest cycle is
+    /// always kept so numbering resumes from it, and the archived spend and
+    /// cycle count are added to `agent_meta` in the same transaction.
+    pub async fn archive_before(&self, before: DateTime<Utc>) -> Result<ArchiveCounts> {
+        let before = sql_timestamp(before);
+        let mut tx = self.pool.begin().await?;
+
+        let costs: Vec<(String,)> =
+            sqlx::query_as("SELECT cost FROM api_costs WHERE created_at < ?")
+                .bind(&before)
+                .fetch_all(&mut *tx)
+                .await
+                .context("Failed to load API costs to archive")?;
+        let archived_cost = sum_costs(&costs)?;
+
+        let counts = ArchiveCounts {
+            cycles: archive_rows(
+                &mut tx,
+                "cycles",
+                "created_at < ? AND cycle_number < (SELECT MAX(cycle_number) FROM cycles)",
+                &before,
+            )
+            .await?,
+            api_costs: archive_rows(&mut tx, "api_costs", "created_at < ?", &before).await?,
+        };
+
+        add_to_meta(&mut tx, ARCHIVED_API_COST_KEY, archived_cost).await?;
+        add_to_meta(
+            &mut tx,
+            ARCHIVED_CYCLE_COUNT_KEY,
+            Decimal::from(counts.cycles),
+        )
+        .await?;
+        tx.commit().await.context("Failed to commit archive")?;
+        Ok(counts)
+    }
+
+    /// Rebuild the file to release free pages, refresh the query planner's
+    /// statistics, and truncate the WAL.
+    pub async fn vacuum(&self) -> Result<()> {
+        for statement in ["VACUUM", "ANALYZE", "PRAGMA wal_checkpoint(TRUNCATE)"] {
+            sqlx::query(statement)
+                .execute(&self.pool)
+                .await
+                .with_context(|| format!("Database maintenance failed at {statement}"))?;
+        }
+        Ok(())
+    }
+
+    /// Size of the database in bytes, excluding the WAL.
+    pub async fn database_size_bytes(&self) -> Result<u64> {
+        let row: (i64,) = sqlx::query_as(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to get database size")?;
+        Ok(row.0.max(0) as u64)
+    }
+
+    // --- Agent metadata ---
+
+    pub async fn get_meta(&self, key: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT value FROM agent_meta WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await
+            .context("Failed to get agent metadata")?;
+        Ok(row.map(|r| r.0))
+    }
+
+    pub async fn set_meta(&self, key: &str, value: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO agent_meta (key, value, updated_at) VALUES (?, ?, datetime('now'))
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        )
+        .bind(key)
+        .bind(value)
+        .execute(&self.pool)
+        .await
+        .context("Failed to set agent metadata")?;
+        Ok(())
+    }
+
+    // --- Valuations ---
+
+    /// Latest cached (probability, confidence) for a market, if it was
+    /// valued after `since` (a SQLite `datetime` string).
+    pub async fn get_valuation_since(
+        &self,
+        condition_id: &str,
+        since: &str,
+    ) -> Result<Option<(Decimal, Decimal)>> {
+        let row: Option<(String, String)> = sqlx::query_as(
+            "SELECT probability, confidence FROM valuation_cache
+             WHERE condition_id = ? AND cached_at > ?",
+        )
+        .bind(condition_id)
+        .bind(since)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch valuation")?;
+
+        Ok(row.and_then(|(p, c)| Some((Decimal::from_str(&p).ok()?, Decimal::from_str(&c).ok()?))))
+    }
+
+    /// Mark a market's cached valuation as traded on, so the next entry on
+    /// the market needs a fresh one. Re-valuations replace the row and
+    /// clear the mark.
+    pub async fn consume_valuation(&self, condition_id: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE valuation_cache SET consumed_at = ?
+             WHERE condition_id = ? AND consumed_at IS NULL",
+        )
+        .bind(sql_timestamp(self.clock.now()))
+        .bind(condition_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to mark valuation consumed")?;
+        Ok(())
+    }
+
+    /// `(condition_id, probability, confidence)` of every cached valuation
+    /// newer than `since`, newest first.
+    pub async fn get_valuations_since(
+        &self,
+        since: &str,
+    ) -> Result<Vec<(String, Decimal, Decimal)>> {
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT condition_id, probability, confidence FROM valuation_cache
+             WHERE cached_at > ? ORDER BY cached_at DESC, condition_id",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch valuations")?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, p, c)| {
+                Some((id, Decimal::from_str(&p).ok()?, Decimal::from_str(&c).ok()?))
+            })
+            .collect())
+    }
+
+    // --- Trade latency ---
+
+    pub async fn insert_trade_latency(&self, record: &LatencyRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO trade_latency (trade_id, scanned_at, data_fetched_at, valued_at, submitted_at, filled_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(record.trade_id)
+        .bind(&record.scanned_at)
+        .bind(&record.data_fetched_at)
+        .bind(&record.valued_at)
+        .bind(&record.submitted_at)
+        .bind(&record.filled_at)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert trade latency")?;
+        Ok(())
+    }
+
+    pub async fn get_trade_latencies(&self) -> Result<Vec<LatencyRecord>> {
+        let records = sqlx::query_as::<_, LatencyRecord>("SELECT * FROM trade_latency")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch trade latencies")?;
+        Ok(records)
+    }
+
+    // --- Orders ---
+
+    pub async fn insert_order(&self, order: &OrderRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO orders (order_id, market_id, token_id, side, price, size, status)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&order.order_id)
+        .bind(&order.market_id)
+        .bind(&order.token_id)
+        .bind(&order.side)
+        .bind(&order.price)
+        .bind(&order.size)
+        .bind(&order.status)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert order")?;
+        Ok(())
+    }
+
+    pub async fn update_order_status(&self, order_id: &str, status: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE orders SET status = ?, updated_at = datetime('now') WHERE order_id = ?",
+        )
+        .bind(status)
+        .bind(order_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update order status")?;
+        Ok(())
+    }
+
+    pub async fn get_orders_by_status(&self, status: &str) -> Result<Vec<OrderRecord>> {
+        let orders = sqlx::query_as::<_, OrderRecord>(
+            "SELECT * FROM orders WHERE status = ? ORDER BY created_at, order_id",
+        )
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch orders")?;
+        Ok(orders)
+    }
+
+    pub async fn get_recent_orders(&self, limit: i64) -> Result<Vec<OrderRecord>> {
+        let orders = sqlx::query_as::<_, OrderRecord>(
+            "SELECT * FROM orders ORDER BY created_at DESC, rowid DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch recent orders")?;
+        Ok(orders)
+    }
+
+    /// Mark every open order cancelled. Returns how many were open.
+    pub async fn cancel_open_orders(&self) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE orders SET status = 'CANCELLED', updated_at = datetime('now') WHERE status = 'OPEN'",
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to cancel open orders")?;
+        Ok(result.rows_affected())
+    }
+
+    // --- Redemptions ---
+
+    /// Winning trades confirmed against live fills whose market has not been
+    /// redeemed yet.
+    pub async fn get_unredeemed_wins(&self) -> Result<Vec<TradeRecord>> {
+        let trades = sqlx::query_as::<_, TradeRecord>(
+            "SELECT * FROM trades WHERE status = 'RESOLVED_WIN' AND reconciled_at IS NOT NULL
+             AND market_id NOT IN (SELECT market_id FROM redemptions)
+             ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch unredeemed wins")?;
+        Ok(trades)
+    }
+
+    pub async fn insert_redemption(&self, record: &RedemptionRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO redemptions (market_id, tx_hash, gas_used, gas_cost, redeemed_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&record.market_id)
+        .bind(&record.tx_hash)
+        .bind(record.gas_used)
+        .bind(&record.gas_cost)
+        .bind(&record.redeemed_at)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert redemption")?;
+        Ok(())
+    }
+
+    // --- Cycle journal ---
+
+    pub async fn insert_journal_entry(
+        &self,
+        cycle: i64,
+        kind: &str,
+        market_id: &str,
+        payload: &str,
+        status: &str,
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            "INSERT INTO cycle_journal (cycle, kind, market_id, payload, status, created_at, completed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, CASE WHEN ?5 IN ('PENDING', 'SUBMITTED') THEN NULL ELSE ?6 END)",
+        )
+        .bind(cycle)
+        .bind(kind)
+        .bind(market_id)
+        .bind(payload)
+        .bind(status)
+        .bind(sql_timestamp(self.clock.now()))
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert journal entry")?;
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Move an entry to `status`, keeping any order id already recorded.
+    pub async fn update_journal_entry(
+        &self,
+        id: i64,
+        status: &str,
+        order_id: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE cycle_journal SET status = ?1, order_id = COALESCE(?2, order_id),
+             completed_at = CASE WHEN ?1 IN ('PENDING', 'SUBMITTED') THEN NULL ELSE ?3 END
+             WHERE id = ?4",
+        )
+        .bind(status)
+        .bind(order_id)
+        .bind(sql_timestamp(self.clock.now()))
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update journal entry")?;
+        Ok(())
+    }
+
+    /// Order intents never marked complete, oldest first.
+    pub async fn get_incomplete_journal_orders(&self) -> Result<Vec<JournalEntry>> {
+        let entries = sqlx::query_as::<_, JournalEntry>(
+            "SELECT * FROM cycle_journal WHERE kind = 'ORDER' AND status IN ('PENDING', 'SUBMITTED')
+             ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch incomplete journal entries")?;
+        Ok(entries)
+    }
+
+    /// Valuations journaled by a cycle that never finished, oldest first.
+    pub async fn get_incomplete_journal_valuations(&self) -> Result<Vec<JournalEntry>> {
+        let entries = sqlx::query_as::<_, JournalEntry>(
+            "SELECT * FROM cycle_journal WHERE kind = 'VALUATION' AND status = 'PENDING'
+             ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch incomplete journal valuations")?;
+        Ok(entries)
+    }
+
+    /// Mark every pending valuation of `cycle` done.
+    pub async fn complete_journal_valuations(&self, cycle: i64) -> Result<()> {
+        sqlx::query(
+            "UPDATE cycle_journal SET status = 'DONE', completed_at = ?
+             WHERE kind = 'VALUATION' AND cycle = ? AND status = 'PENDING'",
+        )
+        .bind(sql_timestamp(self.clock.now()))
+        .bind(cycle)
+        .execute(&self.pool)
+        .await
+        .context("Failed to complete journal valuations")?;
+        Ok(())
+    }
+
+    /// Whether a trade on `market_id` in `direction` was recorded at or
+    /// after `since`.
+    pub async fn has_trade_since(
+        &self,
+        market_id: &str,
+        direction: &str,
+        since: DateTime<Utc>,
+    ) -> Result<bool> {
+        let (count,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM trades WHERE market_id = ? AND direction = ? AND created_at >= ?",
+        )
+        .bind(market_id)
+        .bind(direction)
+        .bind(sql_timestamp(since))
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to look up trades")?;
+        Ok(count > 0)
+    }
+
+    // --- Order book snapshots ---
+
+    /// Store `book`, keeping the best `depth` levels per side.
+    pub async fn insert_order_book_snapshot(
+        &self,
+        market_id: &str,
+        book: &OrderBookSnapshot,
+        depth: usize,
+    ) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        write_order_book_snapshot(&mut conn, market_id, book, depth).await
+    }
+
+    /// Snapshots of `token_id`'s book, oldest first.
+    pub async fn get_order_book_snapshots(
+        &self,
+        token_id: &str,
+    ) -> Result<Vec<OrderBookSnapshotRecord>> {
+        let snapshots = sqlx::query_as::<_, OrderBookSnapshotRecord>(
+            "SELECT * FROM order_book_snapshots WHERE token_id = ? ORDER BY captured_at, id",
+        )
+        .bind(token_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch order book snapshots")?;
+        Ok(snapshots)
+    }
+
+    /// Snapshots of every market captured since `since`, oldest first.
+    pub async fn get_order_book_snapshots_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<OrderBookSnapshotRecord>> {
+        let snapshots = sqlx::query_as::<_, OrderBookSnapshotRecord>(
+            "SELECT * FROM order_book_snapshots WHERE captured_at >= ? ORDER BY captured_at, id",
+        )
+        .bind(sql_timestamp(since))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch order book snapshots")?;
+        Ok(snapshots)
+    }
+
+    // --- Execution style statistics ---
+
+    /// Count one order routed with `style`: `requested` shares asked for,
+    /// `filled` shares bought.
+    pub async fn record_execution_style(
+        &self,
+        style: &str,
+        requested: Decimal,
+        filled: Decimal,
+    ) -> Result<()> {
+        let existing = sqlx::query_as::<_, ExecutionStyleStats>(
+            "SELECT * FROM execution_style_stats WHERE style = ?",
+        )
+        .bind(style)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch execution style stats")?;
+
+        let (requested_total, filled_total) = match &existing {
+            Some(s) => (
+                Decimal::from_str(&s.requested_shares).unwrap_or_default() + requested,
+                Decimal::from_str(&s.filled_shares).unwrap_or_default() + filled,
+            ),
+            None => (requested, filled),
+        };
+        let filled_order = i64::from(filled > Decimal::ZERO);
+
+        sqlx::query(
+            "INSERT INTO execution_style_stats (style, orders, filled_orders, requested_shares, filled_shares, updated_at)
+             VALUES (?, 1, ?, ?, ?, datetime('now'))
+             ON CONFLICT(style) DO UPDATE SET
+                orders = orders + 1,
+                filled_orders = filled_orders + excluded.filled_orders,
+                requested_shares = excluded.requested_shares,
+                filled_shares = excluded.filled_shares,
+                updated_at = excluded.updated_at",
+        )
+        .bind(style)
+        .bind(filled_order)
+        .bind(requested_total.to_string())
+        .bind(filled_total.to_string())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record execution style")?;
+        Ok(())
+    }
+
+    pub async fn get_execution_style_stats(&self) -> Result<Vec<ExecutionStyleStats>> {
+        let stats = sqlx::query_as::<_, ExecutionStyleStats>(
+            "SELECT * FROM execution_style_stats ORDER BY style",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch execution style stats")?;
+        Ok(stats)
+    }
+
+    // --- Market evaluations ---
+
+    pub async fn insert_market_evaluation(&self, record: &MarketEvaluationRecord) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        write_market_evaluation(&mut conn, record).await
+    }
+
+    /// Markets evaluated in the last `lookback_hours` without any edge found,
+    /// with how many times each was evaluated.
+    pub async fn get_no_edge_evaluation_counts(
+        &self,
+        lookback_hours: u64,
+    ) -> Result<HashMap<String, i64>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT condition_id, COUNT(*) FROM market_evaluations
+             WHERE evaluated_at >= datetime('now', ?)
+             GROUP BY condition_id
+             HAVING SUM(edge_found) = 0",
+        )
+        .bind(format!("-{lookback_hours} hours"))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count no-edge evaluations")?;
+        Ok(rows.into_iter().collect())
+    }
+
+    // --- Market listings ---
+
+    /// Record `market_ids` as seen at `now` and return those first seen
+    /// within `max_age_hours`. Markets in the first scan ever recorded are
+    /// never new.
+    pub async fn record_market_listings(
+        &self,
+        market_ids: &[String],
+        now: DateTime<Utc>,
+        max_age_hours: u64,
+    ) -> Result<HashSet<String>> {
+        let mut tx = self.pool.begin().await?;
+        let (known,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM market_listings")
+            .fetch_one(&mut *tx)
+            .await
+            .context("Failed to count market listings")?;
+        let seen_at = sql_timestamp(now);
+        for market_id in market_ids {
+            sqlx::query(
+                "INSERT OR IGNORE INTO market_listings (market_id, first_seen_at, initial_scan)
+                 VALUES (?, ?, ?)",
+            )
+            .bind(market_id)
+            .bind(&seen_at)
+            .bind(known == 0)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to record market listing")?;
+        }
+        let cutoff = sql_timestamp(now - chrono::Duration::hours(max_age_hours as i64));
+        let recent: Vec<(String,)> = sqlx::query_as(
+            "SELECT market_id FROM market_listings
+             WHERE initial_scan = 0 AND first_seen_at >= ?",
+        )
+        .bind(cutoff)
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to fetch new listings")?;
+        tx.commit()
+            .await
+            .context("Failed to commit market listings")?;
+        Ok(recent
+            .into_iter()
+            .map(|(id,)| id)
+            .filter(|id| market_ids.contains(id))
+            .collect())
+    }
+
+    // --- Trade idea journal ---
+
+    pub async fn insert_trade_idea(&self, record: &TradeIdeaRecord) -> Result<i64> {
+        let result = sqlx::query(
+            "INSERT INTO journal (cycle, market_id, market_question, category, side, decision, reason,
+                                  fair_value, market_price, net_edge, confidence, key_factors, size_usd, end_date)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(record.cycle)
+        .bind(&record.market_id)
+        .bind(&record.market_question)
+        .bind(&record.category)
+        .bind(&record.side)
+        .bind(&record.decision)
+        .bind(&record.reason)
+        .bind(&record.fair_value)
+        .bind(&record.market_price)
+        .bind(&record.net_edge)
+        .bind(&record.confidence)
+        .bind(&record.key_factors)
+        .bind(&record.size_usd)
+        .bind(&record.end_date)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record trade idea")?;
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Most recent ideas first, optionally only one decision.
+    pub async fn get_recent_trade_ideas(
+        &self,
+        decision: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<TradeIdeaRecord>> {
+        let ideas = sqlx::query_as::<_, TradeIdeaRecord>(
+            "SELECT * FROM journal WHERE (?1 IS NULL OR decision = ?1) ORDER BY id DESC LIMIT ?2",
+        )
+        .bind(decision)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch trade ideas")?;
+        Ok(ideas)
+    }
+
+    /// Category of every journaled market, keyed by market id.
+    pub async fn get_market_categories(&self) -> Result<HashMap<String, String>> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT market_id, MAX(category) FROM journal GROUP BY market_id")
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to fetch market categories")?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Markets of unresolved ideas that ended between `max_age_days` ago
+    /// and `now`, longest-ended first.
+    pub async fn get_unresolved_idea_markets(
+        &self,
+        now: DateTime<Utc>,
+        max_age_days: i64,
+        limit: i64,
+    ) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT market_id FROM journal
+             WHERE resolved_at IS NULL AND end_date <= ?1 AND end_date >= datetime(?1, ?2)
+             GROUP BY market_id ORDER BY MIN(end_date) LIMIT ?3",
+        )
+        .bind(sql_timestamp(now))
+        .bind(format!("-{max_age_days} days"))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch unresolved trade ideas")?;
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// Grade every unresolved idea on `market_id`. Returns how many were graded.
+    pub async fn resolve_trade_ideas(&self, market_id: &str, yes_won: bool) -> Result<u64> {
+        let winner = if yes_won { "YES" } else { "NO" };
+        let result = sqlx::query(
+            "UPDATE journal
+             SET resolved_at = datetime('now'),
+                 side_won = (side = ?1),
+                 correct = ((side = ?1) = (decision = 'TRADED'))
+             WHERE market_id = ?2 AND resolved_at IS NULL",
+        )
+        .bind(winner)
+        .bind(market_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to resolve trade ideas")?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn get_idea_stats(&self) -> Result<IdeaStats> {
+        let rows: Vec<(String, Option<String>, i64, i64, i64)> = sqlx::query_as(
+            "SELECT decision, reason, COUNT(*),
+                    COUNT(resolved_at), COALESCE(SUM(side_won), 0)
+             FROM journal GROUP BY decision, reason",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to compute trade idea stats")?;
+
+        let mut stats = IdeaStats::default();
+        for (decision, reason, ideas, resolved, side_won) in rows {
+            let total = if decision == "TRADED" {
+                &mut stats.traded
+            } else {
+                let by_reason = stats
+                    .by_reason
+                    .entry(reason.unwrap_or_else(|| "unknown".to_string()))
+                    .or_default();
+                by_reason.ideas += ideas;
+                by_reason.resolved += resolved;
+                by_reason.side_won += side_won;
+                &mut stats.skipped
+            };
+            total.ideas += ideas;
+            total.resolved += resolved;
+            total.side_won += side_won;
+        }
+        stats.skipped_winners = stats.skipped.side_won;
+        stats.traded_losers = stats.traded.resolved - stats.traded.side_won;
+        Ok(stats)
+    }
+
+    // --- Market blacklist ---
+
+    /// Add a market to the blacklist. Returns false if it was already listed.
+    pub async fn insert_blacklisted_market(&self, record: &BlacklistRecord) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO market_blacklist (market_id, market_question, realized_loss)
+             VALUES (?, ?, ?)",
+        )
+        .bind(&record.market_id)
+        .bind(&record.market_question)
+        .bind(&record.realized_loss)
+        .execute(&self.pool)
+        .await
+        .context("Failed to blacklist market")?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn get_blacklisted_markets(&self) -> Result<Vec<BlacklistRecord>> {
+        let records = sqlx::query_as::<_, BlacklistRecord>(
+            "SELECT * FROM market_blacklist ORDER BY blacklisted_at, market_id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch blacklisted markets")?;
+        Ok(records)
+    }
+
+    // --- Trade approvals ---
+
+    pub async fn insert_approval(&self, approval: &ApprovalRecord) -> Result<i64> {
+        let result = sqlx::query(
+            "INSERT INTO pending_approvals (cycle, market_id, market_question, category, token_id, direction, price, size, size_usd, edge, fair_value, confidence, kelly_raw, kelly_adjusted, bucket, status, expires_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(approval.cycle)
+        .bind(&approval.market_id)
+        .bind(&approval.market_question)
+        .bind(&approval.category)
+        .bind(&approval.token_id)
+        .bind(&approval.direction)
+        .bind(&approval.price)
+        .bind(&approval.size)
+        .bind(&approval.size_usd)
+        .bind(&approval.edge)
+        .bind(&approval.fair_value)
+        .bind(&approval.confidence)
+        .bind(&approval.kelly_raw)
+        .bind(&approval.kelly_adjusted)
+        .bind(&approval.bucket)
+        .bind(&approval.status)
+        .bind(&approval.expires_at)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert approval")?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    pub async fn get_approval(&self, id: i64) -> Result<Option<ApprovalRecord>> {
+        let approval =
+            sqlx::query_as::<_, ApprovalRecord>("SELECT * FROM pending_approvals WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to fetch approval")?;
+        Ok(approval)
+    }
+
+    pub async fn get_approvals_by_status(&self, status: &str) -> Result<Vec<ApprovalRecord>> {
+        let approvals = sqlx::query_as::<_, ApprovalRecord>(
+            "SELECT * FROM pending_approvals WHERE status = ? ORDER BY id",
+        )
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch approvals by status")?;
+        Ok(approvals)
+    }
+
+    /// Whether a market already has a trade awaiting approval or execution.
+    pub async fn has_open_approval(&self, market_id: &str) -> Result<bool> {
+        let row: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM pending_approvals
+             WHERE market_id = ? AND status IN ('PENDING_APPROVAL', 'APPROVED')",
+        )
+        .bind(market_id)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to check open approvals")?;
+        Ok(row.0 > 0)
+    }
+
+    /// Move an approval from `from` to `to`, unless it has expired.
+    /// Returns false if the approval was not in `from` or is past its TTL.
+    pub async fn transition_approval(
+        &self,
+        id: i64,
+        from: &str,
+        to: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let now = now.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let result = sqlx::query(
+            "UPDATE pending_approvals SET status = ?, decided_at = ?
+             WHERE id = ? AND status = ? AND expires_at > ?",
+        )
+        .bind(to)
+        .bind(&now)
+        .bind(id)
+        .bind(from)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update approval status")?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Record the outcome of executing an approved trade.
+    pub async fn update_approval_status(&self, id: i64, status: &str) -> Result<()> {
+        sqlx::query("UPDATE pending_approvals SET status = ? WHERE id = ?")
+            .bind(status)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to update approval status")?;
+        Ok(())
+    }
+
+    /// Expire pending and approved-but-unexecuted trades past their TTL.
+    pub async fn expire_approvals(&self, now: DateTime<Utc>) -> Result<u64> {
+        let now = now.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let result = sqlx::query(
+            "UPDATE pending_approvals SET status = 'EXPIRED', decided_at = ?
+             WHERE status IN ('PENDING_APPROVAL', 'APPROVED') AND expires_at <= ?",
+        )
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .context("Failed to expire approvals")?;
+        Ok(result.rows_affected())
+    }
+
+    /// Names of all tables in the database.
+    pub async fn table_names(&self) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> =
+            sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table'")
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to list tables")?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+}
+
+/// Insert one order book snapshot on `conn`, which may be inside a
+/// transaction (see [`crate::db::batch`]).
+pub(crate) async fn write_order_book_snapshot(
+    conn: &mut SqliteConnection,
+    market_id: &str,
+    book: &OrderBookSnapshot,
+    depth: usize,
+) -> Result<()> {
+    let best_bid = book.bids.first().map(|l| l.price).unwrap_or_default();
+    let best_ask = book.asks.first().map(|l| l.price).unwrap_or(Decimal::ONE);
+    sqlx::query(
+        "INSERT INTO order_book_snapshots
+         (market_id, token_id, best_bid, best_ask, midpoint, spread, bids, asks)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(market_id)
+    .bind(&book.token_id)
+    .bind(best_bid.to_string())
+    .bind(best_ask.to_string())
+    .bind(book.midpoint.to_string())
+    .bind(book.spread.to_string())
+    .bind(serde_json::to_string(
+        &book.bids.iter().take(depth).collect::<Vec<_>>(),
+    )?)
+    .bind(serde_json::to_string(
+        &book.asks.iter().take(depth).collect::<Vec<_>>(),
+    )?)
+    .execute(conn)
+    .await
+    .context("Failed to insert order book snapshot")?;
+    Ok(())
+}
+
+/// Insert one market evaluation on `conn`.
+pub(crate) async fn write_market_evaluation(
+    conn: &mut SqliteConnection,
+    record: &MarketEvaluationRecord,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO market_evaluations (condition_id, cycle, fair_value, market_price, net_edge, edge_found)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&record.condition_id)
+    .bind(record.cycle)
+    .bind(&record.fair_value)
+    .bind(&record.market_price)
+    .bind(&record.net_edge)
+    .bind(record.edge_found)
+    .execute(conn)
+    .await
+    .context("Failed to record market evaluation")?;
+    Ok(())
+}
+
+/// Move the rows of `table` matching `filter` (bound to `before`) into
+/// per-month archive tables. Returns how many rows were moved.
+async fn archive_rows(
+    conn: &mut SqliteConnection,
+    table: &str,
+    filter: &str,
+    before: &str,
+) -> Result<u64> {
+    let months: Vec<(String,)> = sqlx::query_as(&format!(
+        "SELECT DISTINCT strftime('%Y_%m', created_at) FROM {table} WHERE {filter}"
+    ))
+    .bind(before)
+    .fetch_all(&mut *conn)
+    .await
+    .with_context(|| format!("Failed to find {table} rows to archive"))?;
+
+    let mut moved = 0;
+    for (month,) in months {
+        let archive = format!("{table}_archive_{month}");
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {archive} AS SELECT * FROM {table} WHERE 0"
+        ))
+        .execute(&mut *conn)
+        .await
+        .with_context(|| format!("Failed to create {archive}"))?;
+        // Copy by the archive's columns: one created before a later
+        // migration added a column keeps its original shape
+        let columns: Vec<(String,)> =
+            sqlx::query_as(&format!("SELECT name FROM pragma_table_info('{archive}')"))
+                .fetch_all(&mut *conn)
+                .await
+                .with_context(|| format!("Failed to read {archive} columns"))?;
+        let columns = columns
+            .into_iter()
+            .map(|(name,)| name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        sqlx::query(&format!(
+            "INSERT INTO {archive} ({columns}) SELECT {columns} FROM {table}
+             WHERE {filter} AND strftime('%Y_%m', created_at) = ?"
+        ))
+        .bind(before)
+        .bind(&month)
+        .execute(&mut *conn)
+        .await
+        .with_context(|| format!("Failed to copy {table} rows to {archive}"))?;
+        moved += sqlx::query(&format!(
+            "DELETE FROM {table} WHERE {filter} AND strftime('%Y_%m', created_at) = ?"
+        ))
+        .bind(before)
+        .bind(&month)
+        .execute(&mut *conn)
+        .await
+        .with_context(|| format!("Failed to remove archived {table} rows"))?
+        .rows_affected();
+    }
+    Ok(moved)
+}
+
+/// Read a decimal `agent_meta` value.
+async fn meta_decimal(conn: &mut SqliteConnection, key: &str) -> Result<Option<Decimal>> {
+    let value: Option<(String,)> = sqlx::query_as("SELECT value FROM agent_meta WHERE key = ?")
+        .bind(key)
+        .fetch_optional(&mut *conn)
+        .await
+        .context("Failed to get agent metadata")?;
+    value
+        .map(|(value,)| {
+            Decimal::from_str(&value).with_context(|| format!("Corrupt agent_meta.{key} {value:?}"))
+        })
+        .transpose()
+}
+
+/// Add `amount` to the decimal total stored under `key` in `agent_meta`.
+async fn add_to_meta(conn: &mut SqliteConnection, key: &str, amount: Decimal) -> Result<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let current = meta_decimal(conn, key).await?.unwrap_or(Decimal::ZERO);
+    sqlx::query(
+        "INSERT INTO agent_meta (key, value, updated_at) VALUES (?, ?, datetime('now'))
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+    )
+    .bind(key)
+    .bind((current + amount).to_string())
+    .execute(&mut *conn)
+    .await
+    .context("Failed to set agent metadata")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_store_create_and_migrate() {
+        let store = Store::new(":memory:").await.expect("should create store");
+        // Verify tables exist by inserting a cycle
+        let cycle = CycleRecord {
+            id: None,
+            cycle_number: 1,
+            markets_scanned: Some(50),
+            opportunities_found: Some(3),
+            trades_placed: Some(1),
+            api_cost: Some(dec!(0.05)),
+            bankroll: Some(dec!(100.00)),
+            unrealized_pnl: Some(dec!(0.00)),
+            agent_state: "ALIVE".to_string(),
+            duration_ms: Some(1500),
+            truncated_phases: None,
+            timings: PhaseTimings::default(),
+            created_at: None,
+        };
+        let id = store
+            .insert_cycle(&cycle)
+            .await
+            .expect("should insert cycle");
+        assert!(id > 0);
+    }
+
+    #[tokio::test]
+    async fn test_avg_phase_timings() {
+        let store = Store::new(":memory:").await.unwrap();
+        for (n, scan_ms, valuation_ms) in [(1, 100, Some(900)), (2, 300, None)] {
+            let cycle = CycleRecord {
+                id: None,
+                cycle_number: n,
+                markets_scanned: None,
+                opportunities_found: None,
+                trades_placed: None,
+                api_cost: None,
+                bankroll: None,
+                unrealized_pnl: None,
+                agent_state: "ALIVE".to_string(),
+                duration_ms: None,
+                truncated_phases: None,
+                timings: PhaseTimings {
+                    scan_ms: Some(scan_ms),
+                    valuation_ms,
+                    ..PhaseTimings::default()
+                },
+                created_at: None,
+            };
+            store.insert_cycle(&cycle).await.unwrap();
+        }
+
+        let avg = store.get_avg_phase_timings().await.unwrap();
+        assert_eq!(avg.scan_ms, Some(200));
+        // Averaged over the cycles that ran the phase
+        assert_eq!(avg.valuation_ms, Some(900));
+        assert_eq!(avg.data_ms, None);
+        assert_eq!(avg.slowest(), Some(("valuation", 900)));
+        let latest = store.get_latest_cycle().await.unwrap().unwrap();
+        assert_eq!(latest.timings.scan_ms, Some(300));
+    }
+
+    fn temp_db() -> String {
+        std::env::temp_dir()
+            .join(format!("agent-store-{}.db", uuid::Uuid::new_v4()))
+            .display()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_schema_version_after_migrate() {
+        let store = Store::new(":memory:").await.unwrap();
+        assert_eq!(
+            store.schema_version().await.unwrap(),
+            Some(latest_schema_version())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adopts_pre_versioning_database() {
+        let path = temp_db();
+        // A database as an older build left it: only the first migrations
+        // applied, no migrations table
+        let pool = SqlitePool::connect(&format!("sqlite:{path}?mode=rwc"))
+            .await
+            .unwrap();
+        for statement in LEGACY_MIGRATIONS[0].split(';') {
+            if !statement.trim().is_empty() {
+                sqlx::query(statement).execute(&pool).await.unwrap();
+            }
+        }
+        pool.close().await;
+
+        let store = Store::new(&path).await.unwrap();
+        assert_eq!(
+            store.schema_version().await.unwrap(),
+            Some(latest_schema_version())
+        );
+        // Columns from later legacy migrations were added
+        store.get_all_trades().await.unwrap();
+        assert!(store
+            .table_names()
+            .await
+            .unwrap()
+            .contains(&"market_evaluations".to_string()));
+        store.pool().close().await;
+
+        // Reopening applies nothing twice
+        Store::new(&path).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_refuses_newer_schema() {
+        let path = temp_db();
+        let store = Store::new(&path).await.unwrap();
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+             VALUES (9999, 'from the future', TRUE, x'00', 0)",
+        )
+        .execute(store.pool())
+        .await
+        .unwrap();
+        store.pool().close().await;
+
+        let err = Store::new(&path).await.err().unwrap();
+        assert!(err.to_string().contains("newer than this build"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_trade_insert_and_query() {
+        let store = Store::new(":memory:").await.expect("should create store");
+        let trade = TradeRecord {
+            id: None,
             cycle: 1,
             market_id: "0xabc".to_string(),
             market_question: Some("Will it rain?".to_string()),
             direction: "YES".to_string(),
-            entry_price: "0.65".to_string(),
-            size: "10.00".to_string(),
-            edge_at_entry: "0.12".to_string(),
-            claude_fair_value: "0.77".to_string(),
-            confidence: "0.85".to_string(),
-            kelly_raw: "0.04".to_string(),
-            kelly_adjusted: "0.02".to_string(),
+            entry_price: dec!(0.65),
+            size: dec!(10.00),
+            edge_at_entry: dec!(0.12),
+            claude_fair_value: dec!(0.77),
+            confidence: dec!(0.85),
+            kelly_raw: dec!(0.04),
+            kelly_adjusted: dec!(0.02),
             status: "OPEN".to_string(),
             pnl: None,
             created_at: None,
             resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
         };
         let id = store
             .insert_trade(&trade)
@@ -396,5 +2237,466 @@ mod tests {
             .expect("should get open trades");
         assert_eq!(open.len(), 1);
         assert_eq!(open[0].market_id, "0xabc");
+
+        store
+            .insert_trade(&TradeRecord {
+                status: "RESOLVED_WIN".to_string(),
+                ..trade
+            })
+            .await
+            .unwrap();
+        let won = store
+            .get_trades_filtered(Some("RESOLVED_WIN"), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(won.len(), 1);
+        let newest = store.get_trades_filtered(None, None, 1).await.unwrap();
+        assert_eq!(newest[0].status, "RESOLVED_WIN");
+
+        let tags = vec!["manual override".to_string(), "weather".to_string()];
+        assert!(store
+            .annotate_trade(id, &tags, Some("Forecast looked stale"))
+            .await
+            .unwrap());
+        assert!(!store.annotate_trade(999, &tags, None).await.unwrap());
+        let tagged = store
+            .get_trades_filtered(None, Some("weather"), 10)
+            .await
+            .unwrap();
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].tags, tags);
+        assert_eq!(tagged[0].notes.as_deref(), Some("Forecast looked stale"));
+        assert_eq!(
+            store.get_trade_tags().await.unwrap(),
+            vec![
+                ("manual override".to_string(), 1),
+                ("weather".to_string(), 1)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_columns_migrate_existing_rows() {
+        let path = temp_db();
+        let pool = SqlitePool::connect(&format!("sqlite:{path}?mode=rwc"))
+            .await
+            .unwrap();
+        for migration in LEGACY_MIGRATIONS {
+            for statement in migration.split(';') {
+                if !statement.trim().is_empty() {
+                    sqlx::query(statement).execute(&pool).await.unwrap();
+                }
+            }
+        }
+        // As older builds wrote them: RFC 3339 resolution times, loose text
+        sqlx::query(
+            "INSERT INTO trades (cycle, market_id, direction, entry_price, size, edge_at_entry, claude_fair_value, confidence, kelly_raw, kelly_adjusted, status, pnl, created_at, resolved_at)
+             VALUES (1, '0xabc', 'YES', ' 0.65', '10', '0.12', '0.77', '0.85', '0.04', '0.02', 'RESOLVED_WIN', '3.5', '2026-10-01 12:00:00', '2026-10-02T14:30:00.123456789+02:00')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+
+        let store = Store::new(&path).await.unwrap();
+        let trade = &store.get_all_trades().await.unwrap()[0];
+        assert_eq!(trade.entry_price, dec!(0.65));
+        assert_eq!(trade.pnl, Some(dec!(3.5)));
+        assert_eq!(
+            trade.resolved_at.map(sql_timestamp).as_deref(),
+            Some("2026-10-02 12:30:00")
+        );
+        assert_eq!(
+            trade.created_at.map(sql_timestamp).as_deref(),
+            Some("2026-10-01 12:00:00")
+        );
+        store.pool().close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_decimals_are_rejected() {
+        let store = Store::new(":memory:").await.unwrap();
+        let insert = |cost: &'static str| {
+            sqlx::query("INSERT INTO api_costs (provider, cost) VALUES ('anthropic', ?)")
+                .bind(cost)
+                .execute(store.pool())
+        };
+        assert!(insert("n/a").await.is_err());
+
+        insert("0.1").await.unwrap();
+        insert("0.2").await.unwrap();
+        // Passes the column check but is not a number: an error, not $0
+        insert("1.2.3").await.unwrap();
+        assert!(store.get_total_api_cost().await.is_err());
+        assert!(store.get_all_api_costs().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_total_api_cost_is_kept_running() {
+        let store = Store::new(":memory:").await.unwrap();
+        let cost = |cost: Decimal| ApiCostRecord {
+            id: None,
+            provider: "anthropic".to_string(),
+            endpoint: None,
+            input_tokens: None,
+            output_tokens: None,
+            cost,
+            cycle: Some(1),
+            created_at: None,
+        };
+        store.insert_api_cost(&cost(dec!(0.1))).await.unwrap();
+        store.insert_api_cost(&cost(dec!(0.2))).await.unwrap();
+        assert_eq!(store.get_meta(API_COST_TOTAL_KEY).await.unwrap(), None);
+
+        // The first read seeds the total from the rows, later inserts add to it
+        assert_eq!(store.get_total_api_cost().await.unwrap(), dec!(0.3));
+        store.insert_api_cost(&cost(dec!(0.000125))).await.unwrap();
+        assert_eq!(
+            store.get_meta(API_COST_TOTAL_KEY).await.unwrap().as_deref(),
+            Some("0.300125")
+        );
+        assert_eq!(store.get_total_api_cost().await.unwrap(), dec!(0.300125));
+    }
+
+    #[tokio::test]
+    async fn test_agent_meta_roundtrip() {
+        let store = Store::new(":memory:").await.unwrap();
+        assert_eq!(store.get_meta("version").await.unwrap(), None);
+
+        store.set_meta("version", "a").await.unwrap();
+        store.set_meta("version", "b").await.unwrap();
+        assert_eq!(
+            store.get_meta("version").await.unwrap().as_deref(),
+            Some("b")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_before_keeps_lifetime_totals() {
+        let store = Store::new(":memory:").await.unwrap();
+        for (number, created_at) in [
+            (1, "2026-01-15 10:00:00"),
+            (2, "2026-02-20 10:00:00"),
+            (3, "2026-02-21 10:00:00"),
+        ] {
+            let cycle = CycleRecord {
+                id: None,
+                cycle_number: number,
+                markets_scanned: None,
+                opportunities_found: None,
+                trades_placed: None,
+                api_cost: None,
+                bankroll: None,
+                unrealized_pnl: None,
+                agent_state: "ALIVE".to_string(),
+                duration_ms: None,
+                truncated_phases: None,
+                timings: PhaseTimings::default(),
+                created_at: None,
+            };
+            store.insert_cycle(&cycle).await.unwrap();
+            let cost = ApiCostRecord {
+                id: None,
+                provider: "anthropic".to_string(),
+                endpoint: None,
+                input_tokens: None,
+                output_tokens: None,
+                cost: dec!(0.1),
+                cycle: Some(number),
+                created_at: None,
+            };
+            store.insert_api_cost(&cost).await.unwrap();
+            for table in ["cycles", "api_costs"] {
+                sqlx::query(&format!(
+                    "UPDATE {table} SET created_at = ? WHERE id = (SELECT MAX(id) FROM {table})"
+                ))
+                .bind(created_at)
+                .execute(store.pool())
+                .await
+                .unwrap();
+            }
+        }
+
+        let before = NaiveDateTime::parse_from_str("2026-03-01 00:00:00", TIMESTAMP_FORMAT)
+            .unwrap()
+            .and_utc();
+        let counts = store.archive_before(before).await.unwrap();
+        // The latest cycle stays so numbering resumes from it
+        assert_eq!(
+            counts,
+            ArchiveCounts {
+                cycles: 2,
+                api_costs: 3
+            }
+        );
+        assert_eq!(store.get_all_cycles().await.unwrap().len(), 1);
+        assert_eq!(
+            store
+                .get_latest_cycle()
+                .await
+                .unwrap()
+                .unwrap()
+                .cycle_number,
+            3
+        );
+        assert!(store.get_all_api_costs().await.unwrap().is_empty());
+
+        let tables = store.table_names().await.unwrap();
+        for table in [
+            "cycles_archive_2026_01",
+            "cycles_archive_2026_02",
+            "api_costs_archive_2026_01",
+            "api_costs_archive_2026_02",
+        ] {
+            assert!(tables.iter().any(|t| t == table), "missing {table}");
+        }
+        let archived: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM api_costs_archive_2026_02")
+            .fetch_one(store.pool())
+            .await
+            .unwrap();
+        assert_eq!(archived.0, 2);
+
+        assert_eq!(store.get_total_api_cost().await.unwrap(), dec!(0.3));
+        assert_eq!(store.get_cycle_count().await.unwrap(), 3);
+
+        // Nothing left to move; a second pass changes nothing
+        assert_eq!(
+            store.archive_before(before).await.unwrap(),
+            ArchiveCounts::default()
+        );
+        assert_eq!(store.get_total_api_cost().await.unwrap(), dec!(0.3));
+
+        store.vacuum().await.unwrap();
+        assert!(store.database_size_bytes().await.unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_open_orders() {
+        let store = Store::new(":memory:").await.unwrap();
+        for (order_id, status) in [("o1", "OPEN"), ("o2", "FILLED"), ("o3", "OPEN")] {
+            store
+                .insert_order(&OrderRecord {
+                    order_id: order_id.to_string(),
+                    market_id: "0xabc".to_string(),
+                    token_id: "tok".to_string(),
+                    side: "YES".to_string(),
+                    price: "0.40".to_string(),
+                    size: "10".to_string(),
+                    status: status.to_string(),
+                    created_at: None,
+                    updated_at: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(store.get_orders_by_status("OPEN").await.unwrap().len(), 2);
+        assert_eq!(store.cancel_open_orders().await.unwrap(), 2);
+        assert!(store.get_orders_by_status("OPEN").await.unwrap().is_empty());
+        assert_eq!(store.get_recent_orders(10).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_execution_style_stats() {
+        let store = Store::new(":memory:").await.unwrap();
+        store
+            .record_execution_style("passive", Decimal::from(10), Decimal::ZERO)
+            .await
+            .unwrap();
+        store
+            .record_execution_style("passive", Decimal::from(10), Decimal::from(4))
+            .await
+            .unwrap();
+
+        let stats = store.get_execution_style_stats().await.unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].orders, 2);
+        assert_eq!(stats[0].filled_orders, 1);
+        assert_eq!(stats[0].requested_shares, "20");
+        assert_eq!(stats[0].filled_shares, "4");
+        assert_eq!(stats[0].fill_rate(), Decimal::new(5, 1));
+    }
+
+    #[tokio::test]
+    async fn test_no_edge_evaluation_counts() {
+        let store = Store::new(":memory:").await.unwrap();
+        let record = |condition_id: &str, edge_found: bool| MarketEvaluationRecord {
+            id: None,
+            condition_id: condition_id.to_string(),
+            cycle: 1,
+            fair_value: "0.5".to_string(),
+            market_price: "0.5".to_string(),
+            net_edge: edge_found.then(|| "0.1".to_string()),
+            edge_found,
+            evaluated_at: None,
+        };
+        for r in [
+            record("stale", false),
+            record("stale", false),
+            record("mixed", false),
+            record("mixed", true),
+        ] {
+            store.insert_market_evaluation(&r).await.unwrap();
+        }
+
+        let counts = store.get_no_edge_evaluation_counts(24).await.unwrap();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts["stale"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_market_listings() {
+        let store = Store::new(":memory:").await.unwrap();
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let start = Utc::now();
+
+        // The first scan only establishes what was already listed
+        let new = store
+            .record_market_listings(&ids(&["old"]), start, 24)
+            .await
+            .unwrap();
+        assert!(new.is_empty());
+
+        let later = start + chrono::Duration::hours(2);
+        let new = store
+            .record_market_listings(&ids(&["old", "fresh"]), later, 24)
+            .await
+            .unwrap();
+        assert_eq!(new, HashSet::from(["fresh".to_string()]));
+
+        // First-seen time sticks; past the window the market is established
+        let much_later = later + chrono::Duration::hours(25);
+        let new = store
+            .record_market_listings(&ids(&["old", "fresh", "newest"]), much_later, 24)
+            .await
+            .unwrap();
+        assert_eq!(new, HashSet::from(["newest".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_trade_idea_post_mortem() {
+        let store = Store::new(":memory:").await.unwrap();
+        let ended = sql_timestamp(Utc::now() - chrono::TimeDelta::hours(1));
+        let idea = |market_id: &str, side: &str, reason: Option<&str>| TradeIdeaRecord {
+            id: None,
+            cycle: 1,
+            market_id: market_id.to_string(),
+            market_question: "Will it rain?".to_string(),
+            category: "weather".to_string(),
+            side: side.to_string(),
+            decision: if reason.is_some() {
+                "SKIPPED"
+            } else {
+                "TRADED"
+            }
+            .to_string(),
+            reason: reason.map(str::to_string),
+            fair_value: "0.6".to_string(),
+            market_price: "0.5".to_string(),
+            net_edge: Some("0.08".to_string()),
+            confidence: "0.7".to_string(),
+            key_factors: "[]".to_string(),
+            size_usd: reason.is_none().then(|| "5".to_string()),
+            end_date: ended.clone(),
+            created_at: None,
+            resolved_at: None,
+            side_won: None,
+            correct: None,
+        };
+        for record in [
+            idea("m1", "YES", None),
+            idea("m1", "YES", Some("insufficient_liquidity")),
+            idea("m2", "NO", None),
+            idea("m2", "YES", Some("no_edge")),
+            idea("m3", "YES", Some("no_edge")),
+        ] {
+            store.insert_trade_idea(&record).await.unwrap();
+        }
+
+        let pending = store
+            .get_unresolved_idea_markets(Utc::now(), 30, 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 3);
+        // YES won m1 and m2
+        assert_eq!(store.resolve_trade_ideas("m1", true).await.unwrap(), 2);
+        assert_eq!(store.resolve_trade_ideas("m2", true).await.unwrap(), 2);
+        assert_eq!(store.resolve_trade_ideas("m2", true).await.unwrap(), 0);
+
+        let stats = store.get_idea_stats().await.unwrap();
+        assert_eq!(stats.traded.ideas, 2);
+        assert_eq!(stats.skipped.ideas, 3);
+        assert_eq!(stats.skipped.resolved, 2);
+        // Skipped YES on both m1 and m2, which won
+        assert_eq!(stats.skipped_winners, 2);
+        // Traded NO on m2
+        assert_eq!(stats.traded_losers, 1);
+        assert_eq!(
+            stats.by_reason["no_edge"],
+            IdeaOutcomes {
+                ideas: 2,
+                resolved: 1,
+                side_won: 1
+            }
+        );
+
+        let skipped = store
+            .get_recent_trade_ideas(Some("SKIPPED"), 10)
+            .await
+            .unwrap();
+        assert_eq!(skipped.len(), 3);
+        assert_eq!(skipped[1].correct, Some(false));
+        assert_eq!(skipped[0].side_won, None);
+        assert_eq!(
+            store
+                .get_unresolved_idea_markets(Utc::now(), 30, 10)
+                .await
+                .unwrap(),
+            vec!["m3".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_book_snapshot_keeps_depth() {
+        use crate::market::models::{BookLiquidity, PriceLevel};
+
+        let store = Store::new(":memory:").await.unwrap();
+        let level = |price: i64| PriceLevel {
+            price: Decimal::new(price, 2),
+            size: Decimal::from(100),
+        };
+        let book = OrderBookSnapshot {
+            token_id: "t1".to_string(),
+            bids: vec![level(45), level(44), level(43)],
+            asks: vec![level(47), level(48)],
+            spread: Decimal::new(2, 2),
+            midpoint: Decimal::new(46, 2),
+            implied_probability: Decimal::new(46, 2),
+            timestamp: Utc::now(),
+            liquidity: BookLiquidity::TwoSided,
+        };
+        store
+            .insert_order_book_snapshot("m1", &book, 2)
+            .await
+            .unwrap();
+
+        let snapshots = store.get_order_book_snapshots("t1").await.unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].best_bid, "0.45");
+        assert_eq!(snapshots[0].best_ask, "0.47");
+        let bids: Vec<PriceLevel> = serde_json::from_str(&snapshots[0].bids).unwrap();
+        assert_eq!(bids.len(), 2);
+
+        let since = Utc::now() - chrono::Duration::hours(1);
+        let recent = store.get_order_book_snapshots_since(since).await.unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].market_id, "m1");
+        let later = Utc::now() + chrono::Duration::hours(1);
+        assert!(store
+            .get_order_book_snapshots_since(later)
+            .await
+            .unwrap()
+            .is_empty());
     }
 }
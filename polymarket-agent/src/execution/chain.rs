@@ -0,0 +1,199 @@
+//! Polygon JSON-RPC plumbing shared by redemptions and wallet approvals.
+//!
+//! Transactions are legacy-typed, signed locally by the trading key and
+//! submitted with `eth_sendRawTransaction`; callers wait for the receipt to
+//! learn the gas actually paid, priced in USD by
+//! [`PriceFeed`](super::price_feed::PriceFeed). CLOB orders are signed
+//! off-chain and settled by the operator, so they pay no gas of their own.
+
+use std::time::Duration;
+
+use alloy::consensus::{SignableTransaction, TxEnvelope, TxLegacy};
+use alloy::eips::Encodable2718;
+use alloy::network::TxSignerSync;
+use alloy::primitives::{Address, Bytes, TxKind, U256};
+use alloy::signers::k256::ecdsa::SigningKey;
+use alloy::signers::local::LocalSigner;
+use anyhow::{bail, Context, Result};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+use tracing::info;
+
+const WEI_PER_POL: Decimal = dec!(1_000_000_000_000_000_000);
+
+/// A mined transaction.
+#[derive(Debug, Clone)]
+pub struct TxReceipt {
+    pub tx_hash: String,
+    pub gas_used: u64,
+    pub gas_price_wei: u128,
+}
+
+impl TxReceipt {
+    /// Gas paid, in USD at `pol_usd_price`.
+    pub fn gas_cost_usd(&self, pol_usd_price: Decimal) -> Decimal {
+        let wei = Decimal::from(self.gas_used) * Decimal::from(self.gas_price_wei);
+        wei / WEI_PER_POL * pol_usd_price
+    }
+}
+
+/// Minimal JSON-RPC client over the shared HTTP client.
+pub(crate) struct JsonRpc<'a> {
+    pub http: &'a reqwest::Client,
+    pub url: &'a str,
+}
+
+impl JsonRpc<'_> {
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+        let mut response: Value = self
+            .http
+            .post(self.url)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("{method} request failed"))?
+            .json()
+            .await
+            .with_context(|| format!("{method} returned invalid JSON"))?;
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+        Ok(response["result"].take())
+    }
+
+    /// Read-only contract call at the latest block.
+    pub async fn eth_call(&self, to: Address, input: &Bytes) -> Result<Bytes> {
+        let result = self
+            .call(
+                "eth_call",
+                json!([{"to": to.to_string(), "data": input.to_string()}, "latest"]),
+            )
+            .await?;
+        let hex = result.as_str().context("eth_call returned no data")?;
+        hex.parse::<Bytes>()
+            .with_context(|| format!("eth_call returned invalid hex: {hex}"))
+    }
+
+    /// Native POL balance of `owner` at the latest block, in POL.
+    pub async fn pol_balance(&self, owner: Address) -> Result<Decimal> {
+        let wei = parse_quantity(
+            &self
+                .call("eth_getBalance", json!([owner.to_string(), "latest"]))
+                .await?,
+        )?;
+        Ok(Decimal::from(wei) / WEI_PER_POL)
+    }
+
+    /// Sign and submit a call to `to`, then wait up to `timeout` for it to be
+    /// mined. `what` names the transaction in errors and logs.
+    pub async fn send_transaction(
+        &self,
+        signer: &LocalSigner<SigningKey>,
+        chain_id: u64,
+        to: Address,
+        input: Bytes,
+        timeout: Duration,
+        what: &str,
+    ) -> Result<TxReceipt> {
+        let from = signer.address();
+
+        let nonce = parse_quantity(
+            &self
+                .call(
+                    "eth_getTransactionCount",
+                    json!([from.to_string(), "pending"]),
+                )
+                .await?,
+        )? as u64;
+        let gas_price = parse_quantity(&self.call("eth_gasPrice", json!([])).await?)?;
+        let estimate = parse_quantity(
+            &self
+                .call(
+                    "eth_estimateGas",
+                    json!([{
+                        "from": from.to_string(),
+                        "to": to.to_string(),
+                        "data": input.to_string(),
+                    }]),
+                )
+                .await
+                .with_context(|| format!("{what} would revert"))?,
+        )? as u64;
+
+        let mut tx = TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price,
+            // Headroom over the estimate
+            gas_limit: estimate + estimate / 5,
+            to: TxKind::Call(to),
+            value: U256::ZERO,
+            input,
+        };
+        let signature = signer
+            .sign_transaction_sync(&mut tx)
+            .with_context(|| format!("Failed to sign {what}"))?;
+        let raw = TxEnvelope::from(tx.into_signed(signature)).encoded_2718();
+
+        let tx_hash = self
+            .call(
+                "eth_sendRawTransaction",
+                json!([Bytes::from(raw).to_string()]),
+            )
+            .await?
+            .as_str()
+            .map(str::to_string)
+            .context("eth_sendRawTransaction returned no hash")?;
+        info!(tx_hash = %tx_hash, "{what} submitted");
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let receipt = self
+                .call("eth_getTransactionReceipt", json!([tx_hash]))
+                .await?;
+            if !receipt.is_null() {
+                if receipt["status"].as_str() != Some("0x1") {
+                    bail!("{what} {tx_hash} reverted");
+                }
+                let gas_used = parse_quantity(&receipt["gasUsed"])? as u64;
+                let gas_price_wei =
+                    parse_quantity(&receipt["effectiveGasPrice"]).unwrap_or(gas_price);
+                return Ok(TxReceipt {
+                    tx_hash,
+                    gas_used,
+                    gas_price_wei,
+                });
+            }
+            if tokio::time::Instant::now() >= deadline {
+                bail!("{what} {tx_hash} not mined within {}s", timeout.as_secs());
+            }
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+    }
+}
+
+/// Parse a hex-encoded JSON-RPC quantity (`"0x1a"`).
+pub(crate) fn parse_quantity(value: &Value) -> Result<u128> {
+    let s = value.as_str().context("Expected a hex quantity")?;
+    u128::from_str_radix(s.trim_start_matches("0x"), 16)
+        .with_context(|| format!("Invalid hex quantity: {s}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_cost_usd() {
+        let receipt = TxReceipt {
+            tx_hash: "0x1".to_string(),
+            gas_used: 100_000,
+            gas_price_wei: 50_000_000_000, // 50 gwei
+        };
+        // 0.005 POL at $0.40
+        assert_eq!(receipt.gas_cost_usd(dec!(0.40)), dec!(0.002));
+        assert_eq!(parse_quantity(&json!("0x1a")).unwrap(), 26);
+    }
+}
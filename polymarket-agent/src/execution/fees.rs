@@ -0,0 +1,92 @@
+//! Trading fee and rebate model.
+//!
+//! Polymarket charges fees per share scaled by `min(price, 1 - price)`, so
+//! they peak at 50¢ and vanish near certainty. Takers pay the base rate;
+//! makers may earn a rebate, modelled as a negative fee.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FeeConfig {
+    /// Taker fee in basis points of `min(price, 1 - price)` per share.
+    #[serde(default)]
+    pub taker_fee_bps: Decimal,
+    /// Maker rebate in basis points, on the same base.
+    #[serde(default)]
+    pub maker_rebate_bps: Decimal,
+}
+
+/// Which side of the book an order's fill took.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeeModel {
+    taker_rate: Decimal,
+    maker_rebate_rate: Decimal,
+}
+
+impl FeeModel {
+    pub fn new(config: &FeeConfig) -> Self {
+        Self {
+            taker_rate: config.taker_fee_bps / dec!(10000),
+            maker_rebate_rate: config.maker_rebate_bps / dec!(10000),
+        }
+    }
+
+    /// Fee for one share at `price`. Negative for a maker rebate.
+    pub fn per_share(&self, liquidity: Liquidity, price: Decimal) -> Decimal {
+        let base = price.min(Decimal::ONE - price).max(Decimal::ZERO);
+        match liquidity {
+            Liquidity::Taker => self.taker_rate * base,
+            Liquidity::Maker => -self.maker_rebate_rate * base,
+        }
+    }
+
+    /// Total fee for a fill of `shares` at `price`.
+    pub fn fee(&self, liquidity: Liquidity, price: Decimal, shares: Decimal) -> Decimal {
+        self.per_share(liquidity, price) * shares
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> FeeModel {
+        FeeModel::new(&FeeConfig {
+            taker_fee_bps: dec!(200),
+            maker_rebate_bps: dec!(50),
+        })
+    }
+
+    #[test]
+    fn test_taker_fee_peaks_at_midpoint() {
+        let fees = model();
+        assert_eq!(fees.per_share(Liquidity::Taker, dec!(0.50)), dec!(0.01));
+        assert_eq!(fees.per_share(Liquidity::Taker, dec!(0.90)), dec!(0.002));
+        assert_eq!(fees.fee(Liquidity::Taker, dec!(0.10), dec!(100)), dec!(0.2));
+    }
+
+    #[test]
+    fn test_maker_rebate_is_negative() {
+        assert_eq!(
+            model().fee(Liquidity::Maker, dec!(0.40), dec!(100)),
+            dec!(-0.2)
+        );
+    }
+
+    #[test]
+    fn test_default_is_free() {
+        let fees = FeeModel::new(&FeeConfig::default());
+        assert_eq!(
+            fees.fee(Liquidity::Taker, dec!(0.5), dec!(100)),
+            Decimal::ZERO
+        );
+    }
+}
@@ -1,14 +1,19 @@
 //! Fill tracking and reconciliation.
 //!
 //! Records executed trades in the database and tracks open positions
-//! for P&L monitoring.
+//! for P&L monitoring. In live mode, recorded trades are reconciled against
+//! the exchange's actual fills; in paper mode, trades from resting orders
+//! are reconciled against their simulated fills.
 
 use anyhow::Result;
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use tracing::{info, warn};
 
-use crate::db::store::{Store, TradeRecord};
+use crate::db::store::{OrderRecord, Store, TradeRecord};
 use crate::execution::order::{ExecutionResult, OrderStatus, PreparedOrder};
+use crate::market::polymarket::{OrderFill, PolymarketClient};
+use crate::risk::portfolio;
 
 /// Record a successful trade execution in the database.
 pub async fn record_trade(
@@ -18,24 +23,30 @@ pub async fn record_trade(
     cycle: u64,
 ) -> Result<Option<i64>> {
     match &execution.status {
-        OrderStatus::Filled => {
+        OrderStatus::Filled | OrderStatus::PartiallyFilled { .. } => {
             let trade = TradeRecord {
                 id: None,
                 cycle: cycle as i64,
                 market_id: order.market_id.clone(),
                 market_question: Some(order.market_question.clone()),
                 direction: order.side.to_string(),
-                entry_price: execution.price.to_string(),
-                size: execution.size.to_string(),
-                edge_at_entry: order.edge.to_string(),
-                claude_fair_value: order.fair_value.to_string(),
-                confidence: order.confidence.to_string(),
-                kelly_raw: order.kelly_raw.to_string(),
-                kelly_adjusted: order.kelly_adjusted.to_string(),
+                entry_price: execution.price,
+                size: execution.size,
+                edge_at_entry: order.edge,
+                claude_fair_value: order.fair_value,
+                confidence: order.confidence,
+                kelly_raw: order.kelly_raw,
+                kelly_adjusted: order.kelly_adjusted,
                 status: "OPEN".to_string(),
                 pnl: None,
                 created_at: None,
                 resolved_at: None,
+                bucket: order.bucket.clone(),
+                fee: execution.fee,
+                order_id: Some(execution.order_id.clone()).filter(|id| !id.is_empty()),
+                reconciled_at: None,
+                tags: order.tags.clone(),
+                notes: None,
             };
 
             let trade_id = store.insert_trade(&trade).await?;
@@ -64,43 +75,195 @@ pub async fn record_trade(
     }
 }
 
+/// Price difference (per share) above which a fill counts as a discrepancy.
+const PRICE_TOLERANCE: Decimal = dec!(0.005);
+/// Relative size difference above which a fill counts as a discrepancy.
+const SIZE_TOLERANCE_PCT: Decimal = dec!(0.01);
+
+/// Difference between what a trade recorded and what the exchange filled.
+#[derive(Debug, Clone)]
+pub struct FillDiscrepancy {
+    pub trade_id: i64,
+    pub order_id: String,
+    pub market_id: String,
+    pub intended_price: Decimal,
+    pub actual_price: Decimal,
+    pub intended_size: Decimal,
+    pub actual_size: Decimal,
+}
+
+/// Compare a recorded trade with its exchange fill. Returns `None` when
+/// they agree within tolerance.
+pub fn fill_discrepancy(trade: &TradeRecord, fill: &OrderFill) -> Option<FillDiscrepancy> {
+    let intended_price = trade.entry_price;
+    let intended_size = trade.size;
+    let price_off = (fill.avg_price - intended_price).abs() > PRICE_TOLERANCE;
+    let size_off = intended_size > Decimal::ZERO
+        && ((fill.size_matched - intended_size).abs() / intended_size) > SIZE_TOLERANCE_PCT;
+    if !price_off && !size_off {
+        return None;
+    }
+    Some(FillDiscrepancy {
+        trade_id: trade.id?,
+        order_id: fill.order_id.clone(),
+        market_id: trade.market_id.clone(),
+        intended_price,
+        actual_price: fill.avg_price,
+        intended_size,
+        actual_size: fill.size_matched,
+    })
+}
+
+/// Reconcile open live trades against the CLOB's actual fills.
+///
+/// Each trade with an order id is updated to the real average price and
+/// matched size. Orders still resting on the book are revisited next cycle;
+/// orders that ended with nothing matched are cancelled. Returns the trades
+/// whose execution differed from what was recorded.
+pub async fn reconcile_fills(
+    store: &Store,
+    client: &PolymarketClient,
+) -> Result<Vec<FillDiscrepancy>> {
+    let trades = store.get_unreconciled_trades().await?;
+    let mut discrepancies = Vec::new();
+
+    for trade in &trades {
+        let (Some(trade_id), Some(order_id)) = (trade.id, trade.order_id.as_deref()) else {
+            continue;
+        };
+        let fill = match client.get_order_fill(order_id).await {
+            Ok(f) => f,
+            Err(e) => {
+                warn!(trade_id, order_id, error = %e, "Fill lookup failed");
+                continue;
+            }
+        };
+        discrepancies.extend(apply_fill(store, trade, &fill).await?);
+    }
+
+    Ok(discrepancies)
+}
+
+/// Reconcile open paper trades against the final fills of resting paper
+/// orders, as returned by `PolymarketClient::match_paper_orders`.
+pub async fn reconcile_paper_fills(
+    store: &Store,
+    fills: &[OrderFill],
+) -> Result<Vec<FillDiscrepancy>> {
+    if fills.is_empty() {
+        return Ok(Vec::new());
+    }
+    let trades = store.get_unreconciled_trades().await?;
+    let mut discrepancies = Vec::new();
+
+    for fill in fills {
+        let trade = trades
+            .iter()
+            .find(|t| t.order_id.as_deref() == Some(fill.order_id.as_str()));
+        match trade {
+            Some(trade) => discrepancies.extend(apply_fill(store, trade, fill).await?),
+            None => warn!(order_id = %fill.order_id, "No open trade for paper order fill"),
+        }
+    }
+
+    Ok(discrepancies)
+}
+
+/// Settle one trade against its order's fill. Resting orders are left for
+/// a later pass.
+async fn apply_fill(
+    store: &Store,
+    trade: &TradeRecord,
+    fill: &OrderFill,
+) -> Result<Option<FillDiscrepancy>> {
+    let (Some(trade_id), Some(order_id)) = (trade.id, trade.order_id.as_deref()) else {
+        return Ok(None);
+    };
+    if fill.is_resting() {
+        return Ok(None);
+    }
+    let order_status = if fill.size_matched > Decimal::ZERO {
+        "FILLED"
+    } else {
+        "CANCELLED"
+    };
+    store.update_order_status(order_id, order_status).await?;
+
+    let now = store.clock().now();
+    if fill.size_matched <= Decimal::ZERO {
+        warn!(trade_id, order_id, status = %fill.status, "Order never filled — cancelling trade");
+        store
+            .update_trade_status(trade_id, "CANCELLED", Some(Decimal::ZERO), Some(now))
+            .await?;
+        return Ok(None);
+    }
+
+    let discrepancy = fill_discrepancy(trade, fill);
+    if let Some(d) = &discrepancy {
+        warn!(
+            trade_id,
+            order_id,
+            market = %d.market_id,
+            intended_price = %d.intended_price,
+            actual_price = %d.actual_price,
+            intended_size = %d.intended_size,
+            actual_size = %d.actual_size,
+            "Execution differs from recorded trade"
+        );
+    }
+    store
+        .update_trade_fill(trade_id, fill.avg_price, fill.size_matched, now)
+        .await?;
+    Ok(discrepancy)
+}
+
+/// Track a submitted order. Orders that may still be working on the book
+/// (`resting`) are recorded `OPEN` until reconciliation sees them matched or
+/// cancelled; a partial fill whose remainder was dropped is `CANCELLED`.
+/// Rejected orders never reached the book and are skipped.
+pub async fn record_order(
+    store: &Store,
+    order: &PreparedOrder,
+    execution: &ExecutionResult,
+    resting: bool,
+) -> Result<()> {
+    if !execution.status.has_fill() || execution.order_id.is_empty() {
+        return Ok(());
+    }
+    let status = match (&execution.status, resting) {
+        (_, true) => "OPEN",
+        (OrderStatus::PartiallyFilled { .. }, false) => "CANCELLED",
+        _ => "FILLED",
+    };
+    store
+        .insert_order(&OrderRecord {
+            order_id: execution.order_id.clone(),
+            market_id: order.market_id.clone(),
+            token_id: order.token_id.clone(),
+            side: order.side.to_string(),
+            price: order.price.to_string(),
+            size: order.size.to_string(),
+            status: status.to_string(),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+}
+
 /// Count currently open trades.
 pub async fn open_trade_count(store: &Store) -> Result<usize> {
     let trades = store.get_open_trades().await?;
     Ok(trades.len())
 }
 
-/// Calculate total unrealized exposure from open trades.
+/// Calculate total unrealized exposure from open trades, netting YES and
+/// NO holdings in the same market.
 pub async fn unrealized_exposure(store: &Store) -> Result<Decimal> {
     let trades = store.get_open_trades().await?;
-    let mut total = Decimal::ZERO;
-    for trade in &trades {
-        match (
-            trade.entry_price.parse::<Decimal>(),
-            trade.size.parse::<Decimal>(),
-        ) {
-            (Ok(price), Ok(size)) => {
-                total += price * size;
-            }
-            (Err(e), _) => {
-                warn!(
-                    trade_id = ?trade.id,
-                    entry_price = %trade.entry_price,
-                    error = %e,
-                    "Corrupted entry_price in trade — exposure underreported"
-                );
-            }
-            (_, Err(e)) => {
-                warn!(
-                    trade_id = ?trade.id,
-                    size = %trade.size,
-                    error = %e,
-                    "Corrupted size in trade — exposure underreported"
-                );
-            }
-        }
-    }
-    Ok(total)
+    Ok(portfolio::net_by_market(&trades)
+        .iter()
+        .map(|m| m.net_exposure())
+        .sum())
 }
 
 #[cfg(test)]
@@ -123,6 +286,8 @@ mod tests {
             confidence: dec!(0.85),
             kelly_raw: dec!(0.27),
             kelly_adjusted: dec!(0.12),
+            bucket: "directional".to_string(),
+            tags: Vec::new(),
         }
     }
 
@@ -136,6 +301,7 @@ mod tests {
             side: Side::Yes,
             price: dec!(0.62),
             size: dec!(10),
+            fee: Decimal::ZERO,
             status: OrderStatus::Filled,
         };
 
@@ -148,6 +314,131 @@ mod tests {
         assert_eq!(open[0].direction, "YES");
     }
 
+    #[tokio::test]
+    async fn test_record_partial_fill() {
+        let store = Store::new(":memory:").await.unwrap();
+        let order = test_order();
+        let execution = ExecutionResult {
+            order_id: "order-456".to_string(),
+            token_id: "tok1".to_string(),
+            side: Side::Yes,
+            price: dec!(0.63),
+            size: dec!(6),
+            fee: Decimal::ZERO,
+            status: OrderStatus::PartiallyFilled {
+                filled_size: dec!(6),
+            },
+        };
+
+        record_trade(&store, &order, &execution, 1).await.unwrap();
+        record_order(&store, &order, &execution, false)
+            .await
+            .unwrap();
+
+        // The position is what filled, not what was asked for
+        let open = store.get_open_trades().await.unwrap();
+        assert_eq!(open[0].size, dec!(6));
+        assert_eq!(open[0].entry_price, dec!(0.63));
+        let orders = store.get_orders_by_status("CANCELLED").await.unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].size, "10");
+    }
+
+    #[tokio::test]
+    async fn test_fill_discrepancy() {
+        let store = Store::new(":memory:").await.unwrap();
+        let execution = ExecutionResult {
+            order_id: "order-123".to_string(),
+            token_id: "tok1".to_string(),
+            side: Side::Yes,
+            price: dec!(0.62),
+            size: dec!(10),
+            fee: Decimal::ZERO,
+            status: OrderStatus::Filled,
+        };
+        record_trade(&store, &test_order(), &execution, 1)
+            .await
+            .unwrap();
+        let trade = store.get_unreconciled_trades().await.unwrap().remove(0);
+        assert_eq!(trade.order_id.as_deref(), Some("order-123"));
+
+        let fill = |avg_price, size_matched| OrderFill {
+            order_id: "order-123".to_string(),
+            status: "matched".to_string(),
+            size_matched,
+            avg_price,
+        };
+        assert!(fill_discrepancy(&trade, &fill(dec!(0.622), dec!(10))).is_none());
+        let d = fill_discrepancy(&trade, &fill(dec!(0.64), dec!(7))).unwrap();
+        assert_eq!(d.actual_price, dec!(0.64));
+        assert_eq!(d.actual_size, dec!(7));
+
+        store
+            .update_trade_fill(
+                d.trade_id,
+                d.actual_price,
+                d.actual_size,
+                chrono::Utc::now(),
+            )
+            .await
+            .unwrap();
+        assert!(store.get_unreconciled_trades().await.unwrap().is_empty());
+        let open = store.get_open_trades().await.unwrap();
+        assert_eq!(open[0].entry_price, dec!(0.64));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_paper_fills() {
+        let store = Store::new(":memory:").await.unwrap();
+        let order = test_order();
+        // Resting orders are recorded with nothing filled yet
+        for order_id in ["rest-1", "rest-2"] {
+            let execution = ExecutionResult {
+                order_id: order_id.to_string(),
+                token_id: "tok1".to_string(),
+                side: Side::Yes,
+                price: dec!(0.60),
+                size: Decimal::ZERO,
+                fee: Decimal::ZERO,
+                status: OrderStatus::PartiallyFilled {
+                    filled_size: Decimal::ZERO,
+                },
+            };
+            record_trade(&store, &order, &execution, 1).await.unwrap();
+            record_order(&store, &order, &execution, true)
+                .await
+                .unwrap();
+        }
+
+        let fill = |order_id: &str, status: &str, size_matched| OrderFill {
+            order_id: order_id.to_string(),
+            status: status.to_string(),
+            size_matched,
+            avg_price: dec!(0.60),
+        };
+        reconcile_paper_fills(
+            &store,
+            &[
+                fill("rest-1", "matched", dec!(10)),
+                fill("rest-2", "canceled", Decimal::ZERO),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let open = store.get_open_trades().await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].order_id.as_deref(), Some("rest-1"));
+        assert_eq!(open[0].size, dec!(10));
+        assert_eq!(open[0].entry_price, dec!(0.60));
+        assert!(store.get_unreconciled_trades().await.unwrap().is_empty());
+        assert_eq!(store.get_orders_by_status("FILLED").await.unwrap().len(), 1);
+        assert_eq!(
+            store.get_orders_by_status("CANCELLED").await.unwrap().len(),
+            1
+        );
+    }
+
     #[tokio::test]
     async fn test_record_rejected_trade() {
         let store = Store::new(":memory:").await.unwrap();
@@ -158,6 +449,7 @@ mod tests {
             side: Side::Yes,
             price: dec!(0.62),
             size: dec!(10),
+            fee: Decimal::ZERO,
             status: OrderStatus::Rejected("Insufficient balance".to_string()),
         };
 
@@ -179,17 +471,23 @@ mod tests {
             market_id: "m1".to_string(),
             market_question: Some("Test?".to_string()),
             direction: "YES".to_string(),
-            entry_price: "0.60".to_string(),
-            size: "10".to_string(),
-            edge_at_entry: "0.10".to_string(),
-            claude_fair_value: "0.70".to_string(),
-            confidence: "0.85".to_string(),
-            kelly_raw: "0.20".to_string(),
-            kelly_adjusted: "0.10".to_string(),
+            entry_price: dec!(0.60),
+            size: dec!(10),
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.70),
+            confidence: dec!(0.85),
+            kelly_raw: dec!(0.20),
+            kelly_adjusted: dec!(0.10),
             status: "OPEN".to_string(),
             pnl: None,
             created_at: None,
             resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
         };
         let trade2 = TradeRecord {
             id: None,
@@ -197,17 +495,23 @@ mod tests {
             market_id: "m2".to_string(),
             market_question: Some("Test 2?".to_string()),
             direction: "NO".to_string(),
-            entry_price: "0.40".to_string(),
-            size: "20".to_string(),
-            edge_at_entry: "0.12".to_string(),
-            claude_fair_value: "0.30".to_string(),
-            confidence: "0.80".to_string(),
-            kelly_raw: "0.15".to_string(),
-            kelly_adjusted: "0.08".to_string(),
+            entry_price: dec!(0.40),
+            size: dec!(20),
+            edge_at_entry: dec!(0.12),
+            claude_fair_value: dec!(0.30),
+            confidence: dec!(0.80),
+            kelly_raw: dec!(0.15),
+            kelly_adjusted: dec!(0.08),
             status: "OPEN".to_string(),
             pnl: None,
             created_at: None,
             resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
         };
 
         store.insert_trade(&trade1).await.unwrap();
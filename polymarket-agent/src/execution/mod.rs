@@ -1,4 +1,11 @@
+pub mod chain;
+pub mod fees;
 pub mod fills;
 pub mod order;
+pub mod paper_matching;
+pub mod price_feed;
+pub mod redemption;
 pub mod resolution;
+pub mod routing;
+pub mod twap;
 pub mod wallet;
@@ -1,18 +1,22 @@
 //! Order building and submission.
 //!
 //! Constructs orders from opportunities, applies risk checks,
-//! and submits via the Polymarket client.
+//! and submits them to the exchange.
 
 use anyhow::{bail, Result};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use tracing::{info, instrument, warn};
 
 use crate::config::ExecutionConfig;
+use crate::execution::fees::Liquidity;
+use crate::market::api::MarketApi;
+use crate::market::listings::NEW_LISTING_TAG;
 use crate::market::models::{Opportunity, Side};
-use crate::market::polymarket::PolymarketClient;
+use crate::risk::buckets::DIRECTIONAL_BUCKET;
 
 /// An order ready for submission.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreparedOrder {
     pub token_id: String,
     pub side: Side,
@@ -25,6 +29,11 @@ pub struct PreparedOrder {
     pub confidence: Decimal,
     pub kelly_raw: Decimal,
     pub kelly_adjusted: Decimal,
+    /// Strategy bucket this order is charged to.
+    pub bucket: String,
+    /// Tags recorded on the trade this order opens.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// Result of an order execution attempt.
@@ -35,19 +44,100 @@ pub struct ExecutionResult {
     pub side: Side,
     pub price: Decimal,
     pub size: Decimal,
+    /// Fee charged on the fill in USD (zero when rejected).
+    pub fee: Decimal,
     pub status: OrderStatus,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OrderStatus {
     Filled,
+    /// Only `filled_size` shares matched; the remainder was cancelled or is
+    /// still working, per `PartialFillPolicy`.
+    PartiallyFilled {
+        filled_size: Decimal,
+    },
     Rejected(String),
 }
 
+impl OrderStatus {
+    /// Whether any shares were bought.
+    pub fn has_fill(&self) -> bool {
+        !matches!(self, OrderStatus::Rejected(_))
+    }
+}
+
+/// What happens to the unfilled remainder of a partially filled order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartialFillPolicy {
+    /// Cancel the remainder and keep only what filled.
+    #[default]
+    Cancel,
+    /// Leave the remainder resting on the book (live mode, or paper mode with
+    /// `paper_matching`; other simulated fills are final).
+    Keep,
+}
+
+/// Result of walking book levels for a USD budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookFill {
+    pub shares: Decimal,
+    pub cost: Decimal,
+    /// Worst level price touched — the limit price needed for the fill.
+    pub limit_price: Decimal,
+}
+
+impl BookFill {
+    pub fn vwap(&self) -> Decimal {
+        if self.shares > Decimal::ZERO {
+            self.cost / self.shares
+        } else {
+            Decimal::ZERO
+        }
+    }
+}
+
+/// Walk `levels` (best first) spending up to `budget_usd`, stopping before
+/// the volume-weighted price would exceed `max_vwap`.
+pub fn fill_within_vwap(
+    levels: &[(Decimal, Decimal)],
+    budget_usd: Decimal,
+    max_vwap: Decimal,
+) -> BookFill {
+    let mut fill = BookFill {
+        shares: Decimal::ZERO,
+        cost: Decimal::ZERO,
+        limit_price: Decimal::ZERO,
+    };
+    for &(price, size) in levels {
+        let remaining = budget_usd - fill.cost;
+        if remaining <= Decimal::ZERO || price <= Decimal::ZERO {
+            break;
+        }
+        let mut take = size.min(remaining / price);
+        if price > max_vwap {
+            // Largest x with (cost + price·x) / (shares + x) <= max_vwap
+            let headroom = (max_vwap * fill.shares - fill.cost) / (price - max_vwap);
+            take = take.min(headroom.max(Decimal::ZERO));
+        }
+        if take <= Decimal::ZERO {
+            break;
+        }
+        fill.shares += take;
+        fill.cost += take * price;
+        fill.limit_price = price;
+    }
+    fill
+}
+
 /// Build a prepared order from an opportunity.
 ///
-/// Selects the correct token and price based on the recommended side.
-/// Applies slippage limit to the order price.
+/// Selects the correct token based on the recommended side and prices the
+/// order against book depth: the fill's volume-weighted price must stay
+/// within the slippage limit of the best price. A size the book can't absorb
+/// within tolerance is reduced to what it can, or rejected when
+/// `reduce_on_impact` is off.
 pub fn prepare_order(
     opportunity: &Opportunity,
     kelly_raw: Decimal,
@@ -58,7 +148,7 @@ pub fn prepare_order(
 
     // Find the token for the recommended side by matching outcome name (TRD-04).
     // Do NOT rely on array index — Polymarket API doesn't guarantee order.
-    let (token_id, best_price) = match side {
+    let (token_id, best_price, levels) = match side {
         Side::Yes => {
             // Buying YES: find token with outcome "Yes"
             let token = opportunity
@@ -74,7 +164,13 @@ pub fn prepare_order(
                 .first()
                 .map(|a| a.price)
                 .unwrap_or(opportunity.order_book.midpoint);
-            (token.token_id.clone(), ask_price)
+            let levels: Vec<(Decimal, Decimal)> = opportunity
+                .order_book
+                .asks
+                .iter()
+                .map(|l| (l.price, l.size))
+                .collect();
+            (token.token_id.clone(), ask_price, levels)
         }
         Side::No => {
             // Buying NO: find token with outcome "No"
@@ -93,19 +189,50 @@ pub fn prepare_order(
                 .map(|b| b.price)
                 .unwrap_or(opportunity.order_book.midpoint);
             let no_price = Decimal::ONE - bid_price;
-            (token.token_id.clone(), no_price)
+            // Each YES bid is a NO offer at the complementary price
+            let levels: Vec<(Decimal, Decimal)> = opportunity
+                .order_book
+                .bids
+                .iter()
+                .map(|l| (Decimal::ONE - l.price, l.size))
+                .collect();
+            (token.token_id.clone(), no_price, levels)
         }
     };
 
-    // Apply slippage limit: don't pay more than best_price * (1 + slippage)
-    let max_price = best_price * (Decimal::ONE + config.max_slippage_pct);
-    let order_price = best_price.min(max_price);
+    if best_price <= Decimal::ZERO {
+        bail!("Order price is zero");
+    }
+
+    // Slippage limit: the fill's VWAP may not exceed best_price * (1 + slippage)
+    let max_vwap = best_price * (Decimal::ONE + config.max_slippage_pct);
 
-    // Size in number of shares (position_usd / price)
-    let size = if order_price > Decimal::ZERO {
-        opportunity.kelly_size / order_price
+    let (order_price, size) = if levels.is_empty() {
+        // No depth to walk — size at the reference price
+        (best_price, opportunity.kelly_size / best_price)
     } else {
-        return Err(anyhow::anyhow!("Order price is zero"));
+        let fill = fill_within_vwap(&levels, opportunity.kelly_size, max_vwap);
+        let shortfall = opportunity.kelly_size - fill.cost;
+        if shortfall > Decimal::ZERO {
+            if !config.reduce_on_impact {
+                bail!(
+                    "Book can only absorb ${} of ${} within {} slippage",
+                    fill.cost.round_dp(2),
+                    opportunity.kelly_size,
+                    config.max_slippage_pct
+                );
+            }
+            if fill.shares > Decimal::ZERO {
+                info!(
+                    market = %opportunity.market.condition_id,
+                    requested = %opportunity.kelly_size,
+                    fillable = %fill.cost,
+                    vwap = %fill.vwap(),
+                    "Reducing order to depth available within slippage"
+                );
+            }
+        }
+        (fill.limit_price, fill.shares)
     };
 
     if size <= Decimal::ZERO {
@@ -124,34 +251,71 @@ pub fn prepare_order(
         confidence: opportunity.confidence,
         kelly_raw,
         kelly_adjusted,
+        bucket: DIRECTIONAL_BUCKET.to_string(),
+        tags: if opportunity.new_listing {
+            vec![NEW_LISTING_TAG.to_string()]
+        } else {
+            Vec::new()
+        },
     })
 }
 
-/// Execute a prepared order via the Polymarket client.
+/// Execute a prepared order on the exchange.
 #[instrument(skip(client, order), fields(
     market = %order.market_id,
     side = %order.side,
     price = %order.price,
     size = %order.size,
 ))]
-pub async fn execute_order(client: &PolymarketClient, order: &PreparedOrder) -> ExecutionResult {
+pub async fn execute_order(client: &dyn MarketApi, order: &PreparedOrder) -> ExecutionResult {
     match client
         .place_limit_order(&order.token_id, order.side, order.price, order.size)
         .await
     {
-        Ok(order_id) => {
+        Ok(placed) => {
+            let filled = placed.filled_size.min(order.size);
+            let status = if filled < order.size {
+                OrderStatus::PartiallyFilled {
+                    filled_size: filled,
+                }
+            } else {
+                OrderStatus::Filled
+            };
             info!(
-                order_id = %order_id,
+                order_id = %placed.order_id,
                 edge = %order.edge,
+                filled = %filled,
+                size = %order.size,
                 "Order executed successfully"
             );
+
+            if status != OrderStatus::Filled
+                && client.partial_fill_policy() == PartialFillPolicy::Cancel
+            {
+                match client.cancel_order(&placed.order_id).await {
+                    Ok(()) => info!(
+                        order_id = %placed.order_id,
+                        remainder = %(order.size - filled),
+                        "Cancelled unfilled remainder"
+                    ),
+                    Err(e) => warn!(
+                        order_id = %placed.order_id,
+                        error = %e,
+                        "Failed to cancel unfilled remainder"
+                    ),
+                }
+            }
+
             ExecutionResult {
-                order_id,
+                fee: client
+                    .fees()
+                    .fee(Liquidity::Taker, placed.fill_price, filled),
+                order_id: placed.order_id,
                 token_id: order.token_id.clone(),
                 side: order.side,
-                price: order.price,
-                size: order.size,
-                status: OrderStatus::Filled,
+                price: placed.fill_price,
+                size: filled,
+                status,
             }
         }
         Err(e) => {
@@ -165,6 +329,7 @@ pub async fn execute_order(client: &PolymarketClient, order: &PreparedOrder) ->
                 side: order.side,
                 price: order.price,
                 size: order.size,
+                fee: Decimal::ZERO,
                 status: OrderStatus::Rejected(e.to_string()),
             }
         }
@@ -174,7 +339,9 @@ pub async fn execute_order(client: &PolymarketClient, order: &PreparedOrder) ->
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::market::models::{Market, MarketCategory, OrderBookSnapshot, PriceLevel, TokenInfo};
+    use crate::market::models::{
+        BookLiquidity, Market, MarketCategory, OrderBookSnapshot, PriceLevel, TokenInfo,
+    };
     use chrono::Utc;
     use rust_decimal_macros::dec;
 
@@ -183,6 +350,12 @@ mod tests {
             order_type: "limit".to_string(),
             order_ttl_seconds: 60,
             max_slippage_pct: dec!(0.02),
+            reduce_on_impact: true,
+            partial_fill: Default::default(),
+            twap: Default::default(),
+            routing: Default::default(),
+            fees: Default::default(),
+            paper_matching: Default::default(),
             max_retries: 3,
         }
     }
@@ -209,6 +382,11 @@ mod tests {
                 category: MarketCategory::Crypto,
                 volume_24h: dec!(50000),
                 active: true,
+                event_id: None,
+                event_slug: None,
+                neg_risk: false,
+                description: String::new(),
+                resolution_source: None,
             },
             order_book: OrderBookSnapshot {
                 token_id: "tok_yes".to_string(),
@@ -224,12 +402,14 @@ mod tests {
                 midpoint: dec!(0.60),
                 implied_probability: dec!(0.60),
                 timestamp: Utc::now(),
+                liquidity: BookLiquidity::TwoSided,
             },
             fair_value: dec!(0.75),
             confidence: dec!(0.85),
             edge: dec!(0.15),
             recommended_side: side,
             kelly_size,
+            new_listing: false,
         }
     }
 
@@ -265,6 +445,48 @@ mod tests {
         assert!(order.size > dec!(11));
     }
 
+    #[test]
+    fn test_fill_within_vwap_walks_levels() {
+        let levels = vec![(dec!(0.50), dec!(10)), (dec!(0.60), dec!(100))];
+        // $11 budget: 10 @ 0.50 = $5, then 10 @ 0.60 = $6
+        let fill = fill_within_vwap(&levels, dec!(11), dec!(1));
+        assert_eq!(fill.shares, dec!(20));
+        assert_eq!(fill.limit_price, dec!(0.60));
+        assert_eq!(fill.vwap(), dec!(0.55));
+
+        // VWAP capped at 0.52: x ≤ (0.52·10 - 5) / (0.60 - 0.52) = 2.5 more shares
+        let capped = fill_within_vwap(&levels, dec!(11), dec!(0.52));
+        assert_eq!(capped.shares, dec!(12.5));
+        assert_eq!(capped.vwap(), dec!(0.52));
+    }
+
+    #[test]
+    fn test_prepare_order_reduces_for_thin_book() {
+        let config = test_config();
+        let mut opp = test_opportunity(Side::Yes, dec!(20));
+        opp.order_book.asks = vec![
+            PriceLevel {
+                price: dec!(0.62),
+                size: dec!(10),
+            },
+            PriceLevel {
+                price: dec!(0.70),
+                size: dec!(100),
+            },
+        ];
+
+        let order = prepare_order(&opp, dec!(0.27), dec!(0.12), &config).unwrap();
+        // Only a sliver of the 0.70 level fits under 0.62 · 1.02
+        assert!(order.size < dec!(12));
+        assert!(order.price * order.size < dec!(20));
+
+        let strict = ExecutionConfig {
+            reduce_on_impact: false,
+            ..test_config()
+        };
+        assert!(prepare_order(&opp, dec!(0.27), dec!(0.12), &strict).is_err());
+    }
+
     #[test]
     fn test_prepare_order_zero_kelly() {
         let config = test_config();
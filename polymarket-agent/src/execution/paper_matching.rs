@@ -0,0 +1,98 @@
+//! Resting limit orders in paper mode.
+//!
+//! The default paper simulation decides on submission whether an order
+//! fills, which makes passive execution look the same as crossing the
+//! spread. With matching enabled, a paper order priced below the best ask
+//! rests instead, and each cycle it is checked against the token's live
+//! order book: it fills only once sellers trade through its limit price.
+//! Orders that don't fill within `execution.order_ttl_seconds` expire.
+//!
+//! Orders only rest under `execution.partial_fill = "keep"`. Under
+//! `"cancel"` an unfilled live order is cancelled as soon as it's placed,
+//! so paper orders keep the immediate simulation.
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::market::models::PriceLevel;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PaperMatchingConfig {
+    /// Rest non-marketable paper orders and match them against the live
+    /// book each cycle.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Also fill when the best ask only touches the limit. Assumes the
+    /// order is at the front of the queue, which flatters passive fills.
+    #[serde(default)]
+    pub fill_on_touch: bool,
+}
+
+/// Whether a buy at `limit` would rest rather than take liquidity.
+pub fn rests(limit: Decimal, asks: &[PriceLevel]) -> bool {
+    asks.first().map_or(true, |best| limit < best.price)
+}
+
+/// Shares of a resting buy at `limit` filled by `asks`, up to `remaining`.
+///
+/// Only asks priced through the limit count (at it too with
+/// `fill_on_touch`): sellers willing to go below our bid would have hit us
+/// first. Fills are at the limit price, as a maker's are.
+pub fn fillable_shares(
+    limit: Decimal,
+    remaining: Decimal,
+    asks: &[PriceLevel],
+    fill_on_touch: bool,
+) -> Decimal {
+    let available: Decimal = asks
+        .iter()
+        .filter(|a| a.price < limit || (fill_on_touch && a.price == limit))
+        .map(|a| a.size)
+        .sum();
+    available.min(remaining).max(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn asks(levels: &[(Decimal, Decimal)]) -> Vec<PriceLevel> {
+        levels
+            .iter()
+            .map(|&(price, size)| PriceLevel { price, size })
+            .collect()
+    }
+
+    #[test]
+    fn test_rests_below_best_ask() {
+        let book = asks(&[(dec!(0.55), dec!(100))]);
+        assert!(rests(dec!(0.50), &book));
+        assert!(!rests(dec!(0.55), &book));
+        assert!(!rests(dec!(0.60), &book));
+        // Nothing to take against — the order can only rest
+        assert!(rests(dec!(0.50), &[]));
+    }
+
+    #[test]
+    fn test_fills_only_when_traded_through() {
+        let limit = dec!(0.50);
+        // Market still above the limit
+        let book = asks(&[(dec!(0.52), dec!(100))]);
+        assert_eq!(fillable_shares(limit, dec!(40), &book, false), dec!(0));
+
+        // Touching the limit fills only with fill_on_touch
+        let book = asks(&[(dec!(0.50), dec!(100))]);
+        assert_eq!(fillable_shares(limit, dec!(40), &book, false), dec!(0));
+        assert_eq!(fillable_shares(limit, dec!(40), &book, true), dec!(40));
+
+        // Traded through: capped by the size priced below the limit
+        let book = asks(&[
+            (dec!(0.48), dec!(10)),
+            (dec!(0.49), dec!(15)),
+            (dec!(0.50), dec!(100)),
+        ]);
+        assert_eq!(fillable_shares(limit, dec!(40), &book, false), dec!(25));
+        assert_eq!(fillable_shares(limit, dec!(20), &book, false), dec!(20));
+    }
+}
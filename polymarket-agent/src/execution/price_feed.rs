@@ -0,0 +1,314 @@
+//! USD prices for assets other than USDC.
+//!
+//! The agent keeps its bankroll in USDC but pays Polygon gas in POL and may
+//! hold POL for it. [`PriceFeed`] is the one place POL is priced in USD:
+//! gas booked to `api_costs`, the wallet's POL balance and the survival
+//! check all read it.
+//!
+//! Prices come from CoinGecko or from the Chainlink POL/USD aggregator on
+//! Polygon, and are reused for `ttl_seconds`. Every price carries the time
+//! the source last updated it; one older than `max_age_seconds` is treated
+//! as unavailable, so a frozen feed can't quietly misprice gas. When no
+//! fresh price is available the last good one is used while still within
+//! `max_age_seconds`, then `pol_usd_fallback`.
+
+use std::time::{Duration, Instant};
+
+use alloy::primitives::{address, Address, Bytes, U256};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, TimeDelta, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::execution::chain::JsonRpc;
+
+const COINGECKO_POL_URL: &str = "https://api.coingecko.com/api/v3/simple/price?ids=polygon-ecosystem-token&vs_currencies=usd&include_last_updated_at=true";
+
+/// Chainlink POL/USD (formerly MATIC/USD) aggregator on Polygon.
+const CHAINLINK_POL_USD: Address = address!("ab594600376ec9fd91f8e885dadf0ce036862de0");
+
+/// The aggregator reports prices with 8 decimals.
+const CHAINLINK_SCALE: Decimal = dec!(100_000_000);
+
+/// Selector of `latestRoundData()`.
+const LATEST_ROUND_DATA: [u8; 4] = [0xfe, 0xaf, 0x96, 0x8c];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSource {
+    #[default]
+    Coingecko,
+    /// Read over `redemption.rpc_url`.
+    Chainlink,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceFeedConfig {
+    #[serde(default)]
+    pub source: PriceSource,
+    /// How long a fetched price is reused before asking the source again.
+    #[serde(default = "default_ttl_seconds")]
+    pub ttl_seconds: u64,
+    /// Prices the source last updated longer ago than this are not used.
+    #[serde(default = "default_max_age_seconds")]
+    pub max_age_seconds: u64,
+    /// USD price of POL when no fresh price is available.
+    #[serde(default = "default_pol_usd_fallback")]
+    pub pol_usd_fallback: Decimal,
+}
+
+impl Default for PriceFeedConfig {
+    fn default() -> Self {
+        Self {
+            source: PriceSource::default(),
+            ttl_seconds: default_ttl_seconds(),
+            max_age_seconds: default_max_age_seconds(),
+            pol_usd_fallback: default_pol_usd_fallback(),
+        }
+    }
+}
+
+fn default_ttl_seconds() -> u64 {
+    600
+}
+
+fn default_max_age_seconds() -> u64 {
+    3600
+}
+
+fn default_pol_usd_fallback() -> Decimal {
+    dec!(0.25)
+}
+
+/// A USD price and when its source last updated it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PriceQuote {
+    pub usd: Decimal,
+    pub updated_at: DateTime<Utc>,
+    /// The configured fallback, used because no fresh price was available.
+    pub fallback: bool,
+}
+
+impl PriceQuote {
+    fn is_fresh(&self, max_age: TimeDelta, now: DateTime<Utc>) -> bool {
+        now - self.updated_at <= max_age
+    }
+}
+
+pub struct PriceFeed {
+    config: PriceFeedConfig,
+    http: reqwest::Client,
+    rpc_url: String,
+    /// Last good quote and when it was fetched.
+    cached: Mutex<Option<(PriceQuote, Instant)>>,
+}
+
+impl PriceFeed {
+    pub fn new(config: PriceFeedConfig, http: reqwest::Client, rpc_url: String) -> Self {
+        Self {
+            config,
+            http,
+            rpc_url,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Current POL/USD quote. Never fails; see the module docs for the
+    /// fallback order.
+    pub async fn pol_usd(&self) -> PriceQuote {
+        let mut cached = self.cached.lock().await;
+        if let Some((quote, fetched)) = *cached {
+            if fetched.elapsed() < Duration::from_secs(self.config.ttl_seconds) {
+                return quote;
+            }
+        }
+
+        let now = Utc::now();
+        let max_age = TimeDelta::seconds(self.config.max_age_seconds as i64);
+        let fetched = match self.fetch().await {
+            Ok(quote) if quote.is_fresh(max_age, now) => Ok(quote),
+            Ok(quote) => Err(anyhow::anyhow!(
+                "POL price last updated at {}",
+                quote.updated_at
+            )),
+            Err(e) => Err(e),
+        };
+        match fetched {
+            Ok(quote) => {
+                *cached = Some((quote, Instant::now()));
+                quote
+            }
+            Err(e) => {
+                let quote = cached
+                    .map(|(q, _)| q)
+                    .filter(|q| q.is_fresh(max_age, now))
+                    .unwrap_or(PriceQuote {
+                        usd: self.config.pol_usd_fallback,
+                        updated_at: now,
+                        fallback: true,
+                    });
+                warn!(
+                    error = %e,
+                    price = %quote.usd,
+                    fallback = quote.fallback,
+                    "POL price feed unavailable or stale — using last known price"
+                );
+                quote
+            }
+        }
+    }
+
+    /// USD value of `amount` POL.
+    pub async fn pol_to_usd(&self, amount: Decimal) -> Decimal {
+        amount * self.pol_usd().await.usd
+    }
+
+    async fn fetch(&self) -> Result<PriceQuote> {
+        match self.config.source {
+            PriceSource::Coingecko => fetch_coingecko(&self.http).await,
+            PriceSource::Chainlink => {
+                let rpc = JsonRpc {
+                    http: &self.http,
+                    url: &self.rpc_url,
+                };
+                let data = rpc
+                    .eth_call(CHAINLINK_POL_USD, &Bytes::from(LATEST_ROUND_DATA.to_vec()))
+                    .await
+                    .context("Chainlink latestRoundData failed")?;
+                parse_round_data(&data)
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SimplePrice {
+    #[serde(rename = "polygon-ecosystem-token")]
+    pol: SimplePriceQuote,
+}
+
+#[derive(Deserialize)]
+struct SimplePriceQuote {
+    usd: f64,
+    last_updated_at: i64,
+}
+
+async fn fetch_coingecko(http: &reqwest::Client) -> Result<PriceQuote> {
+    let quote: SimplePrice = http
+        .get(COINGECKO_POL_URL)
+        .send()
+        .await
+        .context("POL price request failed")?
+        .error_for_status()
+        .context("POL price request rejected")?
+        .json()
+        .await
+        .context("Failed to parse POL price")?;
+    parse_simple_price(quote)
+}
+
+fn parse_simple_price(quote: SimplePrice) -> Result<PriceQuote> {
+    let usd = match Decimal::from_f64(quote.pol.usd) {
+        Some(price) if price > Decimal::ZERO => price.round_dp(6),
+        _ => bail!("POL price feed returned {}", quote.pol.usd),
+    };
+    let updated_at = DateTime::from_timestamp(quote.pol.last_updated_at, 0)
+        .context("POL price has an invalid update time")?;
+    Ok(PriceQuote {
+        usd,
+        updated_at,
+        fallback: false,
+    })
+}
+
+/// Decode `latestRoundData()`: `(roundId, answer, startedAt, updatedAt,
+/// answeredInRound)`, one 32-byte word each.
+fn parse_round_data(data: &Bytes) -> Result<PriceQuote> {
+    if data.len() < 160 {
+        bail!("Expected latestRoundData, got {} bytes", data.len());
+    }
+    let word = |i: usize| U256::from_be_slice(&data[i * 32..(i + 1) * 32]);
+    // A set top bit is a negative int256 answer
+    let answer = u128::try_from(word(1))
+        .ok()
+        .filter(|a| *a > 0)
+        .context("Chainlink returned a non-positive POL price")?;
+    let updated_at = u64::try_from(word(3))
+        .ok()
+        .and_then(|t| DateTime::from_timestamp(t as i64, 0))
+        .context("Chainlink round has an invalid update time")?;
+    Ok(PriceQuote {
+        usd: Decimal::from(answer) / CHAINLINK_SCALE,
+        updated_at,
+        fallback: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_price() {
+        let quote: SimplePrice = serde_json::from_str(
+            r#"{"polygon-ecosystem-token":{"usd":0.2345,"last_updated_at":1735689600}}"#,
+        )
+        .unwrap();
+        let quote = parse_simple_price(quote).unwrap();
+        assert_eq!(quote.usd, dec!(0.2345));
+        assert_eq!(quote.updated_at.timestamp(), 1735689600);
+        assert!(!quote.fallback);
+    }
+
+    #[test]
+    fn test_parse_round_data() {
+        let mut data = vec![0u8; 160];
+        // answer = 23_450_000 ($0.2345), updatedAt = 1735689600
+        data[32 + 28..64].copy_from_slice(&23_450_000u32.to_be_bytes());
+        data[96 + 28..128].copy_from_slice(&1_735_689_600u32.to_be_bytes());
+        let quote = parse_round_data(&Bytes::from(data.clone())).unwrap();
+        assert_eq!(quote.usd, dec!(0.2345));
+        assert_eq!(quote.updated_at.timestamp(), 1735689600);
+
+        // Negative answer
+        data[32..64].fill(0xff);
+        assert!(parse_round_data(&Bytes::from(data)).is_err());
+        assert!(parse_round_data(&Bytes::from(vec![0u8; 64])).is_err());
+    }
+
+    #[test]
+    fn test_stale_guard() {
+        let now = Utc::now();
+        let max_age = TimeDelta::seconds(3600);
+        let quote = |age| PriceQuote {
+            usd: dec!(0.25),
+            updated_at: now - TimeDelta::seconds(age),
+            fallback: false,
+        };
+        assert!(quote(3600).is_fresh(max_age, now));
+        assert!(!quote(3601).is_fresh(max_age, now));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_source_falls_back() {
+        let config = PriceFeedConfig {
+            source: PriceSource::Chainlink,
+            pol_usd_fallback: dec!(0.30),
+            ..PriceFeedConfig::default()
+        };
+        // Nothing listens on port 9
+        let feed = PriceFeed::new(
+            config,
+            reqwest::Client::new(),
+            "http://127.0.0.1:9".to_string(),
+        );
+        let quote = feed.pol_usd().await;
+        assert!(quote.fallback);
+        assert_eq!(quote.usd, dec!(0.30));
+        assert_eq!(feed.pol_to_usd(dec!(10)).await, dec!(3.00));
+    }
+}
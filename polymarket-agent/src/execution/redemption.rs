@@ -0,0 +1,210 @@
+//! Redemption of winning positions (live mode).
+//!
+//! Once a market resolves on-chain, winning outcome tokens are worth $1 but
+//! sit in the wallet until redeemed through the Conditional Tokens contract.
+//! Settled wins are redeemed with a `redeemPositions` transaction signed by
+//! the trading key and sent over plain JSON-RPC; the gas paid, at the
+//! current POL price, is booked to `api_costs` alongside Claude spend.
+//!
+//! Only standard (non neg-risk) markets held directly by the signing wallet
+//! are supported. A redemption that fails is retried next cycle.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use alloy::primitives::{address, Address, Bytes, B256, U256};
+use alloy::signers::k256::ecdsa::SigningKey;
+use alloy::signers::local::LocalSigner;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::db::store::{ApiCostRecord, RedemptionRecord, Store};
+use crate::execution::chain::{JsonRpc, TxReceipt};
+use crate::market::polymarket::PolymarketClient;
+
+/// Conditional Tokens Framework contract on Polygon.
+const CTF_ADDRESS: Address = address!("4D97DCd97eC945f40cF65F87097ACe5EA0476045");
+
+/// USDC.e, the collateral backing Polymarket outcome tokens.
+pub(crate) const USDC_ADDRESS: Address = address!("2791Bca1f2de4661ED88A30C99A7a9449Aa84174");
+
+/// Both outcome slots of a binary market; redeeming both burns the loser
+/// and pays out the winner.
+const BINARY_INDEX_SETS: [u64; 2] = [1, 2];
+
+sol! {
+    function redeemPositions(
+        address collateralToken,
+        bytes32 parentCollectionId,
+        bytes32 conditionId,
+        uint256[] indexSets
+    ) external;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RedemptionConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Polygon JSON-RPC endpoint used to submit redemptions.
+    #[serde(default = "default_rpc_url")]
+    pub rpc_url: String,
+    /// How long to wait for a redemption to be mined.
+    #[serde(default = "default_receipt_timeout_seconds")]
+    pub receipt_timeout_seconds: u64,
+}
+
+impl Default for RedemptionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            rpc_url: default_rpc_url(),
+            receipt_timeout_seconds: default_receipt_timeout_seconds(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_rpc_url() -> String {
+    "https://polygon-rpc.com".to_string()
+}
+
+fn default_receipt_timeout_seconds() -> u64 {
+    120
+}
+
+/// ABI-encoded `redeemPositions` call for a binary market.
+pub fn redeem_calldata(condition_id: &str) -> Result<Bytes> {
+    let condition_id = B256::from_str(condition_id)
+        .with_context(|| format!("Invalid condition id: {condition_id}"))?;
+    let call = redeemPositionsCall {
+        collateralToken: USDC_ADDRESS,
+        parentCollectionId: B256::ZERO,
+        conditionId: condition_id,
+        indexSets: BINARY_INDEX_SETS.iter().map(|i| U256::from(*i)).collect(),
+    };
+    Ok(call.abi_encode().into())
+}
+
+/// Sign and submit a redemption for `condition_id`, then wait for it to be
+/// mined.
+pub async fn submit_redeem(
+    http: &reqwest::Client,
+    config: &RedemptionConfig,
+    signer: &LocalSigner<SigningKey>,
+    chain_id: u64,
+    condition_id: &str,
+) -> Result<TxReceipt> {
+    let rpc = JsonRpc {
+        http,
+        url: &config.rpc_url,
+    };
+    let input = redeem_calldata(condition_id)?;
+    let receipt = rpc
+        .send_transaction(
+            signer,
+            chain_id,
+            CTF_ADDRESS,
+            input,
+            Duration::from_secs(config.receipt_timeout_seconds),
+            "Redemption",
+        )
+        .await?;
+    info!(condition_id, tx_hash = %receipt.tx_hash, "Redemption mined");
+    Ok(receipt)
+}
+
+/// The outcome of redeeming one market.
+#[derive(Debug, Clone)]
+pub struct Redemption {
+    pub market_id: String,
+    pub tx_hash: String,
+    pub gas_cost_usd: Decimal,
+}
+
+/// Redeem every market with confirmed live wins that hasn't been redeemed
+/// yet, recording each transaction and its gas cost.
+pub async fn redeem_winnings(
+    store: &Store,
+    client: &PolymarketClient,
+    cycle: u64,
+) -> Result<Vec<Redemption>> {
+    let wins = store.get_unredeemed_wins().await?;
+    let markets: BTreeMap<String, usize> = wins.iter().fold(BTreeMap::new(), |mut acc, t| {
+        *acc.entry(t.market_id.clone()).or_default() += 1;
+        acc
+    });
+
+    let mut redeemed = Vec::new();
+    for (market_id, trades) in markets {
+        let receipt = match client.redeem_positions(&market_id).await {
+            Ok(r) => r,
+            Err(e) => {
+                warn!(market_id = %market_id, error = %e, "Redemption failed — will retry next cycle");
+                continue;
+            }
+        };
+        let gas_cost_usd = client.gas_cost_usd(&receipt).await;
+
+        store
+            .insert_redemption(&RedemptionRecord {
+                market_id: market_id.clone(),
+                tx_hash: receipt.tx_hash.clone(),
+                gas_used: receipt.gas_used as i64,
+                gas_cost: gas_cost_usd.to_string(),
+                redeemed_at: Utc::now().to_rfc3339(),
+            })
+            .await?;
+        store
+            .insert_api_cost(&ApiCostRecord {
+                id: None,
+                provider: "polygon".to_string(),
+                endpoint: Some("redeemPositions".to_string()),
+                input_tokens: None,
+                output_tokens: None,
+                cost: gas_cost_usd,
+                cycle: Some(cycle as i64),
+                created_at: None,
+            })
+            .await?;
+
+        info!(
+            market_id = %market_id,
+            trades,
+            tx_hash = %receipt.tx_hash,
+            gas_cost_usd = %gas_cost_usd,
+            "Winning position redeemed"
+        );
+        redeemed.push(Redemption {
+            market_id,
+            tx_hash: receipt.tx_hash,
+            gas_cost_usd,
+        });
+    }
+
+    Ok(redeemed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redeem_calldata() {
+        let condition_id = format!("0x{}", "ab".repeat(32));
+        let data = redeem_calldata(&condition_id).unwrap();
+        // redeemPositions(address,bytes32,bytes32,uint256[])
+        assert_eq!(&data[..4], &[0x01, 0xb7, 0x03, 0x7c]);
+        // selector + 4 head words + array length + 2 elements
+        assert_eq!(data.len(), 4 + 32 * 7);
+        assert!(redeem_calldata("not-a-condition").is_err());
+    }
+}
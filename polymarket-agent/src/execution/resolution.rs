@@ -5,14 +5,15 @@
 //! and feeds resolved outcomes into the calibration system.
 
 use anyhow::{Context, Result};
-use chrono::Utc;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::Deserialize;
+use std::future::Future;
 use std::str::FromStr;
 use tracing::{info, warn};
 
 use crate::db::store::{Store, TradeRecord};
+use crate::market::api::MarketApi;
 use crate::market::models::Side;
 use crate::valuation::calibration;
 
@@ -37,6 +38,7 @@ struct GammaResolutionResponse {
 pub struct ResolutionResult {
     pub trade_id: i64,
     pub market_id: String,
+    pub side: Side,
     pub pnl: Decimal,
     pub won: bool,
 }
@@ -53,6 +55,31 @@ pub async fn check_and_settle(
     http: &reqwest::Client,
     gamma_base_url: &str,
 ) -> Result<Vec<ResolutionResult>> {
+    settle_open_trades(store, |market_id| async move {
+        fetch_market_resolution(http, gamma_base_url, &market_id).await
+    })
+    .await
+}
+
+/// [`check_and_settle`] against whichever exchange the agent trades on,
+/// e.g. the simulated one.
+pub async fn settle_resolved(
+    store: &Store,
+    exchange: &dyn MarketApi,
+) -> Result<Vec<ResolutionResult>> {
+    settle_open_trades(store, |market_id| async move {
+        let yes_won = exchange.market_resolution(&market_id).await?;
+        Ok(yes_won.map(|yes_won| MarketResolution { yes_won }))
+    })
+    .await
+}
+
+/// Settle open trades on the markets `resolve` reports resolved.
+async fn settle_open_trades<F, Fut>(store: &Store, resolve: F) -> Result<Vec<ResolutionResult>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<Option<MarketResolution>>>,
+{
     let open_trades = store.get_open_trades().await?;
     if open_trades.is_empty() {
         return Ok(Vec::new());
@@ -72,8 +99,7 @@ pub async fn check_and_settle(
     let mut results = Vec::new();
 
     for market_id in &market_ids {
-        // Query Gamma API for this specific market
-        let resolution = match fetch_market_resolution(http, gamma_base_url, market_id).await {
+        let resolution = match resolve(market_id.clone()).await {
             Ok(Some(r)) => r,
             Ok(None) => continue, // Market not found or not resolved
             Err(e) => {
@@ -139,15 +165,76 @@ pub async fn check_and_settle(
     Ok(results)
 }
 
+/// Markets of journaled trade ideas checked per call.
+const IDEA_MARKETS_PER_CHECK: i64 = 20;
+
+/// Ideas on markets that ended longer ago than this are no longer checked.
+const IDEA_MAX_AGE_DAYS: i64 = 30;
+
+/// Grade journaled trade ideas whose markets have resolved on `exchange`.
+/// Returns how many ideas were graded.
+pub async fn grade_trade_ideas(store: &Store, exchange: &dyn MarketApi) -> Result<u64> {
+    grade_ideas(store, |market_id| async move {
+        let yes_won = exchange.market_resolution(&market_id).await?;
+        Ok(yes_won.map(|yes_won| MarketResolution { yes_won }))
+    })
+    .await
+}
+
+/// [`grade_trade_ideas`] against the Gamma API.
+pub async fn check_trade_ideas(
+    store: &Store,
+    http: &reqwest::Client,
+    gamma_base_url: &str,
+) -> Result<u64> {
+    grade_ideas(store, |market_id| async move {
+        fetch_market_resolution(http, gamma_base_url, &market_id).await
+    })
+    .await
+}
+
+/// Grade the ideas on ended markets that `resolve` reports resolved.
+async fn grade_ideas<F, Fut>(store: &Store, resolve: F) -> Result<u64>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<Option<MarketResolution>>>,
+{
+    let markets = store
+        .get_unresolved_idea_markets(
+            store.clock().now(),
+            IDEA_MAX_AGE_DAYS,
+            IDEA_MARKETS_PER_CHECK,
+        )
+        .await?;
+    let mut graded = 0;
+    for market_id in markets {
+        match resolve(market_id.clone()).await {
+            Ok(Some(resolution)) => {
+                graded += store
+                    .resolve_trade_ideas(&market_id, resolution.yes_won)
+                    .await?;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(market_id = %market_id, error = %e, "Failed to check trade idea resolution");
+            }
+        }
+    }
+    if graded > 0 {
+        info!(graded, "Trade ideas graded against resolutions");
+    }
+    Ok(graded)
+}
+
 /// Parsed resolution state for a market.
-struct MarketResolution {
+pub(crate) struct MarketResolution {
     /// Whether YES won (YES outcome price = 1.0).
-    yes_won: bool,
+    pub(crate) yes_won: bool,
 }
 
 /// Fetch market resolution status from Gamma API.
 /// Returns `Ok(None)` if the market hasn't resolved yet.
-async fn fetch_market_resolution(
+pub(crate) async fn fetch_market_resolution(
     http: &reqwest::Client,
     gamma_base_url: &str,
     condition_id: &str,
@@ -232,15 +319,16 @@ async fn fetch_market_resolution(
 /// - YES trade that loses: (0.0 - entry_price) × size (negative)
 /// - NO trade that wins: entry_price × size (we bought NO at entry_price, payout = 1 - entry)
 /// - NO trade that loses: -(1.0 - entry_price) × size
+///
+/// The fee paid at entry is then deducted.
 async fn settle_trade(
     store: &Store,
     trade: &TradeRecord,
     resolution: &MarketResolution,
 ) -> Result<ResolutionResult> {
     let trade_id = trade.id.unwrap();
-    let entry_price =
-        Decimal::from_str(&trade.entry_price).context("Invalid entry_price in trade record")?;
-    let size = Decimal::from_str(&trade.size).context("Invalid size in trade record")?;
+    let entry_price = trade.entry_price;
+    let size = trade.size;
 
     let side = match trade.direction.as_str() {
         "YES" => Side::Yes,
@@ -268,8 +356,11 @@ async fn settle_trade(
         -entry_price * size
     };
 
+    // Entry fees come out of settlement P&L
+    let pnl = pnl - trade.fee;
+
     let status = if won { "RESOLVED_WIN" } else { "RESOLVED_LOSS" };
-    let now = Utc::now();
+    let now = store.clock().now();
 
     store
         .update_trade_status(trade_id, status, Some(pnl), Some(now))
@@ -289,6 +380,7 @@ async fn settle_trade(
     Ok(ResolutionResult {
         trade_id,
         market_id: trade.market_id.clone(),
+        side,
         pnl,
         won,
     })
@@ -299,33 +391,39 @@ mod tests {
     use super::*;
     use crate::db::store::TradeRecord;
 
-    fn open_yes_trade(id: i64, entry: &str, size: &str) -> TradeRecord {
+    fn open_yes_trade(id: i64, entry: Decimal, size: Decimal) -> TradeRecord {
         TradeRecord {
             id: Some(id),
             cycle: 1,
             market_id: "mkt_1".to_string(),
             market_question: Some("Will X happen?".to_string()),
             direction: "YES".to_string(),
-            entry_price: entry.to_string(),
-            size: size.to_string(),
-            edge_at_entry: "0.10".to_string(),
-            claude_fair_value: "0.70".to_string(),
-            confidence: "0.85".to_string(),
-            kelly_raw: "0.20".to_string(),
-            kelly_adjusted: "0.10".to_string(),
+            entry_price: entry,
+            size,
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.70),
+            confidence: dec!(0.85),
+            kelly_raw: dec!(0.20),
+            kelly_adjusted: dec!(0.10),
             status: "OPEN".to_string(),
             pnl: None,
             created_at: None,
             resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
         }
     }
 
     #[tokio::test]
     async fn test_settle_yes_trade_wins() {
         let store = Store::new(":memory:").await.unwrap();
-        let trade = open_yes_trade(0, "0.60", "10");
-        let trade_id = store.insert_trade(&trade).await.unwrap();
-        let mut stored = store.get_open_trades().await.unwrap();
+        let trade = open_yes_trade(0, dec!(0.60), dec!(10));
+        store.insert_trade(&trade).await.unwrap();
+        let stored = store.get_open_trades().await.unwrap();
         let t = &stored[0];
 
         let resolution = MarketResolution { yes_won: true };
@@ -343,7 +441,7 @@ mod tests {
     #[tokio::test]
     async fn test_settle_yes_trade_loses() {
         let store = Store::new(":memory:").await.unwrap();
-        let trade = open_yes_trade(0, "0.60", "10");
+        let trade = open_yes_trade(0, dec!(0.60), dec!(10));
         store.insert_trade(&trade).await.unwrap();
         let stored = store.get_open_trades().await.unwrap();
         let t = &stored[0];
@@ -359,7 +457,7 @@ mod tests {
     #[tokio::test]
     async fn test_settle_no_trade_wins() {
         let store = Store::new(":memory:").await.unwrap();
-        let mut trade = open_yes_trade(0, "0.40", "10");
+        let mut trade = open_yes_trade(0, dec!(0.40), dec!(10));
         trade.direction = "NO".to_string();
         store.insert_trade(&trade).await.unwrap();
         let stored = store.get_open_trades().await.unwrap();
@@ -372,4 +470,19 @@ mod tests {
         // PnL = (1.0 - 0.40) * 10 = 6.0
         assert_eq!(result.pnl, dec!(6.0));
     }
+
+    #[tokio::test]
+    async fn test_settle_deducts_entry_fee() {
+        let store = Store::new(":memory:").await.unwrap();
+        let mut trade = open_yes_trade(0, dec!(0.60), dec!(10));
+        trade.fee = dec!(0.08);
+        store.insert_trade(&trade).await.unwrap();
+        let stored = store.get_open_trades().await.unwrap();
+
+        let resolution = MarketResolution { yes_won: true };
+        let result = settle_trade(&store, &stored[0], &resolution).await.unwrap();
+
+        // (1.0 - 0.60) * 10 - 0.08 fee
+        assert_eq!(result.pnl, dec!(3.92));
+    }
 }
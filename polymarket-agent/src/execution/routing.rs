@@ -0,0 +1,277 @@
+//! Execution style selection.
+//!
+//! Crossing the spread fills immediately but pays it; joining the best bid
+//! earns the spread but may never fill; pegging to the midpoint sits between
+//! the two, repricing until it fills or runs out of attempts. The style can
+//! be fixed or chosen per trade from edge size and time to resolution.
+
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::execution::order::{
+    execute_order, ExecutionResult, OrderStatus, PartialFillPolicy, PreparedOrder,
+};
+use crate::market::api::MarketApi;
+use crate::market::models::{OrderBookSnapshot, Side};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStyle {
+    /// Cross the spread, walking the book up to the slippage limit.
+    #[default]
+    Aggressive,
+    /// Join the best bid and wait to be filled.
+    Passive,
+    /// Bid at the midpoint, repricing as it moves.
+    MidpointPeg,
+}
+
+impl ExecutionStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionStyle::Aggressive => "aggressive",
+            ExecutionStyle::Passive => "passive",
+            ExecutionStyle::MidpointPeg => "midpoint_peg",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoutingConfig {
+    /// Style used for every trade unless `adaptive` is set.
+    #[serde(default)]
+    pub style: ExecutionStyle,
+    /// Pick the style per trade from edge and time to resolution.
+    #[serde(default)]
+    pub adaptive: bool,
+    /// Net edge at or above which an adaptive route crosses the spread.
+    #[serde(default = "default_cross_edge")]
+    pub cross_edge: Decimal,
+    /// Net edge below which an adaptive route only joins the bid.
+    #[serde(default = "default_passive_edge")]
+    pub passive_edge: Decimal,
+    /// Markets resolving within this many hours always cross.
+    #[serde(default = "default_urgent_hours")]
+    pub urgent_hours: Decimal,
+    #[serde(default = "default_peg_reprice_seconds")]
+    pub peg_reprice_seconds: u64,
+    #[serde(default = "default_peg_max_reprices")]
+    pub peg_max_reprices: u32,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            style: ExecutionStyle::default(),
+            adaptive: false,
+            cross_edge: default_cross_edge(),
+            passive_edge: default_passive_edge(),
+            urgent_hours: default_urgent_hours(),
+            peg_reprice_seconds: default_peg_reprice_seconds(),
+            peg_max_reprices: default_peg_max_reprices(),
+        }
+    }
+}
+
+fn default_cross_edge() -> Decimal {
+    dec!(0.10)
+}
+
+fn default_passive_edge() -> Decimal {
+    dec!(0.05)
+}
+
+fn default_urgent_hours() -> Decimal {
+    dec!(24)
+}
+
+fn default_peg_reprice_seconds() -> u64 {
+    30
+}
+
+fn default_peg_max_reprices() -> u32 {
+    3
+}
+
+/// Choose how to execute a trade with `edge` whose market resolves in
+/// `hours_to_resolution`. Large edges and imminent resolutions are worth
+/// paying the spread for; thin edges are not.
+pub fn choose_style(
+    config: &RoutingConfig,
+    edge: Decimal,
+    hours_to_resolution: Decimal,
+) -> ExecutionStyle {
+    if !config.adaptive {
+        return config.style;
+    }
+    if edge >= config.cross_edge || hours_to_resolution <= config.urgent_hours {
+        ExecutionStyle::Aggressive
+    } else if edge < config.passive_edge {
+        ExecutionStyle::Passive
+    } else {
+        ExecutionStyle::MidpointPeg
+    }
+}
+
+/// Best bid for the outcome token being bought, from the YES book. A NO
+/// buyer joins opposite the best YES ask.
+pub fn join_bid_price(book: &OrderBookSnapshot, side: Side) -> Option<Decimal> {
+    match side {
+        Side::Yes => book.bids.first().map(|l| l.price),
+        Side::No => book.asks.first().map(|l| Decimal::ONE - l.price),
+    }
+    .filter(|p| *p > Decimal::ZERO && *p < Decimal::ONE)
+}
+
+/// Work `order` at the token's midpoint, repricing every
+/// `peg_reprice_seconds` for whatever is still unfilled. The peg never bids
+/// above `order.price`, the aggressive limit. Stops early if a partial
+/// fill's remainder is left resting.
+pub async fn execute_pegged(
+    client: &dyn MarketApi,
+    order: &PreparedOrder,
+    config: &RoutingConfig,
+) -> ExecutionResult {
+    let mut remaining = order.size;
+    let mut filled = Decimal::ZERO;
+    let mut notional = Decimal::ZERO;
+    let mut fee = Decimal::ZERO;
+    let mut order_id = String::new();
+    let mut last_rejection = None;
+
+    for attempt in 0..=config.peg_max_reprices {
+        if attempt > 0 {
+            tokio::time::sleep(Duration::from_secs(config.peg_reprice_seconds)).await;
+        }
+        let price = match client.get_midpoint(&order.token_id).await {
+            Ok(mid) if mid > Decimal::ZERO => mid.round_dp(2).min(order.price),
+            Ok(_) => order.price,
+            Err(e) => {
+                warn!(market = %order.market_id, error = %e, "Midpoint lookup failed — using limit");
+                order.price
+            }
+        };
+        let child = PreparedOrder {
+            price,
+            size: remaining,
+            ..order.clone()
+        };
+        let result = execute_order(client, &child).await;
+        match result.status {
+            OrderStatus::Rejected(reason) => {
+                info!(
+                    market = %order.market_id,
+                    attempt = attempt + 1,
+                    price = %price,
+                    reason = %reason,
+                    "Pegged order unfilled — repricing"
+                );
+                last_rejection = Some(reason);
+                continue;
+            }
+            status => {
+                filled += result.size;
+                notional += result.price * result.size;
+                fee += result.fee;
+                remaining -= result.size;
+                if order_id.is_empty() {
+                    order_id = result.order_id;
+                }
+                let keeps_working = client.partial_fill_policy() == PartialFillPolicy::Keep;
+                if status == OrderStatus::Filled || keeps_working || remaining <= Decimal::ZERO {
+                    break;
+                }
+            }
+        }
+    }
+
+    let status = if filled >= order.size {
+        OrderStatus::Filled
+    } else if filled > Decimal::ZERO {
+        OrderStatus::PartiallyFilled {
+            filled_size: filled,
+        }
+    } else {
+        OrderStatus::Rejected(last_rejection.unwrap_or_else(|| "peg never filled".to_string()))
+    };
+    let price = if filled > Decimal::ZERO {
+        notional / filled
+    } else {
+        order.price
+    };
+
+    ExecutionResult {
+        order_id,
+        token_id: order.token_id.clone(),
+        side: order.side,
+        price,
+        size: filled,
+        fee,
+        status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::{BookLiquidity, PriceLevel};
+    use chrono::Utc;
+
+    fn adaptive() -> RoutingConfig {
+        RoutingConfig {
+            adaptive: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_choose_style() {
+        let config = adaptive();
+        assert_eq!(
+            choose_style(&config, dec!(0.12), dec!(200)),
+            ExecutionStyle::Aggressive
+        );
+        assert_eq!(
+            choose_style(&config, dec!(0.07), dec!(200)),
+            ExecutionStyle::MidpointPeg
+        );
+        assert_eq!(
+            choose_style(&config, dec!(0.03), dec!(200)),
+            ExecutionStyle::Passive
+        );
+        // Resolving soon: cross regardless of edge
+        assert_eq!(
+            choose_style(&config, dec!(0.03), dec!(6)),
+            ExecutionStyle::Aggressive
+        );
+        // Fixed style ignores edge
+        assert_eq!(
+            choose_style(&RoutingConfig::default(), dec!(0.03), dec!(200)),
+            ExecutionStyle::Aggressive
+        );
+    }
+
+    #[test]
+    fn test_join_bid_price() {
+        let level = |price| PriceLevel {
+            price,
+            size: dec!(100),
+        };
+        let book = OrderBookSnapshot {
+            token_id: "tok".to_string(),
+            bids: vec![level(dec!(0.44))],
+            asks: vec![level(dec!(0.47))],
+            spread: dec!(0.03),
+            midpoint: dec!(0.455),
+            implied_probability: dec!(0.455),
+            timestamp: Utc::now(),
+            liquidity: BookLiquidity::TwoSided,
+        };
+        assert_eq!(join_bid_price(&book, Side::Yes), Some(dec!(0.44)));
+        assert_eq!(join_bid_price(&book, Side::No), Some(dec!(0.53)));
+    }
+}
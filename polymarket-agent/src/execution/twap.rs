@@ -0,0 +1,232 @@
+//! TWAP (sliced) execution for large orders.
+//!
+//! Polymarket books are often thin at the top. An order taking more than
+//! `max_depth_share` of the best level is split into equal child orders
+//! spaced `slice_interval_seconds` apart, so the book can refill between
+//! slices instead of being swept in one go.
+
+use std::time::Duration;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::execution::order::{execute_order, ExecutionResult, OrderStatus, PreparedOrder};
+use crate::market::api::MarketApi;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TwapConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Largest share of top-of-book depth a single child order may take.
+    #[serde(default = "default_max_depth_share")]
+    pub max_depth_share: Decimal,
+    #[serde(default = "default_max_slices")]
+    pub max_slices: u32,
+    #[serde(default = "default_slice_interval_seconds")]
+    pub slice_interval_seconds: u64,
+}
+
+impl Default for TwapConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_depth_share: default_max_depth_share(),
+            max_slices: default_max_slices(),
+            slice_interval_seconds: default_slice_interval_seconds(),
+        }
+    }
+}
+
+fn default_max_depth_share() -> Decimal {
+    dec!(0.10)
+}
+
+fn default_max_slices() -> u32 {
+    5
+}
+
+fn default_slice_interval_seconds() -> u64 {
+    60
+}
+
+/// Outcome of one child order.
+#[derive(Debug, Clone)]
+pub struct SliceFill {
+    pub index: usize,
+    pub size: Decimal,
+    pub status: OrderStatus,
+}
+
+/// Split `shares` into child order sizes so none exceeds
+/// `max_depth_share` of `top_depth`, using at most `max_slices` slices.
+/// Returns a single slice when slicing is disabled or not needed.
+pub fn plan_slices(shares: Decimal, top_depth: Decimal, config: &TwapConfig) -> Vec<Decimal> {
+    let per_slice = top_depth * config.max_depth_share;
+    if !config.enabled || shares <= per_slice || per_slice <= Decimal::ZERO {
+        return vec![shares];
+    }
+
+    let needed = (shares / per_slice).ceil().to_u32().unwrap_or(u32::MAX);
+    let count = needed.clamp(1, config.max_slices.max(1));
+    let slice = (shares / Decimal::from(count)).round_dp(2);
+
+    // Last slice absorbs rounding so the slices sum to `shares`
+    let mut slices = vec![slice; count as usize - 1];
+    slices.push(shares - slice * Decimal::from(count - 1));
+    slices
+}
+
+/// Execute `order` in slices sized against `top_depth`, waiting between
+/// slices. Stops at the first rejected slice.
+///
+/// Returns the combined result — size is the total filled and price the
+/// size-weighted average — plus each slice's outcome. The combined result is
+/// partially filled if any slice fell short, and rejected only if nothing
+/// filled.
+pub async fn execute_sliced(
+    client: &dyn MarketApi,
+    order: &PreparedOrder,
+    top_depth: Decimal,
+    config: &TwapConfig,
+) -> (ExecutionResult, Vec<SliceFill>) {
+    let slices = plan_slices(order.size, top_depth, config);
+    if slices.len() == 1 {
+        let result = execute_order(client, order).await;
+        let fill = SliceFill {
+            index: 0,
+            size: result.size,
+            status: result.status.clone(),
+        };
+        return (result, vec![fill]);
+    }
+
+    info!(
+        market = %order.market_id,
+        total = %order.size,
+        slices = slices.len(),
+        "Slicing order (TWAP)"
+    );
+
+    let mut fills = Vec::with_capacity(slices.len());
+    let mut filled = Decimal::ZERO;
+    let mut notional = Decimal::ZERO;
+    let mut fee = Decimal::ZERO;
+    let mut order_id = String::new();
+    let mut last_rejection = None;
+
+    for (index, size) in slices.iter().copied().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(Duration::from_secs(config.slice_interval_seconds)).await;
+        }
+        let child = PreparedOrder {
+            size,
+            ..order.clone()
+        };
+        let result = execute_order(client, &child).await;
+        fills.push(SliceFill {
+            index,
+            size,
+            status: result.status.clone(),
+        });
+        match result.status {
+            OrderStatus::Filled | OrderStatus::PartiallyFilled { .. } => {
+                filled += result.size;
+                notional += result.price * result.size;
+                fee += result.fee;
+                if order_id.is_empty() {
+                    order_id = result.order_id;
+                }
+                info!(
+                    market = %order.market_id,
+                    slice = index + 1,
+                    of = slices.len(),
+                    size = %size,
+                    filled = %filled,
+                    "TWAP slice filled"
+                );
+            }
+            OrderStatus::Rejected(reason) => {
+                warn!(
+                    market = %order.market_id,
+                    slice = index + 1,
+                    of = slices.len(),
+                    reason = %reason,
+                    "TWAP slice rejected — stopping"
+                );
+                last_rejection = Some(reason);
+                break;
+            }
+        }
+    }
+
+    let status = match (filled > Decimal::ZERO, last_rejection) {
+        (true, _) if filled >= order.size => OrderStatus::Filled,
+        (true, _) => OrderStatus::PartiallyFilled {
+            filled_size: filled,
+        },
+        (false, reason) => {
+            OrderStatus::Rejected(reason.unwrap_or_else(|| "no slices filled".to_string()))
+        }
+    };
+    let price = if filled > Decimal::ZERO {
+        notional / filled
+    } else {
+        order.price
+    };
+
+    (
+        ExecutionResult {
+            order_id,
+            token_id: order.token_id.clone(),
+            side: order.side,
+            price,
+            size: filled,
+            fee,
+            status,
+        },
+        fills,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> TwapConfig {
+        TwapConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_small_order_not_sliced() {
+        // 10% of 200 shares = 20 per slice
+        assert_eq!(plan_slices(dec!(15), dec!(200), &enabled()), vec![dec!(15)]);
+    }
+
+    #[test]
+    fn test_disabled_not_sliced() {
+        let config = TwapConfig::default();
+        assert_eq!(plan_slices(dec!(500), dec!(200), &config), vec![dec!(500)]);
+    }
+
+    #[test]
+    fn test_slices_sum_to_total() {
+        let slices = plan_slices(dec!(50), dec!(200), &enabled());
+        assert_eq!(slices.len(), 3);
+        assert_eq!(slices.iter().sum::<Decimal>(), dec!(50));
+        assert!(slices.iter().all(|s| *s <= dec!(20)));
+    }
+
+    #[test]
+    fn test_slice_count_capped() {
+        // Would need 50 slices of 2; capped at 5 larger ones
+        let slices = plan_slices(dec!(100), dec!(20), &enabled());
+        assert_eq!(slices.len(), 5);
+        assert_eq!(slices.iter().sum::<Decimal>(), dec!(100));
+    }
+}
@@ -1,14 +1,116 @@
 //! Wallet and balance management.
 //!
 //! Tracks effective bankroll accounting for API costs and
-//! determines available capital for trading.
+//! determines available capital for trading. In live mode it also reads the
+//! wallet's on-chain USDC balance and exchange allowance, and tops the
+//! allowance up with a capped `approve` when it runs low.
 
-use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::{address, Address, Bytes, U256};
+use alloy::signers::k256::ecdsa::SigningKey;
+use alloy::signers::local::LocalSigner;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use tracing::info;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
 
+use crate::db::store::{ApiCostRecord, Store};
+use crate::execution::chain::{JsonRpc, TxReceipt};
+use crate::execution::redemption::{RedemptionConfig, USDC_ADDRESS};
 use crate::market::polymarket::PolymarketClient;
 
+/// Polymarket CTF Exchange, which pulls USDC from the wallet on fills.
+const EXCHANGE_ADDRESS: Address = address!("4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E");
+
+/// USDC.e has 6 decimals.
+const USDC_UNIT: Decimal = dec!(1_000_000);
+
+sol! {
+    function balanceOf(address account) external view returns (uint256);
+    function allowance(address owner, address spender) external view returns (uint256);
+    function approve(address spender, uint256 amount) external returns (bool);
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WalletConfig {
+    /// Submit a USDC approval for the exchange when the allowance runs low.
+    #[serde(default = "default_auto_approve")]
+    pub auto_approve: bool,
+    /// Approve again once the allowance drops below this (or below the
+    /// balance, if smaller).
+    #[serde(default = "default_min_allowance")]
+    pub min_allowance: Decimal,
+    /// Allowance granted by each approval. Never unlimited.
+    #[serde(default = "default_approval_amount")]
+    pub approval_amount: Decimal,
+}
+
+impl Default for WalletConfig {
+    fn default() -> Self {
+        Self {
+            auto_approve: default_auto_approve(),
+            min_allowance: default_min_allowance(),
+            approval_amount: default_approval_amount(),
+        }
+    }
+}
+
+fn default_auto_approve() -> bool {
+    true
+}
+
+fn default_min_allowance() -> Decimal {
+    dec!(100)
+}
+
+fn default_approval_amount() -> Decimal {
+    dec!(1000)
+}
+
+/// On-chain USDC position of the trading wallet.
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletStatus {
+    pub address: String,
+    pub usdc_balance: Decimal,
+    /// USDC the exchange may pull from the wallet.
+    pub allowance: Decimal,
+    /// Native POL held for gas.
+    pub pol_balance: Decimal,
+    /// `pol_balance` in USD at the price feed's price.
+    pub pol_usd: Decimal,
+    pub checked_at: DateTime<Utc>,
+    /// Most recent approval submitted by the agent, if any.
+    pub last_approval_tx: Option<String>,
+}
+
+/// Latest [`WalletStatus`], shared between the agent and the dashboard.
+#[derive(Clone, Default)]
+pub struct WalletState {
+    inner: Arc<RwLock<Option<WalletStatus>>>,
+}
+
+impl WalletState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self) -> Option<WalletStatus> {
+        self.inner.read().await.clone()
+    }
+
+    pub async fn set(&self, status: WalletStatus) {
+        *self.inner.write().await = Some(status);
+    }
+}
+
 /// Calculate the effective bankroll available for trading.
 ///
 /// effective = wallet_balance - api_reserve - unrealized_exposure
@@ -70,10 +172,153 @@ pub async fn log_balance_summary(
     Ok(())
 }
 
+/// Whether the allowance is too low to cover upcoming orders.
+pub fn needs_approval(balance: Decimal, allowance: Decimal, config: &WalletConfig) -> bool {
+    allowance < config.min_allowance.min(balance)
+}
+
+/// Read the USDC balance, exchange allowance and POL balance of `owner`.
+pub async fn query_status(
+    http: &reqwest::Client,
+    rpc_url: &str,
+    owner: Address,
+) -> Result<WalletStatus> {
+    let rpc = JsonRpc { http, url: rpc_url };
+    let balance = rpc
+        .eth_call(
+            USDC_ADDRESS,
+            &balanceOfCall { account: owner }.abi_encode().into(),
+        )
+        .await
+        .context("USDC balanceOf failed")?;
+    let allowance = rpc
+        .eth_call(
+            USDC_ADDRESS,
+            &allowanceCall {
+                owner,
+                spender: EXCHANGE_ADDRESS,
+            }
+            .abi_encode()
+            .into(),
+        )
+        .await
+        .context("USDC allowance failed")?;
+
+    Ok(WalletStatus {
+        address: owner.to_string(),
+        usdc_balance: usdc_from_units(decode_uint(&balance)?),
+        allowance: usdc_from_units(decode_uint(&allowance)?),
+        pol_balance: rpc.pol_balance(owner).await.context("POL balance failed")?,
+        // Priced by the caller
+        pol_usd: Decimal::ZERO,
+        checked_at: Utc::now(),
+        last_approval_tx: None,
+    })
+}
+
+/// Approve the exchange to spend `amount` USDC and wait for it to be mined.
+pub async fn submit_approval(
+    http: &reqwest::Client,
+    config: &RedemptionConfig,
+    signer: &LocalSigner<SigningKey>,
+    chain_id: u64,
+    amount: Decimal,
+) -> Result<TxReceipt> {
+    if amount <= Decimal::ZERO {
+        bail!("Approval amount must be positive, got {amount}");
+    }
+    let rpc = JsonRpc {
+        http,
+        url: &config.rpc_url,
+    };
+    let input: Bytes = approveCall {
+        spender: EXCHANGE_ADDRESS,
+        amount: usdc_to_units(amount),
+    }
+    .abi_encode()
+    .into();
+    rpc.send_transaction(
+        signer,
+        chain_id,
+        USDC_ADDRESS,
+        input,
+        Duration::from_secs(config.receipt_timeout_seconds),
+        "USDC approval",
+    )
+    .await
+}
+
+/// Check the wallet and, if allowed, raise a low allowance. Approval gas is
+/// booked to `api_costs`.
+pub async fn refresh(
+    client: &PolymarketClient,
+    config: &WalletConfig,
+    store: &Store,
+    cycle: u64,
+) -> Result<WalletStatus> {
+    let mut status = client.wallet_status().await?;
+    if !needs_approval(status.usdc_balance, status.allowance, config) {
+        return Ok(status);
+    }
+    if !config.auto_approve {
+        warn!(
+            allowance = %status.allowance,
+            balance = %status.usdc_balance,
+            "USDC allowance is low and auto-approve is off — orders may be rejected"
+        );
+        return Ok(status);
+    }
+
+    let receipt = client.approve_usdc(config.approval_amount).await?;
+    let gas_cost_usd = client.gas_cost_usd(&receipt).await;
+    store
+        .insert_api_cost(&ApiCostRecord {
+            id: None,
+            provider: "polygon".to_string(),
+            endpoint: Some("approve".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            cost: gas_cost_usd,
+            cycle: Some(cycle as i64),
+            created_at: None,
+        })
+        .await?;
+    info!(
+        previous_allowance = %status.allowance,
+        allowance = %config.approval_amount,
+        tx_hash = %receipt.tx_hash,
+        gas_cost_usd = %gas_cost_usd,
+        "USDC allowance raised"
+    );
+
+    // approve() sets, rather than adds to, the allowance
+    status.allowance = config.approval_amount;
+    status.last_approval_tx = Some(receipt.tx_hash);
+    Ok(status)
+}
+
+fn decode_uint(data: &Bytes) -> Result<U256> {
+    if data.len() < 32 {
+        bail!("Expected a uint256 return value, got {} bytes", data.len());
+    }
+    Ok(U256::from_be_slice(&data[..32]))
+}
+
+/// Convert raw USDC units to dollars. Unlimited allowances saturate.
+fn usdc_from_units(units: U256) -> Decimal {
+    u64::try_from(units)
+        .ok()
+        .and_then(|u| Decimal::from(u).checked_div(USDC_UNIT))
+        .unwrap_or(Decimal::MAX)
+}
+
+fn usdc_to_units(amount: Decimal) -> U256 {
+    U256::from((amount * USDC_UNIT).trunc().to_u128().unwrap_or(0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rust_decimal_macros::dec;
 
     #[test]
     fn test_effective_bankroll_normal() {
@@ -112,4 +357,29 @@ mod tests {
         let cycles = estimated_cycles_remaining(dec!(5), dec!(0.05), dec!(10));
         assert_eq!(cycles, 0);
     }
+
+    #[test]
+    fn test_needs_approval() {
+        let config = WalletConfig::default();
+        assert!(needs_approval(dec!(500), dec!(50), &config));
+        assert!(!needs_approval(dec!(500), dec!(100), &config));
+        // A small wallet only needs its balance covered
+        assert!(!needs_approval(dec!(30), dec!(40), &config));
+        assert!(needs_approval(dec!(30), dec!(20), &config));
+    }
+
+    #[test]
+    fn test_usdc_unit_conversion() {
+        assert_eq!(usdc_from_units(U256::from(12_345_678u64)), dec!(12.345678));
+        assert_eq!(usdc_from_units(U256::MAX), Decimal::MAX);
+        assert_eq!(usdc_to_units(dec!(1000)), U256::from(1_000_000_000u64));
+
+        let mut word = [0u8; 32];
+        word[31] = 42;
+        assert_eq!(
+            decode_uint(&Bytes::from(word.to_vec())).unwrap(),
+            U256::from(42)
+        );
+        assert!(decode_uint(&Bytes::new()).is_err());
+    }
 }
@@ -0,0 +1,292 @@
+//! Conditional GET cache for slow-changing HTTP resources.
+//!
+//! Remembers the `ETag` and `Last-Modified` validators of successful GET
+//! responses, keyed by full URL. The next request for the same URL is sent
+//! with `If-None-Match` / `If-Modified-Since`; a `304 Not Modified` reply is
+//! answered from the stored body instead of re-downloading it. Used for
+//! Gamma market listings and data-source forecasts, which rarely change
+//! between cycles.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::api_error::{parse_retry_after, ApiError};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpCacheConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// URLs remembered at once. The least recently used entry is evicted
+    /// when full.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for HttpCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            max_entries: default_max_entries(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_entries() -> usize {
+    512
+}
+
+/// A response body, fresh or replayed from the cache.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub body: String,
+    /// True when the server answered 304 and `body` came from the cache.
+    pub not_modified: bool,
+    /// Delay requested by a 429 or 503 response.
+    pub retry_after: Option<Duration>,
+}
+
+impl CachedResponse {
+    pub fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+
+    /// Turn a non-success response into a typed [`ApiError`].
+    pub fn error_for_status(self, service: &'static str) -> Result<Self, ApiError> {
+        if self.is_success() {
+            return Ok(self);
+        }
+        Err(ApiError::Status {
+            service,
+            status: self.status,
+            body: self.body,
+            retry_after: self.retry_after,
+        })
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_str(&self.body).context("Failed to parse HTTP response body")
+    }
+}
+
+/// Cumulative cache usage since startup.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HttpCacheStats {
+    pub requests: u64,
+    pub not_modified: u64,
+    pub entries: usize,
+}
+
+struct CacheEntry {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+    last_used: DateTime<Utc>,
+}
+
+/// Validator cache shared by every client that opts in.
+pub struct HttpCache {
+    config: HttpCacheConfig,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    requests: AtomicU64,
+    not_modified: AtomicU64,
+}
+
+impl HttpCache {
+    pub fn new(config: &HttpCacheConfig) -> Self {
+        Self {
+            config: config.clone(),
+            entries: Mutex::new(HashMap::new()),
+            requests: AtomicU64::new(0),
+            not_modified: AtomicU64::new(0),
+        }
+    }
+
+    /// A cache that never stores anything; requests pass straight through.
+    pub fn disabled() -> Self {
+        Self::new(&HttpCacheConfig {
+            enabled: false,
+            ..HttpCacheConfig::default()
+        })
+    }
+
+    /// Send a GET, revalidating against any stored copy of the same URL.
+    /// Non-success responses are returned as-is and never cached.
+    pub async fn send(&self, request: RequestBuilder) -> Result<CachedResponse> {
+        let (client, request) = request.build_split();
+        let mut request = request.context("Failed to build HTTP request")?;
+        let key = request.url().to_string();
+        self.requests.fetch_add(1, Ordering::Relaxed);
+
+        if self.config.enabled {
+            let entries = self.entries.lock().expect("http cache poisoned");
+            if let Some(entry) = entries.get(&key) {
+                let headers = request.headers_mut();
+                if let Some(etag) = entry.etag.as_deref().and_then(|v| v.parse().ok()) {
+                    headers.insert(IF_NONE_MATCH, etag);
+                }
+                if let Some(modified) = entry.last_modified.as_deref().and_then(|v| v.parse().ok())
+                {
+                    headers.insert(IF_MODIFIED_SINCE, modified);
+                }
+            }
+        }
+
+        let resp = client
+            .execute(request)
+            .await
+            .context("HTTP request failed")?;
+        let status = resp.status();
+
+        if status == StatusCode::NOT_MODIFIED {
+            let mut entries = self.entries.lock().expect("http cache poisoned");
+            if let Some(entry) = entries.get_mut(&key) {
+                entry.last_used = Utc::now();
+                self.not_modified.fetch_add(1, Ordering::Relaxed);
+                debug!(url = %key, "HTTP cache hit (304)");
+                return Ok(CachedResponse {
+                    status: StatusCode::OK,
+                    body: entry.body.clone(),
+                    not_modified: true,
+                    retry_after: None,
+                });
+            }
+        }
+
+        let header = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let retry_after = parse_retry_after(resp.headers(), Utc::now());
+        let body = resp.text().await.context("Failed to read HTTP body")?;
+
+        if self.config.enabled && status.is_success() && (etag.is_some() || last_modified.is_some())
+        {
+            self.store(
+                key,
+                CacheEntry {
+                    etag,
+                    last_modified,
+                    body: body.clone(),
+                    last_used: Utc::now(),
+                },
+            );
+        }
+
+        Ok(CachedResponse {
+            status,
+            body,
+            not_modified: false,
+            retry_after,
+        })
+    }
+
+    pub fn stats(&self) -> HttpCacheStats {
+        HttpCacheStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            not_modified: self.not_modified.load(Ordering::Relaxed),
+            entries: self.entries.lock().expect("http cache poisoned").len(),
+        }
+    }
+
+    fn store(&self, key: String, entry: CacheEntry) {
+        let mut entries = self.entries.lock().expect("http cache poisoned");
+        if !entries.contains_key(&key) && entries.len() >= self.config.max_entries.max(1) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_not_modified_replays_stored_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v1\"")
+                    .set_body_string("[1,2,3]"),
+            )
+            .mount(&server)
+            .await;
+
+        let cache = HttpCache::new(&HttpCacheConfig::default());
+        let http = reqwest::Client::new();
+        let url = format!("{}/markets", server.uri());
+
+        let first = cache.send(http.get(&url)).await.unwrap();
+        assert!(!first.not_modified);
+        let second = cache.send(http.get(&url)).await.unwrap();
+        assert!(second.not_modified);
+        assert!(second.is_success());
+        assert_eq!(second.json::<Vec<u32>>().unwrap(), vec![1, 2, 3]);
+
+        let stats = cache.stats();
+        assert_eq!(stats.requests, 2);
+        assert_eq!(stats.not_modified, 1);
+        assert_eq!(stats.entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_cache_sends_no_validators() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v1\"")
+                    .set_body_string("{}"),
+            )
+            .mount(&server)
+            .await;
+
+        let cache = HttpCache::disabled();
+        let http = reqwest::Client::new();
+        cache.send(http.get(server.uri())).await.unwrap();
+        let again = cache.send(http.get(server.uri())).await.unwrap();
+
+        assert!(!again.not_modified);
+        assert_eq!(cache.stats().entries, 0);
+        let received = server.received_requests().await.unwrap();
+        assert!(received
+            .iter()
+            .all(|r| !r.headers.contains_key("if-none-match")));
+    }
+}
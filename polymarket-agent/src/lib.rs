@@ -1,10 +1,15 @@
 pub mod agent;
+pub mod api_error;
 pub mod backtesting;
+pub mod chaos;
+pub mod clock;
 pub mod config;
 pub mod data;
 pub mod db;
 pub mod execution;
+pub mod http_cache;
 pub mod market;
 pub mod monitoring;
 pub mod risk;
+pub mod rng;
 pub mod valuation;
@@ -1,27 +1,149 @@
-use anyhow::Result;
-use clap::Parser;
+use std::path::{Path, PathBuf};
 
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+
+use polymarket_agent::agent::canary;
+use polymarket_agent::agent::control::ControlState;
+use polymarket_agent::agent::crash;
 use polymarket_agent::agent::lifecycle::Agent;
+use polymarket_agent::agent::triggers::TriggerHandle;
 use polymarket_agent::config::{self, AgentMode, AppConfig};
-use polymarket_agent::db::store::Store;
+use polymarket_agent::db::export;
+use polymarket_agent::db::maintenance::spawn_maintenance;
+use polymarket_agent::db::store::{self, Store};
+use polymarket_agent::execution::wallet::WalletState;
 use polymarket_agent::monitoring;
+use polymarket_agent::monitoring::alerts::AlertClient;
+use polymarket_agent::monitoring::auth::DashboardAuth;
 use polymarket_agent::monitoring::dashboard::{spawn_dashboard, DashboardState};
+use polymarket_agent::monitoring::health::{
+    spawn_readiness_checks, AnthropicKeyCheck, DependencyCheck, PolymarketCheck, SqliteCheck,
+};
 use polymarket_agent::monitoring::logger;
+use polymarket_agent::monitoring::systemd::SystemdNotifier;
+use polymarket_agent::monitoring::watchdog::spawn_watchdog;
 
 /// Polymarket Autonomous Trading Agent
 #[derive(Parser, Debug)]
 #[command(
     name = "polymarket-agent",
-    about = "Autonomous prediction market trading agent"
+    about = "Autonomous prediction market trading agent",
+    version = canary::BUILD_VERSION
 )]
 struct CliArgs {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Override agent mode from config file
-    #[arg(long, value_enum)]
+    #[arg(long, value_enum, global = true)]
     mode: Option<AgentModeArg>,
 
     /// Run a quick validation check (single cycle, no trades)
     #[arg(long)]
     dry_run: bool,
+
+    /// Apply pending database migrations, then exit
+    #[arg(long)]
+    migrate_only: bool,
+
+    /// Validate configuration and secrets, then exit (non-zero on error)
+    #[arg(long)]
+    check_config: bool,
+
+    /// Override risk.kelly_fraction (e.g. 0.25 for quarter-Kelly)
+    #[arg(long, global = true)]
+    kelly_fraction: Option<rust_decimal::Decimal>,
+
+    /// Override database.path
+    #[arg(long, global = true)]
+    database: Option<String>,
+
+    /// Override agent.cycle_interval_seconds
+    #[arg(long, global = true)]
+    cycle_interval: Option<u64>,
+
+    /// Override monitoring.dashboard_port
+    #[arg(long, global = true)]
+    dashboard_port: Option<u16>,
+}
+
+/// With no subcommand the agent runs (or backtests) according to its mode.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the trading agent in the configured mode
+    Run,
+    /// Backtest on historical data, synthetic data, or the agent's own history
+    Backtest {
+        /// Historical snapshot CSV (default: data/backtest.csv, else synthetic)
+        #[arg(long)]
+        data: Option<PathBuf>,
+        /// Replay the agent's own database instead of market data
+        #[arg(long)]
+        replay: bool,
+    },
+    /// Show a running agent's health and metrics
+    Status {
+        /// Dashboard base URL (default: from monitoring config)
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// Export recorded history for analysis
+    Export {
+        /// Table to export; `all` writes one file per table into --output
+        #[arg(long, value_enum, default_value_t = ExportTableArg::Trades)]
+        table: ExportTableArg,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// Output file, or directory for `--table all` (default: stdout)
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Tell a running agent to cancel all orders, sell every position and
+    /// stop trading
+    Flatten {
+        /// Dashboard base URL (default: from monitoring config)
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// Run one settlement pass over open trades, then exit
+    Resolve,
+    /// Run the full agent loop against a seeded simulated market
+    Simulate {
+        /// Override simulation.seed
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Override simulation.cycles
+        #[arg(long)]
+        cycles: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Json,
+    /// Requires building with `--features parquet`
+    Parquet,
+}
+
+impl From<ExportFormat> for export::ExportFormat {
+    fn from(arg: ExportFormat) -> Self {
+        match arg {
+            ExportFormat::Csv => export::ExportFormat::Csv,
+            ExportFormat::Json => export::ExportFormat::Json,
+            ExportFormat::Parquet => export::ExportFormat::Parquet,
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum ExportTableArg {
+    Trades,
+    Cycles,
+    ApiCosts,
+    Calibration,
+    All,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -29,6 +151,7 @@ enum AgentModeArg {
     Paper,
     Live,
     Backtest,
+    Simulation,
 }
 
 impl From<AgentModeArg> for AgentMode {
@@ -37,6 +160,7 @@ impl From<AgentModeArg> for AgentMode {
             AgentModeArg::Paper => AgentMode::Paper,
             AgentModeArg::Live => AgentMode::Live,
             AgentModeArg::Backtest => AgentMode::Backtest,
+            AgentModeArg::Simulation => AgentMode::Simulation,
         }
     }
 }
@@ -47,28 +171,126 @@ async fn main() -> Result<()> {
 
     let (mut config, secrets) = AppConfig::load()?;
 
-    // Override mode from CLI if provided
+    // Override config from CLI flags if provided
     if let Some(mode) = args.mode {
         config.agent.mode = mode.into();
     }
+    if let Some(fraction) = args.kelly_fraction {
+        config.risk.kelly_fraction = fraction;
+    }
+    if let Some(path) = args.database {
+        config.database.path = path;
+    }
+    if let Some(seconds) = args.cycle_interval {
+        config.agent.cycle_interval_seconds = seconds;
+    }
+    if let Some(port) = args.dashboard_port {
+        config.monitoring.dashboard_port = port;
+    }
+
+    if args.check_config {
+        return check_config(&config, &secrets);
+    }
+
+    if args.migrate_only {
+        return run_migrations(&config).await;
+    }
 
     // Dry run mode: single cycle validation
     if args.dry_run {
         return run_dry_run(&config, &secrets).await;
     }
 
+    match args.command.unwrap_or(Command::Run) {
+        Command::Run => {
+            start_logging(&config)?;
+            match config.agent.mode {
+                AgentMode::Backtest => run_backtest(&config, &secrets, None).await,
+                AgentMode::Paper | AgentMode::Live => run_agent(config, secrets).await,
+                AgentMode::Simulation => run_simulation(config).await,
+            }
+        }
+        Command::Backtest { data, replay } => {
+            config.agent.mode = AgentMode::Backtest;
+            start_logging(&config)?;
+            if replay {
+                run_replay(&config).await
+            } else {
+                run_backtest(&config, &secrets, data.as_deref()).await
+            }
+        }
+        Command::Status { url } => run_status(&config, url).await,
+        Command::Flatten { url } => run_flatten(&config, &secrets, url).await,
+        Command::Export {
+            table,
+            format,
+            output,
+        } => run_export(&config, table, format.into(), output.as_deref()).await,
+        Command::Resolve => {
+            start_logging(&config)?;
+            run_resolve(&config).await
+        }
+        Command::Simulate { seed, cycles } => {
+            if let Some(seed) = seed {
+                config.simulation.seed = seed;
+            }
+            if let Some(cycles) = cycles {
+                config.simulation.cycles = cycles;
+            }
+            config.agent.mode = AgentMode::Simulation;
+            start_logging(&config)?;
+            run_simulation(config).await
+        }
+    }
+}
+
+/// Bring the database schema up to date without starting the agent, e.g.
+/// as a deploy step before the new build takes over.
+async fn run_migrations(config: &AppConfig) -> Result<()> {
+    let db = Store::new(&config.database.path).await?;
+    let version = db.schema_version().await?.unwrap_or(0);
+    println!(
+        "Database {} is at schema version {version} (latest {})",
+        config.database.path,
+        store::latest_schema_version()
+    );
+    Ok(())
+}
+
+/// Validate the effective configuration without touching the network or
+/// database, e.g. as a container entrypoint step.
+fn check_config(config: &AppConfig, secrets: &config::Secrets) -> Result<()> {
+    let warnings = match config.validate(secrets) {
+        Ok(warnings) => warnings,
+        Err(e) => {
+            println!("❌ Configuration invalid: {e:#}");
+            return Err(e);
+        }
+    };
+    println!("Mode: {:?}", config.agent.mode);
+    println!("Database: {}", config.database.path);
+    println!("Cycle interval: {}s", config.agent.cycle_interval_seconds);
+    println!(
+        "Dashboard: {}:{}",
+        config.monitoring.dashboard_bind, config.monitoring.dashboard_port
+    );
+    for warning in &warnings {
+        println!("⚠️  {warning}");
+    }
+    println!("✅ Configuration valid");
+    Ok(())
+}
+
+fn start_logging(config: &AppConfig) -> Result<()> {
     logger::init_logging(&config.monitoring)?;
 
     tracing::info!(
         mode = ?config.agent.mode,
+        version = canary::BUILD_VERSION,
         cycle_interval_s = config.agent.cycle_interval_seconds,
         "Polymarket Agent starting"
     );
-
-    match config.agent.mode {
-        AgentMode::Backtest => run_backtest(&config),
-        AgentMode::Paper | AgentMode::Live => run_agent(config, secrets).await,
-    }
+    Ok(())
 }
 
 /// Quick dry-run validation: tests connectivity and pipeline without placing trades.
@@ -181,7 +403,7 @@ async fn run_dry_run(config: &AppConfig, secrets: &config::Secrets) -> Result<()
     println!("Next steps:");
     println!("  Paper mode:  cargo run -- --mode paper");
     println!("  Live mode:   cargo run -- --mode live");
-    println!("  Backtest:    cargo run -- --mode backtest");
+    println!("  Backtest:    cargo run -- backtest");
     println!();
     println!("⚠️  Always run paper trading for 48-72h before going live.");
 
@@ -190,66 +412,241 @@ async fn run_dry_run(config: &AppConfig, secrets: &config::Secrets) -> Result<()
 
 /// Run the agent in paper or live trading mode.
 async fn run_agent(config: AppConfig, secrets: config::Secrets) -> Result<()> {
+    let crash_marker = crash::marker_path(&config.database.path);
+    let last_panic = crash::install_panic_hook(crash_marker.clone());
+    let notifier = SystemdNotifier::from_env(&config.monitoring.systemd);
+
     // Create shared database store
     let store = Store::new(&config.database.path).await?;
 
     // Create health state and dashboard
-    let health_state = monitoring::health::HealthState::new();
+    let cycle_interval = std::time::Duration::from_secs(config.agent.cycle_interval_seconds);
+    let health_state = monitoring::health::HealthState::new()
+        .with_liveness_timeout(cycle_interval * config.monitoring.watchdog.stall_intervals.max(1));
     let dashboard_store = Store::from_pool(store.pool().clone());
+    let control = ControlState::new();
+    let wallet = WalletState::new();
+
+    let dashboard_auth = DashboardAuth::from_secrets(&secrets);
+    let watchdog_alerts = AlertClient::from_config(&config.monitoring, &secrets)?;
+    let maintenance_alerts = AlertClient::from_config(&config.monitoring, &secrets)?;
+    let crash_alerts = AlertClient::from_config(&config.monitoring, &secrets)?;
+    match crash::take_marker(&crash_marker) {
+        Ok(Some(report)) => {
+            tracing::error!(panic = %report, "Previous run crashed");
+            if let Err(e) = crash_alerts.previous_crash(&report).await {
+                tracing::warn!(error = %e, "Failed to send crash alert");
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!(error = %e, "Failed to read crash marker"),
+    }
+
+    let canary_store = Store::from_pool(store.pool().clone());
+    let mut readiness_checks: Vec<Box<dyn DependencyCheck>> = vec![
+        Box::new(SqliteCheck::new(Store::from_pool(store.pool().clone()))),
+        Box::new(PolymarketCheck::new(&config.polymarket.clob_base_url)),
+    ];
+    if config.monitoring.health.check_anthropic {
+        if let Some(key) = secrets.anthropic_api_key.clone() {
+            readiness_checks.push(Box::new(AnthropicKeyCheck::new(key)));
+        }
+    }
+    let readiness_handle = spawn_readiness_checks(
+        config.monitoring.health.clone(),
+        health_state.clone(),
+        readiness_checks,
+    );
+    let mut agent = Agent::new(config.clone(), secrets, store)
+        .await?
+        .with_control(control.clone())
+        .with_wallet(wallet.clone());
+
     let dashboard_state = DashboardState::new(
         dashboard_store,
         health_state.clone(),
         config.agent.initial_paper_balance,
-    );
+    )
+    .with_control(control.clone())
+    .with_wallet(wallet)
+    .with_market_client(agent.market_client())
+    .with_auth(dashboard_auth);
     let dashboard_handle = spawn_dashboard(
         dashboard_state,
         &config.monitoring.dashboard_bind,
         config.monitoring.dashboard_port,
     );
 
-    let mut agent = Agent::new(config.clone(), secrets, store).await?;
-    let interval = std::time::Duration::from_secs(config.agent.cycle_interval_seconds);
-
-    loop {
-        tokio::select! {
-            result = agent.run_cycle() => {
-                result?;
+    // A new build runs a dry-run canary before it may trade. Live mode
+    // refuses to start on failure; paper mode only warns.
+    if let Some(previous_version) = canary::version_change(&canary_store).await? {
+        let report = agent.run_canary(previous_version).await;
+        agent.alert_canary(&report).await;
+        if report.passed() {
+            tracing::info!(summary = %report.summary(), "Canary passed");
+            canary::record_version(&canary_store).await?;
+        } else if config.agent.mode == AgentMode::Live {
+            dashboard_handle.abort();
+            readiness_handle.abort();
+            anyhow::bail!("Canary failed — refusing to trade:\n{}", report.summary());
+        } else {
+            tracing::warn!(summary = %report.summary(), "Canary failed — continuing in paper mode");
+        }
+    }
+    agent.resume().await?;
+    notifier.ready();
+
+    let triggers = TriggerHandle::new();
+    let watcher_handle = agent.watch_triggers(triggers.clone());
+    let capture_handle = agent.capture_order_books();
+    let maintenance_handle = config.database.maintenance.enabled.then(|| {
+        spawn_maintenance(
+            config.database.maintenance.clone(),
+            Store::from_pool(canary_store.pool().clone()),
+            maintenance_alerts,
+        )
+    });
+    let watchdog_handle = config.monitoring.watchdog.enabled.then(|| {
+        spawn_watchdog(
+            config.monitoring.watchdog.clone(),
+            cycle_interval,
+            health_state.clone(),
+            watchdog_alerts,
+            vec![("dashboard server", dashboard_handle.abort_handle())],
+        )
+    });
+
+    let grace = std::time::Duration::from_secs(config.agent.shutdown_timeout_seconds);
+    let cooldown = std::time::Duration::from_secs(config.agent.triggers.min_cooldown_seconds);
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    let reason = loop {
+        let cycle_started = std::time::Instant::now();
+        triggers.cycle_started();
+        {
+            // A cycle interrupted mid-way may leave orders half-tracked, so
+            // on Ctrl+C let it finish (up to the grace period) before stopping.
+            let cycle = crash::catch_panic(agent.run_cycle());
+            tokio::pin!(cycle);
+            tokio::select! {
+                result = &mut cycle => match result {
+                    Ok(result) => result?,
+                    Err(_) => break "panic",
+                },
+                _ = &mut ctrl_c => {
+                    tracing::info!(grace_s = grace.as_secs(), "Received Ctrl+C — finishing current cycle");
+                    match tokio::time::timeout(grace, cycle).await {
+                        Ok(Ok(Ok(()))) => {}
+                        Ok(Ok(Err(e))) => tracing::error!(error = %e, "Final cycle failed"),
+                        Ok(Err(_)) => break "panic",
+                        Err(_) => tracing::warn!("Cycle did not finish in time — abandoning it"),
+                    }
+                    break "signal";
+                }
+            }
+        }
 
-                // Update health state
-                health_state.record_cycle(agent.cycle_number(), agent.current_state());
+        // Update health state
+        health_state.record_cycle(agent.cycle_number(), agent.current_state());
+        health_state.record_source_failures(agent.source_failures());
+        health_state.record_degraded_sources(agent.degraded_sources());
+        notifier.watchdog();
+        notifier.status(&format!(
+            "Cycle {}: {}",
+            agent.cycle_number(),
+            agent.current_state()
+        ));
+
+        if agent.is_dead() {
+            tracing::error!("Agent has died. Shutting down.");
+            break "death";
+        }
 
-                if agent.is_dead() {
-                    tracing::error!("Agent has died. Shutting down.");
-                    break;
+        tokio::select! {
+            _ = notifier.idle(cycle_interval) => {}
+            // Act on pause, resume or kill switch without waiting out the interval
+            _ = control.changed() => {}
+            reasons = triggers.wait() => {
+                let reasons: Vec<String> = reasons.iter().map(|r| r.to_string()).collect();
+                let wait = cooldown.saturating_sub(cycle_started.elapsed());
+                tracing::info!(?reasons, cooldown_s = wait.as_secs(), "Early cycle triggered");
+                tokio::select! {
+                    _ = notifier.idle(wait) => {}
+                    _ = &mut ctrl_c => {
+                        tracing::info!("Received Ctrl+C — shutting down gracefully");
+                        break "signal";
+                    }
                 }
-
-                tokio::time::sleep(interval).await;
             }
-            _ = tokio::signal::ctrl_c() => {
+            _ = &mut ctrl_c => {
                 tracing::info!("Received Ctrl+C — shutting down gracefully");
-                break;
+                break "signal";
+            }
+        }
+    };
+
+    notifier.stopping();
+    for handle in [
+        watcher_handle,
+        capture_handle,
+        maintenance_handle,
+        watchdog_handle,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        handle.abort();
+    }
+
+    if reason == "panic" {
+        if let Some(report) = last_panic.take() {
+            if let Err(e) = crash_alerts.panic(agent.cycle_number(), &report).await {
+                tracing::warn!(error = %e, "Failed to send panic alert");
             }
         }
     }
 
+    // Leave no live orders working after we stop (death already cancelled them)
+    if !agent.is_dead() {
+        agent.cancel_all_orders().await;
+    }
+    agent.flush_writes().await;
+
     // Clean up dashboard server
     dashboard_handle.abort();
+    readiness_handle.abort();
+
+    // After a panic the shutdown marker stays unclean and the crash marker
+    // is reported on the next start
+    if reason == "panic" {
+        anyhow::bail!(
+            "Agent stopped after a panic in cycle {}",
+            agent.cycle_number()
+        );
+    }
+    if let Err(e) = agent.checkpoint(reason).await {
+        tracing::error!(error = %e, "Failed to write shutdown checkpoint");
+    }
     tracing::info!("Agent shutdown complete");
 
     Ok(())
 }
 
 /// Run a backtest using historical or synthetic data.
-fn run_backtest(config: &AppConfig) -> Result<()> {
+async fn run_backtest(
+    config: &AppConfig,
+    secrets: &config::Secrets,
+    data: Option<&Path>,
+) -> Result<()> {
     use polymarket_agent::backtesting::engine::{self, BacktestConfig};
     use polymarket_agent::backtesting::historical;
-    use std::path::Path;
 
     let bt_config = BacktestConfig::from_app_config(config);
 
     // Check for a historical data file, otherwise use synthetic data
-    let data_path = Path::new("data/backtest.csv");
-    let snapshots = if data_path.exists() {
+    let data_path = data.unwrap_or(Path::new("data/backtest.csv"));
+    let snapshots = if data.is_some() || data_path.exists() {
         tracing::info!(path = %data_path.display(), "Loading historical data from CSV");
         historical::load_from_csv(data_path)?
     } else {
@@ -263,10 +660,15 @@ fn run_backtest(config: &AppConfig) -> Result<()> {
 
     tracing::info!(snapshots = snapshots.len(), "Starting backtest");
 
+    if config.backtest.claude.enabled {
+        return run_claude_backtest(config, secrets, &bt_config, &snapshots).await;
+    }
+
     let results = engine::run_backtest(&snapshots, &bt_config);
 
     // Print results to stdout
     println!("\n{results}");
+    write_reports(config, &results, "backtest");
 
     if results.total_trades >= 500 {
         tracing::info!("Backtest completed with 500+ trades — ready for paper trading");
@@ -279,3 +681,253 @@ fn run_backtest(config: &AppConfig) -> Result<()> {
 
     Ok(())
 }
+
+/// Run the agent loop against a seeded simulated market and print what it did.
+async fn run_simulation(config: AppConfig) -> Result<()> {
+    use polymarket_agent::agent::simulation;
+
+    let database = config.simulation.database_path.clone();
+    let report = simulation::run(config).await?;
+    println!("\n{report}");
+    println!("Database: {database}");
+    Ok(())
+}
+
+/// Replay recorded cycles from the agent's database under the current config.
+async fn run_replay(config: &AppConfig) -> Result<()> {
+    use polymarket_agent::backtesting::engine::BacktestConfig;
+    use polymarket_agent::backtesting::replay;
+
+    let store = Store::new(&config.database.path).await?;
+    let cycles = replay::load_cycles(&store).await?;
+    tracing::info!(
+        path = %config.database.path,
+        cycles = cycles.len(),
+        kelly_fraction = %config.risk.kelly_fraction,
+        "Replaying recorded history"
+    );
+
+    let results = replay::run_replay(&cycles, &BacktestConfig::from_app_config(config));
+    println!("\n{results}");
+    write_reports(config, &results, "replay");
+
+    Ok(())
+}
+
+/// Export backtest reports, logging rather than failing if they can't be written.
+fn write_reports(
+    config: &AppConfig,
+    results: &polymarket_agent::backtesting::results::BacktestResults,
+    label: &str,
+) {
+    use polymarket_agent::backtesting::report;
+
+    let dir = std::path::Path::new(&config.backtest.report_dir);
+    match report::write_reports(results, dir, label) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Report written: {}", path.display());
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "Failed to write backtest reports"),
+    }
+}
+
+/// Backtest with Claude valuations and compare against the noise model.
+async fn run_claude_backtest(
+    config: &AppConfig,
+    secrets: &config::Secrets,
+    bt_config: &polymarket_agent::backtesting::engine::BacktestConfig,
+    snapshots: &[polymarket_agent::backtesting::historical::HistoricalSnapshot],
+) -> Result<()> {
+    use polymarket_agent::backtesting::valuation;
+    use polymarket_agent::valuation::claude::ClaudeClient;
+    use polymarket_agent::valuation::fair_value::ValuationEngine;
+    use std::sync::Arc;
+
+    let api_key = secrets
+        .anthropic_api_key
+        .clone()
+        .context("ANTHROPIC_API_KEY is required for backtest.claude")?;
+    let claude_config = &config.backtest.claude;
+    let store = Store::new(&claude_config.cache_db).await?;
+    let claude = ClaudeClient::new(
+        api_key,
+        config.valuation.claude_model.clone(),
+        store.clone_for_parallel(),
+    );
+    let engine = ValuationEngine::new(
+        Arc::new(claude),
+        polymarket_agent::config::ValuationConfig {
+            cache_ttl_seconds: valuation::CACHE_TTL_SECONDS,
+            ..config.valuation.clone()
+        },
+        store.clone_for_parallel(),
+    );
+
+    let run = valuation::value_snapshots(
+        &engine,
+        &store,
+        snapshots,
+        claude_config.budget_usd,
+        bt_config.initial_balance,
+    )
+    .await?;
+    println!(
+        "Valued {} markets ({} new calls, {} cached, {} over budget) for ${}",
+        run.valuations.len(),
+        run.calls,
+        run.cached,
+        run.skipped,
+        run.spent.round_dp(4)
+    );
+
+    let comparison = valuation::compare(snapshots, bt_config, &run.valuations);
+    println!("\n{}\n\n{comparison}", comparison.claude);
+    write_reports(config, &comparison.claude, "backtest-claude");
+
+    Ok(())
+}
+
+/// Print a running agent's health and metrics from its dashboard.
+async fn run_status(config: &AppConfig, url: Option<String>) -> Result<()> {
+    let base = url.unwrap_or_else(|| config.monitoring.dashboard_url());
+    let base = base.trim_end_matches('/');
+    let http = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()?;
+
+    for (name, path) in [("Health", "/api/health"), ("Metrics", "/api/metrics")] {
+        let value: serde_json::Value = http
+            .get(format!("{base}{path}"))
+            .send()
+            .await
+            .with_context(|| format!("No dashboard at {base} — is the agent running?"))?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("Invalid response from {path}"))?;
+        println!(
+            "=== {name} ===\n{}\n",
+            serde_json::to_string_pretty(&value)?
+        );
+    }
+    Ok(())
+}
+
+/// Trip a running agent's kill switch with flattening, via its dashboard.
+async fn run_flatten(
+    config: &AppConfig,
+    secrets: &config::Secrets,
+    url: Option<String>,
+) -> Result<()> {
+    let base = url.unwrap_or_else(|| config.monitoring.dashboard_url());
+    let base = base.trim_end_matches('/');
+    let http = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()?;
+
+    let mut request = http.post(format!("{base}/control/flatten"));
+    if let Some(token) = secrets.dashboard_token.as_deref() {
+        request = request.bearer_auth(token);
+    }
+    let value: serde_json::Value = request
+        .send()
+        .await
+        .with_context(|| format!("No dashboard at {base} — is the agent running?"))?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Invalid response from /control/flatten")?;
+    println!(
+        "Flatten requested — agent is {}. Orders are cancelled and positions sold as it \
+         wakes; POST /control/resume to trade again.",
+        value["status"].as_str().unwrap_or("unknown")
+    );
+    Ok(())
+}
+
+/// Export every recorded trade to `output`, or stdout.
+async fn run_export(
+    config: &AppConfig,
+    table: ExportTableArg,
+    format: export::ExportFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    let store = Store::new(&config.database.path).await?;
+    let table = match table {
+        ExportTableArg::Trades => export::ExportTable::Trades,
+        ExportTableArg::Cycles => export::ExportTable::Cycles,
+        ExportTableArg::ApiCosts => export::ExportTable::ApiCosts,
+        ExportTableArg::Calibration => export::ExportTable::Calibration,
+        ExportTableArg::All => {
+            let dir = output.context("--table all needs --output <directory>")?;
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            for table in export::ExportTable::ALL {
+                let path = dir.join(format!("{}.{}", table.name(), format.extension()));
+                export_table(&store, table, format, Some(&path)).await?;
+            }
+            return Ok(());
+        }
+    };
+    export_table(&store, table, format, output).await
+}
+
+async fn export_table(
+    store: &Store,
+    table: export::ExportTable,
+    format: export::ExportFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    let rows = export::load_table(store, table).await?;
+    let contents = rows.render(format)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!(
+                "Exported {} {} rows to {}",
+                rows.len(),
+                table.name(),
+                path.display()
+            );
+        }
+        None => {
+            use std::io::Write;
+            std::io::stdout()
+                .write_all(&contents)
+                .context("Failed to write export to stdout")?;
+        }
+    }
+    Ok(())
+}
+
+/// Settle any open trades whose markets have resolved, then exit.
+async fn run_resolve(config: &AppConfig) -> Result<()> {
+    use polymarket_agent::execution::resolution;
+
+    let store = Store::new(&config.database.path).await?;
+    let http = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+    let settled =
+        resolution::check_and_settle(&store, &http, &config.polymarket.gamma_base_url).await?;
+
+    for r in &settled {
+        println!(
+            "Trade {} on {}: {} (P&L ${})",
+            r.trade_id,
+            r.market_id,
+            if r.won { "won" } else { "lost" },
+            r.pnl.round_dp(2)
+        );
+    }
+    let pnl: rust_decimal::Decimal = settled.iter().map(|r| r.pnl).sum();
+    println!("Settled {} trades, P&L ${}", settled.len(), pnl.round_dp(2));
+    let graded =
+        resolution::check_trade_ideas(&store, &http, &config.polymarket.gamma_base_url).await?;
+    println!("Graded {graded} trade ideas");
+    Ok(())
+}
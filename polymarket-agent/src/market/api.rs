@@ -0,0 +1,143 @@
+//! Exchange operations the scanner and execution code depend on.
+//!
+//! [`MarketApi`] is the slice of [`PolymarketClient`] needed to discover
+//! markets, read books and work orders. Code written against it runs
+//! unchanged on the real client or on [`FakeExchange`](super::fake::FakeExchange)
+//! with scripted books and fills.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use crate::execution::fees::FeeModel;
+use crate::execution::order::PartialFillPolicy;
+use crate::execution::resolution;
+use crate::market::models::{Market, OrderBookSnapshot, Side};
+use crate::market::polymarket::{MarketFilters, PlacedOrder, PolymarketClient};
+
+#[async_trait]
+pub trait MarketApi: Send + Sync {
+    /// Active markets passing `filters`, highest volume first.
+    async fn get_markets(&self, filters: &MarketFilters) -> Result<Vec<Market>>;
+
+    /// Market `condition_id`, whether or not it passes any filters.
+    async fn get_market(&self, condition_id: &str) -> Result<Market>;
+
+    async fn get_order_book(&self, token_id: &str) -> Result<OrderBookSnapshot>;
+
+    async fn get_midpoint(&self, token_id: &str) -> Result<Decimal> {
+        Ok(self.get_order_book(token_id).await?.midpoint)
+    }
+
+    /// Last YES price of market `condition_id`.
+    async fn get_current_yes_price(&self, condition_id: &str) -> Result<Decimal>;
+
+    /// Token id of the `side` outcome of market `condition_id`.
+    async fn find_token_id(&self, condition_id: &str, side: Side) -> Option<String>;
+
+    /// `Some(yes_won)` once market `condition_id` has resolved.
+    async fn market_resolution(&self, condition_id: &str) -> Result<Option<bool>>;
+
+    /// Buy `size` shares of `token_id` at up to `price`.
+    async fn place_limit_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<PlacedOrder>;
+
+    /// Sell `size` held shares at `price`. Returns the order id.
+    async fn exit_position(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<String>;
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()>;
+
+    /// Cancel every open order. Returns the cancelled ids.
+    async fn cancel_all_orders(&self) -> Result<Vec<String>>;
+
+    /// Cash available to trade.
+    async fn get_balance(&self) -> Result<Decimal>;
+
+    fn fees(&self) -> FeeModel;
+
+    fn partial_fill_policy(&self) -> PartialFillPolicy;
+}
+
+#[async_trait]
+impl MarketApi for PolymarketClient {
+    async fn get_markets(&self, filters: &MarketFilters) -> Result<Vec<Market>> {
+        PolymarketClient::get_markets(self, filters).await
+    }
+
+    async fn get_market(&self, condition_id: &str) -> Result<Market> {
+        PolymarketClient::get_market(self, condition_id).await
+    }
+
+    async fn get_order_book(&self, token_id: &str) -> Result<OrderBookSnapshot> {
+        PolymarketClient::get_order_book(self, token_id).await
+    }
+
+    async fn get_current_yes_price(&self, condition_id: &str) -> Result<Decimal> {
+        PolymarketClient::get_current_yes_price(self, condition_id).await
+    }
+
+    async fn find_token_id(&self, condition_id: &str, side: Side) -> Option<String> {
+        PolymarketClient::find_token_id(self, condition_id, side).await
+    }
+
+    async fn market_resolution(&self, condition_id: &str) -> Result<Option<bool>> {
+        let resolution = resolution::fetch_market_resolution(
+            self.http_client(),
+            self.gamma_base_url(),
+            condition_id,
+        )
+        .await?;
+        Ok(resolution.map(|r| r.yes_won))
+    }
+
+    async fn place_limit_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<PlacedOrder> {
+        PolymarketClient::place_limit_order(self, token_id, side, price, size).await
+    }
+
+    async fn exit_position(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<String> {
+        PolymarketClient::exit_position(self, token_id, side, price, size).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        PolymarketClient::cancel_order(self, order_id).await
+    }
+
+    async fn cancel_all_orders(&self) -> Result<Vec<String>> {
+        PolymarketClient::cancel_all_orders(self).await
+    }
+
+    async fn get_balance(&self) -> Result<Decimal> {
+        PolymarketClient::get_balance(self).await
+    }
+
+    fn fees(&self) -> FeeModel {
+        PolymarketClient::fees(self)
+    }
+
+    fn partial_fill_policy(&self) -> PartialFillPolicy {
+        PolymarketClient::partial_fill_policy(self)
+    }
+}
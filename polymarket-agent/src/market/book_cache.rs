@@ -0,0 +1,198 @@
+//! Short-lived order book cache.
+//!
+//! One cycle reads the same books several times: the scanner, order
+//! preparation, exit and hedge checks, market making and paper matching.
+//! [`BookCache`] keeps each token's latest book for `ttl_ms` so those reads
+//! share one CLOB request. Books fetched fresh — e.g. by the book capture
+//! task — are pushed in with [`BookCache::insert`], and a token's entry is
+//! dropped whenever the agent places or exits an order on it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::market::models::OrderBookSnapshot;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BookCacheConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// How long a book is served from the cache.
+    #[serde(default = "default_ttl_ms")]
+    pub ttl_ms: u64,
+    /// Tokens remembered at once. The oldest book is evicted when full.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for BookCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            ttl_ms: default_ttl_ms(),
+            max_entries: default_max_entries(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_ttl_ms() -> u64 {
+    5000
+}
+
+fn default_max_entries() -> usize {
+    1024
+}
+
+/// Cumulative cache usage since startup.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BookCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+pub struct BookCache {
+    config: BookCacheConfig,
+    entries: Mutex<HashMap<String, (OrderBookSnapshot, Instant)>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl BookCache {
+    pub fn new(config: &BookCacheConfig) -> Self {
+        Self {
+            config: config.clone(),
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// The book of `token_id`, if one was stored within the TTL.
+    pub fn get(&self, token_id: &str) -> Option<OrderBookSnapshot> {
+        if !self.config.enabled {
+            return None;
+        }
+        let ttl = Duration::from_millis(self.config.ttl_ms);
+        let entries = self.entries.lock().expect("book cache poisoned");
+        match entries.get(token_id) {
+            Some((book, stored)) if stored.elapsed() < ttl => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(book.clone())
+            }
+            _ => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Store a freshly fetched book, replacing the token's previous one.
+    pub fn insert(&self, book: &OrderBookSnapshot) {
+        if !self.config.enabled || self.config.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().expect("book cache poisoned");
+        if entries.len() >= self.config.max_entries && !entries.contains_key(&book.token_id) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (_, stored))| *stored)
+                .map(|(token, _)| token.clone());
+            if let Some(token) = oldest {
+                entries.remove(&token);
+            }
+        }
+        entries.insert(book.token_id.clone(), (book.clone(), Instant::now()));
+    }
+
+    /// Forget `token_id`'s book, e.g. after trading against it.
+    pub fn invalidate(&self, token_id: &str) {
+        self.entries
+            .lock()
+            .expect("book cache poisoned")
+            .remove(token_id);
+    }
+
+    pub fn stats(&self) -> BookCacheStats {
+        BookCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().expect("book cache poisoned").len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::BookLiquidity;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    fn book(token_id: &str) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            token_id: token_id.to_string(),
+            bids: Vec::new(),
+            asks: Vec::new(),
+            spread: Decimal::ZERO,
+            midpoint: Decimal::ZERO,
+            implied_probability: Decimal::ZERO,
+            timestamp: Utc::now(),
+            liquidity: BookLiquidity::Empty,
+        }
+    }
+
+    #[test]
+    fn test_hit_miss_and_invalidate() {
+        let cache = BookCache::new(&BookCacheConfig::default());
+        assert!(cache.get("a").is_none());
+        cache.insert(&book("a"));
+        assert_eq!(cache.get("a").unwrap().token_id, "a");
+
+        cache.invalidate("a");
+        assert!(cache.get("a").is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 0));
+    }
+
+    #[test]
+    fn test_expired_and_disabled() {
+        let expired = BookCache::new(&BookCacheConfig {
+            ttl_ms: 0,
+            ..BookCacheConfig::default()
+        });
+        expired.insert(&book("a"));
+        assert!(expired.get("a").is_none());
+
+        let disabled = BookCache::new(&BookCacheConfig {
+            enabled: false,
+            ..BookCacheConfig::default()
+        });
+        disabled.insert(&book("a"));
+        assert!(disabled.get("a").is_none());
+        assert_eq!(disabled.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_evicts_oldest_when_full() {
+        let cache = BookCache::new(&BookCacheConfig {
+            max_entries: 2,
+            ..BookCacheConfig::default()
+        });
+        for token in ["a", "b", "c"] {
+            cache.insert(&book(token));
+            // Distinct insertion times
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_some());
+        assert!(cache.get("c").is_some());
+    }
+}
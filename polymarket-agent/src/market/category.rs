@@ -1,11 +1,97 @@
-//! Keyword-based market category detection.
+//! Market category detection.
 //!
-//! Infers a market's category from the question text using keyword matching.
-//! This enables the portfolio concentration limit (`max_positions_per_category`)
-//! which was previously broken because all markets were categorized as "Other".
+//! Gamma tags markets and events with a taxonomy (`sports`, `nba`,
+//! `crypto`, `elections`, ...); [`categorize`] maps those tags to a category
+//! and falls back to keyword matching on the question text only for untagged
+//! markets. This enables the portfolio concentration limit
+//! (`max_positions_per_category`) which was previously broken because all
+//! markets were categorized as "Other".
 
 use crate::market::models::MarketCategory;
 
+/// Gamma tag slugs/labels (lowercased) that identify each category.
+const WEATHER_TAGS: &[&str] = &["weather", "climate", "hurricanes", "temperature"];
+const SPORTS_TAGS: &[&str] = &[
+    "sports",
+    "nfl",
+    "nba",
+    "nhl",
+    "mlb",
+    "wnba",
+    "ncaa",
+    "cfb",
+    "cbb",
+    "soccer",
+    "epl",
+    "ucl",
+    "mls",
+    "football",
+    "basketball",
+    "baseball",
+    "hockey",
+    "tennis",
+    "golf",
+    "mma",
+    "ufc",
+    "boxing",
+    "f1",
+    "formula 1",
+    "cricket",
+    "esports",
+    "olympics",
+];
+const CRYPTO_TAGS: &[&str] = &[
+    "crypto",
+    "bitcoin",
+    "ethereum",
+    "solana",
+    "xrp",
+    "dogecoin",
+    "crypto prices",
+    "defi",
+    "stablecoins",
+    "airdrops",
+];
+const POLITICS_TAGS: &[&str] = &[
+    "politics",
+    "us politics",
+    "us-politics",
+    "elections",
+    "us elections",
+    "global elections",
+    "geopolitics",
+    "congress",
+    "senate",
+    "trump",
+    "world elections",
+    "courts",
+];
+
+/// Category of a market from its Gamma tags, falling back to
+/// [`infer_category`] on the question when no tag is recognized.
+pub fn categorize(tags: &[&str], question: &str) -> MarketCategory {
+    category_from_tags(tags).unwrap_or_else(|| infer_category(question))
+}
+
+/// The category of the first recognized tag, if any.
+pub fn category_from_tags(tags: &[&str]) -> Option<MarketCategory> {
+    tags.iter().find_map(|tag| {
+        let tag = tag.trim().to_lowercase();
+        let tag = tag.as_str();
+        if WEATHER_TAGS.contains(&tag) {
+            Some(MarketCategory::Weather)
+        } else if SPORTS_TAGS.contains(&tag) {
+            Some(MarketCategory::Sports)
+        } else if CRYPTO_TAGS.contains(&tag) {
+            Some(MarketCategory::Crypto)
+        } else if POLITICS_TAGS.contains(&tag) {
+            Some(MarketCategory::Politics)
+        } else {
+            None
+        }
+    })
+}
+
 /// Infer market category from the question text using keyword matching.
 ///
 /// Falls back to `Other("unclassified")` if no keywords match.
@@ -201,6 +287,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tags_take_precedence_over_keywords() {
+        // "match" would read as sports, but the tag says crypto
+        assert_eq!(
+            categorize(&["Featured", "Crypto"], "Will BTC match its ATH?"),
+            MarketCategory::Crypto
+        );
+        assert_eq!(
+            categorize(&["nba"], "Will the Lakers win?"),
+            MarketCategory::Sports
+        );
+        // Unrecognized tags fall back to the question
+        assert_eq!(
+            categorize(&["featured"], "Will it rain in NYC on Feb 20?"),
+            MarketCategory::Weather
+        );
+        assert_eq!(category_from_tags(&[]), None);
+    }
+
     #[test]
     fn test_unclassified_fallback() {
         assert_eq!(
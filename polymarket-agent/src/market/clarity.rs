@@ -0,0 +1,129 @@
+//! Resolution-clarity screening.
+//!
+//! Markets whose resolution criteria are vague tend to resolve on a judgment
+//! call, which no valuation can price. [`clarity_score`] rates a market's
+//! description and resolution source from 0 (hopelessly ambiguous) to 1
+//! (mechanical) with cheap text heuristics, so the scanner can drop the
+//! worst before any valuation budget is spent on them.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::market::models::Market;
+
+/// Phrases that leave the outcome to someone's judgment.
+const AMBIGUOUS_PHRASES: &[&str] = &[
+    "sole discretion",
+    "at the discretion",
+    "subjective",
+    "ambiguous",
+    "interpretation",
+    "consensus of credible reporting",
+    "may resolve",
+    "unclear",
+    "in the spirit of",
+];
+
+/// Phrases that spell out how the market resolves.
+const EXPLICIT_PHRASES: &[&str] = &[
+    "will resolve to \"yes\"",
+    "will resolve to \"no\"",
+    "resolves to yes",
+    "resolves to no",
+    "resolution source",
+    "according to",
+    "as reported by",
+];
+
+/// Phrases that pin the resolution to a time.
+const CUTOFF_PHRASES: &[&str] = &[" et", " utc", "11:59", "by the end of", "deadline"];
+
+/// Heuristic resolution-clarity score in `[0, 1]`.
+pub fn clarity_score(market: &Market) -> Decimal {
+    let description = market.description.to_lowercase();
+    if description.trim().is_empty() {
+        // Nothing but the question to go on
+        return dec!(0.2)
+            + if has_source(market) {
+                dec!(0.2)
+            } else {
+                Decimal::ZERO
+            };
+    }
+
+    let mut score = dec!(0.5);
+    if has_source(market) {
+        score += dec!(0.2);
+    }
+    if description.len() >= 200 {
+        score += dec!(0.1);
+    }
+    if contains_any(&description, EXPLICIT_PHRASES) {
+        score += dec!(0.1);
+    }
+    if contains_any(&description, CUTOFF_PHRASES) {
+        score += dec!(0.1);
+    }
+    let ambiguous = AMBIGUOUS_PHRASES
+        .iter()
+        .filter(|p| description.contains(*p))
+        .count();
+    score -= dec!(0.15) * Decimal::from(ambiguous);
+
+    score.max(Decimal::ZERO).min(Decimal::ONE)
+}
+
+fn has_source(market: &Market) -> bool {
+    market
+        .resolution_source
+        .as_deref()
+        .is_some_and(|s| !s.trim().is_empty())
+}
+
+fn contains_any(text: &str, phrases: &[&str]) -> bool {
+    phrases.iter().any(|p| text.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+    use chrono::Utc;
+
+    fn market(description: &str, resolution_source: Option<&str>) -> Market {
+        Market {
+            condition_id: "0x1".to_string(),
+            question: "Will BTC close above $100k?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            tokens: Vec::new(),
+            end_date: Utc::now(),
+            category: MarketCategory::Crypto,
+            volume_24h: Decimal::ZERO,
+            active: true,
+            event_id: None,
+            event_slug: None,
+            neg_risk: false,
+            description: description.to_string(),
+            resolution_source: resolution_source.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_explicit_markets_score_above_vague_ones() {
+        let explicit = market(
+            "This market will resolve to \"Yes\" if the Binance BTC/USDT 1-minute candle \
+             for 12:00 ET on March 1 closes above $100,000, according to Binance.",
+            Some("https://www.binance.com"),
+        );
+        let vague = market(
+            "Resolves Yes if BTC is widely considered to have broken out, at the sole \
+             discretion of the market creator, based on a consensus of credible reporting.",
+            None,
+        );
+        let bare = market("", None);
+
+        assert_eq!(clarity_score(&explicit), dec!(0.9));
+        assert!(clarity_score(&vague) < dec!(0.3));
+        assert_eq!(clarity_score(&bare), dec!(0.2));
+    }
+}
@@ -0,0 +1,548 @@
+//! In-memory exchange for tests.
+//!
+//! [`FakeExchange`] implements [`MarketApi`] without the network: markets
+//! and order books are set by the test, and orders fill against those books
+//! (taking liquidity at or below their limit) unless a scripted outcome is
+//! queued for them. Every order is kept for inspection. Filled shares are
+//! held until sold or until [`FakeExchange::resolve_market`] pays them out.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+
+use crate::clock::Clock;
+use crate::execution::fees::{FeeConfig, FeeModel, Liquidity};
+use crate::execution::order::PartialFillPolicy;
+use crate::market::api::MarketApi;
+use crate::market::models::{BookLiquidity, Market, OrderBookSnapshot, PriceLevel, Side};
+use crate::market::polymarket::{MarketFilters, PlacedOrder};
+
+/// Scripted outcome for the next order placed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FakeFill {
+    /// Fill against the book, as unscripted orders do.
+    Book,
+    /// Fill this many shares at the limit price, whatever the book holds.
+    Shares(Decimal),
+    /// Fail the placement with this message.
+    Reject(String),
+}
+
+/// An order as the fake exchange saw it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeOrder {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub filled: Decimal,
+    /// A sell from `exit_position` rather than a buy.
+    pub exit: bool,
+    pub cancelled: bool,
+}
+
+impl FakeOrder {
+    pub fn is_open(&self) -> bool {
+        !self.cancelled && !self.exit && self.filled < self.size
+    }
+}
+
+#[derive(Default)]
+struct FakeState {
+    markets: Vec<Market>,
+    books: HashMap<String, OrderBookSnapshot>,
+    balance: Decimal,
+    script: VecDeque<FakeFill>,
+    orders: Vec<FakeOrder>,
+    /// Shares held per token.
+    holdings: HashMap<String, Decimal>,
+    /// Whether YES won, per resolved market.
+    resolutions: HashMap<String, bool>,
+}
+
+pub struct FakeExchange {
+    state: Mutex<FakeState>,
+    fees: FeeConfig,
+    partial_fill: PartialFillPolicy,
+    clock: Clock,
+}
+
+impl FakeExchange {
+    pub fn new(balance: Decimal) -> Self {
+        Self {
+            state: Mutex::new(FakeState {
+                balance,
+                ..FakeState::default()
+            }),
+            fees: FeeConfig::default(),
+            partial_fill: PartialFillPolicy::default(),
+            clock: Clock::default(),
+        }
+    }
+
+    pub fn with_fees(mut self, fees: FeeConfig) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    pub fn with_partial_fill(mut self, policy: PartialFillPolicy) -> Self {
+        self.partial_fill = policy;
+        self
+    }
+
+    /// Measure the resolution horizon from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, FakeState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// List a market, replacing any with the same condition id.
+    pub fn add_market(&self, market: Market) {
+        let mut state = self.state();
+        state
+            .markets
+            .retain(|m| m.condition_id != market.condition_id);
+        state.markets.push(market);
+    }
+
+    /// Set a token's book. Levels are best first.
+    pub fn set_book(&self, token_id: &str, bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) {
+        self.state()
+            .books
+            .insert(token_id.to_string(), book(token_id, bids, asks));
+    }
+
+    pub fn remove_book(&self, token_id: &str) {
+        self.state().books.remove(token_id);
+    }
+
+    /// Resolve a market: it stops trading, its books are removed and each
+    /// winning share held pays out $1.
+    pub fn resolve_market(&self, condition_id: &str, yes_won: bool) {
+        let mut state = self.state();
+        let Some(market) = state
+            .markets
+            .iter_mut()
+            .find(|m| m.condition_id == condition_id)
+        else {
+            return;
+        };
+        market.active = false;
+        let tokens = market.tokens.clone();
+        for token in tokens {
+            state.books.remove(&token.token_id);
+            let shares = state.holdings.remove(&token.token_id).unwrap_or_default();
+            if token.outcome.eq_ignore_ascii_case("yes") == yes_won {
+                state.balance += shares;
+            }
+        }
+        state.resolutions.insert(condition_id.to_string(), yes_won);
+    }
+
+    /// Queue the outcome of the next order placed; outcomes are used in order.
+    pub fn script_fill(&self, fill: FakeFill) {
+        self.state().script.push_back(fill);
+    }
+
+    pub fn set_balance(&self, balance: Decimal) {
+        self.state().balance = balance;
+    }
+
+    pub fn balance(&self) -> Decimal {
+        self.state().balance
+    }
+
+    /// Shares of `token_id` currently held.
+    pub fn holdings(&self, token_id: &str) -> Decimal {
+        self.state()
+            .holdings
+            .get(token_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Every order placed so far, oldest first.
+    pub fn orders(&self) -> Vec<FakeOrder> {
+        self.state().orders.clone()
+    }
+}
+
+#[async_trait]
+impl MarketApi for FakeExchange {
+    async fn get_markets(&self, filters: &MarketFilters) -> Result<Vec<Market>> {
+        let max_end_date =
+            self.clock.now() + chrono::Duration::days(filters.max_resolution_days.into());
+        let mut markets: Vec<Market> = self
+            .state()
+            .markets
+            .iter()
+            .filter(|m| {
+                m.active && m.volume_24h >= filters.min_volume_24h && m.end_date <= max_end_date
+            })
+            .cloned()
+            .collect();
+        markets.sort_by_key(|m| std::cmp::Reverse(m.volume_24h));
+        markets.truncate(filters.max_markets);
+        Ok(markets)
+    }
+
+    async fn get_market(&self, condition_id: &str) -> Result<Market> {
+        match self
+            .state()
+            .markets
+            .iter()
+            .find(|m| m.condition_id == condition_id)
+        {
+            Some(market) => Ok(market.clone()),
+            None => bail!("Unknown market {condition_id}"),
+        }
+    }
+
+    async fn get_order_book(&self, token_id: &str) -> Result<OrderBookSnapshot> {
+        match self.state().books.get(token_id) {
+            Some(book) => Ok(book.clone()),
+            None => bail!("No order book for token {token_id}"),
+        }
+    }
+
+    async fn get_current_yes_price(&self, condition_id: &str) -> Result<Decimal> {
+        let state = self.state();
+        if let Some(&yes_won) = state.resolutions.get(condition_id) {
+            return Ok(if yes_won { Decimal::ONE } else { Decimal::ZERO });
+        }
+        let Some(yes) = state
+            .markets
+            .iter()
+            .find(|m| m.condition_id == condition_id)
+            .and_then(|m| {
+                m.tokens
+                    .iter()
+                    .find(|t| t.outcome.eq_ignore_ascii_case("yes"))
+            })
+        else {
+            bail!("Unknown market {condition_id}");
+        };
+        Ok(state
+            .books
+            .get(&yes.token_id)
+            .map_or(yes.price, |book| book.midpoint))
+    }
+
+    async fn find_token_id(&self, condition_id: &str, side: Side) -> Option<String> {
+        let outcome = match side {
+            Side::Yes => "yes",
+            Side::No => "no",
+        };
+        self.state()
+            .markets
+            .iter()
+            .find(|m| m.condition_id == condition_id)?
+            .tokens
+            .iter()
+            .find(|t| t.outcome.eq_ignore_ascii_case(outcome))
+            .map(|t| t.token_id.clone())
+    }
+
+    async fn market_resolution(&self, condition_id: &str) -> Result<Option<bool>> {
+        Ok(self.state().resolutions.get(condition_id).copied())
+    }
+
+    async fn place_limit_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<PlacedOrder> {
+        let fees = self.fees();
+        let mut state = self.state();
+        let (filled, notional) = match state.script.pop_front().unwrap_or(FakeFill::Book) {
+            FakeFill::Book => take_asks(&mut state, token_id, price, size),
+            FakeFill::Shares(shares) => (shares.min(size), price * shares.min(size)),
+            FakeFill::Reject(reason) => bail!(reason),
+        };
+        let fill_price = if filled > Decimal::ZERO {
+            notional / filled
+        } else {
+            price
+        };
+
+        let cost = notional + fees.fee(Liquidity::Taker, fill_price, filled);
+        if cost > state.balance {
+            bail!("Insufficient balance: {} < cost {}", state.balance, cost);
+        }
+        state.balance -= cost;
+        *state.holdings.entry(token_id.to_string()).or_default() += filled;
+
+        let order_id = format!("fake-{}", state.orders.len() + 1);
+        state.orders.push(FakeOrder {
+            order_id: order_id.clone(),
+            token_id: token_id.to_string(),
+            side,
+            price,
+            size,
+            filled,
+            exit: false,
+            cancelled: false,
+        });
+        Ok(PlacedOrder {
+            order_id,
+            filled_size: filled,
+            fill_price,
+        })
+    }
+
+    async fn exit_position(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<String> {
+        let mut state = self.state();
+        let held = state.holdings.entry(token_id.to_string()).or_default();
+        if *held < size {
+            bail!("Cannot sell {size} shares of {token_id}: only {held} held");
+        }
+        *held -= size;
+        state.balance += price * size;
+        let order_id = format!("fake-{}", state.orders.len() + 1);
+        state.orders.push(FakeOrder {
+            order_id: order_id.clone(),
+            token_id: token_id.to_string(),
+            side,
+            price,
+            size,
+            filled: size,
+            exit: true,
+            cancelled: false,
+        });
+        Ok(order_id)
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let mut state = self.state();
+        match state.orders.iter_mut().find(|o| o.order_id == order_id) {
+            Some(order) => {
+                order.cancelled = true;
+                Ok(())
+            }
+            None => bail!("Unknown order {order_id}"),
+        }
+    }
+
+    async fn cancel_all_orders(&self) -> Result<Vec<String>> {
+        let mut cancelled = Vec::new();
+        for order in self.state().orders.iter_mut().filter(|o| o.is_open()) {
+            order.cancelled = true;
+            cancelled.push(order.order_id.clone());
+        }
+        Ok(cancelled)
+    }
+
+    async fn get_balance(&self) -> Result<Decimal> {
+        Ok(self.balance())
+    }
+
+    fn fees(&self) -> FeeModel {
+        FeeModel::new(&self.fees)
+    }
+
+    fn partial_fill_policy(&self) -> PartialFillPolicy {
+        self.partial_fill
+    }
+}
+
+/// Take up to `size` shares from the asks priced at or below `limit`,
+/// removing them from the book. Returns the shares taken and their cost.
+fn take_asks(
+    state: &mut FakeState,
+    token_id: &str,
+    limit: Decimal,
+    size: Decimal,
+) -> (Decimal, Decimal) {
+    let Some(current) = state.books.get(token_id) else {
+        return (Decimal::ZERO, Decimal::ZERO);
+    };
+    let mut remaining = size;
+    let mut cost = Decimal::ZERO;
+    let mut asks = Vec::with_capacity(current.asks.len());
+    for level in &current.asks {
+        let take = if level.price <= limit {
+            remaining.min(level.size)
+        } else {
+            Decimal::ZERO
+        };
+        remaining -= take;
+        cost += take * level.price;
+        if level.size > take {
+            asks.push(PriceLevel {
+                price: level.price,
+                size: level.size - take,
+            });
+        }
+    }
+    let updated = book(token_id, current.bids.clone(), asks);
+    state.books.insert(token_id.to_string(), updated);
+    (size - remaining, cost)
+}
+
+/// A snapshot summarizing `bids` and `asks` the way the CLOB client does.
+fn book(token_id: &str, bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) -> OrderBookSnapshot {
+    let best_bid = bids.first().map_or(Decimal::ZERO, |b| b.price);
+    let best_ask = asks.first().map_or(Decimal::ONE, |a| a.price);
+    let midpoint = (best_bid + best_ask) / Decimal::TWO;
+    OrderBookSnapshot {
+        token_id: token_id.to_string(),
+        liquidity: BookLiquidity::of(&bids, &asks),
+        bids,
+        asks,
+        spread: best_ask - best_bid,
+        midpoint,
+        implied_probability: midpoint,
+        timestamp: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::{MarketCategory, TokenInfo};
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, size: Decimal) -> PriceLevel {
+        PriceLevel { price, size }
+    }
+
+    #[tokio::test]
+    async fn test_orders_fill_against_the_book() {
+        let exchange = FakeExchange::new(dec!(100));
+        exchange.set_book(
+            "tok",
+            vec![level(dec!(0.48), dec!(50))],
+            vec![level(dec!(0.50), dec!(10)), level(dec!(0.55), dec!(10))],
+        );
+
+        // Only the level at or below the limit is taken
+        let placed = exchange
+            .place_limit_order("tok", Side::Yes, dec!(0.52), dec!(15))
+            .await
+            .unwrap();
+        assert_eq!(placed.filled_size, dec!(10));
+        assert_eq!(placed.fill_price, dec!(0.50));
+        assert_eq!(exchange.balance(), dec!(95));
+
+        let book = exchange.get_order_book("tok").await.unwrap();
+        assert_eq!(book.asks.len(), 1);
+        assert_eq!(book.asks[0].price, dec!(0.55));
+        assert_eq!(book.midpoint, dec!(0.515));
+
+        let cancelled = exchange.cancel_all_orders().await.unwrap();
+        assert_eq!(cancelled, vec![placed.order_id]);
+        assert!(exchange.orders()[0].cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_resolution_pays_winning_shares() {
+        let exchange = FakeExchange::new(dec!(100));
+        exchange.add_market(Market {
+            condition_id: "mkt".to_string(),
+            question: "Will it rain?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            tokens: vec![
+                TokenInfo {
+                    token_id: "yes".to_string(),
+                    outcome: "Yes".to_string(),
+                    price: dec!(0.40),
+                },
+                TokenInfo {
+                    token_id: "no".to_string(),
+                    outcome: "No".to_string(),
+                    price: dec!(0.60),
+                },
+            ],
+            end_date: Utc::now(),
+            category: MarketCategory::Weather,
+            volume_24h: dec!(10000),
+            active: true,
+            event_id: None,
+            event_slug: None,
+            neg_risk: false,
+            description: String::new(),
+            resolution_source: None,
+        });
+        exchange.set_book(
+            "yes",
+            vec![level(dec!(0.38), dec!(50))],
+            vec![level(dec!(0.40), dec!(50))],
+        );
+        assert_eq!(
+            exchange.find_token_id("mkt", Side::No).await.as_deref(),
+            Some("no")
+        );
+        assert_eq!(
+            exchange.get_current_yes_price("mkt").await.unwrap(),
+            dec!(0.39)
+        );
+
+        exchange
+            .place_limit_order("yes", Side::Yes, dec!(0.40), dec!(25))
+            .await
+            .unwrap();
+        assert_eq!(exchange.holdings("yes"), dec!(25));
+        assert_eq!(exchange.market_resolution("mkt").await.unwrap(), None);
+
+        exchange.resolve_market("mkt", true);
+        assert_eq!(exchange.market_resolution("mkt").await.unwrap(), Some(true));
+        assert_eq!(exchange.holdings("yes"), dec!(0));
+        // 100 - 25 × 0.40 + 25 × $1
+        assert_eq!(exchange.balance(), dec!(115));
+        assert!(exchange
+            .get_markets(&MarketFilters {
+                min_volume_24h: Decimal::ZERO,
+                max_resolution_days: 30,
+                max_markets: 10,
+                max_spread_pct: Decimal::ONE,
+            })
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scripted_fills() {
+        let exchange = FakeExchange::new(dec!(100));
+        exchange.script_fill(FakeFill::Shares(dec!(4)));
+        exchange.script_fill(FakeFill::Reject("market closed".to_string()));
+
+        // No book needed for a scripted fill
+        let placed = exchange
+            .place_limit_order("tok", Side::No, dec!(0.40), dec!(10))
+            .await
+            .unwrap();
+        assert_eq!(placed.filled_size, dec!(4));
+
+        let err = exchange
+            .place_limit_order("tok", Side::No, dec!(0.40), dec!(10))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("market closed"));
+
+        // Unscripted again, and there's no book to fill against
+        let placed = exchange
+            .place_limit_order("tok", Side::No, dec!(0.40), dec!(10))
+            .await
+            .unwrap();
+        assert_eq!(placed.filled_size, dec!(0));
+        assert_eq!(exchange.orders().len(), 2);
+    }
+}
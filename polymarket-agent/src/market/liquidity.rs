@@ -0,0 +1,262 @@
+//! Liquidity scoring from order book history.
+//!
+//! A tight spread at scan time can be a one-off. With
+//! `[scanning.liquidity]` enabled, the scanner records every candidate's
+//! book in `order_book_snapshots` and scores each market from its recent
+//! snapshots: how tight the spread is on average and how stable the resting
+//! depth is. Scores run from 0 (wide or erratic) to 1 (consistently tight
+//! and deep). Markets below `min_score` are queued behind the rest, and
+//! positions are scaled down toward `min_size_factor` as the score falls.
+//! Markets with fewer than `min_snapshots` snapshots are left unscored.
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::db::store::OrderBookSnapshotRecord;
+use crate::market::models::{Market, PriceLevel};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LiquidityConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Snapshots older than this are ignored.
+    #[serde(default = "default_lookback_hours")]
+    pub lookback_hours: u64,
+    /// Snapshots needed before a market is scored.
+    #[serde(default = "default_min_snapshots")]
+    pub min_snapshots: usize,
+    /// Price levels kept per side of each recorded snapshot.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+    /// Average spread that scores zero on the spread half of the score.
+    #[serde(default = "default_max_spread")]
+    pub max_spread: Decimal,
+    /// Markets scoring below this are queued behind the rest.
+    #[serde(default = "default_min_score")]
+    pub min_score: Decimal,
+    /// Position size multiplier at a score of zero; a score of 1 sizes in
+    /// full.
+    #[serde(default = "default_min_size_factor")]
+    pub min_size_factor: Decimal,
+}
+
+impl Default for LiquidityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lookback_hours: default_lookback_hours(),
+            min_snapshots: default_min_snapshots(),
+            depth: default_depth(),
+            max_spread: default_max_spread(),
+            min_score: default_min_score(),
+            min_size_factor: default_min_size_factor(),
+        }
+    }
+}
+
+impl LiquidityConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.max_spread <= Decimal::ZERO {
+            anyhow::bail!("scanning.liquidity.max_spread must be positive");
+        }
+        if self.min_size_factor < Decimal::ZERO || self.min_size_factor > Decimal::ONE {
+            anyhow::bail!("scanning.liquidity.min_size_factor must be between 0 and 1");
+        }
+        Ok(())
+    }
+}
+
+fn default_lookback_hours() -> u64 {
+    24
+}
+
+fn default_min_snapshots() -> usize {
+    3
+}
+
+fn default_depth() -> usize {
+    5
+}
+
+fn default_max_spread() -> Decimal {
+    dec!(0.10)
+}
+
+fn default_min_score() -> Decimal {
+    dec!(0.4)
+}
+
+fn default_min_size_factor() -> Decimal {
+    dec!(0.5)
+}
+
+/// Score a market from its snapshots: the mean of a spread score (1 at a
+/// zero average spread, 0 at `max_spread` or wider) and a depth stability
+/// score (1 minus the mean absolute deviation of resting depth, in USD,
+/// relative to its mean).
+pub fn score(snapshots: &[OrderBookSnapshotRecord], config: &LiquidityConfig) -> Option<Decimal> {
+    if snapshots.is_empty() || snapshots.len() < config.min_snapshots {
+        return None;
+    }
+    let count = Decimal::from(snapshots.len());
+    let mut spreads = Decimal::ZERO;
+    let mut depths = Vec::with_capacity(snapshots.len());
+    for snapshot in snapshots {
+        spreads += snapshot.spread.parse::<Decimal>().unwrap_or(Decimal::ONE);
+        depths.push(resting_depth(&snapshot.bids) + resting_depth(&snapshot.asks));
+    }
+    let spread_score = Decimal::ONE - (spreads / count / config.max_spread).min(Decimal::ONE);
+
+    let mean_depth = depths.iter().sum::<Decimal>() / count;
+    let stability = if mean_depth.is_zero() {
+        Decimal::ZERO
+    } else {
+        let deviation = depths
+            .iter()
+            .map(|d| (d - mean_depth).abs())
+            .sum::<Decimal>()
+            / count;
+        Decimal::ONE - (deviation / mean_depth).min(Decimal::ONE)
+    };
+    Some(((spread_score + stability) / dec!(2)).round_dp(4))
+}
+
+/// Notional resting on one side of a stored snapshot.
+fn resting_depth(levels_json: &str) -> Decimal {
+    serde_json::from_str::<Vec<PriceLevel>>(levels_json)
+        .unwrap_or_default()
+        .iter()
+        .map(|l| l.price * l.size)
+        .sum()
+}
+
+/// Score every market with enough snapshots in `snapshots`.
+pub fn scores(
+    snapshots: Vec<OrderBookSnapshotRecord>,
+    config: &LiquidityConfig,
+) -> HashMap<String, Decimal> {
+    let mut by_market: HashMap<String, Vec<OrderBookSnapshotRecord>> = HashMap::new();
+    for snapshot in snapshots {
+        by_market
+            .entry(snapshot.market_id.clone())
+            .or_default()
+            .push(snapshot);
+    }
+    by_market
+        .into_iter()
+        .filter_map(|(market_id, snapshots)| Some((market_id, score(&snapshots, config)?)))
+        .collect()
+}
+
+/// Move markets scoring below `min_score` to the back, best score first;
+/// unscored markets keep their place.
+pub fn order_by_liquidity(
+    markets: Vec<Market>,
+    scores: &HashMap<String, Decimal>,
+    min_score: Decimal,
+) -> Vec<Market> {
+    let (mut illiquid, mut liquid): (Vec<Market>, Vec<Market>) = markets
+        .into_iter()
+        .partition(|m| scores.get(&m.condition_id).is_some_and(|s| *s < min_score));
+    if !illiquid.is_empty() {
+        debug!(
+            deprioritized = illiquid.len(),
+            "Markets with a poor liquidity history queued last"
+        );
+    }
+    illiquid.sort_by(|a, b| scores[&b.condition_id].cmp(&scores[&a.condition_id]));
+    liquid.append(&mut illiquid);
+    liquid
+}
+
+/// Position size multiplier for a market scoring `score`.
+pub fn size_factor(score: Decimal, config: &LiquidityConfig) -> Decimal {
+    let score = score.clamp(Decimal::ZERO, Decimal::ONE);
+    config.min_size_factor + (Decimal::ONE - config.min_size_factor) * score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+    use chrono::Utc;
+
+    fn snapshot(market_id: &str, spread: Decimal, size: Decimal) -> OrderBookSnapshotRecord {
+        let levels = serde_json::to_string(&[PriceLevel {
+            price: dec!(0.5),
+            size,
+        }])
+        .unwrap();
+        OrderBookSnapshotRecord {
+            id: 0,
+            market_id: market_id.to_string(),
+            token_id: format!("{market_id}-yes"),
+            best_bid: "0.49".to_string(),
+            best_ask: "0.51".to_string(),
+            midpoint: "0.5".to_string(),
+            spread: spread.to_string(),
+            bids: levels.clone(),
+            asks: levels,
+            captured_at: None,
+        }
+    }
+
+    fn market(id: &str) -> Market {
+        Market {
+            condition_id: id.to_string(),
+            question: format!("Market {id}?"),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            tokens: Vec::new(),
+            end_date: Utc::now(),
+            category: MarketCategory::Crypto,
+            volume_24h: dec!(10000),
+            active: true,
+            event_id: None,
+            event_slug: None,
+            neg_risk: false,
+            description: String::new(),
+            resolution_source: None,
+        }
+    }
+
+    #[test]
+    fn test_consistent_liquidity_beats_a_momentary_tight_spread() {
+        let config = LiquidityConfig::default();
+        let steady: Vec<_> = (0..4)
+            .map(|_| snapshot("steady", dec!(0.02), dec!(1000)))
+            .collect();
+        // Tight right now, but usually wide and thin
+        let flaky = vec![
+            snapshot("flaky", dec!(0.12), dec!(50)),
+            snapshot("flaky", dec!(0.10), dec!(20)),
+            snapshot("flaky", dec!(0.15), dec!(40)),
+            snapshot("flaky", dec!(0.01), dec!(900)),
+        ];
+
+        assert_eq!(score(&steady, &config), Some(dec!(0.9)));
+        let flaky_score = score(&flaky, &config).unwrap();
+        assert!(flaky_score < config.min_score, "{flaky_score}");
+        assert_eq!(score(&steady[..2], &config), None);
+
+        let mut history = steady;
+        history.extend(flaky);
+        let scores = scores(history, &config);
+        let ordered: Vec<String> = order_by_liquidity(
+            vec![market("flaky"), market("new"), market("steady")],
+            &scores,
+            config.min_score,
+        )
+        .into_iter()
+        .map(|m| m.condition_id)
+        .collect();
+        assert_eq!(ordered, ["new", "steady", "flaky"]);
+
+        assert_eq!(size_factor(Decimal::ONE, &config), Decimal::ONE);
+        assert_eq!(size_factor(Decimal::ZERO, &config), dec!(0.5));
+        assert_eq!(size_factor(dec!(0.9), &config), dec!(0.95));
+    }
+}
@@ -0,0 +1,156 @@
+//! New-listing detection.
+//!
+//! Freshly listed markets are often mispriced until traders find them. The
+//! scanner records when it first sees each market; those first seen within
+//! `max_age_hours` are flagged as new listings and get up to
+//! `evaluation_quota` valuations per cycle of their own, on top of the
+//! regular evaluation budget. Trades in them carry [`NEW_LISTING_TAG`], so
+//! tag-filtered metrics (and the backtest's per-listing breakdown) show
+//! whether new listings actually pay.
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::market::models::MarketCandidate;
+
+/// Tag on trades entered in a new listing.
+pub const NEW_LISTING_TAG: &str = "new listing";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewListingsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Markets first seen within this many hours count as new.
+    #[serde(default = "default_max_age_hours")]
+    pub max_age_hours: u64,
+    /// Valuations per cycle reserved for new listings.
+    #[serde(default = "default_evaluation_quota")]
+    pub evaluation_quota: usize,
+}
+
+impl Default for NewListingsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_age_hours: default_max_age_hours(),
+            evaluation_quota: default_evaluation_quota(),
+        }
+    }
+}
+
+fn default_max_age_hours() -> u64 {
+    24
+}
+
+fn default_evaluation_quota() -> usize {
+    3
+}
+
+/// Candidates to value this cycle, in scan order: up to `quota` new
+/// listings plus up to `max_evaluations` other markets.
+pub fn select_for_evaluation<'a>(
+    candidates: impl IntoIterator<Item = &'a MarketCandidate>,
+    max_evaluations: usize,
+    quota: usize,
+) -> Vec<&'a MarketCandidate> {
+    let (mut listings, mut regular) = (0, 0);
+    candidates
+        .into_iter()
+        .filter(|c| {
+            let taken = if c.new_listing && listings < quota {
+                &mut listings
+            } else if regular < max_evaluations {
+                &mut regular
+            } else {
+                return false;
+            };
+            *taken += 1;
+            true
+        })
+        .collect()
+}
+
+/// Whether a market first seen `first_seen_hours_ago` still counts as new.
+pub fn is_new(first_seen_hours_ago: Decimal, config: &NewListingsConfig) -> bool {
+    config.enabled && first_seen_hours_ago <= Decimal::from(config.max_age_hours)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::{BookLiquidity, Market, MarketCategory, OrderBookSnapshot};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn candidate(id: &str, new_listing: bool) -> MarketCandidate {
+        MarketCandidate {
+            market: Market {
+                condition_id: id.to_string(),
+                question: format!("Market {id}?"),
+                outcomes: vec!["Yes".to_string(), "No".to_string()],
+                tokens: Vec::new(),
+                end_date: Utc::now(),
+                category: MarketCategory::Crypto,
+                volume_24h: dec!(10000),
+                active: true,
+                event_id: None,
+                event_slug: None,
+                neg_risk: false,
+                description: String::new(),
+                resolution_source: None,
+            },
+            order_book: OrderBookSnapshot {
+                token_id: format!("{id}-yes"),
+                bids: Vec::new(),
+                asks: Vec::new(),
+                spread: dec!(0.02),
+                midpoint: dec!(0.5),
+                implied_probability: dec!(0.5),
+                timestamp: Utc::now(),
+                liquidity: BookLiquidity::TwoSided,
+            },
+            new_listing,
+            liquidity_score: None,
+        }
+    }
+
+    #[test]
+    fn test_new_listings_have_their_own_quota() {
+        let candidates = [
+            candidate("a", false),
+            candidate("b", false),
+            candidate("c", true),
+            candidate("d", false),
+            candidate("e", true),
+            candidate("f", true),
+        ];
+        let ids = |selected: Vec<&MarketCandidate>| {
+            selected
+                .into_iter()
+                .map(|c| c.market.condition_id.clone())
+                .collect::<Vec<_>>()
+        };
+        // Two regular slots, two for new listings; order is kept
+        assert_eq!(
+            ids(select_for_evaluation(&candidates, 2, 2)),
+            ["a", "b", "c", "e"]
+        );
+        // Past its quota a new listing competes for a regular slot
+        assert_eq!(
+            ids(select_for_evaluation(&candidates, 3, 1)),
+            ["a", "b", "c", "d"]
+        );
+        assert_eq!(ids(select_for_evaluation(&candidates, 1, 0)), ["a"]);
+    }
+
+    #[test]
+    fn test_is_new() {
+        let config = NewListingsConfig {
+            enabled: true,
+            ..NewListingsConfig::default()
+        };
+        assert!(is_new(dec!(3), &config));
+        assert!(!is_new(dec!(30), &config));
+        assert!(!is_new(dec!(3), &NewListingsConfig::default()));
+    }
+}
@@ -1,4 +1,13 @@
+pub mod api;
+pub mod book_cache;
 pub mod category;
+pub mod clarity;
+pub mod fake;
+pub mod liquidity;
+pub mod listings;
 pub mod models;
 pub mod polymarket;
+pub mod rate_limit;
+pub mod rules;
 pub mod scanner;
+pub mod watchlist;
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -13,6 +15,44 @@ pub struct Market {
     pub category: MarketCategory,
     pub volume_24h: Decimal,
     pub active: bool,
+    /// Gamma event grouping this market with related ones, if any.
+    #[serde(default)]
+    pub event_id: Option<String>,
+    #[serde(default)]
+    pub event_slug: Option<String>,
+    /// Part of a negative-risk event: its markets' outcomes are mutually
+    /// exclusive, so at most one resolves YES.
+    #[serde(default)]
+    pub neg_risk: bool,
+    /// Resolution criteria as written by the market creator.
+    #[serde(default)]
+    pub description: String,
+    /// Where the outcome is read from, usually a URL.
+    #[serde(default)]
+    pub resolution_source: Option<String>,
+}
+
+/// A Polymarket event: a group of related markets, such as every
+/// candidate's market in one election.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketEvent {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub neg_risk: bool,
+    /// Condition ids of the event's markets.
+    pub market_ids: Vec<String>,
+}
+
+/// Markets grouped by event id. Markets without an event are left out.
+pub fn group_by_event(markets: &[Market]) -> BTreeMap<&str, Vec<&Market>> {
+    let mut groups: BTreeMap<&str, Vec<&Market>> = BTreeMap::new();
+    for market in markets {
+        if let Some(event_id) = market.event_id.as_deref() {
+            groups.entry(event_id).or_default().push(market);
+        }
+    }
+    groups
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +73,30 @@ pub enum MarketCategory {
     Other(String),
 }
 
+impl MarketCategory {
+    /// Lowercase name, matching the serialized form used in config files.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Weather => "weather",
+            Self::Sports => "sports",
+            Self::Crypto => "crypto",
+            Self::Politics => "politics",
+            Self::Other(name) => name,
+        }
+    }
+
+    /// Inverse of [`as_str`](Self::as_str).
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "weather" => Self::Weather,
+            "sports" => Self::Sports,
+            "crypto" => Self::Crypto,
+            "politics" => Self::Politics,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
 /// Snapshot of an order book at a point in time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBookSnapshot {
@@ -43,6 +107,29 @@ pub struct OrderBookSnapshot {
     pub midpoint: Decimal,
     pub implied_probability: Decimal,
     pub timestamp: DateTime<Utc>,
+    /// Which sides of the book had orders. For anything but `TwoSided` the
+    /// midpoint comes from the CLOB midpoint or last-trade price instead.
+    #[serde(default)]
+    pub liquidity: BookLiquidity,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookLiquidity {
+    #[default]
+    TwoSided,
+    OneSided,
+    Empty,
+}
+
+impl BookLiquidity {
+    pub fn of(bids: &[PriceLevel], asks: &[PriceLevel]) -> Self {
+        match (bids.is_empty(), asks.is_empty()) {
+            (false, false) => Self::TwoSided,
+            (true, true) => Self::Empty,
+            _ => Self::OneSided,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +149,11 @@ pub struct PriceHistoryPoint {
 pub struct MarketCandidate {
     pub market: Market,
     pub order_book: OrderBookSnapshot,
+    /// First seen by the scanner recently enough to count as a new listing.
+    pub new_listing: bool,
+    /// Order book history score (see [`crate::market::liquidity`]); `None`
+    /// when scoring is off or the market has too little history.
+    pub liquidity_score: Option<Decimal>,
 }
 
 /// A fully evaluated trading opportunity.
@@ -74,14 +166,25 @@ pub struct Opportunity {
     pub edge: Decimal,
     pub recommended_side: Side,
     pub kelly_size: Decimal,
+    /// The market is a new listing; its trade is tagged as such.
+    pub new_listing: bool,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Side {
     Yes,
     No,
 }
 
+impl Side {
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Yes => Self::No,
+            Self::No => Self::Yes,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AgentState {
     Alive,
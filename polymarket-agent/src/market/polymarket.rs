@@ -3,7 +3,7 @@
 //! Wraps `polymarket-client-sdk` with rate limiting, paper trading,
 //! retry logic, authenticated live trading, and domain type conversion.
 
-use std::num::NonZeroU32;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -12,13 +12,11 @@ use alloy::signers::local::LocalSigner;
 use alloy::signers::Signer;
 use anyhow::{bail, Context, Result};
 use chrono::{DateTime, TimeDelta, Utc};
-use governor::clock::DefaultClock;
-use governor::state::{InMemoryState, NotKeyed};
-use governor::{Quota, RateLimiter};
 use polymarket_client_sdk::auth::state::Authenticated;
 use polymarket_client_sdk::auth::Normal;
 use polymarket_client_sdk::clob::types::request::{
-    BalanceAllowanceRequest, OrderBookSummaryRequest, PriceHistoryRequest,
+    BalanceAllowanceRequest, LastTradePriceRequest, MidpointRequest, OrderBookSummaryRequest,
+    PriceHistoryRequest, TradesRequest,
 };
 use polymarket_client_sdk::clob::types::response::OrderBookSummaryResponse;
 use polymarket_client_sdk::clob::types::{Interval, OrderType, Side as ClobSide, TimeRange};
@@ -30,14 +28,25 @@ use rust_decimal_macros::dec;
 use serde::Deserialize;
 use std::str::FromStr;
 use tokio::sync::Mutex;
-use tracing::{info, instrument, warn};
-
-use crate::config::{AgentMode, AppConfig, RateLimitConfig, Secrets};
+use tracing::{debug, info, instrument, warn};
+
+use crate::api_error::{self, ApiError, RetryClass};
+use crate::chaos::{ChaosInjector, ChaosTarget};
+use crate::config::{AgentMode, AppConfig, Secrets};
+use crate::execution::chain::TxReceipt;
+use crate::execution::fees::{FeeModel, Liquidity};
+use crate::execution::order::PartialFillPolicy;
+use crate::execution::paper_matching;
+use crate::execution::price_feed::PriceFeed;
+use crate::execution::redemption;
+use crate::execution::wallet::{self, WalletStatus};
+use crate::http_cache::{CachedResponse, HttpCache, HttpCacheStats};
+use crate::market::book_cache::{BookCache, BookCacheStats};
 use crate::market::models::{
-    Market, OrderBookSnapshot, PriceHistoryPoint, PriceLevel, Side, TokenInfo,
+    BookLiquidity, Market, MarketEvent, OrderBookSnapshot, PriceHistoryPoint, PriceLevel, Side,
+    TokenInfo,
 };
-
-type Limiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+use crate::market::rate_limit::{ApiFamily, RateLimitStats, RateLimiters};
 
 #[derive(Debug)]
 pub struct MarketFilters {
@@ -47,14 +56,56 @@ pub struct MarketFilters {
     pub max_spread_pct: Decimal,
 }
 
-/// Paper trading simulated position.
+/// Share inventory held for one outcome token in paper mode.
+///
+/// YES and NO tokens are separate books, so each keeps its own inventory
+/// and average cost.
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct PaperPosition {
+pub struct PaperHolding {
     pub token_id: String,
     pub side: Side,
-    pub size: Decimal,
-    pub entry_price: Decimal,
+    pub shares: Decimal,
+    pub avg_entry_price: Decimal,
+}
+
+/// What the exchange actually filled for one order.
+#[derive(Debug, Clone)]
+pub struct OrderFill {
+    pub order_id: String,
+    /// Raw CLOB status (`live`, `matched`, `canceled`, ...).
+    pub status: String,
+    pub size_matched: Decimal,
+    /// Size-weighted price of the order's trades; the limit price if none
+    /// are reported.
+    pub avg_price: Decimal,
+}
+
+impl OrderFill {
+    /// Whether the order can still fill more.
+    pub fn is_resting(&self) -> bool {
+        self.status.eq_ignore_ascii_case("live") || self.status.eq_ignore_ascii_case("delayed")
+    }
+}
+
+/// What a limit order filled on submission.
+#[derive(Debug, Clone)]
+pub struct PlacedOrder {
+    pub order_id: String,
+    /// Shares matched at submission. May be less than the order size.
+    pub filled_size: Decimal,
+    /// Average price of the matched shares.
+    pub fill_price: Decimal,
+}
+
+/// Paper account valued at current marks.
+#[derive(Debug, Clone)]
+pub struct PaperValuation {
+    pub cash: Decimal,
+    pub position_value: Decimal,
+    pub cost_basis: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub realized_pnl: Decimal,
+    pub equity: Decimal,
 }
 
 /// Paper trading simulated order.
@@ -69,6 +120,11 @@ struct PaperOrder {
     pub filled: bool,
     /// Whether this order was filled with adverse selection (price moved against us).
     pub adverse_selection: bool,
+    /// Shares matched so far.
+    pub matched: Decimal,
+    /// Still working on the book, waiting for the market to trade through it.
+    pub resting: bool,
+    pub placed_at: DateTime<Utc>,
 }
 
 /// Result of a paper trading fill simulation.
@@ -84,8 +140,157 @@ pub struct PaperFillResult {
 /// Tracks simulated state for paper trading.
 struct PaperTradingState {
     balance: Decimal,
-    positions: Vec<PaperPosition>,
+    holdings: HashMap<String, PaperHolding>,
+    realized_pnl: Decimal,
     order_history: Vec<PaperOrder>,
+    /// Final fills of resting orders that stopped working, awaiting
+    /// reconciliation.
+    finished: Vec<OrderFill>,
+}
+
+impl PaperTradingState {
+    fn new(balance: Decimal) -> Self {
+        Self {
+            balance,
+            holdings: HashMap::new(),
+            realized_pnl: Decimal::ZERO,
+            order_history: Vec::new(),
+            finished: Vec::new(),
+        }
+    }
+
+    /// Cancel an unfinished order. A resting order queues its final fill.
+    fn cancel(&mut self, index: usize) {
+        if self.order_history[index].resting {
+            self.finish_resting(index, "canceled");
+        } else {
+            self.order_history[index].filled = true;
+        }
+    }
+
+    /// Take a resting order off the book and queue its final fill.
+    fn finish_resting(&mut self, index: usize, status: &str) {
+        let order = &mut self.order_history[index];
+        order.resting = false;
+        order.filled = true;
+        self.finished.push(OrderFill {
+            order_id: order.order_id.clone(),
+            status: status.to_string(),
+            size_matched: order.matched,
+            avg_price: order.price,
+        });
+    }
+
+    /// Buy `shares` of a token, debiting cash plus `fee` and updating
+    /// average cost. The fee is booked as realized P&L, not cost basis.
+    fn buy(
+        &mut self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        shares: Decimal,
+        fee: Decimal,
+    ) -> Result<()> {
+        let cost = price * shares;
+        if cost + fee > self.balance {
+            bail!(
+                "Insufficient paper balance: {} < cost {} + fee {}",
+                self.balance,
+                cost,
+                fee
+            );
+        }
+
+        self.balance -= cost + fee;
+        self.realized_pnl -= fee;
+        let holding = self
+            .holdings
+            .entry(token_id.to_string())
+            .or_insert_with(|| PaperHolding {
+                token_id: token_id.to_string(),
+                side,
+                shares: Decimal::ZERO,
+                avg_entry_price: Decimal::ZERO,
+            });
+        let total_shares = holding.shares + shares;
+        holding.avg_entry_price = (holding.avg_entry_price * holding.shares + cost) / total_shares;
+        holding.shares = total_shares;
+        Ok(())
+    }
+
+    /// Sell up to `shares` of a token back to the book. Returns shares sold.
+    fn sell(&mut self, token_id: &str, price: Decimal, shares: Decimal) -> Result<Decimal> {
+        let Some(holding) = self.holdings.get_mut(token_id) else {
+            bail!("No paper inventory for token {token_id}");
+        };
+
+        let sold = shares.min(holding.shares);
+        self.balance += price * sold;
+        self.realized_pnl += (price - holding.avg_entry_price) * sold;
+        holding.shares -= sold;
+
+        if holding.shares <= Decimal::ZERO {
+            self.holdings.remove(token_id);
+        }
+        Ok(sold)
+    }
+
+    /// Merge matched YES/NO shares into $1 of cash per pair. Returns pairs merged.
+    fn merge(&mut self, yes_token: &str, no_token: &str, shares: Decimal) -> Result<Decimal> {
+        let (Some(yes), Some(no)) = (self.holdings.get(yes_token), self.holdings.get(no_token))
+        else {
+            bail!("Paper inventory missing for merge of {yes_token}/{no_token}");
+        };
+        let merged = shares.min(yes.shares).min(no.shares);
+        let pair_cost = yes.avg_entry_price + no.avg_entry_price;
+
+        self.balance += merged;
+        self.realized_pnl += (Decimal::ONE - pair_cost) * merged;
+        for token_id in [yes_token, no_token] {
+            if let Some(holding) = self.holdings.get_mut(token_id) {
+                holding.shares -= merged;
+                if holding.shares <= Decimal::ZERO {
+                    self.holdings.remove(token_id);
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Pay out a resolved token's inventory at `payout` per share ($1 if it
+    /// won, $0 if it lost) and drop the holding. Returns shares settled.
+    fn settle(&mut self, token_id: &str, payout: Decimal) -> Decimal {
+        let Some(holding) = self.holdings.remove(token_id) else {
+            return Decimal::ZERO;
+        };
+        self.balance += payout * holding.shares;
+        self.realized_pnl += (payout - holding.avg_entry_price) * holding.shares;
+        holding.shares
+    }
+
+    /// Value the account using `marks` (token_id → price). Tokens without a
+    /// mark are carried at cost.
+    fn mark_to_market(&self, marks: &HashMap<String, Decimal>) -> PaperValuation {
+        let mut position_value = Decimal::ZERO;
+        let mut cost_basis = Decimal::ZERO;
+        for holding in self.holdings.values() {
+            let mark = marks
+                .get(&holding.token_id)
+                .copied()
+                .unwrap_or(holding.avg_entry_price);
+            position_value += mark * holding.shares;
+            cost_basis += holding.avg_entry_price * holding.shares;
+        }
+
+        PaperValuation {
+            cash: self.balance,
+            position_value,
+            cost_basis,
+            unrealized_pnl: position_value - cost_basis,
+            realized_pnl: self.realized_pnl,
+            equity: self.balance + position_value,
+        }
+    }
 }
 
 /// Authenticated CLOB client for live trading.
@@ -104,10 +309,18 @@ pub struct PolymarketClient {
     http: reqwest::Client,
     /// Gamma API base URL
     gamma_base_url: String,
-    /// Rate limiter
-    limiter: Arc<Limiter>,
+    /// Rate limiters, one per API family
+    limiters: Arc<RateLimiters>,
+    /// ETag/Last-Modified cache for Gamma reads, shared with data sources
+    cache: Arc<HttpCache>,
     /// Paper trading state (only in Paper/Backtest mode)
     paper_state: Option<Mutex<PaperTradingState>>,
+    /// Fault injection (paper/backtest chaos testing)
+    chaos: Arc<ChaosInjector>,
+    /// USD prices for gas and non-USDC balances
+    price_feed: Arc<PriceFeed>,
+    /// Recently fetched order books, shared by every reader
+    books: BookCache,
 }
 
 impl PolymarketClient {
@@ -129,7 +342,15 @@ impl PolymarketClient {
             .trim_end_matches('/')
             .to_string();
 
-        let limiter = create_rate_limiter(&config.rate_limit);
+        let limiters = Arc::new(RateLimiters::new(&config.rate_limit));
+        let cache = Arc::new(HttpCache::new(&config.http_cache));
+        let chaos = Arc::new(ChaosInjector::new(&config.chaos, config.agent.mode)?);
+        let price_feed = Arc::new(PriceFeed::new(
+            config.price_feed.clone(),
+            http.clone(),
+            config.redemption.rpc_url.clone(),
+        ));
+        let books = BookCache::new(&config.book_cache);
 
         // Initialize authenticated client for live trading mode
         let auth_client = match config.agent.mode {
@@ -159,11 +380,9 @@ impl PolymarketClient {
         };
 
         let paper_state = match config.agent.mode {
-            AgentMode::Paper | AgentMode::Backtest => Some(Mutex::new(PaperTradingState {
-                balance: config.agent.initial_paper_balance,
-                positions: Vec::new(),
-                order_history: Vec::new(),
-            })),
+            AgentMode::Paper | AgentMode::Backtest | AgentMode::Simulation => Some(Mutex::new(
+                PaperTradingState::new(config.agent.initial_paper_balance),
+            )),
             _ => None,
         };
 
@@ -173,8 +392,12 @@ impl PolymarketClient {
             auth_client,
             http,
             gamma_base_url,
-            limiter,
+            limiters,
+            cache,
             paper_state,
+            chaos,
+            price_feed,
+            books,
         })
     }
 
@@ -193,43 +416,30 @@ impl PolymarketClient {
         let max_end_date = now + chrono::Duration::days(filters.max_resolution_days as i64);
 
         loop {
-            self.rate_limit().await;
+            self.rate_limit(ApiFamily::Gamma).await;
 
             let url = format!("{}/markets", self.gamma_base_url);
 
             let gamma_markets: Vec<GammaMarketResponse> = self
-                .with_retry(|| {
+                .with_retry(ChaosTarget::Gamma, || {
                     let url = url.clone();
                     let end_min = now.to_rfc3339();
                     let end_max = max_end_date.to_rfc3339();
                     let vol_min = filters.min_volume_24h.to_string();
                     async move {
-                        let resp = self
-                            .http
-                            .get(&url)
-                            .query(&[
-                                ("limit", limit.to_string()),
-                                ("offset", offset.to_string()),
-                                ("closed", "false".to_string()),
-                                ("end_date_min", end_min),
-                                ("end_date_max", end_max),
-                                ("volume_num_min", vol_min),
-                                ("order", "volume".to_string()),
-                                ("ascending", "false".to_string()),
-                            ])
-                            .send()
-                            .await
-                            .map_err(|e| anyhow::anyhow!("HTTP error: {e}"))?;
-
-                        if !resp.status().is_success() {
-                            let status = resp.status();
-                            let body = resp.text().await.unwrap_or_default();
-                            return Err(anyhow::anyhow!("Gamma API {status}: {body}"));
-                        }
-
+                        let request = self.http.get(&url).query(&[
+                            ("limit", limit.to_string()),
+                            ("offset", offset.to_string()),
+                            ("closed", "false".to_string()),
+                            ("end_date_min", end_min),
+                            ("end_date_max", end_max),
+                            ("volume_num_min", vol_min),
+                            ("order", "volume".to_string()),
+                            ("ascending", "false".to_string()),
+                            ("include_tag", "true".to_string()),
+                        ]);
+                        let resp = self.send_gamma(request).await?;
                         resp.json::<Vec<GammaMarketResponse>>()
-                            .await
-                            .map_err(|e| anyhow::anyhow!("Deserialization error: {e}"))
                     }
                 })
                 .await
@@ -258,16 +468,87 @@ impl PolymarketClient {
 
         all_markets.truncate(filters.max_markets);
 
+        // Older Gamma responses omit the embedded event; look those up
+        if all_markets.iter().any(|m| m.event_id.is_none()) {
+            match self.get_events(filters).await {
+                Ok(events) => attach_events(&mut all_markets, &events),
+                Err(e) => warn!(error = %e, "Failed to fetch Gamma events; markets left ungrouped"),
+            }
+        }
+
         info!(count = all_markets.len(), "Markets fetched from Gamma API");
         Ok(all_markets)
     }
 
+    /// Fetch open events resolving within the filter window, with the
+    /// condition ids of their markets. At most `max_markets` events.
+    pub async fn get_events(&self, filters: &MarketFilters) -> Result<Vec<MarketEvent>> {
+        let mut all_events = Vec::new();
+        let mut offset = 0u32;
+        let limit = 100u32;
+
+        let now = Utc::now();
+        let max_end_date = now + chrono::Duration::days(filters.max_resolution_days as i64);
+
+        loop {
+            self.rate_limit(ApiFamily::Gamma).await;
+
+            let url = format!("{}/events", self.gamma_base_url);
+
+            let gamma_events: Vec<GammaEventResponse> = self
+                .with_retry(ChaosTarget::Gamma, || {
+                    let url = url.clone();
+                    let end_min = now.to_rfc3339();
+                    let end_max = max_end_date.to_rfc3339();
+                    async move {
+                        let request = self.http.get(&url).query(&[
+                            ("limit", limit.to_string()),
+                            ("offset", offset.to_string()),
+                            ("closed", "false".to_string()),
+                            ("end_date_min", end_min),
+                            ("end_date_max", end_max),
+                            ("order", "volume".to_string()),
+                            ("ascending", "false".to_string()),
+                        ]);
+                        let resp = self.send_gamma(request).await?;
+                        resp.json::<Vec<GammaEventResponse>>()
+                    }
+                })
+                .await
+                .context("Failed to fetch events from Gamma API")?;
+
+            let page_count = gamma_events.len();
+            all_events.extend(gamma_events.iter().filter_map(convert_gamma_event));
+
+            offset += limit;
+
+            if all_events.len() >= filters.max_markets || (page_count as u32) < limit {
+                break;
+            }
+        }
+
+        all_events.truncate(filters.max_markets);
+
+        info!(count = all_events.len(), "Events fetched from Gamma API");
+        Ok(all_events)
+    }
+
     // === Order Book (via CLOB API) ===
 
-    /// Get order book for a specific token.
-    #[instrument(skip(self), fields(token_id = %token_id))]
+    /// Get order book for a specific token, from the book cache if it was
+    /// fetched within the TTL.
     pub async fn get_order_book(&self, token_id: &str) -> Result<OrderBookSnapshot> {
-        self.rate_limit().await;
+        match self.books.get(token_id) {
+            Some(book) => Ok(book),
+            None => self.fetch_order_book(token_id).await,
+        }
+    }
+
+    /// Fetch a token's order book from the CLOB, bypassing the book cache,
+    /// and store it there for other readers.
+    #[instrument(skip(self), fields(token_id = %token_id))]
+    pub async fn fetch_order_book(&self, token_id: &str) -> Result<OrderBookSnapshot> {
+        self.rate_limit(ApiFamily::ClobRead).await;
 
         let token_u256 = parse_token_id(token_id)?;
 
@@ -276,19 +557,53 @@ impl PolymarketClient {
             .build();
 
         let response: OrderBookSummaryResponse = self
-            .with_retry(|| {
+            .with_retry(ChaosTarget::Clob, || {
                 let req = &request;
                 async move {
                     self.clob
                         .order_book(req)
                         .await
-                        .map_err(|e| anyhow::anyhow!("{e}"))
+                        .map_err(clob_error("Order book request failed"))
                 }
             })
             .await
             .context("Failed to fetch order book")?;
 
-        Ok(convert_order_book(token_id, &response))
+        let mut book = convert_order_book(token_id, &response);
+        if book.liquidity != BookLiquidity::TwoSided {
+            // The book's own midpoint and spread are meaningless without both sides
+            let price = self.fallback_price(token_id, token_u256).await;
+            reprice_one_sided(&mut book, price);
+        }
+        self.books.insert(&book);
+        Ok(book)
+    }
+
+    /// Price for a token whose book is empty or one-sided: the CLOB midpoint,
+    /// else the last trade price. `None` if neither gives a usable price.
+    async fn fallback_price(&self, token_id: &str, token_u256: U256) -> Option<Decimal> {
+        self.rate_limit(ApiFamily::ClobRead).await;
+        let request = MidpointRequest::builder().token_id(token_u256).build();
+        match self.clob.midpoint(&request).await {
+            Ok(resp) => {
+                if let Some(mid) = usable_fallback(resp.mid) {
+                    return Some(mid);
+                }
+            }
+            Err(e) => debug!(token_id, error = %e, "Midpoint fallback failed"),
+        }
+
+        self.rate_limit(ApiFamily::ClobRead).await;
+        let request = LastTradePriceRequest::builder()
+            .token_id(token_u256)
+            .build();
+        match self.clob.last_trade_price(&request).await {
+            Ok(resp) => usable_fallback(resp.price),
+            Err(e) => {
+                debug!(token_id, error = %e, "Last-trade-price fallback failed");
+                None
+            }
+        }
     }
 
     // === Price History ===
@@ -300,7 +615,7 @@ impl PolymarketClient {
         token_id: &str,
         interval: Interval,
     ) -> Result<Vec<PriceHistoryPoint>> {
-        self.rate_limit().await;
+        self.rate_limit(ApiFamily::ClobRead).await;
 
         let token_u256 = parse_token_id(token_id)?;
 
@@ -310,13 +625,13 @@ impl PolymarketClient {
             .build();
 
         let response: polymarket_client_sdk::clob::types::response::PriceHistoryResponse = self
-            .with_retry(|| {
+            .with_retry(ChaosTarget::Clob, || {
                 let req = &request;
                 async move {
                     self.clob
                         .price_history(req)
                         .await
-                        .map_err(|e| anyhow::anyhow!("{e}"))
+                        .map_err(clob_error("Price history request failed"))
                 }
             })
             .await
@@ -345,22 +660,91 @@ impl PolymarketClient {
         Ok(book.midpoint)
     }
 
+    /// Look up one market by condition_id on the Gamma API.
+    pub async fn get_market(&self, condition_id: &str) -> Result<Market> {
+        self.rate_limit(ApiFamily::Gamma).await;
+        self.chaos.inject(ChaosTarget::Gamma).await?;
+
+        let url = format!("{}/markets", self.gamma_base_url);
+        let markets: Vec<GammaMarketResponse> = self
+            .send_gamma(self.http.get(&url).query(&[("condition_id", condition_id)]))
+            .await
+            .context("HTTP request to Gamma API failed")?
+            .json()
+            .context("Failed to parse Gamma response")?;
+
+        markets
+            .first()
+            .and_then(convert_gamma_response)
+            .context("Market not found on Gamma")
+    }
+
     /// Get current YES price for a market by condition_id from Gamma API.
     /// Returns the first outcome price (YES) as a Decimal.
     /// This is a lightweight call for exit signal evaluation.
     pub async fn get_current_yes_price(&self, condition_id: &str) -> Result<Decimal> {
-        self.rate_limit().await;
+        Ok(self.get_price_and_end_date(condition_id).await?.0)
+    }
 
+    /// Find the token_id for a given market and side, e.g. to place an exit.
+    pub async fn find_token_id(&self, condition_id: &str, side: Side) -> Option<String> {
+        // Query the Gamma API to get market tokens
         let url = format!("{}/markets", self.gamma_base_url);
-        let markets: Vec<GammaMarketResponse> = self
+        let markets: Vec<serde_json::Value> = match self
             .http
             .get(&url)
             .query(&[("condition_id", condition_id)])
             .send()
             .await
+        {
+            Ok(resp) => match resp.json().await {
+                Ok(m) => m,
+                Err(_) => return None,
+            },
+            Err(_) => return None,
+        };
+
+        let market = markets.first()?;
+        let token_ids = market
+            .get("clobTokenIds")
+            .and_then(|v| v.as_str())
+            .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())?;
+
+        let outcomes = market
+            .get("outcomes")
+            .and_then(|v| v.as_str())
+            .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())?;
+
+        // Find the token index matching the side
+        let target_outcome = match side {
+            Side::Yes => "Yes",
+            Side::No => "No",
+        };
+
+        for (i, outcome) in outcomes.iter().enumerate() {
+            if outcome.eq_ignore_ascii_case(target_outcome) {
+                return token_ids.get(i).cloned();
+            }
+        }
+
+        // Fallback to first token
+        token_ids.first().cloned()
+    }
+
+    /// Current YES price and end date for a market from one Gamma call.
+    pub async fn get_price_and_end_date(
+        &self,
+        condition_id: &str,
+    ) -> Result<(Decimal, Option<DateTime<Utc>>)> {
+        self.rate_limit(ApiFamily::Gamma).await;
+        self.chaos.inject(ChaosTarget::Gamma).await?;
+
+        let url = format!("{}/markets", self.gamma_base_url);
+        let markets: Vec<GammaMarketResponse> = self
+            .send_gamma(self.http.get(&url).query(&[("condition_id", condition_id)]))
+            .await
             .context("HTTP request to Gamma API failed")?
             .json()
-            .await
             .context("Failed to parse Gamma response")?;
 
         let market = markets.first().context("Market not found on Gamma")?;
@@ -370,8 +754,13 @@ impl PolymarketClient {
             .first()
             .and_then(|s| Decimal::from_str(s).ok())
             .unwrap_or(dec!(0.5));
+        let end_date = market
+            .end_date
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|d| d.with_timezone(&Utc));
 
-        Ok(yes_price)
+        Ok((yes_price, end_date))
     }
 
     // === Order Placement ===
@@ -385,14 +774,16 @@ impl PolymarketClient {
         side: Side,
         price: Decimal,
         size: Decimal,
-    ) -> Result<String> {
+    ) -> Result<PlacedOrder> {
+        self.chaos.inject(ChaosTarget::Clob).await?;
+        self.books.invalidate(token_id);
         match self.config.agent.mode {
             AgentMode::Paper => self.paper_place_order(token_id, side, price, size).await,
             AgentMode::Live => {
                 self.live_place_limit_order(token_id, side, price, size)
                     .await
             }
-            AgentMode::Backtest => {
+            AgentMode::Backtest | AgentMode::Simulation => {
                 // In backtest mode, simulate orders same as paper trading
                 self.paper_place_order(token_id, side, price, size).await
             }
@@ -406,10 +797,11 @@ impl PolymarketClient {
         side: Side,
         price: Decimal,
         size: Decimal,
-    ) -> Result<String> {
+    ) -> Result<PlacedOrder> {
         let auth = self.auth_client.as_ref().ok_or_else(|| {
             anyhow::anyhow!("Authenticated client not available for live trading")
         })?;
+        self.rate_limit(ApiFamily::ClobOrder).await;
 
         let token_u256 = parse_token_id(token_id)?;
         let sdk_price = SdkDecimal::from_str(&price.to_string())
@@ -448,15 +840,40 @@ impl PolymarketClient {
             .clob
             .post_order(signed_order)
             .await
-            .map_err(|e| anyhow::anyhow!("Order submission failed: {e}"))?;
+            .map_err(clob_error("Order submission failed"))?;
 
         info!(
             order_id = %response.order_id,
             success = response.success,
+            status = ?response.status,
             "Live order placed successfully"
         );
 
-        Ok(response.order_id)
+        // Buys give USDC for shares; sells give shares for USDC
+        let to_decimal = |d: SdkDecimal| Decimal::from_str(&d.to_string()).unwrap_or_default();
+        let (shares, usdc) = match clob_side {
+            ClobSide::Sell => (
+                to_decimal(response.making_amount),
+                to_decimal(response.taking_amount),
+            ),
+            _ => (
+                to_decimal(response.taking_amount),
+                to_decimal(response.making_amount),
+            ),
+        };
+        // A resting order with nothing matched yet is tracked at full size
+        // until reconciliation sees what it actually filled.
+        let (filled_size, fill_price) = if shares > Decimal::ZERO && usdc > Decimal::ZERO {
+            (shares.min(size), usdc / shares)
+        } else {
+            (size, price)
+        };
+
+        Ok(PlacedOrder {
+            order_id: response.order_id,
+            filled_size,
+            fill_price,
+        })
     }
 
     /// Cancel an order by ID.
@@ -465,19 +882,19 @@ impl PolymarketClient {
             AgentMode::Paper => {
                 if let Some(ref state) = self.paper_state {
                     let mut state = state.lock().await;
-                    if let Some(order) = state
+                    if let Some(index) = state
                         .order_history
-                        .iter_mut()
-                        .find(|o| o.order_id == order_id && !o.filled)
+                        .iter()
+                        .position(|o| o.order_id == order_id && !o.filled)
                     {
-                        order.filled = true;
+                        state.cancel(index);
                         info!(order_id, "Paper order cancelled");
                     }
                 }
                 Ok(())
             }
             AgentMode::Live => self.live_cancel_order(order_id).await,
-            AgentMode::Backtest => Ok(()),
+            AgentMode::Backtest | AgentMode::Simulation => Ok(()),
         }
     }
 
@@ -486,14 +903,15 @@ impl PolymarketClient {
         let auth = self.auth_client.as_ref().ok_or_else(|| {
             anyhow::anyhow!("Authenticated client not available for live trading")
         })?;
+        self.rate_limit(ApiFamily::ClobOrder).await;
 
-        self.with_retry(|| {
+        self.with_retry(ChaosTarget::Clob, || {
             let oid = order_id.to_string();
             async move {
                 auth.clob
                     .cancel_order(&oid)
                     .await
-                    .map_err(|e| anyhow::anyhow!("Cancel order failed: {e}"))
+                    .map_err(clob_error("Cancel order failed"))
             }
         })
         .await?;
@@ -502,6 +920,166 @@ impl PolymarketClient {
         Ok(())
     }
 
+    /// Cancel every open order on the account. Returns the cancelled ids.
+    pub async fn cancel_all_orders(&self) -> Result<Vec<String>> {
+        match self.config.agent.mode {
+            AgentMode::Paper => {
+                let mut cancelled = Vec::new();
+                if let Some(ref state) = self.paper_state {
+                    let mut state = state.lock().await;
+                    for index in 0..state.order_history.len() {
+                        if !state.order_history[index].filled {
+                            state.cancel(index);
+                            cancelled.push(state.order_history[index].order_id.clone());
+                        }
+                    }
+                }
+                info!(count = cancelled.len(), "Paper orders cancelled");
+                Ok(cancelled)
+            }
+            AgentMode::Live => {
+                let auth = self.auth_client.as_ref().ok_or_else(|| {
+                    anyhow::anyhow!("Authenticated client not available for live trading")
+                })?;
+                self.rate_limit(ApiFamily::ClobOrder).await;
+                let response = self
+                    .with_retry(ChaosTarget::Clob, || async {
+                        auth.clob
+                            .cancel_all_orders()
+                            .await
+                            .map_err(clob_error("Cancel all orders failed"))
+                    })
+                    .await?;
+                for (order_id, reason) in &response.not_canceled {
+                    warn!(order_id = %order_id, reason = %reason, "Live order not cancelled");
+                }
+                info!(count = response.canceled.len(), "Live orders cancelled");
+                Ok(response.canceled)
+            }
+            AgentMode::Backtest | AgentMode::Simulation => Ok(Vec::new()),
+        }
+    }
+
+    /// Redeem a resolved market's outcome tokens for USDC on-chain.
+    pub async fn redeem_positions(&self, condition_id: &str) -> Result<TxReceipt> {
+        if self.config.agent.mode != AgentMode::Live {
+            bail!("Redemption only applies to on-chain positions");
+        }
+        let auth = self.auth_client.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Authenticated client not available for live trading")
+        })?;
+        redemption::submit_redeem(
+            &self.http,
+            &self.config.redemption,
+            &auth.signer,
+            self.config.polymarket.chain_id,
+            condition_id,
+        )
+        .await
+    }
+
+    /// On-chain USDC balance, exchange allowance and POL balance of the
+    /// trading wallet.
+    pub async fn wallet_status(&self) -> Result<WalletStatus> {
+        if self.config.agent.mode != AgentMode::Live {
+            bail!("Wallet status only applies to live mode");
+        }
+        let auth = self.auth_client.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Authenticated client not available for live trading")
+        })?;
+        let mut status = wallet::query_status(
+            &self.http,
+            &self.config.redemption.rpc_url,
+            auth.signer.address(),
+        )
+        .await?;
+        status.pol_usd = self.price_feed.pol_to_usd(status.pol_balance).await;
+        Ok(status)
+    }
+
+    /// The shared USD price feed.
+    pub fn price_feed(&self) -> &PriceFeed {
+        &self.price_feed
+    }
+
+    /// Let the exchange spend up to `amount` USDC from the wallet.
+    pub async fn approve_usdc(&self, amount: Decimal) -> Result<TxReceipt> {
+        if self.config.agent.mode != AgentMode::Live {
+            bail!("USDC approval only applies to live mode");
+        }
+        let auth = self.auth_client.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Authenticated client not available for live trading")
+        })?;
+        wallet::submit_approval(
+            &self.http,
+            &self.config.redemption,
+            &auth.signer,
+            self.config.polymarket.chain_id,
+            amount,
+        )
+        .await
+    }
+
+    /// Gas paid by a mined transaction, in USD at the current POL price.
+    pub async fn gas_cost_usd(&self, receipt: &TxReceipt) -> Decimal {
+        receipt.gas_cost_usd(self.price_feed.pol_usd().await.usd)
+    }
+
+    /// Look up the actual fill of a live order: matched size and the
+    /// size-weighted price of its trades.
+    pub async fn get_order_fill(&self, order_id: &str) -> Result<OrderFill> {
+        if self.config.agent.mode != AgentMode::Live {
+            bail!("Order fills are only available in live mode");
+        }
+        let auth = self.auth_client.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Authenticated client not available for live trading")
+        })?;
+
+        let order = self
+            .with_retry(ChaosTarget::Clob, || async {
+                auth.clob
+                    .order(order_id)
+                    .await
+                    .map_err(clob_error("Order lookup failed"))
+            })
+            .await?;
+
+        let to_decimal = |d: SdkDecimal| Decimal::from_str(&d.to_string()).unwrap_or_default();
+        let size_matched = to_decimal(order.size_matched);
+
+        let mut filled = Decimal::ZERO;
+        let mut notional = Decimal::ZERO;
+        for trade_id in &order.associate_trades {
+            let request = TradesRequest::builder().id(trade_id.clone()).build();
+            let page = self
+                .with_retry(ChaosTarget::Clob, || async {
+                    auth.clob
+                        .trades(&request, None)
+                        .await
+                        .map_err(clob_error("Trade lookup failed"))
+                })
+                .await?;
+            for trade in page.data {
+                let size = to_decimal(trade.size);
+                filled += size;
+                notional += to_decimal(trade.price) * size;
+            }
+        }
+
+        let avg_price = if filled > Decimal::ZERO {
+            notional / filled
+        } else {
+            to_decimal(order.price)
+        };
+
+        Ok(OrderFill {
+            order_id: order.id,
+            status: format!("{:?}", order.status).to_lowercase(),
+            size_matched,
+            avg_price,
+        })
+    }
+
     // === Balance ===
 
     /// Get available balance. In paper mode, returns simulated balance.
@@ -517,7 +1095,7 @@ impl PolymarketClient {
                 }
             }
             AgentMode::Live => self.live_get_balance().await,
-            AgentMode::Backtest => Ok(Decimal::ZERO),
+            AgentMode::Backtest | AgentMode::Simulation => Ok(Decimal::ZERO),
         }
     }
 
@@ -528,11 +1106,11 @@ impl PolymarketClient {
         })?;
 
         let response = self
-            .with_retry(|| async move {
+            .with_retry(ChaosTarget::Clob, || async move {
                 auth.clob
                     .balance_allowance(BalanceAllowanceRequest::default())
                     .await
-                    .map_err(|e| anyhow::anyhow!("Balance query failed: {e}"))
+                    .map_err(clob_error("Balance query failed"))
             })
             .await?;
 
@@ -564,18 +1142,9 @@ impl PolymarketClient {
         price: Decimal,
         size: Decimal,
     ) -> Result<String> {
+        self.books.invalidate(token_id);
         match self.config.agent.mode {
-            AgentMode::Paper => {
-                // In paper mode, just simulate the exit
-                info!(
-                    token_id,
-                    side = %side,
-                    price = %price,
-                    size = %size,
-                    "Paper position exited (simulated)"
-                );
-                Ok(format!("paper_exit_{token_id}"))
-            }
+            AgentMode::Paper => self.paper_sell(token_id, price, size).await,
             AgentMode::Live => {
                 // In live mode, place a sell order to exit
                 // The exit side is opposite to the original buy side
@@ -587,7 +1156,42 @@ impl PolymarketClient {
                 self.live_place_limit_order_with_side(token_id, exit_side, price, size)
                     .await
             }
-            AgentMode::Backtest => Ok(format!("backtest_exit_{token_id}")),
+            AgentMode::Backtest | AgentMode::Simulation => Ok(format!("backtest_exit_{token_id}")),
+        }
+    }
+
+    /// Merge `shares` YES/NO pairs of one market back into USDC.
+    ///
+    /// Paper mode credits $1 per pair. Live merging is an on-chain CTF call
+    /// this client does not make, so live pairs are left to settle at
+    /// resolution.
+    pub async fn merge_positions(
+        &self,
+        yes_token: &str,
+        no_token: &str,
+        shares: Decimal,
+    ) -> Result<Decimal> {
+        match self.config.agent.mode {
+            AgentMode::Paper => {
+                let Some(ref state_mutex) = self.paper_state else {
+                    bail!("Paper trading state not initialized");
+                };
+                let mut state = state_mutex.lock().await;
+                let merged = state.merge(yes_token, no_token, shares)?;
+                info!(
+                    yes_token,
+                    no_token,
+                    pairs = %merged,
+                    balance = %state.balance,
+                    "Paper YES/NO pairs merged"
+                );
+                Ok(merged)
+            }
+            AgentMode::Live => bail!("On-chain position merging is not supported"),
+            AgentMode::Backtest => Ok(shares),
+            // Simulated positions live on the fake exchange, which holds
+            // pairs to resolution
+            AgentMode::Simulation => bail!("Position merging is not simulated"),
         }
     }
 
@@ -635,7 +1239,7 @@ impl PolymarketClient {
             .clob
             .post_order(signed_order)
             .await
-            .map_err(|e| anyhow::anyhow!("Exit order submission failed: {e}"))?;
+            .map_err(clob_error("Exit order submission failed"))?;
 
         info!(
             order_id = %response.order_id,
@@ -660,7 +1264,17 @@ impl PolymarketClient {
         side: Side,
         price: Decimal,
         size: Decimal,
-    ) -> Result<String> {
+    ) -> Result<PlacedOrder> {
+        if self.config.agent.mode == AgentMode::Paper
+            && self.config.execution.paper_matching.enabled
+            && self.partial_fill_policy() == PartialFillPolicy::Keep
+        {
+            let book = self.get_order_book(token_id).await?;
+            if paper_matching::rests(price, &book.asks) {
+                return self.paper_rest_order(token_id, side, price, size).await;
+            }
+        }
+
         let fill_result = self
             .simulate_paper_fill(token_id, side, price, size)
             .await?;
@@ -669,7 +1283,164 @@ impl PolymarketClient {
             bail!("Paper order not filled — simulated queue position not reached");
         }
 
-        Ok(fill_result.order_id)
+        Ok(PlacedOrder {
+            order_id: fill_result.order_id,
+            filled_size: fill_result.fill_size,
+            fill_price: fill_result.fill_price,
+        })
+    }
+
+    /// Leave a non-marketable paper order on the book. Nothing is debited
+    /// until [`Self::match_paper_orders`] sees the market trade through it.
+    async fn paper_rest_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<PlacedOrder> {
+        let Some(ref state_mutex) = self.paper_state else {
+            bail!("Paper trading state not initialized");
+        };
+
+        let mut state = state_mutex.lock().await;
+        let cost = price * size;
+        if cost > state.balance {
+            bail!(
+                "Insufficient paper balance: {} < cost {}",
+                state.balance,
+                cost
+            );
+        }
+
+        let order_id = uuid::Uuid::new_v4().to_string();
+        state.order_history.push(PaperOrder {
+            order_id: order_id.clone(),
+            token_id: token_id.to_string(),
+            side,
+            price,
+            size,
+            filled: false,
+            adverse_selection: false,
+            matched: Decimal::ZERO,
+            resting: true,
+            placed_at: Utc::now(),
+        });
+
+        info!(order_id = %order_id, price = %price, size = %size, "Paper order resting");
+
+        Ok(PlacedOrder {
+            order_id,
+            filled_size: Decimal::ZERO,
+            fill_price: price,
+        })
+    }
+
+    /// Whether a paper order is still resting on the book.
+    pub async fn paper_order_resting(&self, order_id: &str) -> bool {
+        match self.paper_state {
+            Some(ref state) => state
+                .lock()
+                .await
+                .order_history
+                .iter()
+                .any(|o| o.order_id == order_id && o.resting),
+            None => false,
+        }
+    }
+
+    /// Match resting paper orders against each token's live order book.
+    ///
+    /// Orders the market traded through buy the shares offered past their
+    /// limit, at the limit and with maker fees; orders older than
+    /// `order_ttl_seconds` expire. Returns the final fills of orders that
+    /// stopped resting since the last call, cancellations included, for
+    /// reconciliation against their trades.
+    pub async fn match_paper_orders(&self) -> Result<Vec<OrderFill>> {
+        let Some(ref state_mutex) = self.paper_state else {
+            return Ok(Vec::new());
+        };
+
+        let mut tokens: Vec<String> = state_mutex
+            .lock()
+            .await
+            .order_history
+            .iter()
+            .filter(|o| o.resting)
+            .map(|o| o.token_id.clone())
+            .collect();
+        tokens.sort();
+        tokens.dedup();
+
+        // Fetch books without holding the lock so placements aren't blocked
+        let mut books = HashMap::new();
+        for token_id in tokens {
+            match self.get_order_book(&token_id).await {
+                Ok(book) => {
+                    books.insert(token_id, book);
+                }
+                Err(e) => {
+                    warn!(token_id = %token_id, error = %e, "No book for resting paper orders")
+                }
+            }
+        }
+
+        let matching = &self.config.execution.paper_matching;
+        let ttl = TimeDelta::seconds(self.config.execution.order_ttl_seconds as i64);
+        let fees = self.fees();
+        let now = Utc::now();
+
+        let mut state = state_mutex.lock().await;
+        for index in 0..state.order_history.len() {
+            let order = &state.order_history[index];
+            if !order.resting {
+                continue;
+            }
+            let (token_id, side, price) = (order.token_id.clone(), order.side, order.price);
+            let remaining = order.size - order.matched;
+            let placed_at = order.placed_at;
+
+            if let Some(book) = books.get(&token_id) {
+                let shares = paper_matching::fillable_shares(
+                    price,
+                    remaining,
+                    &book.asks,
+                    matching.fill_on_touch,
+                );
+                if shares > Decimal::ZERO {
+                    let fee = fees.fee(Liquidity::Maker, price, shares);
+                    if let Err(e) = state.buy(&token_id, side, price, shares, fee) {
+                        warn!(error = %e, "Resting paper order can't be paid for — cancelling");
+                        state.finish_resting(index, "canceled");
+                        continue;
+                    }
+                    let order = &mut state.order_history[index];
+                    order.matched += shares;
+                    info!(
+                        order_id = %order.order_id,
+                        price = %price,
+                        shares = %shares,
+                        matched = %order.matched,
+                        size = %order.size,
+                        "Resting paper order filled"
+                    );
+                    if order.matched >= order.size {
+                        state.finish_resting(index, "matched");
+                        continue;
+                    }
+                }
+            }
+
+            if ttl > TimeDelta::zero() && now - placed_at >= ttl {
+                info!(
+                    order_id = %state.order_history[index].order_id,
+                    "Resting paper order expired"
+                );
+                state.finish_resting(index, "canceled");
+            }
+        }
+
+        Ok(std::mem::take(&mut state.finished))
     }
 
     /// Realistic paper trading fill simulation.
@@ -715,6 +1486,9 @@ impl PolymarketClient {
                 size,
                 filled: false,
                 adverse_selection: false,
+                matched: Decimal::ZERO,
+                resting: false,
+                placed_at: Utc::now(),
             });
 
             info!(
@@ -747,23 +1521,10 @@ impl PolymarketClient {
             price
         };
 
-        let cost = adverse_slippage * actual_size;
-
-        if cost > state.balance {
-            bail!(
-                "Insufficient paper balance: {} < cost {}",
-                state.balance,
-                cost
-            );
-        }
-
-        state.balance -= cost;
-        state.positions.push(PaperPosition {
-            token_id: token_id.to_string(),
-            side,
-            size: actual_size,
-            entry_price: adverse_slippage,
-        });
+        let fee = self
+            .fees()
+            .fee(Liquidity::Taker, adverse_slippage, actual_size);
+        state.buy(token_id, side, adverse_slippage, actual_size, fee)?;
         state.order_history.push(PaperOrder {
             order_id: order_id.clone(),
             token_id: token_id.to_string(),
@@ -772,6 +1533,9 @@ impl PolymarketClient {
             size: actual_size,
             filled: true,
             adverse_selection,
+            matched: actual_size,
+            resting: false,
+            placed_at: Utc::now(),
         });
 
         info!(
@@ -792,13 +1556,125 @@ impl PolymarketClient {
         })
     }
 
+    /// Sell paper inventory back to the book at `price`.
+    ///
+    /// Sells at most the shares held; the fill is immediate since exits in
+    /// paper mode are assumed to cross the spread.
+    async fn paper_sell(&self, token_id: &str, price: Decimal, size: Decimal) -> Result<String> {
+        let Some(ref state_mutex) = self.paper_state else {
+            bail!("Paper trading state not initialized");
+        };
+
+        let mut state = state_mutex.lock().await;
+        let sold = state.sell(token_id, price, size)?;
+        let order_id = uuid::Uuid::new_v4().to_string();
+        let side = state
+            .order_history
+            .iter()
+            .rev()
+            .find(|o| o.token_id == token_id)
+            .map(|o| o.side)
+            .unwrap_or(Side::Yes);
+        state.order_history.push(PaperOrder {
+            order_id: order_id.clone(),
+            token_id: token_id.to_string(),
+            side,
+            price,
+            size: sold,
+            filled: true,
+            adverse_selection: false,
+            matched: sold,
+            resting: false,
+            placed_at: Utc::now(),
+        });
+
+        info!(
+            order_id = %order_id,
+            token_id,
+            price = %price,
+            shares = %sold,
+            balance = %state.balance,
+            realized_pnl = %state.realized_pnl,
+            "Paper shares sold"
+        );
+
+        Ok(order_id)
+    }
+
+    /// Settle paper inventory of a resolved token at `payout` per share.
+    /// Returns shares settled; zero if none were held.
+    pub async fn paper_settle(&self, token_id: &str, payout: Decimal) -> Result<Decimal> {
+        let Some(ref state_mutex) = self.paper_state else {
+            bail!("Paper trading state not initialized");
+        };
+
+        let mut state = state_mutex.lock().await;
+        let shares = state.settle(token_id, payout);
+        if shares > Decimal::ZERO {
+            info!(
+                token_id,
+                shares = %shares,
+                payout = %payout,
+                balance = %state.balance,
+                realized_pnl = %state.realized_pnl,
+                "Paper holding settled"
+            );
+        }
+        Ok(shares)
+    }
+
+    /// Current paper share inventories, one per token held.
+    pub async fn paper_holdings(&self) -> Vec<PaperHolding> {
+        match self.paper_state {
+            Some(ref state) => state.lock().await.holdings.values().cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Value the paper account at each token's current order book midpoint.
+    pub async fn paper_mark_to_market(&self) -> Result<PaperValuation> {
+        let Some(ref state_mutex) = self.paper_state else {
+            bail!("Paper trading state not initialized");
+        };
+
+        let token_ids: Vec<String> = state_mutex.lock().await.holdings.keys().cloned().collect();
+
+        let mut marks = HashMap::new();
+        for token_id in token_ids {
+            match self.get_midpoint(&token_id).await {
+                Ok(mid) => {
+                    marks.insert(token_id, mid);
+                }
+                Err(e) => warn!(token_id = %token_id, error = %e, "No mark for paper holding"),
+            }
+        }
+
+        let state = state_mutex.lock().await;
+        Ok(state.mark_to_market(&marks))
+    }
+
     // === Accessors ===
 
     /// Borrow the HTTP client for use by resolution and other modules.
+    /// Fault injector, shared with other clients so one seed drives all faults.
+    pub fn chaos(&self) -> Arc<ChaosInjector> {
+        self.chaos.clone()
+    }
+
     pub fn http_client(&self) -> &reqwest::Client {
         &self.http
     }
 
+    /// Fee model from the execution config.
+    pub fn fees(&self) -> FeeModel {
+        FeeModel::new(&self.config.execution.fees)
+    }
+
+    /// What to do with the unfilled remainder of a partially filled order.
+    pub fn partial_fill_policy(&self) -> PartialFillPolicy {
+        self.config.execution.partial_fill
+    }
+
     /// Borrow the Gamma API base URL.
     pub fn gamma_base_url(&self) -> &str {
         &self.gamma_base_url
@@ -806,13 +1682,39 @@ impl PolymarketClient {
 
     // === Rate Limiting ===
 
-    async fn rate_limit(&self) {
-        self.limiter.until_ready().await;
+    async fn rate_limit(&self, family: ApiFamily) {
+        self.limiters.until_ready(family).await;
+    }
+
+    /// Requests made and time spent waiting, per API family, since startup.
+    pub fn rate_limit_stats(&self) -> Vec<RateLimitStats> {
+        self.limiters.stats()
+    }
+
+    /// HTTP cache shared with data sources, so one budget covers all reads.
+    pub fn http_cache(&self) -> Arc<HttpCache> {
+        self.cache.clone()
+    }
+
+    pub fn http_cache_stats(&self) -> HttpCacheStats {
+        self.cache.stats()
+    }
+
+    pub fn book_cache_stats(&self) -> BookCacheStats {
+        self.books.stats()
+    }
+
+    /// Send a Gamma request through the HTTP cache. Chaos may corrupt the
+    /// body, which is then parsed like any other.
+    async fn send_gamma(&self, request: reqwest::RequestBuilder) -> Result<CachedResponse> {
+        let mut resp = self.cache.send(request).await?.error_for_status("gamma")?;
+        resp.body = self.chaos.malform(ChaosTarget::Gamma, resp.body).await;
+        Ok(resp)
     }
 
     // === Retry Logic ===
 
-    async fn with_retry<F, Fut, T>(&self, operation: F) -> Result<T>
+    async fn with_retry<F, Fut, T>(&self, target: ChaosTarget, operation: F) -> Result<T>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T>>,
@@ -824,33 +1726,42 @@ impl PolymarketClient {
         let mut attempt = 0u32;
 
         loop {
-            match operation().await {
+            // Injected faults go through the same retry path as real ones
+            let outcome = match self.chaos.inject(target).await {
+                Ok(()) => operation().await,
+                Err(e) => Err(e),
+            };
+            // CLOB responses are decoded inside the SDK, out of reach of
+            // body corruption; fail them as decoding would instead
+            let outcome = match outcome {
+                Ok(result) if target == ChaosTarget::Clob => {
+                    self.chaos.decode_fault(target).await.map(|()| result)
+                }
+                outcome => outcome,
+            };
+            match outcome {
                 Ok(result) => return Ok(result),
                 Err(e) => {
                     attempt += 1;
 
-                    let err_str = e.to_string();
-
-                    // Non-retryable errors
-                    if err_str.contains("insufficient")
-                        || err_str.contains("Insufficient")
-                        || err_str.contains("balance")
-                    {
-                        return Err(e.context("Insufficient balance — not retrying"));
-                    }
-                    if err_str.contains("401")
-                        || err_str.contains("403")
-                        || err_str.contains("auth")
-                    {
-                        return Err(e.context("Authentication failure — not retrying"));
-                    }
+                    let retry_after = match api_error::classify(&e) {
+                        RetryClass::Permanent => {
+                            return Err(e.context("Non-retryable API error — not retrying"));
+                        }
+                        RetryClass::Transient { retry_after } => retry_after,
+                    };
 
                     if attempt > max_retries {
                         return Err(e.context(format!("Failed after {max_retries} retries")));
                     }
 
-                    let backoff_ms =
-                        std::cmp::min(base_ms.saturating_mul(2u64.pow(attempt - 1)), max_ms);
+                    // Honor the server's Retry-After, within our own ceiling
+                    let backoff_ms = match retry_after {
+                        Some(delay) => std::cmp::min(delay.as_millis() as u64, max_ms),
+                        None => {
+                            std::cmp::min(base_ms.saturating_mul(2u64.pow(attempt - 1)), max_ms)
+                        }
+                    };
 
                     warn!(
                         attempt,
@@ -868,12 +1779,12 @@ impl PolymarketClient {
 
 // === Helper Functions ===
 
-fn create_rate_limiter(config: &RateLimitConfig) -> Arc<Limiter> {
-    let rps = NonZeroU32::new(config.requests_per_second).unwrap_or(NonZeroU32::new(10).unwrap());
-    let burst = NonZeroU32::new(config.burst_size).unwrap_or(NonZeroU32::new(20).unwrap());
-
-    let quota = Quota::per_second(rps).allow_burst(burst);
-    Arc::new(RateLimiter::direct(quota))
+/// Wrap a CLOB SDK error so [`api_error::classify`] can see its HTTP status.
+fn clob_error<E>(context: &'static str) -> impl FnOnce(E) -> anyhow::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    move |e| anyhow::Error::new(ApiError::from_sdk("clob", &e)).context(context)
 }
 
 fn parse_token_id(token_id: &str) -> Result<U256> {
@@ -902,6 +1813,45 @@ struct GammaMarketResponse {
     volume24hr: Option<f64>,
     active: Option<bool>,
     closed: Option<bool>,
+    neg_risk: Option<bool>,
+    description: Option<String>,
+    resolution_source: Option<String>,
+    /// Legacy free-text category, set on older markets.
+    category: Option<String>,
+    /// Only returned when requested with `include_tag=true`.
+    #[serde(default)]
+    tags: Vec<GammaTag>,
+    /// Events the market belongs to; in practice at most one.
+    #[serde(default)]
+    events: Vec<GammaEventResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GammaTag {
+    label: Option<String>,
+    slug: Option<String>,
+}
+
+/// Gamma API event. Embedded in market responses without `markets`; the
+/// `/events` endpoint includes them.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GammaEventResponse {
+    id: Option<String>,
+    slug: Option<String>,
+    title: Option<String>,
+    neg_risk: Option<bool>,
+    category: Option<String>,
+    #[serde(default)]
+    tags: Vec<GammaTag>,
+    #[serde(default)]
+    markets: Vec<GammaEventMarket>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GammaEventMarket {
+    condition_id: Option<String>,
 }
 
 /// Parse a JSON-encoded string array like "[\"a\", \"b\"]" into Vec<String>.
@@ -942,13 +1892,14 @@ fn convert_gamma_response(gm: &GammaMarketResponse) -> Option<Market> {
         })
         .collect();
 
-    let category = crate::market::category::infer_category(&question);
+    let category = crate::market::category::categorize(&gamma_tags(gm), &question);
 
     let volume_24h = gm
         .volume24hr
         .and_then(|v| Decimal::try_from(v).ok())
         .unwrap_or(Decimal::ZERO);
     let active = gm.active.unwrap_or(false) && !gm.closed.unwrap_or(true);
+    let event = gm.events.first();
 
     Some(Market {
         condition_id: gm.condition_id.clone().unwrap_or_default(),
@@ -959,6 +1910,59 @@ fn convert_gamma_response(gm: &GammaMarketResponse) -> Option<Market> {
         category,
         volume_24h,
         active,
+        event_id: event.and_then(|e| e.id.clone()),
+        event_slug: event.and_then(|e| e.slug.clone()),
+        neg_risk: gm.neg_risk.or(event.and_then(|e| e.neg_risk)) == Some(true),
+        description: gm.description.clone().unwrap_or_default(),
+        resolution_source: gm.resolution_source.clone().filter(|s| !s.is_empty()),
+    })
+}
+
+/// Every tag and category Gamma gave a market or its events, most specific
+/// first.
+fn gamma_tags(gm: &GammaMarketResponse) -> Vec<&str> {
+    fn tag_names(tags: &[GammaTag]) -> impl Iterator<Item = &str> {
+        tags.iter()
+            .flat_map(|t| [t.slug.as_deref(), t.label.as_deref()])
+            .flatten()
+    }
+    tag_names(&gm.tags)
+        .chain(gm.category.as_deref())
+        .chain(
+            gm.events
+                .iter()
+                .flat_map(|e| tag_names(&e.tags).chain(e.category.as_deref())),
+        )
+        .collect()
+}
+
+/// Fill in the event of markets that lack one from `events`.
+fn attach_events(markets: &mut [Market], events: &[MarketEvent]) {
+    let by_market: HashMap<&str, &MarketEvent> = events
+        .iter()
+        .flat_map(|e| e.market_ids.iter().map(move |id| (id.as_str(), e)))
+        .collect();
+    for market in markets.iter_mut().filter(|m| m.event_id.is_none()) {
+        if let Some(event) = by_market.get(market.condition_id.as_str()) {
+            market.event_id = Some(event.id.clone());
+            market.event_slug = Some(event.slug.clone());
+            market.neg_risk |= event.neg_risk;
+        }
+    }
+}
+
+/// Convert a Gamma event to the domain type, skipping events without an id.
+fn convert_gamma_event(ge: &GammaEventResponse) -> Option<MarketEvent> {
+    Some(MarketEvent {
+        id: ge.id.clone()?,
+        slug: ge.slug.clone().unwrap_or_default(),
+        title: ge.title.clone().unwrap_or_default(),
+        neg_risk: ge.neg_risk.unwrap_or(false),
+        market_ids: ge
+            .markets
+            .iter()
+            .filter_map(|m| m.condition_id.clone())
+            .collect(),
     })
 }
 
@@ -982,6 +1986,7 @@ fn convert_order_book(token_id: &str, response: &OrderBookSummaryResponse) -> Or
         })
         .collect();
 
+    let liquidity = BookLiquidity::of(&bids, &asks);
     let best_bid = bids.first().map(|b| b.price).unwrap_or(Decimal::ZERO);
     let best_ask = asks.first().map(|a| a.price).unwrap_or(Decimal::ONE);
     let midpoint = (best_bid + best_ask) / dec!(2);
@@ -998,9 +2003,37 @@ fn convert_order_book(token_id: &str, response: &OrderBookSummaryResponse) -> Or
         midpoint,
         implied_probability,
         timestamp: Utc::now(),
+        liquidity,
     }
 }
 
+/// A fallback price strictly between 0 and 1; the CLOB reports 0 or 1 for
+/// tokens it has no price for.
+fn usable_fallback(price: Decimal) -> Option<Decimal> {
+    (price > Decimal::ZERO && price < Decimal::ONE).then_some(price)
+}
+
+/// Price a book missing one or both sides at `fallback`. The spread is
+/// twice the distance from the fallback price to the side that exists;
+/// without a fallback price, or without either side, it is the maximum of
+/// 1 so no spread limit lets the book through.
+fn reprice_one_sided(book: &mut OrderBookSnapshot, fallback: Option<Decimal>) {
+    let Some(price) = fallback else {
+        book.spread = Decimal::ONE;
+        return;
+    };
+    book.midpoint = price;
+    book.implied_probability = price;
+    let half_spread = book
+        .bids
+        .first()
+        .map(|b| price - b.price)
+        .or_else(|| book.asks.first().map(|a| a.price - price));
+    book.spread = half_spread
+        .map(|d| (d.abs() * dec!(2)).min(Decimal::ONE))
+        .unwrap_or(Decimal::ONE);
+}
+
 // === Paper Trading Fill Simulation Helpers ===
 
 /// Compute fill probability based on order aggressiveness.
@@ -1032,11 +2065,57 @@ fn compute_adverse_selection(seed: u64) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::RateLimitConfig;
 
     fn deserialize_order_book(json: &str) -> OrderBookSummaryResponse {
         serde_json::from_str(json).expect("valid order book JSON")
     }
 
+    #[test]
+    fn test_gamma_market_carries_event() {
+        let json = r#"[{
+            "conditionId": "0xabc",
+            "question": "Will Alice win the election?",
+            "outcomes": "[\"Yes\", \"No\"]",
+            "outcomePrices": "[\"0.3\", \"0.7\"]",
+            "clobTokenIds": "[\"1\", \"2\"]",
+            "endDate": "2030-11-05T00:00:00Z",
+            "volume24hr": 5000.0,
+            "active": true,
+            "closed": false,
+            "events": [{"id": "903", "slug": "election-winner", "title": "Election winner", "negRisk": true,
+                        "tags": [{"label": "Elections", "slug": "elections"}]}]
+        }]"#;
+        let markets: Vec<GammaMarketResponse> = serde_json::from_str(json).unwrap();
+        let market = convert_gamma_response(&markets[0]).unwrap();
+        assert_eq!(
+            market.category,
+            crate::market::models::MarketCategory::Politics
+        );
+        assert_eq!(market.event_id.as_deref(), Some("903"));
+        assert_eq!(market.event_slug.as_deref(), Some("election-winner"));
+        assert!(market.neg_risk);
+
+        let mut other = market.clone();
+        other.condition_id = "0xdef".to_string();
+        let mut loose = market.clone();
+        loose.condition_id = "0x123".to_string();
+        loose.event_id = None;
+        let mut markets = vec![market, other, loose];
+        let groups = crate::market::models::group_by_event(&markets);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups["903"].len(), 2);
+
+        let events: Vec<GammaEventResponse> = serde_json::from_str(
+            r#"[{"id": "77", "slug": "fed-march", "title": "Fed decision", "markets": [{"conditionId": "0x123"}]}]"#,
+        )
+        .unwrap();
+        let events: Vec<MarketEvent> = events.iter().filter_map(convert_gamma_event).collect();
+        attach_events(&mut markets, &events);
+        assert_eq!(markets[2].event_slug.as_deref(), Some("fed-march"));
+        assert_eq!(markets[0].event_id.as_deref(), Some("903"));
+    }
+
     #[test]
     fn test_spread_calculation() {
         let json = r#"{
@@ -1055,6 +2134,7 @@ mod tests {
         assert_eq!(book.spread, dec!(0.10));
         assert_eq!(book.midpoint, dec!(0.65));
         assert_eq!(book.implied_probability, dec!(0.65));
+        assert_eq!(book.liquidity, BookLiquidity::TwoSided);
     }
 
     #[test]
@@ -1076,18 +2156,56 @@ mod tests {
         assert_eq!(book.asks.len(), 0);
         assert_eq!(book.midpoint, dec!(0.5));
         assert_eq!(book.spread, dec!(1));
+        assert_eq!(book.liquidity, BookLiquidity::Empty);
     }
 
     #[test]
-    fn test_rate_limiter_creation() {
+    fn test_fallback_price() {
+        assert_eq!(usable_fallback(dec!(0.42)), Some(dec!(0.42)));
+        assert_eq!(usable_fallback(Decimal::ZERO), None);
+        assert_eq!(usable_fallback(Decimal::ONE), None);
+
+        let json = r#"{
+            "market": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "asset_id": "0",
+            "timestamp": "1700000000000",
+            "bids": [{"price": "0.40", "size": "100"}],
+            "asks": [],
+            "min_order_size": "1",
+            "neg_risk": false,
+            "tick_size": "0.01"
+        }"#;
+        let bids_only = convert_order_book("12345", &deserialize_order_book(json));
+        assert_eq!(bids_only.liquidity, BookLiquidity::OneSided);
+        // Against the default ask of 1 the spread is useless
+        assert_eq!(bids_only.spread, dec!(0.60));
+
+        let mut book = bids_only.clone();
+        reprice_one_sided(&mut book, Some(dec!(0.42)));
+        assert_eq!(book.midpoint, dec!(0.42));
+        assert_eq!(book.implied_probability, dec!(0.42));
+        assert_eq!(book.spread, dec!(0.04));
+
+        // No fallback price: never tradeable
+        let mut book = bids_only;
+        reprice_one_sided(&mut book, None);
+        assert_eq!(book.spread, Decimal::ONE);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_creation() {
         let config = RateLimitConfig {
             requests_per_second: 10,
             burst_size: 20,
             backoff_base_ms: 1000,
             backoff_max_ms: 30000,
+            gamma: None,
+            clob_read: None,
+            clob_order: None,
         };
-        let limiter = create_rate_limiter(&config);
-        assert!(limiter.check().is_ok());
+        let limiters = RateLimiters::new(&config);
+        limiters.until_ready(ApiFamily::Gamma).await;
+        assert_eq!(limiters.stats()[0].requests, 1);
     }
 
     #[tokio::test]
@@ -1150,6 +2268,126 @@ mod tests {
         assert!(balance >= Decimal::ZERO);
     }
 
+    #[test]
+    fn test_paper_inventory_per_token() {
+        let mut state = PaperTradingState::new(dec!(100));
+        state
+            .buy("yes_tok", Side::Yes, dec!(0.40), dec!(10), Decimal::ZERO)
+            .unwrap();
+        state
+            .buy("no_tok", Side::No, dec!(0.60), dec!(10), Decimal::ZERO)
+            .unwrap();
+        state
+            .buy("yes_tok", Side::Yes, dec!(0.60), dec!(10), Decimal::ZERO)
+            .unwrap();
+
+        assert_eq!(state.balance, dec!(84));
+        assert_eq!(state.holdings.len(), 2);
+        let yes = &state.holdings["yes_tok"];
+        assert_eq!(yes.shares, dec!(20));
+        assert_eq!(yes.avg_entry_price, dec!(0.50));
+        assert_eq!(state.holdings["no_tok"].side, Side::No);
+    }
+
+    #[test]
+    fn test_paper_sell_back() {
+        let mut state = PaperTradingState::new(dec!(100));
+        state
+            .buy("tok", Side::Yes, dec!(0.50), dec!(20), Decimal::ZERO)
+            .unwrap();
+
+        let sold = state.sell("tok", dec!(0.70), dec!(5)).unwrap();
+        assert_eq!(sold, dec!(5));
+        assert_eq!(state.balance, dec!(93.5));
+        assert_eq!(state.realized_pnl, dec!(1.0));
+        assert_eq!(state.holdings["tok"].shares, dec!(15));
+
+        // Oversized sell only closes what is held, then drops the holding
+        let sold = state.sell("tok", dec!(0.40), dec!(100)).unwrap();
+        assert_eq!(sold, dec!(15));
+        assert!(state.holdings.is_empty());
+        assert!(state.sell("tok", dec!(0.40), dec!(1)).is_err());
+    }
+
+    #[test]
+    fn test_paper_merge_pairs() {
+        let mut state = PaperTradingState::new(dec!(100));
+        state
+            .buy("yes_tok", Side::Yes, dec!(0.60), dec!(10), Decimal::ZERO)
+            .unwrap();
+        state
+            .buy("no_tok", Side::No, dec!(0.30), dec!(12), Decimal::ZERO)
+            .unwrap();
+        assert_eq!(state.balance, dec!(90.4));
+
+        let merged = state.merge("yes_tok", "no_tok", dec!(10)).unwrap();
+        assert_eq!(merged, dec!(10));
+        assert_eq!(state.balance, dec!(100.4));
+        assert_eq!(state.realized_pnl, dec!(1.0));
+        assert!(!state.holdings.contains_key("yes_tok"));
+        assert_eq!(state.holdings["no_tok"].shares, dec!(2));
+    }
+
+    #[test]
+    fn test_paper_mark_to_market() {
+        let mut state = PaperTradingState::new(dec!(100));
+        state
+            .buy("a", Side::Yes, dec!(0.50), dec!(10), Decimal::ZERO)
+            .unwrap();
+        state
+            .buy("b", Side::No, dec!(0.20), dec!(10), Decimal::ZERO)
+            .unwrap();
+
+        let marks = HashMap::from([("a".to_string(), dec!(0.65))]);
+        let v = state.mark_to_market(&marks);
+        assert_eq!(v.cash, dec!(93));
+        // a marked at 0.65, b carried at cost 0.20
+        assert_eq!(v.position_value, dec!(8.5));
+        assert_eq!(v.unrealized_pnl, dec!(1.5));
+        assert_eq!(v.equity, dec!(101.5));
+    }
+
+    #[test]
+    fn test_paper_settlement() {
+        let mut state = PaperTradingState::new(dec!(100));
+        state
+            .buy("yes_tok", Side::Yes, dec!(0.40), dec!(10), Decimal::ZERO)
+            .unwrap();
+        state
+            .buy("no_tok", Side::No, dec!(0.30), dec!(10), Decimal::ZERO)
+            .unwrap();
+
+        // YES won: its shares pay $1, the NO shares expire worthless
+        assert_eq!(state.settle("yes_tok", Decimal::ONE), dec!(10));
+        assert_eq!(state.settle("no_tok", Decimal::ZERO), dec!(10));
+        assert_eq!(state.balance, dec!(103));
+        assert_eq!(state.realized_pnl, dec!(3));
+        assert!(state.holdings.is_empty());
+        // Nothing left to settle
+        assert_eq!(state.settle("yes_tok", Decimal::ONE), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_paper_buy_deducts_fee() {
+        let mut state = PaperTradingState::new(dec!(100));
+        state
+            .buy("tok", Side::Yes, dec!(0.50), dec!(20), dec!(0.10))
+            .unwrap();
+        assert_eq!(state.balance, dec!(89.90));
+        assert_eq!(state.realized_pnl, dec!(-0.10));
+        assert_eq!(state.holdings["tok"].avg_entry_price, dec!(0.50));
+    }
+
+    #[test]
+    fn test_paper_buy_insufficient_balance() {
+        let mut state = PaperTradingState::new(dec!(5));
+        let err = state
+            .buy("tok", Side::Yes, dec!(0.50), dec!(20), Decimal::ZERO)
+            .unwrap_err();
+        assert!(err.to_string().contains("Insufficient"));
+        assert!(state.holdings.is_empty());
+    }
+
     fn test_paper_config() -> AppConfig {
         let toml_str = include_str!("../../config/default.toml");
         toml::from_str(toml_str).unwrap()
@@ -1160,8 +2398,16 @@ mod tests {
             polymarket_private_key: None,
             anthropic_api_key: None,
             discord_webhook_url: None,
+            slack_webhook_url: None,
+            signal_webhook_url: None,
+            smtp_username: None,
+            smtp_password: None,
             noaa_api_token: None,
             espn_api_key: None,
+            voyage_api_key: None,
+            dashboard_token: None,
+            dashboard_username: None,
+            dashboard_password: None,
         }
     }
 }
@@ -0,0 +1,156 @@
+//! Rate limiting per API family.
+//!
+//! Gamma pagination, CLOB market-data reads and CLOB order calls each draw
+//! from their own quota, so a long market scan can't starve order-book
+//! fetches or order placement. Each limiter also counts how long callers
+//! spent waiting on it.
+
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use governor::clock::DefaultClock;
+use governor::state::{InMemoryState, NotKeyed};
+use governor::{Quota, RateLimiter};
+use serde::Serialize;
+
+use crate::config::{ApiQuota, RateLimitConfig};
+
+type Limiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
+/// A group of endpoints sharing one quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiFamily {
+    /// Gamma market and event listings.
+    Gamma,
+    /// CLOB market data: books, prices, history.
+    ClobRead,
+    /// CLOB order placement and cancellation.
+    ClobOrder,
+}
+
+impl ApiFamily {
+    const ALL: [ApiFamily; 3] = [Self::Gamma, Self::ClobRead, Self::ClobOrder];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gamma => "gamma",
+            Self::ClobRead => "clob_read",
+            Self::ClobOrder => "clob_order",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Cumulative limiter usage for one family.
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitStats {
+    pub family: &'static str,
+    pub requests: u64,
+    pub waited_ms: u64,
+}
+
+struct FamilyLimiter {
+    limiter: Limiter,
+    requests: AtomicU64,
+    waited_us: AtomicU64,
+}
+
+/// One limiter per [`ApiFamily`].
+pub struct RateLimiters {
+    families: [FamilyLimiter; 3],
+}
+
+impl RateLimiters {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let default = ApiQuota {
+            requests_per_second: config.requests_per_second,
+            burst_size: config.burst_size,
+        };
+        let family = |quota: &Option<ApiQuota>| FamilyLimiter {
+            limiter: create_limiter(quota.as_ref().unwrap_or(&default)),
+            requests: AtomicU64::new(0),
+            waited_us: AtomicU64::new(0),
+        };
+        Self {
+            families: [
+                family(&config.gamma),
+                family(&config.clob_read),
+                family(&config.clob_order),
+            ],
+        }
+    }
+
+    /// Wait until `family` may make another request.
+    pub async fn until_ready(&self, family: ApiFamily) {
+        let entry = &self.families[family.index()];
+        let started = Instant::now();
+        entry.limiter.until_ready().await;
+        entry.requests.fetch_add(1, Ordering::Relaxed);
+        entry
+            .waited_us
+            .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> Vec<RateLimitStats> {
+        ApiFamily::ALL
+            .iter()
+            .map(|family| {
+                let entry = &self.families[family.index()];
+                RateLimitStats {
+                    family: family.as_str(),
+                    requests: entry.requests.load(Ordering::Relaxed),
+                    waited_ms: entry.waited_us.load(Ordering::Relaxed) / 1000,
+                }
+            })
+            .collect()
+    }
+}
+
+fn create_limiter(quota: &ApiQuota) -> Limiter {
+    let rps = NonZeroU32::new(quota.requests_per_second).unwrap_or(NonZeroU32::new(10).unwrap());
+    let burst = NonZeroU32::new(quota.burst_size).unwrap_or(NonZeroU32::new(20).unwrap());
+
+    RateLimiter::direct(Quota::per_second(rps).allow_burst(burst))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_families_have_independent_quotas() {
+        let config = RateLimitConfig {
+            requests_per_second: 1,
+            burst_size: 1,
+            backoff_base_ms: 1000,
+            backoff_max_ms: 30000,
+            gamma: None,
+            clob_read: Some(ApiQuota {
+                requests_per_second: 100,
+                burst_size: 5,
+            }),
+            clob_order: None,
+        };
+        let limiters = RateLimiters::new(&config);
+
+        // Exhaust the gamma burst; CLOB reads are unaffected
+        limiters.until_ready(ApiFamily::Gamma).await;
+        assert!(limiters.families[ApiFamily::Gamma.index()]
+            .limiter
+            .check()
+            .is_err());
+        for _ in 0..5 {
+            limiters.until_ready(ApiFamily::ClobRead).await;
+        }
+
+        let stats = limiters.stats();
+        assert_eq!(stats[0].family, "gamma");
+        assert_eq!(stats[0].requests, 1);
+        assert_eq!(stats[1].requests, 5);
+        assert_eq!(stats[2].requests, 0);
+    }
+}
@@ -0,0 +1,114 @@
+//! Operator include/exclude rules for which markets the agent may trade.
+//!
+//! Rules match on question keywords, category, Gamma event slug or
+//! condition id. An excluded market is never traded; a non-empty include
+//! list restricts the agent to the markets it matches.
+
+use serde::Deserialize;
+
+use crate::market::models::Market;
+
+/// One set of market-matching rules. A market matches if any rule does.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MarketRules {
+    /// Case-insensitive substrings of the question.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Lowercase category names.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub event_slugs: Vec<String>,
+    #[serde(default)]
+    pub condition_ids: Vec<String>,
+}
+
+impl MarketRules {
+    pub fn is_empty(&self) -> bool {
+        self.keywords.is_empty()
+            && self.categories.is_empty()
+            && self.event_slugs.is_empty()
+            && self.condition_ids.is_empty()
+    }
+
+    pub fn matches(&self, market: &Market) -> bool {
+        let question = market.question.to_lowercase();
+        self.keywords
+            .iter()
+            .any(|k| question.contains(&k.to_lowercase()))
+            || self
+                .categories
+                .iter()
+                .any(|c| c == market.category.as_str())
+            || market
+                .event_slug
+                .as_ref()
+                .is_some_and(|slug| self.event_slugs.contains(slug))
+            || self.condition_ids.contains(&market.condition_id)
+    }
+}
+
+/// Whether `market` passes the `include` whitelist (if any) and isn't
+/// caught by the `exclude` blacklist.
+pub fn is_allowed(include: &MarketRules, exclude: &MarketRules, market: &Market) -> bool {
+    (include.is_empty() || include.matches(market)) && !exclude.matches(market)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    fn market(condition_id: &str, question: &str, category: MarketCategory) -> Market {
+        Market {
+            condition_id: condition_id.to_string(),
+            question: question.to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            tokens: Vec::new(),
+            end_date: Utc::now(),
+            category,
+            volume_24h: Decimal::ZERO,
+            active: true,
+            event_id: None,
+            event_slug: Some("fed-decision".to_string()),
+            neg_risk: false,
+            description: String::new(),
+            resolution_source: None,
+        }
+    }
+
+    #[test]
+    fn test_exclude_overrides_include() {
+        let include = MarketRules {
+            categories: vec!["politics".to_string()],
+            event_slugs: vec!["fed-decision".to_string()],
+            ..Default::default()
+        };
+        let exclude = MarketRules {
+            keywords: vec!["Assassinat".to_string()],
+            condition_ids: vec!["0xbad".to_string()],
+            ..Default::default()
+        };
+
+        let election = market("0x1", "Who wins the election?", MarketCategory::Politics);
+        let attack = market(
+            "0x2",
+            "Will there be an assassination attempt?",
+            MarketCategory::Politics,
+        );
+        let banned = market("0xbad", "Will the Fed cut rates?", MarketCategory::Crypto);
+        let fed = market("0x3", "Will the Fed cut rates?", MarketCategory::Crypto);
+        let mut sports = market("0x4", "Who wins the NBA finals?", MarketCategory::Sports);
+        sports.event_slug = None;
+
+        assert!(is_allowed(&include, &exclude, &election));
+        assert!(!is_allowed(&include, &exclude, &attack));
+        assert!(!is_allowed(&include, &exclude, &banned));
+        assert!(is_allowed(&include, &exclude, &fed));
+        assert!(!is_allowed(&include, &exclude, &sports));
+        // No whitelist: everything not excluded is allowed
+        assert!(is_allowed(&MarketRules::default(), &exclude, &sports));
+    }
+}
@@ -1,65 +1,223 @@
 //! Market discovery and filtering.
 //!
 //! Scans Polymarket for trading candidates that pass liquidity,
-//! spread, and resolution-date filters.
+//! spread, and resolution-date filters. A [`ScanScheduler`] lets each
+//! category be scanned on its own cadence, so slow-moving categories don't
+//! spend order-book and valuation calls every cycle. Order books are fetched
+//! several at a time, bounded by `book_concurrency`. Markets recently
+//! valued with no edge are queued behind fresh ones, so a capped evaluation
+//! budget goes to markets that haven't been looked at yet. With
+//! `[scanning.new_listings]` enabled, markets first seen recently are
+//! flagged as new listings (see [`crate::market::listings`]). Markets
+//! matching a `[scanning.watchlist]` topic get a lower volume floor, skip
+//! category cadences and are queued first (see [`crate::market::watchlist`]).
+//! With `[scanning.liquidity]` enabled, candidate books are recorded and
+//! markets with a poor liquidity history are queued last (see
+//! [`crate::market::liquidity`]).
 
-use anyhow::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{info, instrument, warn};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, info, instrument, warn};
 
 use crate::config::ScanningConfig;
-use crate::market::models::MarketCandidate;
-use crate::market::polymarket::{MarketFilters, PolymarketClient};
+use crate::db::store::Store;
+use crate::market::api::MarketApi;
+use crate::market::models::{
+    BookLiquidity, Market, MarketCandidate, MarketCategory, OrderBookSnapshot,
+};
+use crate::market::polymarket::MarketFilters;
+use crate::market::{clarity, liquidity, rules};
 
 pub struct MarketScanner {
-    client: Arc<PolymarketClient>,
+    client: Arc<dyn MarketApi>,
     config: ScanningConfig,
+    scheduler: ScanScheduler,
+    history: Option<Store>,
+}
+
+/// Tracks when each category was last scanned against its cadence.
+#[derive(Debug, Clone)]
+pub struct ScanScheduler {
+    cadences: HashMap<String, Duration>,
+    last_scanned: HashMap<String, DateTime<Utc>>,
+}
+
+impl ScanScheduler {
+    pub fn new(cadence_seconds: &HashMap<String, u64>) -> Self {
+        Self {
+            cadences: cadence_seconds
+                .iter()
+                .map(|(category, &secs)| (category.clone(), Duration::seconds(secs as i64)))
+                .collect(),
+            last_scanned: HashMap::new(),
+        }
+    }
+
+    /// Whether `category` should be scanned at `now`.
+    pub fn is_due(&self, category: &MarketCategory, now: DateTime<Utc>) -> bool {
+        let Some(cadence) = self.cadences.get(category.as_str()) else {
+            return true;
+        };
+        self.last_scanned
+            .get(category.as_str())
+            .map_or(true, |&last| now - last >= *cadence)
+    }
+
+    pub fn mark_scanned(&mut self, category: &MarketCategory, now: DateTime<Utc>) {
+        self.last_scanned.insert(category.as_str().to_string(), now);
+    }
 }
 
 impl MarketScanner {
-    pub fn new(client: Arc<PolymarketClient>, config: ScanningConfig) -> Self {
-        Self { client, config }
+    pub fn new(client: Arc<dyn MarketApi>, config: ScanningConfig) -> Self {
+        let scheduler = ScanScheduler::new(&config.cadence_seconds);
+        Self {
+            client,
+            config,
+            scheduler,
+            history: None,
+        }
+    }
+
+    /// Order candidates using the `market_evaluations` history in `store`,
+    /// and record market sightings and order books there to detect new
+    /// listings and score liquidity.
+    pub fn with_evaluation_history(mut self, store: Store) -> Self {
+        self.history = Some(store);
+        self
     }
 
     /// Scan markets and return candidates worth evaluating.
-    #[instrument(skip(self))]
     pub async fn scan(&self) -> Result<Vec<MarketCandidate>> {
+        self.scan_where(|_| true).await
+    }
+
+    /// Scan only the categories whose cadence has elapsed, and record them
+    /// as scanned.
+    pub async fn scan_due(&mut self) -> Result<Vec<MarketCandidate>> {
+        let now = Utc::now();
+        let scheduler = self.scheduler.clone();
+        let mut scanned: Vec<MarketCategory> = Vec::new();
+        let candidates = self
+            .scan_where(|category| {
+                let due = scheduler.is_due(category, now);
+                if due && !scanned.contains(category) {
+                    scanned.push(category.clone());
+                }
+                due
+            })
+            .await?;
+        for category in &scanned {
+            self.scheduler.mark_scanned(category, now);
+        }
+        Ok(candidates)
+    }
+
+    /// Scan markets in categories accepted by `include`.
+    #[instrument(skip(self, include))]
+    async fn scan_where(
+        &self,
+        mut include: impl FnMut(&MarketCategory) -> bool,
+    ) -> Result<Vec<MarketCandidate>> {
+        let watchlist = &self.config.watchlist;
         let filters = MarketFilters {
-            min_volume_24h: self.config.min_volume_24h,
+            min_volume_24h: watchlist.discovery_min_volume(self.config.min_volume_24h),
             max_resolution_days: self.config.max_resolution_days,
             max_markets: self.config.max_markets,
             max_spread_pct: self.config.max_spread_pct,
         };
 
-        let markets = self.client.get_markets(&filters).await?;
-        info!(count = markets.len(), "Markets discovered");
-
-        let mut candidates = Vec::new();
-
-        for market in markets {
-            for token in &market.tokens {
-                match self.client.get_order_book(&token.token_id).await {
-                    Ok(book) => {
-                        // Filter by spread
-                        if book.spread <= self.config.max_spread_pct {
-                            candidates.push(MarketCandidate {
-                                market: market.clone(),
-                                order_book: book,
-                            });
-                            // Only take one token per market for now (YES side)
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        warn!(
-                            token_id = %token.token_id,
-                            error = %e,
-                            "Failed to get order book, skipping"
-                        );
-                    }
+        let discovered = self.client.get_markets(&filters).await?;
+        let total = discovered.len();
+        let allowed: Vec<_> = discovered
+            .into_iter()
+            .filter(|m| watchlist.has_volume(m, self.config.min_volume_24h))
+            .filter(|m| rules::is_allowed(&self.config.include, &self.config.exclude, m))
+            .collect();
+        info!(count = total, "Markets discovered");
+        if allowed.len() < total {
+            debug!(
+                skipped = total - allowed.len(),
+                "Markets skipped — below volume minimum or excluded by scanning rules"
+            );
+        }
+        let eligible = allowed.len();
+        let markets: Vec<_> = allowed
+            .into_iter()
+            .filter(|m| {
+                let score = clarity::clarity_score(m);
+                if score < self.config.min_clarity_score {
+                    debug!(market = %m.condition_id, %score, "Market skipped — unclear resolution criteria");
+                    return false;
                 }
+                true
+            })
+            .filter(|m| include(&m.category) || watchlist.matches(m))
+            .collect();
+        if markets.len() < eligible {
+            debug!(
+                skipped = eligible - markets.len(),
+                "Markets skipped — unclear or category not due for a scan"
+            );
+        }
+
+        let scores = self.liquidity_scores().await;
+        let markets =
+            liquidity::order_by_liquidity(markets, &scores, self.config.liquidity.min_score);
+        let markets = watchlist.prioritize(self.deprioritize_no_edge(markets).await);
+        let watched = markets.iter().filter(|m| watchlist.matches(m)).count();
+        if watched > 0 {
+            info!(watched, "Watchlist markets queued first");
+        }
+
+        // Books are fetched `book_concurrency` markets at a time. Each
+        // request still waits on the client's rate limiter; failed or slow
+        // books only drop their own market.
+        let permits = Arc::new(Semaphore::new(self.config.book_concurrency.max(1)));
+        let timeout = std::time::Duration::from_millis(self.config.book_timeout_ms);
+        let config = Arc::new(self.config.clone());
+        let mut join_set = JoinSet::new();
+        for (index, market) in markets.into_iter().enumerate() {
+            let client = self.client.clone();
+            let permits = permits.clone();
+            let config = config.clone();
+            join_set.spawn(async move {
+                let _permit = permits.acquire_owned().await.ok()?;
+                let book = first_tradable_book(client.as_ref(), &market, &config, timeout).await?;
+                Some((
+                    index,
+                    MarketCandidate {
+                        market,
+                        order_book: book,
+                        new_listing: false,
+                        liquidity_score: None,
+                    },
+                ))
+            });
+        }
+
+        let mut indexed = Vec::new();
+        while let Some(joined) = join_set.join_next().await {
+            match joined {
+                Ok(Some(candidate)) => indexed.push(candidate),
+                Ok(None) => {}
+                Err(e) => warn!(error = %e, "Order book fetch task failed"),
             }
         }
+        // Keep the listing order deprioritize_no_edge chose
+        indexed.sort_by_key(|(index, _)| *index);
+        let mut candidates: Vec<MarketCandidate> = indexed.into_iter().map(|(_, c)| c).collect();
+        for candidate in &mut candidates {
+            candidate.liquidity_score = scores.get(&candidate.market.condition_id).copied();
+        }
+        self.flag_new_listings(&mut candidates).await;
+        self.record_books(&candidates).await;
 
         info!(
             candidates = candidates.len(),
@@ -67,4 +225,264 @@ impl MarketScanner {
         );
         Ok(candidates)
     }
+
+    /// Record every candidate as seen and flag the ones first seen within
+    /// `new_listings.max_age_hours`.
+    async fn flag_new_listings(&self, candidates: &mut [MarketCandidate]) {
+        let config = &self.config.new_listings;
+        let Some(store) = self.history.as_ref().filter(|_| config.enabled) else {
+            return;
+        };
+        let ids: Vec<String> = candidates
+            .iter()
+            .map(|c| c.market.condition_id.clone())
+            .collect();
+        let new = match store
+            .record_market_listings(&ids, store.clock().now(), config.max_age_hours)
+            .await
+        {
+            Ok(new) => new,
+            Err(e) => {
+                warn!(error = %e, "Failed to record market listings");
+                return;
+            }
+        };
+        for candidate in candidates.iter_mut() {
+            candidate.new_listing = new.contains(&candidate.market.condition_id);
+        }
+        if !new.is_empty() {
+            info!(new_listings = new.len(), "New listings found");
+        }
+    }
+
+    /// Liquidity scores of markets with enough recent order book history.
+    async fn liquidity_scores(&self) -> HashMap<String, Decimal> {
+        let config = &self.config.liquidity;
+        let Some(store) = self.history.as_ref().filter(|_| config.enabled) else {
+            return HashMap::new();
+        };
+        let since = Utc::now() - Duration::hours(config.lookback_hours as i64);
+        match store.get_order_book_snapshots_since(since).await {
+            Ok(snapshots) => liquidity::scores(snapshots, config),
+            Err(e) => {
+                warn!(error = %e, "Failed to load order book history — markets left unscored");
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Snapshot each candidate's book for future liquidity scores.
+    async fn record_books(&self, candidates: &[MarketCandidate]) {
+        let config = &self.config.liquidity;
+        let Some(store) = self.history.as_ref().filter(|_| config.enabled) else {
+            return;
+        };
+        for candidate in candidates {
+            if let Err(e) = store
+                .insert_order_book_snapshot(
+                    &candidate.market.condition_id,
+                    &candidate.order_book,
+                    config.depth,
+                )
+                .await
+            {
+                warn!(error = %e, "Failed to record order book snapshot");
+                return;
+            }
+        }
+    }
+
+    /// Move markets repeatedly evaluated without an edge to the back.
+    async fn deprioritize_no_edge(&self, markets: Vec<Market>) -> Vec<Market> {
+        let threshold = self.config.no_edge_deprioritize_after;
+        let Some(store) = self.history.as_ref().filter(|_| threshold > 0) else {
+            return markets;
+        };
+        match store
+            .get_no_edge_evaluation_counts(self.config.no_edge_lookback_hours)
+            .await
+        {
+            Ok(counts) => order_by_evaluation_history(markets, &counts, threshold),
+            Err(e) => {
+                warn!(error = %e, "Failed to load evaluation history — keeping scan order");
+                markets
+            }
+        }
+    }
+}
+
+/// The first of `market`'s tokens whose book is liquid enough and within
+/// `max_spread_pct` (normally YES). Each fetch gets `timeout`, including any
+/// wait for the rate limiter.
+async fn first_tradable_book(
+    client: &dyn MarketApi,
+    market: &Market,
+    config: &ScanningConfig,
+    timeout: std::time::Duration,
+) -> Option<OrderBookSnapshot> {
+    for token in &market.tokens {
+        let book = match tokio::time::timeout(timeout, client.get_order_book(&token.token_id)).await
+        {
+            Ok(Ok(book)) => book,
+            Ok(Err(e)) => {
+                warn!(
+                    token_id = %token.token_id,
+                    error = %e,
+                    "Failed to get order book, skipping"
+                );
+                continue;
+            }
+            Err(_) => {
+                warn!(
+                    token_id = %token.token_id,
+                    timeout_ms = config.book_timeout_ms,
+                    "Order book fetch timed out, skipping"
+                );
+                continue;
+            }
+        };
+        if passes_book_filters(&book, config) {
+            // Only take one token per market for now (YES side)
+            return Some(book);
+        }
+    }
+    None
+}
+
+/// Whether `book` is liquid enough to trade: both sides present (or one,
+/// when `allow_one_sided_books` is set) and the spread within
+/// `max_spread_pct`. One-sided books carry the spread measured from their
+/// fallback price.
+fn passes_book_filters(book: &OrderBookSnapshot, config: &ScanningConfig) -> bool {
+    let liquid = match book.liquidity {
+        BookLiquidity::TwoSided => true,
+        BookLiquidity::OneSided => config.allow_one_sided_books,
+        BookLiquidity::Empty => false,
+    };
+    liquid && book.spread <= config.max_spread_pct
+}
+
+/// Stable reorder: markets with fewer than `threshold` no-edge evaluations
+/// keep their order up front; the rest follow, least-evaluated first.
+pub fn order_by_evaluation_history(
+    markets: Vec<Market>,
+    no_edge_counts: &HashMap<String, i64>,
+    threshold: u32,
+) -> Vec<Market> {
+    let count = |m: &Market| no_edge_counts.get(&m.condition_id).copied().unwrap_or(0);
+    let (mut stale, mut fresh): (Vec<Market>, Vec<Market>) = markets
+        .into_iter()
+        .partition(|m| count(m) >= i64::from(threshold));
+    if !stale.is_empty() {
+        debug!(
+            deprioritized = stale.len(),
+            "Markets repeatedly valued with no edge queued last"
+        );
+    }
+    stale.sort_by_key(|m| count(m));
+    fresh.append(&mut stale);
+    fresh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduler_respects_cadence() {
+        let cadences = HashMap::from([("politics".to_string(), 3600), ("sports".to_string(), 0)]);
+        let mut scheduler = ScanScheduler::new(&cadences);
+        let now = Utc::now();
+        let politics = MarketCategory::Politics;
+
+        assert!(scheduler.is_due(&politics, now));
+        scheduler.mark_scanned(&politics, now);
+        assert!(!scheduler.is_due(&politics, now + Duration::minutes(30)));
+        assert!(scheduler.is_due(&politics, now + Duration::hours(1)));
+
+        scheduler.mark_scanned(&MarketCategory::Sports, now);
+        assert!(scheduler.is_due(&MarketCategory::Sports, now));
+        // Unlisted categories scan every cycle
+        assert!(scheduler.is_due(&MarketCategory::Weather, now));
+    }
+
+    fn market(condition_id: &str) -> Market {
+        Market {
+            condition_id: condition_id.to_string(),
+            question: format!("Market {condition_id}?"),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            tokens: Vec::new(),
+            end_date: Utc::now(),
+            category: MarketCategory::Crypto,
+            volume_24h: rust_decimal::Decimal::ZERO,
+            active: true,
+            event_id: None,
+            event_slug: None,
+            neg_risk: false,
+            description: String::new(),
+            resolution_source: None,
+        }
+    }
+
+    #[test]
+    fn test_no_edge_markets_queued_last() {
+        let markets = vec![market("a"), market("b"), market("c"), market("d")];
+        let counts = HashMap::from([
+            ("a".to_string(), 5),
+            ("b".to_string(), 1),
+            ("c".to_string(), 2),
+        ]);
+
+        let ordered: Vec<String> = order_by_evaluation_history(markets, &counts, 2)
+            .into_iter()
+            .map(|m| m.condition_id)
+            .collect();
+        assert_eq!(ordered, ["b", "d", "c", "a"]);
+    }
+
+    #[test]
+    fn test_book_filters_let_priced_one_sided_books_through() {
+        use crate::market::models::PriceLevel;
+        use rust_decimal_macros::dec;
+
+        let config: crate::config::AppConfig =
+            toml::from_str(include_str!("../../config/default.toml")).unwrap();
+        let mut config = config.scanning;
+        let level = |price| PriceLevel {
+            price,
+            size: dec!(100),
+        };
+        let book = |bids: Vec<PriceLevel>, asks: Vec<PriceLevel>, spread| OrderBookSnapshot {
+            token_id: "t".to_string(),
+            liquidity: BookLiquidity::of(&bids, &asks),
+            bids,
+            asks,
+            spread,
+            midpoint: dec!(0.5),
+            implied_probability: dec!(0.5),
+            timestamp: Utc::now(),
+        };
+
+        assert!(passes_book_filters(
+            &book(vec![level(dec!(0.49))], vec![level(dec!(0.51))], dec!(0.02)),
+            &config
+        ));
+        assert!(!passes_book_filters(
+            &book(vec![level(dec!(0.40))], vec![level(dec!(0.60))], dec!(0.20)),
+            &config
+        ));
+
+        // Bids only, spread measured from a fallback price of 0.5
+        let one_sided = book(vec![level(dec!(0.49))], Vec::new(), dec!(0.02));
+        assert!(!passes_book_filters(&one_sided, &config));
+        config.allow_one_sided_books = true;
+        assert!(passes_book_filters(&one_sided, &config));
+        // No fallback price: left at the maximum spread
+        let unpriced = book(vec![level(dec!(0.49))], Vec::new(), dec!(1));
+        assert!(!passes_book_filters(&unpriced, &config));
+        assert!(!passes_book_filters(
+            &book(Vec::new(), Vec::new(), dec!(0.02)),
+            &config
+        ));
+    }
 }
@@ -0,0 +1,147 @@
+//! Topic watchlist for targeted market hunting.
+//!
+//! Markets whose question mentions a watched topic ("CPI", "Lakers",
+//! "Hurricane") are high priority for the scanner: they are kept down to
+//! the watchlist's own `min_volume_24h` instead of the regular minimum,
+//! scanned every cycle regardless of their category's cadence, and queued
+//! ahead of every other candidate so they are valued (with their data
+//! fetched) even when the scan turns up more markets than the cycle can
+//! value.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::market::models::Market;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchlistConfig {
+    /// Case-insensitive words or phrases matched against the question.
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Volume floor for watched markets, in place of
+    /// `scanning.min_volume_24h`. Never raises it.
+    #[serde(default = "default_min_volume_24h")]
+    pub min_volume_24h: Decimal,
+}
+
+impl Default for WatchlistConfig {
+    fn default() -> Self {
+        Self {
+            topics: Vec::new(),
+            min_volume_24h: default_min_volume_24h(),
+        }
+    }
+}
+
+fn default_min_volume_24h() -> Decimal {
+    dec!(500)
+}
+
+impl WatchlistConfig {
+    pub fn is_empty(&self) -> bool {
+        self.topics.iter().all(|t| t.trim().is_empty())
+    }
+
+    /// Whether `market`'s question mentions a watched topic as a whole
+    /// word or phrase, so "CPI" doesn't match "CPIs" or "ICPI".
+    pub fn matches(&self, market: &Market) -> bool {
+        let question = market.question.to_lowercase();
+        self.topics
+            .iter()
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .any(|topic| contains_phrase(&question, &topic))
+    }
+
+    /// Volume floor discovery must use to see watched markets.
+    pub fn discovery_min_volume(&self, regular: Decimal) -> Decimal {
+        if self.is_empty() {
+            regular
+        } else {
+            regular.min(self.min_volume_24h)
+        }
+    }
+
+    /// Whether `market` clears the volume floor that applies to it.
+    pub fn has_volume(&self, market: &Market, regular: Decimal) -> bool {
+        market.volume_24h >= regular
+            || (market.volume_24h >= self.min_volume_24h && self.matches(market))
+    }
+
+    /// Move watched markets to the front, keeping the order within each group.
+    pub fn prioritize(&self, markets: Vec<Market>) -> Vec<Market> {
+        if self.is_empty() {
+            return markets;
+        }
+        let (mut watched, rest): (Vec<Market>, Vec<Market>) =
+            markets.into_iter().partition(|m| self.matches(m));
+        watched.extend(rest);
+        watched
+    }
+}
+
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric());
+    text.match_indices(phrase).any(|(at, _)| {
+        !is_word(text[..at].chars().next_back())
+            && !is_word(text[at + phrase.len()..].chars().next())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+    use chrono::Utc;
+
+    fn market(id: &str, question: &str, volume: Decimal) -> Market {
+        Market {
+            condition_id: id.to_string(),
+            question: question.to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            tokens: Vec::new(),
+            end_date: Utc::now(),
+            category: MarketCategory::Other("economics".to_string()),
+            volume_24h: volume,
+            active: true,
+            event_id: None,
+            event_slug: None,
+            neg_risk: false,
+            description: String::new(),
+            resolution_source: None,
+        }
+    }
+
+    #[test]
+    fn test_watched_markets_first_with_lower_volume_floor() {
+        let watchlist = WatchlistConfig {
+            topics: vec!["CPI".to_string(), "Hurricane Milton".to_string()],
+            min_volume_24h: dec!(500),
+        };
+        let regular = dec!(5000);
+        let busy = market("busy", "Will the Fed cut rates?", dec!(90000));
+        let cpi = market("cpi", "Will May CPI come in above 3%?", dec!(800));
+        let storm = market("storm", "Will Hurricane Milton make landfall?", dec!(20));
+        let thin = market("thin", "Will ICPI stock rise?", dec!(800));
+
+        assert_eq!(watchlist.discovery_min_volume(regular), dec!(500));
+        assert!(watchlist.has_volume(&busy, regular));
+        assert!(watchlist.has_volume(&cpi, regular));
+        // Too thin even for the watchlist, or not watched ("ICPI" isn't "CPI")
+        assert!(!watchlist.has_volume(&storm, regular));
+        assert!(!watchlist.has_volume(&thin, regular));
+
+        let ordered: Vec<String> = watchlist
+            .prioritize(vec![busy, cpi, storm])
+            .into_iter()
+            .map(|m| m.condition_id)
+            .collect();
+        assert_eq!(ordered, ["cpi", "storm", "busy"]);
+
+        // An empty watchlist changes nothing
+        let none = WatchlistConfig::default();
+        assert_eq!(none.discovery_min_volume(regular), regular);
+        assert!(!none.has_volume(&thin, regular));
+    }
+}
@@ -1,68 +1,120 @@
-//! Discord/Telegram alert system.
+//! Alert system.
 //!
-//! Sends notifications via Discord webhooks for trade events,
-//! state changes, and daily summaries.
+//! Formats trade events, state changes and summaries once and routes them to
+//! every configured channel — Discord, Slack, email — subject to that
+//! channel's severity and alert-kind filters.
 
 use anyhow::Result;
 use rust_decimal::Decimal;
-use serde::Serialize;
 use tracing::warn;
 
+use crate::agent::canary::CanaryReport;
+use crate::agent::crash::CrashReport;
+use crate::config::{MonitoringConfig, Secrets};
 use crate::market::models::{AgentState, Side};
+use crate::monitoring::anomaly::Anomaly;
+use crate::monitoring::funding::{FundingLevel, FundingStatus};
 use crate::monitoring::metrics::PerformanceMetrics;
+use crate::monitoring::notify::discord::DiscordNotifier;
+use crate::monitoring::notify::email::EmailNotifier;
+use crate::monitoring::notify::slack::SlackNotifier;
+use crate::monitoring::notify::{
+    AlertKind, ChannelRouting, Embed, EmbedField, Notification, Notifier, Severity,
+};
+use crate::monitoring::watchdog::WatchdogAlert;
+use crate::monitoring::weekly::{pct, usd, TradeHighlight, WeeklyReport};
+use crate::risk::approval::ApprovedOrder;
+use crate::risk::drawdown::{Cooldown, DrawdownReport};
 
-/// Discord webhook client.
+/// Fans alerts out to the configured channels.
 pub struct AlertClient {
-    webhook_url: Option<String>,
-    http: reqwest::Client,
-    enabled: bool,
+    channels: Vec<Channel>,
 }
 
-/// Discord webhook message format.
-#[derive(Debug, Serialize)]
-struct DiscordMessage {
-    content: String,
-    username: String,
+struct Channel {
+    notifier: Box<dyn Notifier>,
+    routing: ChannelRouting,
 }
 
+const EMBED_GREEN: u32 = 0x2e_cc71;
+const EMBED_RED: u32 = 0xe7_4c3c;
+
 impl AlertClient {
+    /// Discord only, receiving every alert.
     pub fn new(webhook_url: Option<String>, enabled: bool) -> Self {
-        Self {
-            enabled: enabled && webhook_url.is_some(),
-            webhook_url,
-            http: reqwest::Client::new(),
+        let client = Self {
+            channels: Vec::new(),
+        };
+        match webhook_url {
+            Some(url) if enabled => {
+                client.with_channel(DiscordNotifier::new(url), ChannelRouting::default())
+            }
+            _ => client,
         }
     }
 
-    /// Send a raw message to Discord.
-    async fn send(&self, message: &str) -> Result<()> {
-        if !self.enabled {
-            return Ok(());
+    /// Every channel enabled in `[monitoring]` that has its credentials set.
+    pub fn from_config(config: &MonitoringConfig, secrets: &Secrets) -> Result<Self> {
+        let mut client = Self {
+            channels: Vec::new(),
+        };
+        if config.discord_enabled {
+            if let Some(url) = secrets.discord_webhook_url.clone() {
+                client = client.with_channel(DiscordNotifier::new(url), config.discord.clone());
+            }
         }
+        if config.slack.enabled {
+            match secrets.slack_webhook_url.clone() {
+                Some(url) => {
+                    client = client.with_channel(SlackNotifier::new(url), config.slack.routing())
+                }
+                None => warn!("Slack alerts enabled but SLACK_WEBHOOK_URL is not set"),
+            }
+        }
+        if config.email.enabled {
+            let notifier = EmailNotifier::new(
+                &config.email,
+                secrets.smtp_username.clone(),
+                secrets.smtp_password.clone(),
+            )?;
+            client = client.with_channel(notifier, config.email.routing());
+        }
+        Ok(client)
+    }
 
-        let Some(ref url) = self.webhook_url else {
-            return Ok(());
-        };
+    /// Add a channel receiving the alerts `routing` allows.
+    pub fn with_channel(
+        mut self,
+        notifier: impl Notifier + 'static,
+        routing: ChannelRouting,
+    ) -> Self {
+        self.channels.push(Channel {
+            notifier: Box::new(notifier),
+            routing,
+        });
+        self
+    }
 
-        let payload = DiscordMessage {
-            content: message.to_string(),
-            username: "Polymarket Agent".to_string(),
-        };
+    /// Deliver a message to every channel that accepts it. A failing
+    /// channel is logged and doesn't stop the others.
+    async fn send(&self, kind: AlertKind, severity: Severity, message: String) -> Result<()> {
+        self.dispatch(Notification::new(kind, severity, message))
+            .await
+    }
 
-        match self.http.post(url).json(&payload).send().await {
-            Ok(response) => {
-                if !response.status().is_success() {
-                    warn!(
-                        status = %response.status(),
-                        "Discord webhook returned non-success status"
-                    );
-                }
+    async fn dispatch(&self, notification: Notification) -> Result<()> {
+        for channel in &self.channels {
+            if !channel.routing.allows(&notification) {
+                continue;
             }
-            Err(e) => {
-                warn!(error = %e, "Failed to send Discord alert");
+            if let Err(e) = channel.notifier.notify(&notification).await {
+                warn!(
+                    channel = channel.notifier.name(),
+                    error = %e,
+                    "Failed to send alert"
+                );
             }
         }
-
         Ok(())
     }
 
@@ -83,7 +135,35 @@ impl AlertClient {
              Edge: {:.1}%",
             edge * Decimal::from(100),
         );
-        self.send(&msg).await
+        self.send(AlertKind::TradePlaced, Severity::Info, msg).await
+    }
+
+    /// Alert: Large trade waiting for human approval.
+    pub async fn approval_requested(
+        &self,
+        approval: &ApprovedOrder,
+        dashboard_url: &str,
+        ttl_seconds: u64,
+    ) -> Result<()> {
+        let order = &approval.order;
+        let msg = format!(
+            "**Approval Required** (#{})\n\
+             Market: {}\n\
+             Side: {} @ ${}\n\
+             Size: ${}\n\
+             Edge: {:.1}%\n\
+             Approve or reject under Pending Approvals: {dashboard_url}\n\
+             Expires in {} min",
+            approval.id,
+            order.market_question,
+            order.side,
+            order.price,
+            approval.size_usd,
+            order.edge * Decimal::from(100),
+            ttl_seconds / 60,
+        );
+        self.send(AlertKind::ApprovalRequested, Severity::Warning, msg)
+            .await
     }
 
     /// Alert: Trade resolved.
@@ -102,7 +182,8 @@ impl AlertClient {
              Side: {side}\n\
              P&L: {emoji}${pnl}"
         );
-        self.send(&msg).await
+        self.send(AlertKind::TradeResolved, Severity::Info, msg)
+            .await
     }
 
     /// Alert: Agent state change.
@@ -113,10 +194,10 @@ impl AlertClient {
         balance: Decimal,
     ) -> Result<()> {
         let urgency = match new_state {
-            AgentState::Dead => "CRITICAL",
-            AgentState::CriticalSurvival => "WARNING",
-            AgentState::LowFuel => "NOTICE",
-            AgentState::Alive => "INFO",
+            AgentState::Dead => Severity::Critical,
+            AgentState::CriticalSurvival => Severity::Warning,
+            AgentState::LowFuel => Severity::Notice,
+            AgentState::Alive => Severity::Info,
         };
 
         let msg = format!(
@@ -124,7 +205,50 @@ impl AlertClient {
              {old_state} -> {new_state}\n\
              Balance: ${balance}"
         );
-        self.send(&msg).await
+        self.send(AlertKind::StateChange, urgency, msg).await
+    }
+
+    /// Alert: Daily API budget spent.
+    pub async fn api_spend_cap(&self, budget: Decimal) -> Result<()> {
+        let msg = format!(
+            "**[NOTICE] Daily API Budget Reached**\n\
+             Daily limit: ${budget}\n\
+             New valuations paused until 00:00 UTC (reduced operations)"
+        );
+        self.send(AlertKind::SpendCap, Severity::Notice, msg).await
+    }
+
+    /// Alert: Funding level escalated, or recovered to healthy.
+    pub async fn funding(&self, status: &FundingStatus, paused: bool) -> Result<()> {
+        let (label, severity) = match status.level {
+            FundingLevel::Healthy => ("RECOVERED", Severity::Info),
+            FundingLevel::Warning => ("WARNING", Severity::Warning),
+            FundingLevel::Critical => ("CRITICAL", Severity::Critical),
+        };
+        let mut msg = format!("**[{label}] Funding {}**\n{status}", status.level);
+        if status.level != FundingLevel::Healthy {
+            msg.push_str("\nTop up the wallet to keep trading");
+        }
+        if paused {
+            msg.push_str("\nNew entries paused until funding recovers");
+        }
+        self.send(AlertKind::Funding, severity, msg).await
+    }
+
+    /// Alert: Drawdown cooldown started, with what drove the losses.
+    pub async fn drawdown_cooldown(&self, report: &DrawdownReport) -> Result<()> {
+        let msg = format!("**[WARNING] Drawdown Cooldown**\n```\n{report}```");
+        self.send(AlertKind::Drawdown, Severity::Warning, msg).await
+    }
+
+    /// Alert: Drawdown cooldown over, new entries resumed.
+    pub async fn drawdown_resumed(&self, cooldown: &Cooldown) -> Result<()> {
+        let msg = format!(
+            "**[RECOVERED] Drawdown Cooldown Ended**\n\
+             Paused since cycle {}; new entries resumed",
+            cooldown.triggered_cycle
+        );
+        self.send(AlertKind::Drawdown, Severity::Info, msg).await
     }
 
     /// Alert: Bankroll milestone reached.
@@ -134,13 +258,78 @@ impl AlertClient {
              Balance reached ${milestone}\n\
              Current: ${balance}"
         );
-        self.send(&msg).await
+        self.send(AlertKind::Milestone, Severity::Info, msg).await
     }
 
     /// Alert: Daily performance summary.
     pub async fn daily_summary(&self, metrics: &PerformanceMetrics) -> Result<()> {
         let msg = format!("**Daily Summary**\n```\n{}\n```", metrics.summary());
-        self.send(&msg).await
+        self.send(AlertKind::DailySummary, Severity::Info, msg)
+            .await
+    }
+
+    /// Alert: Weekly performance report, as an embed. `html_path` is where
+    /// the full HTML copy was written, if it was.
+    pub async fn weekly_report(
+        &self,
+        report: &WeeklyReport,
+        html_path: Option<&str>,
+    ) -> Result<()> {
+        self.dispatch(Notification::with_embed(
+            AlertKind::WeeklyReport,
+            Severity::Info,
+            weekly_embed(report, html_path),
+        ))
+        .await
+    }
+
+    /// Alert: Post-deploy canary result.
+    pub async fn canary_result(&self, report: &CanaryReport) -> Result<()> {
+        let (status, severity) = if report.passed() {
+            ("PASSED", Severity::Info)
+        } else {
+            ("FAILED", Severity::Critical)
+        };
+        let msg = format!("**Canary {status}**\n```\n{}\n```", report.summary());
+        self.send(AlertKind::Canary, severity, msg).await
+    }
+
+    /// Alert: Unusual agent behavior.
+    pub async fn anomaly(&self, cycle: u64, anomaly: &Anomaly) -> Result<()> {
+        let msg = format!(
+            "**[WARNING] Anomaly Detected**\n\
+             Cycle: {cycle}\n\
+             {anomaly}"
+        );
+        self.send(AlertKind::Anomaly, Severity::Warning, msg).await
+    }
+
+    /// Alert: Watchdog incident or recovery.
+    pub async fn watchdog(&self, alert: &WatchdogAlert) -> Result<()> {
+        let (label, severity) = if alert.is_recovery() {
+            ("RECOVERED", Severity::Info)
+        } else if matches!(alert, WatchdogAlert::TaskDied { .. }) {
+            ("WATCHDOG", Severity::Critical)
+        } else {
+            ("WATCHDOG", Severity::Warning)
+        };
+        self.send(
+            AlertKind::Watchdog,
+            severity,
+            format!("**[{label}]** {alert}"),
+        )
+        .await
+    }
+
+    /// Alert: Database grew past its configured size cap.
+    pub async fn database_size(&self, size_mb: u64, max_size_mb: u64) -> Result<()> {
+        let msg = format!(
+            "**[WARNING] Database Size**\n\
+             Size: {size_mb} MB (cap {max_size_mb} MB)\n\
+             Shorten `database.maintenance.retention_days` or move old archive tables out"
+        );
+        self.send(AlertKind::DatabaseSize, Severity::Warning, msg)
+            .await
     }
 
     /// Alert: Agent death.
@@ -151,11 +340,112 @@ impl AlertClient {
              Final balance: ${balance}\n\
              The agent has been shut down due to insufficient funds."
         );
-        self.send(&msg).await
+        self.send(AlertKind::AgentDeath, Severity::Critical, msg)
+            .await
+    }
+
+    /// Alert: A cycle panicked and the agent is stopping.
+    pub async fn panic(&self, cycle: u64, report: &CrashReport) -> Result<()> {
+        let msg = format!(
+            "**[CRITICAL] Agent Panic**\n\
+             Cycle: {cycle}\n\
+             Panic: {report}\n\
+             Open orders are being cancelled and the agent is stopping; \
+             the backtrace is in the log."
+        );
+        self.send(AlertKind::Crash, Severity::Critical, msg).await
+    }
+
+    /// Alert: The previous run ended in a panic.
+    pub async fn previous_crash(&self, report: &CrashReport) -> Result<()> {
+        let msg = format!(
+            "**[WARNING] Restarted After Crash**\n\
+             Panic: {report}\n\
+             Verify open orders and positions on the exchange"
+        );
+        self.send(AlertKind::Crash, Severity::Warning, msg).await
     }
 
     pub fn is_enabled(&self) -> bool {
-        self.enabled
+        !self.channels.is_empty()
+    }
+}
+
+fn weekly_embed(report: &WeeklyReport, html_path: Option<&str>) -> Embed {
+    let highlight = |h: &Option<TradeHighlight>| match h {
+        Some(h) => format!("{} — {} {}", usd(h.pnl), h.direction, h.market),
+        None => "—".to_string(),
+    };
+    let categories = if report.by_category.is_empty() {
+        "No resolved trades".to_string()
+    } else {
+        report
+            .by_category
+            .iter()
+            .map(|c| {
+                format!(
+                    "{}: {} trades, {} won, {}",
+                    c.category,
+                    c.trades,
+                    pct(c.win_rate()),
+                    usd(c.pnl)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let calibration = match (report.brier, report.calibration_drift()) {
+        (Some(brier), Some(drift)) => format!(
+            "Brier {} ({}{} vs before)",
+            brier.round_dp(4),
+            if drift > Decimal::ZERO { "+" } else { "" },
+            drift.round_dp(4)
+        ),
+        (Some(brier), None) => format!("Brier {}", brier.round_dp(4)),
+        _ => "No resolved predictions".to_string(),
+    };
+
+    let mut description = format!(
+        "{} – {}",
+        report.start.format("%Y-%m-%d"),
+        report.end.format("%Y-%m-%d")
+    );
+    if let Some(path) = html_path {
+        description.push_str(&format!("\nFull report: `{path}`"));
+    }
+
+    Embed {
+        title: "Weekly Performance Report".to_string(),
+        description,
+        color: if report.pnl >= Decimal::ZERO {
+            EMBED_GREEN
+        } else {
+            EMBED_RED
+        },
+        fields: vec![
+            EmbedField::new("Net P&L", usd(report.pnl), true),
+            EmbedField::new(
+                "Win rate",
+                format!(
+                    "{} ({}/{})",
+                    pct(report.win_rate()),
+                    report.wins,
+                    report.trades_resolved
+                ),
+                true,
+            ),
+            EmbedField::new("Trades placed", report.trades_placed.to_string(), true),
+            EmbedField::new("By category", categories, false),
+            EmbedField::new("Biggest win", highlight(&report.biggest_win), false),
+            EmbedField::new("Biggest loss", highlight(&report.biggest_loss), false),
+            EmbedField::new("Calibration", calibration, true),
+            EmbedField::new("API spend", usd(report.api_cost), true),
+            EmbedField::new(
+                "Uptime",
+                format!("{} ({} cycles)", pct(report.uptime), report.cycles),
+                true,
+            ),
+        ],
     }
 }
 
@@ -176,7 +466,9 @@ pub fn check_milestone(old_balance: Decimal, new_balance: Decimal) -> Option<Dec
 #[cfg(test)]
 mod tests {
     use super::*;
+    use async_trait::async_trait;
     use rust_decimal_macros::dec;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_alert_client_disabled() {
@@ -227,6 +519,84 @@ mod tests {
         assert_eq!(milestone, Some(dec!(50)));
     }
 
+    #[test]
+    fn test_weekly_embed() {
+        let now = chrono::Utc::now();
+        let report = WeeklyReport {
+            start: now - chrono::Duration::days(7),
+            end: now,
+            trades_placed: 4,
+            trades_resolved: 2,
+            wins: 1,
+            pnl: dec!(-2.5),
+            bankroll_start: None,
+            bankroll_end: None,
+            by_category: Vec::new(),
+            biggest_win: None,
+            biggest_loss: None,
+            brier: Some(dec!(0.2)),
+            prior_brier: Some(dec!(0.15)),
+            api_cost: dec!(1.25),
+            api_cost_by_provider: Default::default(),
+            cycles: 10,
+            uptime: dec!(1),
+        };
+
+        let embed =
+            serde_json::to_value(weekly_embed(&report, Some("data/reports/w.html"))).unwrap();
+        assert_eq!(embed["color"], EMBED_RED);
+        assert!(embed["description"]
+            .as_str()
+            .unwrap()
+            .ends_with("`data/reports/w.html`"));
+        let fields = embed["fields"].as_array().unwrap();
+        assert_eq!(fields[0]["value"], "$-2.5");
+        assert_eq!(fields[1]["value"], "50.0% (1/2)");
+        assert_eq!(fields[6]["value"], "Brier 0.2 (+0.05 vs before)");
+    }
+
+    /// Records the titles it is asked to deliver.
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl Notifier for Recorder {
+        async fn notify(&self, notification: &Notification) -> Result<()> {
+            self.0.lock().unwrap().push(notification.title());
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "recorder"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_by_channel() {
+        let everything = Arc::new(Mutex::new(Vec::new()));
+        let critical = Arc::new(Mutex::new(Vec::new()));
+        let client = AlertClient::new(None, false)
+            .with_channel(Recorder(everything.clone()), ChannelRouting::default())
+            .with_channel(
+                Recorder(critical.clone()),
+                ChannelRouting::min_severity(Severity::Critical),
+            );
+
+        client
+            .trade_placed("Test market?", Side::Yes, dec!(5), dec!(0.60), dec!(0.10))
+            .await
+            .unwrap();
+        client
+            .state_change(AgentState::LowFuel, AgentState::Dead, dec!(0.5))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *everything.lock().unwrap(),
+            vec!["Trade Placed", "[CRITICAL] State Change"]
+        );
+        assert_eq!(*critical.lock().unwrap(), vec!["[CRITICAL] State Change"]);
+    }
+
     #[tokio::test]
     async fn test_send_disabled_noop() {
         let client = AlertClient::new(None, false);
@@ -0,0 +1,338 @@
+//! Anomaly detection on the agent's own behavior.
+//!
+//! After each cycle the latest numbers are compared against the agent's
+//! recent history. A sudden burst of trades, positions growing, API spend
+//! per cycle jumping, or the win rate collapsing usually means a bug, a bad
+//! prompt, or a regime change — worth a human look before the bankroll
+//! pays for it.
+
+use std::fmt;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::db::store::{CycleRecord, TradeRecord};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnomalyConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Cycles before the latest one that form the per-cycle baseline.
+    #[serde(default = "default_baseline_cycles")]
+    pub baseline_cycles: usize,
+    /// Flag a cycle placing this many times the baseline trade count...
+    #[serde(default = "default_trade_spike_factor")]
+    pub trade_spike_factor: Decimal,
+    /// ...and at least this many trades.
+    #[serde(default = "default_min_spike_trades")]
+    pub min_spike_trades: i64,
+    /// Flag API cost per cycle at this multiple of the baseline.
+    #[serde(default = "default_api_cost_factor")]
+    pub api_cost_factor: Decimal,
+    /// Trades averaged for the recent position size.
+    #[serde(default = "default_recent_trades")]
+    pub recent_trades: usize,
+    /// Flag recent average position size at this multiple of earlier trades'.
+    #[serde(default = "default_position_size_factor")]
+    pub position_size_factor: Decimal,
+    /// Resolved trades in the rolling win-rate window.
+    #[serde(default = "default_win_rate_window")]
+    pub win_rate_window: usize,
+    /// Flag a rolling win rate this far below the earlier win rate.
+    #[serde(default = "default_win_rate_drop")]
+    pub win_rate_drop: Decimal,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            baseline_cycles: default_baseline_cycles(),
+            trade_spike_factor: default_trade_spike_factor(),
+            min_spike_trades: default_min_spike_trades(),
+            api_cost_factor: default_api_cost_factor(),
+            recent_trades: default_recent_trades(),
+            position_size_factor: default_position_size_factor(),
+            win_rate_window: default_win_rate_window(),
+            win_rate_drop: default_win_rate_drop(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_baseline_cycles() -> usize {
+    20
+}
+
+fn default_trade_spike_factor() -> Decimal {
+    dec!(3)
+}
+
+fn default_min_spike_trades() -> i64 {
+    3
+}
+
+fn default_api_cost_factor() -> Decimal {
+    dec!(2)
+}
+
+fn default_recent_trades() -> usize {
+    10
+}
+
+fn default_position_size_factor() -> Decimal {
+    dec!(2)
+}
+
+fn default_win_rate_window() -> usize {
+    20
+}
+
+fn default_win_rate_drop() -> Decimal {
+    dec!(0.25)
+}
+
+/// Minimum baseline cycles before per-cycle checks run.
+const MIN_BASELINE_CYCLES: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnomalyKind {
+    TradeSpike,
+    ApiCostSpike,
+    PositionSizeJump,
+    WinRateCollapse,
+}
+
+impl AnomalyKind {
+    pub fn metric(&self) -> &'static str {
+        match self {
+            AnomalyKind::TradeSpike => "trades per cycle",
+            AnomalyKind::ApiCostSpike => "API cost per cycle",
+            AnomalyKind::PositionSizeJump => "average position size",
+            AnomalyKind::WinRateCollapse => "rolling win rate",
+        }
+    }
+}
+
+/// One flagged metric with its current and baseline values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub kind: AnomalyKind,
+    pub current: Decimal,
+    pub baseline: Decimal,
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is {} (baseline {})",
+            self.kind.metric(),
+            self.current.round_dp(4).normalize(),
+            self.baseline.round_dp(4).normalize()
+        )
+    }
+}
+
+/// Check the latest cycle and trade history for anomalies. `cycles` are
+/// newest first, as from `Store::get_recent_cycles`; `trades` are all trades
+/// in insertion order and `resolved` the resolved ones in resolution order.
+pub fn detect(
+    cycles: &[CycleRecord],
+    trades: &[TradeRecord],
+    resolved: &[TradeRecord],
+    config: &AnomalyConfig,
+) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+
+    if let Some((latest, history)) = cycles.split_first() {
+        let history = &history[..history.len().min(config.baseline_cycles)];
+        if history.len() >= MIN_BASELINE_CYCLES {
+            let current = Decimal::from(latest.trades_placed.unwrap_or(0));
+            let baseline = mean(
+                history
+                    .iter()
+                    .map(|c| Decimal::from(c.trades_placed.unwrap_or(0))),
+            );
+            if current >= Decimal::from(config.min_spike_trades)
+                && current > baseline * config.trade_spike_factor
+            {
+                anomalies.push(Anomaly {
+                    kind: AnomalyKind::TradeSpike,
+                    current,
+                    baseline,
+                });
+            }
+
+            let current = latest.api_cost.unwrap_or(Decimal::ZERO);
+            let baseline = mean(history.iter().map(|c| c.api_cost.unwrap_or(Decimal::ZERO)));
+            if baseline > Decimal::ZERO && current > baseline * config.api_cost_factor {
+                anomalies.push(Anomaly {
+                    kind: AnomalyKind::ApiCostSpike,
+                    current,
+                    baseline,
+                });
+            }
+        }
+    }
+
+    let sizes: Vec<Decimal> = trades.iter().map(position_size).collect();
+    if config.recent_trades > 0 && sizes.len() >= config.recent_trades * 2 {
+        let (earlier, recent) = sizes.split_at(sizes.len() - config.recent_trades);
+        let current = mean(recent.iter().copied());
+        let baseline = mean(earlier.iter().copied());
+        if baseline > Decimal::ZERO && current > baseline * config.position_size_factor {
+            anomalies.push(Anomaly {
+                kind: AnomalyKind::PositionSizeJump,
+                current,
+                baseline,
+            });
+        }
+    }
+
+    if config.win_rate_window > 0 && resolved.len() >= config.win_rate_window * 2 {
+        let (earlier, recent) = resolved.split_at(resolved.len() - config.win_rate_window);
+        let current = win_rate(recent);
+        let baseline = win_rate(earlier);
+        if baseline - current >= config.win_rate_drop {
+            anomalies.push(Anomaly {
+                kind: AnomalyKind::WinRateCollapse,
+                current,
+                baseline,
+            });
+        }
+    }
+
+    anomalies
+}
+
+fn mean(values: impl Iterator<Item = Decimal>) -> Decimal {
+    let (sum, count) = values.fold((Decimal::ZERO, 0u32), |(sum, n), v| (sum + v, n + 1));
+    if count == 0 {
+        Decimal::ZERO
+    } else {
+        sum / Decimal::from(count)
+    }
+}
+
+/// Cost of the position in USD.
+fn position_size(trade: &TradeRecord) -> Decimal {
+    trade.entry_price * trade.size
+}
+
+fn win_rate(trades: &[TradeRecord]) -> Decimal {
+    let wins = trades.iter().filter(|t| t.status == "RESOLVED_WIN").count();
+    Decimal::from(wins) / Decimal::from(trades.len().max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(trades_placed: i64, api_cost: Decimal) -> CycleRecord {
+        CycleRecord {
+            id: None,
+            cycle_number: 0,
+            markets_scanned: None,
+            opportunities_found: None,
+            trades_placed: Some(trades_placed),
+            api_cost: Some(api_cost),
+            bankroll: None,
+            unrealized_pnl: None,
+            agent_state: "ALIVE".to_string(),
+            duration_ms: None,
+            truncated_phases: None,
+            timings: Default::default(),
+            created_at: None,
+        }
+    }
+
+    fn trade(size: Decimal, status: &str) -> TradeRecord {
+        TradeRecord {
+            id: None,
+            cycle: 1,
+            market_id: "m".to_string(),
+            market_question: None,
+            direction: "YES".to_string(),
+            entry_price: dec!(0.50),
+            size,
+            edge_at_entry: dec!(0.1),
+            claude_fair_value: dec!(0.6),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.1),
+            kelly_adjusted: dec!(0.05),
+            status: status.to_string(),
+            pnl: None,
+            created_at: None,
+            resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_trade_and_cost_spikes() {
+        let config = AnomalyConfig::default();
+        let mut cycles = vec![cycle(6, dec!(0.50))];
+        cycles.extend((0..10).map(|_| cycle(1, dec!(0.20))));
+
+        let kinds: Vec<AnomalyKind> = detect(&cycles, &[], &[], &config)
+            .into_iter()
+            .map(|a| a.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![AnomalyKind::TradeSpike, AnomalyKind::ApiCostSpike]
+        );
+
+        // Normal cycle, and too little history to judge
+        cycles[0] = cycle(1, dec!(0.21));
+        assert!(detect(&cycles, &[], &[], &config).is_empty());
+        assert!(detect(&[cycle(9, dec!(5)), cycle(0, dec!(0.1))], &[], &[], &config).is_empty());
+    }
+
+    #[test]
+    fn test_position_size_jump() {
+        let config = AnomalyConfig::default();
+        let mut trades: Vec<TradeRecord> = (0..10).map(|_| trade(dec!(10), "OPEN")).collect();
+        trades.extend((0..10).map(|_| trade(dec!(30), "OPEN")));
+
+        let anomalies = detect(&[], &trades, &[], &config);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::PositionSizeJump);
+        assert_eq!(anomalies[0].current, dec!(15));
+        assert_eq!(anomalies[0].baseline, dec!(5));
+        assert_eq!(
+            anomalies[0].to_string(),
+            "average position size is 15 (baseline 5)"
+        );
+    }
+
+    #[test]
+    fn test_win_rate_collapse() {
+        let config = AnomalyConfig::default();
+        // 70% before, 30% in the last 20
+        let mut resolved = Vec::new();
+        for i in 0..40 {
+            let won = if i < 20 { i % 10 < 7 } else { i % 10 < 3 };
+            resolved.push(trade(
+                dec!(10),
+                if won { "RESOLVED_WIN" } else { "RESOLVED_LOSS" },
+            ));
+        }
+
+        let anomalies = detect(&[], &[], &resolved, &config);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::WinRateCollapse);
+        assert_eq!(anomalies[0].current, dec!(0.3));
+        assert_eq!(anomalies[0].baseline, dec!(0.7));
+    }
+}
@@ -0,0 +1,227 @@
+//! Dashboard access control and response hardening.
+//!
+//! The dashboard can pause trading, approve orders and trip the kill
+//! switch, so anything reachable beyond localhost must be authenticated.
+//! Credentials come from the environment (`DASHBOARD_TOKEN` for scripts,
+//! `DASHBOARD_USERNAME`/`DASHBOARD_PASSWORD` for browsers); with neither set
+//! the dashboard is open, which is only safe on a loopback bind.
+//!
+//! TLS is expected to be terminated by a reverse proxy. Security headers are
+//! added to every response, and HSTS once the proxy reports
+//! `X-Forwarded-Proto: https`.
+
+use axum::extract::{Request, State};
+use axum::http::header::{self, HeaderMap, HeaderValue};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::config::Secrets;
+use crate::monitoring::dashboard::DashboardState;
+
+const REALM: &str = r#"Basic realm="polymarket-agent", charset="UTF-8""#;
+
+/// Accepted dashboard credentials. Either kind is enough when both are set.
+#[derive(Debug, Clone, Default)]
+pub struct DashboardAuth {
+    token: Option<String>,
+    /// Expected `Authorization` header value for basic auth.
+    basic: Option<String>,
+}
+
+impl DashboardAuth {
+    /// No authentication.
+    pub fn open() -> Self {
+        Self::default()
+    }
+
+    pub fn from_secrets(secrets: &Secrets) -> Self {
+        let mut auth = Self::open();
+        if let Some(token) = secrets.dashboard_token.as_deref().filter(|t| !t.is_empty()) {
+            auth = auth.with_token(token);
+        }
+        if let (Some(user), Some(password)) = (
+            secrets.dashboard_username.as_deref(),
+            secrets
+                .dashboard_password
+                .as_deref()
+                .filter(|p| !p.is_empty()),
+        ) {
+            auth = auth.with_basic(user, password);
+        }
+        auth
+    }
+
+    /// Accept `Authorization: Bearer <token>`.
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Accept HTTP basic auth with these credentials.
+    pub fn with_basic(mut self, user: &str, password: &str) -> Self {
+        self.basic = Some(format!(
+            "Basic {}",
+            base64_encode(format!("{user}:{password}").as_bytes())
+        ));
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some() || self.basic.is_some()
+    }
+
+    /// Whether a request with these headers may proceed.
+    pub fn allows(&self, headers: &HeaderMap) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+        let Some(presented) = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+        else {
+            return false;
+        };
+        let token_ok = self.token.as_deref().is_some_and(|token| {
+            presented
+                .strip_prefix("Bearer ")
+                .is_some_and(|p| constant_time_eq(p.trim().as_bytes(), token.as_bytes()))
+        });
+        let basic_ok = self
+            .basic
+            .as_deref()
+            .is_some_and(|expected| constant_time_eq(presented.as_bytes(), expected.as_bytes()));
+        token_ok || basic_ok
+    }
+}
+
+/// Reject unauthenticated requests with a basic-auth challenge, so browsers
+/// prompt for credentials.
+pub async fn require_auth(
+    State(state): State<DashboardState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.auth().allows(request.headers()) {
+        return next.run(request).await;
+    }
+    let challenge = if state.auth().basic.is_some() {
+        REALM
+    } else {
+        "Bearer"
+    };
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, challenge)],
+        "Unauthorized",
+    )
+        .into_response()
+}
+
+/// Add security headers suited to running behind a TLS-terminating proxy.
+pub async fn security_headers(request: Request, next: Next) -> Response {
+    let behind_tls = request
+        .headers()
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|proto| proto.eq_ignore_ascii_case("https"));
+    let is_api = request.uri().path().starts_with("/api");
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    headers.insert(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
+    headers.insert(
+        header::REFERRER_POLICY,
+        HeaderValue::from_static("no-referrer"),
+    );
+    if is_api {
+        headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    }
+    if behind_tls {
+        headers.insert(
+            header::STRICT_TRANSPORT_SECURITY,
+            HeaderValue::from_static("max-age=31536000; includeSubDomains"),
+        );
+    }
+    response
+}
+
+/// Whether `bind` only accepts local connections.
+pub fn is_loopback(bind: &str) -> bool {
+    bind == "localhost"
+        || bind
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(authorization).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b"admin:hunter2"), "YWRtaW46aHVudGVyMg==");
+        assert_eq!(base64_encode(b"abc"), "YWJj");
+        assert_eq!(base64_encode(b"ab"), "YWI=");
+    }
+
+    #[test]
+    fn test_allows() {
+        assert!(DashboardAuth::open().allows(&HeaderMap::new()));
+
+        let auth = DashboardAuth::open()
+            .with_token("s3cret")
+            .with_basic("admin", "hunter2");
+        assert!(!auth.allows(&HeaderMap::new()));
+        assert!(auth.allows(&headers("Bearer s3cret")));
+        assert!(!auth.allows(&headers("Bearer wrong")));
+        assert!(auth.allows(&headers("Basic YWRtaW46aHVudGVyMg==")));
+        assert!(!auth.allows(&headers("Basic YWRtaW46d3Jvbmc=")));
+    }
+
+    #[test]
+    fn test_is_loopback() {
+        assert!(is_loopback("127.0.0.1"));
+        assert!(is_loopback("::1"));
+        assert!(is_loopback("localhost"));
+        assert!(!is_loopback("0.0.0.0"));
+    }
+}
@@ -1,19 +1,51 @@
 //! Web dashboard — axum HTTP server serving REST API + embedded HTML.
+//!
+//! Every route sits behind [`auth::require_auth`]; see [`crate::monitoring::auth`].
+//! The exceptions are the `/live` and `/ready` probes, which carry no
+//! trading data and must work for systemd/Kubernetes without credentials.
+//! The `/api` list endpoints take `limit` (and `status` / `provider` / `tag`
+//! filters where noted in the README) and return the newest records first.
 
 use std::sync::Arc;
 
-use axum::extract::State;
-use axum::http::header;
-use axum::response::{IntoResponse, Json};
-use axum::routing::get;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::middleware;
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::{get, post};
 use axum::Router;
+use chrono::Utc;
 use rust_decimal::Decimal;
+use serde::Deserialize;
 use tokio::task::JoinHandle;
 use tracing::{info, warn};
 
+use crate::agent::control::ControlState;
+use crate::db::export::{self, ExportFormat, ExportTable};
 use crate::db::store::Store;
+use crate::execution::wallet::WalletState;
+use crate::market::polymarket::PolymarketClient;
+use crate::monitoring::auth::{self, DashboardAuth};
 use crate::monitoring::health::HealthState;
-use crate::monitoring::metrics::compute_metrics;
+use crate::monitoring::metrics::{compute_metrics_tagged, equity_curve, pnl_by_day};
+use crate::monitoring::positions::open_positions;
+use crate::risk::approval;
+
+/// Largest page a list endpoint returns; the `/all` routes are unbounded.
+const MAX_LIMIT: i64 = 5000;
+
+const TRADE_STATUSES: [&str; 5] = [
+    "OPEN",
+    "RESOLVED_WIN",
+    "RESOLVED_LOSS",
+    "CANCELLED",
+    "EXPIRED",
+];
+
+/// Limits on trade annotations, so the dashboard stays readable.
+const MAX_TAGS: usize = 10;
+const MAX_TAG_LEN: usize = 40;
+const MAX_NOTES_LEN: usize = 2000;
 
 /// Shared state accessible by all dashboard route handlers.
 #[derive(Clone)]
@@ -21,6 +53,10 @@ pub struct DashboardState {
     store: Arc<Store>,
     health: HealthState,
     initial_bankroll: Decimal,
+    control: ControlState,
+    wallet: WalletState,
+    polymarket: Option<Arc<PolymarketClient>>,
+    auth: Arc<DashboardAuth>,
 }
 
 impl DashboardState {
@@ -29,25 +65,97 @@ impl DashboardState {
             store: Arc::new(store),
             health,
             initial_bankroll,
+            control: ControlState::new(),
+            wallet: WalletState::new(),
+            polymarket: None,
+            auth: Arc::new(DashboardAuth::open()),
         }
     }
+
+    /// Share the agent's control flags with the `/control` endpoints.
+    pub fn with_control(mut self, control: ControlState) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Show the agent's on-chain wallet status at `/api/wallet`.
+    pub fn with_wallet(mut self, wallet: WalletState) -> Self {
+        self.wallet = wallet;
+        self
+    }
+
+    /// Mark open positions to live prices at `/api/positions`.
+    pub fn with_market_client(mut self, client: Arc<PolymarketClient>) -> Self {
+        self.polymarket = Some(client);
+        self
+    }
+
+    /// Require these credentials on every route.
+    pub fn with_auth(mut self, auth: DashboardAuth) -> Self {
+        self.auth = Arc::new(auth);
+        self
+    }
+
+    pub(crate) fn auth(&self) -> &DashboardAuth {
+        &self.auth
+    }
 }
 
 /// Spawn the dashboard HTTP server. Returns a handle that can be aborted.
 pub fn spawn_dashboard(state: DashboardState, bind: &str, port: u16) -> JoinHandle<()> {
-    let addr = format!("{bind}:{port}");
+    let addr = match bind.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V6(_)) => format!("[{bind}]:{port}"),
+        _ => format!("{bind}:{port}"),
+    };
     let addr_clone = addr.clone();
+    if !auth::is_loopback(bind) && !state.auth.is_enabled() {
+        warn!(
+            addr = %addr,
+            "Dashboard exposed beyond localhost WITHOUT authentication — set DASHBOARD_TOKEN or DASHBOARD_USERNAME/DASHBOARD_PASSWORD"
+        );
+    }
 
     tokio::spawn(async move {
         let app = Router::new()
             .route("/", get(index_handler))
+            .route("/positions", get(positions_page_handler))
             .route("/api/health", get(health_handler))
             .route("/api/metrics", get(metrics_handler))
             .route("/api/trades", get(trades_handler))
             .route("/api/trades/all", get(trades_all_handler))
-            .route("/api/cycles", get(cycles_latest_handler))
+            .route("/api/trades/tags", get(trade_tags_handler))
+            .route("/api/trades/{id}/annotations", post(annotate_trade_handler))
+            .route("/api/cycles", get(cycles_handler))
+            .route("/api/cycles/latest", get(cycles_latest_handler))
             .route("/api/cycles/all", get(cycles_all_handler))
             .route("/api/costs", get(costs_handler))
+            .route("/api/costs/all", get(costs_all_handler))
+            .route("/api/export/{table}", get(export_handler))
+            .route("/api/equity", get(equity_handler))
+            .route("/api/pnl_by_day", get(pnl_by_day_handler))
+            .route("/api/wallet", get(wallet_handler))
+            .route("/api/positions", get(positions_handler))
+            .route("/api/orders", get(orders_handler))
+            .route("/api/orders/all", get(orders_all_handler))
+            .route("/api/execution/styles", get(execution_styles_handler))
+            .route("/api/journal", get(journal_handler))
+            .route("/api/journal/stats", get(journal_stats_handler))
+            .route("/api/approvals", get(approvals_handler))
+            // POST only: a prefetched or embedded link must never place an order
+            .route("/api/approvals/{id}/approve", post(approve_handler))
+            .route("/api/approvals/{id}/reject", post(reject_handler))
+            .route("/control", get(control_status_handler))
+            .route("/control/pause", post(pause_handler))
+            .route("/control/resume", post(resume_handler))
+            .route("/control/kill-switch", post(kill_switch_handler))
+            .route("/control/flatten", post(flatten_handler))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth::require_auth,
+            ))
+            .route("/live", get(live_handler))
+            .route("/ready", get(ready_handler))
+            .layer(middleware::from_fn(auth::security_headers))
             .with_state(state);
 
         let listener = match tokio::net::TcpListener::bind(&addr_clone).await {
@@ -74,22 +182,165 @@ async fn index_handler() -> impl IntoResponse {
     ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], html)
 }
 
+async fn positions_page_handler() -> impl IntoResponse {
+    let html = include_str!("../../static/positions.html");
+    ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], html)
+}
+
 async fn health_handler(State(state): State<DashboardState>) -> impl IntoResponse {
     let data = state.health.to_json().await;
     Json(data)
 }
 
-async fn metrics_handler(State(state): State<DashboardState>) -> impl IntoResponse {
-    match compute_metrics(&state.store, state.initial_bankroll).await {
+async fn live_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    probe_response(state.health.liveness(Utc::now()).await)
+}
+
+async fn ready_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    probe_response(state.health.readiness().await)
+}
+
+fn probe_response((ok, body): (bool, serde_json::Value)) -> Response {
+    let status = if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(body)).into_response()
+}
+
+async fn metrics_handler(
+    State(state): State<DashboardState>,
+    Query(query): Query<MetricsQuery>,
+) -> impl IntoResponse {
+    match compute_metrics_tagged(&state.store, state.initial_bankroll, query.tag.as_deref()).await {
         Ok(metrics) => Json(serde_json::to_value(&metrics).unwrap_or_default()),
         Err(e) => Json(serde_json::json!({"error": e.to_string()})),
     }
 }
 
-async fn trades_handler(State(state): State<DashboardState>) -> impl IntoResponse {
-    match state.store.get_recent_trades(50).await {
-        Ok(trades) => Json(serde_json::to_value(&trades).unwrap_or_default()),
-        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+#[derive(Debug, Deserialize)]
+struct MetricsQuery {
+    tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TradesQuery {
+    status: Option<String>,
+    tag: Option<String>,
+    limit: Option<i64>,
+}
+
+/// New tags and note for a trade; both replace the current ones.
+#[derive(Debug, Deserialize)]
+struct AnnotationRequest {
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CyclesQuery {
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CostsQuery {
+    provider: Option<String>,
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JournalQuery {
+    /// `traded` or `skipped`.
+    decision: Option<String>,
+    limit: Option<i64>,
+}
+
+/// Clamp a requested page size to `1..=MAX_LIMIT`.
+fn page_size(limit: Option<i64>, default: i64) -> i64 {
+    limit.unwrap_or(default).clamp(1, MAX_LIMIT)
+}
+
+/// Normalise a `status` filter, rejecting values no trade can have.
+fn parse_trade_status(status: &str) -> Option<&'static str> {
+    TRADE_STATUSES
+        .into_iter()
+        .find(|s| s.eq_ignore_ascii_case(status))
+}
+
+/// Trim and lowercase tags, dropping blanks and duplicates, and trim the
+/// note (blank means none). Rejects annotations over the size limits.
+fn normalize_annotation(
+    request: AnnotationRequest,
+) -> Result<(Vec<String>, Option<String>), String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in request.tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || tags.contains(&tag) {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LEN {
+            return Err(format!(
+                "tag '{tag}' is longer than {MAX_TAG_LEN} characters"
+            ));
+        }
+        tags.push(tag);
+    }
+    if tags.len() > MAX_TAGS {
+        return Err(format!("at most {MAX_TAGS} tags per trade"));
+    }
+
+    let notes = request
+        .notes
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    if notes
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_NOTES_LEN)
+    {
+        return Err(format!("notes are longer than {MAX_NOTES_LEN} characters"));
+    }
+    Ok((tags, notes))
+}
+
+fn bad_request(message: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({"error": message})),
+    )
+        .into_response()
+}
+
+async fn trades_handler(
+    State(state): State<DashboardState>,
+    Query(query): Query<TradesQuery>,
+) -> Response {
+    let status = match query.status.as_deref() {
+        Some(raw) => match parse_trade_status(raw) {
+            Some(status) => Some(status),
+            None => {
+                return bad_request(format!(
+                    "unknown status '{raw}'; expected one of {}",
+                    TRADE_STATUSES.join(", ")
+                ))
+            }
+        },
+        None => None,
+    };
+    match state
+        .store
+        .get_trades_filtered(status, query.tag.as_deref(), page_size(query.limit, 50))
+        .await
+    {
+        Ok(trades) => Json(serde_json::to_value(&trades).unwrap_or_default()).into_response(),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})).into_response(),
     }
 }
 
@@ -100,6 +351,57 @@ async fn trades_all_handler(State(state): State<DashboardState>) -> impl IntoRes
     }
 }
 
+async fn trade_tags_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state.store.get_trade_tags().await {
+        Ok(tags) => Json(serde_json::json!(tags
+            .into_iter()
+            .map(|(tag, trades)| serde_json::json!({"tag": tag, "trades": trades}))
+            .collect::<Vec<_>>())),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn annotate_trade_handler(
+    State(state): State<DashboardState>,
+    Path(id): Path<i64>,
+    Json(request): Json<AnnotationRequest>,
+) -> Response {
+    let (tags, notes) = match normalize_annotation(request) {
+        Ok(annotation) => annotation,
+        Err(message) => return bad_request(message),
+    };
+    match state
+        .store
+        .annotate_trade(id, &tags, notes.as_deref())
+        .await
+    {
+        Ok(true) => {
+            info!(id, ?tags, "Trade annotated from dashboard");
+            Json(serde_json::json!({"id": id, "tags": tags, "notes": notes})).into_response()
+        }
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("no trade with id {id}")})),
+        )
+            .into_response(),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})).into_response(),
+    }
+}
+
+async fn cycles_handler(
+    State(state): State<DashboardState>,
+    Query(query): Query<CyclesQuery>,
+) -> impl IntoResponse {
+    match state
+        .store
+        .get_recent_cycles(page_size(query.limit, 100))
+        .await
+    {
+        Ok(cycles) => Json(serde_json::to_value(&cycles).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
 async fn cycles_latest_handler(State(state): State<DashboardState>) -> impl IntoResponse {
     match state.store.get_latest_cycle().await {
         Ok(Some(cycle)) => Json(serde_json::to_value(&cycle).unwrap_or_default()),
@@ -115,9 +417,279 @@ async fn cycles_all_handler(State(state): State<DashboardState>) -> impl IntoRes
     }
 }
 
-async fn costs_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+async fn costs_handler(
+    State(state): State<DashboardState>,
+    Query(query): Query<CostsQuery>,
+) -> impl IntoResponse {
+    match state
+        .store
+        .get_recent_api_costs(query.provider.as_deref(), page_size(query.limit, 500))
+        .await
+    {
+        Ok(costs) => Json(serde_json::to_value(&costs).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn costs_all_handler(State(state): State<DashboardState>) -> impl IntoResponse {
     match state.store.get_all_api_costs().await {
         Ok(costs) => Json(serde_json::to_value(&costs).unwrap_or_default()),
         Err(e) => Json(serde_json::json!({"error": e.to_string()})),
     }
 }
+
+/// Download a whole table as CSV (default), JSON or Parquet.
+async fn export_handler(
+    State(state): State<DashboardState>,
+    Path(table): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Response {
+    let Some(table) = ExportTable::from_name(&table) else {
+        return bad_request(format!(
+            "unknown table '{table}'; expected one of {}",
+            ExportTable::ALL.map(|t| t.name()).join(", ")
+        ));
+    };
+    let format = match query.format.as_deref() {
+        None => ExportFormat::Csv,
+        Some(raw) => match ExportFormat::from_name(raw) {
+            Some(format) => format,
+            None => {
+                return bad_request(format!(
+                    "unknown format '{raw}'; expected csv, json or parquet"
+                ))
+            }
+        },
+    };
+
+    let body = match export::load_table(&state.store, table).await {
+        Ok(rows) => rows.render(format),
+        Err(e) => Err(e),
+    };
+    match body {
+        Ok(body) => (
+            [
+                (header::CONTENT_TYPE, format.content_type().to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!(
+                        "attachment; filename=\"{}.{}\"",
+                        table.name(),
+                        format.extension()
+                    ),
+                ),
+            ],
+            body,
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+async fn equity_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state.store.get_all_cycles().await {
+        Ok(cycles) => Json(serde_json::to_value(equity_curve(&cycles)).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn pnl_by_day_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state.store.get_all_trades().await {
+        Ok(trades) => Json(serde_json::to_value(pnl_by_day(&trades)).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn wallet_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state.wallet.get().await {
+        Some(status) => Json(serde_json::to_value(&status).unwrap_or_default()),
+        // Paper mode, or no live cycle has run yet
+        None => Json(serde_json::Value::Null),
+    }
+}
+
+async fn positions_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match open_positions(&state.store, state.polymarket.as_deref()).await {
+        Ok(positions) => Json(serde_json::to_value(&positions).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn orders_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state.store.get_orders_by_status("OPEN").await {
+        Ok(orders) => Json(serde_json::to_value(&orders).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn orders_all_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state.store.get_recent_orders(100).await {
+        Ok(orders) => Json(serde_json::to_value(&orders).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn journal_handler(
+    State(state): State<DashboardState>,
+    Query(query): Query<JournalQuery>,
+) -> impl IntoResponse {
+    let decision = query.decision.map(|d| d.to_uppercase());
+    match state
+        .store
+        .get_recent_trade_ideas(decision.as_deref(), page_size(query.limit, 100))
+        .await
+    {
+        Ok(ideas) => Json(serde_json::to_value(&ideas).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn journal_stats_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state.store.get_idea_stats().await {
+        Ok(stats) => Json(serde_json::to_value(&stats).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn execution_styles_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state.store.get_execution_style_stats().await {
+        Ok(stats) => Json(serde_json::json!(stats
+            .iter()
+            .map(|s| serde_json::json!({
+                "style": s.style,
+                "orders": s.orders,
+                "filled_orders": s.filled_orders,
+                "fill_rate": s.fill_rate().round_dp(4).to_string(),
+                "requested_shares": s.requested_shares,
+                "filled_shares": s.filled_shares,
+            }))
+            .collect::<Vec<_>>())),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn approvals_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    match state
+        .store
+        .get_approvals_by_status(approval::PENDING_APPROVAL)
+        .await
+    {
+        Ok(approvals) => Json(serde_json::to_value(&approvals).unwrap_or_default()),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+async fn approve_handler(
+    State(state): State<DashboardState>,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    decide_approval(&state, id, true).await
+}
+
+async fn reject_handler(
+    State(state): State<DashboardState>,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    decide_approval(&state, id, false).await
+}
+
+async fn control_status_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    Json(serde_json::json!({"status": state.control.status()}))
+}
+
+async fn pause_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    state.control.pause();
+    warn!("Trading paused from dashboard");
+    Json(serde_json::json!({"status": state.control.status()}))
+}
+
+async fn resume_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    state.control.resume();
+    info!("Trading resumed from dashboard");
+    Json(serde_json::json!({"status": state.control.status()}))
+}
+
+async fn kill_switch_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    state.control.kill();
+    warn!("KILL SWITCH engaged from dashboard — cancelling orders and halting");
+    Json(serde_json::json!({"status": state.control.status()}))
+}
+
+async fn flatten_handler(State(state): State<DashboardState>) -> impl IntoResponse {
+    state.control.flatten();
+    warn!("FLATTEN requested from dashboard — cancelling orders and selling all positions");
+    Json(serde_json::json!({"status": state.control.status()}))
+}
+
+async fn decide_approval(
+    state: &DashboardState,
+    id: i64,
+    approve: bool,
+) -> Json<serde_json::Value> {
+    match approval::decide(&state.store, id, approve, Utc::now()).await {
+        Ok(true) => {
+            info!(id, approve, "Trade approval decided from dashboard");
+            let status = if approve {
+                approval::APPROVED
+            } else {
+                approval::REJECTED
+            };
+            Json(serde_json::json!({"id": id, "status": status}))
+        }
+        Ok(false) => Json(serde_json::json!({
+            "id": id,
+            "error": "approval not pending (already decided, expired, or unknown)"
+        })),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_size_clamps() {
+        assert_eq!(page_size(None, 50), 50);
+        assert_eq!(page_size(Some(0), 50), 1);
+        assert_eq!(page_size(Some(1_000_000), 50), MAX_LIMIT);
+    }
+
+    #[test]
+    fn test_normalize_annotation() {
+        let (tags, notes) = normalize_annotation(AnnotationRequest {
+            tags: vec![
+                " Manual Override ".to_string(),
+                "manual override".to_string(),
+                "".to_string(),
+                "weather".to_string(),
+            ],
+            notes: Some("  ".to_string()),
+        })
+        .unwrap();
+        assert_eq!(tags, vec!["manual override", "weather"]);
+        assert_eq!(notes, None);
+
+        assert!(normalize_annotation(AnnotationRequest {
+            tags: vec!["x".repeat(MAX_TAG_LEN + 1)],
+            notes: None,
+        })
+        .is_err());
+        assert!(normalize_annotation(AnnotationRequest {
+            tags: (0..=MAX_TAGS).map(|i| i.to_string()).collect(),
+            notes: None,
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_trade_status() {
+        assert_eq!(parse_trade_status("open"), Some("OPEN"));
+        assert_eq!(parse_trade_status("RESOLVED_WIN"), Some("RESOLVED_WIN"));
+        assert_eq!(parse_trade_status("WON"), None);
+    }
+}
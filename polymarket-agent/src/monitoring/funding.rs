@@ -0,0 +1,195 @@
+//! Funding monitor.
+//!
+//! The survival states only react once the balance crosses
+//! `low_fuel_threshold`. This module raises the alarm earlier: each cycle it
+//! compares the balance with multiples of that threshold and projects the
+//! runway — days until the death threshold at the lifetime burn rate — and
+//! grades the result [`FundingLevel::Warning`] or [`FundingLevel::Critical`].
+//! Alerts go out when the level escalates, with a notice on recovery, and
+//! with `pause_entries` a critical level stops new positions until the
+//! wallet is topped up.
+
+use std::fmt;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+const SECONDS_PER_DAY: Decimal = dec!(86400);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FundingConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Warn below this multiple of `agent.low_fuel_threshold`...
+    #[serde(default = "default_warning_balance_multiple")]
+    pub warning_balance_multiple: Decimal,
+    /// ...or with less than this many days of runway.
+    #[serde(default = "default_warning_runway_days")]
+    pub warning_runway_days: Decimal,
+    /// Critical below this multiple of `agent.low_fuel_threshold`...
+    #[serde(default = "default_critical_balance_multiple")]
+    pub critical_balance_multiple: Decimal,
+    /// ...or with less than this many days of runway.
+    #[serde(default = "default_critical_runway_days")]
+    pub critical_runway_days: Decimal,
+    /// Open no new positions while funding is critical.
+    #[serde(default)]
+    pub pause_entries: bool,
+}
+
+impl Default for FundingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            warning_balance_multiple: default_warning_balance_multiple(),
+            warning_runway_days: default_warning_runway_days(),
+            critical_balance_multiple: default_critical_balance_multiple(),
+            critical_runway_days: default_critical_runway_days(),
+            pause_entries: false,
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_warning_balance_multiple() -> Decimal {
+    dec!(2)
+}
+
+fn default_warning_runway_days() -> Decimal {
+    dec!(14)
+}
+
+fn default_critical_balance_multiple() -> Decimal {
+    dec!(1.25)
+}
+
+fn default_critical_runway_days() -> Decimal {
+    dec!(3)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FundingLevel {
+    #[default]
+    Healthy,
+    Warning,
+    Critical,
+}
+
+impl fmt::Display for FundingLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            FundingLevel::Healthy => "HEALTHY",
+            FundingLevel::Warning => "WARNING",
+            FundingLevel::Critical => "CRITICAL",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Funding assessment for one cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingStatus {
+    pub level: FundingLevel,
+    pub balance: Decimal,
+    pub low_fuel_threshold: Decimal,
+    /// Days until the death threshold at the current burn rate; `None`
+    /// while nothing is being spent.
+    pub runway_days: Option<Decimal>,
+}
+
+impl fmt::Display for FundingStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Balance: ${} (low fuel at ${})\nRunway: ",
+            self.balance.round_dp(2),
+            self.low_fuel_threshold
+        )?;
+        match self.runway_days {
+            Some(days) => write!(f, "{} days", days.round_dp(1)),
+            None => write!(f, "unlimited (no spend yet)"),
+        }
+    }
+}
+
+/// Days `balance` lasts above `death_threshold` spending `burn_per_cycle`
+/// every `cycle_interval_seconds`.
+pub fn runway_days(
+    balance: Decimal,
+    death_threshold: Decimal,
+    burn_per_cycle: Decimal,
+    cycle_interval_seconds: u64,
+) -> Option<Decimal> {
+    if burn_per_cycle <= Decimal::ZERO || cycle_interval_seconds == 0 {
+        return None;
+    }
+    let cycles_per_day = SECONDS_PER_DAY / Decimal::from(cycle_interval_seconds);
+    let days = (balance - death_threshold).max(Decimal::ZERO) / (burn_per_cycle * cycles_per_day);
+    Some(days)
+}
+
+/// Grade `balance` and `runway_days` against the configured thresholds.
+pub fn assess(
+    config: &FundingConfig,
+    balance: Decimal,
+    low_fuel_threshold: Decimal,
+    runway_days: Option<Decimal>,
+) -> FundingStatus {
+    let below = |multiple: Decimal, days: Decimal| {
+        balance < low_fuel_threshold * multiple || runway_days.is_some_and(|r| r < days)
+    };
+    let level = if below(
+        config.critical_balance_multiple,
+        config.critical_runway_days,
+    ) {
+        FundingLevel::Critical
+    } else if below(config.warning_balance_multiple, config.warning_runway_days) {
+        FundingLevel::Warning
+    } else {
+        FundingLevel::Healthy
+    };
+    FundingStatus {
+        level,
+        balance,
+        low_fuel_threshold,
+        runway_days,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runway_days() {
+        // $0.01 per 10-minute cycle is $1.44/day; $14.40 above death lasts 10 days
+        assert_eq!(
+            runway_days(dec!(24.40), dec!(10), dec!(0.01), 600),
+            Some(dec!(10))
+        );
+        assert_eq!(
+            runway_days(dec!(5), dec!(10), dec!(0.01), 600),
+            Some(Decimal::ZERO)
+        );
+        assert_eq!(runway_days(dec!(100), dec!(10), Decimal::ZERO, 600), None);
+    }
+
+    #[test]
+    fn test_assess_escalates_on_balance_or_runway() {
+        let config = FundingConfig::default();
+        let level = |balance, runway| assess(&config, balance, dec!(20), runway).level;
+
+        assert_eq!(level(dec!(100), None), FundingLevel::Healthy);
+        assert_eq!(level(dec!(100), Some(dec!(30))), FundingLevel::Healthy);
+        // Below 2x low fuel, or under two weeks of runway
+        assert_eq!(level(dec!(39), None), FundingLevel::Warning);
+        assert_eq!(level(dec!(100), Some(dec!(10))), FundingLevel::Warning);
+        // Below 1.25x low fuel, or under three days
+        assert_eq!(level(dec!(24), None), FundingLevel::Critical);
+        assert_eq!(level(dec!(100), Some(dec!(2))), FundingLevel::Critical);
+    }
+}
@@ -1,24 +1,74 @@
-//! Health check HTTP endpoint.
+//! Health check HTTP endpoints.
 //!
-//! Provides a tiny HTTP server on localhost:9090/health that returns
-//! agent status as JSON. Used by external uptime monitors.
+//! `/health` returns the agent status as JSON for uptime monitors. For
+//! systemd and Kubernetes probes there are two more:
+//!
+//! - `/live` — 503 once no cycle has completed within the liveness timeout,
+//!   i.e. the process is up but the loop is hung and should be restarted.
+//! - `/ready` — 503 unless every dependency (SQLite, the Polymarket API and,
+//!   if enabled, the Anthropic key) passed its last check. Checks run in the
+//!   background every `readiness_interval_seconds`, and each dependency
+//!   keeps its last error for diagnosis.
+//!
+//! The same probes are served unauthenticated by the dashboard.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tokio::io::AsyncWriteExt;
 use tokio::net::TcpListener;
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use tracing::{info, warn};
 
+use crate::db::store::Store;
 use crate::market::models::AgentState;
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthCheckConfig {
+    #[serde(default = "default_readiness_interval_seconds")]
+    pub readiness_interval_seconds: u64,
+    /// Per-check timeout.
+    #[serde(default = "default_check_timeout_seconds")]
+    pub check_timeout_seconds: u64,
+    /// Verify the Anthropic API key as part of readiness.
+    #[serde(default = "default_check_anthropic")]
+    pub check_anthropic: bool,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            readiness_interval_seconds: default_readiness_interval_seconds(),
+            check_timeout_seconds: default_check_timeout_seconds(),
+            check_anthropic: default_check_anthropic(),
+        }
+    }
+}
+
+fn default_readiness_interval_seconds() -> u64 {
+    30
+}
+
+fn default_check_timeout_seconds() -> u64 {
+    5
+}
+
+fn default_check_anthropic() -> bool {
+    true
+}
+
 /// Shared health state updated by the agent loop.
 #[derive(Clone)]
 pub struct HealthState {
     inner: Arc<RwLock<HealthData>>,
+    /// `/live` fails once no cycle has completed for this long.
+    liveness_timeout: Option<chrono::Duration>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -29,6 +79,24 @@ struct HealthData {
     started_at: DateTime<Utc>,
     last_cycle_at: Option<DateTime<Utc>>,
     uptime_seconds: i64,
+    /// Data sources whose last fetches failed, with the failure streak.
+    failing_sources: BTreeMap<String, u32>,
+    /// Data sources demoted for poor health, with consecutive degraded
+    /// fetches.
+    degraded_sources: BTreeMap<String, u32>,
+    /// Latest readiness check of each dependency.
+    dependencies: BTreeMap<String, DependencyStatus>,
+}
+
+/// Result of the latest check of one dependency. The last error is kept
+/// after recovery so an intermittent failure can still be diagnosed.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyStatus {
+    pub healthy: bool,
+    pub checked_at: DateTime<Utc>,
+    pub latency_ms: u64,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
 }
 
 impl HealthState {
@@ -41,10 +109,20 @@ impl HealthState {
                 started_at: Utc::now(),
                 last_cycle_at: None,
                 uptime_seconds: 0,
+                failing_sources: BTreeMap::new(),
+                degraded_sources: BTreeMap::new(),
+                dependencies: BTreeMap::new(),
             })),
+            liveness_timeout: None,
         }
     }
 
+    /// Fail `/live` when no cycle completes within `timeout`.
+    pub fn with_liveness_timeout(mut self, timeout: Duration) -> Self {
+        self.liveness_timeout = chrono::Duration::from_std(timeout).ok();
+        self
+    }
+
     /// Get health data as a serializable JSON value.
     pub async fn to_json(&self) -> serde_json::Value {
         let data = self.inner.read().await;
@@ -66,6 +144,258 @@ impl HealthState {
             };
         });
     }
+
+    /// Record each data source's consecutive fetch failures.
+    pub fn record_source_failures(&self, failures: BTreeMap<String, u32>) {
+        let inner = self.inner.clone();
+        tokio::spawn(async move {
+            let mut data = inner.write().await;
+            data.failing_sources = failures.into_iter().filter(|(_, n)| *n > 0).collect();
+        });
+    }
+
+    /// Record the data sources currently degraded.
+    pub fn record_degraded_sources(&self, degraded: BTreeMap<String, u32>) {
+        let inner = self.inner.clone();
+        tokio::spawn(async move {
+            inner.write().await.degraded_sources = degraded;
+        });
+    }
+
+    pub async fn degraded_sources(&self) -> BTreeMap<String, u32> {
+        self.inner.read().await.degraded_sources.clone()
+    }
+
+    /// When the last cycle completed (the start time before the first one),
+    /// and the sources currently failing.
+    pub async fn heartbeat(&self) -> (DateTime<Utc>, BTreeMap<String, u32>) {
+        let data = self.inner.read().await;
+        (
+            data.last_cycle_at.unwrap_or(data.started_at),
+            data.failing_sources.clone(),
+        )
+    }
+
+    /// Record the outcome of one dependency check.
+    pub async fn record_dependency(&self, name: &str, result: Result<()>, latency: Duration) {
+        let mut data = self.inner.write().await;
+        let now = Utc::now();
+        let previous = data.dependencies.get(name);
+        let (last_error, last_error_at) = match &result {
+            Ok(()) => (
+                previous.and_then(|p| p.last_error.clone()),
+                previous.and_then(|p| p.last_error_at),
+            ),
+            Err(e) => (Some(format!("{e:#}")), Some(now)),
+        };
+        data.dependencies.insert(
+            name.to_string(),
+            DependencyStatus {
+                healthy: result.is_ok(),
+                checked_at: now,
+                latency_ms: latency.as_millis() as u64,
+                last_error,
+                last_error_at,
+            },
+        );
+    }
+
+    /// Whether the agent loop is still completing cycles, with details.
+    pub async fn liveness(&self, now: DateTime<Utc>) -> (bool, serde_json::Value) {
+        let data = self.inner.read().await;
+        let since = now - data.last_cycle_at.unwrap_or(data.started_at);
+        let alive = self
+            .liveness_timeout
+            .map_or(true, |timeout| since <= timeout);
+        (
+            alive,
+            serde_json::json!({
+                "status": if alive { "alive" } else { "stalled" },
+                "seconds_since_cycle": since.num_seconds(),
+                "cycle_number": data.cycle_number,
+            }),
+        )
+    }
+
+    /// Whether every dependency passed its last check and the agent is not
+    /// dead, with per-dependency details. Not ready until the first checks
+    /// have run.
+    pub async fn readiness(&self) -> (bool, serde_json::Value) {
+        let data = self.inner.read().await;
+        let ready = !data.dependencies.is_empty()
+            && data.dependencies.values().all(|d| d.healthy)
+            && data.status != "dead";
+        (
+            ready,
+            serde_json::json!({
+                "status": if ready { "ready" } else { "not_ready" },
+                "agent_state": data.agent_state,
+                "dependencies": data.dependencies,
+            }),
+        )
+    }
+}
+
+/// One external dependency probed for readiness.
+#[async_trait]
+pub trait DependencyCheck: Send + Sync {
+    /// Fails with a description of what is wrong.
+    async fn check(&self) -> Result<()>;
+
+    /// Name reported under `dependencies`.
+    fn name(&self) -> &str;
+}
+
+/// The SQLite pool can run a query.
+pub struct SqliteCheck {
+    store: Store,
+}
+
+impl SqliteCheck {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for SqliteCheck {
+    async fn check(&self) -> Result<()> {
+        sqlx::query("SELECT 1")
+            .execute(self.store.pool())
+            .await
+            .context("SQLite query failed")?;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "sqlite"
+    }
+}
+
+/// The Polymarket CLOB answers HTTP. Any non-5xx response counts.
+pub struct PolymarketCheck {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl PolymarketCheck {
+    pub fn new(clob_base_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: clob_base_url.trim_end_matches('/').to_string() + "/",
+        }
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for PolymarketCheck {
+    async fn check(&self) -> Result<()> {
+        let response = self
+            .http
+            .get(&self.url)
+            .send()
+            .await
+            .context("Polymarket API unreachable")?;
+        if response.status().is_server_error() {
+            bail!("Polymarket API returned {}", response.status());
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "polymarket"
+    }
+}
+
+/// The Anthropic API accepts the configured key. Listing models is free.
+pub struct AnthropicKeyCheck {
+    http: reqwest::Client,
+    api_key: String,
+    url: String,
+}
+
+impl AnthropicKeyCheck {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_key,
+            url: "https://api.anthropic.com/v1/models".to_string(),
+        }
+    }
+
+    /// Point at a different host, for tests.
+    pub fn with_url(mut self, url: &str) -> Self {
+        self.url = url.to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for AnthropicKeyCheck {
+    async fn check(&self) -> Result<()> {
+        let response = self
+            .http
+            .get(&self.url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .send()
+            .await
+            .context("Anthropic API unreachable")?;
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                bail!("Anthropic API key rejected ({})", response.status())
+            }
+            status => bail!("Anthropic API returned {status}"),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "anthropic"
+    }
+}
+
+/// Run every check once, each bounded by `timeout`, and record the results.
+pub async fn run_checks(
+    state: &HealthState,
+    checks: &[Box<dyn DependencyCheck>],
+    timeout: Duration,
+) {
+    for check in checks {
+        let started = Instant::now();
+        let result = match tokio::time::timeout(timeout, check.check()).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!("timed out after {}s", timeout.as_secs())),
+        };
+        if let Err(e) = &result {
+            warn!(dependency = check.name(), error = %e, "Readiness check failed");
+        }
+        state
+            .record_dependency(check.name(), result, started.elapsed())
+            .await;
+    }
+}
+
+/// Re-run the readiness checks every `readiness_interval_seconds`.
+pub fn spawn_readiness_checks(
+    config: HealthCheckConfig,
+    state: HealthState,
+    checks: Vec<Box<dyn DependencyCheck>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let every = Duration::from_secs(config.readiness_interval_seconds.max(1));
+        let timeout = Duration::from_secs(config.check_timeout_seconds.max(1));
+        let names: Vec<&str> = checks.iter().map(|c| c.name()).collect();
+        info!(
+            ?names,
+            interval_s = every.as_secs(),
+            "Readiness checks started"
+        );
+        loop {
+            run_checks(&state, &checks, timeout).await;
+            tokio::time::sleep(every).await;
+        }
+    })
 }
 
 impl Default for HealthState {
@@ -100,18 +430,25 @@ pub fn spawn_health_server(state: HealthState) -> JoinHandle<()> {
 
             let state = state.clone();
             tokio::spawn(async move {
-                // Read the request (we don't care about the contents)
                 let mut buf = [0u8; 1024];
-                let _ = tokio::io::AsyncReadExt::read(&mut socket, &mut buf).await;
+                let n = tokio::io::AsyncReadExt::read(&mut socket, &mut buf)
+                    .await
+                    .unwrap_or(0);
 
-                // Build JSON response
-                let data = state.inner.read().await;
-                let body = serde_json::to_string(&*data).unwrap_or_else(|_| {
-                    r#"{"status":"error","message":"serialization failed"}"#.to_string()
-                });
+                let (ok, body) = match request_path(&buf[..n]) {
+                    "/live" => state.liveness(Utc::now()).await,
+                    "/ready" => state.readiness().await,
+                    _ => (true, state.to_json().await),
+                };
+                let status = if ok {
+                    "200 OK"
+                } else {
+                    "503 Service Unavailable"
+                };
+                let body = body.to_string();
 
                 let response = format!(
-                    "HTTP/1.1 200 OK\r\n\
+                    "HTTP/1.1 {status}\r\n\
                      Content-Type: application/json\r\n\
                      Content-Length: {}\r\n\
                      Connection: close\r\n\
@@ -127,6 +464,15 @@ pub fn spawn_health_server(state: HealthState) -> JoinHandle<()> {
     })
 }
 
+/// Path of an HTTP request line, e.g. `/ready` from `GET /ready HTTP/1.1`.
+fn request_path(request: &[u8]) -> &str {
+    std::str::from_utf8(request)
+        .ok()
+        .and_then(|r| r.split_whitespace().nth(1))
+        .map(|path| path.split('?').next().unwrap_or(path))
+        .unwrap_or("/")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +544,102 @@ mod tests {
 
         handle.abort();
     }
+
+    struct FakeCheck(&'static str, bool);
+
+    #[async_trait]
+    impl DependencyCheck for FakeCheck {
+        async fn check(&self) -> Result<()> {
+            if self.1 {
+                Ok(())
+            } else {
+                bail!("connection refused")
+            }
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_readiness_tracks_dependencies() {
+        let state = HealthState::new();
+        let timeout = Duration::from_secs(1);
+        assert!(!state.readiness().await.0, "not ready before first check");
+
+        let failing: Vec<Box<dyn DependencyCheck>> = vec![
+            Box::new(FakeCheck("sqlite", true)),
+            Box::new(FakeCheck("polymarket", false)),
+        ];
+        run_checks(&state, &failing, timeout).await;
+        let (ready, body) = state.readiness().await;
+        assert!(!ready);
+        assert_eq!(body["dependencies"]["sqlite"]["healthy"], true);
+        assert_eq!(
+            body["dependencies"]["polymarket"]["last_error"],
+            "connection refused"
+        );
+
+        let healthy: Vec<Box<dyn DependencyCheck>> = vec![Box::new(FakeCheck("polymarket", true))];
+        run_checks(&state, &healthy, timeout).await;
+        let (ready, body) = state.readiness().await;
+        assert!(ready);
+        // The last error survives recovery
+        assert_eq!(
+            body["dependencies"]["polymarket"]["last_error"],
+            "connection refused"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_liveness_timeout() {
+        let state = HealthState::new().with_liveness_timeout(Duration::from_secs(600));
+        let now = Utc::now();
+        assert!(state.liveness(now).await.0);
+
+        let (alive, body) = state.liveness(now + chrono::Duration::minutes(11)).await;
+        assert!(!alive);
+        assert_eq!(body["status"], "stalled");
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_key_check() {
+        use wiremock::matchers::{header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("x-api-key", "good"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/v1/models", server.uri());
+        assert!(AnthropicKeyCheck::new("good".to_string())
+            .with_url(&url)
+            .check()
+            .await
+            .is_ok());
+        let err = AnthropicKeyCheck::new("bad".to_string())
+            .with_url(&url)
+            .check()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("key rejected"));
+    }
+
+    #[test]
+    fn test_request_path() {
+        assert_eq!(
+            request_path(b"GET /ready HTTP/1.1\r\nHost: x\r\n\r\n"),
+            "/ready"
+        );
+        assert_eq!(request_path(b"GET /live?verbose=1 HTTP/1.1\r\n"), "/live");
+        assert_eq!(request_path(b""), "/");
+    }
 }
@@ -0,0 +1,192 @@
+//! Per-trade pipeline latency.
+//!
+//! Each executed trade carries timestamps for when its order book was seen
+//! during the scan, when external data arrived, when the valuation returned,
+//! and when the order was submitted and filled. The report shows where the
+//! time goes and how old our price information is by the time we trade.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::db::store::LatencyRecord;
+
+/// Timestamps collected for one trade as it moves through the pipeline.
+#[derive(Debug, Clone)]
+pub struct TradeTimeline {
+    pub scanned_at: DateTime<Utc>,
+    pub data_fetched_at: DateTime<Utc>,
+    pub valued_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub filled_at: Option<DateTime<Utc>>,
+}
+
+impl TradeTimeline {
+    pub fn new(
+        scanned_at: DateTime<Utc>,
+        data_fetched_at: DateTime<Utc>,
+        valued_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            scanned_at,
+            data_fetched_at,
+            valued_at,
+            submitted_at: None,
+            filled_at: None,
+        }
+    }
+
+    /// Database row for a filled trade; `None` until submit and fill are set.
+    pub fn to_record(&self, trade_id: i64) -> Option<LatencyRecord> {
+        let ts = |t: DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::Millis, true);
+        Some(LatencyRecord {
+            trade_id,
+            scanned_at: ts(self.scanned_at),
+            data_fetched_at: ts(self.data_fetched_at),
+            valued_at: ts(self.valued_at),
+            submitted_at: ts(self.submitted_at?),
+            filled_at: ts(self.filled_at?),
+        })
+    }
+}
+
+/// Distribution of one pipeline interval, in milliseconds.
+#[derive(Debug, Clone, Serialize)]
+pub struct StageStats {
+    pub stage: String,
+    pub mean_ms: i64,
+    pub p50_ms: i64,
+    pub p90_ms: i64,
+    pub max_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyReport {
+    pub trades: usize,
+    /// Consecutive stages, scan → data → valuation → submit → fill.
+    pub stages: Vec<StageStats>,
+    /// Age of the scanned order book when the order was submitted.
+    pub staleness_at_submit: StageStats,
+    pub end_to_end: StageStats,
+    /// Stage with the highest median — the first place to optimize.
+    pub slowest_stage: String,
+}
+
+const STAGES: [&str; 4] = [
+    "scan→data",
+    "data→valuation",
+    "valuation→submit",
+    "submit→fill",
+];
+
+/// Build the latency distribution from recorded trades. Rows with
+/// unparseable timestamps are skipped; `None` if nothing is left.
+pub fn latency_report(records: &[LatencyRecord]) -> Option<LatencyReport> {
+    let parse = |s: &str| {
+        DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    };
+    let timelines: Vec<[DateTime<Utc>; 5]> = records
+        .iter()
+        .filter_map(|r| {
+            Some([
+                parse(&r.scanned_at)?,
+                parse(&r.data_fetched_at)?,
+                parse(&r.valued_at)?,
+                parse(&r.submitted_at)?,
+                parse(&r.filled_at)?,
+            ])
+        })
+        .collect();
+    if timelines.is_empty() {
+        return None;
+    }
+
+    let interval = |name: &str, from: usize, to: usize| {
+        let samples: Vec<i64> = timelines
+            .iter()
+            .map(|t| (t[to] - t[from]).num_milliseconds())
+            .collect();
+        stats(name, samples)
+    };
+
+    let stages: Vec<StageStats> = STAGES
+        .iter()
+        .enumerate()
+        .map(|(i, name)| interval(name, i, i + 1))
+        .collect();
+    let slowest_stage = stages
+        .iter()
+        .max_by_key(|s| s.p50_ms)
+        .map(|s| s.stage.clone())
+        .unwrap_or_default();
+
+    Some(LatencyReport {
+        trades: timelines.len(),
+        staleness_at_submit: interval("scan→submit", 0, 3),
+        end_to_end: interval("scan→fill", 0, 4),
+        stages,
+        slowest_stage,
+    })
+}
+
+fn stats(stage: &str, mut samples: Vec<i64>) -> StageStats {
+    samples.sort_unstable();
+    // Nearest-rank percentile
+    let pct = |p: usize| samples[((samples.len() * p).div_ceil(100)).saturating_sub(1)];
+    StageStats {
+        stage: stage.to_string(),
+        mean_ms: samples.iter().sum::<i64>() / samples.len() as i64,
+        p50_ms: pct(50),
+        p90_ms: pct(90),
+        max_ms: *samples.last().unwrap_or(&0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn timeline(offsets_ms: [i64; 4]) -> TradeTimeline {
+        let start = Utc::now();
+        let at = |ms: i64| start + Duration::milliseconds(ms);
+        let mut t = TradeTimeline::new(start, at(offsets_ms[0]), at(offsets_ms[1]));
+        t.submitted_at = Some(at(offsets_ms[2]));
+        t.filled_at = Some(at(offsets_ms[3]));
+        t
+    }
+
+    #[test]
+    fn test_incomplete_timeline_has_no_record() {
+        let t = TradeTimeline::new(Utc::now(), Utc::now(), Utc::now());
+        assert!(t.to_record(1).is_none());
+    }
+
+    #[test]
+    fn test_latency_report() {
+        let records: Vec<LatencyRecord> = [
+            [1000, 4000, 4100, 4300],
+            [2000, 9000, 9200, 9300],
+            [1500, 5000, 5100, 5200],
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, o)| timeline(*o).to_record(i as i64).unwrap())
+        .collect();
+
+        let report = latency_report(&records).unwrap();
+        assert_eq!(report.trades, 3);
+        assert_eq!(report.stages[0].p50_ms, 1500);
+        assert_eq!(report.stages[1].p50_ms, 3500);
+        assert_eq!(report.stages[1].max_ms, 7000);
+        assert_eq!(report.slowest_stage, "data→valuation");
+        assert_eq!(report.staleness_at_submit.p50_ms, 5100);
+        assert_eq!(report.end_to_end.max_ms, 9300);
+    }
+
+    #[test]
+    fn test_empty_report() {
+        assert!(latency_report(&[]).is_none());
+    }
+}
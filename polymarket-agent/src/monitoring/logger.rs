@@ -1,19 +1,339 @@
-use anyhow::Result;
-use tracing_subscriber::EnvFilter;
+//! Log output and correlation spans.
+//!
+//! Every cycle runs inside a `cycle` span and every order inside a `trade`
+//! span, so in JSON mode each event carries `cycle_id` (and, once the trade
+//! is recorded, `trade_id`) in its `spans` list and a log shipper can filter
+//! one decision end to end.
+//!
+//! Events always go to stdout. With `[monitoring.log_file]` enabled they are
+//! also written to files rolled daily, hourly or by size, keeping only the
+//! newest `max_files`.
 
-use crate::config::MonitoringConfig;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::{info_span, Span, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+use crate::config::{LogFormat, MonitoringConfig};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogFileConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_directory")]
+    pub directory: String,
+    /// File name stem: `<prefix>.log` plus a date or index suffix.
+    #[serde(default = "default_prefix")]
+    pub prefix: String,
+    #[serde(default)]
+    pub rotation: LogRotation,
+    /// File size that triggers a roll with `rotation = "size"`.
+    #[serde(default = "default_max_size_mb")]
+    pub max_size_mb: u64,
+    /// Log files kept, including the one being written.
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: default_directory(),
+            prefix: default_prefix(),
+            rotation: LogRotation::default(),
+            max_size_mb: default_max_size_mb(),
+            max_files: default_max_files(),
+        }
+    }
+}
+
+fn default_directory() -> String {
+    "logs".to_string()
+}
+
+fn default_prefix() -> String {
+    "polymarket-agent".to_string()
+}
+
+fn default_max_size_mb() -> u64 {
+    50
+}
+
+fn default_max_files() -> usize {
+    14
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    #[default]
+    Daily,
+    Hourly,
+    /// Roll once the file reaches `max_size_mb`.
+    Size,
+}
 
 pub fn init_logging(config: &MonitoringConfig) -> Result<()> {
     let filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.log_level));
 
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .json()
-        .with_target(true)
-        .with_file(true)
-        .with_line_number(true)
+    let mut layers: Vec<Box<dyn Layer<Registry> + Send + Sync>> =
+        vec![fmt_layer(config.log_format, io::stdout, true)];
+    let file = &config.log_file;
+    if file.enabled {
+        layers.push(match file.rotation {
+            LogRotation::Daily | LogRotation::Hourly => {
+                fmt_layer(config.log_format, time_rolling_appender(file)?, false)
+            }
+            LogRotation::Size => fmt_layer(
+                config.log_format,
+                SizeRollingAppender::new(
+                    Path::new(&file.directory),
+                    &file.prefix,
+                    file.max_size_mb * 1024 * 1024,
+                    file.max_files,
+                )?,
+                false,
+            ),
+        });
+    }
+
+    tracing_subscriber::registry()
+        .with(layers)
+        .with(filter)
         .init();
 
     Ok(())
 }
+
+/// A formatting layer writing to `writer`. JSON events include the fields of
+/// every enclosing span.
+fn fmt_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(false)
+            .with_span_list(true)
+            .with_target(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Text => tracing_subscriber::fmt::layer()
+            .with_target(true)
+            .with_ansi(ansi)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+fn time_rolling_appender(config: &LogFileConfig) -> Result<RollingFileAppender> {
+    let rotation = match config.rotation {
+        LogRotation::Hourly => Rotation::HOURLY,
+        _ => Rotation::DAILY,
+    };
+    RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix(&config.prefix)
+        .filename_suffix("log")
+        .max_log_files(config.max_files.max(1))
+        .build(&config.directory)
+        .with_context(|| format!("Failed to open log directory {}", config.directory))
+}
+
+/// Appends to `<dir>/<prefix>.log`. When a write would take the file past
+/// `max_bytes` it is renamed to `<prefix>.log.1` (shifting older files up)
+/// and a fresh file is started; files beyond `max_files` are deleted.
+pub struct SizeRollingAppender {
+    state: Mutex<SizeRollingState>,
+}
+
+struct SizeRollingState {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+    max_files: usize,
+}
+
+impl SizeRollingAppender {
+    pub fn new(dir: &Path, prefix: &str, max_bytes: u64, max_files: usize) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create log directory {}", dir.display()))?;
+        let path = dir.join(format!("{prefix}.log"));
+        let file = open_append(&path)
+            .with_context(|| format!("Failed to open log file {}", path.display()))?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            state: Mutex::new(SizeRollingState {
+                path,
+                file,
+                written,
+                max_bytes: max_bytes.max(1),
+                max_files: max_files.max(1),
+            }),
+        })
+    }
+}
+
+impl SizeRollingState {
+    fn rolled(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn roll(&mut self) -> io::Result<()> {
+        // Rolled files are .1 (newest) through .{max_files - 1}
+        let oldest = self.max_files - 1;
+        if oldest == 0 {
+            self.file.set_len(0)?;
+        } else {
+            let _ = fs::remove_file(self.rolled(oldest));
+            for index in (1..oldest).rev() {
+                let from = self.rolled(index);
+                if from.exists() {
+                    fs::rename(&from, self.rolled(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rolled(1))?;
+            self.file = open_append(&self.path)?;
+        }
+        self.written = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+pub struct SizeRollingWriter<'a>(MutexGuard<'a, SizeRollingState>);
+
+impl Write for SizeRollingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let state = &mut self.0;
+        if state.written > 0 && state.written + buf.len() as u64 > state.max_bytes {
+            state.roll()?;
+        }
+        let n = state.file.write(buf)?;
+        state.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.file.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for SizeRollingAppender {
+    type Writer = SizeRollingWriter<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        // A panic mid-write can't leave the state inconsistent enough to
+        // stop logging
+        SizeRollingWriter(self.state.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Span for one agent cycle.
+pub fn cycle_span(cycle: u64) -> Span {
+    info_span!("cycle", cycle_id = cycle)
+}
+
+/// Span for one order, from journaling to bookkeeping. `trade_id` is
+/// filled in with [`record_trade_id`] once the trade row exists.
+pub fn trade_span(market_id: &str) -> Span {
+    info_span!(
+        "trade",
+        market_id = %market_id,
+        trade_id = tracing::field::Empty
+    )
+}
+
+/// Attach `trade_id` to the current trade span.
+pub fn record_trade_id(trade_id: i64) {
+    Span::current().record("trade_id", trade_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_events_carry_cycle_and_trade_ids() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(fmt_layer::<Registry, _>(
+            LogFormat::Json,
+            move || writer.clone(),
+            false,
+        ));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _cycle = cycle_span(42).entered();
+            let _trade = trade_span("0xabc").entered();
+            record_trade_id(7);
+            tracing::info!("Order filled");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let event: serde_json::Value =
+            serde_json::from_str(output.lines().last().unwrap()).unwrap();
+        let spans = event["spans"].as_array().unwrap();
+        assert_eq!(spans[0]["cycle_id"], 42);
+        assert_eq!(spans[1]["market_id"], "0xabc");
+        assert_eq!(spans[1]["trade_id"], 7);
+    }
+
+    #[test]
+    fn test_size_rolling_keeps_max_files() {
+        let dir = std::env::temp_dir().join(format!("agent-logs-{}", uuid::Uuid::new_v4()));
+        let appender = SizeRollingAppender::new(&dir, "agent", 10, 3).unwrap();
+
+        for line in [
+            "first line\n",
+            "second line\n",
+            "third line\n",
+            "fourth line\n",
+        ] {
+            appender.make_writer().write_all(line.as_bytes()).unwrap();
+        }
+
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("agent.log"), "fourth line\n");
+        assert_eq!(read("agent.log.1"), "third line\n");
+        assert_eq!(read("agent.log.2"), "second line\n");
+        assert!(!dir.join("agent.log.3").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
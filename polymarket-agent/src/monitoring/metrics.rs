@@ -4,13 +4,17 @@
 //! trading statistics from SQLite trade history.
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::Serialize;
-use std::str::FromStr;
+use std::collections::BTreeMap;
 use tracing::info;
 
-use crate::db::store::Store;
+use crate::db::store::{BlacklistRecord, CycleRecord, PhaseTimings, Store, TradeRecord};
+use crate::monitoring::latency::{latency_report, LatencyReport};
+use crate::risk::portfolio::net_by_market;
+use crate::risk::var::{self, VarReport};
 
 /// Aggregated performance metrics snapshot.
 #[derive(Debug, Clone, Serialize)]
@@ -32,16 +36,39 @@ pub struct PerformanceMetrics {
     pub sharpe_ratio: Option<Decimal>,
     pub cycles_completed: u64,
     pub avg_cycle_duration_ms: Option<f64>,
+    /// Average time per cycle phase, to find which one is slow.
+    pub avg_phase_ms: PhaseTimings,
+    /// Per strategy bucket breakdown, sorted by bucket name.
+    pub buckets: Vec<BucketMetrics>,
+    /// Simulated tail risk of open positions; `None` with no open positions.
+    pub value_at_risk: Option<VarReport>,
+    /// Markets no longer traded after exceeding their lifetime loss limit.
+    pub blacklisted_markets: Vec<BlacklistRecord>,
+    /// Pipeline latency of executed trades; `None` before the first one.
+    pub latency: Option<LatencyReport>,
+}
+
+/// Trade statistics for a single strategy bucket.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BucketMetrics {
+    pub bucket: String,
+    pub total_trades: u64,
+    pub open_trades: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub realized_pnl: Decimal,
+    pub open_exposure: Decimal,
 }
 
 impl PerformanceMetrics {
     /// Format metrics for logging or alerts.
     pub fn summary(&self) -> String {
-        format!(
+        let mut summary = format!(
             "Trades: {} ({} open, {} resolved: {}W/{}L, {:.1}% win rate)\n\
              P&L: ${} realized, ${} net (after ${} API costs)\n\
              ROI: {:.1}% | Sharpe: {} | Avg edge: {:.1}%\n\
-             Cycles: {} | Avg duration: {:.0}ms",
+             Cycles: {} | Avg duration: {:.0}ms\n\
+             {}",
             self.total_trades,
             self.open_trades,
             self.resolved_trades,
@@ -58,7 +85,72 @@ impl PerformanceMetrics {
             self.avg_edge_at_entry * dec!(100),
             self.cycles_completed,
             self.avg_cycle_duration_ms.unwrap_or(0.0),
-        )
+            match &self.value_at_risk {
+                Some(v) => format!(
+                    "VaR({:.0}%): ${:.2} | ES: ${:.2} | Worst case: ${:.2}",
+                    v.confidence * dec!(100),
+                    v.value_at_risk,
+                    v.expected_shortfall,
+                    v.max_loss
+                ),
+                None => "VaR: N/A (no open positions)".to_string(),
+            },
+        );
+
+        if let Some((slowest, _)) = self.avg_phase_ms.slowest() {
+            let phases: Vec<String> = self
+                .avg_phase_ms
+                .phases()
+                .into_iter()
+                .filter_map(|(name, ms)| Some(format!("{name} {}ms", ms?)))
+                .collect();
+            summary.push_str(&format!(
+                "\nAvg phases: {} | slowest: {slowest}",
+                phases.join(" | ")
+            ));
+        }
+
+        if let Some(l) = &self.latency {
+            summary.push_str(&format!(
+                "\nLatency ({} trades): scan→fill p50 {}ms p90 {}ms | book age at submit p50 {}ms | slowest: {}",
+                l.trades,
+                l.end_to_end.p50_ms,
+                l.end_to_end.p90_ms,
+                l.staleness_at_submit.p50_ms,
+                l.slowest_stage,
+            ));
+        }
+
+        if !self.blacklisted_markets.is_empty() {
+            let ids: Vec<&str> = self
+                .blacklisted_markets
+                .iter()
+                .map(|m| m.market_id.as_str())
+                .collect();
+            summary.push_str(&format!(
+                "\nBlacklisted markets ({}): {}",
+                ids.len(),
+                ids.join(", ")
+            ));
+        }
+
+        // Only worth a breakdown once more than one bucket has traded
+        if self.buckets.len() > 1 {
+            for b in &self.buckets {
+                summary.push_str(&format!(
+                    "\n[{}] {} trades ({} open, {}W/{}L) | P&L: ${} | Exposure: ${}",
+                    b.bucket,
+                    b.total_trades,
+                    b.open_trades,
+                    b.wins,
+                    b.losses,
+                    b.realized_pnl,
+                    b.open_exposure,
+                ));
+            }
+        }
+
+        summary
     }
 }
 
@@ -67,12 +159,30 @@ pub async fn compute_metrics(
     store: &Store,
     initial_bankroll: Decimal,
 ) -> Result<PerformanceMetrics> {
-    let all_trades = store.get_all_trades().await?;
-    let resolved = store.get_resolved_trades().await?;
-    let open = store.get_open_trades().await?;
+    compute_metrics_tagged(store, initial_bankroll, None).await
+}
+
+/// Like [`compute_metrics`], but with trade statistics covering only trades
+/// carrying `tag`, if given. API cost, ROI and cycle figures stay global.
+pub async fn compute_metrics_tagged(
+    store: &Store,
+    initial_bankroll: Decimal,
+    tag: Option<&str>,
+) -> Result<PerformanceMetrics> {
+    let mut all_trades = store.get_all_trades().await?;
+    let mut resolved = store.get_resolved_trades().await?;
+    let mut open = store.get_open_trades().await?;
+    if let Some(tag) = tag {
+        for trades in [&mut all_trades, &mut resolved, &mut open] {
+            trades.retain(|t| t.has_tag(tag));
+        }
+    }
     let total_api_cost = store.get_total_api_cost().await?;
     let cycle_count = store.get_cycle_count().await?;
     let avg_duration = store.get_avg_cycle_duration_ms().await?;
+    let avg_phase_ms = store.get_avg_phase_timings().await?;
+    let blacklisted_markets = store.get_blacklisted_markets().await?;
+    let latency = latency_report(&store.get_trade_latencies().await?);
 
     let total_trades = all_trades.len() as u64;
     let open_trades = open.len() as u64;
@@ -84,11 +194,7 @@ pub async fn compute_metrics(
     let mut pnl_values: Vec<Decimal> = Vec::new();
 
     for trade in &resolved {
-        let pnl = trade
-            .pnl
-            .as_deref()
-            .and_then(|s| Decimal::from_str(s).ok())
-            .unwrap_or(Decimal::ZERO);
+        let pnl = trade.pnl.unwrap_or(Decimal::ZERO);
 
         if trade.status == "RESOLVED_WIN" {
             wins += 1;
@@ -105,23 +211,12 @@ pub async fn compute_metrics(
         Decimal::ZERO
     };
 
-    // Unrealized exposure from open trades
-    let mut unrealized_exposure = Decimal::ZERO;
-    for trade in &open {
-        if let (Ok(price), Ok(size)) = (
-            Decimal::from_str(&trade.entry_price),
-            Decimal::from_str(&trade.size),
-        ) {
-            unrealized_exposure += price * size;
-        }
-    }
+    // Unrealized exposure from open trades, net of YES/NO pairs
+    let unrealized_exposure: Decimal = net_by_market(&open).iter().map(|m| m.net_exposure()).sum();
 
     // Average edge at entry across all trades
     let avg_edge = if total_trades > 0 {
-        let total_edge: Decimal = all_trades
-            .iter()
-            .filter_map(|t| Decimal::from_str(&t.edge_at_entry).ok())
-            .sum();
+        let total_edge: Decimal = all_trades.iter().map(|t| t.edge_at_entry).sum();
         total_edge / Decimal::from(total_trades)
     } else {
         Decimal::ZERO
@@ -129,10 +224,7 @@ pub async fn compute_metrics(
 
     // Average position size
     let avg_position_size = if total_trades > 0 {
-        let total_size: Decimal = all_trades
-            .iter()
-            .filter_map(|t| Decimal::from_str(&t.size).ok())
-            .sum();
+        let total_size: Decimal = all_trades.iter().map(|t| t.size).sum();
         total_size / Decimal::from(total_trades)
     } else {
         Decimal::ZERO
@@ -150,6 +242,9 @@ pub async fn compute_metrics(
     // Sharpe ratio: mean(returns) / std(returns)
     let sharpe_ratio = compute_sharpe(&pnl_values);
 
+    let buckets = bucket_breakdown(&all_trades);
+    let value_at_risk = var::portfolio_var(&var::positions_from_trades(&open));
+
     Ok(PerformanceMetrics {
         total_trades,
         open_trades,
@@ -168,9 +263,128 @@ pub async fn compute_metrics(
         sharpe_ratio,
         cycles_completed: cycle_count as u64,
         avg_cycle_duration_ms: avg_duration,
+        avg_phase_ms,
+        buckets,
+        value_at_risk,
+        blacklisted_markets,
+        latency,
     })
 }
 
+/// Group trades by strategy bucket.
+pub(crate) fn bucket_breakdown(trades: &[TradeRecord]) -> Vec<BucketMetrics> {
+    let mut by_bucket: BTreeMap<&str, BucketMetrics> = BTreeMap::new();
+
+    for trade in trades {
+        let entry = by_bucket
+            .entry(trade.bucket.as_str())
+            .or_insert_with(|| BucketMetrics {
+                bucket: trade.bucket.clone(),
+                ..Default::default()
+            });
+        entry.total_trades += 1;
+
+        match trade.status.as_str() {
+            "OPEN" => {
+                entry.open_trades += 1;
+                entry.open_exposure += trade.entry_price * trade.size;
+            }
+            "RESOLVED_WIN" | "RESOLVED_LOSS" => {
+                if trade.status == "RESOLVED_WIN" {
+                    entry.wins += 1;
+                } else {
+                    entry.losses += 1;
+                }
+                entry.realized_pnl += trade.pnl.unwrap_or(Decimal::ZERO);
+            }
+            _ => {}
+        }
+    }
+
+    by_bucket.into_values().collect()
+}
+
+/// Bankroll at the end of one cycle, with the drawdown from its running peak.
+#[derive(Debug, Clone, Serialize)]
+pub struct EquityPoint {
+    pub cycle: i64,
+    pub at: Option<DateTime<Utc>>,
+    pub bankroll: Decimal,
+    pub peak: Decimal,
+    /// Fraction below `peak` (0.0 at a new high).
+    pub drawdown_pct: Decimal,
+}
+
+/// Realized P&L of trades resolved on one UTC day.
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyPnl {
+    /// `YYYY-MM-DD`.
+    pub day: String,
+    pub realized_pnl: Decimal,
+    pub cumulative_pnl: Decimal,
+    pub wins: u64,
+    pub losses: u64,
+}
+
+/// Bankroll curve and drawdown from cycle history. Cycles without a
+/// recorded bankroll are skipped.
+pub fn equity_curve(cycles: &[CycleRecord]) -> Vec<EquityPoint> {
+    let mut peak = Decimal::ZERO;
+    cycles
+        .iter()
+        .filter_map(|c| {
+            let bankroll = c.bankroll?;
+            peak = peak.max(bankroll);
+            let drawdown_pct = if peak > Decimal::ZERO {
+                ((peak - bankroll) / peak).round_dp(6)
+            } else {
+                Decimal::ZERO
+            };
+            Some(EquityPoint {
+                cycle: c.cycle_number,
+                at: c.created_at,
+                bankroll,
+                peak,
+                drawdown_pct,
+            })
+        })
+        .collect()
+}
+
+/// Realized P&L per day of resolution, oldest first.
+pub fn pnl_by_day(trades: &[TradeRecord]) -> Vec<DailyPnl> {
+    let mut by_day: BTreeMap<String, DailyPnl> = BTreeMap::new();
+    for trade in trades {
+        let (Some(resolved_at), Some(pnl)) = (trade.resolved_at, trade.pnl) else {
+            continue;
+        };
+        let day = resolved_at.format("%Y-%m-%d").to_string();
+        let entry = by_day.entry(day.clone()).or_insert_with(|| DailyPnl {
+            day,
+            realized_pnl: Decimal::ZERO,
+            cumulative_pnl: Decimal::ZERO,
+            wins: 0,
+            losses: 0,
+        });
+        entry.realized_pnl += pnl;
+        if pnl > Decimal::ZERO {
+            entry.wins += 1;
+        } else {
+            entry.losses += 1;
+        }
+    }
+
+    let mut cumulative = Decimal::ZERO;
+    by_day
+        .into_values()
+        .map(|mut d| {
+            cumulative += d.realized_pnl;
+            d.cumulative_pnl = cumulative;
+            d
+        })
+        .collect()
+}
+
 /// Compute annualized Sharpe ratio from per-trade P&L values.
 /// Assumes ~144 trades/day as the scaling factor (one per 10-min cycle).
 fn compute_sharpe(pnl_values: &[Decimal]) -> Option<Decimal> {
@@ -242,12 +456,62 @@ pub fn log_metrics(metrics: &PerformanceMetrics) {
         cycles = metrics.cycles_completed,
         "Performance metrics"
     );
+
+    if let Some(ref v) = metrics.value_at_risk {
+        info!(
+            confidence = %v.confidence,
+            value_at_risk = %v.value_at_risk,
+            expected_shortfall = %v.expected_shortfall,
+            max_loss = %v.max_loss,
+            "Portfolio value-at-risk"
+        );
+    }
+
+    if let Some(ref l) = metrics.latency {
+        for stage in l
+            .stages
+            .iter()
+            .chain([&l.staleness_at_submit, &l.end_to_end])
+        {
+            info!(
+                stage = %stage.stage,
+                mean_ms = stage.mean_ms,
+                p50_ms = stage.p50_ms,
+                p90_ms = stage.p90_ms,
+                max_ms = stage.max_ms,
+                "Trade latency"
+            );
+        }
+    }
+
+    for market in &metrics.blacklisted_markets {
+        info!(
+            market_id = %market.market_id,
+            question = market.market_question.as_deref().unwrap_or(""),
+            realized_loss = %market.realized_loss,
+            "Blacklisted market"
+        );
+    }
+
+    for bucket in &metrics.buckets {
+        info!(
+            bucket = %bucket.bucket,
+            trades = bucket.total_trades,
+            open_trades = bucket.open_trades,
+            wins = bucket.wins,
+            losses = bucket.losses,
+            realized_pnl = %bucket.realized_pnl,
+            open_exposure = %bucket.open_exposure,
+            "Bucket metrics"
+        );
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::store::{ApiCostRecord, TradeRecord};
+    use crate::db::export::parse_timestamp;
+    use crate::db::store::ApiCostRecord;
 
     #[test]
     fn test_decimal_sqrt() {
@@ -295,8 +559,6 @@ mod tests {
 
     #[tokio::test]
     async fn test_compute_metrics_with_trades() {
-        use chrono::Utc;
-
         let store = Store::new(":memory:").await.unwrap();
 
         // Insert trades as OPEN first (insert_trade doesn't persist pnl)
@@ -306,17 +568,23 @@ mod tests {
             market_id: "m1".to_string(),
             market_question: Some("Test?".to_string()),
             direction: "YES".to_string(),
-            entry_price: "0.60".to_string(),
-            size: "10".to_string(),
-            edge_at_entry: "0.10".to_string(),
-            claude_fair_value: "0.70".to_string(),
-            confidence: "0.85".to_string(),
-            kelly_raw: "0.20".to_string(),
-            kelly_adjusted: "0.10".to_string(),
+            entry_price: dec!(0.60),
+            size: dec!(10),
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.70),
+            confidence: dec!(0.85),
+            kelly_raw: dec!(0.20),
+            kelly_adjusted: dec!(0.10),
             status: "OPEN".to_string(),
             pnl: None,
             created_at: None,
             resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
         };
         let id1 = store.insert_trade(&trade).await.unwrap();
 
@@ -343,7 +611,7 @@ mod tests {
             endpoint: Some("/v1/messages".to_string()),
             input_tokens: Some(2000),
             output_tokens: Some(300),
-            cost: "0.05".to_string(),
+            cost: dec!(0.05),
             cycle: Some(1),
             created_at: None,
         };
@@ -359,6 +627,143 @@ mod tests {
         assert_eq!(metrics.total_api_cost, dec!(0.05));
         // Net: -2 - 0.05 = -2.05
         assert_eq!(metrics.net_profit, dec!(-2.05));
+        assert_eq!(metrics.buckets.len(), 1);
+        assert_eq!(metrics.buckets[0].realized_pnl, dec!(-2));
+    }
+
+    #[tokio::test]
+    async fn test_bucket_breakdown() {
+        let store = Store::new(":memory:").await.unwrap();
+        let base = TradeRecord {
+            id: None,
+            cycle: 1,
+            market_id: "m1".to_string(),
+            market_question: None,
+            direction: "YES".to_string(),
+            entry_price: dec!(0.50),
+            size: dec!(10),
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.60),
+            confidence: dec!(0.80),
+            kelly_raw: dec!(0.20),
+            kelly_adjusted: dec!(0.10),
+            status: "OPEN".to_string(),
+            pnl: None,
+            created_at: None,
+            resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        };
+        store.insert_trade(&base).await.unwrap();
+        store
+            .insert_trade(&TradeRecord {
+                market_id: "m2".to_string(),
+                bucket: "experimental".to_string(),
+                fee: Decimal::ZERO,
+                order_id: None,
+                reconciled_at: None,
+                ..base.clone()
+            })
+            .await
+            .unwrap();
+
+        let metrics = compute_metrics(&store, dec!(100)).await.unwrap();
+        assert_eq!(metrics.buckets.len(), 2);
+        assert_eq!(metrics.buckets[0].bucket, "directional");
+        assert_eq!(metrics.buckets[0].open_exposure, dec!(5));
+        assert!(metrics.value_at_risk.is_some());
+        assert_eq!(metrics.buckets[1].bucket, "experimental");
+        assert!(metrics.summary().contains("[experimental] 1 trades"));
+
+        let id = store.get_trades_by_market("m2").await.unwrap()[0]
+            .id
+            .unwrap();
+        assert!(store
+            .annotate_trade(
+                id,
+                &["manual override".to_string()],
+                Some("Took it by hand")
+            )
+            .await
+            .unwrap());
+        let tagged = compute_metrics_tagged(&store, dec!(100), Some("manual override"))
+            .await
+            .unwrap();
+        assert_eq!(tagged.total_trades, 1);
+        assert_eq!(tagged.buckets.len(), 1);
+        assert_eq!(tagged.buckets[0].bucket, "experimental");
+        let untagged = compute_metrics_tagged(&store, dec!(100), Some("other"))
+            .await
+            .unwrap();
+        assert_eq!(untagged.total_trades, 0);
+    }
+
+    #[test]
+    fn test_equity_curve_and_daily_pnl() {
+        let cycle = |n: i64, bankroll: Option<Decimal>| CycleRecord {
+            id: None,
+            cycle_number: n,
+            markets_scanned: None,
+            opportunities_found: None,
+            trades_placed: None,
+            api_cost: None,
+            bankroll,
+            unrealized_pnl: None,
+            agent_state: "ALIVE".to_string(),
+            duration_ms: None,
+            truncated_phases: None,
+            timings: Default::default(),
+            created_at: None,
+        };
+        let curve = equity_curve(&[
+            cycle(0, Some(dec!(100))),
+            cycle(1, Some(dec!(120))),
+            cycle(2, None),
+            cycle(3, Some(dec!(90))),
+        ]);
+        assert_eq!(curve.len(), 3);
+        assert_eq!(curve[1].drawdown_pct, Decimal::ZERO);
+        assert_eq!(curve[2].peak, dec!(120));
+        assert_eq!(curve[2].drawdown_pct, dec!(0.25));
+
+        let trade = |pnl: Decimal, resolved_at: &str| TradeRecord {
+            id: None,
+            cycle: 1,
+            market_id: "m1".to_string(),
+            market_question: None,
+            direction: "YES".to_string(),
+            entry_price: dec!(0.50),
+            size: dec!(10),
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.60),
+            confidence: dec!(0.80),
+            kelly_raw: dec!(0.20),
+            kelly_adjusted: dec!(0.10),
+            status: "RESOLVED_WIN".to_string(),
+            pnl: Some(pnl),
+            created_at: None,
+            resolved_at: parse_timestamp(resolved_at),
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        };
+        let days = pnl_by_day(&[
+            trade(dec!(-3), "2025-03-02 08:00:00"),
+            trade(dec!(5), "2025-03-01T10:00:00Z"),
+            trade(dec!(2), "2025-03-02T12:00:00Z"),
+        ]);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].day, "2025-03-01");
+        assert_eq!(days[1].realized_pnl, dec!(-1));
+        assert_eq!((days[1].wins, days[1].losses), (1, 1));
+        assert_eq!(days[1].cumulative_pnl, dec!(4));
     }
 
     #[test]
@@ -381,11 +786,22 @@ mod tests {
             sharpe_ratio: Some(dec!(1.25)),
             cycles_completed: 100,
             avg_cycle_duration_ms: Some(1500.0),
+            avg_phase_ms: PhaseTimings {
+                scan_ms: Some(400),
+                valuation_ms: Some(900),
+                ..PhaseTimings::default()
+            },
+            buckets: Vec::new(),
+            value_at_risk: None,
+            blacklisted_markets: Vec::new(),
+            latency: None,
         };
 
         let summary = metrics.summary();
         assert!(summary.contains("10"));
         assert!(summary.contains("62.5%"));
         assert!(summary.contains("5W/3L"));
+        assert!(summary.contains("VaR: N/A"));
+        assert!(summary.contains("Avg phases: scan 400ms | valuation 900ms | slowest: valuation"));
     }
 }
@@ -1,5 +1,15 @@
 pub mod alerts;
+pub mod anomaly;
+pub mod auth;
 pub mod dashboard;
+pub mod funding;
 pub mod health;
+pub mod latency;
 pub mod logger;
 pub mod metrics;
+pub mod notify;
+pub mod positions;
+pub mod signals;
+pub mod systemd;
+pub mod watchdog;
+pub mod weekly;
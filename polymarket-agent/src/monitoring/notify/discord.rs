@@ -0,0 +1,66 @@
+//! Discord webhook channel.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::{Embed, Notification, Notifier};
+
+/// Posts to a Discord webhook. Embeds are sent as-is; everything else as
+/// message content.
+pub struct DiscordNotifier {
+    webhook_url: String,
+    http: reqwest::Client,
+}
+
+/// Discord webhook message format.
+#[derive(Debug, Serialize)]
+struct DiscordMessage<'a> {
+    content: &'a str,
+    username: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<&'a Embed>,
+}
+
+impl DiscordNotifier {
+    pub fn new(webhook_url: String) -> Self {
+        Self {
+            webhook_url,
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<()> {
+        let payload = match &notification.embed {
+            Some(embed) => DiscordMessage {
+                content: "",
+                username: "Polymarket Agent",
+                embeds: vec![embed],
+            },
+            None => DiscordMessage {
+                content: &notification.text,
+                username: "Polymarket Agent",
+                embeds: Vec::new(),
+            },
+        };
+
+        let response = self
+            .http
+            .post(&self.webhook_url)
+            .json(&payload)
+            .send()
+            .await
+            .context("Failed to send Discord alert")?;
+        if !response.status().is_success() {
+            bail!("Discord webhook returned {}", response.status());
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "discord"
+    }
+}
@@ -0,0 +1,212 @@
+//! SMTP email channel.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Deserialize;
+
+use super::{plain_text, AlertKind, ChannelRouting, Notification, Notifier, Severity};
+
+/// `[monitoring.email]`. SMTP credentials come from `SMTP_USERNAME` and
+/// `SMTP_PASSWORD`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub smtp_host: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default)]
+    pub tls: SmtpTls,
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: Vec<String>,
+    /// Email is for paging, so only CRITICAL by default.
+    #[serde(default = "default_min_severity")]
+    pub min_severity: Severity,
+    /// Alert kinds delivered; empty means all.
+    #[serde(default)]
+    pub alerts: Vec<AlertKind>,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            smtp_host: String::new(),
+            smtp_port: default_smtp_port(),
+            tls: SmtpTls::default(),
+            from: String::new(),
+            to: Vec::new(),
+            min_severity: default_min_severity(),
+            alerts: Vec::new(),
+        }
+    }
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_min_severity() -> Severity {
+    Severity::Critical
+}
+
+impl EmailConfig {
+    pub fn routing(&self) -> ChannelRouting {
+        ChannelRouting {
+            min_severity: self.min_severity,
+            alerts: self.alerts.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// Upgrade a plain connection (port 587).
+    #[default]
+    Starttls,
+    /// TLS from the first byte (port 465).
+    Implicit,
+    /// Unencrypted, for a local relay only.
+    None,
+}
+
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl EmailNotifier {
+    pub fn new(
+        config: &EmailConfig,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> Result<Self> {
+        if config.smtp_host.is_empty() {
+            bail!("monitoring.email.smtp_host is required");
+        }
+        if config.to.is_empty() {
+            bail!("monitoring.email.to needs at least one recipient");
+        }
+        let from = config
+            .from
+            .parse()
+            .with_context(|| format!("Invalid email sender {:?}", config.from))?;
+        let to = config
+            .to
+            .iter()
+            .map(|addr| {
+                addr.parse()
+                    .with_context(|| format!("Invalid email recipient {addr:?}"))
+            })
+            .collect::<Result<Vec<Mailbox>>>()?;
+
+        let mut builder = match config.tls {
+            SmtpTls::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)?
+            }
+            SmtpTls::Implicit => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)?,
+            SmtpTls::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)
+            }
+        }
+        .port(config.smtp_port);
+        if let (Some(username), Some(password)) = (username, password) {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            to,
+        })
+    }
+}
+
+/// Subject line and plain-text body for a notification.
+fn compose(notification: &Notification) -> (String, String) {
+    let title = notification.title();
+    let subject = if title.starts_with('[') {
+        format!("Polymarket Agent: {title}")
+    } else {
+        format!("Polymarket Agent: [{}] {title}", notification.severity)
+    };
+    (subject, plain_text(&notification.text))
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<()> {
+        let (subject, body) = compose(notification);
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN);
+        for to in &self.to {
+            message = message.to(to.clone());
+        }
+        let message = message.body(body).context("Failed to build alert email")?;
+
+        self.transport
+            .send(message)
+            .await
+            .context("Failed to send alert email")?;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "email"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose() {
+        let death = Notification::new(
+            AlertKind::AgentDeath,
+            Severity::Critical,
+            "**AGENT DEATH**\nCycle: 42\nFinal balance: $0.12".to_string(),
+        );
+        let (subject, body) = compose(&death);
+        assert_eq!(subject, "Polymarket Agent: [CRITICAL] AGENT DEATH");
+        assert_eq!(body, "AGENT DEATH\nCycle: 42\nFinal balance: $0.12");
+
+        let state = Notification::new(
+            AlertKind::StateChange,
+            Severity::Critical,
+            "**[CRITICAL] State Change**\nLowFuel -> Dead".to_string(),
+        );
+        assert_eq!(
+            compose(&state).0,
+            "Polymarket Agent: [CRITICAL] State Change"
+        );
+    }
+
+    #[test]
+    fn test_new_validates_config() {
+        let mut config = EmailConfig {
+            enabled: true,
+            smtp_host: "smtp.example.com".to_string(),
+            from: "Agent <agent@example.com>".to_string(),
+            to: vec!["ops@example.com".to_string()],
+            ..EmailConfig::default()
+        };
+        assert!(EmailNotifier::new(&config, None, None).is_ok());
+
+        config.to = vec!["not an address".to_string()];
+        assert!(EmailNotifier::new(&config, None, None).is_err());
+        config.to.clear();
+        assert!(EmailNotifier::new(&config, None, None).is_err());
+    }
+}
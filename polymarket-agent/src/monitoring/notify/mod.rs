@@ -0,0 +1,248 @@
+//! Alert delivery channels.
+//!
+//! [`AlertClient`](crate::monitoring::alerts::AlertClient) formats each alert
+//! once as a [`Notification`] and hands it to every configured [`Notifier`]
+//! whose [`ChannelRouting`] accepts it — e.g. everything to Discord, only
+//! CRITICAL to email.
+
+pub mod discord;
+pub mod email;
+pub mod slack;
+
+use std::fmt;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// How urgently a human should look at an alert.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Info,
+    Notice,
+    Warning,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Severity::Info => "INFO",
+            Severity::Notice => "NOTICE",
+            Severity::Warning => "WARNING",
+            Severity::Critical => "CRITICAL",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Which alert a notification carries, for per-channel filtering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    TradePlaced,
+    ApprovalRequested,
+    TradeResolved,
+    StateChange,
+    SpendCap,
+    Funding,
+    Drawdown,
+    Milestone,
+    DailySummary,
+    WeeklyReport,
+    Canary,
+    Anomaly,
+    Watchdog,
+    DatabaseSize,
+    AgentDeath,
+    Crash,
+}
+
+/// Rich card content. Discord renders it as an embed; other channels get
+/// [`Embed::to_text`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Embed {
+    pub title: String,
+    pub description: String,
+    pub color: u32,
+    pub fields: Vec<EmbedField>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbedField {
+    pub name: String,
+    pub value: String,
+    pub inline: bool,
+}
+
+impl EmbedField {
+    pub fn new(name: &str, value: String, inline: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            inline,
+        }
+    }
+}
+
+impl Embed {
+    /// Markdown rendering: bold title, description, one field per line.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("**{}**\n{}", self.title, self.description);
+        for field in &self.fields {
+            if field.value.contains('\n') {
+                text.push_str(&format!("\n{}:\n{}", field.name, field.value));
+            } else {
+                text.push_str(&format!("\n{}: {}", field.name, field.value));
+            }
+        }
+        text
+    }
+}
+
+/// One formatted alert. `text` uses Discord-style markdown (`**bold**`,
+/// fenced code blocks); channels convert it as needed.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: AlertKind,
+    pub severity: Severity,
+    pub text: String,
+    pub embed: Option<Embed>,
+}
+
+impl Notification {
+    pub fn new(kind: AlertKind, severity: Severity, text: String) -> Self {
+        Self {
+            kind,
+            severity,
+            text,
+            embed: None,
+        }
+    }
+
+    pub fn with_embed(kind: AlertKind, severity: Severity, embed: Embed) -> Self {
+        Self {
+            kind,
+            severity,
+            text: embed.to_text(),
+            embed: Some(embed),
+        }
+    }
+
+    /// First line without markdown, e.g. for an email subject.
+    pub fn title(&self) -> String {
+        plain_text(self.text.lines().next().unwrap_or_default())
+            .trim()
+            .to_string()
+    }
+}
+
+/// `text` with Discord markdown markers removed.
+pub fn plain_text(text: &str) -> String {
+    text.replace("```", "").replace("**", "").replace('`', "")
+}
+
+/// A destination alerts can be delivered to.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Deliver one notification.
+    async fn notify(&self, notification: &Notification) -> Result<()>;
+
+    /// Human-readable channel name, for logs.
+    fn name(&self) -> &str;
+}
+
+/// Which alerts a channel receives.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChannelRouting {
+    /// Least severe alert delivered.
+    #[serde(default)]
+    pub min_severity: Severity,
+    /// Alert kinds delivered; empty means all.
+    #[serde(default)]
+    pub alerts: Vec<AlertKind>,
+}
+
+impl ChannelRouting {
+    pub fn min_severity(severity: Severity) -> Self {
+        Self {
+            min_severity: severity,
+            alerts: Vec::new(),
+        }
+    }
+
+    pub fn allows(&self, notification: &Notification) -> bool {
+        notification.severity >= self.min_severity
+            && (self.alerts.is_empty() || self.alerts.contains(&notification.kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing() {
+        let critical = Notification::new(
+            AlertKind::AgentDeath,
+            Severity::Critical,
+            "**AGENT DEATH**".to_string(),
+        );
+        let info = Notification::new(
+            AlertKind::TradePlaced,
+            Severity::Info,
+            "**Trade Placed**".to_string(),
+        );
+
+        let all = ChannelRouting::default();
+        assert!(all.allows(&critical) && all.allows(&info));
+
+        let email = ChannelRouting::min_severity(Severity::Critical);
+        assert!(email.allows(&critical));
+        assert!(!email.allows(&info));
+
+        let trades_only = ChannelRouting {
+            min_severity: Severity::Info,
+            alerts: vec![AlertKind::TradePlaced],
+        };
+        assert!(trades_only.allows(&info));
+        assert!(!trades_only.allows(&critical));
+    }
+
+    #[test]
+    fn test_routing_from_toml() {
+        let routing: ChannelRouting =
+            toml::from_str("min_severity = \"warning\"\nalerts = [\"anomaly\", \"watchdog\"]")
+                .unwrap();
+        assert_eq!(routing.min_severity, Severity::Warning);
+        assert_eq!(
+            routing.alerts,
+            vec![AlertKind::Anomaly, AlertKind::Watchdog]
+        );
+    }
+
+    #[test]
+    fn test_title_and_embed_text() {
+        let n = Notification::new(
+            AlertKind::StateChange,
+            Severity::Warning,
+            "**[WARNING] State Change**\nAlive -> LowFuel".to_string(),
+        );
+        assert_eq!(n.title(), "[WARNING] State Change");
+
+        let embed = Embed {
+            title: "Weekly Performance Report".to_string(),
+            description: "2026-10-05 – 2026-10-12".to_string(),
+            color: 0,
+            fields: vec![EmbedField::new("Net P&L", "$4.20".to_string(), true)],
+        };
+        assert_eq!(
+            Notification::with_embed(AlertKind::WeeklyReport, Severity::Info, embed).text,
+            "**Weekly Performance Report**\n2026-10-05 – 2026-10-12\nNet P&L: $4.20"
+        );
+    }
+}
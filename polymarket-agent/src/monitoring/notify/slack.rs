@@ -0,0 +1,98 @@
+//! Slack incoming-webhook channel.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{AlertKind, ChannelRouting, Notification, Notifier, Severity};
+
+/// `[monitoring.slack]`. The webhook URL comes from `SLACK_WEBHOOK_URL`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SlackConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub min_severity: Severity,
+    /// Alert kinds delivered; empty means all.
+    #[serde(default)]
+    pub alerts: Vec<AlertKind>,
+}
+
+impl SlackConfig {
+    pub fn routing(&self) -> ChannelRouting {
+        ChannelRouting {
+            min_severity: self.min_severity,
+            alerts: self.alerts.clone(),
+        }
+    }
+}
+
+pub struct SlackNotifier {
+    webhook_url: String,
+    http: reqwest::Client,
+}
+
+impl SlackNotifier {
+    pub fn new(webhook_url: String) -> Self {
+        Self {
+            webhook_url,
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+/// Slack mrkdwn marks bold with single asterisks.
+fn to_mrkdwn(text: &str) -> String {
+    text.replace("**", "*")
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<()> {
+        let payload = serde_json::json!({ "text": to_mrkdwn(&notification.text) });
+        let response = self
+            .http
+            .post(&self.webhook_url)
+            .json(&payload)
+            .send()
+            .await
+            .context("Failed to send Slack alert")?;
+        if !response.status().is_success() {
+            bail!("Slack webhook returned {}", response.status());
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "slack"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_posts_mrkdwn_text() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_json(serde_json::json!({
+                "text": "*Trade Placed*\nMarket: Will it rain?"
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let notifier = SlackNotifier::new(format!("{}/hook", server.uri()));
+        let notification = Notification::new(
+            AlertKind::TradePlaced,
+            Severity::Info,
+            "**Trade Placed**\nMarket: Will it rain?".to_string(),
+        );
+        notifier.notify(&notification).await.unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+//! Open positions marked to the current market, for the dashboard.
+//!
+//! Prices are fetched from Gamma on demand when the positions view is
+//! loaded, so marks are as fresh as the request rather than the last cycle.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::db::store::{Store, TradeRecord};
+use crate::market::polymarket::PolymarketClient;
+
+/// One open trade with its live mark.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionView {
+    pub trade_id: Option<i64>,
+    pub market_id: String,
+    pub market_question: Option<String>,
+    pub direction: String,
+    pub shares: Decimal,
+    pub entry_price: Decimal,
+    /// Current price of the held outcome; `None` if it couldn't be fetched.
+    pub current_price: Option<Decimal>,
+    pub unrealized_pnl: Option<Decimal>,
+    pub edge_at_entry: Decimal,
+    /// Claude's fair value at entry against the current price.
+    pub edge_now: Option<Decimal>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub hours_to_resolution: Option<i64>,
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
+/// Build the view for `trade` given the market's current YES price and end
+/// date.
+pub fn position_view(
+    trade: &TradeRecord,
+    yes_price: Option<Decimal>,
+    end_date: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> PositionView {
+    let (entry_price, shares) = (trade.entry_price, trade.size);
+    let is_no = trade.direction == "NO";
+
+    // Prices and fair value in terms of the held outcome
+    let held = |yes: Decimal| if is_no { Decimal::ONE - yes } else { yes };
+    let current_price = yes_price.map(held);
+    let fair_value = held(trade.claude_fair_value);
+
+    PositionView {
+        trade_id: trade.id,
+        market_id: trade.market_id.clone(),
+        market_question: trade.market_question.clone(),
+        direction: trade.direction.clone(),
+        shares,
+        entry_price,
+        current_price,
+        unrealized_pnl: current_price.map(|p| ((p - entry_price) * shares).round_dp(4)),
+        edge_at_entry: trade.edge_at_entry,
+        edge_now: current_price.map(|p| fair_value - p),
+        end_date,
+        hours_to_resolution: end_date.map(|end| (end - now).num_hours()),
+        opened_at: trade.created_at,
+    }
+}
+
+/// Every open trade, marked against Gamma's current price.
+pub async fn open_positions(
+    store: &Store,
+    client: Option<&PolymarketClient>,
+) -> Result<Vec<PositionView>> {
+    let trades = store.get_open_trades().await?;
+    let now = Utc::now();
+
+    let mut positions = Vec::with_capacity(trades.len());
+    for trade in &trades {
+        let (yes_price, end_date) = match client {
+            Some(client) => match client.get_price_and_end_date(&trade.market_id).await {
+                Ok((price, end_date)) => (Some(price), end_date),
+                Err(e) => {
+                    warn!(market_id = %trade.market_id, error = %e, "Failed to mark position");
+                    (None, None)
+                }
+            },
+            None => (None, None),
+        };
+        positions.push(position_view(trade, yes_price, end_date, now));
+    }
+    Ok(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use rust_decimal_macros::dec;
+
+    fn trade(direction: &str) -> TradeRecord {
+        TradeRecord {
+            id: Some(1),
+            cycle: 1,
+            market_id: "m1".to_string(),
+            market_question: None,
+            direction: direction.to_string(),
+            entry_price: dec!(0.40),
+            size: dec!(10),
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.30),
+            confidence: dec!(0.80),
+            kelly_raw: dec!(0.20),
+            kelly_adjusted: dec!(0.10),
+            status: "OPEN".to_string(),
+            pnl: None,
+            created_at: None,
+            resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_no_position_marked_on_no_price() {
+        let now = Utc::now();
+        // Bought NO at 0.40 with YES fair value 0.30 (NO worth 0.70)
+        let view = position_view(
+            &trade("NO"),
+            Some(dec!(0.45)),
+            Some(now + Duration::hours(30)),
+            now,
+        );
+        assert_eq!(view.current_price, Some(dec!(0.55)));
+        assert_eq!(view.unrealized_pnl, Some(dec!(1.5)));
+        assert_eq!(view.edge_now, Some(dec!(0.15)));
+        assert_eq!(view.hours_to_resolution, Some(30));
+    }
+
+    #[test]
+    fn test_unmarked_position_keeps_entry_fields() {
+        let view = position_view(&trade("YES"), None, None, Utc::now());
+        assert_eq!(view.entry_price, dec!(0.40));
+        assert!(view.current_price.is_none());
+        assert!(view.unrealized_pnl.is_none());
+        assert!(view.edge_now.is_none());
+    }
+}
@@ -0,0 +1,290 @@
+//! Trade signal export.
+//!
+//! When enabled, every opportunity the agent decides to trade is POSTed as
+//! JSON to `SIGNAL_WEBHOOK_URL` before the order is sent (`"opportunity"`),
+//! and again once it has executed (`"executed"`), so other systems can
+//! mirror the trade.
+//!
+//! With `require_approval`, opportunities are parked in the approval queue
+//! instead of executing, and the signal carries approve/reject callback URLs
+//! on the dashboard. The order runs on the first cycle after the callback,
+//! or expires after `risk.approval.ttl_seconds`.
+
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::execution::order::{ExecutionResult, OrderStatus, PreparedOrder};
+use crate::market::models::MarketCategory;
+
+/// `[monitoring.signals]`. The webhook URL comes from `SIGNAL_WEBHOOK_URL`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignalConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Hold every opportunity until it is approved through the callback.
+    #[serde(default)]
+    pub require_approval: bool,
+    /// Give up on a delivery after this long, so a slow receiver can't
+    /// stall the cycle.
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for SignalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            require_approval: false,
+            timeout_seconds: default_timeout_seconds(),
+        }
+    }
+}
+
+fn default_timeout_seconds() -> u64 {
+    5
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalEvent {
+    Opportunity,
+    Executed,
+}
+
+/// Callback URLs for an opportunity awaiting approval.
+#[derive(Debug, Clone, Serialize)]
+pub struct SignalApproval {
+    pub id: i64,
+    pub approve_url: String,
+    pub reject_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Outcome of an executed signal.
+#[derive(Debug, Clone, Serialize)]
+pub struct SignalExecution {
+    pub order_id: String,
+    /// `filled`, `partially_filled` or `rejected`.
+    pub status: String,
+    pub price: Decimal,
+    pub filled_shares: Decimal,
+    pub fee: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl SignalExecution {
+    fn from_result(result: &ExecutionResult) -> Self {
+        let (status, filled_shares, reason) = match &result.status {
+            OrderStatus::Filled => ("filled", result.size, None),
+            OrderStatus::PartiallyFilled { filled_size } => {
+                ("partially_filled", *filled_size, None)
+            }
+            OrderStatus::Rejected(reason) => ("rejected", Decimal::ZERO, Some(reason.clone())),
+        };
+        Self {
+            order_id: result.order_id.clone(),
+            status: status.to_string(),
+            price: result.price,
+            filled_shares,
+            fee: result.fee,
+            reason,
+        }
+    }
+}
+
+/// One webhook payload. Decimals are serialized as strings.
+#[derive(Debug, Clone, Serialize)]
+pub struct Signal {
+    pub event: SignalEvent,
+    pub market_id: String,
+    pub market_question: String,
+    pub category: String,
+    pub token_id: String,
+    /// `YES` or `NO`.
+    pub side: String,
+    /// Limit price of the suggested order.
+    pub price: Decimal,
+    pub shares: Decimal,
+    pub size_usd: Decimal,
+    /// Fair YES probability.
+    pub fair_value: Decimal,
+    pub edge: Decimal,
+    pub confidence: Decimal,
+    pub bucket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval: Option<SignalApproval>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution: Option<SignalExecution>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Signal {
+    /// An order about to be sent, or parked if `approval` is set.
+    pub fn opportunity(
+        order: &PreparedOrder,
+        category: &MarketCategory,
+        size_usd: Decimal,
+        approval: Option<SignalApproval>,
+    ) -> Self {
+        Self {
+            event: SignalEvent::Opportunity,
+            market_id: order.market_id.clone(),
+            market_question: order.market_question.clone(),
+            category: category.as_str().to_string(),
+            token_id: order.token_id.clone(),
+            side: order.side.to_string(),
+            price: order.price,
+            shares: order.size,
+            size_usd,
+            fair_value: order.fair_value,
+            edge: order.edge,
+            confidence: order.confidence,
+            bucket: order.bucket.clone(),
+            approval,
+            execution: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// The same order once the exchange has answered.
+    pub fn executed(
+        order: &PreparedOrder,
+        category: &MarketCategory,
+        size_usd: Decimal,
+        result: &ExecutionResult,
+    ) -> Self {
+        Self {
+            event: SignalEvent::Executed,
+            execution: Some(SignalExecution::from_result(result)),
+            ..Self::opportunity(order, category, size_usd, None)
+        }
+    }
+}
+
+pub struct SignalPublisher {
+    webhook_url: String,
+    http: reqwest::Client,
+}
+
+impl SignalPublisher {
+    pub fn new(webhook_url: String, config: &SignalConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .context("Failed to build signal webhook client")?;
+        Ok(Self { webhook_url, http })
+    }
+
+    pub async fn publish(&self, signal: &Signal) -> Result<()> {
+        let response = self
+            .http
+            .post(&self.webhook_url)
+            .json(signal)
+            .send()
+            .await
+            .context("Failed to send signal")?;
+        if !response.status().is_success() {
+            bail!("Signal webhook returned {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::Side;
+    use crate::risk::buckets::DIRECTIONAL_BUCKET;
+    use rust_decimal_macros::dec;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn order() -> PreparedOrder {
+        PreparedOrder {
+            token_id: "tok1".to_string(),
+            side: Side::Yes,
+            price: dec!(0.42),
+            size: dec!(10),
+            market_id: "m1".to_string(),
+            market_question: "Will it rain?".to_string(),
+            edge: dec!(0.11),
+            fair_value: dec!(0.53),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.2),
+            kelly_adjusted: dec!(0.1),
+            bucket: DIRECTIONAL_BUCKET.to_string(),
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publishes_opportunity_and_execution() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/signals"))
+            .and(body_partial_json(serde_json::json!({
+                "event": "opportunity",
+                "market_id": "m1",
+                "side": "YES",
+                "price": "0.42",
+                "size_usd": "4.20",
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/signals"))
+            .and(body_partial_json(serde_json::json!({
+                "event": "executed",
+                "execution": { "status": "partially_filled", "filled_shares": "4" },
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let publisher = SignalPublisher::new(
+            format!("{}/signals", server.uri()),
+            &SignalConfig::default(),
+        )
+        .unwrap();
+        let category = MarketCategory::Weather;
+        publisher
+            .publish(&Signal::opportunity(&order(), &category, dec!(4.20), None))
+            .await
+            .unwrap();
+        let result = ExecutionResult {
+            order_id: "o1".to_string(),
+            token_id: "tok1".to_string(),
+            side: Side::Yes,
+            price: dec!(0.42),
+            size: dec!(10),
+            fee: Decimal::ZERO,
+            status: OrderStatus::PartiallyFilled {
+                filled_size: dec!(4),
+            },
+        };
+        publisher
+            .publish(&Signal::executed(&order(), &category, dec!(4.20), &result))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_error_status_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        let publisher = SignalPublisher::new(server.uri(), &SignalConfig::default()).unwrap();
+        let signal = Signal::opportunity(&order(), &MarketCategory::Weather, dec!(4.20), None);
+        assert!(publisher.publish(&signal).await.is_err());
+    }
+}
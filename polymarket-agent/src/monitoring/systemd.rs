@@ -0,0 +1,206 @@
+//! systemd service notifications.
+//!
+//! Under a `Type=notify` unit systemd sets `NOTIFY_SOCKET`, and with
+//! `WatchdogSec=` also `WATCHDOG_USEC`. [`SystemdNotifier`] tells systemd
+//! when the agent is ready (after startup and the canary), pings the
+//! watchdog after every cycle and while waiting for the next one, and
+//! reports `STOPPING` on shutdown. A cycle that hangs stops the pings, so
+//! systemd kills and restarts the agent once `WatchdogSec` passes. Without
+//! `NOTIFY_SOCKET` every call does nothing.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SystemdConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl Default for SystemdConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SystemdNotifier {
+    /// `NOTIFY_SOCKET`; `None` when not run by systemd or disabled.
+    socket: Option<String>,
+    /// Half of `WATCHDOG_USEC`, when the unit has a watchdog.
+    ping_interval: Option<Duration>,
+}
+
+impl SystemdNotifier {
+    pub fn from_env(config: &SystemdConfig) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+        let socket = std::env::var("NOTIFY_SOCKET")
+            .ok()
+            .filter(|s| !s.is_empty());
+        let ping_interval = watchdog_ping_interval(
+            std::env::var("WATCHDOG_USEC").ok().as_deref(),
+            std::env::var("WATCHDOG_PID").ok().as_deref(),
+            std::process::id(),
+        );
+        if socket.is_some() {
+            debug!(watchdog = ?ping_interval, "systemd notifications enabled");
+        }
+        Self {
+            ping_interval: socket.as_ref().and(ping_interval),
+            socket,
+        }
+    }
+
+    /// Startup finished; `Type=notify` units become active.
+    pub fn ready(&self) {
+        self.notify("READY=1");
+    }
+
+    /// Reset the watchdog timer.
+    pub fn watchdog(&self) {
+        if self.ping_interval.is_some() {
+            self.notify("WATCHDOG=1");
+        }
+    }
+
+    /// Graceful shutdown has begun.
+    pub fn stopping(&self) {
+        self.notify("STOPPING=1");
+    }
+
+    /// One-line status shown by `systemctl status`.
+    pub fn status(&self, status: &str) {
+        self.notify(&format!("STATUS={status}"));
+    }
+
+    /// Sleep for `duration`, pinging the watchdog often enough to keep it
+    /// from firing.
+    pub async fn idle(&self, duration: Duration) {
+        let Some(period) = self.ping_interval else {
+            tokio::time::sleep(duration).await;
+            return;
+        };
+        let deadline = tokio::time::Instant::now() + duration;
+        loop {
+            self.watchdog();
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return;
+            }
+            tokio::time::sleep(period.min(deadline - now)).await;
+        }
+    }
+
+    fn notify(&self, message: &str) {
+        let Some(socket) = &self.socket else {
+            return;
+        };
+        if let Err(e) = send(socket, message) {
+            warn!(error = %e, message, "Failed to notify systemd");
+        }
+    }
+}
+
+/// Half the watchdog timeout, if the watchdog is meant for this process.
+fn watchdog_ping_interval(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    if let Some(pid) = pid {
+        if pid.parse::<u32>().ok() != Some(own_pid) {
+            return None;
+        }
+    }
+    let usec: u64 = usec?.parse().ok().filter(|u| *u > 0)?;
+    Some(Duration::from_micros(usec) / 2)
+}
+
+#[cfg(unix)]
+fn send(socket: &str, message: &str) -> std::io::Result<()> {
+    use std::os::unix::net::UnixDatagram;
+
+    let sock = UnixDatagram::unbound()?;
+    match socket.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            sock.send_to_addr(message.as_bytes(), &addr)?;
+        }
+        _ => {
+            sock.send_to(message.as_bytes(), socket)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send(_socket: &str, _message: &str) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixDatagram;
+
+    #[test]
+    fn test_watchdog_ping_interval() {
+        assert_eq!(
+            watchdog_ping_interval(Some("30000000"), None, 42),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(
+            watchdog_ping_interval(Some("30000000"), Some("42"), 42),
+            Some(Duration::from_secs(15))
+        );
+        // Meant for another process
+        assert_eq!(
+            watchdog_ping_interval(Some("30000000"), Some("7"), 42),
+            None
+        );
+        assert_eq!(watchdog_ping_interval(Some("0"), None, 42), None);
+        assert_eq!(watchdog_ping_interval(None, None, 42), None);
+    }
+
+    #[test]
+    fn test_notifications_reach_socket() {
+        let path = std::env::temp_dir().join(format!("notify-{}.sock", uuid::Uuid::new_v4()));
+        let listener = UnixDatagram::bind(&path).unwrap();
+        let notifier = SystemdNotifier {
+            socket: Some(path.display().to_string()),
+            ping_interval: Some(Duration::from_secs(15)),
+        };
+        notifier.ready();
+        notifier.watchdog();
+        notifier.status("Cycle 3: ALIVE");
+        notifier.stopping();
+
+        let mut received = Vec::new();
+        let mut buf = [0u8; 256];
+        for _ in 0..4 {
+            let n = listener.recv(&mut buf).unwrap();
+            received.push(String::from_utf8_lossy(&buf[..n]).to_string());
+        }
+        assert_eq!(
+            received,
+            [
+                "READY=1",
+                "WATCHDOG=1",
+                "STATUS=Cycle 3: ALIVE",
+                "STOPPING=1"
+            ]
+        );
+        std::fs::remove_file(path).unwrap();
+
+        // Outside systemd nothing is sent
+        SystemdNotifier::default().ready();
+    }
+}
@@ -0,0 +1,369 @@
+//! Heartbeat watchdog.
+//!
+//! The regular alerts only fire when something happens, so a hung cycle, a
+//! data source that quietly fails every fetch or stays degraded (see
+//! [`crate::data::health`]), or a crashed dashboard would go unnoticed. The watchdog runs on its own task, checks the health state
+//! every `check_interval_seconds`, and alerts once per incident, with a
+//! follow-up when it clears.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::task::{AbortHandle, JoinHandle};
+use tracing::{info, warn};
+
+use crate::monitoring::alerts::AlertClient;
+use crate::monitoring::health::HealthState;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchdogConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Alert when no cycle has completed for this many cycle intervals.
+    #[serde(default = "default_stall_intervals")]
+    pub stall_intervals: u32,
+    /// Alert when a data source has failed this many fetches in a row.
+    #[serde(default = "default_source_failure_streak")]
+    pub source_failure_streak: u32,
+    /// Alert when a data source has been degraded for more than this many
+    /// fetches in a row.
+    #[serde(default = "default_source_degraded_cycles")]
+    pub source_degraded_cycles: u32,
+    #[serde(default = "default_check_interval_seconds")]
+    pub check_interval_seconds: u64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            stall_intervals: default_stall_intervals(),
+            source_failure_streak: default_source_failure_streak(),
+            source_degraded_cycles: default_source_degraded_cycles(),
+            check_interval_seconds: default_check_interval_seconds(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_stall_intervals() -> u32 {
+    3
+}
+
+fn default_source_failure_streak() -> u32 {
+    3
+}
+
+fn default_source_degraded_cycles() -> u32 {
+    5
+}
+
+fn default_check_interval_seconds() -> u64 {
+    60
+}
+
+/// Something the watchdog noticed, or noticed recovering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchdogAlert {
+    Stalled { minutes_since_cycle: i64 },
+    Resumed,
+    SourceFailing { source: String, streak: u32 },
+    SourceRecovered { source: String },
+    SourceDegraded { source: String, cycles: u32 },
+    SourceHealthy { source: String },
+    TaskDied { task: String },
+}
+
+impl WatchdogAlert {
+    pub fn is_recovery(&self) -> bool {
+        matches!(
+            self,
+            WatchdogAlert::Resumed
+                | WatchdogAlert::SourceRecovered { .. }
+                | WatchdogAlert::SourceHealthy { .. }
+        )
+    }
+}
+
+impl fmt::Display for WatchdogAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchdogAlert::Stalled {
+                minutes_since_cycle,
+            } => write!(
+                f,
+                "No cycle has completed in {minutes_since_cycle} min — the agent may be hung"
+            ),
+            WatchdogAlert::Resumed => write!(f, "Cycles are completing again"),
+            WatchdogAlert::SourceFailing { source, streak } => {
+                write!(
+                    f,
+                    "Data source {source} has failed {streak} fetches in a row"
+                )
+            }
+            WatchdogAlert::SourceRecovered { source } => {
+                write!(f, "Data source {source} is fetching again")
+            }
+            WatchdogAlert::SourceDegraded { source, cycles } => write!(
+                f,
+                "Data source {source} has been degraded for {cycles} fetches — its confidence is demoted"
+            ),
+            WatchdogAlert::SourceHealthy { source } => {
+                write!(f, "Data source {source} is healthy again")
+            }
+            WatchdogAlert::TaskDied { task } => write!(f, "The {task} has stopped"),
+        }
+    }
+}
+
+/// Incident state, so each problem is reported once rather than every check.
+#[derive(Debug)]
+pub struct Watchdog {
+    config: WatchdogConfig,
+    cycle_interval: Duration,
+    stalled: bool,
+    failing_sources: HashSet<String>,
+    degraded_sources: HashSet<String>,
+    dead_tasks: HashSet<String>,
+}
+
+impl Watchdog {
+    pub fn new(config: WatchdogConfig, cycle_interval: Duration) -> Self {
+        Self {
+            config,
+            cycle_interval,
+            stalled: false,
+            failing_sources: HashSet::new(),
+            degraded_sources: HashSet::new(),
+            dead_tasks: HashSet::new(),
+        }
+    }
+
+    /// Compare the latest heartbeat against the thresholds. `finished` names
+    /// the monitored tasks that have exited.
+    pub fn check(
+        &mut self,
+        now: DateTime<Utc>,
+        last_cycle_at: DateTime<Utc>,
+        source_failures: &BTreeMap<String, u32>,
+        finished: &[&str],
+    ) -> Vec<WatchdogAlert> {
+        let mut alerts = Vec::new();
+
+        let stall_after = self.cycle_interval.as_secs() * u64::from(self.config.stall_intervals);
+        let silent = (now - last_cycle_at).num_seconds().max(0) as u64;
+        if silent > stall_after && !self.stalled {
+            self.stalled = true;
+            alerts.push(WatchdogAlert::Stalled {
+                minutes_since_cycle: (silent / 60) as i64,
+            });
+        } else if silent <= stall_after && self.stalled {
+            self.stalled = false;
+            alerts.push(WatchdogAlert::Resumed);
+        }
+
+        for (source, &streak) in source_failures {
+            if streak >= self.config.source_failure_streak {
+                if self.failing_sources.insert(source.clone()) {
+                    alerts.push(WatchdogAlert::SourceFailing {
+                        source: source.clone(),
+                        streak,
+                    });
+                }
+            } else if streak == 0 && self.failing_sources.remove(source) {
+                alerts.push(WatchdogAlert::SourceRecovered {
+                    source: source.clone(),
+                });
+            }
+        }
+        // Sources that stopped reporting failures have recovered
+        let recovered: Vec<String> = self
+            .failing_sources
+            .iter()
+            .filter(|s| !source_failures.contains_key(*s))
+            .cloned()
+            .collect();
+        for source in recovered {
+            self.failing_sources.remove(&source);
+            alerts.push(WatchdogAlert::SourceRecovered { source });
+        }
+
+        for task in finished {
+            if self.dead_tasks.insert(task.to_string()) {
+                alerts.push(WatchdogAlert::TaskDied {
+                    task: task.to_string(),
+                });
+            }
+        }
+
+        alerts
+    }
+
+    /// Compare each degraded source's streak against
+    /// `source_degraded_cycles`; sources no longer listed are healthy.
+    pub fn check_degraded(&mut self, degraded: &BTreeMap<String, u32>) -> Vec<WatchdogAlert> {
+        let mut alerts = Vec::new();
+        for (source, &cycles) in degraded {
+            if cycles > self.config.source_degraded_cycles
+                && self.degraded_sources.insert(source.clone())
+            {
+                alerts.push(WatchdogAlert::SourceDegraded {
+                    source: source.clone(),
+                    cycles,
+                });
+            }
+        }
+        let healthy: Vec<String> = self
+            .degraded_sources
+            .iter()
+            .filter(|s| !degraded.contains_key(*s))
+            .cloned()
+            .collect();
+        for source in healthy {
+            self.degraded_sources.remove(&source);
+            alerts.push(WatchdogAlert::SourceHealthy { source });
+        }
+        alerts
+    }
+}
+
+/// Spawn the watchdog loop. `tasks` are background servers whose exit
+/// should raise an alert, e.g. `("dashboard server", handle.abort_handle())`.
+pub fn spawn_watchdog(
+    config: WatchdogConfig,
+    cycle_interval: Duration,
+    health: HealthState,
+    alerts: AlertClient,
+    tasks: Vec<(&'static str, AbortHandle)>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let check_every = Duration::from_secs(config.check_interval_seconds.max(1));
+        let mut watchdog = Watchdog::new(config, cycle_interval);
+        info!(
+            check_s = check_every.as_secs(),
+            tasks = tasks.len(),
+            "Watchdog started"
+        );
+
+        loop {
+            tokio::time::sleep(check_every).await;
+            let (last_cycle_at, source_failures) = health.heartbeat().await;
+            let finished: Vec<&str> = tasks
+                .iter()
+                .filter(|(_, handle)| handle.is_finished())
+                .map(|(name, _)| *name)
+                .collect();
+
+            let degraded = health.degraded_sources().await;
+            let mut raised = watchdog.check(Utc::now(), last_cycle_at, &source_failures, &finished);
+            raised.extend(watchdog.check_degraded(&degraded));
+            for alert in raised {
+                if alert.is_recovery() {
+                    info!(alert = %alert, "Watchdog recovery");
+                } else {
+                    warn!(alert = %alert, "Watchdog alert");
+                }
+                if let Err(e) = alerts.watchdog(&alert).await {
+                    warn!(error = %e, "Failed to send watchdog alert");
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+
+    fn watchdog() -> Watchdog {
+        Watchdog::new(WatchdogConfig::default(), Duration::from_secs(600))
+    }
+
+    #[test]
+    fn test_stall_alerts_once_then_resumes() {
+        let mut dog = watchdog();
+        let now = Utc::now();
+        let none = BTreeMap::new();
+
+        assert!(dog
+            .check(now, now - ChronoDuration::minutes(20), &none, &[])
+            .is_empty());
+        let alerts = dog.check(now, now - ChronoDuration::minutes(31), &none, &[]);
+        assert_eq!(
+            alerts,
+            vec![WatchdogAlert::Stalled {
+                minutes_since_cycle: 31
+            }]
+        );
+        assert!(dog
+            .check(now, now - ChronoDuration::minutes(40), &none, &[])
+            .is_empty());
+        assert_eq!(
+            dog.check(now, now, &none, &[]),
+            vec![WatchdogAlert::Resumed]
+        );
+    }
+
+    #[test]
+    fn test_source_failures_and_dead_tasks() {
+        let mut dog = watchdog();
+        let now = Utc::now();
+        let failures: BTreeMap<String, u32> =
+            [("noaa".to_string(), 3), ("espn".to_string(), 1)].into();
+
+        let alerts = dog.check(now, now, &failures, &["dashboard server"]);
+        assert_eq!(
+            alerts,
+            vec![
+                WatchdogAlert::SourceFailing {
+                    source: "noaa".to_string(),
+                    streak: 3
+                },
+                WatchdogAlert::TaskDied {
+                    task: "dashboard server".to_string()
+                },
+            ]
+        );
+        // Already reported
+        assert!(dog
+            .check(now, now, &failures, &["dashboard server"])
+            .is_empty());
+
+        let healthy: BTreeMap<String, u32> = [("noaa".to_string(), 0)].into();
+        assert_eq!(
+            dog.check(now, now, &healthy, &[]),
+            vec![WatchdogAlert::SourceRecovered {
+                source: "noaa".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_degraded_source_alerts_past_threshold() {
+        let mut dog = watchdog();
+        let degraded = |cycles: u32| BTreeMap::from([("noaa".to_string(), cycles)]);
+
+        assert!(dog.check_degraded(&degraded(5)).is_empty());
+        assert_eq!(
+            dog.check_degraded(&degraded(6)),
+            vec![WatchdogAlert::SourceDegraded {
+                source: "noaa".to_string(),
+                cycles: 6
+            }]
+        );
+        assert!(dog.check_degraded(&degraded(7)).is_empty());
+        assert_eq!(
+            dog.check_degraded(&BTreeMap::new()),
+            vec![WatchdogAlert::SourceHealthy {
+                source: "noaa".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,563 @@
+//! Weekly performance report.
+//!
+//! Once a week (by default Monday 09:00 UTC) the agent summarizes the past
+//! seven days — P&L, win rate by category, the biggest win and loss,
+//! calibration drift, API spend and uptime — posts it to Discord as an embed
+//! and writes the same numbers to a standalone HTML page in `report_dir`.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Utc, Weekday};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::backtesting::report::escape_html;
+use crate::db::export::parse_timestamp;
+use crate::db::store::{ApiCostRecord, CalibrationRecord, CycleRecord, TradeRecord};
+use crate::market::category::categorize;
+
+/// `agent_meta` key holding when the last weekly report was sent.
+pub const LAST_SENT_KEY: &str = "weekly_report_sent_at";
+
+const REPORT_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeeklyReportConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// UTC day the report goes out, e.g. `"mon"`.
+    #[serde(default = "default_weekday")]
+    pub weekday: Weekday,
+    /// UTC hour the report goes out.
+    #[serde(default = "default_hour")]
+    pub hour: u32,
+    /// Directory the HTML copy is written to.
+    #[serde(default = "default_report_dir")]
+    pub report_dir: String,
+}
+
+impl Default for WeeklyReportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            weekday: default_weekday(),
+            hour: default_hour(),
+            report_dir: default_report_dir(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_weekday() -> Weekday {
+    Weekday::Mon
+}
+
+fn default_hour() -> u32 {
+    9
+}
+
+fn default_report_dir() -> String {
+    "data/reports".to_string()
+}
+
+/// The most recent scheduled send time at or before `now`.
+pub fn last_slot(now: DateTime<Utc>, weekday: Weekday, hour: u32) -> DateTime<Utc> {
+    let days_back = (now.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+    let slot = (now.date_naive() - Duration::days(i64::from(days_back)))
+        .and_hms_opt(hour.min(23), 0, 0)
+        .expect("valid hour")
+        .and_utc();
+    if slot > now {
+        slot - Duration::days(7)
+    } else {
+        slot
+    }
+}
+
+/// Whether a report is owed: the latest slot has passed since the last one
+/// was sent, or none ever was.
+pub fn is_due(
+    now: DateTime<Utc>,
+    last_sent: Option<DateTime<Utc>>,
+    config: &WeeklyReportConfig,
+) -> bool {
+    config.enabled
+        && last_sent.map_or(true, |sent| {
+            sent < last_slot(now, config.weekday, config.hour)
+        })
+}
+
+/// Resolved trades and P&L for one market category.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryStats {
+    pub category: String,
+    pub trades: usize,
+    pub wins: usize,
+    pub pnl: Decimal,
+}
+
+impl CategoryStats {
+    pub fn win_rate(&self) -> Decimal {
+        Decimal::from(self.wins) / Decimal::from(self.trades.max(1))
+    }
+}
+
+/// A single notable trade.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TradeHighlight {
+    pub market: String,
+    pub direction: String,
+    pub pnl: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WeeklyReport {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub trades_placed: usize,
+    pub trades_resolved: usize,
+    pub wins: usize,
+    /// Net realized P&L of trades resolved in the window.
+    pub pnl: Decimal,
+    pub bankroll_start: Option<Decimal>,
+    pub bankroll_end: Option<Decimal>,
+    /// Sorted by P&L, best first.
+    pub by_category: Vec<CategoryStats>,
+    pub biggest_win: Option<TradeHighlight>,
+    pub biggest_loss: Option<TradeHighlight>,
+    /// Brier score of predictions resolved in the window.
+    pub brier: Option<Decimal>,
+    /// Brier score of everything resolved before the window.
+    pub prior_brier: Option<Decimal>,
+    pub api_cost: Decimal,
+    pub api_cost_by_provider: BTreeMap<String, Decimal>,
+    pub cycles: usize,
+    /// Completed cycles as a share of the cycles the interval allows.
+    pub uptime: Decimal,
+}
+
+impl WeeklyReport {
+    pub fn win_rate(&self) -> Decimal {
+        Decimal::from(self.wins) / Decimal::from(self.trades_resolved.max(1))
+    }
+
+    /// Change in Brier score against earlier predictions; positive means
+    /// forecasts got worse.
+    pub fn calibration_drift(&self) -> Option<Decimal> {
+        Some(self.brier? - self.prior_brier?)
+    }
+}
+
+/// Summarize the seven days ending at `now`. Records are the full history;
+/// the window filter happens here.
+pub fn build(
+    now: DateTime<Utc>,
+    trades: &[TradeRecord],
+    cycles: &[CycleRecord],
+    api_costs: &[ApiCostRecord],
+    calibration: &[CalibrationRecord],
+    cycle_interval_seconds: u64,
+) -> WeeklyReport {
+    let start = now - Duration::days(REPORT_DAYS);
+    let in_window = |ts: Option<DateTime<Utc>>| ts.is_some_and(|t| t > start && t <= now);
+
+    let trades_placed = trades.iter().filter(|t| in_window(t.created_at)).count();
+
+    let resolved: Vec<&TradeRecord> = trades
+        .iter()
+        .filter(|t| matches!(t.status.as_str(), "RESOLVED_WIN" | "RESOLVED_LOSS"))
+        .filter(|t| in_window(t.resolved_at))
+        .collect();
+    let pnl_of = |t: &TradeRecord| t.pnl.unwrap_or(Decimal::ZERO);
+
+    let mut categories: BTreeMap<String, CategoryStats> = BTreeMap::new();
+    for t in &resolved {
+        let category = categorize(&[], t.market_question.as_deref().unwrap_or(""));
+        let stats = categories
+            .entry(category.as_str().to_string())
+            .or_insert_with(|| CategoryStats {
+                category: category.as_str().to_string(),
+                trades: 0,
+                wins: 0,
+                pnl: Decimal::ZERO,
+            });
+        stats.trades += 1;
+        stats.wins += usize::from(t.status == "RESOLVED_WIN");
+        stats.pnl += pnl_of(t);
+    }
+    let mut by_category: Vec<CategoryStats> = categories.into_values().collect();
+    by_category.sort_by_key(|c| Reverse(c.pnl));
+
+    let highlight = |t: &&TradeRecord| TradeHighlight {
+        market: t
+            .market_question
+            .clone()
+            .unwrap_or_else(|| t.market_id.clone()),
+        direction: t.direction.clone(),
+        pnl: pnl_of(t),
+    };
+    let biggest_win = resolved
+        .iter()
+        .filter(|t| pnl_of(t) > Decimal::ZERO)
+        .max_by_key(|t| pnl_of(t))
+        .map(highlight);
+    let biggest_loss = resolved
+        .iter()
+        .filter(|t| pnl_of(t) < Decimal::ZERO)
+        .min_by_key(|t| pnl_of(t))
+        .map(highlight);
+
+    let (mut week, mut prior) = (Vec::new(), Vec::new());
+    for r in calibration.iter().filter(|r| r.resolved) {
+        let (Some(p), Some(outcome)) = (
+            Decimal::from_str(&r.fair_value).ok(),
+            r.actual_outcome
+                .as_deref()
+                .and_then(|o| Decimal::from_str(o).ok()),
+        ) else {
+            continue;
+        };
+        let score = (p - outcome) * (p - outcome);
+        if in_window(r.resolved_at.as_deref().and_then(parse_timestamp)) {
+            week.push(score);
+        } else {
+            prior.push(score);
+        }
+    }
+
+    let mut api_cost_by_provider = BTreeMap::new();
+    for c in api_costs.iter().filter(|c| in_window(c.created_at)) {
+        *api_cost_by_provider
+            .entry(c.provider.clone())
+            .or_insert(Decimal::ZERO) += c.cost;
+    }
+
+    let window_cycles: Vec<&CycleRecord> =
+        cycles.iter().filter(|c| in_window(c.created_at)).collect();
+    // An agent first started mid-week is only expected to cycle since then
+    let first_cycle = cycles.iter().filter_map(|c| c.created_at).min();
+    let expected_from = first_cycle.map_or(start, |first| first.max(start));
+    let expected =
+        (now - expected_from).num_seconds().max(0) as u64 / cycle_interval_seconds.max(1) + 1;
+    let uptime = (Decimal::from(window_cycles.len()) / Decimal::from(expected)).min(Decimal::ONE);
+    let bankroll = |c: &&CycleRecord| c.bankroll;
+
+    WeeklyReport {
+        start,
+        end: now,
+        trades_placed,
+        trades_resolved: resolved.len(),
+        wins: resolved
+            .iter()
+            .filter(|t| t.status == "RESOLVED_WIN")
+            .count(),
+        pnl: resolved.iter().map(|t| pnl_of(t)).sum(),
+        bankroll_start: window_cycles.first().and_then(bankroll),
+        bankroll_end: window_cycles.last().and_then(bankroll),
+        by_category,
+        biggest_win,
+        biggest_loss,
+        brier: mean(&week),
+        prior_brier: mean(&prior),
+        api_cost: api_cost_by_provider.values().sum(),
+        api_cost_by_provider,
+        cycles: window_cycles.len(),
+        uptime,
+    }
+}
+
+fn mean(values: &[Decimal]) -> Option<Decimal> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<Decimal>() / Decimal::from(values.len()))
+    }
+}
+
+/// Write the report as `weekly-<end date>.html` in `dir`.
+pub fn write_html(report: &WeeklyReport, dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create report directory {}", dir.display()))?;
+    let path = dir.join(format!("weekly-{}.html", report.end.format("%Y-%m-%d")));
+    fs::write(&path, html(report))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// A standalone HTML page with the summary, category and spend tables.
+pub fn html(report: &WeeklyReport) -> String {
+    let title = format!(
+        "Weekly report {} – {}",
+        report.start.format("%Y-%m-%d"),
+        report.end.format("%Y-%m-%d")
+    );
+    let highlight = |h: &Option<TradeHighlight>| {
+        h.as_ref()
+            .map(|h| {
+                format!(
+                    "{} ({} {})",
+                    usd(h.pnl),
+                    h.direction,
+                    escape_html(&h.market)
+                )
+            })
+            .unwrap_or_else(|| "–".to_string())
+    };
+    let optional = |v: Option<Decimal>| {
+        v.map(|v| v.round_dp(4).to_string())
+            .unwrap_or_else(|| "–".to_string())
+    };
+    let summary = [
+        ("Net P&amp;L", usd(report.pnl)),
+        (
+            "Bankroll",
+            format!(
+                "{} → {}",
+                report.bankroll_start.map(usd).unwrap_or_default(),
+                report.bankroll_end.map(usd).unwrap_or_default()
+            ),
+        ),
+        ("Trades placed", report.trades_placed.to_string()),
+        (
+            "Trades resolved",
+            format!("{} ({} won)", report.trades_resolved, report.wins),
+        ),
+        ("Win rate", pct(report.win_rate())),
+        ("Biggest win", highlight(&report.biggest_win)),
+        ("Biggest loss", highlight(&report.biggest_loss)),
+        ("Brier score", optional(report.brier)),
+        ("Brier score before", optional(report.prior_brier)),
+        ("Calibration drift", optional(report.calibration_drift())),
+        ("API spend", usd(report.api_cost)),
+        (
+            "Uptime",
+            format!("{} ({} cycles)", pct(report.uptime), report.cycles),
+        ),
+    ];
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title>\n\
+         <style>body{{font-family:sans-serif;margin:2em;color:#222}}\
+         table{{border-collapse:collapse;margin-bottom:1.5em}}\
+         td,th{{border:1px solid #ccc;padding:4px 8px;text-align:right}}\
+         td:first-child,th:first-child{{text-align:left}}</style>\n\
+         </head><body>\n<h1>{title}</h1>\n<table>\n"
+    );
+    for (name, value) in &summary {
+        let _ = writeln!(html, "<tr><th>{name}</th><td>{value}</td></tr>");
+    }
+    html.push_str(
+        "</table>\n<h2>By category</h2>\n<table>\n<tr><th>Category</th><th>Trades</th>\
+         <th>Win rate</th><th>P&amp;L</th></tr>\n",
+    );
+    for c in &report.by_category {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&c.category),
+            c.trades,
+            pct(c.win_rate()),
+            usd(c.pnl),
+        );
+    }
+    html.push_str(
+        "</table>\n<h2>API spend</h2>\n<table>\n<tr><th>Provider</th><th>Cost</th></tr>\n",
+    );
+    for (provider, cost) in &report.api_cost_by_provider {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td></tr>",
+            escape_html(provider),
+            usd(*cost)
+        );
+    }
+    html.push_str("</table>\n</body></html>\n");
+    html
+}
+
+pub fn usd(v: Decimal) -> String {
+    format!("${}", v.round_dp(2))
+}
+
+pub fn pct(v: Decimal) -> String {
+    format!("{:.1}%", v * dec!(100))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        parse_timestamp(s).unwrap()
+    }
+
+    fn trade(question: &str, status: &str, pnl: Decimal, resolved_at: &str) -> TradeRecord {
+        TradeRecord {
+            id: None,
+            cycle: 1,
+            market_id: "m".to_string(),
+            market_question: Some(question.to_string()),
+            direction: "YES".to_string(),
+            entry_price: dec!(0.50),
+            size: dec!(10),
+            edge_at_entry: dec!(0.1),
+            claude_fair_value: dec!(0.6),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.1),
+            kelly_adjusted: dec!(0.05),
+            status: status.to_string(),
+            pnl: Some(pnl),
+            created_at: Some(at("2026-10-10 12:00:00")),
+            resolved_at: Some(at(resolved_at)),
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        }
+    }
+
+    fn calibration(fair_value: &str, outcome: &str, resolved_at: &str) -> CalibrationRecord {
+        CalibrationRecord {
+            id: None,
+            market_id: "m".to_string(),
+            claude_confidence: "0.8".to_string(),
+            fair_value: fair_value.to_string(),
+            market_price_at_entry: "0.5".to_string(),
+            actual_outcome: Some(outcome.to_string()),
+            forecast_correct: None,
+            resolved: true,
+            created_at: None,
+            resolved_at: Some(resolved_at.to_string()),
+        }
+    }
+
+    fn cycle(created_at: &str, bankroll: Decimal) -> CycleRecord {
+        CycleRecord {
+            id: None,
+            cycle_number: 0,
+            markets_scanned: None,
+            opportunities_found: None,
+            trades_placed: None,
+            api_cost: None,
+            bankroll: Some(bankroll),
+            unrealized_pnl: None,
+            agent_state: "ALIVE".to_string(),
+            duration_ms: None,
+            truncated_phases: None,
+            timings: Default::default(),
+            created_at: Some(at(created_at)),
+        }
+    }
+
+    #[test]
+    fn test_schedule() {
+        let config = WeeklyReportConfig::default();
+        // 2026-10-14 is a Wednesday
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 12, 0, 0).unwrap();
+        assert_eq!(
+            last_slot(now, Weekday::Mon, 9),
+            Utc.with_ymd_and_hms(2026, 10, 12, 9, 0, 0).unwrap()
+        );
+        // Monday before the hour still points at last week
+        let monday = Utc.with_ymd_and_hms(2026, 10, 12, 8, 0, 0).unwrap();
+        assert_eq!(
+            last_slot(monday, Weekday::Mon, 9),
+            Utc.with_ymd_and_hms(2026, 10, 5, 9, 0, 0).unwrap()
+        );
+
+        assert!(is_due(now, None, &config));
+        assert!(is_due(now, Some(at("2026-10-12T08:00:00Z")), &config));
+        assert!(!is_due(now, Some(at("2026-10-12T09:05:00Z")), &config));
+    }
+
+    #[test]
+    fn test_build() {
+        let now = at("2026-10-14T12:00:00Z");
+        let trades = vec![
+            trade(
+                "Will BTC close above $100k?",
+                "RESOLVED_WIN",
+                dec!(4.00),
+                "2026-10-12T10:00:00Z",
+            ),
+            trade(
+                "Will it rain in Chicago tomorrow?",
+                "RESOLVED_LOSS",
+                dec!(-3.00),
+                "2026-10-13T10:00:00Z",
+            ),
+            trade(
+                "Will it snow in Denver tomorrow?",
+                "RESOLVED_WIN",
+                dec!(1.50),
+                "2026-10-13T11:00:00Z",
+            ),
+            // Resolved before the window
+            trade(
+                "Will ETH flip BTC?",
+                "RESOLVED_LOSS",
+                dec!(-9.00),
+                "2026-10-01T10:00:00Z",
+            ),
+        ];
+        let calibration = vec![
+            calibration("0.8", "1", "2026-10-12T10:00:00Z"),
+            calibration("0.6", "0", "2026-10-13T10:00:00Z"),
+            calibration("0.9", "1", "2026-10-01T10:00:00Z"),
+        ];
+        let api_costs = vec![ApiCostRecord {
+            id: None,
+            provider: "anthropic".to_string(),
+            endpoint: None,
+            input_tokens: None,
+            output_tokens: None,
+            cost: dec!(1.25),
+            cycle: None,
+            created_at: Some(at("2026-10-13 09:00:00")),
+        }];
+        // Started a day ago on a 6-hour interval: 5 cycles expected, 4 ran
+        let cycles = vec![
+            cycle("2026-10-13 12:00:00", dec!(100)),
+            cycle("2026-10-13 18:00:00", dec!(101)),
+            cycle("2026-10-14 00:00:00", dec!(102)),
+            cycle("2026-10-14 06:00:00", dec!(102.5)),
+        ];
+
+        let report = build(now, &trades, &cycles, &api_costs, &calibration, 6 * 3600);
+        assert_eq!(report.trades_resolved, 3);
+        assert_eq!(report.wins, 2);
+        assert_eq!(report.pnl, dec!(2.50));
+        assert_eq!(report.by_category[0].category, "crypto");
+        assert_eq!(report.by_category[1].category, "weather");
+        assert_eq!(report.by_category[1].trades, 2);
+        assert_eq!(report.by_category[1].win_rate(), dec!(0.5));
+        assert_eq!(report.biggest_win.as_ref().unwrap().pnl, dec!(4.00));
+        assert_eq!(report.biggest_loss.as_ref().unwrap().pnl, dec!(-3.00));
+        // (0.04 + 0.36) / 2 this week vs 0.01 before
+        assert_eq!(report.brier, Some(dec!(0.2)));
+        assert_eq!(report.calibration_drift(), Some(dec!(0.19)));
+        assert_eq!(report.api_cost, dec!(1.25));
+        assert_eq!(report.cycles, 4);
+        assert_eq!(report.uptime, dec!(0.8));
+        assert_eq!(report.bankroll_end, Some(dec!(102.5)));
+
+        let page = html(&report);
+        assert!(page.contains("<td>weather</td><td>2</td><td>50.0%</td><td>$-1.50</td>"));
+        assert!(page.contains("anthropic"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Human approval for large trades.
+//!
+//! Orders at or above the configured size are parked in `pending_approvals`
+//! instead of being sent. An alert points to the dashboard, where they are
+//! approved or rejected (by POST only); approved orders are re-checked and
+//! executed at the start of the next cycle, and anything not acted on within
+//! the TTL expires.
+
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use rust_decimal::Decimal;
+
+use crate::config::ApprovalConfig;
+use crate::db::store::{ApprovalRecord, Store};
+use crate::execution::order::PreparedOrder;
+use crate::market::models::{MarketCategory, Side};
+
+pub const PENDING_APPROVAL: &str = "PENDING_APPROVAL";
+pub const APPROVED: &str = "APPROVED";
+pub const REJECTED: &str = "REJECTED";
+pub const EXECUTED: &str = "EXECUTED";
+pub const FAILED: &str = "FAILED";
+/// Timed out, or no longer passing the pre-trade checks when approved.
+pub const EXPIRED: &str = "EXPIRED";
+
+/// Whether an order of `size_usd` must wait for a human.
+pub fn requires_approval(config: &ApprovalConfig, size_usd: Decimal) -> bool {
+    config.enabled && size_usd >= config.threshold_usd
+}
+
+/// Park an order for approval. Returns the approval id.
+pub async fn park(
+    store: &Store,
+    order: &PreparedOrder,
+    category: &MarketCategory,
+    size_usd: Decimal,
+    cycle: u64,
+    config: &ApprovalConfig,
+    now: DateTime<Utc>,
+) -> Result<i64> {
+    let expires_at = now + Duration::seconds(config.ttl_seconds as i64);
+    let record = ApprovalRecord {
+        id: None,
+        cycle: cycle as i64,
+        market_id: order.market_id.clone(),
+        market_question: order.market_question.clone(),
+        category: category.as_str().to_string(),
+        token_id: order.token_id.clone(),
+        direction: order.side.to_string(),
+        price: order.price.to_string(),
+        size: order.size.to_string(),
+        size_usd: size_usd.to_string(),
+        edge: order.edge.to_string(),
+        fair_value: order.fair_value.to_string(),
+        confidence: order.confidence.to_string(),
+        kelly_raw: order.kelly_raw.to_string(),
+        kelly_adjusted: order.kelly_adjusted.to_string(),
+        bucket: order.bucket.clone(),
+        status: PENDING_APPROVAL.to_string(),
+        created_at: None,
+        expires_at: expires_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        decided_at: None,
+    };
+    store.insert_approval(&record).await
+}
+
+/// Approve or reject a pending order. Returns false if it is no longer
+/// pending (already decided or expired).
+pub async fn decide(store: &Store, id: i64, approve: bool, now: DateTime<Utc>) -> Result<bool> {
+    let to = if approve { APPROVED } else { REJECTED };
+    store
+        .transition_approval(id, PENDING_APPROVAL, to, now)
+        .await
+}
+
+/// A parked order restored for execution.
+#[derive(Debug, Clone)]
+pub struct ApprovedOrder {
+    pub id: i64,
+    pub order: PreparedOrder,
+    pub category: MarketCategory,
+    pub size_usd: Decimal,
+}
+
+impl ApprovedOrder {
+    pub fn from_record(record: &ApprovalRecord) -> Result<Self> {
+        let id = record.id.context("Approval record has no id")?;
+        let side = match record.direction.as_str() {
+            "YES" => Side::Yes,
+            "NO" => Side::No,
+            other => bail!("Unknown direction '{other}' on approval {id}"),
+        };
+        let dec = |field: &str, value: &str| {
+            Decimal::from_str(value)
+                .with_context(|| format!("Invalid {field} '{value}' on approval {id}"))
+        };
+        Ok(Self {
+            id,
+            order: PreparedOrder {
+                token_id: record.token_id.clone(),
+                side,
+                price: dec("price", &record.price)?,
+                size: dec("size", &record.size)?,
+                market_id: record.market_id.clone(),
+                market_question: record.market_question.clone(),
+                edge: dec("edge", &record.edge)?,
+                fair_value: dec("fair_value", &record.fair_value)?,
+                confidence: dec("confidence", &record.confidence)?,
+                kelly_raw: dec("kelly_raw", &record.kelly_raw)?,
+                kelly_adjusted: dec("kelly_adjusted", &record.kelly_adjusted)?,
+                bucket: record.bucket.clone(),
+                tags: Vec::new(),
+            },
+            category: MarketCategory::from_name(&record.category),
+            size_usd: dec("size_usd", &record.size_usd)?,
+        })
+    }
+}
+
+/// Whether an approved order may still be placed at `fresh_price`: at most
+/// `max_slippage_pct` above the price that was approved, and still below
+/// the fair value of the side bought.
+pub fn still_priced(
+    order: &PreparedOrder,
+    fresh_price: Decimal,
+    max_slippage_pct: Decimal,
+) -> bool {
+    let fair = match order.side {
+        Side::Yes => order.fair_value,
+        Side::No => Decimal::ONE - order.fair_value,
+    };
+    fresh_price <= order.price * (Decimal::ONE + max_slippage_pct) && fresh_price < fair
+}
+
+/// Approve and reject URLs for an approval, relative to the dashboard root.
+pub fn links(dashboard_url: &str, id: i64) -> (String, String) {
+    (
+        format!("{dashboard_url}/api/approvals/{id}/approve"),
+        format!("{dashboard_url}/api/approvals/{id}/reject"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::buckets::DIRECTIONAL_BUCKET;
+    use rust_decimal_macros::dec;
+
+    fn config() -> ApprovalConfig {
+        ApprovalConfig {
+            enabled: true,
+            threshold_usd: dec!(25),
+            ttl_seconds: 600,
+        }
+    }
+
+    fn order() -> PreparedOrder {
+        PreparedOrder {
+            token_id: "tok1".to_string(),
+            side: Side::No,
+            price: dec!(0.40),
+            size: dec!(75),
+            market_id: "m1".to_string(),
+            market_question: "Will it rain?".to_string(),
+            edge: dec!(0.12),
+            fair_value: dec!(0.30),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.2),
+            kelly_adjusted: dec!(0.1),
+            bucket: DIRECTIONAL_BUCKET.to_string(),
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_requires_approval() {
+        assert!(requires_approval(&config(), dec!(25)));
+        assert!(!requires_approval(&config(), dec!(24.99)));
+        assert!(!requires_approval(&ApprovalConfig::default(), dec!(1000)));
+    }
+
+    #[tokio::test]
+    async fn test_approve_and_restore() {
+        let store = Store::new(":memory:").await.unwrap();
+        let now = Utc::now();
+        let id = park(
+            &store,
+            &order(),
+            &MarketCategory::Weather,
+            dec!(30),
+            4,
+            &config(),
+            now,
+        )
+        .await
+        .unwrap();
+        assert!(store.has_open_approval("m1").await.unwrap());
+
+        assert!(decide(&store, id, true, now).await.unwrap());
+        // A second decision is a no-op
+        assert!(!decide(&store, id, false, now).await.unwrap());
+
+        let approved = store.get_approvals_by_status(APPROVED).await.unwrap();
+        let restored = ApprovedOrder::from_record(&approved[0]).unwrap();
+        assert_eq!(restored.id, id);
+        assert_eq!(restored.order.side, Side::No);
+        assert_eq!(restored.order.size, dec!(75));
+        assert_eq!(restored.category, MarketCategory::Weather);
+        assert_eq!(restored.size_usd, dec!(30));
+    }
+
+    #[tokio::test]
+    async fn test_expiry() {
+        let store = Store::new(":memory:").await.unwrap();
+        let now = Utc::now();
+        let id = park(
+            &store,
+            &order(),
+            &MarketCategory::Crypto,
+            dec!(30),
+            1,
+            &config(),
+            now,
+        )
+        .await
+        .unwrap();
+
+        let later = now + Duration::seconds(601);
+        assert!(!decide(&store, id, true, later).await.unwrap());
+        assert_eq!(store.expire_approvals(later).await.unwrap(), 1);
+        assert!(!store.has_open_approval("m1").await.unwrap());
+    }
+
+    #[test]
+    fn test_still_priced() {
+        // NO at 0.40 with a fair NO probability of 0.70
+        let order = order();
+        assert!(still_priced(&order, dec!(0.40), dec!(0.02)));
+        assert!(still_priced(&order, dec!(0.35), dec!(0.02)));
+        assert!(still_priced(&order, dec!(0.408), dec!(0.02)));
+        // Moved past the slippage limit since it was approved
+        assert!(!still_priced(&order, dec!(0.41), dec!(0.02)));
+        // No edge left at any slippage
+        assert!(!still_priced(&order, dec!(0.70), dec!(1)));
+    }
+
+    #[test]
+    fn test_links() {
+        let (approve, reject) = links("http://localhost:8080", 7);
+        assert_eq!(approve, "http://localhost:8080/api/approvals/7/approve");
+        assert_eq!(reject, "http://localhost:8080/api/approvals/7/reject");
+    }
+}
@@ -0,0 +1,135 @@
+//! Per-market lifetime loss limit.
+//!
+//! Sums realized P&L per market across every entry (resolutions and exits).
+//! Once a market has cost more than the configured amount it is added to
+//! `market_blacklist` and never traded again by this agent, whatever edge a
+//! later valuation finds. The table is only ever appended to, so raising the
+//! limit afterwards does not un-blacklist anything.
+
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+
+use crate::db::store::{BlacklistRecord, Store, TradeRecord};
+
+/// Net realized P&L and question per market, over trades with a P&L.
+pub fn realized_pnl_by_market(trades: &[TradeRecord]) -> BTreeMap<&str, (Option<&str>, Decimal)> {
+    let mut by_market: BTreeMap<&str, (Option<&str>, Decimal)> = BTreeMap::new();
+    for trade in trades {
+        let Some(pnl) = trade.pnl else {
+            continue;
+        };
+        let entry = by_market
+            .entry(trade.market_id.as_str())
+            .or_insert((None, Decimal::ZERO));
+        entry.0 = entry.0.or(trade.market_question.as_deref());
+        entry.1 += pnl;
+    }
+    by_market
+}
+
+/// Markets whose cumulative realized loss exceeds `max_loss`.
+pub fn markets_over_limit(trades: &[TradeRecord], max_loss: Decimal) -> Vec<BlacklistRecord> {
+    realized_pnl_by_market(trades)
+        .into_iter()
+        .filter(|(_, (_, pnl))| -*pnl > max_loss)
+        .map(|(market_id, (question, pnl))| BlacklistRecord {
+            market_id: market_id.to_string(),
+            market_question: question.map(str::to_string),
+            realized_loss: (-pnl).to_string(),
+            blacklisted_at: None,
+        })
+        .collect()
+}
+
+/// Blacklist every market over the limit. Returns the newly added ones.
+pub async fn refresh(store: &Store, max_loss: Decimal) -> Result<Vec<BlacklistRecord>> {
+    let trades = store.get_all_trades().await?;
+    let mut added = Vec::new();
+    for record in markets_over_limit(&trades, max_loss) {
+        if store.insert_blacklisted_market(&record).await? {
+            added.push(record);
+        }
+    }
+    Ok(added)
+}
+
+/// Condition ids that must not be traded.
+pub async fn blacklisted_ids(store: &Store) -> Result<HashSet<String>> {
+    Ok(store
+        .get_blacklisted_markets()
+        .await?
+        .into_iter()
+        .map(|r| r.market_id)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn trade(market_id: &str, status: &str, pnl: Option<Decimal>) -> TradeRecord {
+        TradeRecord {
+            id: None,
+            cycle: 1,
+            market_id: market_id.to_string(),
+            market_question: Some(format!("{market_id}?")),
+            direction: "YES".to_string(),
+            entry_price: dec!(0.50),
+            size: dec!(10),
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.60),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.1),
+            kelly_adjusted: dec!(0.05),
+            status: status.to_string(),
+            pnl,
+            created_at: None,
+            resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_losses_accumulate_across_entries() {
+        let trades = vec![
+            trade("m1", "RESOLVED_LOSS", Some(dec!(-5))),
+            trade("m1", "CANCELLED", Some(dec!(-4))),
+            trade("m1", "OPEN", None),
+            trade("m2", "RESOLVED_LOSS", Some(dec!(-8))),
+            trade("m2", "RESOLVED_WIN", Some(dec!(6))),
+        ];
+        let over = markets_over_limit(&trades, dec!(8));
+        assert_eq!(over.len(), 1);
+        assert_eq!(over[0].market_id, "m1");
+        assert_eq!(over[0].realized_loss, "9");
+        assert_eq!(over[0].market_question.as_deref(), Some("m1?"));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_is_permanent() {
+        let store = Store::new(":memory:").await.unwrap();
+        let id = store
+            .insert_trade(&trade("m1", "OPEN", None))
+            .await
+            .unwrap();
+        store
+            .update_trade_status(id, "RESOLVED_LOSS", Some(dec!(-12)), None)
+            .await
+            .unwrap();
+
+        let added = refresh(&store, dec!(10)).await.unwrap();
+        assert_eq!(added.len(), 1);
+        // Already listed: nothing new, and a higher limit keeps it listed
+        assert!(refresh(&store, dec!(10)).await.unwrap().is_empty());
+        assert!(refresh(&store, dec!(50)).await.unwrap().is_empty());
+        assert!(blacklisted_ids(&store).await.unwrap().contains("m1"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Bankroll segmentation into strategy buckets.
+//!
+//! Splits the bankroll into named allocations (e.g. 70% directional, 20%
+//! arbitrage, 10% experimentation). Each strategy sizes against its own
+//! bucket balance and is capped at its own exposure limit.
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::config::BucketConfig;
+
+/// Bucket used by the Claude directional strategy.
+pub const DIRECTIONAL_BUCKET: &str = "directional";
+
+/// Bucket configuration with allocation lookups.
+///
+/// With no buckets configured the whole bankroll behaves as a single bucket,
+/// so existing deployments size exactly as before.
+#[derive(Debug, Clone, Default)]
+pub struct BankrollBuckets {
+    buckets: Vec<BucketConfig>,
+}
+
+/// Point-in-time view of one bucket, for logging and the dashboard.
+#[derive(Debug, Clone, Serialize)]
+pub struct BucketStatus {
+    pub name: String,
+    pub allocation_pct: Decimal,
+    pub balance: Decimal,
+    pub exposure: Decimal,
+    pub exposure_cap: Decimal,
+    pub available: Decimal,
+}
+
+impl BankrollBuckets {
+    pub fn new(buckets: Vec<BucketConfig>) -> Self {
+        Self { buckets }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.buckets.is_empty()
+    }
+
+    /// Check that allocations are positive and sum to at most 100%.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut total = Decimal::ZERO;
+        for bucket in &self.buckets {
+            if bucket.allocation_pct <= Decimal::ZERO {
+                anyhow::bail!("Bucket '{}' has non-positive allocation", bucket.name);
+            }
+            total += bucket.allocation_pct;
+        }
+        if total > Decimal::ONE {
+            anyhow::bail!("Bucket allocations sum to {total}, exceeding 100%");
+        }
+        Ok(())
+    }
+
+    fn find(&self, name: &str) -> Option<&BucketConfig> {
+        self.buckets.iter().find(|b| b.name == name)
+    }
+
+    /// Share of `total_bankroll` allocated to `name`.
+    ///
+    /// Unknown buckets get nothing once segmentation is enabled, so a
+    /// strategy without an allocation cannot trade.
+    pub fn balance(&self, name: &str, total_bankroll: Decimal) -> Decimal {
+        if !self.is_enabled() {
+            return total_bankroll;
+        }
+        self.find(name)
+            .map(|b| total_bankroll * b.allocation_pct)
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Maximum open exposure for `name`, as a share of its bucket balance.
+    pub fn exposure_cap(&self, name: &str, total_bankroll: Decimal) -> Decimal {
+        if !self.is_enabled() {
+            return total_bankroll;
+        }
+        self.find(name)
+            .map(|b| total_bankroll * b.allocation_pct * b.max_exposure_pct)
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Status of every configured bucket given per-bucket exposure.
+    pub fn statuses(
+        &self,
+        total_bankroll: Decimal,
+        exposure_of: impl Fn(&str) -> Decimal,
+    ) -> Vec<BucketStatus> {
+        self.buckets
+            .iter()
+            .map(|b| {
+                let exposure = exposure_of(&b.name);
+                let exposure_cap = self.exposure_cap(&b.name, total_bankroll);
+                BucketStatus {
+                    name: b.name.clone(),
+                    allocation_pct: b.allocation_pct,
+                    balance: self.balance(&b.name, total_bankroll),
+                    exposure,
+                    exposure_cap,
+                    available: (exposure_cap - exposure).max(Decimal::ZERO),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn bucket(name: &str, allocation_pct: Decimal, max_exposure_pct: Decimal) -> BucketConfig {
+        BucketConfig {
+            name: name.to_string(),
+            allocation_pct,
+            max_exposure_pct,
+        }
+    }
+
+    fn three_buckets() -> BankrollBuckets {
+        BankrollBuckets::new(vec![
+            bucket("directional", dec!(0.70), dec!(0.50)),
+            bucket("arbitrage", dec!(0.20), dec!(1.0)),
+            bucket("experimental", dec!(0.10), dec!(0.25)),
+        ])
+    }
+
+    #[test]
+    fn test_disabled_uses_full_bankroll() {
+        let buckets = BankrollBuckets::default();
+        assert!(!buckets.is_enabled());
+        assert_eq!(buckets.balance(DIRECTIONAL_BUCKET, dec!(100)), dec!(100));
+        assert_eq!(buckets.exposure_cap("anything", dec!(100)), dec!(100));
+    }
+
+    #[test]
+    fn test_bucket_balances() {
+        let buckets = three_buckets();
+        assert_eq!(buckets.balance("directional", dec!(100)), dec!(70));
+        assert_eq!(buckets.balance("arbitrage", dec!(100)), dec!(20));
+        assert_eq!(buckets.balance("experimental", dec!(100)), dec!(10));
+        assert_eq!(buckets.balance("unknown", dec!(100)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_exposure_caps() {
+        let buckets = three_buckets();
+        assert_eq!(buckets.exposure_cap("directional", dec!(100)), dec!(35));
+        assert_eq!(buckets.exposure_cap("experimental", dec!(100)), dec!(2.5));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(three_buckets().validate().is_ok());
+
+        let over = BankrollBuckets::new(vec![
+            bucket("a", dec!(0.80), dec!(1)),
+            bucket("b", dec!(0.30), dec!(1)),
+        ]);
+        assert!(over.validate().is_err());
+
+        let zero = BankrollBuckets::new(vec![bucket("a", Decimal::ZERO, dec!(1))]);
+        assert!(zero.validate().is_err());
+    }
+
+    #[test]
+    fn test_statuses() {
+        let buckets = three_buckets();
+        let statuses = buckets.statuses(dec!(100), |name| {
+            if name == "directional" {
+                dec!(30)
+            } else {
+                Decimal::ZERO
+            }
+        });
+        assert_eq!(statuses.len(), 3);
+        assert_eq!(statuses[0].exposure, dec!(30));
+        assert_eq!(statuses[0].available, dec!(5));
+        assert_eq!(statuses[1].available, dec!(20));
+    }
+}
@@ -0,0 +1,354 @@
+//! Drawdown-triggered cooldown.
+//!
+//! A losing streak usually means something changed — a data source went
+//! bad, a category stopped behaving like it used to, Claude is overconfident
+//! somewhere — and trading through it compounds the damage. Each cycle the
+//! bankroll's peak-to-trough drawdown over the last `window_cycles` is
+//! measured; past `max_drawdown_pct`, new entries pause for
+//! `cooldown_cycles` cycles and an analysis of the trades closed in the
+//! window (P&L by category and by confidence bucket) is alerted. Exits and
+//! settlement carry on. Trading resumes by itself once the cooldown ends,
+//! and only drawdown after the trigger counts toward the next one, so the
+//! same losses don't pause the agent twice.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::db::store::{CycleRecord, TradeRecord};
+use crate::monitoring::metrics::equity_curve;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DrawdownConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cycles the drawdown is measured over.
+    #[serde(default = "default_window_cycles")]
+    pub window_cycles: usize,
+    /// Peak-to-trough fall of the bankroll that triggers a cooldown.
+    #[serde(default = "default_max_drawdown_pct")]
+    pub max_drawdown_pct: Decimal,
+    /// Cycles new entries stay paused.
+    #[serde(default = "default_cooldown_cycles")]
+    pub cooldown_cycles: u64,
+}
+
+impl Default for DrawdownConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_cycles: default_window_cycles(),
+            max_drawdown_pct: default_max_drawdown_pct(),
+            cooldown_cycles: default_cooldown_cycles(),
+        }
+    }
+}
+
+impl DrawdownConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.window_cycles < 2 {
+            bail!("risk.drawdown.window_cycles must be at least 2");
+        }
+        if self.max_drawdown_pct <= Decimal::ZERO || self.max_drawdown_pct >= Decimal::ONE {
+            bail!(
+                "risk.drawdown.max_drawdown_pct must be in (0, 1), got {}",
+                self.max_drawdown_pct
+            );
+        }
+        if self.cooldown_cycles == 0 {
+            bail!("risk.drawdown.cooldown_cycles must be positive");
+        }
+        Ok(())
+    }
+}
+
+fn default_window_cycles() -> usize {
+    50
+}
+
+fn default_max_drawdown_pct() -> Decimal {
+    dec!(0.15)
+}
+
+fn default_cooldown_cycles() -> u64 {
+    12
+}
+
+/// Largest peak-to-trough fall across `cycles` (oldest first).
+pub fn max_drawdown(cycles: &[CycleRecord]) -> Decimal {
+    equity_curve(cycles)
+        .iter()
+        .map(|p| p.drawdown_pct)
+        .max()
+        .unwrap_or(Decimal::ZERO)
+}
+
+/// A paused stretch of cycles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cooldown {
+    pub triggered_cycle: u64,
+    /// First cycle allowed to open positions again.
+    pub resume_cycle: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CooldownEvent {
+    Started(Cooldown),
+    Ended(Cooldown),
+}
+
+/// Cooldown state carried from cycle to cycle.
+#[derive(Debug, Default)]
+pub struct CooldownTracker {
+    active: Option<Cooldown>,
+    /// Cycle of the last trigger; earlier cycles don't count toward the next.
+    rearmed_after: Option<u64>,
+}
+
+impl CooldownTracker {
+    pub fn active(&self) -> Option<Cooldown> {
+        self.active
+    }
+
+    /// End the cooldown once `cycle` reaches its resume cycle.
+    pub fn expire(&mut self, cycle: u64) -> Option<CooldownEvent> {
+        let cooldown = self.active.filter(|c| cycle >= c.resume_cycle)?;
+        self.active = None;
+        Some(CooldownEvent::Ended(cooldown))
+    }
+
+    /// Only cycles after this one count toward a new trigger.
+    pub fn rearmed_after(&self) -> Option<u64> {
+        self.rearmed_after
+    }
+
+    /// Start a cooldown at `cycle` if `drawdown` crosses the threshold and
+    /// none is running.
+    pub fn observe(
+        &mut self,
+        cycle: u64,
+        drawdown: Decimal,
+        config: &DrawdownConfig,
+    ) -> Option<CooldownEvent> {
+        if self.active.is_some() || drawdown < config.max_drawdown_pct {
+            return None;
+        }
+        let cooldown = Cooldown {
+            triggered_cycle: cycle,
+            resume_cycle: cycle + config.cooldown_cycles,
+        };
+        self.active = Some(cooldown);
+        self.rearmed_after = Some(cycle);
+        Some(CooldownEvent::Started(cooldown))
+    }
+}
+
+/// Closed trades grouped one way, e.g. one category.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LossGroup {
+    pub label: String,
+    pub trades: usize,
+    pub losses: usize,
+    pub pnl: Decimal,
+}
+
+/// What the trades closed during a drawdown had in common.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawdownReport {
+    pub drawdown_pct: Decimal,
+    pub window_cycles: usize,
+    pub cooldown: Cooldown,
+    pub realized_pnl: Decimal,
+    /// Worst first.
+    pub by_category: Vec<LossGroup>,
+    pub by_confidence: Vec<LossGroup>,
+}
+
+impl DrawdownReport {
+    /// Analyze trades closed since `since`. `categories` maps market ids to
+    /// their category; unknown markets are grouped as "unknown".
+    pub fn build(
+        drawdown_pct: Decimal,
+        window_cycles: usize,
+        cooldown: Cooldown,
+        trades: &[TradeRecord],
+        categories: &HashMap<String, String>,
+        since: DateTime<Utc>,
+    ) -> Self {
+        let closed: Vec<&TradeRecord> = trades
+            .iter()
+            .filter(|t| t.pnl.is_some() && t.resolved_at.is_some_and(|at| at >= since))
+            .collect();
+        let by_category = group(&closed, |t| {
+            categories
+                .get(&t.market_id)
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string())
+        });
+        let by_confidence = group(&closed, |t| confidence_bucket(t.confidence));
+        Self {
+            drawdown_pct,
+            window_cycles,
+            cooldown,
+            realized_pnl: closed.iter().filter_map(|t| t.pnl).sum(),
+            by_category,
+            by_confidence,
+        }
+    }
+}
+
+fn group(trades: &[&TradeRecord], key: impl Fn(&TradeRecord) -> String) -> Vec<LossGroup> {
+    let mut groups: BTreeMap<String, LossGroup> = BTreeMap::new();
+    for trade in trades {
+        let label = key(trade);
+        let pnl = trade.pnl.unwrap_or(Decimal::ZERO);
+        let group = groups.entry(label.clone()).or_insert_with(|| LossGroup {
+            label,
+            trades: 0,
+            losses: 0,
+            pnl: Decimal::ZERO,
+        });
+        group.trades += 1;
+        group.losses += usize::from(pnl < Decimal::ZERO);
+        group.pnl += pnl;
+    }
+    let mut groups: Vec<LossGroup> = groups.into_values().collect();
+    groups.sort_by_key(|g| g.pnl);
+    groups
+}
+
+/// Tenth of the confidence scale a trade's confidence falls in, e.g.
+/// "0.7-0.8".
+fn confidence_bucket(confidence: Decimal) -> String {
+    let tenths = (confidence * dec!(10))
+        .floor()
+        .clamp(Decimal::ZERO, dec!(9));
+    let low = tenths / dec!(10);
+    format!("{:.1}-{:.1}", low, low + dec!(0.1))
+}
+
+impl fmt::Display for DrawdownReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Drawdown {:.1}% over the last {} cycles (realized P&L ${:.2})",
+            self.drawdown_pct * dec!(100),
+            self.window_cycles,
+            self.realized_pnl
+        )?;
+        writeln!(
+            f,
+            "New entries paused from cycle {} until cycle {}",
+            self.cooldown.triggered_cycle, self.cooldown.resume_cycle
+        )?;
+        for (title, groups) in [
+            ("By category", &self.by_category),
+            ("By confidence", &self.by_confidence),
+        ] {
+            write!(f, "{title}:")?;
+            if groups.is_empty() {
+                write!(f, " no closed trades")?;
+            }
+            for g in groups {
+                write!(
+                    f,
+                    "\n  {}: ${:.2} ({} of {} trades lost)",
+                    g.label, g.pnl, g.losses, g.trades
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(market_id: &str, confidence: Decimal, pnl: Decimal) -> TradeRecord {
+        TradeRecord {
+            id: None,
+            cycle: 1,
+            market_id: market_id.to_string(),
+            market_question: None,
+            direction: "YES".to_string(),
+            entry_price: dec!(0.5),
+            size: dec!(10),
+            edge_at_entry: dec!(0.1),
+            claude_fair_value: dec!(0.6),
+            confidence,
+            kelly_raw: dec!(0.1),
+            kelly_adjusted: dec!(0.05),
+            status: if pnl < Decimal::ZERO {
+                "RESOLVED_LOSS"
+            } else {
+                "RESOLVED_WIN"
+            }
+            .to_string(),
+            pnl: Some(pnl),
+            created_at: None,
+            resolved_at: Some(Utc::now()),
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_cooldown_pauses_then_rearms_and_reports_losses() {
+        let config = DrawdownConfig {
+            enabled: true,
+            ..DrawdownConfig::default()
+        };
+        let mut tracker = CooldownTracker::default();
+        assert_eq!(tracker.observe(10, dec!(0.10), &config), None);
+
+        let started = tracker.observe(11, dec!(0.20), &config);
+        let cooldown = Cooldown {
+            triggered_cycle: 11,
+            resume_cycle: 23,
+        };
+        assert_eq!(started, Some(CooldownEvent::Started(cooldown)));
+        assert_eq!(tracker.rearmed_after(), Some(11));
+        // Still deep in drawdown, but already paused
+        assert_eq!(tracker.observe(12, dec!(0.25), &config), None);
+        assert_eq!(tracker.expire(22), None);
+        assert_eq!(tracker.expire(23), Some(CooldownEvent::Ended(cooldown)));
+        assert_eq!(tracker.active(), None);
+
+        let categories = HashMap::from([
+            ("w1".to_string(), "weather".to_string()),
+            ("s1".to_string(), "sports".to_string()),
+        ]);
+        let trades = [
+            trade("w1", dec!(0.85), dec!(-8)),
+            trade("w1", dec!(0.9), dec!(-4)),
+            trade("s1", dec!(0.62), dec!(3)),
+            trade("x1", dec!(0.65), dec!(-1)),
+        ];
+        let since = Utc::now() - chrono::Duration::hours(1);
+        let report = DrawdownReport::build(dec!(0.2), 50, cooldown, &trades, &categories, since);
+        assert_eq!(report.realized_pnl, dec!(-10));
+        assert_eq!(report.by_category[0].label, "weather");
+        assert_eq!(report.by_category[0].pnl, dec!(-12));
+        assert_eq!(report.by_category[0].losses, 2);
+        let labels: Vec<&str> = report
+            .by_confidence
+            .iter()
+            .map(|g| g.label.as_str())
+            .collect();
+        assert_eq!(labels, ["0.8-0.9", "0.9-1.0", "0.6-0.7"]);
+        assert!(report
+            .to_string()
+            .contains("weather: $-12.00 (2 of 2 trades lost)"));
+    }
+}
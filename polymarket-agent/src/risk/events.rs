@@ -0,0 +1,200 @@
+//! Event risk calendar.
+//!
+//! Scheduled macro and political events (FOMC decisions, CPI prints, elections)
+//! make model estimates less reliable in the hours around them. Markets in the
+//! affected categories need a larger edge and get a lower position cap while
+//! a window is open.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::market::models::MarketCategory;
+
+/// A scheduled event that widens edge requirements for nearby trades.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventWindow {
+    /// Human-readable label, e.g. "FOMC rate decision".
+    pub name: String,
+    /// Scheduled time of the event (UTC).
+    pub time: DateTime<Utc>,
+    /// Hours before the event at which caution starts.
+    #[serde(default = "default_hours_before")]
+    pub hours_before: i64,
+    /// Hours after the event at which caution ends.
+    #[serde(default = "default_hours_after")]
+    pub hours_after: i64,
+    /// Categories affected. An empty list applies to every category.
+    #[serde(default)]
+    pub categories: Vec<MarketCategory>,
+    /// Multiplier applied to the edge threshold (>= 1 raises the bar).
+    #[serde(default = "default_edge_multiplier")]
+    pub edge_multiplier: Decimal,
+    /// Multiplier applied to `max_position_pct` (<= 1 lowers the cap).
+    #[serde(default = "default_size_multiplier")]
+    pub size_multiplier: Decimal,
+}
+
+fn default_hours_before() -> i64 {
+    6
+}
+
+fn default_hours_after() -> i64 {
+    2
+}
+
+fn default_edge_multiplier() -> Decimal {
+    rust_decimal_macros::dec!(1.5)
+}
+
+fn default_size_multiplier() -> Decimal {
+    rust_decimal_macros::dec!(0.5)
+}
+
+impl EventWindow {
+    /// Whether `now` falls inside this event's caution window.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        let start = self.time - Duration::hours(self.hours_before);
+        let end = self.time + Duration::hours(self.hours_after);
+        now >= start && now <= end
+    }
+
+    /// Whether this event affects markets in `category`.
+    pub fn applies_to(&self, category: &MarketCategory) -> bool {
+        self.categories.is_empty() || self.categories.contains(category)
+    }
+}
+
+/// Combined adjustment from every event window active for a market.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventAdjustment {
+    pub edge_multiplier: Decimal,
+    pub size_multiplier: Decimal,
+    pub active_events: Vec<String>,
+}
+
+impl EventAdjustment {
+    /// No events in effect — thresholds and sizes unchanged.
+    pub fn none() -> Self {
+        Self {
+            edge_multiplier: Decimal::ONE,
+            size_multiplier: Decimal::ONE,
+            active_events: Vec::new(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.active_events.is_empty()
+    }
+}
+
+/// Compute the adjustment for `category` at `now`.
+///
+/// When several windows overlap, the most conservative multipliers win:
+/// the highest edge multiplier and the lowest size multiplier. Multipliers
+/// are clamped so an event can never lower the edge bar or grow a position.
+pub fn adjustment_for(
+    events: &[EventWindow],
+    category: &MarketCategory,
+    now: DateTime<Utc>,
+) -> EventAdjustment {
+    let mut adjustment = EventAdjustment::none();
+
+    for event in events
+        .iter()
+        .filter(|e| e.applies_to(category) && e.is_active(now))
+    {
+        adjustment.edge_multiplier = adjustment
+            .edge_multiplier
+            .max(event.edge_multiplier.max(Decimal::ONE));
+        adjustment.size_multiplier = adjustment
+            .size_multiplier
+            .min(event.size_multiplier.clamp(Decimal::ZERO, Decimal::ONE));
+        adjustment.active_events.push(event.name.clone());
+    }
+
+    adjustment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn fomc(time: DateTime<Utc>) -> EventWindow {
+        EventWindow {
+            name: "FOMC".to_string(),
+            time,
+            hours_before: 6,
+            hours_after: 2,
+            categories: vec![MarketCategory::Crypto, MarketCategory::Politics],
+            edge_multiplier: dec!(1.5),
+            size_multiplier: dec!(0.5),
+        }
+    }
+
+    #[test]
+    fn test_no_events_is_neutral() {
+        let adj = adjustment_for(&[], &MarketCategory::Crypto, Utc::now());
+        assert_eq!(adj, EventAdjustment::none());
+        assert!(!adj.is_active());
+    }
+
+    #[test]
+    fn test_window_bounds() {
+        let now = Utc::now();
+        let event = fomc(now);
+        assert!(event.is_active(now - Duration::hours(6)));
+        assert!(event.is_active(now + Duration::hours(2)));
+        assert!(!event.is_active(now - Duration::hours(7)));
+        assert!(!event.is_active(now + Duration::hours(3)));
+    }
+
+    #[test]
+    fn test_category_filter() {
+        let now = Utc::now();
+        let events = vec![fomc(now)];
+
+        let crypto = adjustment_for(&events, &MarketCategory::Crypto, now);
+        assert!(crypto.is_active());
+        assert_eq!(crypto.edge_multiplier, dec!(1.5));
+        assert_eq!(crypto.size_multiplier, dec!(0.5));
+
+        let weather = adjustment_for(&events, &MarketCategory::Weather, now);
+        assert!(!weather.is_active());
+    }
+
+    #[test]
+    fn test_empty_categories_apply_to_all() {
+        let now = Utc::now();
+        let mut event = fomc(now);
+        event.categories.clear();
+        let adj = adjustment_for(&[event], &MarketCategory::Weather, now);
+        assert!(adj.is_active());
+    }
+
+    #[test]
+    fn test_overlapping_events_take_most_conservative() {
+        let now = Utc::now();
+        let mut cpi = fomc(now);
+        cpi.name = "CPI".to_string();
+        cpi.edge_multiplier = dec!(2.0);
+        cpi.size_multiplier = dec!(0.75);
+
+        let adj = adjustment_for(&[fomc(now), cpi], &MarketCategory::Crypto, now);
+        assert_eq!(adj.edge_multiplier, dec!(2.0));
+        assert_eq!(adj.size_multiplier, dec!(0.5));
+        assert_eq!(adj.active_events, vec!["FOMC", "CPI"]);
+    }
+
+    #[test]
+    fn test_multipliers_never_loosen_limits() {
+        let now = Utc::now();
+        let mut event = fomc(now);
+        event.edge_multiplier = dec!(0.5);
+        event.size_multiplier = dec!(3.0);
+        let adj = adjustment_for(&[event], &MarketCategory::Crypto, now);
+        assert_eq!(adj.edge_multiplier, Decimal::ONE);
+        assert_eq!(adj.size_multiplier, Decimal::ONE);
+    }
+}
@@ -0,0 +1,266 @@
+//! Hedging open positions on adverse re-valuation.
+//!
+//! When a fresh valuation turns a position's edge strongly negative, the
+//! position has to be neutralized. Selling into a thin book gives away most
+//! of its value, so the alternative is to buy the opposite token: holding
+//! both YES and NO locks in a $1 payout per share pair regardless of outcome.
+//! A tight, deep book is exited directly; otherwise whichever of selling the
+//! held token or buying the opposite token recovers more value is chosen.
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::market::models::{OrderBookSnapshot, PriceLevel, Side};
+use crate::risk::limits;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HedgeConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Act once the position's edge is below `-flip_threshold`.
+    #[serde(default = "default_flip_threshold")]
+    pub flip_threshold: Decimal,
+    /// Spread on the held token's book at or below which it is exited directly.
+    #[serde(default = "default_max_exit_spread")]
+    pub max_exit_spread: Decimal,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            flip_threshold: default_flip_threshold(),
+            max_exit_spread: default_max_exit_spread(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_flip_threshold() -> Decimal {
+    rust_decimal_macros::dec!(0.10)
+}
+
+fn default_max_exit_spread() -> Decimal {
+    rust_decimal_macros::dec!(0.04)
+}
+
+/// What to do with a position after re-valuation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HedgeAction {
+    Hold,
+    /// Sell the held shares, limit at `price`.
+    Exit {
+        price: Decimal,
+    },
+    /// Buy `shares` of the opposite token, limit at `price`.
+    Hedge {
+        price: Decimal,
+        shares: Decimal,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct HedgeDecision {
+    pub action: HedgeAction,
+    /// Edge of the held position at the current price.
+    pub edge: Decimal,
+    pub reason: String,
+}
+
+/// Edge of holding `side` when the fair YES probability is `fair_yes` and
+/// YES trades at `yes_price`. Negative means the position is overpriced.
+pub fn position_edge(side: Side, fair_yes: Decimal, yes_price: Decimal) -> Decimal {
+    match side {
+        Side::Yes => fair_yes - yes_price,
+        Side::No => yes_price - fair_yes,
+    }
+}
+
+/// Walk book levels for up to `shares`. Returns shares filled, their total
+/// price, and the worst price reached (the limit price needed).
+fn walk(levels: &[PriceLevel], shares: Decimal) -> (Decimal, Decimal, Decimal) {
+    let mut filled = Decimal::ZERO;
+    let mut notional = Decimal::ZERO;
+    let mut worst = Decimal::ZERO;
+    for level in levels {
+        if filled >= shares {
+            break;
+        }
+        let take = level.size.min(shares - filled);
+        filled += take;
+        notional += take * level.price;
+        worst = level.price;
+    }
+    (filled, notional, worst)
+}
+
+/// Choose between holding, exiting and hedging a position of `shares`.
+///
+/// `held_book` is the book of the token we own (exits sell into its bids);
+/// `opposite_book` is the book of the other outcome (hedges lift its asks).
+pub fn decide(
+    config: &HedgeConfig,
+    side: Side,
+    shares: Decimal,
+    fair_yes: Decimal,
+    yes_price: Decimal,
+    held_book: &OrderBookSnapshot,
+    opposite_book: &OrderBookSnapshot,
+) -> HedgeDecision {
+    let edge = position_edge(side, fair_yes, yes_price);
+    let decision = |action, reason: String| HedgeDecision {
+        action,
+        edge,
+        reason,
+    };
+
+    if !config.enabled || edge > -config.flip_threshold {
+        return decision(HedgeAction::Hold, format!("Edge {edge} within tolerance"));
+    }
+
+    let bids: Vec<(Decimal, Decimal)> = held_book.bids.iter().map(|l| (l.price, l.size)).collect();
+    let best_bid = bids.first().map(|(p, _)| *p);
+    if let Some(best_bid) = best_bid {
+        if held_book.spread <= config.max_exit_spread && limits::depth_at_best(&bids) >= shares {
+            return decision(
+                HedgeAction::Exit { price: best_bid },
+                format!("Book is tight and deep — exiting at {best_bid}"),
+            );
+        }
+    }
+
+    // Value recovered per path: selling returns the bids; each hedged share
+    // pair redeems for $1, so hedging recovers 1 - ask per share.
+    let (exit_filled, exit_proceeds, exit_price) = walk(&held_book.bids, shares);
+    let (hedge_filled, hedge_cost, hedge_price) = walk(&opposite_book.asks, shares);
+    let hedge_value = hedge_filled - hedge_cost;
+
+    if hedge_filled > Decimal::ZERO && hedge_value > exit_proceeds {
+        decision(
+            HedgeAction::Hedge {
+                price: hedge_price,
+                shares: hedge_filled,
+            },
+            format!("Hedging recovers {hedge_value} vs {exit_proceeds} from exit"),
+        )
+    } else if exit_filled > Decimal::ZERO {
+        decision(
+            HedgeAction::Exit { price: exit_price },
+            format!("Exit recovers {exit_proceeds} vs {hedge_value} from hedging"),
+        )
+    } else {
+        decision(
+            HedgeAction::Hold,
+            "No liquidity to exit or hedge".to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::BookLiquidity;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn book(bids: &[(Decimal, Decimal)], asks: &[(Decimal, Decimal)]) -> OrderBookSnapshot {
+        let level = |&(price, size): &(Decimal, Decimal)| PriceLevel { price, size };
+        let best_bid = bids.first().map(|b| b.0).unwrap_or(Decimal::ZERO);
+        let best_ask = asks.first().map(|a| a.0).unwrap_or(Decimal::ONE);
+        OrderBookSnapshot {
+            token_id: "tok".to_string(),
+            bids: bids.iter().map(level).collect(),
+            asks: asks.iter().map(level).collect(),
+            spread: best_ask - best_bid,
+            midpoint: (best_ask + best_bid) / dec!(2),
+            implied_probability: (best_ask + best_bid) / dec!(2),
+            timestamp: Utc::now(),
+            liquidity: BookLiquidity::TwoSided,
+        }
+    }
+
+    #[test]
+    fn test_position_edge() {
+        assert_eq!(
+            position_edge(Side::Yes, dec!(0.40), dec!(0.55)),
+            dec!(-0.15)
+        );
+        assert_eq!(position_edge(Side::No, dec!(0.70), dec!(0.55)), dec!(-0.15));
+    }
+
+    #[test]
+    fn test_hold_when_edge_small() {
+        let held = book(&[(dec!(0.50), dec!(100))], &[(dec!(0.52), dec!(100))]);
+        let d = decide(
+            &HedgeConfig::default(),
+            Side::Yes,
+            dec!(10),
+            dec!(0.48),
+            dec!(0.51),
+            &held,
+            &held,
+        );
+        assert_eq!(d.action, HedgeAction::Hold);
+    }
+
+    #[test]
+    fn test_exit_into_deep_book() {
+        let held = book(&[(dec!(0.50), dec!(100))], &[(dec!(0.52), dec!(100))]);
+        let opposite = book(&[(dec!(0.48), dec!(100))], &[(dec!(0.50), dec!(100))]);
+        let d = decide(
+            &HedgeConfig::default(),
+            Side::Yes,
+            dec!(10),
+            dec!(0.30),
+            dec!(0.51),
+            &held,
+            &opposite,
+        );
+        assert_eq!(d.action, HedgeAction::Exit { price: dec!(0.50) });
+    }
+
+    #[test]
+    fn test_hedge_when_held_book_thin() {
+        // Held YES bids are thin and far below the ask; NO is offered at 0.52,
+        // so hedging recovers ~0.48/share vs ~0.2 from dumping
+        let held = book(
+            &[(dec!(0.40), dec!(2)), (dec!(0.10), dec!(50))],
+            &[(dec!(0.50), dec!(100))],
+        );
+        let opposite = book(&[(dec!(0.45), dec!(100))], &[(dec!(0.52), dec!(100))]);
+        let d = decide(
+            &HedgeConfig::default(),
+            Side::Yes,
+            dec!(10),
+            dec!(0.30),
+            dec!(0.45),
+            &held,
+            &opposite,
+        );
+        assert_eq!(
+            d.action,
+            HedgeAction::Hedge {
+                price: dec!(0.52),
+                shares: dec!(10)
+            }
+        );
+    }
+
+    #[test]
+    fn test_hold_without_liquidity() {
+        let empty = book(&[], &[]);
+        let d = decide(
+            &HedgeConfig::default(),
+            Side::No,
+            dec!(10),
+            dec!(0.80),
+            dec!(0.50),
+            &empty,
+            &empty,
+        );
+        assert_eq!(d.action, HedgeAction::Hold);
+    }
+}
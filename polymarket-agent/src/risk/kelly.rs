@@ -108,6 +108,59 @@ pub fn kelly_size(
     }
 }
 
+/// Multiplier on the maximum position size for a market resolving in
+/// `hours_to_resolution`, by linear interpolation over the configured curve.
+///
+/// Points are `(hours, multiplier)` pairs in any order. Beyond the furthest
+/// point the multiplier is 1; inside the nearest point it holds at that
+/// point's value. An empty curve disables the decay.
+pub fn resolution_decay_multiplier(hours_to_resolution: Decimal, config: &RiskConfig) -> Decimal {
+    let mut points: Vec<(Decimal, Decimal)> = config
+        .resolution_decay
+        .iter()
+        .map(|p| (p.hours, p.multiplier.clamp(Decimal::ZERO, Decimal::ONE)))
+        .collect();
+    points.sort_by_key(|p| p.0);
+
+    let (Some(&(nearest_h, nearest_m)), Some(&(furthest_h, _))) = (points.first(), points.last())
+    else {
+        return Decimal::ONE;
+    };
+    if hours_to_resolution > furthest_h {
+        return Decimal::ONE;
+    }
+    if hours_to_resolution <= nearest_h {
+        return nearest_m;
+    }
+
+    for pair in points.windows(2) {
+        let ((h0, m0), (h1, m1)) = (pair[0], pair[1]);
+        if hours_to_resolution <= h1 && h1 > h0 {
+            return m0 + (m1 - m0) * (hours_to_resolution - h0) / (h1 - h0);
+        }
+    }
+    Decimal::ONE
+}
+
+/// Shrink a Kelly result so it fits the resolution-decayed position cap.
+/// Positions that fall below the minimum size are dropped.
+pub fn apply_resolution_decay(
+    result: &mut KellyResult,
+    hours_to_resolution: Decimal,
+    bankroll: Decimal,
+    config: &RiskConfig,
+) {
+    let multiplier = resolution_decay_multiplier(hours_to_resolution, config);
+    let max_position = bankroll * config.max_position_pct * multiplier;
+    if result.position_usd > max_position {
+        result.position_usd = max_position;
+        result.capped = true;
+    }
+    if result.position_usd < config.min_position_usd {
+        result.position_usd = Decimal::ZERO;
+    }
+}
+
 /// Result of Kelly sizing calculation.
 #[derive(Debug, Clone)]
 pub struct KellyResult {
@@ -140,6 +193,8 @@ impl KellyResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ResolutionDecayPoint;
+    use std::collections::HashMap;
 
     fn default_config() -> RiskConfig {
         RiskConfig {
@@ -148,6 +203,17 @@ mod tests {
             max_total_exposure_pct: dec!(0.30),
             max_positions_per_category: 3,
             min_position_usd: dec!(1), // $1 min
+            max_market_loss_usd: None,
+            max_trade_loss_usd: None,
+            resolution_decay: Vec::new(),
+            category_exposure_pct: HashMap::new(),
+            event_windows: Vec::new(),
+            buckets: Vec::new(),
+            approval: Default::default(),
+            hedge: Default::default(),
+            sizing: Default::default(),
+            rebalance: Default::default(),
+            drawdown: Default::default(),
         }
     }
 
@@ -372,4 +438,48 @@ mod tests {
         // Higher confidence → larger position (or both capped)
         assert!(high_conf.kelly_adjusted > low_conf.kelly_adjusted);
     }
+
+    #[test]
+    fn test_resolution_decay_curve() {
+        let mut config = default_config();
+        config.resolution_decay = vec![
+            ResolutionDecayPoint {
+                hours: dec!(72),
+                multiplier: dec!(1.0),
+            },
+            ResolutionDecayPoint {
+                hours: dec!(24),
+                multiplier: dec!(0.5),
+            },
+            ResolutionDecayPoint {
+                hours: dec!(6),
+                multiplier: dec!(0.2),
+            },
+        ];
+
+        assert_eq!(resolution_decay_multiplier(dec!(100), &config), dec!(1));
+        assert_eq!(resolution_decay_multiplier(dec!(48), &config), dec!(0.75));
+        assert_eq!(resolution_decay_multiplier(dec!(24), &config), dec!(0.5));
+        assert_eq!(resolution_decay_multiplier(dec!(2), &config), dec!(0.2));
+
+        // $6 cap at 6% of $100, decayed to $1.20 at 6 hours out
+        let mut result = kelly_size(
+            dec!(0.70),
+            dec!(0.50),
+            dec!(1.0),
+            dec!(100),
+            AgentState::Alive,
+            &config,
+        );
+        assert_eq!(result.position_usd, dec!(6));
+        apply_resolution_decay(&mut result, dec!(6), dec!(100), &config);
+        assert_eq!(result.position_usd, dec!(1.2));
+        assert!(result.capped);
+    }
+
+    #[test]
+    fn test_resolution_decay_disabled() {
+        let config = default_config();
+        assert_eq!(resolution_decay_multiplier(dec!(1), &config), Decimal::ONE);
+    }
 }
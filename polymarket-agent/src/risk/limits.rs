@@ -3,6 +3,8 @@
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
+use crate::execution::fees::{FeeModel, Liquidity};
+
 /// Check if the order book has sufficient liquidity for the position size.
 /// Returns the maximum safely tradeable size.
 pub fn liquidity_adjusted_size(
@@ -29,6 +31,19 @@ pub fn liquidity_adjusted_size(
     position_usd.min(max_from_depth).min(max_from_slippage)
 }
 
+/// Worst-case loss of buying `shares` at `price`: the stake is lost if the
+/// market resolves against us, the fill may slip up to `max_slippage_pct`
+/// above the limit, and the taker fee at the slipped price is charged on top.
+pub fn worst_case_loss(
+    shares: Decimal,
+    price: Decimal,
+    fees: &FeeModel,
+    max_slippage_pct: Decimal,
+) -> Decimal {
+    let fill_price = price * (Decimal::ONE + max_slippage_pct);
+    shares * fill_price + fees.fee(Liquidity::Taker, fill_price, shares)
+}
+
 /// Calculate order book depth in USD at the best price level.
 pub fn depth_at_best(prices: &[(Decimal, Decimal)]) -> Decimal {
     prices
@@ -68,6 +83,22 @@ mod tests {
         assert_eq!(adjusted, Decimal::ZERO);
     }
 
+    #[test]
+    fn test_worst_case_loss() {
+        let fees = FeeModel::new(&crate::execution::fees::FeeConfig {
+            taker_fee_bps: dec!(200),
+            maker_rebate_bps: Decimal::ZERO,
+        });
+        // 20 shares at $0.50 +2% slippage = $10.20 at $0.51, taker fee
+        // 2% of min($0.51, $0.49) per share = $0.196
+        let loss = worst_case_loss(dec!(20), dec!(0.50), &fees, dec!(0.02));
+        assert_eq!(loss, dec!(10.396));
+        assert_eq!(
+            worst_case_loss(dec!(20), dec!(0.50), &FeeModel::default(), Decimal::ZERO),
+            dec!(10)
+        );
+    }
+
     #[test]
     fn test_depth_at_best() {
         let levels = vec![(dec!(0.50), dec!(100)), (dec!(0.49), dec!(200))];
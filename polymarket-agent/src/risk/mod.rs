@@ -1,4 +1,14 @@
+pub mod approval;
+pub mod blacklist;
+pub mod buckets;
+pub mod drawdown;
+pub mod events;
 pub mod exit;
+pub mod hedge;
 pub mod kelly;
 pub mod limits;
 pub mod portfolio;
+pub mod rebalance;
+pub mod sizing;
+pub mod strategy;
+pub mod var;
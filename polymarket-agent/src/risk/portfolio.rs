@@ -1,22 +1,32 @@
 //! Portfolio state and constraint tracking.
 //!
 //! Tracks current positions and enforces portfolio-level risk limits.
+//!
+//! Exposure is netted per market: YES and NO shares held together pay $1
+//! per pair whichever way the market resolves, so only the unmatched part
+//! of the cost is at risk.
+
+use std::collections::BTreeMap;
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use crate::config::RiskConfig;
+use crate::db::store::TradeRecord;
 use crate::market::models::{MarketCategory, Opportunity, Side};
+use crate::risk::buckets::{BankrollBuckets, BucketStatus};
 
 /// Tracks the current portfolio state for risk management.
 pub struct PortfolioManager {
     config: RiskConfig,
     positions: Vec<Position>,
+    buckets: BankrollBuckets,
 }
 
 /// A tracked position in the portfolio.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
     pub market_id: String,
     pub token_id: String,
@@ -24,13 +34,17 @@ pub struct Position {
     pub side: Side,
     pub size_usd: Decimal,
     pub entry_price: Decimal,
+    /// Strategy bucket the position is charged to.
+    pub bucket: String,
 }
 
 impl PortfolioManager {
     pub fn new(config: RiskConfig) -> Self {
+        let buckets = BankrollBuckets::new(config.buckets.clone());
         Self {
             config,
             positions: Vec::new(),
+            buckets,
         }
     }
 
@@ -61,7 +75,19 @@ impl PortfolioManager {
             ));
         }
 
-        // 3. No duplicate position in same market
+        // 3. Per-category exposure budget
+        if let Some(budget) = self.category_budget(&opportunity.market.category, bankroll) {
+            let category_exposure = self.category_exposure(&opportunity.market.category);
+            let new_category_exposure = category_exposure + opportunity.kelly_size;
+            if new_category_exposure > budget {
+                violations.push(format!(
+                    "{:?} exposure {new_category_exposure} would exceed budget {budget}",
+                    opportunity.market.category
+                ));
+            }
+        }
+
+        // 4. No duplicate position in same market
         if self.has_position(&opportunity.market.condition_id) {
             violations.push(format!(
                 "Already have position in market {}",
@@ -69,7 +95,7 @@ impl PortfolioManager {
             ));
         }
 
-        // 4. Spread check (order book liquidity)
+        // 5. Spread check (order book liquidity)
         let _max_spread = self.config.max_position_pct; // Re-use as spread proxy
         if opportunity.order_book.spread > dec!(0.05) {
             violations.push(format!(
@@ -85,11 +111,21 @@ impl PortfolioManager {
         }
     }
 
-    /// Reduce position size to fit within portfolio constraints.
-    pub fn adjust_size(&self, size: Decimal, bankroll: Decimal) -> Decimal {
+    /// Reduce position size to fit within portfolio constraints:
+    /// total exposure and, if configured, the category's exposure budget.
+    pub fn adjust_size(
+        &self,
+        size: Decimal,
+        bankroll: Decimal,
+        category: &MarketCategory,
+    ) -> Decimal {
         let current_exposure = self.total_exposure();
         let max_exposure = bankroll * self.config.max_total_exposure_pct;
-        let remaining_capacity = max_exposure - current_exposure;
+        let mut remaining_capacity = max_exposure - current_exposure;
+
+        if let Some(budget) = self.category_budget(category, bankroll) {
+            remaining_capacity = remaining_capacity.min(budget - self.category_exposure(category));
+        }
 
         if remaining_capacity <= Decimal::ZERO {
             return Decimal::ZERO;
@@ -98,6 +134,67 @@ impl PortfolioManager {
         size.min(remaining_capacity)
     }
 
+    /// USD exposure budget for a category, if one is configured.
+    fn category_budget(&self, category: &MarketCategory, bankroll: Decimal) -> Option<Decimal> {
+        self.config
+            .category_exposure_pct
+            .get(category.as_str())
+            .map(|pct| bankroll * pct)
+    }
+
+    /// Net USD exposure in a category.
+    pub fn category_exposure(&self, category: &MarketCategory) -> Decimal {
+        self.net_exposure_where(|p| &p.category == category)
+    }
+
+    /// Net exposure of the positions matching `filter`, netted per market.
+    fn net_exposure_where(&self, filter: impl Fn(&Position) -> bool) -> Decimal {
+        let mut markets: BTreeMap<&str, MarketNetting> = BTreeMap::new();
+        for p in self.positions.iter().filter(|p| filter(p)) {
+            if p.entry_price <= Decimal::ZERO {
+                continue;
+            }
+            markets
+                .entry(&p.market_id)
+                .or_insert_with(|| MarketNetting::new(&p.market_id))
+                .add(p.side, p.size_usd / p.entry_price, p.size_usd, None);
+        }
+        markets.values().map(MarketNetting::net_exposure).sum()
+    }
+
+    /// Share of the bankroll a strategy bucket may size against.
+    pub fn bucket_balance(&self, bucket: &str, bankroll: Decimal) -> Decimal {
+        self.buckets.balance(bucket, bankroll)
+    }
+
+    /// Reduce position size to fit the bucket's remaining exposure cap.
+    pub fn adjust_size_for_bucket(
+        &self,
+        bucket: &str,
+        size: Decimal,
+        bankroll: Decimal,
+    ) -> Decimal {
+        let cap = self.buckets.exposure_cap(bucket, bankroll);
+        let remaining = cap - self.bucket_exposure(bucket);
+
+        if remaining <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        size.min(remaining)
+    }
+
+    /// Net USD exposure charged to a bucket.
+    pub fn bucket_exposure(&self, bucket: &str) -> Decimal {
+        self.net_exposure_where(|p| p.bucket == bucket)
+    }
+
+    /// Balance, exposure, and headroom for every configured bucket.
+    pub fn bucket_statuses(&self, bankroll: Decimal) -> Vec<BucketStatus> {
+        self.buckets
+            .statuses(bankroll, |name| self.bucket_exposure(name))
+    }
+
     /// Record a new position in the portfolio.
     pub fn add_position(&mut self, position: Position) {
         info!(
@@ -114,9 +211,9 @@ impl PortfolioManager {
         self.positions.retain(|p| p.market_id != market_id);
     }
 
-    /// Total USD exposure across all positions.
+    /// Net USD exposure across all positions.
     pub fn total_exposure(&self) -> Decimal {
-        self.positions.iter().map(|p| p.size_usd).sum()
+        self.net_exposure_where(|_| true)
     }
 
     /// Number of positions in a given category.
@@ -136,6 +233,100 @@ impl PortfolioManager {
     pub fn position_count(&self) -> usize {
         self.positions.len()
     }
+
+    /// All tracked positions.
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+}
+
+/// YES and NO holdings in one market, for netting.
+#[derive(Debug, Clone)]
+pub struct MarketNetting {
+    pub market_id: String,
+    pub yes_shares: Decimal,
+    pub yes_cost: Decimal,
+    pub no_shares: Decimal,
+    pub no_cost: Decimal,
+    /// Trade ids contributing to the holdings, when built from trades.
+    pub trade_ids: Vec<i64>,
+}
+
+impl MarketNetting {
+    fn new(market_id: &str) -> Self {
+        Self {
+            market_id: market_id.to_string(),
+            yes_shares: Decimal::ZERO,
+            yes_cost: Decimal::ZERO,
+            no_shares: Decimal::ZERO,
+            no_cost: Decimal::ZERO,
+            trade_ids: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, side: Side, shares: Decimal, cost: Decimal, trade_id: Option<i64>) {
+        match side {
+            Side::Yes => {
+                self.yes_shares += shares;
+                self.yes_cost += cost;
+            }
+            Side::No => {
+                self.no_shares += shares;
+                self.no_cost += cost;
+            }
+        }
+        self.trade_ids.extend(trade_id);
+    }
+
+    /// Shares held on both sides, each pair redeeming for $1.
+    pub fn matched_shares(&self) -> Decimal {
+        self.yes_shares.min(self.no_shares)
+    }
+
+    /// Worst-case loss: total cost minus the guaranteed payout of matched pairs.
+    pub fn net_exposure(&self) -> Decimal {
+        (self.yes_cost + self.no_cost - self.matched_shares()).max(Decimal::ZERO)
+    }
+
+    /// Whether YES and NO holdings cancel out, leaving no directional risk.
+    pub fn is_flat(&self) -> bool {
+        self.yes_shares > Decimal::ZERO
+            && (self.yes_shares - self.no_shares).abs() <= FLAT_TOLERANCE_SHARES
+    }
+
+    /// Exit prices `(yes, no)` for merging matched pairs into $1 each. The
+    /// pair's profit is split evenly, so each leg exits at its average cost
+    /// plus half of it and the two prices sum to $1.
+    pub fn merge_exit_prices(&self) -> Option<(Decimal, Decimal)> {
+        if self.yes_shares <= Decimal::ZERO || self.no_shares <= Decimal::ZERO {
+            return None;
+        }
+        let yes_avg = self.yes_cost / self.yes_shares;
+        let no_avg = self.no_cost / self.no_shares;
+        let half_profit = (Decimal::ONE - yes_avg - no_avg) / dec!(2);
+        Some((yes_avg + half_profit, no_avg + half_profit))
+    }
+}
+
+/// Share difference below which opposite holdings count as fully offsetting.
+const FLAT_TOLERANCE_SHARES: Decimal = dec!(0.01);
+
+/// Group open trades by market for netting.
+pub fn net_by_market(trades: &[TradeRecord]) -> Vec<MarketNetting> {
+    let mut markets: BTreeMap<&str, MarketNetting> = BTreeMap::new();
+    for trade in trades {
+        let (price, shares) = (trade.entry_price, trade.size);
+        let side = if trade.direction == "NO" {
+            Side::No
+        } else {
+            Side::Yes
+        };
+        markets
+            .entry(&trade.market_id)
+            .or_insert_with(|| MarketNetting::new(&trade.market_id))
+            .add(side, shares, price * shares, trade.id);
+    }
+    markets.into_values().collect()
 }
 
 /// Result of portfolio constraint checking.
@@ -154,8 +345,13 @@ impl ConstraintCheck {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::market::models::{Market, MarketCategory, OrderBookSnapshot, PriceLevel, TokenInfo};
+    use crate::config::BucketConfig;
+    use crate::market::models::{
+        BookLiquidity, Market, MarketCategory, OrderBookSnapshot, PriceLevel, TokenInfo,
+    };
+    use crate::risk::buckets::DIRECTIONAL_BUCKET;
     use chrono::Utc;
+    use std::collections::HashMap;
 
     fn test_config() -> RiskConfig {
         RiskConfig {
@@ -164,6 +360,17 @@ mod tests {
             max_total_exposure_pct: dec!(0.30),
             max_positions_per_category: 3,
             min_position_usd: dec!(1),
+            max_market_loss_usd: None,
+            max_trade_loss_usd: None,
+            resolution_decay: Vec::new(),
+            category_exposure_pct: HashMap::new(),
+            event_windows: Vec::new(),
+            buckets: Vec::new(),
+            approval: Default::default(),
+            hedge: Default::default(),
+            sizing: Default::default(),
+            rebalance: Default::default(),
+            drawdown: Default::default(),
         }
     }
 
@@ -186,6 +393,11 @@ mod tests {
                 category,
                 volume_24h: dec!(10000),
                 active: true,
+                event_id: None,
+                event_slug: None,
+                neg_risk: false,
+                description: String::new(),
+                resolution_source: None,
             },
             order_book: OrderBookSnapshot {
                 token_id: "tok1".to_string(),
@@ -201,12 +413,14 @@ mod tests {
                 midpoint: dec!(0.50),
                 implied_probability: dec!(0.50),
                 timestamp: Utc::now(),
+                liquidity: BookLiquidity::TwoSided,
             },
             fair_value: dec!(0.65),
             confidence: dec!(0.85),
             edge: dec!(0.15),
             recommended_side: Side::Yes,
             kelly_size,
+            new_listing: false,
         }
     }
 
@@ -230,6 +444,7 @@ mod tests {
                 side: Side::Yes,
                 size_usd: dec!(7),
                 entry_price: dec!(0.50),
+                bucket: DIRECTIONAL_BUCKET.to_string(),
             });
         }
         assert_eq!(pm.total_exposure(), dec!(28));
@@ -252,6 +467,7 @@ mod tests {
                 side: Side::Yes,
                 size_usd: dec!(2),
                 entry_price: dec!(0.50),
+                bucket: DIRECTIONAL_BUCKET.to_string(),
             });
         }
 
@@ -270,6 +486,7 @@ mod tests {
             side: Side::Yes,
             size_usd: dec!(3),
             entry_price: dec!(0.50),
+            bucket: DIRECTIONAL_BUCKET.to_string(),
         });
 
         let opp = test_opportunity("m1", MarketCategory::Weather, dec!(3));
@@ -288,13 +505,72 @@ mod tests {
             side: Side::Yes,
             size_usd: dec!(20),
             entry_price: dec!(0.50),
+            bucket: DIRECTIONAL_BUCKET.to_string(),
         });
 
         // Max exposure: 30% of $100 = $30, remaining = $10
-        let adjusted = pm.adjust_size(dec!(15), dec!(100));
+        let adjusted = pm.adjust_size(dec!(15), dec!(100), &MarketCategory::Sports);
         assert_eq!(adjusted, dec!(10));
     }
 
+    #[test]
+    fn test_exposure_nets_opposite_sides() {
+        let mut pm = PortfolioManager::new(test_config());
+        let position = |side, size_usd, entry_price| Position {
+            market_id: "m1".to_string(),
+            token_id: "t1".to_string(),
+            category: MarketCategory::Weather,
+            side,
+            size_usd,
+            entry_price,
+            bucket: DIRECTIONAL_BUCKET.to_string(),
+        };
+        // 20 YES @ 0.50 ($10) hedged with 10 NO @ 0.40 ($4): 10 pairs pay $10
+        pm.add_position(position(Side::Yes, dec!(10), dec!(0.50)));
+        pm.add_position(position(Side::No, dec!(4), dec!(0.40)));
+        assert_eq!(pm.total_exposure(), dec!(4));
+        assert_eq!(pm.category_exposure(&MarketCategory::Weather), dec!(4));
+        assert_eq!(pm.bucket_exposure(DIRECTIONAL_BUCKET), dec!(4));
+    }
+
+    #[test]
+    fn test_net_by_market_flat() {
+        let trade = |id, direction: &str, price: Decimal| TradeRecord {
+            id: Some(id),
+            cycle: 1,
+            market_id: "m1".to_string(),
+            market_question: None,
+            direction: direction.to_string(),
+            entry_price: price,
+            size: dec!(10),
+            edge_at_entry: dec!(0.1),
+            claude_fair_value: dec!(0.5),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.1),
+            kelly_adjusted: dec!(0.05),
+            status: "OPEN".to_string(),
+            pnl: None,
+            created_at: None,
+            resolved_at: None,
+            bucket: DIRECTIONAL_BUCKET.to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        };
+        let netted = net_by_market(&[trade(1, "YES", dec!(0.60)), trade(2, "NO", dec!(0.30))]);
+        assert_eq!(netted.len(), 1);
+        let m = &netted[0];
+        assert!(m.is_flat());
+        assert_eq!(m.trade_ids, vec![1, 2]);
+        // $9 paid for 10 pairs worth $10: nothing at risk
+        assert_eq!(m.net_exposure(), Decimal::ZERO);
+        let (yes_exit, no_exit) = m.merge_exit_prices().unwrap();
+        assert_eq!(yes_exit, dec!(0.65));
+        assert_eq!(no_exit, dec!(0.35));
+    }
+
     #[test]
     fn test_remove_position() {
         let mut pm = PortfolioManager::new(test_config());
@@ -305,10 +581,86 @@ mod tests {
             side: Side::Yes,
             size_usd: dec!(5),
             entry_price: dec!(0.50),
+            bucket: DIRECTIONAL_BUCKET.to_string(),
         });
         assert_eq!(pm.position_count(), 1);
 
         pm.remove_position("m1");
         assert_eq!(pm.position_count(), 0);
     }
+
+    #[test]
+    fn test_bucket_sizing() {
+        let mut config = test_config();
+        config.buckets = vec![
+            BucketConfig {
+                name: DIRECTIONAL_BUCKET.to_string(),
+                allocation_pct: dec!(0.70),
+                max_exposure_pct: dec!(0.50),
+            },
+            BucketConfig {
+                name: "experimental".to_string(),
+                allocation_pct: dec!(0.10),
+                max_exposure_pct: dec!(1.0),
+            },
+        ];
+        let mut pm = PortfolioManager::new(config);
+        assert_eq!(pm.bucket_balance(DIRECTIONAL_BUCKET, dec!(100)), dec!(70));
+
+        pm.add_position(Position {
+            market_id: "m1".to_string(),
+            token_id: "t1".to_string(),
+            category: MarketCategory::Weather,
+            side: Side::Yes,
+            size_usd: dec!(30),
+            entry_price: dec!(0.50),
+            bucket: DIRECTIONAL_BUCKET.to_string(),
+        });
+
+        // Directional cap: 70 * 0.5 = 35, 30 used → 5 left
+        let adjusted = pm.adjust_size_for_bucket(DIRECTIONAL_BUCKET, dec!(10), dec!(100));
+        assert_eq!(adjusted, dec!(5));
+        // Experimental bucket is untouched
+        let adjusted = pm.adjust_size_for_bucket("experimental", dec!(4), dec!(100));
+        assert_eq!(adjusted, dec!(4));
+
+        let statuses = pm.bucket_statuses(dec!(100));
+        assert_eq!(statuses[0].exposure, dec!(30));
+        assert_eq!(statuses[1].exposure, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_category_budget() {
+        let mut config = test_config();
+        config
+            .category_exposure_pct
+            .insert("crypto".to_string(), dec!(0.10));
+        let mut pm = PortfolioManager::new(config);
+        pm.add_position(Position {
+            market_id: "c1".to_string(),
+            token_id: "t1".to_string(),
+            category: MarketCategory::Crypto,
+            side: Side::Yes,
+            size_usd: dec!(7),
+            entry_price: dec!(0.50),
+            bucket: DIRECTIONAL_BUCKET.to_string(),
+        });
+
+        // $7 + $5 > 10% of $100
+        let opp = test_opportunity("c2", MarketCategory::Crypto, dec!(5));
+        assert!(!pm.check_constraints(&opp, dec!(100)).passed());
+        // Other categories are unaffected by the crypto budget
+        let opp = test_opportunity("w1", MarketCategory::Weather, dec!(5));
+        assert!(pm.check_constraints(&opp, dec!(100)).passed());
+
+        // Sizing trims to the $3 left in the crypto budget
+        assert_eq!(
+            pm.adjust_size(dec!(5), dec!(100), &MarketCategory::Crypto),
+            dec!(3)
+        );
+        assert_eq!(
+            pm.adjust_size(dec!(5), dec!(100), &MarketCategory::Weather),
+            dec!(5)
+        );
+    }
 }
@@ -0,0 +1,178 @@
+//! Rebalancing toward better opportunities.
+//!
+//! Once exposure is maxed out, a new opportunity is skipped however large
+//! its edge. With `[risk.rebalance]` enabled, the agent instead looks for
+//! the weakest held position — the one giving up least by being sold now,
+//! i.e. with the smallest remaining edge plus exit cost (half the spread and
+//! the taker fee) — and exits it when the new opportunity's net edge beats
+//! that by `min_edge_advantage`. Positions younger than `min_hold_minutes`
+//! are never swapped out, and at most `max_per_cycle` exits happen per
+//! cycle, so valuations jittering around each other don't churn the book.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::config::RiskConfig;
+use crate::market::models::{MarketCategory, Side};
+use crate::risk::portfolio::PortfolioManager;
+
+/// Tag on trades exited to make room for a better opportunity.
+pub const REBALANCED_TAG: &str = "rebalanced";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RebalanceConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// How much more net edge the new opportunity needs than the exited
+    /// position's remaining edge plus exit cost.
+    #[serde(default = "default_min_edge_advantage")]
+    pub min_edge_advantage: Decimal,
+    /// Positions held for less than this are never exited to rebalance.
+    #[serde(default = "default_min_hold_minutes")]
+    pub min_hold_minutes: i64,
+    #[serde(default = "default_max_per_cycle")]
+    pub max_per_cycle: usize,
+}
+
+impl Default for RebalanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_edge_advantage: default_min_edge_advantage(),
+            min_hold_minutes: default_min_hold_minutes(),
+            max_per_cycle: default_max_per_cycle(),
+        }
+    }
+}
+
+fn default_min_edge_advantage() -> Decimal {
+    dec!(0.10)
+}
+
+fn default_min_hold_minutes() -> i64 {
+    120
+}
+
+fn default_max_per_cycle() -> usize {
+    1
+}
+
+/// Which held positions could make room for a new one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExitScope {
+    /// Only total exposure is short: any position will do.
+    Any,
+    /// A per-category limit binds: only positions in that category help.
+    Category(MarketCategory),
+}
+
+impl ExitScope {
+    pub fn includes(&self, category: &MarketCategory) -> bool {
+        match self {
+            ExitScope::Any => true,
+            ExitScope::Category(c) => c == category,
+        }
+    }
+}
+
+/// Whether a `size_usd` position in `category` is blocked by exposure or
+/// position-count limits, and if so which exits would unblock it.
+pub fn exit_scope(
+    portfolio: &PortfolioManager,
+    risk: &RiskConfig,
+    category: &MarketCategory,
+    size_usd: Decimal,
+    bankroll: Decimal,
+) -> Option<ExitScope> {
+    let in_category = portfolio
+        .positions()
+        .iter()
+        .filter(|p| &p.category == category)
+        .count();
+    let category_full = in_category >= risk.max_positions_per_category as usize
+        || risk
+            .category_exposure_pct
+            .get(category.as_str())
+            .is_some_and(|pct| portfolio.category_exposure(category) + size_usd > bankroll * pct);
+    if category_full {
+        return Some(ExitScope::Category(category.clone()));
+    }
+    let total_full = portfolio.total_exposure() + size_usd > bankroll * risk.max_total_exposure_pct;
+    total_full.then_some(ExitScope::Any)
+}
+
+/// A held position as a rebalancing candidate.
+#[derive(Debug, Clone)]
+pub struct HeldPosition {
+    pub market_id: String,
+    pub side: Side,
+    /// Fair value minus the current price of the held token.
+    pub remaining_edge: Decimal,
+    /// Per-share cost of selling now: midpoint minus best bid, plus fees.
+    pub exit_cost: Decimal,
+    /// Most recent entry into the market.
+    pub entered_at: DateTime<Utc>,
+}
+
+impl HeldPosition {
+    /// Value per share given up by exiting instead of holding.
+    pub fn swap_cost(&self) -> Decimal {
+        self.remaining_edge + self.exit_cost
+    }
+}
+
+/// The position to exit for an opportunity with `new_edge`, if any clears
+/// the hysteresis: held long enough, and cheapest to give up by at least
+/// `min_edge_advantage`.
+pub fn weakest<'a>(
+    held: &'a [HeldPosition],
+    new_edge: Decimal,
+    config: &RebalanceConfig,
+    now: DateTime<Utc>,
+) -> Option<&'a HeldPosition> {
+    let min_hold = Duration::minutes(config.min_hold_minutes);
+    held.iter()
+        .filter(|p| now - p.entered_at >= min_hold)
+        .min_by_key(|p| p.swap_cost())
+        .filter(|p| new_edge >= p.swap_cost() + config.min_edge_advantage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn held(market_id: &str, edge: Decimal, exit_cost: Decimal, hours: i64) -> HeldPosition {
+        HeldPosition {
+            market_id: market_id.to_string(),
+            side: Side::Yes,
+            remaining_edge: edge,
+            exit_cost,
+            entered_at: Utc::now() - Duration::hours(hours),
+        }
+    }
+
+    #[test]
+    fn test_exits_weakest_position_only_for_a_much_larger_edge() {
+        let config = RebalanceConfig {
+            enabled: true,
+            ..RebalanceConfig::default()
+        };
+        let now = Utc::now();
+        let positions = [
+            held("strong", dec!(0.12), dec!(0.01), 10),
+            // Little edge left, but a wide book makes it costly to sell
+            held("illiquid", dec!(0.01), dec!(0.08), 10),
+            held("spent", dec!(0.02), dec!(0.01), 10),
+            // Weakest of all, but only just bought
+            held("fresh", dec!(-0.05), dec!(0.01), 1),
+        ];
+
+        let exit = weakest(&positions, dec!(0.15), &config, now).unwrap();
+        assert_eq!(exit.market_id, "spent");
+        assert_eq!(exit.swap_cost(), dec!(0.03));
+        // An edge that isn't clearly better leaves the book alone
+        assert!(weakest(&positions, dec!(0.12), &config, now).is_none());
+    }
+}
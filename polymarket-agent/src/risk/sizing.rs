@@ -0,0 +1,342 @@
+//! Position sizing strategies.
+//!
+//! Fractional Kelly is the default, but it sizes aggressively on confident
+//! edges. Fixed-fraction stakes the same share of bankroll on every trade,
+//! and equal-risk sizes each trade so its P&L standard deviation is the same
+//! share of bankroll. All strategies only trade a positive Kelly edge and
+//! share the state multiplier and the position caps.
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::config::RiskConfig;
+use crate::market::models::AgentState;
+use crate::risk::kelly::{kelly_size, KellyResult};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingMode {
+    #[default]
+    Kelly,
+    FixedFraction,
+    EqualRisk,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SizingConfig {
+    #[serde(default)]
+    pub mode: SizingMode,
+    /// Share of bankroll staked per trade in `fixed_fraction` mode.
+    #[serde(default = "default_fixed_fraction")]
+    pub fixed_fraction: Decimal,
+    /// P&L standard deviation per trade, as a share of bankroll, in
+    /// `equal_risk` mode.
+    #[serde(default = "default_risk_per_trade_pct")]
+    pub risk_per_trade_pct: Decimal,
+}
+
+impl Default for SizingConfig {
+    fn default() -> Self {
+        Self {
+            mode: SizingMode::default(),
+            fixed_fraction: default_fixed_fraction(),
+            risk_per_trade_pct: default_risk_per_trade_pct(),
+        }
+    }
+}
+
+fn default_fixed_fraction() -> Decimal {
+    dec!(0.02)
+}
+
+fn default_risk_per_trade_pct() -> Decimal {
+    dec!(0.02)
+}
+
+/// A way of turning an edge into a position size.
+pub trait SizingStrategy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Size a buy at `market_price` when the outcome's fair probability is
+    /// `fair_prob`. `kelly_raw` on the result is always the raw Kelly
+    /// fraction; `kelly_adjusted` is the share of bankroll actually staked.
+    fn size(
+        &self,
+        fair_prob: Decimal,
+        market_price: Decimal,
+        confidence: Decimal,
+        bankroll: Decimal,
+        state: AgentState,
+        config: &RiskConfig,
+    ) -> KellyResult;
+}
+
+/// Fractional Kelly scaled by confidence — the original sizing.
+pub struct FractionalKelly;
+
+impl SizingStrategy for FractionalKelly {
+    fn name(&self) -> &'static str {
+        "kelly"
+    }
+
+    fn size(
+        &self,
+        fair_prob: Decimal,
+        market_price: Decimal,
+        confidence: Decimal,
+        bankroll: Decimal,
+        state: AgentState,
+        config: &RiskConfig,
+    ) -> KellyResult {
+        kelly_size(fair_prob, market_price, confidence, bankroll, state, config)
+    }
+}
+
+/// The same share of bankroll on every trade with an edge.
+pub struct FixedFraction {
+    pub fraction: Decimal,
+}
+
+impl SizingStrategy for FixedFraction {
+    fn name(&self) -> &'static str {
+        "fixed_fraction"
+    }
+
+    fn size(
+        &self,
+        fair_prob: Decimal,
+        market_price: Decimal,
+        _confidence: Decimal,
+        bankroll: Decimal,
+        state: AgentState,
+        config: &RiskConfig,
+    ) -> KellyResult {
+        let kelly = kelly_size(
+            fair_prob,
+            market_price,
+            Decimal::ONE,
+            bankroll,
+            state,
+            config,
+        );
+        capped(kelly.kelly_raw, self.fraction, bankroll, state, config)
+    }
+}
+
+/// Each trade gets the same P&L standard deviation.
+///
+/// A stake `s` bought at price `c` pays `s/c` with probability `p`, so its
+/// P&L standard deviation is `s·√(p(1-p))/c`. Solving for a target of
+/// `risk_pct · bankroll` gives a stake of `risk_pct · c / √(p(1-p))`.
+pub struct EqualRisk {
+    pub risk_pct: Decimal,
+}
+
+impl SizingStrategy for EqualRisk {
+    fn name(&self) -> &'static str {
+        "equal_risk"
+    }
+
+    fn size(
+        &self,
+        fair_prob: Decimal,
+        market_price: Decimal,
+        _confidence: Decimal,
+        bankroll: Decimal,
+        state: AgentState,
+        config: &RiskConfig,
+    ) -> KellyResult {
+        let kelly = kelly_size(
+            fair_prob,
+            market_price,
+            Decimal::ONE,
+            bankroll,
+            state,
+            config,
+        );
+        let variance = (fair_prob * (Decimal::ONE - fair_prob))
+            .to_f64()
+            .unwrap_or(0.0);
+        let Some(stdev) = Decimal::from_f64(variance.sqrt()).filter(|s| *s > Decimal::ZERO) else {
+            return capped(kelly.kelly_raw, Decimal::ZERO, bankroll, state, config);
+        };
+        let fraction = self.risk_pct * market_price / stdev;
+        capped(kelly.kelly_raw, fraction, bankroll, state, config)
+    }
+}
+
+/// Apply the state multiplier, position cap and minimum size to a strategy's
+/// bankroll fraction. No edge (`kelly_raw <= 0`) means no trade.
+fn capped(
+    kelly_raw: Decimal,
+    fraction: Decimal,
+    bankroll: Decimal,
+    state: AgentState,
+    config: &RiskConfig,
+) -> KellyResult {
+    let state_multiplier = match state {
+        AgentState::Alive => Decimal::ONE,
+        AgentState::LowFuel => dec!(0.25),
+        AgentState::CriticalSurvival | AgentState::Dead => Decimal::ZERO,
+    };
+    let kelly_adjusted = fraction * state_multiplier;
+    let mut position_usd = if kelly_raw > Decimal::ZERO && bankroll > Decimal::ZERO {
+        kelly_adjusted * bankroll
+    } else {
+        Decimal::ZERO
+    };
+
+    let max_position = bankroll * config.max_position_pct;
+    let capped = position_usd > max_position;
+    if capped {
+        position_usd = max_position;
+    }
+    if position_usd < config.min_position_usd {
+        position_usd = Decimal::ZERO;
+    }
+
+    KellyResult {
+        kelly_raw,
+        kelly_adjusted,
+        position_usd,
+        capped,
+    }
+}
+
+/// Build the strategy selected in config.
+pub fn strategy(config: &SizingConfig) -> Box<dyn SizingStrategy> {
+    match config.mode {
+        SizingMode::Kelly => Box::new(FractionalKelly),
+        SizingMode::FixedFraction => Box::new(FixedFraction {
+            fraction: config.fixed_fraction,
+        }),
+        SizingMode::EqualRisk => Box::new(EqualRisk {
+            risk_pct: config.risk_per_trade_pct,
+        }),
+    }
+}
+
+/// Size a position with the strategy selected in `config.sizing`.
+pub fn position_size(
+    fair_prob: Decimal,
+    market_price: Decimal,
+    confidence: Decimal,
+    bankroll: Decimal,
+    state: AgentState,
+    config: &RiskConfig,
+) -> KellyResult {
+    strategy(&config.sizing).size(fair_prob, market_price, confidence, bankroll, state, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(mode: SizingMode) -> RiskConfig {
+        RiskConfig {
+            kelly_fraction: dec!(0.5),
+            max_position_pct: dec!(0.06),
+            max_total_exposure_pct: dec!(0.30),
+            max_positions_per_category: 3,
+            min_position_usd: dec!(1),
+            max_market_loss_usd: None,
+            max_trade_loss_usd: None,
+            resolution_decay: Vec::new(),
+            category_exposure_pct: HashMap::new(),
+            event_windows: Vec::new(),
+            buckets: Vec::new(),
+            approval: Default::default(),
+            hedge: Default::default(),
+            sizing: SizingConfig {
+                mode,
+                ..Default::default()
+            },
+            rebalance: Default::default(),
+            drawdown: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_default_mode_matches_kelly() {
+        let config = config(SizingMode::Kelly);
+        let via_strategy = position_size(
+            dec!(0.70),
+            dec!(0.50),
+            dec!(0.85),
+            dec!(100),
+            AgentState::Alive,
+            &config,
+        );
+        let direct = kelly_size(
+            dec!(0.70),
+            dec!(0.50),
+            dec!(0.85),
+            dec!(100),
+            AgentState::Alive,
+            &config,
+        );
+        assert_eq!(via_strategy.position_usd, direct.position_usd);
+    }
+
+    #[test]
+    fn test_fixed_fraction_ignores_edge_size() {
+        let config = config(SizingMode::FixedFraction);
+        let small = position_size(
+            dec!(0.55),
+            dec!(0.50),
+            dec!(0.5),
+            dec!(200),
+            AgentState::Alive,
+            &config,
+        );
+        let large = position_size(
+            dec!(0.90),
+            dec!(0.50),
+            dec!(0.9),
+            dec!(200),
+            AgentState::Alive,
+            &config,
+        );
+        assert_eq!(small.position_usd, dec!(4));
+        assert_eq!(large.position_usd, dec!(4));
+
+        // Still no trade without an edge
+        let none = position_size(
+            dec!(0.40),
+            dec!(0.50),
+            dec!(0.9),
+            dec!(200),
+            AgentState::Alive,
+            &config,
+        );
+        assert!(!none.should_trade());
+    }
+
+    #[test]
+    fn test_equal_risk_stakes_less_on_longshots() {
+        let config = config(SizingMode::EqualRisk);
+        // At p = 0.5, c = 0.5: stake = 0.02 · 0.5 / 0.5 = 2% of bankroll
+        let even = position_size(
+            dec!(0.50),
+            dec!(0.45),
+            dec!(1),
+            dec!(1000),
+            AgentState::Alive,
+            &config,
+        );
+        let longshot = position_size(
+            dec!(0.15),
+            dec!(0.10),
+            dec!(1),
+            dec!(1000),
+            AgentState::Alive,
+            &config,
+        );
+        assert!(even.position_usd > longshot.position_usd);
+        assert!(longshot.should_trade());
+        assert!(even.position_usd <= dec!(60)); // max_position_pct cap
+    }
+}
@@ -0,0 +1,205 @@
+//! Concurrent strategies with isolated virtual bankrolls.
+//!
+//! Each strategy owns a set of market categories, a share of the starting
+//! capital and its own risk overrides. A strategy's bankroll is its share
+//! plus whatever it has realized since, so one strategy's losses never
+//! shrink another's sizing. Trades are tagged with the strategy name in
+//! their `bucket` column, which the per-bucket metrics already break down.
+
+pub mod market_making;
+pub mod sniping;
+
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::{bail, Result};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::config::RiskConfig;
+use crate::market::models::MarketCategory;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StrategyConfig {
+    pub name: String,
+    /// Categories this strategy trades. Empty = every category.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Share of the starting capital given to this strategy.
+    pub allocation_pct: Decimal,
+    /// Maximum open exposure as a fraction of the strategy's bankroll.
+    #[serde(default = "default_max_exposure_pct")]
+    pub max_exposure_pct: Decimal,
+    /// Overrides of the global `[risk]` settings; unset values inherit.
+    #[serde(default)]
+    pub kelly_fraction: Option<Decimal>,
+    #[serde(default)]
+    pub max_position_pct: Option<Decimal>,
+    #[serde(default)]
+    pub min_position_usd: Option<Decimal>,
+    #[serde(default)]
+    pub max_trade_loss_usd: Option<Decimal>,
+}
+
+fn default_max_exposure_pct() -> Decimal {
+    Decimal::ONE
+}
+
+impl StrategyConfig {
+    pub fn applies_to(&self, category: &MarketCategory) -> bool {
+        self.categories.is_empty() || self.categories.iter().any(|c| c == category.as_str())
+    }
+
+    /// The global risk config with this strategy's overrides applied.
+    pub fn risk_config(&self, base: &RiskConfig) -> RiskConfig {
+        let mut risk = base.clone();
+        if let Some(v) = self.kelly_fraction {
+            risk.kelly_fraction = v;
+        }
+        if let Some(v) = self.max_position_pct {
+            risk.max_position_pct = v;
+        }
+        if let Some(v) = self.min_position_usd {
+            risk.min_position_usd = v;
+        }
+        if self.max_trade_loss_usd.is_some() {
+            risk.max_trade_loss_usd = self.max_trade_loss_usd;
+        }
+        risk
+    }
+}
+
+/// The configured strategies. With none configured the agent runs its
+/// single directional strategy against the whole bankroll, as before.
+#[derive(Debug, Clone, Default)]
+pub struct Strategies {
+    strategies: Vec<StrategyConfig>,
+}
+
+impl Strategies {
+    pub fn new(strategies: Vec<StrategyConfig>) -> Self {
+        Self { strategies }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.strategies.is_empty()
+    }
+
+    /// Check names are unique, allocations positive and summing to at most
+    /// 100%, and that bankroll buckets aren't also configured.
+    pub fn validate(&self, risk: &RiskConfig) -> Result<()> {
+        if self.is_enabled() && !risk.buckets.is_empty() {
+            bail!("[[strategies]] and [[risk.buckets]] cannot both be configured");
+        }
+        let mut names = HashSet::new();
+        let mut total = Decimal::ZERO;
+        for s in &self.strategies {
+            if !names.insert(s.name.as_str()) {
+                bail!("Duplicate strategy name '{}'", s.name);
+            }
+            if s.allocation_pct <= Decimal::ZERO {
+                bail!("Strategy '{}' has non-positive allocation", s.name);
+            }
+            total += s.allocation_pct;
+        }
+        if total > Decimal::ONE {
+            bail!("Strategy allocations sum to {total}, exceeding 100%");
+        }
+        Ok(())
+    }
+
+    /// The first strategy trading `category`.
+    pub fn route(&self, category: &MarketCategory) -> Option<&StrategyConfig> {
+        self.strategies.iter().find(|s| s.applies_to(category))
+    }
+
+    /// Virtual bankroll of `strategy`: its share of the capital the
+    /// strategies started from, plus its own realized P&L.
+    ///
+    /// `bankroll` is the current total; `realized` maps strategy name to
+    /// realized P&L.
+    pub fn bankroll(
+        &self,
+        strategy: &StrategyConfig,
+        bankroll: Decimal,
+        realized: &BTreeMap<String, Decimal>,
+    ) -> Decimal {
+        let all_realized: Decimal = self
+            .strategies
+            .iter()
+            .filter_map(|s| realized.get(&s.name))
+            .sum();
+        let own = realized.get(&strategy.name).copied().unwrap_or_default();
+        ((bankroll - all_realized) * strategy.allocation_pct + own).max(Decimal::ZERO)
+    }
+
+    /// Room left under `strategy`'s exposure cap.
+    pub fn available(
+        &self,
+        strategy: &StrategyConfig,
+        strategy_bankroll: Decimal,
+        exposure: Decimal,
+    ) -> Decimal {
+        (strategy_bankroll * strategy.max_exposure_pct - exposure).max(Decimal::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use rust_decimal_macros::dec;
+
+    fn strategy(name: &str, categories: &[&str], allocation_pct: Decimal) -> StrategyConfig {
+        StrategyConfig {
+            name: name.to_string(),
+            categories: categories.iter().map(|c| c.to_string()).collect(),
+            allocation_pct,
+            max_exposure_pct: dec!(0.5),
+            kelly_fraction: None,
+            max_position_pct: None,
+            min_position_usd: None,
+            max_trade_loss_usd: None,
+        }
+    }
+
+    #[test]
+    fn test_bankrolls_are_isolated() {
+        let strategies = Strategies::new(vec![
+            strategy("politics", &["politics"], dec!(0.5)),
+            strategy("sports", &["sports"], dec!(0.5)),
+        ]);
+        assert_eq!(
+            strategies.route(&MarketCategory::Sports).unwrap().name,
+            "sports"
+        );
+        assert!(strategies.route(&MarketCategory::Crypto).is_none());
+
+        // Started with $100; sports lost $20, so the total is now $80
+        let realized = BTreeMap::from([("sports".to_string(), dec!(-20))]);
+        let politics = strategies.route(&MarketCategory::Politics).unwrap();
+        let sports = strategies.route(&MarketCategory::Sports).unwrap();
+        assert_eq!(strategies.bankroll(politics, dec!(80), &realized), dec!(50));
+        assert_eq!(strategies.bankroll(sports, dec!(80), &realized), dec!(30));
+        assert_eq!(strategies.available(sports, dec!(30), dec!(10)), dec!(5));
+    }
+
+    #[test]
+    fn test_validate_and_overrides() {
+        let config: AppConfig = toml::from_str(include_str!("../../config/default.toml")).unwrap();
+        let mut aggressive = strategy("sports", &["sports"], dec!(0.6));
+        aggressive.kelly_fraction = Some(dec!(0.5));
+        assert_eq!(
+            aggressive.risk_config(&config.risk).kelly_fraction,
+            dec!(0.5)
+        );
+        assert_eq!(
+            aggressive.risk_config(&config.risk).min_position_usd,
+            config.risk.min_position_usd
+        );
+
+        let ok = Strategies::new(vec![aggressive.clone()]);
+        assert!(ok.validate(&config.risk).is_ok());
+        let over = Strategies::new(vec![aggressive.clone(), aggressive]);
+        assert!(over.validate(&config.risk).is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! Two-sided quoting in wide-spread markets.
+//!
+//! The directional strategy only takes liquidity. In markets whose spread is
+//! wide and whose fair value was established by a recent valuation, the
+//! agent can instead rest a bid on each outcome one tick inside its book,
+//! never closer than `edge_buffer` to that outcome's fair value. The two
+//! bids then sum to less than $1: when both fill, the YES/NO pair is merged
+//! back into cash and the difference is earned. A bid that fills alone is
+//! still priced below fair value.
+//!
+//! Each bid is `quote_size_usd`, shrunk to the room left under
+//! `max_inventory_usd` by unpaired holdings on that side, so a market that
+//! keeps filling one side stops being bid on it. Portfolio exposure limits
+//! apply on top. Quotes are cancelled and replaced every cycle.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::market::models::{OrderBookSnapshot, Side};
+use crate::risk::portfolio::MarketNetting;
+
+/// Bucket quotes and their fills are recorded under.
+pub const MARKET_MAKING_BUCKET: &str = "market_making";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MarketMakingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Quote only books at least this wide.
+    #[serde(default = "default_min_spread")]
+    pub min_spread: Decimal,
+    /// Quote only markets valued within this many seconds.
+    #[serde(default = "default_max_valuation_age_seconds")]
+    pub max_valuation_age_seconds: u64,
+    /// Quote only markets valued with at least this confidence.
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: Decimal,
+    /// Minimum distance of each bid below its outcome's fair value.
+    #[serde(default = "default_edge_buffer")]
+    pub edge_buffer: Decimal,
+    /// Price increment; bids improve the best bid by one tick.
+    #[serde(default = "default_tick")]
+    pub tick: Decimal,
+    /// Size of each bid in USD.
+    #[serde(default = "default_quote_size_usd")]
+    pub quote_size_usd: Decimal,
+    /// Unpaired holdings on one side of a market above which that side is
+    /// no longer bid.
+    #[serde(default = "default_max_inventory_usd")]
+    pub max_inventory_usd: Decimal,
+    /// Markets quoted per cycle at most.
+    #[serde(default = "default_max_markets")]
+    pub max_markets: usize,
+}
+
+impl Default for MarketMakingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_spread: default_min_spread(),
+            max_valuation_age_seconds: default_max_valuation_age_seconds(),
+            min_confidence: default_min_confidence(),
+            edge_buffer: default_edge_buffer(),
+            tick: default_tick(),
+            quote_size_usd: default_quote_size_usd(),
+            max_inventory_usd: default_max_inventory_usd(),
+            max_markets: default_max_markets(),
+        }
+    }
+}
+
+fn default_min_spread() -> Decimal {
+    dec!(0.06)
+}
+
+fn default_max_valuation_age_seconds() -> u64 {
+    1800
+}
+
+fn default_min_confidence() -> Decimal {
+    dec!(0.6)
+}
+
+fn default_edge_buffer() -> Decimal {
+    dec!(0.02)
+}
+
+fn default_tick() -> Decimal {
+    dec!(0.01)
+}
+
+fn default_quote_size_usd() -> Decimal {
+    dec!(2.0)
+}
+
+fn default_max_inventory_usd() -> Decimal {
+    dec!(10.0)
+}
+
+fn default_max_markets() -> usize {
+    3
+}
+
+/// A resting bid for one outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quote {
+    pub side: Side,
+    pub price: Decimal,
+    pub shares: Decimal,
+}
+
+/// Bids for both outcomes of a market valued at `fair_yes`, given each
+/// outcome token's book and current `inventory` in the market.
+///
+/// A side is skipped when its book is one-sided or narrower than
+/// `min_spread`, when improving the bid would come within `edge_buffer` of
+/// fair value, or when its unpaired inventory has used up
+/// `max_inventory_usd`.
+pub fn quotes(
+    config: &MarketMakingConfig,
+    fair_yes: Decimal,
+    yes_book: &OrderBookSnapshot,
+    no_book: &OrderBookSnapshot,
+    inventory: Option<&MarketNetting>,
+) -> Vec<Quote> {
+    [
+        (Side::Yes, fair_yes, yes_book),
+        (Side::No, Decimal::ONE - fair_yes, no_book),
+    ]
+    .into_iter()
+    .filter_map(|(side, fair, book)| {
+        let price = quote_price(config, fair, book)?;
+        let room = config.max_inventory_usd - unpaired_cost(inventory, side);
+        let shares = (config.quote_size_usd.min(room) / price).round_dp(2);
+        (shares > Decimal::ZERO).then_some(Quote {
+            side,
+            price,
+            shares,
+        })
+    })
+    .collect()
+}
+
+/// One tick above the best bid, capped `edge_buffer` below `fair`, if that
+/// still sits strictly inside a wide enough spread.
+fn quote_price(
+    config: &MarketMakingConfig,
+    fair: Decimal,
+    book: &OrderBookSnapshot,
+) -> Option<Decimal> {
+    let best_bid = book.bids.first()?.price;
+    let best_ask = book.asks.first()?.price;
+    if best_ask - best_bid < config.min_spread {
+        return None;
+    }
+    let ceiling = ((fair - config.edge_buffer) / config.tick).floor() * config.tick;
+    let price = (best_bid + config.tick).min(ceiling);
+    (price > best_bid && price < best_ask).then_some(price)
+}
+
+/// Cost of the shares held on `side` beyond those paired with the other side.
+fn unpaired_cost(inventory: Option<&MarketNetting>, side: Side) -> Decimal {
+    let Some(n) = inventory else {
+        return Decimal::ZERO;
+    };
+    let (shares, cost, other) = match side {
+        Side::Yes => (n.yes_shares, n.yes_cost, n.no_shares),
+        Side::No => (n.no_shares, n.no_cost, n.yes_shares),
+    };
+    if shares <= other || shares <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    (shares - other) * cost / shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::{BookLiquidity, PriceLevel};
+    use chrono::Utc;
+
+    fn book(bid: Decimal, ask: Decimal) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            token_id: "tok".to_string(),
+            bids: vec![PriceLevel {
+                price: bid,
+                size: dec!(100),
+            }],
+            asks: vec![PriceLevel {
+                price: ask,
+                size: dec!(100),
+            }],
+            spread: ask - bid,
+            midpoint: (ask + bid) / dec!(2),
+            implied_probability: (ask + bid) / dec!(2),
+            timestamp: Utc::now(),
+            liquidity: BookLiquidity::TwoSided,
+        }
+    }
+
+    fn inventory(yes_shares: Decimal, no_shares: Decimal) -> MarketNetting {
+        MarketNetting {
+            market_id: "m1".to_string(),
+            yes_shares,
+            yes_cost: yes_shares * dec!(0.45),
+            no_shares,
+            no_cost: no_shares * dec!(0.45),
+            trade_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_quotes_both_sides_inside_spread_below_fair() {
+        let config = MarketMakingConfig::default();
+        // YES 0.40/0.52, NO 0.44/0.56, fair YES 0.50
+        let quotes = quotes(
+            &config,
+            dec!(0.50),
+            &book(dec!(0.40), dec!(0.52)),
+            &book(dec!(0.44), dec!(0.56)),
+            None,
+        );
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].side, Side::Yes);
+        assert_eq!(quotes[0].price, dec!(0.41));
+        assert_eq!(quotes[0].shares, dec!(4.88));
+        assert_eq!(quotes[1].side, Side::No);
+        assert_eq!(quotes[1].price, dec!(0.45));
+        // Both filling buys a $1 pair for 0.86
+        assert!(quotes[0].price + quotes[1].price < Decimal::ONE);
+    }
+
+    #[test]
+    fn test_skips_narrow_spread_and_no_room_below_fair() {
+        let config = MarketMakingConfig::default();
+        // YES spread too narrow; NO bid already at fair value minus buffer
+        let quotes = quotes(
+            &config,
+            dec!(0.60),
+            &book(dec!(0.58), dec!(0.62)),
+            &book(dec!(0.38), dec!(0.50)),
+            None,
+        );
+        assert!(quotes.is_empty());
+    }
+
+    #[test]
+    fn test_inventory_limits_the_loaded_side() {
+        let config = MarketMakingConfig::default();
+        let yes_book = book(dec!(0.40), dec!(0.52));
+        let no_book = book(dec!(0.40), dec!(0.52));
+
+        // 20 unpaired YES shares at 0.45 leave $1 of the $10 inventory limit
+        let held = inventory(dec!(20), dec!(0));
+        let q = quotes(&config, dec!(0.50), &yes_book, &no_book, Some(&held));
+        assert_eq!(q[0].shares, dec!(2.44));
+        assert_eq!(q[1].shares, dec!(4.88));
+
+        // Paired shares don't count against either side
+        let held = inventory(dec!(30), dec!(30));
+        let q = quotes(&config, dec!(0.50), &yes_book, &no_book, Some(&held));
+        assert_eq!(q.len(), 2);
+
+        // Past max_inventory_usd the side isn't bid at all
+        let held = inventory(dec!(30), dec!(0));
+        let q = quotes(&config, dec!(0.50), &yes_book, &no_book, Some(&held));
+        assert_eq!(q.len(), 1);
+        assert_eq!(q[0].side, Side::No);
+    }
+}
@@ -0,0 +1,408 @@
+//! Near-resolution sniping.
+//!
+//! Markets whose outcome is already known — a game that has finished — often
+//! keep trading at 0.90–0.97 for hours until they resolve. Kelly sizing
+//! refuses prices that extreme, and the valuation engine rarely sees an edge
+//! worth its fee in them. This strategy buys the known outcome instead,
+//! under its own guards: the market must resolve within
+//! `max_hours_to_resolution`, the outcome's best ask must sit inside
+//! `[min_price, max_price]`, and a data source at least
+//! `min_data_confidence` sure must name the winner. Each snipe is capped at
+//! `max_position_usd` and the best ask's depth, and all snipes together at
+//! `max_exposure_usd`.
+//!
+//! Today the evidence is a completed ESPN game whose winner maps unambiguously
+//! onto the market's outcomes. Questions about spreads, totals, series or
+//! seasons are never sniped, since a final score alone doesn't settle them.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::data::DataPoint;
+use crate::market::models::{Market, MarketCategory, OrderBookSnapshot, Side};
+
+/// Bucket snipes are recorded under.
+pub const SNIPING_BUCKET: &str = "sniping";
+
+/// Question words that mean a final score doesn't settle the market.
+const UNSETTLED_BY_WINNER: &[&str] = &[
+    "spread",
+    "points",
+    "total",
+    "over",
+    "under",
+    "margin",
+    "by more",
+    "by at least",
+    "series",
+    "season",
+    "championship",
+    "finals",
+    "playoff",
+    "title",
+    "mvp",
+    "draw",
+    "tie",
+    "half",
+    "quarter",
+    "period",
+    "inning",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SnipingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Snipe only markets ending within this many hours. Markets past their
+    /// end date but not yet resolved qualify while still active.
+    #[serde(default = "default_max_hours_to_resolution")]
+    pub max_hours_to_resolution: Decimal,
+    /// Buy the known outcome only while its best ask is in this band.
+    #[serde(default = "default_min_price")]
+    pub min_price: Decimal,
+    #[serde(default = "default_max_price")]
+    pub max_price: Decimal,
+    /// Minimum confidence of the data point naming the outcome.
+    #[serde(default = "default_min_data_confidence")]
+    pub min_data_confidence: Decimal,
+    /// Largest single snipe in USD.
+    #[serde(default = "default_max_position_usd")]
+    pub max_position_usd: Decimal,
+    /// Open sniping exposure across all markets at most.
+    #[serde(default = "default_max_exposure_usd")]
+    pub max_exposure_usd: Decimal,
+    /// Markets sniped per cycle at most.
+    #[serde(default = "default_max_markets")]
+    pub max_markets: usize,
+}
+
+impl Default for SnipingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_hours_to_resolution: default_max_hours_to_resolution(),
+            min_price: default_min_price(),
+            max_price: default_max_price(),
+            min_data_confidence: default_min_data_confidence(),
+            max_position_usd: default_max_position_usd(),
+            max_exposure_usd: default_max_exposure_usd(),
+            max_markets: default_max_markets(),
+        }
+    }
+}
+
+impl SnipingConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.min_price <= Decimal::ZERO
+            || self.min_price > self.max_price
+            || self.max_price >= Decimal::ONE
+        {
+            anyhow::bail!(
+                "sniping price band [{}, {}] must satisfy 0 < min_price <= max_price < 1",
+                self.min_price,
+                self.max_price
+            );
+        }
+        Ok(())
+    }
+}
+
+fn default_max_hours_to_resolution() -> Decimal {
+    dec!(6)
+}
+
+fn default_min_price() -> Decimal {
+    dec!(0.90)
+}
+
+fn default_max_price() -> Decimal {
+    dec!(0.97)
+}
+
+fn default_min_data_confidence() -> Decimal {
+    dec!(0.8)
+}
+
+fn default_max_position_usd() -> Decimal {
+    dec!(10.0)
+}
+
+fn default_max_exposure_usd() -> Decimal {
+    dec!(30.0)
+}
+
+fn default_max_markets() -> usize {
+    3
+}
+
+/// A buy of the known outcome at its best ask.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snipe {
+    pub side: Side,
+    pub price: Decimal,
+    pub shares: Decimal,
+}
+
+/// The outcome of `market` that `data` shows to be settled, if every
+/// confident data point about it agrees.
+pub fn settled_side(market: &Market, data: &[DataPoint], min_confidence: Decimal) -> Option<Side> {
+    if market.category != MarketCategory::Sports {
+        return None;
+    }
+    let question = market.question.to_lowercase();
+    if UNSETTLED_BY_WINNER
+        .iter()
+        .any(|w| contains_word(&question, w))
+    {
+        return None;
+    }
+    let mut settled = None;
+    for point in data
+        .iter()
+        .filter(|p| p.confidence >= min_confidence && p.relevance_to.contains(&market.condition_id))
+    {
+        let Some(side) = winner_side(market, &question, &point.payload) else {
+            continue;
+        };
+        match settled {
+            Some(previous) if previous != side => return None,
+            _ => settled = Some(side),
+        }
+    }
+    settled
+}
+
+/// Which outcome a completed game's winner settles, if it maps onto the
+/// market at all.
+fn winner_side(market: &Market, question: &str, payload: &serde_json::Value) -> Option<Side> {
+    if payload["status"]["completed"].as_bool() != Some(true) {
+        return None;
+    }
+    let teams = payload["teams"].as_array()?;
+    let winner = teams.iter().find(|t| t["winner"].as_bool() == Some(true))?["name"].as_str()?;
+    let names: Vec<&str> = teams.iter().filter_map(|t| t["name"].as_str()).collect();
+
+    // Head-to-head markets name the teams as outcomes; the first is YES
+    let outcomes: Vec<String> = market.outcomes.iter().map(|o| o.to_lowercase()).collect();
+    if outcomes.len() == 2 && !outcomes.iter().any(|o| o == "yes" || o == "no") {
+        let winning = outcomes.iter().position(|o| team_matches(o, winner))?;
+        // Both outcomes must be teams from this game
+        if !outcomes
+            .iter()
+            .all(|o| names.iter().any(|n| team_matches(o, n)))
+        {
+            return None;
+        }
+        return Some(if winning == 0 { Side::Yes } else { Side::No });
+    }
+
+    // "Will the Lakers beat the Celtics?": the team named first is the subject
+    if !(contains_word(question, "win") || contains_word(question, "beat")) {
+        return None;
+    }
+    let subject = names
+        .iter()
+        .filter_map(|n| mention(question, n).map(|at| (at, *n)))
+        .min_by_key(|(at, _)| *at)?
+        .1;
+    Some(if subject == winner {
+        Side::Yes
+    } else {
+        Side::No
+    })
+}
+
+/// Whether `text` names `team`, by full name or its final word ("Lakers").
+fn team_matches(text: &str, team: &str) -> bool {
+    mention(text, team).is_some()
+}
+
+/// Position of the earliest mention of `team` in lowercase `text`.
+fn mention(text: &str, team: &str) -> Option<usize> {
+    let team = team.to_lowercase();
+    let nickname = team.split_whitespace().last().filter(|n| n.len() >= 4);
+    [Some(team.as_str()), nickname]
+        .into_iter()
+        .flatten()
+        .filter_map(|name| find_word(text, name))
+        .min()
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    find_word(text, word).is_some()
+}
+
+/// Byte offset of `word` in `text` where it isn't part of a longer word.
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric());
+    text.match_indices(word).map(|(at, _)| at).find(|&at| {
+        !is_word(text[..at].chars().next_back()) && !is_word(text[at + word.len()..].chars().next())
+    })
+}
+
+/// A buy of `side` in `market` from that outcome's `book`, spending at most
+/// `available_usd`, if the market is close enough to resolution and the
+/// best ask is inside the price band.
+pub fn snipe(
+    config: &SnipingConfig,
+    market: &Market,
+    side: Side,
+    book: &OrderBookSnapshot,
+    available_usd: Decimal,
+    now: DateTime<Utc>,
+) -> Option<Snipe> {
+    let hours_left = Decimal::from((market.end_date - now).num_minutes()) / dec!(60);
+    if !market.active || hours_left > config.max_hours_to_resolution {
+        return None;
+    }
+    let best = book.asks.iter().min_by_key(|l| l.price)?;
+    if best.price < config.min_price || best.price > config.max_price {
+        return None;
+    }
+    let budget = config.max_position_usd.min(available_usd);
+    let shares = (budget / best.price).min(best.size).round_dp(2);
+    (shares > Decimal::ZERO).then_some(Snipe {
+        side,
+        price: best.price,
+        shares,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::{BookLiquidity, PriceLevel};
+    use chrono::Duration;
+
+    fn market(question: &str, outcomes: [&str; 2], end_in_hours: i64) -> Market {
+        Market {
+            condition_id: "0xgame".to_string(),
+            question: question.to_string(),
+            outcomes: outcomes.iter().map(|o| o.to_string()).collect(),
+            tokens: Vec::new(),
+            end_date: Utc::now() + Duration::hours(end_in_hours),
+            category: MarketCategory::Sports,
+            volume_24h: dec!(50000),
+            active: true,
+            event_id: None,
+            event_slug: None,
+            neg_risk: false,
+            description: String::new(),
+            resolution_source: None,
+        }
+    }
+
+    fn game(completed: bool, lakers_won: bool, confidence: Decimal) -> DataPoint {
+        DataPoint {
+            source: "espn_NBA".to_string(),
+            category: MarketCategory::Sports,
+            timestamp: Utc::now(),
+            payload: serde_json::json!({
+                "teams": [
+                    {"name": "Los Angeles Lakers", "winner": lakers_won},
+                    {"name": "Boston Celtics", "winner": !lakers_won},
+                ],
+                "status": {"type": "Final", "completed": completed},
+            }),
+            confidence,
+            relevance_to: vec!["0xgame".to_string()],
+        }
+    }
+
+    fn book(asks: &[(Decimal, Decimal)]) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            token_id: "t".to_string(),
+            bids: Vec::new(),
+            asks: asks
+                .iter()
+                .map(|&(price, size)| PriceLevel { price, size })
+                .collect(),
+            spread: dec!(0.01),
+            midpoint: dec!(0.94),
+            implied_probability: dec!(0.94),
+            timestamp: Utc::now(),
+            liquidity: BookLiquidity::OneSided,
+        }
+    }
+
+    #[test]
+    fn test_settled_side_from_completed_game() {
+        let beat = market("Will the Lakers beat the Celtics?", ["Yes", "No"], 2);
+        let won = [game(true, true, dec!(0.85))];
+        let lost = [game(true, false, dec!(0.85))];
+        assert_eq!(settled_side(&beat, &won, dec!(0.8)), Some(Side::Yes));
+        assert_eq!(settled_side(&beat, &lost, dec!(0.8)), Some(Side::No));
+
+        // Outcomes named after teams: the first is YES
+        let h2h = market("Lakers vs. Celtics", ["Celtics", "Lakers"], 2);
+        assert_eq!(settled_side(&h2h, &won, dec!(0.8)), Some(Side::No));
+
+        // Still playing, unsure source, or disagreeing sources
+        assert_eq!(
+            settled_side(&beat, &[game(false, true, dec!(0.85))], dec!(0.8)),
+            None
+        );
+        assert_eq!(settled_side(&beat, &won, dec!(0.9)), None);
+        assert_eq!(
+            settled_side(
+                &beat,
+                &[game(true, true, dec!(0.85)), game(true, false, dec!(0.85))],
+                dec!(0.8)
+            ),
+            None
+        );
+
+        // A final score doesn't settle spreads or series
+        let spread = market("Will the Lakers win by more than 5.5?", ["Yes", "No"], 2);
+        assert_eq!(settled_side(&spread, &won, dec!(0.8)), None);
+        let series = market("Will the Lakers win the series?", ["Yes", "No"], 2);
+        assert_eq!(settled_side(&series, &won, dec!(0.8)), None);
+    }
+
+    #[test]
+    fn test_snipe_guards() {
+        let config = SnipingConfig::default();
+        let soon = market("Will the Lakers beat the Celtics?", ["Yes", "No"], 2);
+        let now = Utc::now();
+
+        // $10 cap at 0.95 = 10.53 shares, within the 50 on offer
+        let hit = snipe(
+            &config,
+            &soon,
+            Side::Yes,
+            &book(&[(dec!(0.95), dec!(50))]),
+            dec!(100),
+            now,
+        )
+        .unwrap();
+        assert_eq!(hit.price, dec!(0.95));
+        assert_eq!(hit.shares, dec!(10.53));
+
+        // Capped by depth at the best ask and by the remaining budget
+        let thin = book(&[(dec!(0.96), dec!(4)), (dec!(0.99), dec!(100))]);
+        let hit = snipe(&config, &soon, Side::Yes, &thin, dec!(100), now).unwrap();
+        assert_eq!(hit.shares, dec!(4));
+        let hit = snipe(
+            &config,
+            &soon,
+            Side::Yes,
+            &book(&[(dec!(0.95), dec!(50))]),
+            dec!(1.9),
+            now,
+        )
+        .unwrap();
+        assert_eq!(hit.shares, dec!(2));
+
+        // Outside the price band or too far from resolution
+        for price in [dec!(0.85), dec!(0.98)] {
+            let book = book(&[(price, dec!(50))]);
+            assert!(snipe(&config, &soon, Side::Yes, &book, dec!(100), now).is_none());
+        }
+        let later = market("Will the Lakers beat the Celtics?", ["Yes", "No"], 30);
+        let book = book(&[(dec!(0.95), dec!(50))]);
+        assert!(snipe(&config, &later, Side::Yes, &book, dec!(100), now).is_none());
+        assert!(snipe(&config, &soon, Side::Yes, &book, Decimal::ZERO, now).is_none());
+    }
+}
@@ -0,0 +1,242 @@
+//! Value-at-Risk for the open portfolio.
+//!
+//! Simulates correlated binary outcomes of open positions with a one-factor
+//! Gaussian copula, using fair probabilities, to estimate VaR and expected
+//! shortfall. Gross exposure hides how much could be lost at once when
+//! positions are correlated; this shows the tail.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+
+use crate::db::store::TradeRecord;
+use crate::rng::SplitMix64;
+
+/// Confidence level for reported VaR.
+pub const DEFAULT_CONFIDENCE: Decimal = dec!(0.95);
+/// Number of Monte Carlo scenarios.
+pub const DEFAULT_SIMULATIONS: usize = 10_000;
+/// Pairwise outcome correlation through the common factor.
+pub const DEFAULT_CORRELATION: f64 = 0.3;
+/// Fixed seed so repeated reports on the same book agree.
+const DEFAULT_SEED: u64 = 0x5EED_1234_ABCD_0042;
+
+/// A binary position for simulation.
+#[derive(Debug, Clone)]
+pub struct VarPosition {
+    /// USD paid for the position (lost entirely if it resolves against us).
+    pub stake: Decimal,
+    /// USD received if it resolves in our favour (one dollar per share).
+    pub payout: Decimal,
+    /// Probability the position wins, from our fair value.
+    pub win_probability: Decimal,
+}
+
+/// Tail-risk estimate for the open portfolio. Losses are positive amounts.
+#[derive(Debug, Clone, Serialize)]
+pub struct VarReport {
+    pub confidence: Decimal,
+    pub simulations: usize,
+    pub value_at_risk: Decimal,
+    pub expected_shortfall: Decimal,
+    pub expected_pnl: Decimal,
+    pub max_loss: Decimal,
+}
+
+/// Build simulation inputs from open trades.
+///
+/// `claude_fair_value` is the YES probability, so NO positions win with the
+/// complement. Trades with an unknown direction are skipped.
+pub fn positions_from_trades(trades: &[TradeRecord]) -> Vec<VarPosition> {
+    trades
+        .iter()
+        .filter_map(|t| {
+            let (price, shares, fair_yes) = (t.entry_price, t.size, t.claude_fair_value);
+            let win_probability = match t.direction.as_str() {
+                "YES" => fair_yes,
+                "NO" => Decimal::ONE - fair_yes,
+                _ => return None,
+            };
+            Some(VarPosition {
+                stake: price * shares,
+                payout: shares,
+                win_probability: win_probability.clamp(Decimal::ZERO, Decimal::ONE),
+            })
+        })
+        .collect()
+}
+
+/// Estimate VaR and expected shortfall with default parameters.
+pub fn portfolio_var(positions: &[VarPosition]) -> Option<VarReport> {
+    simulate(
+        positions,
+        DEFAULT_CORRELATION,
+        DEFAULT_CONFIDENCE,
+        DEFAULT_SIMULATIONS,
+        DEFAULT_SEED,
+    )
+}
+
+/// Monte Carlo VaR over correlated binary outcomes.
+///
+/// Each position wins when `Φ(√ρ·M + √(1-ρ)·εᵢ) < pᵢ`, where `M` is a market
+/// factor shared by all positions. Returns `None` with no positions.
+pub fn simulate(
+    positions: &[VarPosition],
+    correlation: f64,
+    confidence: Decimal,
+    simulations: usize,
+    seed: u64,
+) -> Option<VarReport> {
+    if positions.is_empty() || simulations == 0 {
+        return None;
+    }
+
+    let rho = correlation.clamp(0.0, 0.999);
+    let (load_common, load_idio) = (rho.sqrt(), (1.0 - rho).sqrt());
+    let probs: Vec<f64> = positions
+        .iter()
+        .map(|p| p.win_probability.to_f64().unwrap_or(0.0))
+        .collect();
+
+    let mut rng = SplitMix64::new(seed);
+    let mut pnls: Vec<Decimal> = Vec::with_capacity(simulations);
+
+    for _ in 0..simulations {
+        let market = rng.next_normal();
+        let mut pnl = Decimal::ZERO;
+        for (pos, &p) in positions.iter().zip(&probs) {
+            let latent = load_common * market + load_idio * rng.next_normal();
+            if normal_cdf(latent) < p {
+                pnl += pos.payout - pos.stake;
+            } else {
+                pnl -= pos.stake;
+            }
+        }
+        pnls.push(pnl);
+    }
+
+    pnls.sort();
+
+    let tail_fraction = (Decimal::ONE - confidence).clamp(Decimal::ZERO, Decimal::ONE);
+    let tail_count = (tail_fraction * Decimal::from(simulations))
+        .floor()
+        .to_usize()
+        .unwrap_or(0)
+        .max(1)
+        .min(simulations);
+
+    let quantile = pnls[tail_count - 1];
+    let tail_sum: Decimal = pnls[..tail_count].iter().sum();
+    let tail_mean = tail_sum / Decimal::from(tail_count);
+    let total: Decimal = pnls.iter().sum();
+
+    Some(VarReport {
+        confidence,
+        simulations,
+        value_at_risk: (-quantile).max(Decimal::ZERO),
+        expected_shortfall: (-tail_mean).max(Decimal::ZERO),
+        expected_pnl: total / Decimal::from(simulations),
+        max_loss: (-pnls[0]).max(Decimal::ZERO),
+    })
+}
+
+/// Standard normal CDF via the Abramowitz–Stegun erf approximation (|ε| < 1.5e-7).
+fn normal_cdf(x: f64) -> f64 {
+    let z = x / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * z.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    let erf = if z >= 0.0 { erf } else { -erf };
+    0.5 * (1.0 + erf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(stake: Decimal, payout: Decimal, p: Decimal) -> VarPosition {
+        VarPosition {
+            stake,
+            payout,
+            win_probability: p,
+        }
+    }
+
+    #[test]
+    fn test_empty_portfolio() {
+        assert!(portfolio_var(&[]).is_none());
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-6);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-3);
+        assert!((normal_cdf(-1.96) - 0.025).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_certain_loss() {
+        // A position that can never win loses its full stake in every scenario
+        let report = portfolio_var(&[position(dec!(5), dec!(10), Decimal::ZERO)]).unwrap();
+        assert_eq!(report.value_at_risk, dec!(5));
+        assert_eq!(report.expected_shortfall, dec!(5));
+        assert_eq!(report.max_loss, dec!(5));
+    }
+
+    #[test]
+    fn test_single_coin_flip() {
+        // 50/50 position: 5% tail is always the full $5 loss
+        let report = portfolio_var(&[position(dec!(5), dec!(10), dec!(0.5))]).unwrap();
+        assert_eq!(report.value_at_risk, dec!(5));
+        assert!((report.expected_pnl).abs() < dec!(0.3));
+    }
+
+    #[test]
+    fn test_correlation_fattens_tail() {
+        let book: Vec<VarPosition> = (0..20)
+            .map(|_| position(dec!(5), dec!(10), dec!(0.5)))
+            .collect();
+        let independent = simulate(&book, 0.0, dec!(0.95), 5_000, 7).unwrap();
+        let correlated = simulate(&book, 0.8, dec!(0.95), 5_000, 7).unwrap();
+        assert!(correlated.value_at_risk > independent.value_at_risk);
+        assert!(correlated.expected_shortfall >= correlated.value_at_risk);
+    }
+
+    #[test]
+    fn test_positions_from_trades() {
+        let trade = TradeRecord {
+            id: Some(1),
+            cycle: 1,
+            market_id: "m1".to_string(),
+            market_question: None,
+            direction: "NO".to_string(),
+            entry_price: dec!(0.40),
+            size: dec!(10),
+            edge_at_entry: dec!(0.10),
+            claude_fair_value: dec!(0.30),
+            confidence: dec!(0.8),
+            kelly_raw: dec!(0.1),
+            kelly_adjusted: dec!(0.05),
+            status: "OPEN".to_string(),
+            pnl: None,
+            created_at: None,
+            resolved_at: None,
+            bucket: "directional".to_string(),
+            fee: Decimal::ZERO,
+            order_id: None,
+            reconciled_at: None,
+            tags: Vec::new(),
+            notes: None,
+        };
+        let positions = positions_from_trades(&[trade]);
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].stake, dec!(4));
+        assert_eq!(positions[0].payout, dec!(10));
+        assert_eq!(positions[0].win_probability, dec!(0.70));
+    }
+}
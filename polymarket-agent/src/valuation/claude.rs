@@ -2,12 +2,15 @@
 //!
 //! Sends structured prompts to Claude and tracks every API call cost.
 
+use std::sync::Arc;
+
 use anyhow::{bail, Context, Result};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use tracing::{info, instrument, warn};
 
+use crate::chaos::{ChaosInjector, ChaosTarget};
 use crate::db::store::{ApiCostRecord, Store};
 
 /// Claude API pricing (per token, as of 2025 for claude-sonnet-4-20250514).
@@ -20,6 +23,7 @@ pub struct ClaudeClient {
     api_key: String,
     model: String,
     store: Store,
+    chaos: Arc<ChaosInjector>,
 }
 
 impl ClaudeClient {
@@ -34,9 +38,16 @@ impl ClaudeClient {
             api_key,
             model,
             store,
+            chaos: Arc::new(ChaosInjector::disabled()),
         }
     }
 
+    /// Route calls through a fault injector (chaos testing).
+    pub fn with_chaos(mut self, chaos: Arc<ChaosInjector>) -> Self {
+        self.chaos = chaos;
+        self
+    }
+
     /// Send a message to Claude and return the parsed response with cost tracking.
     #[instrument(skip(self, system_prompt, user_prompt))]
     pub async fn complete(
@@ -55,6 +66,8 @@ impl ClaudeClient {
             }],
         };
 
+        self.chaos.inject(ChaosTarget::Claude).await?;
+
         let response = self
             .client
             .post("https://api.anthropic.com/v1/messages")
@@ -72,10 +85,13 @@ impl ClaudeClient {
             bail!("Claude API error ({}): {}", status, error_body);
         }
 
-        let api_response: ClaudeApiResponse = response
-            .json()
+        let body = response
+            .text()
             .await
-            .context("Failed to parse Claude API response")?;
+            .context("Failed to read Claude API response")?;
+        let body = self.chaos.malform(ChaosTarget::Claude, body).await;
+        let api_response: ClaudeApiResponse =
+            serde_json::from_str(&body).context("Failed to parse Claude API response")?;
 
         // Extract text content
         let text = api_response
@@ -130,7 +146,7 @@ impl ClaudeClient {
             endpoint: Some("messages".to_string()),
             input_tokens: Some(input_tokens),
             output_tokens: Some(output_tokens),
-            cost: cost.to_string(),
+            cost,
             cycle,
             created_at: None,
         };
@@ -0,0 +1,263 @@
+//! Cross-market consistency of a cycle's valuations.
+//!
+//! Each market is valued on its own, so nothing stops Claude from putting
+//! "BTC above $100k" above "BTC above $90k", or giving the candidates of one
+//! election a combined 130%. Before any of them is traded, the cycle's
+//! valuations are checked against two constraints:
+//!
+//! - **Ladders**: questions identical but for their dollar strike ("Will BTC
+//!   be above $X on June 30?") must be monotonic in the strike — falling as
+//!   it rises for "above"/"reach" questions, rising for "below"/"dip to".
+//! - **Exclusive outcomes**: the markets of one negative-risk event resolve
+//!   YES at most once between them, so their probabilities sum to at most 1.
+//!
+//! Both allow `tolerance` of slack. Markets in a violation are re-valued
+//! once when `requery` is set; those still inconsistent are not traded and
+//! their cached valuations are dropped.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::market::models::Market;
+use crate::valuation::crypto_pricer::{self, Threshold};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConsistencyConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Slack allowed on each constraint before it counts as violated.
+    #[serde(default = "default_tolerance")]
+    pub tolerance: Decimal,
+    /// Re-value violating markets once before rejecting them.
+    #[serde(default = "default_requery")]
+    pub requery: bool,
+}
+
+impl Default for ConsistencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            tolerance: default_tolerance(),
+            requery: default_requery(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_tolerance() -> Decimal {
+    dec!(0.02)
+}
+
+fn default_requery() -> bool {
+    true
+}
+
+/// A broken constraint between valued markets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// `looser` should be at least as likely as `stricter`, but is valued
+    /// below it.
+    Ladder {
+        looser: String,
+        stricter: String,
+        looser_probability: Decimal,
+        stricter_probability: Decimal,
+    },
+    /// Mutually exclusive markets whose probabilities add up past 1.
+    ExclusiveSum {
+        event_id: String,
+        condition_ids: Vec<String>,
+        total: Decimal,
+    },
+}
+
+impl Violation {
+    pub fn condition_ids(&self) -> Vec<&str> {
+        match self {
+            Violation::Ladder {
+                looser, stricter, ..
+            } => vec![looser.as_str(), stricter.as_str()],
+            Violation::ExclusiveSum { condition_ids, .. } => {
+                condition_ids.iter().map(String::as_str).collect()
+            }
+        }
+    }
+}
+
+/// Constraints broken by `valued`, each market with its YES probability.
+pub fn check(valued: &[(&Market, Decimal)], tolerance: Decimal) -> Vec<Violation> {
+    let mut violations = ladder_violations(valued, tolerance);
+    violations.extend(exclusive_violations(valued, tolerance));
+    violations
+}
+
+/// Condition ids of every market in `violations`.
+pub fn violating_ids(violations: &[Violation]) -> BTreeSet<String> {
+    violations
+        .iter()
+        .flat_map(|v| v.condition_ids())
+        .map(str::to_string)
+        .collect()
+}
+
+fn ladder_violations(valued: &[(&Market, Decimal)], tolerance: Decimal) -> Vec<Violation> {
+    // Ladder rungs by question template, e.g. "will btc be above $? on june 30?"
+    let mut ladders: BTreeMap<String, Vec<(f64, &Market, Decimal)>> = BTreeMap::new();
+    for &(market, probability) in valued {
+        let question = market.question.to_lowercase();
+        let (Some(strike), Some(_)) = (
+            crypto_pricer::parse_strike(&question),
+            crypto_pricer::parse_threshold(&question),
+        ) else {
+            continue;
+        };
+        let mut template = question.clone();
+        for (span, _) in crypto_pricer::dollar_amounts(&question).into_iter().rev() {
+            template.replace_range(span, "$?");
+        }
+        ladders
+            .entry(template)
+            .or_default()
+            .push((strike, market, probability));
+    }
+
+    let mut violations = Vec::new();
+    for (template, mut rungs) in ladders {
+        rungs.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let falls_with_strike = matches!(
+            crypto_pricer::parse_threshold(&template),
+            Some(Threshold::FinishAbove | Threshold::TouchAbove)
+        );
+        for (i, low) in rungs.iter().enumerate() {
+            for high in rungs.iter().skip(i + 1).filter(|high| high.0 > low.0) {
+                // The lower strike is the easier bar for "above", the harder for "below"
+                let (looser, stricter) = if falls_with_strike {
+                    (low, high)
+                } else {
+                    (high, low)
+                };
+                if stricter.2 > looser.2 + tolerance {
+                    violations.push(Violation::Ladder {
+                        looser: looser.1.condition_id.clone(),
+                        stricter: stricter.1.condition_id.clone(),
+                        looser_probability: looser.2,
+                        stricter_probability: stricter.2,
+                    });
+                }
+            }
+        }
+    }
+    violations
+}
+
+fn exclusive_violations(valued: &[(&Market, Decimal)], tolerance: Decimal) -> Vec<Violation> {
+    let mut events: BTreeMap<&str, Vec<(&Market, Decimal)>> = BTreeMap::new();
+    for &(market, probability) in valued {
+        if let (true, Some(event_id)) = (market.neg_risk, market.event_id.as_deref()) {
+            events
+                .entry(event_id)
+                .or_default()
+                .push((market, probability));
+        }
+    }
+    events
+        .into_iter()
+        .filter(|(_, markets)| markets.len() > 1)
+        .filter_map(|(event_id, markets)| {
+            let total: Decimal = markets.iter().map(|(_, p)| *p).sum();
+            (total > Decimal::ONE + tolerance).then(|| Violation::ExclusiveSum {
+                event_id: event_id.to_string(),
+                condition_ids: markets
+                    .iter()
+                    .map(|(m, _)| m.condition_id.clone())
+                    .collect(),
+                total,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+    use chrono::Utc;
+
+    fn market(id: &str, question: &str, event: Option<&str>) -> Market {
+        Market {
+            condition_id: id.to_string(),
+            question: question.to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            tokens: Vec::new(),
+            end_date: Utc::now(),
+            category: MarketCategory::Crypto,
+            volume_24h: dec!(10000),
+            active: true,
+            event_id: event.map(str::to_string),
+            event_slug: None,
+            neg_risk: event.is_some(),
+            description: String::new(),
+            resolution_source: None,
+        }
+    }
+
+    #[test]
+    fn test_ladders_must_be_monotonic_and_exclusive_outcomes_sum_to_one() {
+        let above_90 = market("a90", "Will BTC be above $90,000 on June 30?", None);
+        let above_100 = market("a100", "Will BTC be above $100k on June 30?", None);
+        let above_110 = market("a110", "Will BTC be above $110,000 on June 30?", None);
+        let below_80 = market("b80", "Will BTC be below $80,000 on June 30?", None);
+        let below_70 = market("b70", "Will BTC be below $70,000 on June 30?", None);
+        // A different date is a different ladder
+        let later = market("later", "Will BTC be above $100,000 on Dec 31?", None);
+
+        let valued = [
+            (&above_90, dec!(0.60)),
+            (&above_100, dec!(0.70)),
+            (&above_110, dec!(0.61)),
+            (&below_80, dec!(0.20)),
+            (&below_70, dec!(0.21)),
+            (&later, dec!(0.90)),
+        ];
+        let violations = check(&valued, dec!(0.02));
+        assert_eq!(
+            violations,
+            [Violation::Ladder {
+                looser: "a90".to_string(),
+                stricter: "a100".to_string(),
+                looser_probability: dec!(0.60),
+                stricter_probability: dec!(0.70),
+            }]
+        );
+        // 0.61 vs 0.60 and 0.21 vs 0.20 are within tolerance
+        assert_eq!(
+            violating_ids(&violations),
+            BTreeSet::from(["a90".to_string(), "a100".to_string()])
+        );
+
+        let alice = market("alice", "Will Alice win?", Some("election"));
+        let bob = market("bob", "Will Bob win?", Some("election"));
+        let carol = market("carol", "Will Carol win?", Some("other"));
+        let coherent = [
+            (&alice, dec!(0.55)),
+            (&bob, dec!(0.46)),
+            (&carol, dec!(0.9)),
+        ];
+        assert!(check(&coherent, dec!(0.02)).is_empty());
+        let incoherent = [(&alice, dec!(0.6)), (&bob, dec!(0.5))];
+        assert_eq!(
+            check(&incoherent, dec!(0.02)),
+            [Violation::ExclusiveSum {
+                event_id: "election".to_string(),
+                condition_ids: vec!["alice".to_string(), "bob".to_string()],
+                total: dec!(1.1),
+            }]
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Analytic pricer for crypto price-threshold markets.
+//!
+//! "Will Bitcoin be above $100,000 on December 31?" has a textbook answer:
+//! under a driftless lognormal model the chance the spot `S` finishes above
+//! the strike `K` after `T` years at annual volatility `σ` is
+//!
+//! `P(S_T > K) = Φ((ln(S/K) − σ²T/2) / (σ√T))`
+//!
+//! Questions asking whether the price will *reach* (or *dip to*) a level at
+//! any point before resolution are priced as barrier touches, roughly twice
+//! the chance of finishing beyond it. The spot comes from the CoinGecko data
+//! point for the coin named in the question and `T` runs to the market's end
+//! date. Volatility is the operator's implied figure for the coin when
+//! `implied_vol` has one, else the Parkinson estimate from the 24h high and
+//! low, clamped to `[min_vol, max_vol]`.
+//!
+//! `mode` decides what the price is used for: `standalone` values the market
+//! without calling Claude, `prior` shows it to Claude in the prompt, and
+//! `veto` discards Claude valuations that stray more than `max_divergence`
+//! from it.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::data::DataPoint;
+use crate::valuation::fair_value::{DataQuality, TimeSensitivity, ValuationResult};
+
+const SECONDS_PER_YEAR: f64 = 365.25 * 86_400.0;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CryptoPricerMode {
+    #[default]
+    Off,
+    /// Value matching markets analytically; Claude is never called for them.
+    Standalone,
+    /// Add the analytic price to Claude's prompt.
+    Prior,
+    /// Discard Claude valuations too far from the analytic price.
+    Veto,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CryptoPricerConfig {
+    #[serde(default)]
+    pub mode: CryptoPricerMode,
+    /// Largest gap between Claude's and the analytic probability that
+    /// `veto` mode lets through.
+    #[serde(default = "default_max_divergence")]
+    pub max_divergence: Decimal,
+    /// Annualized implied volatility by coin symbol (e.g. `btc = 0.55`),
+    /// used in place of the realized estimate.
+    #[serde(default)]
+    pub implied_vol: HashMap<String, f64>,
+    #[serde(default = "default_min_vol")]
+    pub min_vol: f64,
+    #[serde(default = "default_max_vol")]
+    pub max_vol: f64,
+    /// Confidence reported for standalone valuations.
+    #[serde(default = "default_confidence")]
+    pub confidence: Decimal,
+}
+
+impl Default for CryptoPricerConfig {
+    fn default() -> Self {
+        Self {
+            mode: CryptoPricerMode::Off,
+            max_divergence: default_max_divergence(),
+            implied_vol: HashMap::new(),
+            min_vol: default_min_vol(),
+            max_vol: default_max_vol(),
+            confidence: default_confidence(),
+        }
+    }
+}
+
+impl CryptoPricerConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.min_vol <= 0.0 || self.max_vol < self.min_vol {
+            anyhow::bail!("valuation.crypto_pricer needs 0 < min_vol <= max_vol");
+        }
+        if self
+            .implied_vol
+            .values()
+            .any(|v| !v.is_finite() || *v <= 0.0)
+        {
+            anyhow::bail!("valuation.crypto_pricer.implied_vol values must be positive");
+        }
+        if self.max_divergence <= Decimal::ZERO || self.max_divergence > Decimal::ONE {
+            anyhow::bail!("valuation.crypto_pricer.max_divergence must be in (0, 1]");
+        }
+        if self.confidence < Decimal::ZERO || self.confidence > Decimal::ONE {
+            anyhow::bail!("valuation.crypto_pricer.confidence must be between 0 and 1");
+        }
+        Ok(())
+    }
+}
+
+fn default_max_divergence() -> Decimal {
+    dec!(0.15)
+}
+
+fn default_min_vol() -> f64 {
+    0.2
+}
+
+fn default_max_vol() -> f64 {
+    2.0
+}
+
+fn default_confidence() -> Decimal {
+    dec!(0.7)
+}
+
+/// What a question asks of the price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Threshold {
+    /// Above the strike when the market resolves.
+    FinishAbove,
+    FinishBelow,
+    /// Trades at or above the strike at any point before resolution.
+    TouchAbove,
+    TouchBelow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolSource {
+    Implied,
+    Realized,
+}
+
+/// The analytic YES probability of one market.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticPrice {
+    pub probability: Decimal,
+    pub symbol: String,
+    pub spot: f64,
+    pub strike: f64,
+    pub threshold: Threshold,
+    /// Annualized volatility the price was computed at.
+    pub vol: f64,
+    pub vol_source: VolSource,
+    pub years: f64,
+}
+
+impl AnalyticPrice {
+    /// One line for the prompt and the valuation's reasoning.
+    pub fn summary(&self) -> String {
+        let condition = match self.threshold {
+            Threshold::FinishAbove => "finishing above",
+            Threshold::FinishBelow => "finishing below",
+            Threshold::TouchAbove => "touching",
+            Threshold::TouchBelow => "dipping to",
+        };
+        let vol_source = match self.vol_source {
+            VolSource::Implied => "implied",
+            VolSource::Realized => "24h realized",
+        };
+        format!(
+            "Lognormal model: {symbol} at ${spot} has a {pct:.1}% chance of {condition} ${strike} \
+             in {days:.1} days at {vol:.0}% {vol_source} annual volatility",
+            symbol = self.symbol.to_uppercase(),
+            spot = self.spot,
+            pct = self.probability * dec!(100),
+            strike = self.strike,
+            days = self.years * 365.25,
+            vol = self.vol * 100.0,
+        )
+    }
+
+    /// A valuation standing in for Claude's. Data quality is reassessed by
+    /// the engine.
+    pub fn to_valuation(&self, config: &CryptoPricerConfig) -> ValuationResult {
+        let days = self.years * 365.25;
+        ValuationResult {
+            probability: self.probability,
+            confidence: config.confidence,
+            reasoning_summary: self.summary(),
+            key_factors: vec![
+                format!("spot ${}", self.spot),
+                format!("strike ${}", self.strike),
+                format!("volatility {:.0}%", self.vol * 100.0),
+            ],
+            data_quality: DataQuality::High,
+            time_sensitivity: if days < 2.0 {
+                TimeSensitivity::Hours
+            } else if days < 14.0 {
+                TimeSensitivity::Days
+            } else {
+                TimeSensitivity::Weeks
+            },
+        }
+    }
+
+    /// Whether `probability` strays further than `max_divergence` from
+    /// this price.
+    pub fn diverges(&self, probability: Decimal, max_divergence: Decimal) -> bool {
+        (probability - self.probability).abs() > max_divergence
+    }
+}
+
+/// Price `question`, resolving at `end_date`, from the CoinGecko point for
+/// the coin it names. `None` when the question isn't a single-strike price
+/// threshold or no spot price is available.
+pub fn price<'a>(
+    question: &str,
+    end_date: DateTime<Utc>,
+    data_points: &'a [DataPoint],
+    config: &CryptoPricerConfig,
+    now: DateTime<Utc>,
+) -> Option<(&'a DataPoint, AnalyticPrice)> {
+    let question = question.to_lowercase();
+    let threshold = parse_threshold(&question)?;
+    let strike = parse_strike(&question)?;
+    let point = data_points
+        .iter()
+        .find(|dp| dp.source == "coingecko" && names_coin(&question, &dp.payload))?;
+    let payload = &point.payload;
+    let symbol = payload.get("symbol")?.as_str()?.to_lowercase();
+    let spot = number(payload, "current_price").filter(|s| *s > 0.0)?;
+
+    let years = (end_date - now).num_seconds() as f64 / SECONDS_PER_YEAR;
+    if years <= 0.0 {
+        return None;
+    }
+    let (vol, vol_source) = match config.implied_vol.get(&symbol) {
+        Some(vol) => (*vol, VolSource::Implied),
+        None => {
+            let high = number(payload, "high_24h")?;
+            let low = number(payload, "low_24h").filter(|l| *l > 0.0)?;
+            (parkinson_vol(high, low), VolSource::Realized)
+        }
+    };
+    let vol = vol.clamp(config.min_vol, config.max_vol);
+
+    let probability = probability(threshold, spot, strike, vol, years);
+    Some((
+        point,
+        AnalyticPrice {
+            probability: Decimal::from_f64(probability)?.round_dp(4),
+            symbol,
+            spot,
+            strike,
+            threshold,
+            vol,
+            vol_source,
+            years,
+        },
+    ))
+}
+
+fn probability(threshold: Threshold, spot: f64, strike: f64, vol: f64, years: f64) -> f64 {
+    let d2 = ((spot / strike).ln() - vol * vol * years / 2.0) / (vol * years.sqrt());
+    let above = normal_cdf(d2);
+    match threshold {
+        Threshold::FinishAbove => above,
+        Threshold::FinishBelow => 1.0 - above,
+        Threshold::TouchAbove if spot >= strike => 1.0,
+        Threshold::TouchAbove => (2.0 * above).min(1.0),
+        Threshold::TouchBelow if spot <= strike => 1.0,
+        Threshold::TouchBelow => (2.0 * (1.0 - above)).min(1.0),
+    }
+}
+
+/// Annualized volatility from one day's high and low (Parkinson).
+fn parkinson_vol(high: f64, low: f64) -> f64 {
+    let daily = (high / low).ln().abs() / (4.0 * 2f64.ln()).sqrt();
+    daily * 365f64.sqrt()
+}
+
+/// Standard normal CDF (Abramowitz & Stegun 26.2.17, error < 7.5e-8).
+fn normal_cdf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.231_641_9 * x.abs());
+    let poly = t
+        * (0.319_381_530
+            + t * (-0.356_563_782
+                + t * (1.781_477_937 + t * (-1.821_255_978 + t * 1.330_274_429))));
+    let tail = (-x * x / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt() * poly;
+    if x >= 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// What a lowercased question asks of the price, if it names exactly one
+/// direction.
+pub(crate) fn parse_threshold(question: &str) -> Option<Threshold> {
+    let has = |words: &[&str]| words.iter().any(|w| contains_word(question, w));
+    let touch_above = has(&["reach", "hit", "touch"]);
+    let touch_below = has(&["dip to", "fall to", "drop to", "crash to"]);
+    let above = has(&["above", "over", "higher than", "greater than", "exceed"]);
+    let below = has(&["below", "under", "lower than", "less than"]);
+    match (touch_above, touch_below, above, below) {
+        (false, true, _, false) => Some(Threshold::TouchBelow),
+        (true, false, false, false) => Some(Threshold::TouchAbove),
+        (false, false, true, false) => Some(Threshold::FinishAbove),
+        (false, false, false, true) => Some(Threshold::FinishBelow),
+        _ => None,
+    }
+}
+
+/// The one dollar amount in a lowercased question ("$100,000", "$150k",
+/// "$0.25"). Questions with several (ranges) aren't single-strike.
+pub(crate) fn parse_strike(question: &str) -> Option<f64> {
+    let amounts = dollar_amounts(question);
+    match amounts.as_slice() {
+        [(_, strike)] if *strike > 0.0 => Some(*strike),
+        _ => None,
+    }
+}
+
+/// Every dollar amount in a lowercased question, with the byte range it
+/// spans (sign and suffix included).
+pub(crate) fn dollar_amounts(question: &str) -> Vec<(Range<usize>, f64)> {
+    question
+        .match_indices('$')
+        .filter_map(|(at, _)| {
+            let rest = &question[at + 1..];
+            let digits: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == ',' || *c == '.')
+                .collect();
+            let value: f64 = digits.trim_end_matches('.').replace(',', "").parse().ok()?;
+            let multiplier = match rest[digits.len()..].chars().next() {
+                Some('k') => 1e3,
+                Some('m') => 1e6,
+                Some('b') => 1e9,
+                _ => 1.0,
+            };
+            let suffix = usize::from(multiplier > 1.0);
+            let end = at + 1 + digits.trim_end_matches('.').len() + suffix;
+            Some((at..end, value * multiplier))
+        })
+        .collect()
+}
+
+/// Whether `question` names the coin in a CoinGecko payload by id, name
+/// or symbol.
+fn names_coin(question: &str, payload: &serde_json::Value) -> bool {
+    ["coin_id", "name", "symbol"]
+        .iter()
+        .filter_map(|key| payload.get(key)?.as_str())
+        .any(|name| contains_word(question, &name.to_lowercase()))
+}
+
+fn number(payload: &serde_json::Value, key: &str) -> Option<f64> {
+    match payload.get(key)? {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.parse::<Decimal>().ok()?.to_f64(),
+        _ => None,
+    }
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric());
+    !word.is_empty()
+        && text.match_indices(word).any(|(at, _)| {
+            !is_word(text[..at].chars().next_back())
+                && !is_word(text[at + word.len()..].chars().next())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+
+    fn coingecko(symbol: &str, name: &str, spot: &str, high: &str, low: &str) -> DataPoint {
+        DataPoint {
+            source: "coingecko".to_string(),
+            category: MarketCategory::Crypto,
+            timestamp: Utc::now(),
+            payload: serde_json::json!({
+                "coin_id": name.to_lowercase(),
+                "symbol": symbol,
+                "name": name,
+                "current_price": spot,
+                "high_24h": high,
+                "low_24h": low,
+            }),
+            confidence: dec!(0.95),
+            relevance_to: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_prices_threshold_questions_from_spot_and_volatility() {
+        let now = Utc::now();
+        let in_30_days = now + chrono::Duration::days(30);
+        let points = [
+            coingecko("eth", "Ethereum", "3000", "3100", "2900"),
+            coingecko("btc", "Bitcoin", "100000", "102000", "98000"),
+        ];
+        let config = CryptoPricerConfig {
+            implied_vol: HashMap::from([("btc".to_string(), 0.5)]),
+            ..CryptoPricerConfig::default()
+        };
+
+        let (point, at_the_money) = price(
+            "Will Bitcoin be above $100,000 on November 16?",
+            in_30_days,
+            &points,
+            &config,
+            now,
+        )
+        .unwrap();
+        assert_eq!(point.payload["symbol"], "btc");
+        assert_eq!(at_the_money.vol_source, VolSource::Implied);
+        // Slightly under a coin flip: the lognormal median sits below spot
+        assert!(
+            at_the_money.probability > dec!(0.47) && at_the_money.probability < dec!(0.5),
+            "{}",
+            at_the_money.probability
+        );
+
+        let (_, far) = price(
+            "Will BTC be above $150k on Nov 16?",
+            in_30_days,
+            &points,
+            &config,
+            now,
+        )
+        .unwrap();
+        assert!(far.probability < dec!(0.01), "{}", far.probability);
+
+        // Touching a level is about twice as likely as finishing past it
+        let (_, finish) = price(
+            "Will ETH be below $2,700?",
+            in_30_days,
+            &points,
+            &config,
+            now,
+        )
+        .unwrap();
+        let (_, touch) =
+            price("Will ETH dip to $2,700?", in_30_days, &points, &config, now).unwrap();
+        assert_eq!(finish.vol_source, VolSource::Realized);
+        let ratio = touch.probability / finish.probability;
+        assert!(ratio > dec!(1.99) && ratio < dec!(2.01), "{ratio}");
+
+        // Ranges, unknown coins and past dates aren't priced
+        let between = "Will Bitcoin be above $90,000 and below $110,000?";
+        assert!(price(between, in_30_days, &points, &config, now).is_none());
+        let doge = "Will Dogecoin be above $1?";
+        assert!(price(doge, in_30_days, &points, &config, now).is_none());
+        let expired = now - chrono::Duration::days(1);
+        let question = "Will Bitcoin be above $100,000?";
+        assert!(price(question, expired, &points, &config, now).is_none());
+
+        assert!(at_the_money.diverges(dec!(0.8), config.max_divergence));
+        assert!(!at_the_money.diverges(dec!(0.55), config.max_divergence));
+    }
+}
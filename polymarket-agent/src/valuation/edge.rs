@@ -1,12 +1,14 @@
 //! Edge calculation (fair value vs market price).
 //!
 //! Determines whether a market is mispriced enough to trade,
-//! with thresholds adjusted by confidence level.
+//! with thresholds adjusted by confidence level. The threshold is applied
+//! to the edge net of the taker fee for the side we would buy.
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
 use crate::config::ValuationConfig;
+use crate::execution::fees::{FeeModel, Liquidity};
 use crate::market::models::{MarketCandidate, Opportunity, Side};
 use crate::valuation::fair_value::{DataQuality, ValuationResult};
 
@@ -15,6 +17,7 @@ pub fn evaluate_edge(
     candidate: &MarketCandidate,
     valuation: &ValuationResult,
     config: &ValuationConfig,
+    fees: &FeeModel,
 ) -> Option<EdgeResult> {
     let market_prob = candidate.order_book.implied_probability;
     let fair_prob = valuation.probability;
@@ -30,10 +33,6 @@ pub fn evaluate_edge(
         return None;
     }
 
-    if raw_edge < threshold {
-        return None;
-    }
-
     // Determine which side to trade
     let side = if fair_prob > market_prob {
         Side::Yes // Market underprices YES → buy YES
@@ -47,8 +46,16 @@ pub fn evaluate_edge(
         Side::No => Decimal::ONE - candidate.order_book.midpoint,
     };
 
+    let fee_per_share = fees.per_share(Liquidity::Taker, trade_price);
+    let net_edge = raw_edge - fee_per_share;
+    if net_edge < threshold {
+        return None;
+    }
+
     Some(EdgeResult {
         raw_edge,
+        fee_per_share,
+        net_edge,
         threshold,
         side,
         fair_probability: fair_prob,
@@ -73,6 +80,10 @@ fn edge_threshold(valuation: &ValuationResult, config: &ValuationConfig) -> Deci
 pub struct EdgeResult {
     /// Absolute difference between fair value and market price.
     pub raw_edge: Decimal,
+    /// Taker fee per share at `trade_price`.
+    pub fee_per_share: Decimal,
+    /// `raw_edge` less the fee — what the threshold is applied to.
+    pub net_edge: Decimal,
     /// Threshold that was applied.
     pub threshold: Decimal,
     /// Which side to trade (Yes or No).
@@ -97,16 +108,19 @@ pub fn to_opportunity(
         order_book: candidate.order_book.clone(),
         fair_value: valuation.probability,
         confidence: valuation.confidence,
-        edge: edge.raw_edge,
+        edge: edge.net_edge,
         recommended_side: edge.side,
         kelly_size,
+        new_listing: candidate.new_listing,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::market::models::{Market, MarketCategory, OrderBookSnapshot, PriceLevel, TokenInfo};
+    use crate::market::models::{
+        BookLiquidity, Market, MarketCategory, OrderBookSnapshot, PriceLevel, TokenInfo,
+    };
     use chrono::Utc;
 
     fn test_config() -> ValuationConfig {
@@ -116,6 +130,9 @@ mod tests {
             high_confidence_edge: dec!(0.06),
             low_confidence_edge: dec!(0.10),
             cache_ttl_seconds: 300,
+            prompt_token_budget: 2000,
+            crypto_pricer: Default::default(),
+            consistency: Default::default(),
         }
     }
 
@@ -134,6 +151,11 @@ mod tests {
                 category: MarketCategory::Weather,
                 volume_24h: dec!(10000),
                 active: true,
+                event_id: None,
+                event_slug: None,
+                neg_risk: false,
+                description: String::new(),
+                resolution_source: None,
             },
             order_book: OrderBookSnapshot {
                 token_id: "123".to_string(),
@@ -149,7 +171,10 @@ mod tests {
                 midpoint,
                 implied_probability: midpoint,
                 timestamp: Utc::now(),
+                liquidity: BookLiquidity::TwoSided,
             },
+            new_listing: false,
+            liquidity_score: None,
         }
     }
 
@@ -171,7 +196,7 @@ mod tests {
         // Claude says 65% probability, market says 50% → 15% edge → buy YES
         let valuation = test_valuation(dec!(0.65), dec!(0.85));
 
-        let result = evaluate_edge(&candidate, &valuation, &config).unwrap();
+        let result = evaluate_edge(&candidate, &valuation, &config, &FeeModel::default()).unwrap();
         assert_eq!(result.raw_edge, dec!(0.15));
         assert_eq!(result.side, Side::Yes);
     }
@@ -183,11 +208,28 @@ mod tests {
         // Claude says 50% probability, market says 70% → 20% edge → buy NO
         let valuation = test_valuation(dec!(0.50), dec!(0.85));
 
-        let result = evaluate_edge(&candidate, &valuation, &config).unwrap();
+        let result = evaluate_edge(&candidate, &valuation, &config, &FeeModel::default()).unwrap();
         assert_eq!(result.raw_edge, dec!(0.20));
         assert_eq!(result.side, Side::No);
     }
 
+    #[test]
+    fn test_fee_reduces_edge_below_threshold() {
+        let config = test_config();
+        let candidate = test_candidate(dec!(0.50));
+        // 7% raw edge clears the 6% threshold; a 400 bps taker fee costs
+        // 2¢ per share at 50¢, leaving 5%
+        let valuation = test_valuation(dec!(0.57), dec!(0.85));
+        let fees = FeeModel::new(&crate::execution::fees::FeeConfig {
+            taker_fee_bps: dec!(400),
+            maker_rebate_bps: Decimal::ZERO,
+        });
+
+        let free = evaluate_edge(&candidate, &valuation, &config, &FeeModel::default()).unwrap();
+        assert_eq!(free.net_edge, dec!(0.07));
+        assert!(evaluate_edge(&candidate, &valuation, &config, &fees).is_none());
+    }
+
     #[test]
     fn test_edge_below_threshold() {
         let config = test_config();
@@ -195,7 +237,7 @@ mod tests {
         // 3% edge at high confidence (threshold 6%) → no trade
         let valuation = test_valuation(dec!(0.53), dec!(0.85));
 
-        let result = evaluate_edge(&candidate, &valuation, &config);
+        let result = evaluate_edge(&candidate, &valuation, &config, &FeeModel::default());
         assert!(result.is_none());
     }
 
@@ -206,7 +248,7 @@ mod tests {
         // Big edge but confidence too low → skip
         let valuation = test_valuation(dec!(0.80), dec!(0.30));
 
-        let result = evaluate_edge(&candidate, &valuation, &config);
+        let result = evaluate_edge(&candidate, &valuation, &config, &FeeModel::default());
         assert!(result.is_none());
     }
 
@@ -234,7 +276,7 @@ mod tests {
         let mut valuation = test_valuation(dec!(0.80), dec!(0.85));
         valuation.data_quality = DataQuality::Low;
 
-        let result = evaluate_edge(&candidate, &valuation, &config);
+        let result = evaluate_edge(&candidate, &valuation, &config, &FeeModel::default());
         assert!(result.is_none());
     }
 }
@@ -1,4 +1,9 @@
 pub mod calibration;
 pub mod claude;
+pub mod consistency;
+pub mod crypto_pricer;
 pub mod edge;
 pub mod fair_value;
+pub mod prompt_budget;
+pub mod relevance;
+pub mod revaluation;
@@ -0,0 +1,179 @@
+//! Token-budgeted selection of external data for valuation prompts.
+//!
+//! Data points are ranked by relevance to the market question (embedding
+//! similarity when available, keyword overlap otherwise), recency and source
+//! confidence, then added best-first until the input-token budget is
+//! spent. Token counts use a characters-per-token estimate, which is close
+//! enough for budgeting without shipping a tokenizer.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::data::DataPoint;
+use crate::valuation::fair_value::truncate_json;
+
+/// Rough average for English text and JSON under Claude's tokenizer.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Longest payload rendered for a single data point, so one verbose source
+/// can't crowd out the rest of the evidence.
+const MAX_PAYLOAD_CHARS: usize = 600;
+
+/// Age at which a data point's recency score halves.
+const RECENCY_HALF_LIFE_HOURS: f64 = 12.0;
+
+/// Ranking weights: relevance, recency, confidence.
+const RELEVANCE_WEIGHT: f64 = 0.5;
+const RECENCY_WEIGHT: f64 = 0.25;
+const CONFIDENCE_WEIGHT: f64 = 0.25;
+
+/// Estimate the number of input tokens `text` will cost.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Fraction of the question's keywords that appear in the data point's
+/// payload. Words shorter than 3 characters are ignored.
+pub fn keyword_relevance(question: &str, dp: &DataPoint) -> f64 {
+    let keywords = keywords(question);
+    if keywords.is_empty() {
+        return 0.0;
+    }
+    let payload = keywords_of_payload(dp);
+    let hits = keywords.iter().filter(|k| payload.contains(*k)).count();
+    hits as f64 / keywords.len() as f64
+}
+
+/// Exponential decay on the data point's age.
+pub fn recency(dp: &DataPoint, now: DateTime<Utc>) -> f64 {
+    let age_hours = (now - dp.timestamp).num_minutes().max(0) as f64 / 60.0;
+    0.5f64.powf(age_hours / RECENCY_HALF_LIFE_HOURS)
+}
+
+/// Combined ranking score in 0.0–1.0 given the point's relevance (0.0–1.0).
+pub fn score(relevance: f64, dp: &DataPoint, now: DateTime<Utc>) -> f64 {
+    let confidence = dp.confidence.to_f64().unwrap_or(0.5).clamp(0.0, 1.0);
+    relevance.clamp(0.0, 1.0) * RELEVANCE_WEIGHT
+        + recency(dp, now) * RECENCY_WEIGHT
+        + confidence * CONFIDENCE_WEIGHT
+}
+
+/// Render the best of `evidence` (data point, relevance) as numbered prompt
+/// lines, using at most `token_budget` tokens. Points that don't fit are
+/// skipped so smaller, lower-ranked ones can still fill the remaining budget.
+pub fn select_data_lines(evidence: &[(&DataPoint, f64)], token_budget: usize) -> Vec<String> {
+    let now = Utc::now();
+    let mut ranked: Vec<(&DataPoint, f64)> = evidence
+        .iter()
+        .map(|(dp, relevance)| (*dp, score(*relevance, dp, now)))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut lines = Vec::new();
+    let mut used = 0;
+    for (dp, _) in ranked {
+        let line = format!(
+            "{}. [{}] ({}h old, confidence: {}) {}",
+            lines.len() + 1,
+            dp.source,
+            (now - dp.timestamp).num_hours().max(0),
+            dp.confidence,
+            truncate_json(&dp.payload, MAX_PAYLOAD_CHARS)
+        );
+        // +1 for the joining newline
+        let tokens = estimate_tokens(&line) + 1;
+        if used + tokens > token_budget {
+            continue;
+        }
+        used += tokens;
+        lines.push(line);
+    }
+    lines
+}
+
+fn keywords(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn keywords_of_payload(dp: &DataPoint) -> HashSet<String> {
+    keywords(&dp.payload.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+    use chrono::Duration;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn make_data_point(
+        payload: serde_json::Value,
+        hours_ago: i64,
+        confidence: Decimal,
+    ) -> DataPoint {
+        DataPoint {
+            source: "test".to_string(),
+            category: MarketCategory::Weather,
+            timestamp: Utc::now() - Duration::hours(hours_ago),
+            payload,
+            confidence,
+            relevance_to: vec!["cond".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_ranking_prefers_relevant_recent_confident() {
+        let question = "Will it rain in Chicago tomorrow?";
+        let relevant = make_data_point(
+            serde_json::json!({"city": "Chicago", "rain": 0.8}),
+            1,
+            dec!(0.9),
+        );
+        let stale = make_data_point(
+            serde_json::json!({"city": "Chicago", "rain": 0.8}),
+            72,
+            dec!(0.9),
+        );
+        let unrelated = make_data_point(serde_json::json!({"city": "Miami"}), 1, dec!(0.9));
+
+        let evidence: Vec<(&DataPoint, f64)> = [&unrelated, &stale, &relevant]
+            .into_iter()
+            .map(|dp| (dp, keyword_relevance(question, dp)))
+            .collect();
+        let now = Utc::now();
+        assert!(score(evidence[2].1, &relevant, now) > score(evidence[1].1, &stale, now));
+        assert!(score(evidence[1].1, &stale, now) > score(evidence[0].1, &unrelated, now));
+
+        let lines = select_data_lines(&evidence, 10_000);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("1. ") && lines[0].contains("(1h old"));
+        assert!(lines[2].contains("Miami"));
+    }
+
+    #[test]
+    fn test_select_respects_budget() {
+        let big = make_data_point(serde_json::json!({"text": "x".repeat(500)}), 0, dec!(0.9));
+        let small = make_data_point(serde_json::json!({"rain": 1}), 0, dec!(0.5));
+
+        let evidence = [(&big, 1.0), (&small, 1.0)];
+
+        let lines = select_data_lines(&evidence, 40);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("rain"));
+
+        assert!(select_data_lines(&evidence, 0).is_empty());
+    }
+}
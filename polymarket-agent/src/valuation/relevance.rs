@@ -0,0 +1,380 @@
+//! Embedding-based relevance between market questions and external data.
+//!
+//! Sources tag data points with the markets they *might* inform using
+//! keyword matching, which misses paraphrases and over-matches common words.
+//! When a Voyage API key is set, the question and every data point are
+//! embedded and ranked by cosine similarity instead, so the valuation prompt
+//! gets the closest evidence from any source. Embedding spend is booked to
+//! `api_costs` under the "voyage" provider.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::data::DataPoint;
+use crate::db::store::{ApiCostRecord, Store};
+use crate::valuation::fair_value::truncate_json;
+
+/// Payload characters embedded per data point.
+const MAX_EMBED_CHARS: usize = 1000;
+
+/// Inputs per embeddings request.
+const MAX_BATCH: usize = 128;
+
+const MILLION: Decimal = dec!(1_000_000);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RelevanceConfig {
+    /// Use embeddings when `VOYAGE_API_KEY` is set; keyword overlap otherwise.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_model")]
+    pub model: String,
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    /// Data points less similar than this are left out of the prompt unless
+    /// their source tagged them for the market.
+    #[serde(default = "default_min_similarity")]
+    pub min_similarity: f64,
+    /// Embedding price per million input tokens.
+    #[serde(default = "default_price_per_million_tokens")]
+    pub price_per_million_tokens: Decimal,
+    /// Embeddings kept in memory before the cache is reset.
+    #[serde(default = "default_max_cached")]
+    pub max_cached: usize,
+}
+
+impl Default for RelevanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            model: default_model(),
+            base_url: default_base_url(),
+            min_similarity: default_min_similarity(),
+            price_per_million_tokens: default_price_per_million_tokens(),
+            max_cached: default_max_cached(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_model() -> String {
+    "voyage-3-lite".to_string()
+}
+
+fn default_base_url() -> String {
+    "https://api.voyageai.com".to_string()
+}
+
+fn default_min_similarity() -> f64 {
+    0.35
+}
+
+fn default_price_per_million_tokens() -> Decimal {
+    dec!(0.02)
+}
+
+fn default_max_cached() -> usize {
+    4096
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    input: &'a [String],
+    model: &'a str,
+    input_type: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+    usage: EmbeddingUsage,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingUsage {
+    total_tokens: i64,
+}
+
+/// Scores data points against market questions by embedding similarity.
+/// Data point embeddings are cached by text, so each payload is paid for
+/// once no matter how many markets it is compared against.
+pub struct RelevanceScorer {
+    http: reqwest::Client,
+    api_key: String,
+    config: RelevanceConfig,
+    store: Store,
+    cache: Mutex<HashMap<String, Vec<f32>>>,
+}
+
+impl RelevanceScorer {
+    pub fn new(api_key: String, config: RelevanceConfig, store: Store) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .expect("Failed to build HTTP client");
+        Self {
+            http,
+            api_key,
+            config,
+            store,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn min_similarity(&self) -> f64 {
+        self.config.min_similarity
+    }
+
+    /// Embed every data point not already cached, in as few requests as
+    /// possible. Called once per cycle before markets are valued in parallel.
+    pub async fn prime(&self, data_points: &[DataPoint], cycle: Option<i64>) -> Result<()> {
+        let texts: Vec<String> = data_points.iter().map(document_text).collect();
+        self.embed_documents(&texts, cycle).await?;
+        Ok(())
+    }
+
+    /// Cosine similarity of each data point to `question`, in input order.
+    pub async fn score(
+        &self,
+        question: &str,
+        data_points: &[DataPoint],
+        cycle: Option<i64>,
+    ) -> Result<Vec<f64>> {
+        if data_points.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = self
+            .embed(&[question.to_string()], "query", cycle)
+            .await?
+            .pop()
+            .context("No embedding returned for question")?;
+        let texts: Vec<String> = data_points.iter().map(document_text).collect();
+        let documents = self.embed_documents(&texts, cycle).await?;
+        Ok(documents
+            .iter()
+            .map(|d| cosine_similarity(&query, d))
+            .collect())
+    }
+
+    async fn embed_documents(&self, texts: &[String], cycle: Option<i64>) -> Result<Vec<Vec<f32>>> {
+        let missing: Vec<String> = {
+            let cache = self.cache.lock().await;
+            let mut missing: Vec<String> = texts
+                .iter()
+                .filter(|t| !cache.contains_key(*t))
+                .cloned()
+                .collect();
+            missing.sort();
+            missing.dedup();
+            missing
+        };
+
+        if !missing.is_empty() {
+            let mut embedded = Vec::with_capacity(missing.len());
+            for batch in missing.chunks(MAX_BATCH) {
+                embedded.extend(self.embed(batch, "document", cycle).await?);
+            }
+            let mut cache = self.cache.lock().await;
+            if cache.len() + missing.len() > self.config.max_cached {
+                debug!(entries = cache.len(), "Embedding cache full — resetting");
+                cache.clear();
+            }
+            cache.extend(missing.into_iter().zip(embedded));
+        }
+
+        let cache = self.cache.lock().await;
+        texts
+            .iter()
+            .map(|t| {
+                cache
+                    .get(t)
+                    .cloned()
+                    .context("Embedding evicted mid-lookup")
+            })
+            .collect()
+    }
+
+    async fn embed(
+        &self,
+        input: &[String],
+        input_type: &str,
+        cycle: Option<i64>,
+    ) -> Result<Vec<Vec<f32>>> {
+        let response = self
+            .http
+            .post(format!("{}/v1/embeddings", self.config.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&EmbeddingRequest {
+                input,
+                model: &self.config.model,
+                input_type,
+            })
+            .send()
+            .await
+            .context("Embeddings request failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Embeddings API error ({status}): {body}");
+        }
+        let mut parsed: EmbeddingResponse = response
+            .json()
+            .await
+            .context("Failed to parse embeddings response")?;
+        if parsed.data.len() != input.len() {
+            bail!(
+                "Embeddings API returned {} vectors for {} inputs",
+                parsed.data.len(),
+                input.len()
+            );
+        }
+
+        let cost = Decimal::from(parsed.usage.total_tokens) * self.config.price_per_million_tokens
+            / MILLION;
+        if let Err(e) = self
+            .store
+            .insert_api_cost(&ApiCostRecord {
+                id: None,
+                provider: "voyage".to_string(),
+                endpoint: Some("embeddings".to_string()),
+                input_tokens: Some(parsed.usage.total_tokens),
+                output_tokens: None,
+                cost,
+                cycle,
+                created_at: None,
+            })
+            .await
+        {
+            warn!(error = %e, "Failed to track embedding cost");
+        }
+
+        parsed.data.sort_by_key(|d| d.index);
+        Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+/// The text embedded for a data point.
+fn document_text(dp: &DataPoint) -> String {
+    format!(
+        "{}: {}",
+        dp.source,
+        truncate_json(&dp.payload, MAX_EMBED_CHARS)
+    )
+}
+
+/// Cosine similarity of two vectors; 0.0 if either is empty or zero.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (*x as f64, *y as f64);
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::models::MarketCategory;
+    use chrono::Utc;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn make_data_point(source: &str) -> DataPoint {
+        DataPoint {
+            source: source.to_string(),
+            category: MarketCategory::Weather,
+            timestamp: Utc::now(),
+            payload: serde_json::json!({"rain": 0.8}),
+            confidence: dec!(0.9),
+            relevance_to: vec![],
+        }
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-9);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_score_ranks_and_caches_documents() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(body_partial_json(
+                serde_json::json!({"input_type": "query"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"embedding": [1.0, 0.0], "index": 0}],
+                "usage": {"total_tokens": 10}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(body_partial_json(
+                serde_json::json!({"input_type": "document"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"embedding": [0.0, 1.0], "index": 1},
+                    {"embedding": [1.0, 0.1], "index": 0}
+                ],
+                "usage": {"total_tokens": 40}
+            })))
+            // Second score() must hit the cache
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = Store::new(":memory:").await.unwrap();
+        let config = RelevanceConfig {
+            base_url: server.uri(),
+            ..RelevanceConfig::default()
+        };
+        let scorer = RelevanceScorer::new("key".to_string(), config, store.clone_for_parallel());
+        // Sorted document order: "a: ..." then "b: ..."
+        let points = [make_data_point("b"), make_data_point("a")];
+
+        let scores = scorer
+            .score("Will it rain?", &points, Some(1))
+            .await
+            .unwrap();
+        assert!(scores[1] > 0.9, "a matches the query");
+        assert!(scores[0] < 0.1, "b is orthogonal");
+
+        let again = scorer
+            .score("Will it rain?", &points, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(scores, again);
+        assert!(store.get_total_api_cost().await.unwrap() > Decimal::ZERO);
+    }
+}
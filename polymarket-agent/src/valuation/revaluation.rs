@@ -0,0 +1,147 @@
+//! Periodic re-valuation of held markets.
+//!
+//! Markets are normally valued only when the scanner surfaces them, so a
+//! position can go unvalued until it resolves. When enabled, every cycle the
+//! agent re-values held markets whose latest valuation is older than
+//! `interval_seconds`, using a cheaper Claude model. The result replaces the
+//! market's entry in the valuation cache, where the hedge and exit checks
+//! read it, and positions whose edge has dropped below `min_edge` are tagged
+//! [`EVAPORATED_TAG`].
+//!
+//! Re-valuation spend is booked at the same per-token rates as regular
+//! valuations, which overstates it for cheaper models.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+
+use crate::config::ValuationConfig;
+use crate::market::models::Side;
+use crate::risk::hedge;
+
+/// Trade tag marking a position whose re-valued edge fell below `min_edge`.
+pub const EVAPORATED_TAG: &str = "edge evaporated";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RevaluationConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minimum age of a held market's latest valuation before it is
+    /// re-valued. A valuation from the regular scan counts.
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u64,
+    /// Model used for re-valuations, normally cheaper than
+    /// `valuation.claude_model`.
+    #[serde(default = "default_claude_model")]
+    pub claude_model: String,
+    /// Positions whose edge at the current price is below this are flagged.
+    #[serde(default = "default_min_edge")]
+    pub min_edge: Decimal,
+    /// Markets re-valued per cycle at most.
+    #[serde(default = "default_max_per_cycle")]
+    pub max_per_cycle: usize,
+}
+
+impl Default for RevaluationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: default_interval_seconds(),
+            claude_model: default_claude_model(),
+            min_edge: default_min_edge(),
+            max_per_cycle: default_max_per_cycle(),
+        }
+    }
+}
+
+fn default_interval_seconds() -> u64 {
+    3600
+}
+
+fn default_claude_model() -> String {
+    "claude-3-5-haiku-20241022".to_string()
+}
+
+fn default_min_edge() -> Decimal {
+    dec!(0.02)
+}
+
+fn default_max_per_cycle() -> usize {
+    5
+}
+
+impl RevaluationConfig {
+    /// Settings for the re-valuation engine: the cheaper model, and no
+    /// cache reads, since a cached valuation is what is being refreshed.
+    pub fn valuation_config(&self, base: &ValuationConfig) -> ValuationConfig {
+        ValuationConfig {
+            claude_model: self.claude_model.clone(),
+            cache_ttl_seconds: 0,
+            ..base.clone()
+        }
+    }
+}
+
+/// Whether holding `side` has less than `min_edge` left when the fair YES
+/// probability is `fair_yes` and YES trades at `yes_price`.
+pub fn edge_evaporated(
+    side: Side,
+    fair_yes: Decimal,
+    yes_price: Decimal,
+    min_edge: Decimal,
+) -> bool {
+    hedge::position_edge(side, fair_yes, yes_price) < min_edge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_evaporated_per_side() {
+        // Bought YES believing 0.60; re-valued at 0.56 with YES at 0.55
+        assert!(edge_evaporated(
+            Side::Yes,
+            dec!(0.56),
+            dec!(0.55),
+            dec!(0.02)
+        ));
+        assert!(!edge_evaporated(
+            Side::Yes,
+            dec!(0.60),
+            dec!(0.55),
+            dec!(0.02)
+        ));
+        // NO holds edge when fair YES is below the price
+        assert!(!edge_evaporated(
+            Side::No,
+            dec!(0.40),
+            dec!(0.45),
+            dec!(0.02)
+        ));
+        assert!(edge_evaporated(
+            Side::No,
+            dec!(0.44),
+            dec!(0.45),
+            dec!(0.02)
+        ));
+    }
+
+    #[test]
+    fn test_valuation_config_uses_cheap_model_without_cache() {
+        let base: ValuationConfig = toml::from_str(
+            r#"
+            claude_model = "claude-sonnet-4-20250514"
+            min_edge_threshold = 0.08
+            high_confidence_edge = 0.06
+            low_confidence_edge = 0.10
+            cache_ttl_seconds = 300
+            "#,
+        )
+        .unwrap();
+        let config = RevaluationConfig::default().valuation_config(&base);
+        assert_eq!(config.claude_model, default_claude_model());
+        assert_eq!(config.cache_ttl_seconds, 0);
+        assert_eq!(config.min_edge_threshold, base.min_edge_threshold);
+    }
+}
@@ -1,12 +1,23 @@
 //! Integration tests for cross-module functionality.
 
+use polymarket_agent::agent::simulation;
+use polymarket_agent::config::{AppConfig, ExecutionConfig, ScanningConfig};
 use polymarket_agent::data::quality::compute_data_quality;
+use polymarket_agent::db::store::Store;
+use polymarket_agent::execution::fills;
+use polymarket_agent::execution::order::{execute_order, prepare_order, OrderStatus};
+use polymarket_agent::market::api::MarketApi;
 use polymarket_agent::market::category::infer_category;
+use polymarket_agent::market::fake::{FakeExchange, FakeFill};
 use polymarket_agent::market::models::MarketCategory;
 use polymarket_agent::market::models::Side;
+use polymarket_agent::market::models::{Market, Opportunity, PriceLevel, TokenInfo};
+use polymarket_agent::market::scanner::MarketScanner;
 use polymarket_agent::risk::exit::{evaluate_exit, DEFAULT_MAX_LOSS_PCT};
 use polymarket_agent::valuation::fair_value::DataQuality;
 
+use std::sync::Arc;
+
 use chrono::Utc;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -51,7 +62,7 @@ fn category_unknown_falls_back() {
 
 #[test]
 fn data_quality_no_data_is_low() {
-    let quality = compute_data_quality(&[]);
+    let quality = compute_data_quality(&[], Utc::now());
     assert_eq!(quality, DataQuality::Low);
 }
 
@@ -84,7 +95,7 @@ fn data_quality_multiple_fresh_sources_is_high() {
             relevance_to: vec![],
         },
     ];
-    let quality = compute_data_quality(&points);
+    let quality = compute_data_quality(&points, Utc::now());
     assert_eq!(quality, DataQuality::High);
 }
 
@@ -197,3 +208,217 @@ fn sanitize_limits_length() {
     let sanitized = sanitize_market_question(&long_input);
     assert!(sanitized.len() <= 500);
 }
+
+// ──────────────────────────────────────────
+// Fake exchange pipeline tests
+// ──────────────────────────────────────────
+
+fn fake_market() -> Market {
+    Market {
+        condition_id: "0xbtc".to_string(),
+        question: "Will Bitcoin close above $100k on Friday?".to_string(),
+        outcomes: vec!["Yes".to_string(), "No".to_string()],
+        tokens: vec![
+            TokenInfo {
+                token_id: "tok_yes".to_string(),
+                outcome: "Yes".to_string(),
+                price: dec!(0.59),
+            },
+            TokenInfo {
+                token_id: "tok_no".to_string(),
+                outcome: "No".to_string(),
+                price: dec!(0.41),
+            },
+        ],
+        end_date: Utc::now() + chrono::Duration::days(3),
+        category: MarketCategory::Crypto,
+        volume_24h: dec!(20000),
+        active: true,
+        event_id: None,
+        event_slug: None,
+        neg_risk: false,
+        description: String::new(),
+        resolution_source: None,
+    }
+}
+
+fn fake_level(price: Decimal, size: Decimal) -> PriceLevel {
+    PriceLevel { price, size }
+}
+
+fn fake_exchange() -> Arc<FakeExchange> {
+    let exchange = Arc::new(FakeExchange::new(dec!(100)));
+    exchange.add_market(fake_market());
+    exchange.set_book(
+        "tok_yes",
+        vec![fake_level(dec!(0.58), dec!(500))],
+        vec![
+            fake_level(dec!(0.60), dec!(20)),
+            fake_level(dec!(0.61), dec!(500)),
+        ],
+    );
+    exchange
+}
+
+fn fake_scanning_config() -> ScanningConfig {
+    toml::from_str(
+        r#"
+        max_markets = 10
+        min_volume_24h = 5000.0
+        max_resolution_days = 14
+        max_spread_pct = 0.05
+        categories = ["crypto"]
+        no_edge_deprioritize_after = 0
+        "#,
+    )
+    .unwrap()
+}
+
+fn fake_execution_config() -> ExecutionConfig {
+    toml::from_str(
+        r#"
+        order_type = "limit"
+        order_ttl_seconds = 300
+        max_slippage_pct = 0.02
+        max_retries = 3
+        "#,
+    )
+    .unwrap()
+}
+
+async fn fake_opportunity(exchange: &FakeExchange) -> Opportunity {
+    Opportunity {
+        market: fake_market(),
+        order_book: exchange.get_order_book("tok_yes").await.unwrap(),
+        fair_value: dec!(0.75),
+        confidence: dec!(0.8),
+        edge: dec!(0.15),
+        recommended_side: Side::Yes,
+        kelly_size: dec!(6),
+        new_listing: false,
+    }
+}
+
+#[tokio::test]
+async fn scan_execute_and_record_against_fake_exchange() {
+    let exchange = fake_exchange();
+    let scanner = MarketScanner::new(exchange.clone(), fake_scanning_config());
+
+    let candidates = scanner.scan().await.unwrap();
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].order_book.midpoint, dec!(0.59));
+
+    let prepared = prepare_order(
+        &fake_opportunity(&exchange).await,
+        dec!(0.2),
+        dec!(0.1),
+        &fake_execution_config(),
+    )
+    .unwrap();
+    assert_eq!(prepared.price, dec!(0.60));
+    assert_eq!(prepared.size, dec!(10));
+
+    let execution = execute_order(exchange.as_ref(), &prepared).await;
+    assert_eq!(execution.status, OrderStatus::Filled);
+    assert_eq!(execution.price, dec!(0.60));
+    assert_eq!(exchange.balance(), dec!(94));
+
+    let store = Store::new(":memory:").await.unwrap();
+    fills::record_trade(&store, &prepared, &execution, 1)
+        .await
+        .unwrap();
+    let open = store.get_open_trades().await.unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].size, dec!(10));
+}
+
+#[tokio::test]
+async fn scripted_partial_fill_cancels_the_remainder() {
+    let exchange = fake_exchange();
+    let prepared = prepare_order(
+        &fake_opportunity(&exchange).await,
+        dec!(0.2),
+        dec!(0.1),
+        &fake_execution_config(),
+    )
+    .unwrap();
+
+    exchange.script_fill(FakeFill::Shares(dec!(4)));
+    let execution = execute_order(exchange.as_ref(), &prepared).await;
+    assert_eq!(
+        execution.status,
+        OrderStatus::PartiallyFilled {
+            filled_size: dec!(4)
+        }
+    );
+    assert!(exchange.orders()[0].cancelled);
+}
+
+#[tokio::test]
+async fn scanner_skips_markets_without_a_book() {
+    let exchange = fake_exchange();
+    exchange.remove_book("tok_yes");
+    let scanner = MarketScanner::new(exchange.clone(), fake_scanning_config());
+    assert!(scanner.scan().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn scanner_fetches_books_in_parallel_and_keeps_order() {
+    let exchange = Arc::new(FakeExchange::new(dec!(100)));
+    let ids = ["0xa", "0xb", "0xc", "0xd", "0xe"];
+    for id in ids {
+        let mut market = fake_market();
+        market.condition_id = id.to_string();
+        market.tokens[0].token_id = format!("{id}_yes");
+        market.tokens[1].token_id = format!("{id}_no");
+        exchange.add_market(market);
+        // 0xc has no book and is dropped without failing the scan
+        if id != "0xc" {
+            exchange.set_book(
+                &format!("{id}_yes"),
+                vec![fake_level(dec!(0.58), dec!(500))],
+                vec![fake_level(dec!(0.60), dec!(500))],
+            );
+        }
+    }
+    let mut config = fake_scanning_config();
+    config.book_concurrency = 2;
+    let scanner = MarketScanner::new(exchange.clone(), config);
+
+    let found: Vec<String> = scanner
+        .scan()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.market.condition_id)
+        .collect();
+    assert_eq!(found, ["0xa", "0xb", "0xd", "0xe"]);
+}
+
+// ---------------------------------------------------------------------------
+// Simulation tests
+// ---------------------------------------------------------------------------
+
+async fn simulate(seed: u64, database: &str) -> simulation::SimulationReport {
+    let mut config: AppConfig = toml::from_str(include_str!("../config/default.toml")).unwrap();
+    config.simulation.seed = seed;
+    config.simulation.cycles = 48;
+    config.simulation.database_path = std::env::temp_dir()
+        .join(format!("{database}-{}.db", std::process::id()))
+        .to_string_lossy()
+        .into_owned();
+    let path = config.simulation.database_path.clone();
+    let report = simulation::run(config).await.unwrap();
+    let _ = std::fs::remove_file(path);
+    report
+}
+
+#[tokio::test]
+async fn test_simulation_reproducible_for_seed() {
+    let first = simulate(7, "sim_repro_a").await;
+    let second = simulate(7, "sim_repro_b").await;
+    assert_eq!(first.trades, second.trades);
+    assert_eq!(first.final_balance, second.final_balance);
+    assert_eq!(first.transitions, second.transitions);
+    assert_eq!(first.fingerprint, second.fingerprint);
+}
@@ -6,6 +6,7 @@ use polymarket_agent::market::models::AgentState;
 use polymarket_agent::risk::kelly::kelly_size;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use std::collections::HashMap;
 
 fn default_config() -> RiskConfig {
     RiskConfig {
@@ -14,6 +15,17 @@ fn default_config() -> RiskConfig {
         max_total_exposure_pct: dec!(0.30),
         max_positions_per_category: 3,
         min_position_usd: dec!(1),
+        max_market_loss_usd: None,
+        max_trade_loss_usd: None,
+        resolution_decay: Vec::new(),
+        category_exposure_pct: HashMap::new(),
+        event_windows: Vec::new(),
+        buckets: Vec::new(),
+        approval: Default::default(),
+        hedge: Default::default(),
+        sizing: Default::default(),
+        rebalance: Default::default(),
+        drawdown: Default::default(),
     }
 }
 